@@ -1,6 +1,5 @@
-use coredb::{CoreDB, DatabaseConfig};
+use coredb::{CommitLogSync, CoreDB, DatabaseConfig, DiskAccessMode, DiskWriteMode, IntegrityCheckMode};
 use std::path::PathBuf;
-use tokio;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -8,10 +7,35 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let config = DatabaseConfig {
         data_directory: PathBuf::from("./example_data"),
         commitlog_directory: PathBuf::from("./example_commitlog"),
+        commitlog_sync: CommitLogSync::default(),
+        commitlog_segment_size_mb: 32,
+        commitlog_total_space_mb: 8192,
+        commitlog_keyspace_directories: std::collections::HashMap::new(),
+        commitlog_archiver: None,
+        commitlog_restore: None,
+        commitlog_encryption: None,
         memtable_flush_threshold_mb: 16, // 작은 값으로 설정
+        memtable_max_age_secs: 0,
         compaction_throughput_mb_per_sec: 16,
+        concurrent_compactors: 2,
         concurrent_reads: 32,
         concurrent_writes: 32,
+        max_queued_requests: 1024,
+        max_pending_flushes: 4,
+        max_sstables_per_table: 256,
+        large_partition_warning_threshold_mb: 100,
+        ephemeral: false,
+        disk_access_mode: DiskAccessMode::Standard,
+        compaction_write_mode: DiskWriteMode::Buffered,
+        key_cache_entries: 100_000,
+        chunk_cache_size_mb: 128,
+        slow_query_threshold_ms: 500,
+        disk_usage_warn_percent: 90,
+        disk_usage_fail_percent: 95,
+        integrity_check: IntegrityCheckMode::Off,
+        keyspace_quotas: Default::default(),
+        snapshot_schedules: Default::default(),
+        backup_url: Default::default(),
     };
     
     // 데이터베이스 초기화
@@ -59,8 +83,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // 샘플 데이터 삽입
     println!("Inserting sample data...");
-    use coredb::schema::{Row, PartitionKey, Cell, CassandraValue};
-    use std::collections::HashMap;
+    use coredb::schema::{Row, PartitionKey, Cell, Cells, CassandraValue};
     
     let users = vec![
         (1, "John Doe", "john@example.com", 30),
@@ -71,20 +94,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ];
     
     for (id, name, email, age) in users {
-        let mut cells = HashMap::new();
-        cells.insert("name".to_string(), Cell {
+        let mut cells = Cells::new();
+        cells.insert("name", Cell {
             value: CassandraValue::Text(name.to_string()),
             timestamp: chrono::Utc::now().timestamp_micros(),
             ttl: None,
             is_deleted: false,
         });
-        cells.insert("email".to_string(), Cell {
+        cells.insert("email", Cell {
             value: CassandraValue::Text(email.to_string()),
             timestamp: chrono::Utc::now().timestamp_micros(),
             ttl: None,
             is_deleted: false,
         });
-        cells.insert("age".to_string(), Cell {
+        cells.insert("age", Cell {
             value: CassandraValue::Int(age),
             timestamp: chrono::Utc::now().timestamp_micros(),
             ttl: None,
@@ -1,6 +1,7 @@
 use std::collections::{HashMap, BTreeMap};
 use std::fs::{File, OpenOptions, create_dir_all};
-use std::io::{Write, Read, BufReader, BufWriter};
+use std::fmt;
+use std::io::{Write, BufReader};
 use std::path::Path;
 
 // 간단한 데이터 타입
@@ -11,14 +12,17 @@ pub enum SimpleValue {
 }
 
 // 간단한 직렬화/역직렬화
-impl SimpleValue {
-    fn to_string(&self) -> String {
+impl fmt::Display for SimpleValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            SimpleValue::Text(s) => format!("TEXT:{}", s),
-            SimpleValue::Int(i) => format!("INT:{}", i),
+            SimpleValue::Text(s) => write!(f, "TEXT:{}", s),
+            SimpleValue::Int(i) => write!(f, "INT:{}", i),
         }
     }
-    
+}
+
+impl SimpleValue {
+
     fn from_string(s: &str) -> Option<Self> {
         if let Some(text) = s.strip_prefix("TEXT:") {
             Some(SimpleValue::Text(text.to_string()))
@@ -82,8 +86,8 @@ impl SimpleTable {
         lines.push(format!("TABLE:{}", self.name));
         for (key, row) in &self.data {
             lines.push(format!("ROW:{}|{}|{}", 
-                key.to_string(), 
-                row.value.to_string(), 
+                key, 
+                row.value, 
                 row.timestamp
             ));
         }
@@ -165,13 +169,11 @@ impl SimpleKeyspace {
         
         let mut current_table_lines = Vec::new();
         for line in &lines[1..] {
-            if line.starts_with("TABLE:") {
-                if !current_table_lines.is_empty() {
-                    if let Some(table) = SimpleTable::load_from_lines(&current_table_lines) {
-                        keyspace.tables.insert(table.name.clone(), table);
-                    }
-                    current_table_lines.clear();
+            if line.starts_with("TABLE:") && !current_table_lines.is_empty() {
+                if let Some(table) = SimpleTable::load_from_lines(&current_table_lines) {
+                    keyspace.tables.insert(table.name.clone(), table);
                 }
+                current_table_lines.clear();
             }
             current_table_lines.push(line.clone());
         }
@@ -239,11 +241,11 @@ impl SimplePersistentDB {
         
         writeln!(file, "# CoreDB Simple Persistent Database")?;
         writeln!(file, "# Format: KEYSPACE > TABLE > ROW")?;
-        writeln!(file, "")?;
+        writeln!(file)?;
         
         for keyspace in self.keyspaces.values() {
             writeln!(file, "{}", keyspace.save_to_string())?;
-            writeln!(file, "")?;
+            writeln!(file)?;
         }
         
         println!("✅ Database saved successfully!");
@@ -277,13 +279,11 @@ impl SimplePersistentDB {
         
         let mut current_keyspace_lines = Vec::new();
         for line in &lines {
-            if line.starts_with("KEYSPACE:") {
-                if !current_keyspace_lines.is_empty() {
-                    if let Some(ks) = SimpleKeyspace::load_from_lines(&current_keyspace_lines) {
-                        db.keyspaces.insert(ks.name.clone(), ks);
-                    }
-                    current_keyspace_lines.clear();
+            if line.starts_with("KEYSPACE:") && !current_keyspace_lines.is_empty() {
+                if let Some(ks) = SimpleKeyspace::load_from_lines(&current_keyspace_lines) {
+                    db.keyspaces.insert(ks.name.clone(), ks);
                 }
+                current_keyspace_lines.clear();
             }
             current_keyspace_lines.push(line.clone());
         }
@@ -0,0 +1,139 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Instant;
+use tokio::sync::{Semaphore, SemaphorePermit};
+use crate::error::{CoreDBError, Result};
+
+/// 동시 실행 수를 제한하는 입장 제어기 (읽기/쓰기 경로별로 하나씩)
+///
+/// 허용량을 넘는 요청은 대기열에서 기다리고, 대기열이 한도를 넘으면
+/// 기다리지 않고 바로 `Overloaded` 오류를 반환함
+#[derive(Debug)]
+pub struct AdmissionControl {
+    operation: &'static str,
+    semaphore: Semaphore,
+    max_concurrent: usize,
+    max_queued: usize,
+    queued: AtomicUsize,
+    admitted: AtomicU64,
+    rejected: AtomicU64,
+    total_wait_micros: AtomicU64,
+}
+
+/// 입장 제어 통계
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AdmissionStats {
+    pub max_concurrent: usize,
+    pub active: usize,
+    pub queued: usize,
+    pub admitted: u64,
+    pub rejected: u64,
+    /// 입장까지 기다린 시간 합계 (마이크로초)
+    pub total_wait_micros: u64,
+}
+
+impl AdmissionControl {
+    pub fn new(operation: &'static str, max_concurrent: usize, max_queued: usize) -> Self {
+        let max_concurrent = max_concurrent.max(1);
+        Self {
+            operation,
+            semaphore: Semaphore::new(max_concurrent),
+            max_concurrent,
+            max_queued,
+            queued: AtomicUsize::new(0),
+            admitted: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+            total_wait_micros: AtomicU64::new(0),
+        }
+    }
+
+    /// 실행 허가 획득 (허가가 drop될 때 반환)
+    pub async fn acquire(&self) -> Result<SemaphorePermit<'_>> {
+        // 바로 들어갈 수 있으면 대기열을 거치지 않음
+        if let Ok(permit) = self.semaphore.try_acquire() {
+            self.admitted.fetch_add(1, Ordering::Relaxed);
+            return Ok(permit);
+        }
+
+        let queued = self.queued.fetch_add(1, Ordering::AcqRel);
+        if queued >= self.max_queued {
+            self.queued.fetch_sub(1, Ordering::AcqRel);
+            self.rejected.fetch_add(1, Ordering::Relaxed);
+            return Err(CoreDBError::Overloaded {
+                operation: self.operation.to_string(),
+                queued,
+                limit: self.max_queued,
+            });
+        }
+
+        let started = Instant::now();
+        let permit = self.semaphore.acquire().await;
+        self.queued.fetch_sub(1, Ordering::AcqRel);
+        let permit = permit.map_err(|_| CoreDBError::Generic {
+            message: format!("{} admission control closed", self.operation),
+        })?;
+
+        self.admitted.fetch_add(1, Ordering::Relaxed);
+        self.total_wait_micros.fetch_add(started.elapsed().as_micros() as u64, Ordering::Relaxed);
+        Ok(permit)
+    }
+
+    /// 실행 중인 요청과 먼저 대기열에 들어온 요청이 모두 끝날 때까지 대기
+    pub async fn wait_idle(&self) {
+        // 세마포어는 도착 순서대로 허가하므로 앞서 기다리던 요청이 먼저 실행됨
+        let _all = self.semaphore.acquire_many(self.max_concurrent as u32).await;
+    }
+
+    pub fn stats(&self) -> AdmissionStats {
+        AdmissionStats {
+            max_concurrent: self.max_concurrent,
+            active: self.max_concurrent - self.semaphore.available_permits(),
+            queued: self.queued.load(Ordering::Acquire),
+            admitted: self.admitted.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            total_wait_micros: self.total_wait_micros.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_admission_queues_then_rejects() {
+        let control = Arc::new(AdmissionControl::new("write", 1, 1));
+
+        let first = control.acquire().await.unwrap();
+        assert_eq!(control.stats().active, 1);
+
+        // 두 번째 요청은 대기열에서 기다림
+        let waiter = {
+            let control = control.clone();
+            tokio::spawn(async move {
+                let _permit = control.acquire().await.unwrap();
+            })
+        };
+        while control.stats().queued == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        // 대기열이 가득 차면 바로 거부
+        match control.acquire().await {
+            Err(CoreDBError::Overloaded { operation, queued, limit }) => {
+                assert_eq!(operation, "write");
+                assert_eq!((queued, limit), (1, 1));
+            },
+            other => panic!("expected Overloaded, got {:?}", other.map(|_| ())),
+        }
+
+        drop(first);
+        waiter.await.unwrap();
+
+        let stats = control.stats();
+        assert_eq!(stats.active, 0);
+        assert_eq!(stats.queued, 0);
+        assert_eq!(stats.admitted, 2);
+        assert_eq!(stats.rejected, 1);
+    }
+}
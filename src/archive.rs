@@ -0,0 +1,535 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::error::{CoreDBError, Result};
+use crate::schema::{KeyspaceDefinition, TableSchema};
+use crate::query::parser::CqlParser;
+use crate::storage::{ManifestEntry, SSTable, COMPONENT_SUFFIXES, MIN_SUPPORTED_FORMAT_VERSION, SSTABLE_FORMAT_VERSION};
+
+/// 현재 키스페이스 아카이브 형식 버전
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+/// 아카이브 맨 앞의 메타데이터 파일 (<keyspace>/coredb-export.json)
+pub const ARCHIVE_MANIFEST_FILE: &str = "coredb-export.json";
+/// 스키마 DDL 파일 (<keyspace>/schema.cql, 문마다 한 줄)
+pub const ARCHIVE_SCHEMA_FILE: &str = "schema.cql";
+
+/// 가져오는 동안 SSTable을 풀어 두는 키스페이스 디렉토리 아래 디렉토리 접두사 (.import-<uuid>/<table>/)
+const IMPORT_STAGING_PREFIX: &str = ".import-";
+
+const TAR_BLOCK: usize = 512;
+
+/// 키스페이스 아카이브 메타데이터 (`coredb export`)
+///
+/// 아카이브는 zstd로 압축한 tar로, <keyspace>/ 아래에 이 파일, schema.cql, 테이블마다 <table>/<SSTable 구성 파일>이 들어감
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyspaceArchive {
+    pub format_version: u32,
+    /// 내보낸 CoreDB 버전
+    pub coredb_version: String,
+    /// 내보낼 때의 SSTable 형식 버전 (아카이브의 SSTable은 이 버전 이하)
+    pub sstable_format_version: u32,
+    pub keyspace: String,
+    /// 저장 계층이나 CQL 키스페이스 정의 (모르면 None)
+    pub definition: Option<KeyspaceDefinition>,
+    /// 내보낸 시각 (마이크로초)
+    pub exported_at: i64,
+    /// 키스페이스를 다시 만드는 CQL 스키마 문 (버전 순서)
+    pub schema: Vec<String>,
+    pub tables: Vec<ArchivedTable>,
+}
+
+/// 아카이브에 든 테이블
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedTable {
+    pub table: String,
+    /// 저장 계층 테이블 스키마 (열려 있거나 체크포인트에 있을 때만)
+    pub schema: Option<TableSchema>,
+    pub sstables: Vec<ManifestEntry>,
+    /// 구성 파일 이름과 크기
+    pub files: Vec<(String, u64)>,
+}
+
+impl KeyspaceArchive {
+    pub fn new(keyspace: &str, definition: Option<KeyspaceDefinition>, schema: Vec<String>) -> Self {
+        Self {
+            format_version: ARCHIVE_FORMAT_VERSION,
+            coredb_version: env!("CARGO_PKG_VERSION").to_string(),
+            sstable_format_version: SSTABLE_FORMAT_VERSION,
+            keyspace: keyspace.to_string(),
+            definition,
+            exported_at: chrono::Utc::now().timestamp_micros(),
+            schema,
+            tables: Vec::new(),
+        }
+    }
+
+    /// SSTable 합계 크기
+    pub fn size_bytes(&self) -> u64 {
+        self.tables.iter().flat_map(|table| &table.files).map(|(_, size)| size).sum()
+    }
+
+    /// 이 노드가 가져올 수 있는 아카이브인지 확인 (형식 버전과 테이블, 파일 이름)
+    pub fn validate(&self) -> Result<()> {
+        if self.format_version > ARCHIVE_FORMAT_VERSION {
+            return Err(CoreDBError::Archive {
+                message: format!("Archive format version {} is newer than {} (exported by CoreDB {})", self.format_version, ARCHIVE_FORMAT_VERSION, self.coredb_version),
+            });
+        }
+        if !(MIN_SUPPORTED_FORMAT_VERSION..=SSTABLE_FORMAT_VERSION).contains(&self.sstable_format_version) {
+            return Err(CoreDBError::Archive {
+                message: format!("SSTable format version {} is not supported (exported by CoreDB {})", self.sstable_format_version, self.coredb_version),
+            });
+        }
+        validate_identifier(&self.keyspace)?;
+        for table in &self.tables {
+            validate_identifier(&table.table)?;
+            for (file, _) in &table.files {
+                if file.is_empty() || file.starts_with('.') || file.contains(['/', '\\']) {
+                    return Err(CoreDBError::Archive { message: format!("Invalid file name {:?} in archive", file) });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 키스페이스 이름을 바꾼 아카이브 (스키마 문과 키스페이스, 테이블 스키마의 이름을 모두 바꿈)
+    pub fn renamed(mut self, keyspace: &str) -> Result<Self> {
+        validate_identifier(keyspace)?;
+        if keyspace == self.keyspace {
+            return Ok(self);
+        }
+        self.schema = self.schema.iter()
+            .map(|statement| rename_keyspace(statement, &self.keyspace, keyspace))
+            .collect::<Result<_>>()?;
+        if let Some(definition) = &mut self.definition {
+            definition.name = keyspace.to_string();
+        }
+        for schema in self.tables.iter_mut().filter_map(|table| table.schema.as_mut()) {
+            schema.keyspace = keyspace.to_string();
+        }
+        self.keyspace = keyspace.to_string();
+        Ok(self)
+    }
+}
+
+/// 키스페이스와 테이블 이름 확인 (디렉토리 이름으로 쓰므로 CQL 식별자만 허용)
+fn validate_identifier(name: &str) -> Result<()> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(CoreDBError::Archive { message: format!("Invalid keyspace or table name {:?}", name) });
+    }
+    Ok(())
+}
+
+/// 스키마 문의 키스페이스 이름을 from에서 to로 바꿈 (키스페이스 문의 이름과 from.으로 시작하는 이름)
+pub fn rename_keyspace(statement: &str, from: &str, to: &str) -> Result<String> {
+    let from = regex::escape(from);
+    let keyspace = regex::Regex::new(&format!(r"(?i)^(\s*(?:CREATE|DROP)\s+KEYSPACE\s+(?:IF\s+(?:NOT\s+)?EXISTS\s+)?){}\b", from))?;
+    let renamed = keyspace.replace(statement, format!("${{1}}{}", to));
+    let qualified = regex::Regex::new(&format!(r"\b{}\.", from))?;
+    let renamed = qualified.replace_all(&renamed, format!("{}.", to)).to_string();
+    match CqlParser::parse(&renamed)?.schema_keyspace() {
+        Some(keyspace) if keyspace == to => Ok(renamed),
+        _ => Err(CoreDBError::Archive { message: format!("Cannot rename the keyspace of {:?}", statement) }),
+    }
+}
+
+/// 스냅샷 디렉토리들로 아카이브를 out에 씀 (임시 파일에 쓴 뒤 rename, 파일을 읽으므로 spawn_blocking에서 호출)
+///
+/// tables는 (테이블 이름, 스키마, SSTable 목록, 스냅샷 디렉토리)
+pub fn write_keyspace_archive(out: &Path, mut archive: KeyspaceArchive, tables: Vec<(String, Option<TableSchema>, Vec<ManifestEntry>, PathBuf)>) -> Result<KeyspaceArchive> {
+    let mut sources = Vec::new();
+    for (table, schema, sstables, directory) in tables {
+        let mut files = Vec::new();
+        for entry in &sstables {
+            for suffix in COMPONENT_SUFFIXES {
+                let path = SSTable::component_path(&directory, &entry.id, suffix);
+                match std::fs::metadata(&path) {
+                    Ok(metadata) => {
+                        files.push((format!("{}{}", entry.id, suffix), metadata.len()));
+                        sources.push((format!("{}/{}/{}{}", archive.keyspace, table, entry.id, suffix), path));
+                    },
+                    // 이전 형식 SSTable에는 없는 구성 파일이 있음
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {},
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        }
+        archive.tables.push(ArchivedTable { table, schema, sstables, files });
+    }
+
+    let temp_path = out.with_extension("tmp");
+    let written = (|| {
+        let encoder = zstd::stream::Encoder::new(BufWriter::new(File::create(&temp_path)?), 3)?;
+        let mut tar = TarWriter { inner: encoder };
+        // 가져올 때 데이터를 읽기 전에 버전을 확인하도록 메타데이터를 맨 앞에 둠
+        let manifest = serde_json::to_vec_pretty(&archive)?;
+        tar.append(&format!("{}/{}", archive.keyspace, ARCHIVE_MANIFEST_FILE), manifest.len() as u64, &mut manifest.as_slice())?;
+        let schema: String = archive.schema.iter().map(|statement| format!("{};\n", statement)).collect();
+        tar.append(&format!("{}/{}", archive.keyspace, ARCHIVE_SCHEMA_FILE), schema.len() as u64, &mut schema.as_bytes())?;
+        for (name, path) in &sources {
+            let file = File::open(path)?;
+            let size = file.metadata()?.len();
+            tar.append(name, size, &mut BufReader::new(file))?;
+        }
+        tar.inner.write_all(&[0; TAR_BLOCK * 2])?;
+        tar.inner.finish()?.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        Ok::<_, CoreDBError>(())
+    })();
+    if let Err(e) = written {
+        std::fs::remove_file(&temp_path).ok();
+        return Err(e);
+    }
+    std::fs::rename(&temp_path, out)?;
+    Ok(archive)
+}
+
+/// 아카이브 맨 앞의 메타데이터만 읽음
+pub fn read_archive_manifest(path: &Path) -> Result<KeyspaceArchive> {
+    let mut tar = TarReader::open(path)?;
+    let (name, size) = tar.next_entry()?
+        .ok_or_else(|| CoreDBError::Archive { message: format!("{} is an empty archive", path.display()) })?;
+    if !name.ends_with(&format!("/{}", ARCHIVE_MANIFEST_FILE)) {
+        return Err(CoreDBError::Archive { message: format!("{} is not a CoreDB keyspace archive", path.display()) });
+    }
+    Ok(serde_json::from_slice(&tar.read_data(size)?)?)
+}
+
+/// 아카이브의 SSTable 구성 파일을 keyspace_directory/<table>/ 아래에 풀어 놓음 (파일을 쓰므로 spawn_blocking에서 호출)
+///
+/// 메타데이터에 없는 파일이나 크기가 다른 파일이 있으면 실패하며, 테이블 디렉토리는 아직 없어야 함.
+/// 키스페이스 디렉토리 아래 임시 디렉토리에 모두 푼 뒤에야 테이블 디렉토리로 옮기므로,
+/// 아카이브가 잘렸거나 손상되어 실패하면 임시 디렉토리를 지우고 아무것도 남기지 않음
+pub fn extract_keyspace_archive(path: &Path, archive: &KeyspaceArchive, keyspace_directory: &Path) -> Result<()> {
+    for table in &archive.tables {
+        let directory = keyspace_directory.join(&table.table);
+        if directory.exists() {
+            return Err(CoreDBError::Archive { message: format!("{} already exists", directory.display()) });
+        }
+    }
+    let created = !keyspace_directory.exists();
+    let staging = keyspace_directory.join(format!("{}{}", IMPORT_STAGING_PREFIX, uuid::Uuid::new_v4()));
+    let extracted = extract_tables(path, archive, &staging).and_then(|_| {
+        let mut moved = Vec::new();
+        for table in &archive.tables {
+            let directory = keyspace_directory.join(&table.table);
+            if let Err(e) = std::fs::rename(staging.join(&table.table), &directory) {
+                for directory in moved {
+                    std::fs::remove_dir_all(directory).ok();
+                }
+                return Err(e.into());
+            }
+            moved.push(directory);
+        }
+        Ok(())
+    });
+    std::fs::remove_dir_all(&staging).ok();
+    if extracted.is_err() && created {
+        std::fs::remove_dir(keyspace_directory).ok();
+    }
+    extracted
+}
+
+/// 아카이브의 SSTable 구성 파일을 directory/<table>/ 아래에 풂
+fn extract_tables(path: &Path, archive: &KeyspaceArchive, directory: &Path) -> Result<()> {
+    for table in &archive.tables {
+        std::fs::create_dir_all(directory.join(&table.table))?;
+    }
+    let mut tar = TarReader::open(path)?;
+    let mut extracted = 0;
+    while let Some((name, size)) = tar.next_entry()? {
+        let mut parts = name.splitn(3, '/');
+        let (Some(_), Some(table), Some(file)) = (parts.next(), parts.next(), parts.next()) else { continue };
+        let expected = archive.tables.iter()
+            .find(|archived| archived.table == table)
+            .and_then(|archived| archived.files.iter().find(|(name, _)| name == file));
+        match expected {
+            Some((_, expected_size)) if *expected_size == size => {},
+            _ => return Err(CoreDBError::Archive { message: format!("Unexpected archive entry {} ({} bytes)", name, size) }),
+        }
+        let mut output = File::create(directory.join(table).join(file))?;
+        tar.copy_data(size, &mut output)?;
+        output.sync_all()?;
+        extracted += 1;
+    }
+    let listed: usize = archive.tables.iter().map(|table| table.files.len()).sum();
+    if extracted != listed {
+        return Err(CoreDBError::Archive { message: format!("Archive has {} of {} SSTable files", extracted, listed) });
+    }
+    Ok(())
+}
+
+/// tar(ustar) 일반 파일만 쓰는 최소 구현
+struct TarWriter<W: Write> {
+    inner: W,
+}
+
+impl<W: Write> TarWriter<W> {
+    fn append(&mut self, name: &str, size: u64, data: &mut impl Read) -> Result<()> {
+        let mut header = [0u8; TAR_BLOCK];
+        // 100바이트를 넘는 경로는 앞부분을 prefix(155바이트)로 나눔
+        let (prefix, name) = match name.len() {
+            0..=100 => ("", name),
+            _ => name.char_indices()
+                .filter(|&(index, c)| c == '/' && index <= 155 && name.len() - index - 1 <= 100)
+                .map(|(index, _)| (&name[..index], &name[index + 1..]))
+                .next()
+                .ok_or_else(|| CoreDBError::Archive { message: format!("Archive path {} is too long", name) })?,
+        };
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        header[100..108].copy_from_slice(b"0000644\0");
+        header[108..116].copy_from_slice(b"0000000\0");
+        header[116..124].copy_from_slice(b"0000000\0");
+        header[124..136].copy_from_slice(format!("{:011o}\0", size).as_bytes());
+        header[136..148].copy_from_slice(format!("{:011o}\0", chrono::Utc::now().timestamp().max(0)).as_bytes());
+        header[156] = b'0';
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+        header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+        header[148..156].fill(b' ');
+        let checksum: u32 = header.iter().map(|&byte| byte as u32).sum();
+        header[148..156].copy_from_slice(format!("{:06o}\0 ", checksum).as_bytes());
+        self.inner.write_all(&header)?;
+
+        let copied = std::io::copy(&mut data.take(size), &mut self.inner)?;
+        if copied != size {
+            return Err(CoreDBError::Archive { message: format!("{} changed while archiving", name) });
+        }
+        let padding = (TAR_BLOCK - (size as usize % TAR_BLOCK)) % TAR_BLOCK;
+        self.inner.write_all(&[0; TAR_BLOCK][..padding])?;
+        Ok(())
+    }
+}
+
+/// zstd로 압축한 tar 읽기
+pub(crate) struct TarReader<R: Read> {
+    inner: R,
+    /// 현재 엔트리에서 아직 읽지 않은 바이트 (패딩 포함)
+    remaining: u64,
+}
+
+impl TarReader<zstd::stream::Decoder<'static, BufReader<File>>> {
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        Ok(Self { inner: zstd::stream::Decoder::new(File::open(path)?)?, remaining: 0 })
+    }
+}
+
+impl<R: Read> TarReader<R> {
+    /// 다음 일반 파일의 경로와 크기 (끝이면 None, 읽지 않은 앞 엔트리 데이터는 건너뜀)
+    pub(crate) fn next_entry(&mut self) -> Result<Option<(String, u64)>> {
+        loop {
+            std::io::copy(&mut (&mut self.inner).take(self.remaining), &mut std::io::sink())?;
+            let mut header = [0u8; TAR_BLOCK];
+            self.inner.read_exact(&mut header)?;
+            if header.iter().all(|&byte| byte == 0) {
+                return Ok(None);
+            }
+            let invalid = || CoreDBError::Archive { message: "Corrupted archive header".to_string() };
+            let octal = |field: &[u8]| {
+                let text = std::str::from_utf8(field).ok()?.trim_matches(|c: char| c == '\0' || c == ' ');
+                u64::from_str_radix(text, 8).ok()
+            };
+            let mut unsigned = header;
+            unsigned[148..156].fill(b' ');
+            if octal(&header[148..156]) != Some(unsigned.iter().map(|&byte| byte as u64).sum()) {
+                return Err(invalid());
+            }
+            let size = octal(&header[124..136]).ok_or_else(invalid)?;
+            self.remaining = size.div_ceil(TAR_BLOCK as u64) * TAR_BLOCK as u64;
+            if !matches!(header[156], b'0' | 0) {
+                continue;
+            }
+            let text = |field: &[u8]| String::from_utf8_lossy(field.split(|&byte| byte == 0).next().unwrap_or_default()).to_string();
+            let (prefix, name) = (text(&header[345..500]), text(&header[..100]));
+            let path = if prefix.is_empty() { name } else { format!("{}/{}", prefix, name) };
+            return Ok(Some((path, size)));
+        }
+    }
+
+    /// 현재 엔트리의 데이터를 모두 읽음
+    pub(crate) fn read_data(&mut self, size: u64) -> Result<Vec<u8>> {
+        let mut data = Vec::with_capacity(size as usize);
+        (&mut self.inner).take(size).read_to_end(&mut data)?;
+        self.remaining -= size;
+        Ok(data)
+    }
+
+    /// 현재 엔트리의 데이터를 out에 복사
+    pub(crate) fn copy_data(&mut self, size: u64, out: &mut impl Write) -> Result<()> {
+        let copied = std::io::copy(&mut (&mut self.inner).take(size), out)?;
+        self.remaining -= copied;
+        if copied != size {
+            return Err(CoreDBError::Archive { message: "Truncated archive".to_string() });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{CoreDB, DatabaseConfig};
+    use crate::schema::{CassandraDataType, CassandraValue, Cells, ColumnDefinition, PartitionKey, Row};
+
+    #[tokio::test]
+    async fn test_export_keyspace_bundles_schema_and_sstables() {
+        let test_dir = std::env::temp_dir().join("coredb_test_export_keyspace");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        let schema = TableSchema::new("rows".to_string(), "app".to_string(),
+                                      vec![ColumnDefinition { name: "id".to_string(), data_type: CassandraDataType::Int, is_static: false }],
+                                      vec![], vec![], vec![]);
+        db.create_keyspace("app".to_string(), 1).await.unwrap();
+        db.create_table("app".to_string(), "rows".to_string(), schema).await.unwrap();
+        db.execute_cql("CREATE KEYSPACE app WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql("CREATE TABLE app.items (id INT PRIMARY KEY)").await.unwrap();
+        db.execute_cql("CREATE KEYSPACE other WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        let row = Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(1)] }, clustering_key: None, cells: Cells::new(), timestamp: 1 };
+        db.insert_row("app", "rows", row).await.unwrap();
+        db.execute_cql("INSERT INTO app.items (id) VALUES (1)").await.unwrap();
+
+        // 메모리 테이블의 행까지 플러시해 담고, 다른 키스페이스의 스키마 문은 빠짐
+        let out = test_dir.join("app.tar.zst");
+        let archive = db.export_keyspace("app", &out).await.unwrap();
+        assert_eq!(archive.schema.len(), 2);
+        assert!(archive.schema.iter().all(|statement| !statement.contains("other")));
+        assert_eq!(archive.tables.len(), 2);
+        // CQL로 만든 테이블은 스키마 문으로 다시 만듦
+        let items = archive.tables.iter().find(|table| table.table == "items").unwrap();
+        assert_eq!(items.sstables.len(), 1);
+        assert!(items.schema.is_none());
+        let table = archive.tables.iter().find(|table| table.table == "rows").unwrap();
+        assert_eq!(table.sstables.len(), 1);
+        assert!(table.schema.is_some());
+
+        let manifest = read_archive_manifest(&out).unwrap();
+        assert_eq!((manifest.format_version, manifest.keyspace.as_str()), (ARCHIVE_FORMAT_VERSION, "app"));
+        let mut tar = TarReader::open(&out).unwrap();
+        let mut entries = Vec::new();
+        while let Some((name, size)) = tar.next_entry().unwrap() {
+            if name.ends_with(ARCHIVE_SCHEMA_FILE) {
+                let schema = String::from_utf8(tar.read_data(size).unwrap()).unwrap();
+                assert_eq!(schema.lines().count(), 2);
+            }
+            entries.push((name, size));
+        }
+        assert_eq!(entries[0].0, format!("app/{}", ARCHIVE_MANIFEST_FILE));
+        assert_eq!(entries.len(), 2 + table.files.len() + items.files.len());
+        for (file, size) in &table.files {
+            assert!(entries.contains(&(format!("app/rows/{}", file), *size)), "{}", file);
+        }
+        // 내보내며 찍은 스냅샷은 남지 않음
+        assert!(db.list_snapshots("app").await.unwrap().is_empty());
+        assert!(matches!(db.export_keyspace("missing", &test_dir.join("missing.tar.zst")).await, Err(CoreDBError::KeyspaceNotFound { .. })));
+        db.shutdown().await.unwrap();
+
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_import_keyspace_under_a_new_name() {
+        let test_dir = std::env::temp_dir().join("coredb_test_import_keyspace");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = |node: &str| DatabaseConfig {
+            data_directory: test_dir.join(node).join("data"),
+            commitlog_directory: test_dir.join(node).join("commitlog"),
+            ..Default::default()
+        };
+        let schema = TableSchema::new("rows".to_string(), "app".to_string(),
+                                      vec![ColumnDefinition { name: "id".to_string(), data_type: CassandraDataType::Int, is_static: false }],
+                                      vec![], vec![], vec![]);
+        let key = |id: i32| PartitionKey { components: vec![CassandraValue::Int(id)] };
+        let out = test_dir.join("app.tar.zst");
+        let source = CoreDB::new(config("source")).await.unwrap();
+        source.create_keyspace("app".to_string(), 1).await.unwrap();
+        source.create_table("app".to_string(), "rows".to_string(), schema).await.unwrap();
+        source.execute_cql("CREATE KEYSPACE app WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        source.execute_cql("CREATE TABLE app.items (id INT PRIMARY KEY)").await.unwrap();
+        source.execute_cql("INSERT INTO app.items (id) VALUES (1)").await.unwrap();
+        // 스냅샷과 내보내기가 한 번씩 플러시해 SSTable이 두 개
+        source.insert_row("app", "rows", Row { partition_key: key(1), clustering_key: None, cells: Cells::new(), timestamp: 1 }).await.unwrap();
+        source.snapshot_table("app", "rows", "first").await.unwrap();
+        source.insert_row("app", "rows", Row { partition_key: key(2), clustering_key: None, cells: Cells::new(), timestamp: 1 }).await.unwrap();
+        source.export_keyspace("app", &out).await.unwrap();
+        source.shutdown().await.unwrap();
+
+        let db = CoreDB::new(config("target")).await.unwrap();
+        let imported = db.import_keyspace(&out, Some("copy")).await.unwrap();
+        assert_eq!(imported.keyspace, "copy");
+        let rows = imported.tables.iter().find(|table| table.table == "rows").unwrap();
+        assert_eq!(rows.sstables.len(), 2);
+        for id in 1..=2 {
+            assert!(db.get_row("copy", "rows", &key(id), &None).await.unwrap().is_some());
+        }
+        assert!(db.query_engine.read().await.has_table("copy", "items"));
+        let crate::query::QueryResult::Rows(items) = db.execute_cql("SELECT * FROM copy.items WHERE id = 1").await.unwrap() else { panic!("expected rows") };
+        assert_eq!(items.len(), 1);
+        let statements: Vec<_> = db.schema_history().await.unwrap().into_iter().map(|change| change.statement).collect();
+        assert!(statements.iter().any(|statement| statement == "CREATE TABLE copy.items (id INT PRIMARY KEY)"));
+        let manifest = crate::storage::Manifest::load(test_dir.join("target/data/copy/rows")).await.unwrap().unwrap();
+        assert_eq!(manifest.sstables, rows.sstables);
+
+        // 같은 이름으로 다시 가져오면 아무것도 바꾸지 않고 실패
+        assert!(matches!(db.import_keyspace(&out, Some("copy")).await, Err(CoreDBError::Archive { .. })));
+        assert!(matches!(db.import_keyspace(&out, Some("../x")).await, Err(CoreDBError::Archive { .. })));
+        db.import_keyspace(&out, None).await.unwrap();
+        assert!(db.get_row("app", "rows", &key(1), &None).await.unwrap().is_some());
+        db.shutdown().await.unwrap();
+
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_import_of_truncated_archive_leaves_nothing_behind() {
+        let test_dir = std::env::temp_dir().join("coredb_test_import_truncated_archive");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = |node: &str| DatabaseConfig {
+            data_directory: test_dir.join(node).join("data"),
+            commitlog_directory: test_dir.join(node).join("commitlog"),
+            ..Default::default()
+        };
+        let out = test_dir.join("app.tar.zst");
+        let source = CoreDB::new(config("source")).await.unwrap();
+        source.execute_cql("CREATE KEYSPACE app WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        source.execute_cql("CREATE TABLE app.items (id INT PRIMARY KEY, body TEXT)").await.unwrap();
+        source.execute_cql("CREATE TABLE app.events (id INT PRIMARY KEY, body TEXT)").await.unwrap();
+        for id in 0..200 {
+            for table in ["items", "events"] {
+                source.execute_cql(&format!("INSERT INTO app.{} (id, body) VALUES ({}, '{}')", table, id, "x".repeat(id as usize))).await.unwrap();
+            }
+        }
+        source.export_keyspace("app", &out).await.unwrap();
+        source.shutdown().await.unwrap();
+
+        // 메타데이터는 읽히지만 SSTable 파일 중간에서 끊긴 아카이브
+        let truncated = test_dir.join("truncated.tar.zst");
+        let tar = zstd::stream::decode_all(File::open(&out).unwrap()).unwrap();
+        std::fs::write(&truncated, zstd::stream::encode_all(&tar[..tar.len() * 3 / 4], 3).unwrap()).unwrap();
+        assert!(read_archive_manifest(&truncated).is_ok());
+
+        let db = CoreDB::new(config("target")).await.unwrap();
+        assert!(db.import_keyspace(&truncated, None).await.is_err());
+        // 일부만 푼 테이블 디렉토리나 임시 디렉토리가 남지 않아 같은 이름으로 다시 가져올 수 있음
+        assert!(!test_dir.join("target/data/app").exists());
+        db.import_keyspace(&out, None).await.unwrap();
+        let crate::query::QueryResult::Rows(items) = db.execute_cql("SELECT * FROM app.events WHERE id = 199").await.unwrap() else { panic!("expected rows") };
+        assert_eq!(items.len(), 1);
+        db.shutdown().await.unwrap();
+
+        // 이미 있는 테이블 디렉토리는 건드리지 않음
+        let keyspace_directory = test_dir.join("existing");
+        std::fs::create_dir_all(keyspace_directory.join("events")).unwrap();
+        std::fs::write(keyspace_directory.join("events/keep"), b"keep").unwrap();
+        let archive = read_archive_manifest(&out).unwrap();
+        assert!(matches!(extract_keyspace_archive(&out, &archive, &keyspace_directory), Err(CoreDBError::Archive { .. })));
+        assert!(keyspace_directory.join("events/keep").exists());
+        assert_eq!(std::fs::read_dir(&keyspace_directory).unwrap().count(), 1);
+
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+}
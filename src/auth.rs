@@ -0,0 +1,312 @@
+use crate::error::{CoreDBError, Result};
+use crate::query::CqlStatement;
+use crate::query::system_views::DRIVER_METADATA_TABLES;
+use crate::schema::{CassandraDataType, CassandraValue, Cell, Cells, ColumnDefinition, PartitionKey, Row, TableSchema};
+
+/// 인증 정보를 저장하는 키스페이스
+pub const SYSTEM_AUTH_KEYSPACE: &str = "system_auth";
+/// 역할 테이블 (role, salted_hash, is_superuser, can_login, created_at)
+pub const ROLES_TABLE: &str = "roles";
+/// 권한 테이블 ((role, resource), 권한마다 Boolean 열)
+pub const ROLE_PERMISSIONS_TABLE: &str = "role_permissions";
+/// 처음 시작할 때 만드는 기본 슈퍼유저 (Cassandra와 같이 cassandra/cassandra, 바로 바꿔야 함)
+pub const DEFAULT_SUPERUSER: &str = "cassandra";
+/// 기본 슈퍼유저 비밀번호 'cassandra'의 bcrypt 해시 (시작할 때마다 해시하지 않도록 미리 계산)
+const DEFAULT_SUPERUSER_HASH: &str = "$2b$10$hYBrjUHxQ5jmGYzvvw2ZuO5yYrqwTOrpDeScN55xOdlqfrqvu0E9y";
+
+/// bcrypt 비용 (2^n 라운드, 테스트에서는 가장 낮은 값)
+const BCRYPT_COST: u32 = if cfg!(test) { 4 } else { 10 };
+
+/// 로그인하거나 권한을 받는 역할 (CREATE ROLE / CREATE USER)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Role {
+    pub name: String,
+    /// bcrypt 해시 (비밀번호가 없으면 None, 로그인할 수 없음)
+    pub salted_hash: Option<String>,
+    pub is_superuser: bool,
+    pub can_login: bool,
+    /// 만든 시각 (마이크로초, 이보다 먼저 부여된 권한은 같은 이름으로 지워졌던 역할의 것이라 무시)
+    pub created_at: i64,
+}
+
+impl Role {
+    /// system_auth.roles 스키마
+    pub fn table_schema() -> TableSchema {
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        TableSchema::new(
+            ROLES_TABLE.to_string(),
+            SYSTEM_AUTH_KEYSPACE.to_string(),
+            vec![column("role", CassandraDataType::Text)],
+            vec![],
+            vec![
+                column("salted_hash", CassandraDataType::Text),
+                column("is_superuser", CassandraDataType::Boolean),
+                column("can_login", CassandraDataType::Boolean),
+                column("created_at", CassandraDataType::Timestamp),
+            ],
+            vec![],
+        )
+    }
+
+    /// 처음 시작할 때 만드는 기본 슈퍼유저
+    pub fn default_superuser() -> Self {
+        Self {
+            name: DEFAULT_SUPERUSER.to_string(),
+            salted_hash: Some(DEFAULT_SUPERUSER_HASH.to_string()),
+            is_superuser: true,
+            can_login: true,
+            created_at: 0,
+        }
+    }
+
+    pub fn partition_key(name: &str) -> PartitionKey {
+        PartitionKey { components: vec![CassandraValue::Text(name.to_string())] }
+    }
+
+    /// 저장된 행에서 읽기 (DROP ROLE로 지워진 행이면 None)
+    pub fn from_row(name: &str, row: &Row) -> Option<Self> {
+        let live = |column: &str| row.cells.get(column).filter(|cell| !cell.is_deleted).map(|cell| &cell.value);
+        let flag = |column: &str| matches!(live(column), Some(CassandraValue::Boolean(true)));
+        // 역할마다 항상 기록되는 열로 살아 있는지 판단
+        live("can_login")?;
+        Some(Self {
+            name: name.to_string(),
+            salted_hash: match live("salted_hash") {
+                Some(CassandraValue::Text(hash)) => Some(hash.clone()),
+                _ => None,
+            },
+            is_superuser: flag("is_superuser"),
+            can_login: flag("can_login"),
+            created_at: match live("created_at") {
+                Some(CassandraValue::Timestamp(created_at)) => *created_at,
+                _ => 0,
+            },
+        })
+    }
+
+    pub fn to_row(&self, timestamp: i64) -> Row {
+        let cell = |value, is_deleted| Cell { value, timestamp, ttl: None, is_deleted };
+        let mut cells = Cells::new();
+        match &self.salted_hash {
+            Some(hash) => cells.insert("salted_hash", cell(CassandraValue::Text(hash.clone()), false)),
+            None => cells.insert("salted_hash", cell(CassandraValue::Null, true)),
+        };
+        cells.insert("is_superuser", cell(CassandraValue::Boolean(self.is_superuser), false));
+        cells.insert("can_login", cell(CassandraValue::Boolean(self.can_login), false));
+        cells.insert("created_at", cell(CassandraValue::Timestamp(self.created_at), false));
+        Row { partition_key: Self::partition_key(&self.name), clustering_key: None, cells, timestamp }
+    }
+
+    /// role_permissions 행에서 권한을 받았는지 (역할이 만들어지기 전에 쓴 권한은 무시)
+    pub fn is_granted(&self, row: &Row, permission: Permission) -> bool {
+        row.cells.get(permission.column()).is_some_and(|cell| {
+            !cell.is_deleted && cell.timestamp >= self.created_at && cell.value == CassandraValue::Boolean(true)
+        })
+    }
+
+    /// DROP ROLE 툼스톤 (커밋 로그 재생이 삭제 뮤테이션을 지원하지 않아 삭제된 셀로 덮어씀)
+    pub fn tombstone_row(name: &str, timestamp: i64) -> Row {
+        let mut cells = Cells::new();
+        for column in ["salted_hash", "is_superuser", "can_login", "created_at"] {
+            cells.insert(column, Cell { value: CassandraValue::Null, timestamp, ttl: None, is_deleted: true });
+        }
+        Row { partition_key: Self::partition_key(name), clustering_key: None, cells, timestamp }
+    }
+}
+
+/// 키스페이스와 테이블에 부여하는 권한
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    /// SELECT
+    Select,
+    /// INSERT, UPDATE, DELETE
+    Modify,
+    /// CREATE KEYSPACE, CREATE TABLE
+    Create,
+    /// ALTER TABLE, CREATE INDEX
+    Alter,
+    /// DROP KEYSPACE, DROP TABLE
+    Drop,
+}
+
+impl Permission {
+    /// GRANT ALL PERMISSIONS
+    pub const ALL: [Permission; 5] = [Permission::Select, Permission::Modify, Permission::Create, Permission::Alter, Permission::Drop];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|permission| permission.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Permission::Select => "SELECT",
+            Permission::Modify => "MODIFY",
+            Permission::Create => "CREATE",
+            Permission::Alter => "ALTER",
+            Permission::Drop => "DROP",
+        }
+    }
+
+    /// role_permissions의 열 이름
+    fn column(&self) -> &'static str {
+        match self {
+            Permission::Select => "select",
+            Permission::Modify => "modify",
+            Permission::Create => "create",
+            Permission::Alter => "alter",
+            Permission::Drop => "drop",
+        }
+    }
+}
+
+impl std::fmt::Display for Permission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// 권한을 부여하는 대상 (상위 대상의 권한은 하위 대상에도 적용)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resource {
+    AllKeyspaces,
+    Keyspace(String),
+    Table(String, String),
+}
+
+impl Resource {
+    /// 저장할 때 쓰는 이름 (Cassandra와 같은 data/<ks>/<table>)
+    pub fn name(&self) -> String {
+        match self {
+            Resource::AllKeyspaces => "data".to_string(),
+            Resource::Keyspace(keyspace) => format!("data/{}", keyspace),
+            Resource::Table(keyspace, table) => format!("data/{}/{}", keyspace, table),
+        }
+    }
+
+    /// 자신과 상위 대상 (가까운 것부터)
+    pub fn chain(&self) -> Vec<Resource> {
+        match self {
+            Resource::AllKeyspaces => vec![Resource::AllKeyspaces],
+            Resource::Keyspace(_) => vec![self.clone(), Resource::AllKeyspaces],
+            Resource::Table(keyspace, _) => vec![self.clone(), Resource::Keyspace(keyspace.clone()), Resource::AllKeyspaces],
+        }
+    }
+
+    pub fn permissions_table_schema() -> TableSchema {
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        TableSchema::new(
+            ROLE_PERMISSIONS_TABLE.to_string(),
+            SYSTEM_AUTH_KEYSPACE.to_string(),
+            vec![column("role", CassandraDataType::Text), column("resource", CassandraDataType::Text)],
+            vec![],
+            Permission::ALL.iter().map(|permission| column(permission.column(), CassandraDataType::Boolean)).collect(),
+            vec![],
+        )
+    }
+
+    pub fn permissions_key(&self, role: &str) -> PartitionKey {
+        PartitionKey { components: vec![CassandraValue::Text(role.to_string()), CassandraValue::Text(self.name())] }
+    }
+
+    /// GRANT (granted = true) 또는 REVOKE 행 (주어진 권한 열만 씀)
+    pub fn permissions_row(&self, role: &str, permissions: &[Permission], granted: bool, timestamp: i64) -> Row {
+        let mut cells = Cells::new();
+        for permission in permissions {
+            cells.insert(permission.column(), Cell { value: CassandraValue::Boolean(granted), timestamp, ttl: None, is_deleted: false });
+        }
+        Row { partition_key: self.permissions_key(role), clustering_key: None, cells, timestamp }
+    }
+}
+
+impl std::fmt::Display for Resource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Resource::AllKeyspaces => write!(f, "ALL KEYSPACES"),
+            Resource::Keyspace(keyspace) => write!(f, "KEYSPACE {}", keyspace),
+            Resource::Table(keyspace, table) => write!(f, "TABLE {}.{}", keyspace, table),
+        }
+    }
+}
+
+/// 문을 실행하는 데 필요한 권한 (권한이 필요 없거나 역할 관리 문이면 None)
+pub fn required_permission(statement: &CqlStatement) -> Option<(Permission, Resource)> {
+    let table = |keyspace: &String, table: &String| Resource::Table(keyspace.clone(), table.clone());
+    match statement {
+        CqlStatement::CreateKeyspace { .. } => Some((Permission::Create, Resource::AllKeyspaces)),
+        CqlStatement::CreateTable { keyspace, .. } => Some((Permission::Create, Resource::Keyspace(keyspace.clone()))),
+        CqlStatement::CreateIndex { keyspace, table: name, .. } => Some((Permission::Alter, table(keyspace, name))),
+        CqlStatement::AlterTable { keyspace, name, .. } => Some((Permission::Alter, table(keyspace, name))),
+        // 드라이버가 연결할 때 읽는 노드 정보와 스키마 메타데이터는 권한 없이 읽을 수 있음
+        CqlStatement::Select { keyspace, table: name, .. }
+            if DRIVER_METADATA_TABLES.iter().any(|(ks, t)| keyspace == ks && name == t) => None,
+        CqlStatement::Select { keyspace, table: name, .. } => Some((Permission::Select, table(keyspace, name))),
+        CqlStatement::Insert { keyspace, table: name, .. }
+        | CqlStatement::Update { keyspace, table: name, .. }
+        | CqlStatement::Delete { keyspace, table: name, .. } => Some((Permission::Modify, table(keyspace, name))),
+        CqlStatement::DropTable { keyspace, name } => Some((Permission::Drop, table(keyspace, name))),
+        CqlStatement::DropKeyspace { name } => Some((Permission::Drop, Resource::Keyspace(name.clone()))),
+        CqlStatement::Use { .. } | CqlStatement::Tracing { .. } => None,
+        CqlStatement::CreateRole { .. } | CqlStatement::AlterRole { .. } | CqlStatement::DropRole { .. }
+        | CqlStatement::Grant { .. } | CqlStatement::Revoke { .. } => None,
+    }
+}
+
+/// 비밀번호 해시 (bcrypt, 솔트 포함)
+pub fn hash_password(password: &str) -> Result<String> {
+    bcrypt::hash(password, BCRYPT_COST).map_err(|e| CoreDBError::Generic { message: format!("Failed to hash password: {}", e) })
+}
+
+/// 비밀번호가 해시와 맞는지 (해시가 손상되었으면 false)
+pub fn verify_password(password: &str, salted_hash: &str) -> bool {
+    bcrypt::verify(password, salted_hash).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_round_trips_through_rows_and_tombstones() {
+        let role = Role {
+            name: "reporting".to_string(),
+            salted_hash: Some(hash_password("secret").unwrap()),
+            is_superuser: false,
+            can_login: true,
+            created_at: 5,
+        };
+        let row = role.to_row(10);
+        assert_eq!(Role::from_row("reporting", &row), Some(role.clone()));
+        assert!(verify_password("secret", role.salted_hash.as_deref().unwrap()));
+        assert!(!verify_password("wrong", role.salted_hash.as_deref().unwrap()));
+        assert!(!verify_password("secret", "not a hash"));
+        assert!(verify_password(DEFAULT_SUPERUSER, Role::default_superuser().salted_hash.as_deref().unwrap()));
+
+        // 나중 툼스톤이 이기고, 그 뒤에 다시 만들면 살아남
+        let mut dropped = row.clone();
+        dropped.merge(Role::tombstone_row("reporting", 20));
+        assert_eq!(Role::from_row("reporting", &dropped), None);
+        dropped.merge(Role { salted_hash: None, ..role.clone() }.to_row(30));
+        assert_eq!(Role::from_row("reporting", &dropped).unwrap().salted_hash, None);
+    }
+
+    #[test]
+    fn test_permissions_granted_revoked_and_scoped_to_role_lifetime() {
+        let role = Role { name: "reporting".to_string(), salted_hash: None, is_superuser: false, can_login: true, created_at: 100 };
+        let resource = Resource::Keyspace("sales".to_string());
+        let mut row = resource.permissions_row("reporting", &Permission::ALL, true, 200);
+        assert!(role.is_granted(&row, Permission::Drop));
+
+        row.merge(resource.permissions_row("reporting", &[Permission::Drop], false, 300));
+        assert!(!role.is_granted(&row, Permission::Drop));
+        assert!(role.is_granted(&row, Permission::Select));
+
+        // 같은 이름으로 다시 만든 역할은 이전 권한을 받지 않음
+        let recreated = Role { created_at: 400, ..role };
+        assert!(!recreated.is_granted(&row, Permission::Select));
+
+        let table = Resource::Table("sales".to_string(), "orders".to_string());
+        assert_eq!(table.chain(), vec![table.clone(), resource, Resource::AllKeyspaces]);
+        assert_eq!(table.name(), "data/sales/orders");
+        assert_eq!(Permission::parse("modify"), Some(Permission::Modify));
+    }
+}
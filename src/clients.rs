@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// 연결된 클라이언트 목록 (system_views.clients)
+#[derive(Debug, Default)]
+pub struct ClientRegistry {
+    next_id: AtomicU64,
+    connections: Arc<Mutex<BTreeMap<u64, Arc<ClientConnection>>>>,
+}
+
+/// 클라이언트 연결 하나 (요청마다 사용자와 드라이버 정보를 갱신)
+#[derive(Debug)]
+pub struct ClientConnection {
+    pub address: SocketAddr,
+    /// TLS 연결이면 프로토콜 버전 (예: TLSv1_3)
+    pub ssl_protocol: Option<String>,
+    /// 연결 시각 (마이크로초)
+    pub connected_at: i64,
+    request_count: AtomicU64,
+    /// 마지막으로 로그인한 역할과 User-Agent
+    session: Mutex<(Option<String>, Option<String>)>,
+}
+
+/// 조회 시점의 클라이언트 연결 상태
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientInfo {
+    pub address: SocketAddr,
+    pub ssl_protocol: Option<String>,
+    pub connected_at: i64,
+    pub request_count: u64,
+    pub username: Option<String>,
+    pub driver_name: Option<String>,
+}
+
+/// 등록한 연결 (연결이 끝나 버리면 목록에서 빠짐)
+#[derive(Debug)]
+pub struct ClientSession {
+    id: u64,
+    connection: Arc<ClientConnection>,
+    connections: Arc<Mutex<BTreeMap<u64, Arc<ClientConnection>>>>,
+}
+
+impl ClientRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn connect(&self, address: SocketAddr, ssl_protocol: Option<String>) -> ClientSession {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let connection = Arc::new(ClientConnection {
+            address,
+            ssl_protocol,
+            connected_at: chrono::Utc::now().timestamp_micros(),
+            request_count: AtomicU64::new(0),
+            session: Mutex::new((None, None)),
+        });
+        self.connections.lock().unwrap().insert(id, connection.clone());
+        ClientSession { id, connection, connections: self.connections.clone() }
+    }
+
+    /// 연결 순서대로
+    pub fn clients(&self) -> Vec<ClientInfo> {
+        self.connections.lock().unwrap().values().map(|connection| connection.info()).collect()
+    }
+}
+
+impl ClientConnection {
+    /// 요청 하나 (User-Agent가 있으면 드라이버 이름으로 씀)
+    pub fn record_request(&self, user_agent: Option<&str>) {
+        self.request_count.fetch_add(1, Ordering::Relaxed);
+        if let Some(user_agent) = user_agent {
+            self.session.lock().unwrap().1 = Some(user_agent.to_string());
+        }
+    }
+
+    /// 로그인한 역할
+    pub fn set_username(&self, username: &str) {
+        self.session.lock().unwrap().0 = Some(username.to_string());
+    }
+
+    pub fn info(&self) -> ClientInfo {
+        let (username, driver_name) = self.session.lock().unwrap().clone();
+        ClientInfo {
+            address: self.address,
+            ssl_protocol: self.ssl_protocol.clone(),
+            connected_at: self.connected_at,
+            request_count: self.request_count.load(Ordering::Relaxed),
+            username,
+            driver_name,
+        }
+    }
+}
+
+impl ClientSession {
+    pub fn connection(&self) -> &Arc<ClientConnection> {
+        &self.connection
+    }
+}
+
+impl Drop for ClientSession {
+    fn drop(&mut self) {
+        self.connections.lock().unwrap().remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_registry_tracks_open_connections() {
+        let registry = ClientRegistry::new();
+        let first = registry.connect("10.0.0.1:50000".parse().unwrap(), None);
+        let second = registry.connect("10.0.0.2:50001".parse().unwrap(), Some("TLSv1_3".to_string()));
+        first.connection().record_request(Some("curl/8.0"));
+        first.connection().record_request(None);
+        first.connection().set_username("reporting");
+
+        let clients = registry.clients();
+        assert_eq!(clients.len(), 2);
+        assert_eq!((clients[0].request_count, clients[0].username.as_deref(), clients[0].driver_name.as_deref()), (2, Some("reporting"), Some("curl/8.0")));
+        assert_eq!(clients[1].ssl_protocol.as_deref(), Some("TLSv1_3"));
+
+        // 연결이 끝나면 목록에서 빠짐
+        drop(first);
+        assert_eq!(registry.clients().iter().map(|client| client.address.port()).collect::<Vec<_>>(), vec![50001]);
+        drop(second);
+        assert!(registry.clients().is_empty());
+    }
+}
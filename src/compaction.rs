@@ -1,10 +1,28 @@
-use std::path::PathBuf;
-use std::sync::Arc;
-use std::collections::HashMap;
-use tokio::sync::{RwLock, mpsc};
-use crate::storage::SSTable;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{RwLock, Semaphore, mpsc};
+use crate::schema::PartitionKey;
+use crate::storage::{CompressionType, DiskWriteMode, Manifest, SSTable, SSTableWriter, ReadOptions};
+use crate::storage::memtable::Partition;
+use crate::throttle::RateLimiter;
+use crate::latency::{LatencyHistogram, LatencySnapshot};
 use crate::error::*;
 
+/// 크기와 관계없이 같은 버킷으로 묶는 작은 SSTable 크기 (Cassandra min_sstable_size)
+const SMALL_SSTABLE_BYTES: u64 = 50 * 1024 * 1024;
+/// 버킷 평균 크기 대비 같은 버킷에 넣는 크기 범위 (Cassandra bucket_low/bucket_high)
+const BUCKET_LOW: f64 = 0.5;
+const BUCKET_HIGH: f64 = 1.5;
+/// Leveled 컴팩션 결과 SSTable 목표 크기 (Cassandra sstable_size_in_mb)
+pub const DEFAULT_LEVELED_SSTABLE_SIZE_MB: u64 = 160;
+/// L0 컴팩션 한 번에 넣는 L0 SSTable 최대 수
+const MAX_LEVEL0_COMPACTION_INPUTS: usize = 32;
+
 /// 컴팩션 전략
 #[derive(Debug, Clone)]
 pub enum CompactionStrategy {
@@ -15,6 +33,15 @@ pub enum CompactionStrategy {
     Leveled {
         level_size_multiplier: f64,
         max_levels: usize,
+        /// 레벨 1 이상 SSTable을 나누는 크기
+        sstable_size_mb: u64,
+    },
+    /// 가장 큰 타임스탬프로 시간 창을 나눠 창 안에서만 컴팩션
+    TimeWindow {
+        window_seconds: u64,
+        /// 현재 창 안의 Size-Tiered 임계값
+        min_threshold: usize,
+        max_threshold: usize,
     },
 }
 
@@ -27,22 +54,348 @@ impl Default for CompactionStrategy {
     }
 }
 
+impl CompactionStrategy {
+    /// 기본 매개변수의 Leveled 전략
+    pub fn leveled() -> Self {
+        CompactionStrategy::Leveled {
+            level_size_multiplier: 10.0,
+            max_levels: 9,
+            sstable_size_mb: DEFAULT_LEVELED_SSTABLE_SIZE_MB,
+        }
+    }
+    
+    /// 테이블 옵션이 고른 전략 (설정의 전략과 종류가 같으면 그 매개변수, 다르면 기본값)
+    pub fn for_table(table_strategy: &crate::schema::CompactionStrategy, configured: &CompactionStrategy) -> Self {
+        use crate::schema::CompactionStrategy as TableStrategy;
+        match (table_strategy, configured) {
+            (TableStrategy::Leveled, CompactionStrategy::Leveled { .. }) => configured.clone(),
+            (TableStrategy::Leveled, _) => Self::leveled(),
+            (TableStrategy::TimeWindow { window_seconds }, _) => {
+                let (min_threshold, max_threshold) = match configured {
+                    CompactionStrategy::SizeTiered { min_threshold, max_threshold }
+                    | CompactionStrategy::TimeWindow { min_threshold, max_threshold, .. } => (*min_threshold, *max_threshold),
+                    CompactionStrategy::Leveled { .. } => (4, 32),
+                };
+                CompactionStrategy::TimeWindow { window_seconds: *window_seconds, min_threshold, max_threshold }
+            },
+            (_, CompactionStrategy::SizeTiered { .. }) => configured.clone(),
+            _ => Self::default(),
+        }
+    }
+    
+    /// 컴팩션할 SSTable과 결과를 둘 레벨 선택 (sstables는 매니페스트 레벨과 함께, 없으면 None)
+    pub fn select(&self, sstables: &[(Arc<SSTable>, u32)]) -> Option<(Vec<Arc<SSTable>>, u32)> {
+        match *self {
+            CompactionStrategy::SizeTiered { min_threshold, max_threshold } => {
+                let sstables: Vec<_> = sstables.iter().map(|(sstable, _)| sstable.clone()).collect();
+                let inputs = size_tiered_bucket(&sstables, min_threshold, max_threshold);
+                (!inputs.is_empty()).then_some((inputs, 0))
+            },
+            CompactionStrategy::Leveled { level_size_multiplier, max_levels, sstable_size_mb } => {
+                let mut manager = LevelManager::new(max_levels, level_size_multiplier)
+                    .with_sstable_size(sstable_size_mb * 1024 * 1024);
+                for (sstable, level) in sstables {
+                    manager.add_sstable(sstable.clone(), *level as usize);
+                }
+                manager.needs_compaction().map(|(level, inputs)| (inputs, level as u32 + 1))
+            },
+            CompactionStrategy::TimeWindow { window_seconds, min_threshold, max_threshold } => {
+                let sstables: Vec<_> = sstables.iter().map(|(sstable, _)| sstable.clone()).collect();
+                let inputs = time_window_bucket(&sstables, window_seconds, min_threshold, max_threshold);
+                (!inputs.is_empty()).then_some((inputs, 0))
+            },
+        }
+    }
+    
+    /// 지금 SSTable을 전략대로 정리하는 데 필요한 컴팩션 수 추정 (select를 반복할 때 실행될 수)
+    pub fn estimated_tasks(&self, sstables: &[(Arc<SSTable>, u32)]) -> usize {
+        match *self {
+            CompactionStrategy::SizeTiered { min_threshold, max_threshold } => {
+                let sstables: Vec<_> = sstables.iter().map(|(sstable, _)| sstable.clone()).collect();
+                size_tiered_tasks(&sstables, min_threshold, max_threshold)
+            },
+            CompactionStrategy::Leveled { level_size_multiplier, max_levels, sstable_size_mb } => {
+                let mut manager = LevelManager::new(max_levels, level_size_multiplier)
+                    .with_sstable_size(sstable_size_mb * 1024 * 1024);
+                for (sstable, level) in sstables {
+                    manager.add_sstable(sstable.clone(), *level as usize);
+                }
+                manager.estimated_tasks()
+            },
+            CompactionStrategy::TimeWindow { window_seconds, min_threshold, max_threshold } => {
+                let sstables: Vec<_> = sstables.iter().map(|(sstable, _)| sstable.clone()).collect();
+                let windows = time_windows(&sstables, window_seconds);
+                let newest = windows.keys().next_back().copied();
+                windows.into_iter()
+                    .map(|(window, sstables)| match Some(window) == newest {
+                        true => size_tiered_tasks(&sstables, min_threshold, max_threshold),
+                        false if sstables.len() >= 2 => sstables.len().div_ceil(max_threshold.max(2)),
+                        false => 0,
+                    })
+                    .sum()
+            },
+        }
+    }
+    
+    /// 결과 SSTable을 나누는 크기 (Size-Tiered는 나누지 않음)
+    fn max_sstable_bytes(&self) -> u64 {
+        match self {
+            CompactionStrategy::SizeTiered { .. } | CompactionStrategy::TimeWindow { .. } => u64::MAX,
+            CompactionStrategy::Leveled { sstable_size_mb, .. } => sstable_size_mb * 1024 * 1024,
+        }
+    }
+}
+
+/// 함께 컴팩션할 Size-Tiered 버킷 (컴팩션할 것이 없으면 빈 목록)
+///
+/// 크기가 비슷한 SSTable끼리 버킷으로 묶고, min_threshold개 이상인 버킷 중 평균 크기가
+/// 가장 작은 것을 작은 것부터 max_threshold개까지 고름
+fn size_tiered_bucket(sstables: &[Arc<SSTable>], min_threshold: usize, max_threshold: usize) -> Vec<Arc<SSTable>> {
+    // 작은 것부터 묶었으므로 조건을 만족하는 첫 버킷이 평균 크기가 가장 작음
+    let Some(mut bucket) = size_tiered_buckets(sstables).into_iter().find(|bucket| bucket.len() >= min_threshold.max(2)) else {
+        return Vec::new();
+    };
+    bucket.truncate(max_threshold);
+    bucket
+}
+
+/// Size-Tiered 버킷을 모두 정리하는 데 필요한 컴팩션 수 (min_threshold개 이상인 버킷마다 max_threshold개씩)
+fn size_tiered_tasks(sstables: &[Arc<SSTable>], min_threshold: usize, max_threshold: usize) -> usize {
+    size_tiered_buckets(sstables).iter()
+        .filter(|bucket| bucket.len() >= min_threshold.max(2))
+        .map(|bucket| bucket.len().div_ceil(max_threshold.max(2)))
+        .sum()
+}
+
+/// 크기가 비슷한 SSTable끼리 묶은 버킷 (평균 크기가 작은 버킷부터)
+fn size_tiered_buckets(sstables: &[Arc<SSTable>]) -> Vec<Vec<Arc<SSTable>>> {
+    let mut sorted = sstables.to_vec();
+    sorted.sort_by_key(|sstable| sstable.size_bytes);
+    
+    let mut buckets: Vec<Vec<Arc<SSTable>>> = Vec::new();
+    for sstable in sorted {
+        let size = sstable.size_bytes;
+        let fits = buckets.last().is_some_and(|bucket| {
+            let average = bucket.iter().map(|sstable| sstable.size_bytes).sum::<u64>() as f64 / bucket.len() as f64;
+            (size < SMALL_SSTABLE_BYTES && average < SMALL_SSTABLE_BYTES as f64)
+                || (size as f64 >= average * BUCKET_LOW && size as f64 <= average * BUCKET_HIGH)
+        });
+        match buckets.last_mut() {
+            Some(bucket) if fits => bucket.push(sstable),
+            _ => buckets.push(vec![sstable]),
+        }
+    }
+    buckets
+}
+
+/// 함께 컴팩션할 같은 시간 창의 SSTable (컴팩션할 것이 없으면 빈 목록)
+///
+/// 창은 가장 큰 타임스탬프(마이크로초)로 정하고, 가장 최근 창은 아직 쓰이는 중이라 Size-Tiered로
+/// 고르며, 지난 창은 SSTable이 둘 이상이면 하나로 합침. 그래서 창마다 결국 SSTable 하나가 남아
+/// TTL이 지나면 창 전체를 한 번에 지울 수 있음. 최근 창부터 살펴봄
+fn time_window_bucket(sstables: &[Arc<SSTable>], window_seconds: u64, min_threshold: usize, max_threshold: usize) -> Vec<Arc<SSTable>> {
+    let windows = time_windows(sstables, window_seconds);
+    let newest = windows.keys().next_back().copied();
+    for (window, mut sstables) in windows.into_iter().rev() {
+        if Some(window) == newest {
+            let bucket = size_tiered_bucket(&sstables, min_threshold, max_threshold);
+            if !bucket.is_empty() {
+                return bucket;
+            }
+        } else if sstables.len() >= 2 {
+            sstables.sort_by_key(|sstable| sstable.size_bytes);
+            sstables.truncate(max_threshold);
+            return sstables;
+        }
+    }
+    Vec::new()
+}
+
+/// 가장 큰 타임스탬프(마이크로초)로 나눈 시간 창별 SSTable
+fn time_windows(sstables: &[Arc<SSTable>], window_seconds: u64) -> BTreeMap<i64, Vec<Arc<SSTable>>> {
+    let window_micros = (window_seconds as i64).saturating_mul(1_000_000).max(1);
+    let mut windows: BTreeMap<i64, Vec<Arc<SSTable>>> = BTreeMap::new();
+    for sstable in sstables {
+        windows.entry(sstable.max_timestamp.div_euclid(window_micros)).or_default().push(sstable.clone());
+    }
+    windows
+}
+
 /// 컴팩션 작업
 #[derive(Debug)]
 pub struct CompactionTask {
     pub keyspace: String,
     pub table: String,
     pub input_sstables: Vec<Arc<SSTable>>,
-    pub output_sstable: Option<Arc<SSTable>>,
     pub strategy: CompactionStrategy,
+    /// 메이저 컴팩션 (전략과 관계없이 입력을 SSTable 하나로 합침)
+    pub major: bool,
+    /// 진행률과 취소 요청
+    pub progress: Arc<CompactionProgress>,
+    /// 출력 SSTable 압축 방식 (테이블 옵션)
+    pub compression: CompressionType,
+    /// 출력 SSTable을 둘 레벨 (Size-Tiered는 0)
+    pub level: u32,
+    /// 툼스톤을 지울 기준 (None이면 툼스톤을 모두 남김)
+    pub tombstone_gc: Option<TombstoneGc>,
+}
+
+/// 실행 중인 컴팩션의 진행률 (입력 파티션 기준)과 취소 요청
+#[derive(Debug)]
+pub struct CompactionProgress {
+    pub keyspace: String,
+    pub table: String,
+    pub major: bool,
+    total_partitions: AtomicU64,
+    merged_partitions: AtomicU64,
+    /// 입력 SSTable 디스크 크기 합계
+    total_bytes: AtomicU64,
+    /// 병합한 입력 파티션의 디스크 크기 (입력의 파티션당 평균 크기로 추정)
+    compacted_bytes: AtomicU64,
+    cancelled: AtomicBool,
+}
+
+/// 컴팩션 진행 상황 (`CompactionProgress`의 한 시점)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactionInfo {
+    pub keyspace: String,
+    pub table: String,
+    pub major: bool,
+    pub total_partitions: u64,
+    pub merged_partitions: u64,
+    pub total_bytes: u64,
+    pub compacted_bytes: u64,
+    pub cancelled: bool,
+}
+
+impl CompactionProgress {
+    pub fn new(keyspace: &str, table: &str, major: bool) -> Self {
+        Self {
+            keyspace: keyspace.to_string(),
+            table: table.to_string(),
+            major,
+            total_partitions: AtomicU64::new(0),
+            merged_partitions: AtomicU64::new(0),
+            total_bytes: AtomicU64::new(0),
+            compacted_bytes: AtomicU64::new(0),
+            cancelled: AtomicBool::new(false),
+        }
+    }
+    
+    /// 취소 요청 (병합은 다음 파티션 경계에서 멈추고 쓰던 SSTable을 지움)
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+    
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+    
+    pub fn info(&self) -> CompactionInfo {
+        CompactionInfo {
+            keyspace: self.keyspace.clone(),
+            table: self.table.clone(),
+            major: self.major,
+            total_partitions: self.total_partitions.load(Ordering::Relaxed),
+            merged_partitions: self.merged_partitions.load(Ordering::Relaxed),
+            total_bytes: self.total_bytes.load(Ordering::Relaxed),
+            compacted_bytes: self.compacted_bytes.load(Ordering::Relaxed),
+            cancelled: self.is_cancelled(),
+        }
+    }
+}
+
+impl CompactionInfo {
+    /// 진행률 (0.0 ~ 1.0)
+    pub fn fraction(&self) -> f64 {
+        if self.total_partitions == 0 {
+            return 1.0;
+        }
+        self.merged_partitions as f64 / self.total_partitions as f64
+    }
+    
+    /// 아직 병합하지 않은 입력 바이트 (추정)
+    pub fn remaining_bytes(&self) -> u64 {
+        self.total_bytes.saturating_sub(self.compacted_bytes)
+    }
+}
+
+/// 컴팩션에서 툼스톤과 만료된 셀을 지우는 기준
+///
+/// 죽은 지 gc_grace_seconds가 지났고, 타임스탬프가 같은 파티션이 있을 수 있는 나머지 SSTable과
+/// 메모리 테이블의 가장 작은 타임스탬프보다 작아 가리는 데이터가 컴팩션 밖에 남아 있지 않은 셀만 지움
+#[derive(Debug, Clone)]
+pub struct TombstoneGc {
+    /// 죽은 시각(초)이 이보다 이르면 gc_grace_seconds가 지난 것
+    pub gc_before: i64,
+    /// 컴팩션 입력이 아닌 SSTable
+    pub other_sstables: Vec<Arc<SSTable>>,
+    pub min_memtable_timestamp: i64,
+}
+
+impl TombstoneGc {
+    /// 파티션에서 이보다 작은 타임스탬프의 툼스톤은 컴팩션 밖의 데이터를 가리지 않음
+    fn max_purgeable_timestamp(&self, partition_key: &PartitionKey) -> i64 {
+        self.other_sstables.iter()
+            .filter(|sstable| sstable.bloom_filter.might_contain(partition_key))
+            .map(|sstable| sstable.min_timestamp)
+            .fold(self.min_memtable_timestamp, i64::min)
+    }
+    
+    /// 지울 수 있는 셀을 지움 (셀이 모두 지워진 행도 지우고, 파티션에 남은 것이 없으면 false)
+    pub fn purge(&self, partition_key: &PartitionKey, partition: &mut Partition) -> bool {
+        let max_purgeable = self.max_purgeable_timestamp(partition_key);
+        let purgeable = |cell: &crate::schema::Cell| cell.deletion_time() < self.gc_before && cell.timestamp < max_purgeable;
+        partition.static_columns.retain(|_, cell| !purgeable(cell));
+        for entry in partition.rows.iter() {
+            let row = entry.value();
+            if !row.cells.iter().any(|(_, cell)| purgeable(cell)) {
+                continue;
+            }
+            let mut row = row.clone();
+            row.cells.retain(|_, cell| !purgeable(cell));
+            if row.cells.is_empty() {
+                entry.remove();
+            } else {
+                partition.rows.insert(row.clustering_key.clone(), row);
+            }
+        }
+        !partition.rows.is_empty() || !partition.static_columns.is_empty()
+    }
 }
 
 /// 컴팩션 매니저
 pub struct CompactionManager {
     config: CompactionConfig,
-    pending_tasks: Arc<RwLock<HashMap<String, Vec<CompactionTask>>>>,
+    /// 요청된 뒤 아직 실행을 시작하지 않은 테이블 ("keyspace.table", 같은 테이블 요청은 하나로 합침)
+    queued: Arc<Mutex<HashSet<String>>>,
+    /// 동시에 실행할 수 있는 컴팩션 수 (max_concurrent_compactions)
+    workers: Arc<Semaphore>,
+    /// 테이블별 실행 잠금 (같은 테이블의 컴팩션은 한 번에 하나만 실행)
+    table_locks: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
     task_sender: mpsc::UnboundedSender<CompactionTask>,
     task_receiver: Arc<RwLock<Option<mpsc::UnboundedReceiver<CompactionTask>>>>,
+    /// 컴팩션 읽기/쓰기 속도 제한 (실행 중에 바꿀 수 있음)
+    throttle: RateLimiter,
+    /// 실행 중인 컴팩션 ("keyspace.table")
+    running: Mutex<HashMap<String, Arc<CompactionProgress>>>,
+    /// 끝난 컴팩션 누적 통계
+    totals: Mutex<CompactionTotals>,
+    /// 끝난 컴팩션의 실행 시간 분포
+    duration: LatencyHistogram,
+}
+
+/// 끝난 컴팩션의 누적 통계
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CompactionTotals {
+    pub completed: u64,
+    /// 읽은 입력 SSTable 디스크 크기
+    pub bytes_compacted: u64,
+    /// 쓴 결과 SSTable 디스크 크기
+    pub bytes_written: u64,
+    pub cells_written: u64,
+    /// 가려지거나 지워져 결과에 쓰지 않은 셀
+    pub cells_dropped: u64,
 }
 
 /// 컴팩션 설정
@@ -50,6 +403,8 @@ pub struct CompactionManager {
 pub struct CompactionConfig {
     pub throughput_mb_per_sec: u64,
     pub max_concurrent_compactions: usize,
+    /// 결과 데이터 파일 쓰기 방식
+    pub write_mode: DiskWriteMode,
     pub strategy: CompactionStrategy,
     pub data_directory: PathBuf,
 }
@@ -59,121 +414,381 @@ impl CompactionManager {
         let (sender, receiver) = mpsc::unbounded_channel();
         
         Self {
-            pending_tasks: Arc::new(RwLock::new(HashMap::new())),
+            queued: Arc::new(Mutex::new(HashSet::new())),
+            workers: Arc::new(Semaphore::new(config.max_concurrent_compactions.max(1))),
+            table_locks: Mutex::new(HashMap::new()),
             task_sender: sender,
             task_receiver: Arc::new(RwLock::new(Some(receiver))),
+            throttle: RateLimiter::per_second_mb(config.throughput_mb_per_sec),
+            running: Mutex::new(HashMap::new()),
+            totals: Mutex::new(CompactionTotals::default()),
+            duration: LatencyHistogram::default(),
             config,
         }
     }
     
-    /// 컴팩션 작업 스케줄링
+    /// 테이블 컴팩션 요청 (입력 SSTable은 루프의 처리기가 실행 시점의 목록에서 고름)
+    ///
+    /// 같은 테이블의 요청이 아직 대기 중이면 하나로 합침 (실행 중이면 끝난 뒤 다시 실행)
     pub async fn schedule_compaction(&self, keyspace: &str, table: &str) {
-        let table_key = format!("{}.{}", keyspace, table);
-        
-        // TODO: 실제로는 SSTable 리스트를 받아서 컴팩션 전략에 따라 작업 생성
+        if !self.queued.lock().unwrap().insert(format!("{}.{}", keyspace, table)) {
+            return;
+        }
         let task = CompactionTask {
             keyspace: keyspace.to_string(),
             table: table.to_string(),
-            input_sstables: vec![], // 실제 구현에서는 SSTable 리스트를 전달받아야 함
-            output_sstable: None,
+            input_sstables: vec![],
             strategy: self.config.strategy.clone(),
+            major: false,
+            progress: Arc::new(CompactionProgress::new(keyspace, table, false)),
+            // 입력과 함께 처리기가 테이블 옵션으로 채움
+            compression: CompressionType::LZ4,
+            level: 0,
+            tombstone_gc: None,
         };
         
         let _ = self.task_sender.send(task);
     }
     
-    /// 컴팩션 루프 실행
-    pub async fn run_compaction_loop(&self) {
+    /// 컴팩션 루프 실행 (요청마다 작업을 띄워 처리기를 호출)
+    ///
+    /// 입력 선택과 결과 교체는 테이블 목록을 가진 쪽이 하므로 처리기로 받음. 서로 다른 테이블은
+    /// max_concurrent_compactions개까지 동시에 실행하고, 같은 테이블은 앞의 컴팩션이 끝날 때까지
+    /// 기다림 (기다리는 동안은 실행 슬롯을 차지하지 않음)
+    pub async fn run_compaction_loop<F, Fut>(&self, handler: F)
+    where
+        F: Fn(CompactionTask) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
         let mut receiver = self.task_receiver.write().await.take()
             .expect("Compaction receiver already taken");
+        let handler = Arc::new(handler);
         
         while let Some(task) = receiver.recv().await {
-            if let Err(e) = self.execute_compaction(task).await {
-                eprintln!("Compaction failed: {:?}", e);
-            }
+            let key = format!("{}.{}", task.keyspace, task.table);
+            let table_lock = self.table_lock(&task.keyspace, &task.table);
+            let (queued, workers, handler) = (self.queued.clone(), self.workers.clone(), handler.clone());
+            tokio::spawn(async move {
+                let _table = table_lock.lock_owned().await;
+                let Ok(_worker) = workers.acquire().await else { return };
+                // 실행을 시작하면 다시 요청할 수 있음 (그 사이 생긴 SSTable은 다음 실행에서 고름)
+                queued.lock().unwrap().remove(&key);
+                if let Err(e) = handler(task).await {
+                    tracing::error!("Compaction of {} failed: {}", key, e);
+                }
+            });
         }
     }
     
-    /// 컴팩션 실행
-    async fn execute_compaction(&self, task: CompactionTask) -> Result<()> {
-        match task.strategy {
-            CompactionStrategy::SizeTiered { .. } => {
+    /// 테이블의 실행 잠금 (쥐고 있는 동안 같은 테이블의 다른 컴팩션은 기다림)
+    pub fn table_lock(&self, keyspace: &str, table: &str) -> Arc<tokio::sync::Mutex<()>> {
+        self.table_locks.lock().unwrap().entry(format!("{}.{}", keyspace, table)).or_default().clone()
+    }
+    
+    /// 실행 중인 컴팩션의 진행 상황
+    pub fn running_compactions(&self) -> Vec<CompactionInfo> {
+        let mut running: Vec<_> = self.running.lock().unwrap().values().map(|progress| progress.info()).collect();
+        running.sort_by(|a, b| (&a.keyspace, &a.table).cmp(&(&b.keyspace, &b.table)));
+        running
+    }
+    
+    /// 테이블에서 실행 중인 컴팩션 취소 (실행 중인 것이 없으면 false)
+    pub fn cancel_compaction(&self, keyspace: &str, table: &str) -> bool {
+        match self.running.lock().unwrap().get(&format!("{}.{}", keyspace, table)) {
+            Some(progress) => {
+                progress.cancel();
+                true
+            },
+            None => false,
+        }
+    }
+    
+    /// 테이블에 적용할 전략 (테이블 옵션과 설정의 매개변수)
+    pub fn strategy_for(&self, table_strategy: &crate::schema::CompactionStrategy) -> CompactionStrategy {
+        CompactionStrategy::for_table(table_strategy, &self.config.strategy)
+    }
+    
+    /// 컴팩션 처리량 한도 (MB/s, 0이면 제한 없음)
+    pub fn throughput_mb_per_sec(&self) -> u64 {
+        self.throttle.bytes_per_sec() / (1024 * 1024)
+    }
+    
+    /// 컴팩션 처리량 한도 변경 (진행 중인 컴팩션에도 바로 적용)
+    pub fn set_throughput_mb_per_sec(&self, mb_per_sec: u64) {
+        self.throttle.set_bytes_per_sec(mb_per_sec.saturating_mul(1024 * 1024));
+    }
+    
+    /// 컴팩션 실행 (입력 SSTable을 병합한 새 SSTable들 반환, 입력은 그대로 둠)
+    ///
+    /// 실행하는 동안 진행 상황을 running_compactions로 볼 수 있고, 취소되면 CompactionError
+    pub async fn execute_compaction(&self, task: &CompactionTask) -> Result<Vec<SSTable>> {
+        let key = format!("{}.{}", task.keyspace, task.table);
+        let started = std::time::Instant::now();
+        self.running.lock().unwrap().insert(key.clone(), task.progress.clone());
+        let result = match task.strategy {
+            // 메이저 컴팩션과 시간 창 안의 병합은 Size-Tiered와 같음 (결과는 SSTable 하나)
+            _ if task.major => self.execute_size_tiered_compaction(task).await,
+            CompactionStrategy::SizeTiered { .. } | CompactionStrategy::TimeWindow { .. } => {
                 self.execute_size_tiered_compaction(task).await
             },
             CompactionStrategy::Leveled { .. } => {
                 self.execute_leveled_compaction(task).await
             },
+        };
+        self.running.lock().unwrap().remove(&key);
+        if let Ok(outputs) = &result {
+            self.duration.record(started.elapsed());
+            let cells_read: u64 = task.input_sstables.iter().map(|sstable| sstable.statistics.cell_count).sum();
+            let cells_written: u64 = outputs.iter().map(|sstable| sstable.statistics.cell_count).sum();
+            let mut totals = self.totals.lock().unwrap();
+            totals.completed += 1;
+            totals.bytes_compacted += task.input_sstables.iter().map(|sstable| sstable.size_bytes).sum::<u64>();
+            totals.bytes_written += outputs.iter().map(|sstable| sstable.size_bytes).sum::<u64>();
+            totals.cells_written += cells_written;
+            totals.cells_dropped += cells_read.saturating_sub(cells_written);
         }
+        result
     }
     
-    /// Size-Tiered 컴팩션 실행
-    async fn execute_size_tiered_compaction(&self, task: CompactionTask) -> Result<()> {
-        if task.input_sstables.is_empty() {
-            return Ok(());
-        }
-        
-        // 모든 입력 SSTable의 데이터를 읽어서 병합
-        let mut merged_data = HashMap::new();
-        
-        for sstable in &task.input_sstables {
-            // SSTable의 모든 파티션을 읽어서 병합
-            // 실제 구현에서는 더 효율적인 방법을 사용해야 함
-            for (partition_key, _) in &sstable.partition_index {
-                if let Some(partition) = sstable.read_partition(partition_key).await? {
-                    // 파티션 병합 로직
-                    // 최신 타임스탬프의 데이터를 우선시
-                    merged_data.insert(partition_key.clone(), partition);
-                }
-            }
-        }
-        
-        // 병합된 데이터로 새 SSTable 생성
-        // TODO: 실제 구현에서는 Memtable을 거쳐서 SSTable 생성
-        
-        // 기존 SSTable들 삭제
-        for sstable in &task.input_sstables {
-            sstable.delete().await?;
-        }
-        
-        Ok(())
+    /// Size-Tiered 컴팩션 실행 (결과는 SSTable 하나)
+    async fn execute_size_tiered_compaction(&self, task: &CompactionTask) -> Result<Vec<SSTable>> {
+        let output_dir = self.config.data_directory.join(&task.keyspace).join(&task.table);
+        merge_sstables(&task.input_sstables, &output_dir, task.compression, u64::MAX, self.config.write_mode, task.tombstone_gc.as_ref(), &self.throttle, &task.progress).await
     }
     
     /// Leveled 컴팩션 실행
-    async fn execute_leveled_compaction(&self, task: CompactionTask) -> Result<()> {
-        // Leveled 컴팩션은 레벨별로 SSTable을 관리
-        // 각 레벨의 SSTable 크기가 일정 비율로 증가
-        // L0 -> L1 -> L2 ... 순서로 컴팩션
-        
-        // TODO: 실제 구현에서는 레벨별 SSTable 관리 구조가 필요
-        self.execute_size_tiered_compaction(task).await
+    ///
+    /// 입력은 레벨 L의 SSTable과 키 범위가 겹치는 L+1의 SSTable이고, 결과는 키 범위가 겹치지 않도록
+    /// sstable_size_mb마다 나눈 L+1의 SSTable들
+    async fn execute_leveled_compaction(&self, task: &CompactionTask) -> Result<Vec<SSTable>> {
+        let output_dir = self.config.data_directory.join(&task.keyspace).join(&task.table);
+        merge_sstables(&task.input_sstables, &output_dir, task.compression, task.strategy.max_sstable_bytes(), self.config.write_mode, task.tombstone_gc.as_ref(), &self.throttle, &task.progress).await
     }
     
     /// 컴팩션 통계
-    pub async fn get_compaction_stats(&self) -> CompactionStats {
-        let pending = self.pending_tasks.read().await;
-        let total_pending = pending.values().map(|tasks| tasks.len()).sum();
-        
+    ///
+    /// pending_by_table은 SSTable 목록을 가진 쪽이 테이블마다 estimated_tasks로 계산해 넘김
+    pub async fn get_compaction_stats(&self, pending_by_table: BTreeMap<String, usize>) -> CompactionStats {
+        let max_concurrent = self.config.max_concurrent_compactions.max(1);
         CompactionStats {
-            pending_tasks: total_pending,
-            throughput_mb_per_sec: self.config.throughput_mb_per_sec,
+            pending_tasks: pending_by_table.values().sum(),
+            pending_by_table,
+            queued_tables: self.queued.lock().unwrap().len(),
+            active_compactions: max_concurrent - self.workers.available_permits(),
+            max_concurrent_compactions: max_concurrent,
+            running: self.running_compactions(),
+            totals: *self.totals.lock().unwrap(),
+            duration: self.duration.snapshot(),
+            throughput_mb_per_sec: self.throughput_mb_per_sec(),
             strategy: self.config.strategy.clone(),
         }
     }
 }
 
+/// 입력 SSTable들을 파티션 키 순서로 k-way 병합해 새 SSTable로 씀
+///
+/// 입력마다 스캐너를 열고 각각의 다음 파티션 하나씩만 들고 있으므로 메모리는 입력 수에 비례.
+/// 여러 입력에 있는 파티션은 셀 단위로 병합 (타임스탬프 → 툼스톤 → 값 순으로 승자 결정).
+/// 압축 전 크기가 max_sstable_bytes를 넘으면 파티션 경계에서 다음 SSTable로 넘어가므로 결과끼리는
+/// 키 범위가 겹치지 않음. 데이터 파일은 write_mode로 씀 (Direct면 페이지 캐시를 우회).
+/// 읽은 파티션(입력의 파티션당 평균 디스크 크기)과 쓴 바이트만큼 throttle의 토큰을 씀.
+/// gc가 있으면 병합한 파티션에서 지울 수 있는 툼스톤과 만료된 셀을 지우고 쓰며, 남은 것이 없는
+/// 파티션은 쓰지 않음 (결과가 없으면 빈 목록). 병합한 입력 파티션 수를 progress에 기록하고,
+/// 취소되면 그때까지 쓴 SSTable을 모두 지우고 CompactionError를 반환
+#[allow(clippy::too_many_arguments)]
+pub async fn merge_sstables(inputs: &[Arc<SSTable>], output_dir: &Path, compression: CompressionType, max_sstable_bytes: u64, write_mode: DiskWriteMode, gc: Option<&TombstoneGc>, throttle: &RateLimiter, progress: &CompactionProgress) -> Result<Vec<SSTable>> {
+    let expected_partitions = inputs.iter().map(|sstable| sstable.partition_count).sum();
+    progress.total_partitions.store(expected_partitions, Ordering::Relaxed);
+    progress.total_bytes.store(inputs.iter().map(|sstable| sstable.size_bytes).sum(), Ordering::Relaxed);
+    let mut outputs: Vec<SSTable> = Vec::new();
+    let mut writer: Option<SSTableWriter> = None;
+    let mut scanners: Vec<_> = inputs.iter().map(|sstable| sstable.scanner(ReadOptions::all())).collect();
+    let mut heads: Vec<Option<Partition>> = inputs.iter().map(|_| None).collect();
+    let partition_bytes: Vec<u64> = inputs.iter()
+        .map(|sstable| sstable.size_bytes / sstable.partition_count.max(1))
+        .collect();
+    
+    // (다음 파티션 키, 입력 번호)의 최소 힙
+    let mut heap = BinaryHeap::new();
+    for (i, scanner) in scanners.iter_mut().enumerate() {
+        if let Some((partition_key, partition)) = scanner.next_partition().await? {
+            throttle.acquire(partition_bytes[i]).await;
+            heads[i] = Some(partition);
+            heap.push(Reverse((partition_key, i)));
+        }
+    }
+    
+    while let Some(Reverse((partition_key, first))) = heap.pop() {
+        if progress.is_cancelled() {
+            if let Some(writer) = writer {
+                writer.abort().await?;
+            }
+            for output in &outputs {
+                output.delete().await?;
+            }
+            return Err(CoreDBError::CompactionError { message: format!("Compaction of {}.{} cancelled", progress.keyspace, progress.table) });
+        }
+        let mut merged = heads[first].take().expect("head of every queued input");
+        let mut sources = vec![first];
+        while heap.peek().is_some_and(|Reverse((next_key, _))| *next_key == partition_key) {
+            let Reverse((_, i)) = heap.pop().expect("peeked");
+            merged.merge(heads[i].take().expect("head of every queued input"));
+            sources.push(i);
+        }
+        progress.merged_partitions.fetch_add(sources.len() as u64, Ordering::Relaxed);
+        progress.compacted_bytes.fetch_add(sources.iter().map(|&i| partition_bytes[i]).sum(), Ordering::Relaxed);
+        let live = gc.is_none_or(|gc| gc.purge(&partition_key, &mut merged));
+        if live {
+            let current = match writer.as_mut() {
+                Some(current) => current,
+                None => writer.insert(SSTableWriter::with_write_mode(output_dir, compression, expected_partitions, write_mode).await?),
+            };
+            let written = current.data_length();
+            current.append(&partition_key, &merged).await?;
+            throttle.acquire(current.data_length() - written).await;
+            if current.data_length() >= max_sstable_bytes {
+                outputs.push(writer.take().expect("current writer").finish().await?);
+            }
+        }
+        
+        for i in sources {
+            if let Some((next_key, partition)) = scanners[i].next_partition().await? {
+                throttle.acquire(partition_bytes[i]).await;
+                heads[i] = Some(partition);
+                heap.push(Reverse((next_key, i)));
+            }
+        }
+    }
+    if let Some(writer) = writer {
+        outputs.push(writer.finish().await?);
+    }
+    // 파티션당 평균 크기로 더한 추정치의 나머지를 맞춤
+    progress.compacted_bytes.store(progress.total_bytes.load(Ordering::Relaxed), Ordering::Relaxed);
+    Ok(outputs)
+}
+
+/// 테이블 디렉토리의 SSTable을 모두 하나로 합침 (`coredb compact`, 서버를 멈춘 상태에서 실행, 합친 SSTable 수 반환)
+///
+/// 스키마 없이 매니페스트만 보고 합치므로 gc_grace_seconds를 몰라 툼스톤은 그대로 남김.
+/// 결과는 입력 중 가장 높은 레벨에 두고 입력 중 가장 큰 SSTable의 압축 방식을 씀
+pub async fn compact_directory(directory: &Path, progress: &CompactionProgress) -> Result<usize> {
+    let (mut manifest, sstables) = Manifest::open_table(directory).await?;
+    if sstables.len() < 2 {
+        return Ok(0);
+    }
+    let sstables: Vec<_> = sstables.into_iter().map(Arc::new).collect();
+    let level = sstables.iter().filter_map(|sstable| manifest.level(&sstable.id)).max().unwrap_or(0);
+    let compression = sstables.iter().max_by_key(|sstable| sstable.size_bytes).map_or(CompressionType::LZ4, |sstable| sstable.compression);
+    let outputs = merge_sstables(&sstables, directory, compression, u64::MAX, DiskWriteMode::Buffered, None, &RateLimiter::new(0), progress).await?;
+    let ids: Vec<String> = sstables.iter().map(|sstable| sstable.id.clone()).collect();
+    manifest.record_compaction(&ids, &outputs, level).await?;
+    for sstable in &sstables {
+        sstable.delete().await?;
+    }
+    Ok(sstables.len())
+}
+
+/// 큰 SSTable 하나를 압축 전 크기가 비슷한 count개로 나눔 (`coredb sstable split`, 서버를 멈춘 상태에서 실행)
+///
+/// 파티션 경계에서만 나누므로 파티션이 적거나 크기가 치우치면 count개보다 적게 나올 수 있음.
+/// 매니페스트가 있는 테이블 디렉토리면 결과를 원본과 같은 레벨에 기록하고, 원본은 지움 (결과 반환)
+pub async fn split_sstable(sstable: &Arc<SSTable>, count: usize) -> Result<Vec<SSTable>> {
+    if count < 2 {
+        return Err(CoreDBError::Generic { message: format!("Cannot split SSTable {} into {} parts", sstable.id, count) });
+    }
+    if sstable.partition_count < 2 {
+        return Err(CoreDBError::Generic { message: format!("SSTable {} has only {} partition(s)", sstable.id, sstable.partition_count) });
+    }
+    let directory = sstable.file_path.parent().unwrap_or(Path::new("."));
+    let mut manifest = Manifest::load(directory).await?;
+    if manifest.as_ref().is_some_and(|manifest| !manifest.contains(&sstable.id)) {
+        return Err(CoreDBError::Generic { message: format!("SSTable {} is not in the manifest of {}", sstable.id, directory.display()) });
+    }
+    
+    let name = |path: Option<&Path>| path.and_then(|path| path.file_name()).map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+    let progress = CompactionProgress::new(&name(directory.parent()), &name(Some(directory)), false);
+    let max_sstable_bytes = sstable.statistics.uncompressed_bytes.div_ceil(count as u64).max(1);
+    let outputs = merge_sstables(std::slice::from_ref(sstable), directory, sstable.compression, max_sstable_bytes, DiskWriteMode::Buffered, None, &RateLimiter::new(0), &progress).await?;
+    if let Some(manifest) = manifest.as_mut() {
+        let level = manifest.level(&sstable.id).unwrap_or(0);
+        manifest.record_compaction(std::slice::from_ref(&sstable.id), &outputs, level).await?;
+    }
+    sstable.delete().await?;
+    Ok(outputs)
+}
+
+/// 다시 쓰지 않고 통째로 지울 수 있는 SSTable
+///
+/// 모든 데이터가 만료되고 gc_grace_seconds가 지났으며, 가장 큰 타임스탬프가 나머지 SSTable과
+/// 메모리 테이블의 가장 작은 타임스탬프보다 작아 툼스톤이 가리는 데이터가 남아 있지 않은 것만 고름
+pub fn fully_expired_sstables(sstables: &[Arc<SSTable>], min_memtable_timestamp: i64, gc_grace_seconds: u32, now: i64) -> Vec<Arc<SSTable>> {
+    let (candidates, live): (Vec<_>, Vec<_>) = sstables.iter()
+        .partition(|sstable| sstable.statistics.is_fully_expired(gc_grace_seconds, now));
+    let min_live_timestamp = live.iter()
+        .map(|sstable| sstable.min_timestamp)
+        .fold(min_memtable_timestamp, i64::min);
+    candidates.into_iter()
+        .filter(|sstable| sstable.max_timestamp < min_live_timestamp)
+        .cloned()
+        .collect()
+}
+
+/// 툼스톤 비율이 threshold를 넘어 혼자라도 다시 쓸 SSTable과 그 레벨 (비율이 가장 높은 것)
+///
+/// gc_grace_seconds가 아직 안 지났거나 다른 SSTable이 가리는 데이터가 남아 툼스톤을 지우지 못한
+/// 결과를 계속 다시 쓰지 않도록, 쓰인 지 min_age가 지난 파일만 고름
+pub fn tombstone_compaction_candidate(sstables: &[(Arc<SSTable>, u32)], threshold: f64, min_age: Duration) -> Option<(Arc<SSTable>, u32)> {
+    sstables.iter()
+        .filter(|(sstable, _)| sstable.statistics.tombstone_ratio() > threshold)
+        .filter(|(sstable, _)| std::fs::metadata(&sstable.file_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age >= min_age))
+        .max_by(|(a, _), (b, _)| a.statistics.tombstone_ratio().total_cmp(&b.statistics.tombstone_ratio()))
+        .cloned()
+}
+
 /// 컴팩션 통계
 #[derive(Debug)]
 pub struct CompactionStats {
+    /// 모든 테이블을 전략대로 정리하는 데 남은 컴팩션 수 (추정)
     pub pending_tasks: usize,
+    /// 테이블("keyspace.table")별 남은 컴팩션 수 (추정, 남은 것이 있는 테이블만)
+    pub pending_by_table: BTreeMap<String, usize>,
+    /// 실행을 기다리는 컴팩션 요청 수 (같은 테이블 요청은 하나로 합쳐짐)
+    pub queued_tables: usize,
+    pub active_compactions: usize,
+    pub max_concurrent_compactions: usize,
+    /// 실행 중인 컴팩션의 진행 상황
+    pub running: Vec<CompactionInfo>,
+    pub totals: CompactionTotals,
+    /// 끝난 컴팩션의 실행 시간 분포
+    pub duration: LatencySnapshot,
     pub throughput_mb_per_sec: u64,
     pub strategy: CompactionStrategy,
 }
 
-/// SSTable 레벨 관리
+/// SSTable 레벨 관리 (Leveled 컴팩션)
+///
+/// L0은 플러시 결과라 키 범위가 겹칠 수 있고, L1부터는 레벨 안의 SSTable끼리 키 범위가 겹치지 않음.
+/// 레벨 크기가 목표를 넘으면 SSTable 하나와 키 범위가 겹치는 다음 레벨 SSTable을 함께 다음 레벨로 병합
 pub struct LevelManager {
     levels: Vec<Vec<Arc<SSTable>>>,
     max_levels: usize,
     level_size_multiplier: f64,
+    /// 레벨 목표 크기의 단위가 되는 SSTable 크기
+    sstable_size_bytes: u64,
+}
+
+/// 레벨별 통계
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelStats {
+    pub level: usize,
+    pub sstable_count: usize,
+    pub size_bytes: u64,
+    /// 넘으면 다음 레벨로 컴팩션하는 크기 (L0은 SSTable 수 임계값 기준)
+    pub target_size_bytes: u64,
 }
 
 impl LevelManager {
@@ -182,27 +797,81 @@ impl LevelManager {
             levels: vec![Vec::new(); max_levels],
             max_levels,
             level_size_multiplier,
+            sstable_size_bytes: DEFAULT_LEVELED_SSTABLE_SIZE_MB * 1024 * 1024,
         }
     }
     
-    /// SSTable 추가
+    /// 목표 SSTable 크기 지정
+    pub fn with_sstable_size(mut self, sstable_size_bytes: u64) -> Self {
+        self.sstable_size_bytes = sstable_size_bytes;
+        self
+    }
+    
+    /// SSTable 추가 (마지막 레벨보다 깊으면 마지막 레벨에, L1부터는 첫 키 순서로)
     pub fn add_sstable(&mut self, sstable: Arc<SSTable>, level: usize) {
-        if level < self.max_levels {
-            self.levels[level].push(sstable);
+        let Some(last_level) = self.max_levels.checked_sub(1) else { return };
+        let level = level.min(last_level);
+        let sstables = &mut self.levels[level];
+        if level == 0 {
+            sstables.push(sstable);
+        } else {
+            let first_key = |sstable: &Arc<SSTable>| sstable.key_range().map(|(first, _)| first.clone());
+            let position = sstables.partition_point(|existing| first_key(existing) <= first_key(&sstable));
+            sstables.insert(position, sstable);
         }
     }
     
-    /// 컴팩션이 필요한지 확인
+    /// 컴팩션이 필요한지 확인 (입력 레벨과, 그 레벨의 입력 및 키 범위가 겹치는 다음 레벨 SSTable)
+    ///
+    /// L0은 SSTable 수가 임계값 이상이면 오래된 것부터 최대 32개를, L1부터는 목표 크기를 넘으면
+    /// 가장 오래된 SSTable 하나를 고름. 마지막 레벨은 더 내려갈 곳이 없으므로 고르지 않음
     pub fn needs_compaction(&self) -> Option<(usize, Vec<Arc<SSTable>>)> {
-        for (level, sstables) in self.levels.iter().enumerate() {
-            if sstables.len() >= self.get_threshold_for_level(level) {
-                return Some((level, sstables.clone()));
+        for level in 0..self.max_levels.saturating_sub(1) {
+            let mut inputs = self.levels[level].clone();
+            inputs.sort_by_key(|sstable| sstable.max_timestamp);
+            if level == 0 {
+                if inputs.len() < self.get_threshold_for_level(0) {
+                    continue;
+                }
+                inputs.truncate(MAX_LEVEL0_COMPACTION_INPUTS);
+            } else {
+                if self.level_size_bytes(level) <= self.target_size_bytes(level) {
+                    continue;
+                }
+                inputs.truncate(1);
+            }
+            
+            // 입력 전체의 키 범위와 겹치는 다음 레벨 SSTable
+            let ranges: Vec<_> = inputs.iter().filter_map(|sstable| sstable.key_range()).collect();
+            if let (Some(first), Some(last)) = (ranges.iter().map(|(first, _)| *first).min(), ranges.iter().map(|(_, last)| *last).max()) {
+                let overlapping: Vec<_> = self.levels[level + 1].iter()
+                    .filter(|sstable| sstable.overlaps(first, last))
+                    .cloned()
+                    .collect();
+                inputs.extend(overlapping);
             }
+            return Some((level, inputs));
         }
         None
     }
     
-    /// 레벨별 임계값 계산
+    /// 모든 레벨을 목표 크기 안으로 되돌리는 데 필요한 컴팩션 수 추정
+    ///
+    /// L0은 한 번에 32개까지 합치고, L1부터는 목표를 넘은 크기를 SSTable 하나씩 내려보냄
+    pub fn estimated_tasks(&self) -> usize {
+        let level0 = self.levels.first().map_or(0, Vec::len);
+        let mut tasks = match level0 >= self.get_threshold_for_level(0) {
+            true => level0.div_ceil(MAX_LEVEL0_COMPACTION_INPUTS),
+            false => 0,
+        };
+        for level in 1..self.max_levels.saturating_sub(1) {
+            let excess = self.level_size_bytes(level).saturating_sub(self.target_size_bytes(level));
+            tasks += excess.div_ceil(self.sstable_size_bytes.max(1)) as usize;
+        }
+        tasks
+    }
+    
+    /// 레벨별 임계값 계산 (SSTable 수)
     fn get_threshold_for_level(&self, level: usize) -> usize {
         if level == 0 {
             4 // L0은 4개
@@ -211,25 +880,363 @@ impl LevelManager {
         }
     }
     
-    /// 컴팩션 후 레벨 업데이트
-    pub fn update_after_compaction(&mut self, level: usize, input_sstables: &[Arc<SSTable>], output_sstable: Arc<SSTable>) {
-        // 입력 SSTable들 제거
-        self.levels[level].retain(|sstable| !input_sstables.contains(sstable));
-        
-        // 출력 SSTable을 다음 레벨에 추가
-        if level + 1 < self.max_levels {
-            self.levels[level + 1].push(output_sstable);
+    /// 레벨 목표 크기 (임계값만큼의 SSTable)
+    pub fn target_size_bytes(&self, level: usize) -> u64 {
+        (self.get_threshold_for_level(level) as u64).saturating_mul(self.sstable_size_bytes)
+    }
+    
+    pub fn level_size_bytes(&self, level: usize) -> u64 {
+        self.levels.get(level).map_or(0, |sstables| sstables.iter().map(|sstable| sstable.size_bytes).sum())
+    }
+    
+    /// 컴팩션 후 레벨 업데이트 (입력은 level과 level + 1에서 빼고 출력은 level + 1에 추가)
+    pub fn update_after_compaction(&mut self, level: usize, input_sstables: &[Arc<SSTable>], output_sstables: Vec<Arc<SSTable>>) {
+        for sstables in self.levels.iter_mut().skip(level).take(2) {
+            sstables.retain(|sstable| !input_sstables.contains(sstable));
+        }
+        for sstable in output_sstables {
+            self.add_sstable(sstable, level + 1);
         }
     }
+    
+    /// 레벨별 SSTable 수와 크기
+    pub fn level_stats(&self) -> Vec<LevelStats> {
+        self.levels.iter().enumerate()
+            .map(|(level, sstables)| LevelStats {
+                level,
+                sstable_count: sstables.len(),
+                size_bytes: self.level_size_bytes(level),
+                target_size_bytes: self.target_size_bytes(level),
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::schema::{CassandraDataType, CassandraValue, Cell, Cells, ClusteringKey, ColumnDefinition, PartitionKey, Row, TableSchema};
+    use crate::storage::Memtable;
+    
+    fn test_schema() -> Arc<TableSchema> {
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        Arc::new(TableSchema::new(
+            "events".to_string(),
+            "test_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![column("seq", CassandraDataType::BigInt)],
+            vec![column("a", CassandraDataType::Text), column("b", CassandraDataType::Text)],
+            vec![],
+        ))
+    }
+    
+    /// (파티션, 행, 컬럼, 값, 타임스탬프, 툼스톤 여부) 목록으로 SSTable 하나 생성
+    async fn write_sstable(dir: &Path, cells: &[(i32, i64, &str, &str, i64, bool)]) -> Arc<SSTable> {
+        let memtable = Memtable::new(test_schema());
+        for &(id, seq, column, value, timestamp, is_deleted) in cells {
+            let mut row_cells = Cells::new();
+            row_cells.insert(column, Cell { value: CassandraValue::Text(value.to_string()), timestamp, ttl: None, is_deleted });
+            memtable.put(Row {
+                partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] },
+                clustering_key: Some(ClusteringKey { components: vec![CassandraValue::BigInt(seq)] }),
+                cells: row_cells,
+                timestamp,
+            }).unwrap();
+        }
+        Arc::new(SSTable::create_from_memtable(&memtable, dir, CompressionType::LZ4).await.unwrap())
+    }
+    
+    #[tokio::test]
+    async fn test_merge_sstables_reconciles_cells_in_key_order() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_compaction_merge");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let inputs = vec![
+            write_sstable(&temp_dir, &[(1, 1, "a", "old", 10, false), (2, 1, "a", "two", 10, false), (4, 1, "a", "deleted", 10, false)]).await,
+            write_sstable(&temp_dir, &[(1, 1, "a", "new", 20, false), (1, 1, "b", "other", 5, false), (3, 1, "a", "three", 10, false)]).await,
+            write_sstable(&temp_dir, &[(1, 2, "a", "row", 1, false), (4, 1, "a", "", 30, true), (5, 1, "a", "five", 10, false)]).await,
+        ];
+        let mut outputs = merge_sstables(&inputs, &temp_dir, CompressionType::LZ4, u64::MAX, DiskWriteMode::Buffered, None, &RateLimiter::new(0), &CompactionProgress::new("ks", "t", false)).await.unwrap();
+        assert_eq!(outputs.len(), 1);
+        let merged = Arc::new(outputs.remove(0));
+        assert_eq!(merged.partition_count, 5);
+        assert_eq!(merged.statistics.row_count, 6);
+        assert_eq!((merged.min_timestamp, merged.max_timestamp), (1, 30));
+        
+        let mut scanner = merged.scanner(ReadOptions::all());
+        let mut keys = Vec::new();
+        while let Some((partition_key, partition)) = scanner.next_partition().await.unwrap() {
+            keys.push(partition_key.components[0].clone());
+            if partition_key.components[0] == CassandraValue::Int(1) {
+                let rows: Vec<_> = partition.rows.iter().map(|row| row.value().clone()).collect();
+                assert_eq!(rows.len(), 2);
+                // 같은 행의 셀은 타임스탬프가 큰 쪽이 이기고 다른 컬럼은 합쳐짐
+                assert_eq!(rows[0].cells["a"].value, CassandraValue::Text("new".to_string()));
+                assert_eq!(rows[0].cells["b"].value, CassandraValue::Text("other".to_string()));
+                assert_eq!(rows[1].cells["a"].value, CassandraValue::Text("row".to_string()));
+            }
+            if partition_key.components[0] == CassandraValue::Int(4) {
+                // 툼스톤은 gc_grace_seconds 전이라 그대로 남아 더 오래된 값을 가림
+                assert!(partition.rows.front().unwrap().value().cells["a"].is_deleted);
+            }
+        }
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+        
+        // 크기 제한이 있으면 파티션 경계에서 나눠 키 범위가 겹치지 않는 SSTable들로 씀
+        let outputs = merge_sstables(&inputs, &temp_dir, CompressionType::LZ4, 1, DiskWriteMode::Direct, None, &RateLimiter::new(0), &CompactionProgress::new("ks", "t", false)).await.unwrap();
+        assert_eq!(outputs.len(), 5);
+        for pair in outputs.windows(2) {
+            let (_, last) = pair[0].key_range().unwrap();
+            let (first, _) = pair[1].key_range().unwrap();
+            assert!(last < first);
+        }
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_merge_purges_tombstones_past_gc_grace() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_compaction_tombstone_gc");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let now = chrono::Utc::now().timestamp();
+        let old = (now - 7200) * 1_000_000;
+        let recent = now * 1_000_000;
+        let inputs = vec![
+            write_sstable(&temp_dir, &[(1, 1, "a", "x", old, false), (2, 1, "a", "y", old, false), (2, 1, "b", "z", old, false)]).await,
+            write_sstable(&temp_dir, &[(1, 1, "a", "", old + 1, true), (2, 1, "b", "", old + 1, true), (3, 1, "a", "", old + 1, true), (4, 1, "a", "", recent, true)]).await,
+        ];
+        // 컴팩션 밖의 SSTable에 파티션 3의 더 오래된 데이터가 있음
+        let other = write_sstable(&temp_dir, &[(3, 1, "a", "older", old - 1, false)]).await;
+        let gc = TombstoneGc { gc_before: now - 3600, other_sstables: vec![other], min_memtable_timestamp: i64::MAX };
+        let mut outputs = merge_sstables(&inputs, &temp_dir, CompressionType::LZ4, u64::MAX, DiskWriteMode::Buffered, Some(&gc), &RateLimiter::new(0), &CompactionProgress::new("ks", "t", false)).await.unwrap();
+        assert_eq!(outputs.len(), 1);
+        let merged = Arc::new(outputs.remove(0));
+        
+        let mut scanner = merged.scanner(ReadOptions::all());
+        let mut partitions = Vec::new();
+        while let Some((partition_key, partition)) = scanner.next_partition().await.unwrap() {
+            let row = partition.rows.front().unwrap().value().clone();
+            partitions.push((partition_key.components[0].clone(), row.cells.keys().map(str::to_string).collect::<Vec<_>>(), row.cells["a"].is_deleted));
+        }
+        // 1: 툼스톤과 가려진 값을 모두 지움, 2: 툼스톤만 지움, 3: 밖의 데이터를 가리므로 남김, 4: gc_grace 전이라 남김
+        assert_eq!(partitions, vec![
+            (CassandraValue::Int(2), vec!["a".to_string()], false),
+            (CassandraValue::Int(3), vec!["a".to_string()], true),
+            (CassandraValue::Int(4), vec!["a".to_string()], true),
+        ]);
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_compact_directory_merges_everything_and_can_be_cancelled() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_compaction_major");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        let data_files = || std::fs::read_dir(&temp_dir).unwrap()
+            .filter(|entry| entry.as_ref().unwrap().file_name().to_string_lossy().ends_with("-Data.db"))
+            .count();
+        
+        for i in 0..3 {
+            write_sstable(&temp_dir, &[(i, 1, "a", "x", 1, false), (10, i as i64, "a", "y", 1, false)]).await;
+        }
+        
+        // 취소하면 쓰던 SSTable을 지우고 입력은 그대로 둠
+        let cancelled = CompactionProgress::new("ks", "t", true);
+        cancelled.cancel();
+        assert!(matches!(compact_directory(&temp_dir, &cancelled).await, Err(CoreDBError::CompactionError { .. })));
+        assert_eq!(data_files(), 3);
+        
+        let progress = CompactionProgress::new("ks", "t", true);
+        assert_eq!(compact_directory(&temp_dir, &progress).await.unwrap(), 3);
+        assert_eq!(data_files(), 1);
+        let info = progress.info();
+        assert_eq!((info.merged_partitions, info.total_partitions, info.fraction()), (6, 6, 1.0));
+        assert!(info.total_bytes > 0);
+        assert_eq!(info.remaining_bytes(), 0);
+        let (_, sstables) = Manifest::open_table(&temp_dir).await.unwrap();
+        assert_eq!(sstables[0].partition_count, 4);
+        
+        // SSTable이 하나뿐이면 다시 쓰지 않음
+        assert_eq!(compact_directory(&temp_dir, &progress).await.unwrap(), 0);
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_split_sstable_along_partition_boundaries() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_compaction_split");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let value = "v".repeat(1000);
+        let cells: Vec<_> = (0..40).map(|id| (id, 1, "a", value.as_str(), 1, false)).collect();
+        write_sstable(&temp_dir, &cells[..1]).await;
+        write_sstable(&temp_dir, &cells).await;
+        let (_, sstables) = Manifest::open_table(&temp_dir).await.unwrap();
+        let (small, original): (Vec<_>, Vec<_>) = sstables.into_iter().map(Arc::new).partition(|sstable| sstable.partition_count == 1);
+        let original = &original[0];
+        assert!(split_sstable(&small[0], 4).await.is_err());
+        assert!(split_sstable(original, 1).await.is_err());
+        // 매니페스트에 없는 파일은 나누지 않음
+        let unlisted = write_sstable(&temp_dir, &cells).await;
+        assert!(split_sstable(&unlisted, 4).await.is_err());
+        unlisted.delete().await.unwrap();
+        
+        // 결과는 키 범위가 겹치지 않고 원본 파티션을 모두 담으며, 매니페스트에서 원본을 대신함
+        let outputs = split_sstable(original, 4).await.unwrap();
+        assert_eq!(outputs.len(), 4);
+        assert!(!original.file_path.exists());
+        assert_eq!(outputs.iter().map(|sstable| sstable.partition_count).sum::<u64>(), 40);
+        assert!(outputs.iter().all(|sstable| sstable.partition_count >= 8));
+        for pair in outputs.windows(2) {
+            assert!(pair[0].last_key.as_ref().unwrap() < pair[1].summary_index.keys().next().unwrap());
+        }
+        let (manifest, sstables) = Manifest::open_table(&temp_dir).await.unwrap();
+        assert_eq!(sstables.len(), 5);
+        assert!(outputs.iter().all(|sstable| manifest.contains(&sstable.id)));
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_size_tiered_selects_similar_sized_sstables() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_compaction_select");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let strategy = CompactionStrategy::SizeTiered { min_threshold: 4, max_threshold: 5 };
+        let mut sstables = Vec::new();
+        for i in 0..3 {
+            sstables.push(write_sstable(&temp_dir, &[(i, 1, "a", "x", 1, false)]).await);
+        }
+        let level0 = |sstables: &[Arc<SSTable>]| sstables.iter().map(|sstable| (sstable.clone(), 0)).collect::<Vec<_>>();
+        // 임계값보다 적으면 고르지 않음
+        assert!(strategy.select(&level0(&sstables)).is_none());
+        assert_eq!(strategy.estimated_tasks(&level0(&sstables)), 0);
+        
+        for i in 3..7 {
+            sstables.push(write_sstable(&temp_dir, &[(i, 1, "a", "x", 1, false)]).await);
+        }
+        let (selected, level) = strategy.select(&level0(&sstables)).unwrap();
+        assert_eq!((selected.len(), level), (5, 0));
+        // 한 번에 max_threshold개까지 합치므로 일곱 개는 두 번
+        assert_eq!(strategy.estimated_tasks(&level0(&sstables)), 2);
+        
+        // 작은 SSTable 크기를 넘으면 크기가 비슷한 것끼리만 묶음
+        let sized = |size_bytes: u64| Arc::new(SSTable { size_bytes, ..(*sstables[0]).clone() });
+        let mb = 1024 * 1024;
+        let mixed = vec![sized(100 * mb), sized(110 * mb), sized(400 * mb), sized(90 * mb), sized(1000 * mb), sized(105 * mb)];
+        let (selected, _) = strategy.select(&level0(&mixed)).unwrap();
+        assert_eq!(selected.iter().map(|sstable| sstable.size_bytes / mb).collect::<Vec<_>>(), vec![90, 100, 105, 110]);
+        assert_eq!(strategy.estimated_tasks(&level0(&mixed)), 1);
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_time_window_compacts_only_within_a_window() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_compaction_time_window");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let strategy = CompactionStrategy::TimeWindow { window_seconds: 3600, min_threshold: 4, max_threshold: 32 };
+        let written = write_sstable(&temp_dir, &[(1, 1, "a", "x", 1, false)]).await;
+        let hour = 3_600_000_000i64;
+        let at = |max_timestamp: i64, size_bytes: u64| (Arc::new(SSTable { max_timestamp, size_bytes, ..(*written).clone() }), 0);
+        
+        // 최근 창은 임계값 전까지 기다리고, 지난 창도 SSTable이 하나면 그대로 둠
+        let mut sstables = vec![at(hour + 1, 30), at(hour + 2, 20), at(hour + 3, 10), at(5, 10)];
+        assert!(strategy.select(&sstables).is_none());
+        assert_eq!(strategy.estimated_tasks(&sstables), 0);
+        
+        // 지난 창에 SSTable이 둘이 되면 그 창만 합침
+        sstables.push(at(hour - 1, 5));
+        let (selected, level) = strategy.select(&sstables).unwrap();
+        assert_eq!(level, 0);
+        assert_eq!(selected.iter().map(|sstable| sstable.max_timestamp).collect::<Vec<_>>(), vec![hour - 1, 5]);
+        
+        // 최근 창이 임계값에 닿으면 최근 창을 먼저 합침
+        sstables.push(at(2 * hour - 1, 40));
+        let (selected, _) = strategy.select(&sstables).unwrap();
+        assert_eq!(selected.len(), 4);
+        assert!(selected.iter().all(|sstable| sstable.max_timestamp >= hour));
+        // 최근 창과 지난 창을 하나씩
+        assert_eq!(strategy.estimated_tasks(&sstables), 2);
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_leveled_picks_overlapping_inputs_from_next_level() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_compaction_leveled");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        // 파티션 키 목록으로 SSTable 생성 (크기와 가장 큰 타임스탬프는 지정)
+        let mb = 1024 * 1024;
+        let sstable = |ids: Vec<i32>, size_bytes: u64, max_timestamp: i64| {
+            let temp_dir = temp_dir.clone();
+            async move {
+                let cells: Vec<_> = ids.iter().map(|&id| (id, 1, "a", "x", 1, false)).collect();
+                let written = write_sstable(&temp_dir, &cells).await;
+                Arc::new(SSTable { size_bytes, max_timestamp, ..(*written).clone() })
+            }
+        };
+        let mut manager = LevelManager::new(3, 10.0).with_sstable_size(mb);
+        let l1_overlapping = sstable(vec![10, 20], mb, 1).await;
+        let l1_disjoint = sstable(vec![100, 110], mb, 1).await;
+        manager.add_sstable(l1_disjoint.clone(), 1);
+        manager.add_sstable(l1_overlapping.clone(), 1);
+        for (i, ids) in [vec![1, 12], vec![2], vec![3]].into_iter().enumerate() {
+            manager.add_sstable(sstable(ids, mb, i as i64).await, 0);
+        }
+        assert!(manager.needs_compaction().is_none());
+        
+        // L0이 네 개가 되면 L0 전부와 키 범위가 겹치는 L1 SSTable만 고름
+        manager.add_sstable(sstable(vec![4], mb, 3).await, 0);
+        let (level, inputs) = manager.needs_compaction().unwrap();
+        assert_eq!((level, inputs.len()), (0, 5));
+        assert!(inputs.contains(&l1_overlapping) && !inputs.contains(&l1_disjoint));
+        
+        let merged = sstable(vec![1, 2, 3, 4, 10, 12, 20], mb, 3).await;
+        manager.update_after_compaction(0, &inputs, vec![merged.clone()]);
+        let stats = manager.level_stats();
+        assert_eq!(stats.iter().map(|level| level.sstable_count).collect::<Vec<_>>(), vec![0, 2, 0]);
+        assert_eq!((stats[1].size_bytes, stats[1].target_size_bytes), (2 * mb, 100 * mb));
+        // L1은 첫 키 순서
+        assert_eq!(manager.levels[1], vec![merged.clone(), l1_disjoint.clone()]);
+        
+        // L1이 목표 크기를 넘으면 가장 오래된 SSTable과 겹치는 L2 SSTable을 고름
+        let mut manager = LevelManager::new(3, 10.0).with_sstable_size(mb);
+        let old = sstable(vec![10, 20], 60 * mb, 1).await;
+        let new = sstable(vec![30, 40], 60 * mb, 2).await;
+        let l2_overlapping = sstable(vec![15, 25], mb, 0).await;
+        let l2_disjoint = sstable(vec![50], mb, 0).await;
+        for (sstable, level) in [(&old, 1), (&new, 1), (&l2_overlapping, 2), (&l2_disjoint, 2)] {
+            manager.add_sstable(sstable.clone(), level);
+        }
+        let (level, inputs) = manager.needs_compaction().unwrap();
+        assert_eq!((level, inputs), (1, vec![old.clone(), l2_overlapping.clone()]));
+        
+        // 마지막 레벨은 넘쳐도 고르지 않음
+        let mut manager = LevelManager::new(2, 10.0).with_sstable_size(mb);
+        manager.add_sstable(old, 1);
+        manager.add_sstable(new, 5);
+        assert_eq!(manager.level_stats()[1].sstable_count, 2);
+        assert!(manager.needs_compaction().is_none());
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
     
     #[test]
     fn test_level_manager_thresholds() {
-        let mut manager = LevelManager::new(5, 10.0);
+        let manager = LevelManager::new(5, 10.0);
         
         assert_eq!(manager.get_threshold_for_level(0), 4);
         assert_eq!(manager.get_threshold_for_level(1), 100);
@@ -238,7 +1245,7 @@ mod tests {
     
     #[test]
     fn test_level_manager_compaction_trigger() {
-        let mut manager = LevelManager::new(3, 10.0);
+        let manager = LevelManager::new(3, 10.0);
         
         // 아직 컴팩션이 필요하지 않음
         assert!(manager.needs_compaction().is_none());
@@ -252,6 +1259,7 @@ mod tests {
         let config = CompactionConfig {
             throughput_mb_per_sec: 16,
             max_concurrent_compactions: 2,
+            write_mode: DiskWriteMode::Buffered,
             strategy: CompactionStrategy::SizeTiered {
                 min_threshold: 4,
                 max_threshold: 32,
@@ -260,9 +1268,67 @@ mod tests {
         };
         
         let manager = CompactionManager::new(config);
-        let stats = manager.get_compaction_stats().await;
+        let stats = manager.get_compaction_stats(BTreeMap::new()).await;
         
-        assert_eq!(stats.pending_tasks, 0);
+        assert_eq!((stats.pending_tasks, stats.queued_tables), (0, 0));
         assert_eq!(stats.throughput_mb_per_sec, 16);
+        
+        // 처리량 한도는 실행 중에 바꿀 수 있음
+        manager.set_throughput_mb_per_sec(64);
+        assert_eq!(manager.get_compaction_stats(BTreeMap::new()).await.throughput_mb_per_sec, 64);
+    }
+    
+    #[tokio::test]
+    async fn test_compaction_pool_runs_tables_concurrently_and_serializes_each_table() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        
+        let manager = Arc::new(CompactionManager::new(CompactionConfig {
+            throughput_mb_per_sec: 0,
+            max_concurrent_compactions: 2,
+            write_mode: DiskWriteMode::Buffered,
+            strategy: CompactionStrategy::SizeTiered { min_threshold: 4, max_threshold: 32 },
+            data_directory: std::env::temp_dir(),
+        }));
+        
+        // 대기 중인 같은 테이블 요청은 하나로 합침
+        for table in ["a", "a", "b", "c"] {
+            manager.schedule_compaction("ks", table).await;
+        }
+        assert_eq!(manager.get_compaction_stats(BTreeMap::new()).await.queued_tables, 3);
+        
+        let running = Arc::new(Mutex::new(HashSet::new()));
+        let (active, max_active, completed) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+        let handler = {
+            let (manager, running, active, max_active, completed) = (manager.clone(), running.clone(), active.clone(), max_active.clone(), completed.clone());
+            move |task: CompactionTask| {
+                let (manager, running, active, max_active, completed) = (manager.clone(), running.clone(), active.clone(), max_active.clone(), completed.clone());
+                async move {
+                    assert!(running.lock().unwrap().insert(task.table.clone()), "{} compacted twice at once", task.table);
+                    max_active.fetch_max(active.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                    // 실행 중인 테이블을 다시 요청하면 앞의 실행이 끝난 뒤 한 번 더 실행
+                    if task.table == "a" && completed.load(Ordering::SeqCst) == 0 {
+                        manager.schedule_compaction("ks", "a").await;
+                    }
+                    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                    running.lock().unwrap().remove(&task.table);
+                    active.fetch_sub(1, Ordering::SeqCst);
+                    completed.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                }
+            }
+        };
+        let worker = manager.clone();
+        tokio::spawn(async move { worker.run_compaction_loop(handler).await });
+        
+        for _ in 0..100 {
+            if completed.load(Ordering::SeqCst) == 4 && manager.get_compaction_stats(BTreeMap::new()).await.active_compactions == 0 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert_eq!(completed.load(Ordering::SeqCst), 4);
+        assert_eq!(max_active.load(Ordering::SeqCst), 2);
+        let stats = manager.get_compaction_stats(BTreeMap::new()).await;
+        assert_eq!((stats.queued_tables, stats.active_compactions), (0, 0));
     }
 }
@@ -1,12 +1,32 @@
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::net::SocketAddr;
+use std::sync::{Arc, OnceLock};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::collections::HashMap;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
+use tokio::task::JoinHandle;
+use tracing::Instrument;
+use uuid::Uuid;
 use crate::schema::{TableSchema, KeyspaceDefinition, ReplicationStrategy};
-use crate::storage::{Memtable, SSTable};
-use crate::wal::{CommitLog, Mutation};
-use crate::query::{QueryEngine, CqlStatement, QueryResult};
-use crate::compaction::{CompactionManager, CompactionConfig};
+use crate::storage::{CassandraImportStats, CassandraSSTable, ChunkCache, ChunkCacheStats, DiskAccessMode, DiskWriteMode, KeyCache, KeyCacheStats, IntegrityCheckMode, Manifest, Memtable, RowCache, RowCacheStats, SSTable, SSTableStatistics, check_data_directory, DEFAULT_CHUNK_CACHE_SIZE_MB, DEFAULT_KEY_CACHE_ENTRIES};
+use crate::wal::{CommitLog, CommitLogArchiver, CommitLogEncryption, CommitLogStats, CommitLogTail, CommitLogEntry, CommitLogPosition, CommitLogRestore, CommitLogSync, Mutation};
+use crate::query::{QueryEngine, CqlStatement, QueryResult, PagingState, QueryTrace, Trigger, redact_passwords};
+use crate::compaction::{CompactionManager, CompactionConfig, CompactionInfo, CompactionProgress, CompactionStats, CompactionStrategy, CompactionTask, LevelManager, LevelStats, TombstoneGc, DEFAULT_LEVELED_SSTABLE_SIZE_MB, fully_expired_sstables, tombstone_compaction_candidate};
+use crate::admission::{AdmissionControl, AdmissionStats};
+use crate::disk_usage::{DiskMonitor, DiskUsageStats};
+use crate::quota::{KeyspaceQuota, KeyspaceQuotas, QuotaStats};
+use crate::migration::{AppliedMigration, Migration, SchemaVersion, MIGRATIONS_TABLE, SCHEMA_VERSIONS_TABLE};
+use crate::query::system_views::{virtual_table, VirtualRow, VirtualTable, CACHES_TABLE, CASSANDRA_RELEASE_VERSION, CLIENTS_TABLE, COLUMNS_TABLE, INDEXES_TABLE, LOCAL_TABLE, PARTITIONER, SSTABLE_TASKS_TABLE, SYSTEM_SCHEMA_KEYSPACE, TABLES_TABLE, TRIGGERS_TABLE};
+use crate::clients::ClientRegistry;
+use crate::backup::{BackupManifest, BackupStore};
+use crate::snapshots::{create_table_snapshot, default_snapshot_name, list_table_snapshots, remove_table_snapshot, scheduled_snapshot_name, snapshot_directory, snapshot_table_directory, validate_snapshot_name, SnapshotInfo, SnapshotSchedule};
+use crate::metrics::{render_prometheus, Metrics, MetricsSnapshot, TableMetrics, TableMetricsSnapshot};
+use crate::persistence::{Checkpoint, CheckpointTable, CHECKPOINT_SNAPSHOT_PREFIX};
+use crate::restore::PointInTimeRestore;
+use crate::fault_injection::{self, FaultPoint};
+use crate::archive::{extract_keyspace_archive, read_archive_manifest, write_keyspace_archive, KeyspaceArchive};
+use crate::schema::CassandraValue;
+use crate::auth::{hash_password, required_permission, verify_password, Permission, Resource, Role, DEFAULT_SUPERUSER, ROLES_TABLE, ROLE_PERMISSIONS_TABLE, SYSTEM_AUTH_KEYSPACE};
 use crate::error::*;
 
 /// 데이터베이스 설정
@@ -14,10 +34,64 @@ use crate::error::*;
 pub struct DatabaseConfig {
     pub data_directory: PathBuf,
     pub commitlog_directory: PathBuf,
+    /// 커밋 로그 fsync 방식 (기본은 10초마다)
+    pub commitlog_sync: CommitLogSync,
+    /// 커밋 로그 세그먼트 크기 (MB, 모든 테이블의 데이터가 플러시된 세그먼트는 플러시 뒤에 정리됨)
+    pub commitlog_segment_size_mb: u64,
+    /// 커밋 로그마다 전체 크기 한도 (MB, 넘으면 가장 오래된 세그먼트를 붙잡은 테이블을 플러시, 0이면 제한 없음)
+    pub commitlog_total_space_mb: u64,
+    /// 전용 커밋 로그 디렉토리를 쓰는 키스페이스 (중요한 키스페이스의 로그를 다른 장치에 둘 때, 나머지는 commitlog_directory)
+    ///
+    /// 전용 로그는 보관과 시점 복구 대상이 아님. 키스페이스를 옮기기 전에는 플러시해야 기존 로그의 쓰기가 재생되지 않고 남지 않음
+    pub commitlog_keyspace_directories: HashMap<String, PathBuf>,
+    /// 닫힌 커밋 로그 세그먼트 보관 방식 (None이면 보관하지 않음)
+    pub commitlog_archiver: Option<Arc<dyn CommitLogArchiver>>,
+    /// 보관한 커밋 로그로 시점 복구 (테이블을 열 때 적용)
+    pub commitlog_restore: Option<CommitLogRestore>,
+    /// 커밋 로그 암호화 키 (None이면 평문으로 기록)
+    pub commitlog_encryption: Option<CommitLogEncryption>,
     pub memtable_flush_threshold_mb: u64,
+    /// 첫 쓰기부터 이 시간(초)이 지난 메모리 테이블은 크기와 관계없이 플러시 (0이면 제한 없음)
+    pub memtable_max_age_secs: u64,
+    /// 컴팩션 읽기/쓰기 처리량 한도 (MB/s, 0이면 제한 없음)
     pub compaction_throughput_mb_per_sec: u64,
+    /// 동시에 컴팩션할 수 있는 테이블 수
+    pub concurrent_compactors: usize,
     pub concurrent_reads: usize,
     pub concurrent_writes: usize,
+    /// 읽기/쓰기 경로별 대기열 한도 (넘으면 Overloaded 오류)
+    pub max_queued_requests: usize,
+    /// 테이블마다 플러시를 기다리는 메모리 테이블 수 한도 (넘으면 쓰기를 Overloaded로 거부, 0이면 제한 없음)
+    pub max_pending_flushes: usize,
+    /// 테이블마다 SSTable 수 한도 (컴팩션이 밀려 넘으면 쓰기를 Overloaded로 거부, 0이면 제한 없음)
+    pub max_sstables_per_table: usize,
+    /// 이 크기를 넘는 파티션은 경고하고 system_views.large_partitions에 기록
+    pub large_partition_warning_threshold_mb: u64,
+    /// 메모리 전용 모드: 커밋 로그와 SSTable 플러시 없이 메모리 테이블에만 보관
+    /// (테스트나 캐시처럼 내구성이 필요 없는 경우, 디렉토리도 만들지 않음)
+    pub ephemeral: bool,
+    /// SSTable 읽기 방식 (Mmap이면 메모리 매핑으로 읽어 시스템 콜을 줄임)
+    pub disk_access_mode: DiskAccessMode,
+    /// 컴팩션 결과 데이터 파일 쓰기 방식 (Direct면 O_DIRECT로 페이지 캐시를 우회, 기본은 버퍼 쓰기)
+    pub compaction_write_mode: DiskWriteMode,
+    /// 파티션 키 캐시 크기 (항목 수, 0이면 사용하지 않음)
+    pub key_cache_entries: usize,
+    /// 압축 해제된 SSTable 청크 캐시 크기 (MB, 모든 테이블 공유, 0이면 사용하지 않음)
+    pub chunk_cache_size_mb: u64,
+    /// 이 시간(ms)보다 오래 걸린 CQL 쿼리는 경고 로그로 남김 (0이면 끔)
+    pub slow_query_threshold_ms: u64,
+    /// 데이터/커밋 로그 디렉토리의 디스크 사용률이 이 값(%)을 넘으면 경고 (0이면 끔)
+    pub disk_usage_warn_percent: u64,
+    /// 디스크 사용률이 이 값(%)을 넘으면 쓰기를 거부 (읽기는 계속, 0이면 끔)
+    pub disk_usage_fail_percent: u64,
+    /// 시작할 때 데이터 디렉토리 검사 (Check면 문제가 있을 때 시작하지 않고, Repair면 고칠 수 있는 것은 고침)
+    pub integrity_check: IntegrityCheckMode,
+    /// 키스페이스별 디스크/쓰기/동시 쿼리 한도 (넘으면 기다리지 않고 QuotaExceeded로 거부)
+    pub keyspace_quotas: HashMap<String, KeyspaceQuota>,
+    /// 키스페이스별 주기 스냅샷과 보관 정책 (메모리 전용 모드에서는 무시)
+    pub snapshot_schedules: HashMap<String, SnapshotSchedule>,
+    /// 백업을 올릴 오브젝트 스토어 (s3://bucket/prefix, file:///path, None이면 backup 사용 불가)
+    pub backup_url: Option<String>,
 }
 
 impl Default for DatabaseConfig {
@@ -25,14 +99,59 @@ impl Default for DatabaseConfig {
         Self {
             data_directory: PathBuf::from("./data"),
             commitlog_directory: PathBuf::from("./commitlog"),
+            commitlog_sync: CommitLogSync::default(),
+            commitlog_segment_size_mb: 32,
+            commitlog_total_space_mb: 8192,
+            commitlog_keyspace_directories: HashMap::new(),
+            commitlog_archiver: None,
+            commitlog_restore: None,
+            commitlog_encryption: None,
             memtable_flush_threshold_mb: 64,
+            memtable_max_age_secs: 0,
             compaction_throughput_mb_per_sec: 16,
+            concurrent_compactors: 2,
             concurrent_reads: 32,
             concurrent_writes: 32,
+            max_queued_requests: 1024,
+            max_pending_flushes: 4,
+            max_sstables_per_table: 256,
+            large_partition_warning_threshold_mb: 100,
+            ephemeral: false,
+            disk_access_mode: DiskAccessMode::Standard,
+            compaction_write_mode: DiskWriteMode::Buffered,
+            key_cache_entries: DEFAULT_KEY_CACHE_ENTRIES,
+            chunk_cache_size_mb: DEFAULT_CHUNK_CACHE_SIZE_MB,
+            slow_query_threshold_ms: 500,
+            disk_usage_warn_percent: 90,
+            disk_usage_fail_percent: 95,
+            integrity_check: IntegrityCheckMode::Off,
+            keyspace_quotas: HashMap::new(),
+            snapshot_schedules: HashMap::new(),
+            backup_url: None,
         }
     }
 }
 
+/// 재시작 없이 바꿀 수 있는 설정 (None인 항목은 그대로 둠)
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TunableSettings {
+    pub memtable_flush_threshold_mb: Option<u64>,
+    pub memtable_max_age_secs: Option<u64>,
+    pub compaction_throughput_mb_per_sec: Option<u64>,
+    pub slow_query_threshold_ms: Option<u64>,
+    /// 시작할 때 끈 캐시는 켤 수 없음
+    pub key_cache_entries: Option<usize>,
+    pub chunk_cache_size_mb: Option<u64>,
+}
+
+impl DatabaseConfig {
+    /// 디스크를 쓰지 않는 메모리 전용 설정
+    pub fn ephemeral() -> Self {
+        Self { ephemeral: true, ..Self::default() }
+    }
+}
+
 /// 키스페이스
 #[derive(Debug, Clone)]
 pub struct Keyspace {
@@ -45,34 +164,467 @@ pub struct Keyspace {
 #[derive(Debug)]
 pub struct Table {
     pub schema: Arc<TableSchema>,
+    /// SSTable로 쓰이는 중인 고정된 메모리 테이블 (오래된 것부터)
     pub memtables: Vec<Arc<Memtable>>,
     pub sstables: Vec<Arc<SSTable>>,
     pub current_memtable: Arc<Memtable>,
+    /// 현재 SSTable 목록을 기록하는 매니페스트 (메모리 전용 모드에서는 None)
+    pub manifest: Option<Manifest>,
+    /// 병합된 파티션 캐시 (스키마 옵션 row_cache_size_mb가 있을 때만)
+    pub row_cache: Option<Arc<RowCache>>,
+    /// 읽기/쓰기 지연 시간과 읽기당 SSTable/툼스톤 수 (재시작하면 비어 있음)
+    pub metrics: Arc<TableMetrics>,
+}
+
+impl Table {
+    /// SSTable과 매니페스트에 기록된 컴팩션 레벨
+    fn sstable_levels(&self) -> Vec<(Arc<SSTable>, u32)> {
+        self.sstables.iter()
+            .map(|sstable| (sstable.clone(), self.manifest.as_ref().and_then(|manifest| manifest.level(&sstable.id)).unwrap_or(0)))
+            .collect()
+    }
+    
+    /// inputs를 컴팩션할 때 툼스톤을 지울 기준 (나머지 SSTable과 메모리 테이블이 가리는 데이터)
+    fn tombstone_gc(&self, inputs: &[Arc<SSTable>]) -> TombstoneGc {
+        TombstoneGc {
+            gc_before: chrono::Utc::now().timestamp() - self.schema.options.gc_grace_seconds as i64,
+            other_sstables: self.sstables.iter()
+                .filter(|sstable| !inputs.iter().any(|input| Arc::ptr_eq(input, sstable)))
+                .cloned()
+                .collect(),
+            min_memtable_timestamp: self.min_memtable_timestamp(),
+        }
+    }
+    
+    /// 아직 플러시되지 않은 메모리 테이블의 가장 작은 타임스탬프
+    fn min_memtable_timestamp(&self) -> i64 {
+        self.memtables.iter()
+            .chain(std::iter::once(&self.current_memtable))
+            .map(|memtable| memtable.min_timestamp())
+            .min()
+            .unwrap_or(i64::MAX)
+    }
+    
+    /// 모든 데이터가 만료된 SSTable을 매니페스트와 목록에서 빼고 파일을 지움 (지운 수 반환)
+    async fn drop_expired_sstables(&mut self, keyspace: &str, table: &str, now: i64) -> Result<usize> {
+        let expired = fully_expired_sstables(&self.sstables, self.min_memtable_timestamp(), self.schema.options.gc_grace_seconds, now);
+        if expired.is_empty() {
+            return Ok(0);
+        }
+        
+        if let Some(manifest) = self.manifest.as_mut() {
+            let ids: Vec<String> = expired.iter().map(|sstable| sstable.id.clone()).collect();
+            manifest.record_compaction(&ids, &[], 0).await?;
+        }
+        self.sstables.retain(|sstable| !expired.iter().any(|dropped| Arc::ptr_eq(dropped, sstable)));
+        // 캐시된 파티션에 지운 SSTable의 셀이 남아 있을 수 있음
+        self.row_cache = Self::build_row_cache(&self.schema);
+        for sstable in &expired {
+            tracing::info!("Dropping fully expired SSTable {} of {}.{}", sstable.id, keyspace, table);
+            sstable.delete().await?;
+        }
+        Ok(expired.len())
+    }
+    
+    fn build_row_cache(schema: &TableSchema) -> Option<Arc<RowCache>> {
+        schema.options.row_cache_size_mb
+            .filter(|size_mb| *size_mb > 0)
+            .map(|size_mb| Arc::new(RowCache::new(size_mb * 1024 * 1024)))
+    }
+}
+
+/// SSTable 읽기 경로 설정 (파일 읽기 방식과 모든 테이블이 공유하는 캐시)
+#[derive(Debug, Clone)]
+struct SSTableReadPath {
+    access_mode: DiskAccessMode,
+    key_cache: Option<Arc<KeyCache>>,
+    chunk_cache: Option<Arc<ChunkCache>>,
+}
+
+impl SSTableReadPath {
+    fn apply(&self, sstable: SSTable) -> SSTable {
+        sstable.with_access_mode(self.access_mode)
+            .with_key_cache(self.key_cache.clone())
+            .with_chunk_cache(self.chunk_cache.clone())
+    }
+}
+
+/// 디스크 사용률을 다시 확인하는 주기
+const DISK_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// 플러시 스케줄러가 메모리 테이블을 확인하는 주기 (쓰기가 임계값을 넘기면 바로 깨움)
+const FLUSH_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// 진행 중인 플러시 작업 ((키스페이스, 테이블), 작업)
+type PendingFlush = ((String, String), JoinHandle<Result<()>>);
+
+/// CQL로 만든 쿼리 엔진 테이블의 디스크 상태 (CoreDB와 플러시 스케줄러가 공유)
+///
+/// 쿼리 엔진 테이블은 저장 계층 테이블처럼 data_directory/<키스페이스>/<테이블>에 매니페스트와 SSTable을 두고,
+/// 시작할 때 매니페스트의 재생 위치부터 커밋 로그를 다시 반영함
+#[derive(Default)]
+struct EngineTables {
+    /// 열린 테이블의 매니페스트 ((키스페이스, 테이블))
+    manifests: Mutex<HashMap<(String, String), Manifest>>,
+    /// CQL 쓰기는 커밋 로그 기록부터 쿼리 엔진 반영까지 읽기 잠금을 쥠 (플러시와 커밋 로그 정리는
+    /// 쓰기 잠금으로 기록만 하고 아직 반영하지 않은 쓰기가 끝나길 기다림)
+    writes: RwLock<()>,
+}
+
+/// 메모리 테이블 플러시 (CoreDB와 백그라운드 플러시 스케줄러가 공유)
+#[derive(Clone)]
+struct Flusher {
+    keyspaces: Arc<RwLock<HashMap<String, Keyspace>>>,
+    commit_log: Option<Arc<CommitLog>>,
+    keyspace_commit_logs: HashMap<String, Arc<CommitLog>>,
+    query_engine: Arc<RwLock<QueryEngine>>,
+    compaction_manager: Arc<CompactionManager>,
+    read_path: SSTableReadPath,
+    config: DatabaseConfig,
+    /// 전역 플러시 임계값 (MB, 실행 중에 바꿀 수 있음)
+    flush_threshold_mb: Arc<AtomicU64>,
+    /// 메모리 테이블 최대 나이 (초, 실행 중에 바꿀 수 있음)
+    max_age_secs: Arc<AtomicU64>,
+    /// 진행 중인 백그라운드 플러시
+    pending: Arc<Mutex<Vec<PendingFlush>>>,
+    /// 플러시 실행 시간과 플러시한 바이트 (CoreDB와 공유)
+    metrics: Arc<Metrics>,
+    /// CQL로 만든 쿼리 엔진 테이블 (CoreDB와 공유)
+    engine_tables: Arc<EngineTables>,
+}
+
+impl Flusher {
+    fn commit_log_for(&self, keyspace: &str) -> Option<&Arc<CommitLog>> {
+        self.keyspace_commit_logs.get(keyspace).or(self.commit_log.as_ref())
+    }
+    
+    /// 현재 메모리 테이블이 크기 임계값이나 최대 나이를 넘었는지
+    fn memtable_due(&self, table: &Table) -> bool {
+        self.memtable_over_limits(&table.schema, &table.current_memtable)
+    }
+    
+    fn memtable_over_limits(&self, schema: &TableSchema, memtable: &Memtable) -> bool {
+        let threshold_mb = schema.options.memtable_flush_threshold_mb
+            .unwrap_or_else(|| self.flush_threshold_mb.load(Ordering::Relaxed));
+        let max_age = std::time::Duration::from_secs(self.max_age_secs.load(Ordering::Relaxed));
+        memtable.size_bytes() > threshold_mb * 1024 * 1024
+            || (!max_age.is_zero() && memtable.age().is_some_and(|age| age >= max_age))
+    }
+    
+    /// keyspace의 커밋 로그가 전체 크기 한도를 넘었으면 가장 오래된 세그먼트
+    fn commit_log_over_limit(&self, keyspace: &str) -> Option<u64> {
+        let total_space = self.config.commitlog_total_space_mb * 1024 * 1024;
+        self.commit_log_for(keyspace)
+            .filter(|commit_log| total_space > 0 && commit_log.total_size() > total_space)
+            .map(|commit_log| commit_log.oldest_segment_id())
+    }
+    
+    /// 플러시할 때가 된 메모리 테이블을 모두 플러시 (플러시 스케줄러가 주기적으로, 또는 쓰기가 깨울 때 실행)
+    ///
+    /// 커밋 로그가 전체 크기 한도를 넘으면 임계값에 닿지 않았더라도 가장 오래된 세그먼트부터 재생해야 하는
+    /// 테이블을 플러시해 세그먼트를 정리할 수 있게 함 (이미 플러시 중인 테이블은 그 플러시가 끝나길 기다림)
+    async fn flush_due_memtables(&self) -> Result<()> {
+        if self.config.ephemeral {
+            return Ok(());
+        }
+        
+        // 플러시는 쓰기 잠금을 잡으므로 대상 목록만 모은 뒤 읽기 잠금을 놓고 수행
+        let mut targets = Vec::new();
+        {
+            let keyspaces = self.keyspaces.read().await;
+            for (keyspace_name, keyspace) in keyspaces.iter() {
+                // 커밋 로그에 쓰지 않는 키스페이스는 세그먼트를 붙잡지 않음
+                let oldest_segment = self.commit_log_over_limit(keyspace_name).filter(|_| keyspace.definition.durable_writes);
+                let tables = keyspace.tables.read().await;
+                for (table_name, table) in tables.iter() {
+                    let pins_oldest_segment = oldest_segment.is_some_and(|oldest| {
+                        table.memtables.is_empty()
+                            && table.current_memtable.partition_count() > 0
+                            && table.manifest.as_ref().is_some_and(|manifest| manifest.replay_position.segment_id <= oldest)
+                    });
+                    if pins_oldest_segment {
+                        tracing::info!("Commit log exceeds {} MB, flushing {}.{} to free segment {}",
+                                       self.config.commitlog_total_space_mb, keyspace_name, table_name, oldest_segment.unwrap_or_default());
+                    }
+                    if pins_oldest_segment || self.memtable_due(table) {
+                        targets.push((keyspace_name.clone(), table_name.clone()));
+                    }
+                }
+            }
+        }
+        
+        for (keyspace_name, table_name) in targets {
+            self.flush(&keyspace_name, &table_name).await?;
+        }
+        
+        let mut engine_targets = Vec::new();
+        {
+            let manifests = self.engine_tables.manifests.lock().await;
+            let engine = self.query_engine.read().await;
+            for ((keyspace_name, table_name), manifest) in manifests.iter() {
+                let Ok(memtable) = engine.get_memtable(keyspace_name, table_name) else { continue };
+                let pins_oldest_segment = self.commit_log_over_limit(keyspace_name)
+                    .filter(|_| engine.durable_writes(keyspace_name))
+                    .is_some_and(|oldest| {
+                        engine.flushing_memtables(keyspace_name, table_name).is_empty()
+                            && memtable.partition_count() > 0
+                            && manifest.replay_position.segment_id <= oldest
+                    });
+                if pins_oldest_segment || self.memtable_over_limits(memtable.table_schema(), &memtable) {
+                    engine_targets.push((keyspace_name.clone(), table_name.clone()));
+                }
+            }
+        }
+        for (keyspace_name, table_name) in engine_targets {
+            self.flush_engine_table(&keyspace_name, &table_name).await?;
+        }
+        
+        Ok(())
+    }
+    
+    /// CQL로 만든 쿼리 엔진 테이블의 메모리 테이블을 SSTable로 플러시 (데이터가 있었으면 true)
+    ///
+    /// 잠금 안에서는 메모리 테이블을 고정해 새 메모리 테이블로 바꾸기만 하고 SSTable은 잠금 밖에서 씀.
+    /// 고정된 메모리 테이블은 SSTable이 붙을 때까지 쿼리 엔진 읽기에 계속 포함됨. 저장 계층 테이블과 달리
+    /// 백그라운드 작업 없이 쓰기가 끝날 때까지 기다림
+    async fn flush_engine_table(&self, keyspace: &str, table: &str) -> Result<bool> {
+        if self.config.ephemeral {
+            return Ok(false);
+        }
+        
+        let key = (keyspace.to_string(), table.to_string());
+        if !self.engine_tables.manifests.lock().await.contains_key(&key) {
+            return Ok(false);
+        }
+        let (frozen, flushed_until) = {
+            let _writes = self.engine_tables.writes.write().await;
+            if !self.engine_tables.manifests.lock().await.contains_key(&key) {
+                return Ok(false);
+            }
+            let Some(frozen) = self.query_engine.write().await.freeze_memtable(keyspace, table)? else { return Ok(false) };
+            // 쓰기 잠금 중이라 이 위치 앞의 이 테이블 쓰기는 모두 고정된 메모리 테이블까지에 들어 있음
+            (frozen, self.commit_log_for(keyspace).map(|commit_log| commit_log.position()))
+        };
+        
+        let started = std::time::Instant::now();
+        let sstable_dir = self.config.data_directory.join(keyspace).join(table);
+        let written = async {
+            let sstable = SSTable::create_from_memtable(&frozen, &sstable_dir, frozen.table_schema().options.compression).await?;
+            let mut manifests = self.engine_tables.manifests.lock().await;
+            let Some(manifest) = manifests.get_mut(&key) else {
+                // 쓰는 동안 테이블이 지워짐
+                sstable.delete().await?;
+                return Ok(false);
+            };
+            let oldest = {
+                let engine = self.query_engine.read().await;
+                // 재생 위치가 옮겨지기 전에 디스크 인덱스에 고정된 메모리 테이블까지의 쓰기를 남김
+                let mut sstables = engine.sstable_ids(keyspace, table);
+                sstables.push(sstable.id.clone());
+                engine.commit_indexes(keyspace, table, &sstables)?;
+                engine.flushing_memtables(keyspace, table).first().is_some_and(|first| Arc::ptr_eq(first, &frozen))
+            };
+            // 앞선 메모리 테이블이 아직 쓰이는 중이면 재생 위치를 옮기지 않음
+            manifest.record_flush(&sstable, flushed_until.filter(|_| oldest)).await?;
+            self.query_engine.write().await.finish_flush(keyspace, table, &frozen, Arc::new(self.read_path.apply(sstable)));
+            Ok::<_, CoreDBError>(true)
+        }.await;
+        self.metrics.flushes.record(started.elapsed(), written.is_ok());
+        // 실패하면 고정된 메모리 테이블이 플러시 목록에 남아 계속 읽을 수 있고 커밋 로그로 복구 가능
+        if !written? {
+            return Ok(false);
+        }
+        self.metrics.flushed_bytes.fetch_add(frozen.memory().data_bytes, Ordering::Relaxed);
+        self.compaction_manager.schedule_compaction(keyspace, table).await;
+        
+        if let Some(commit_log) = self.commit_log_for(keyspace) {
+            let dedicated = self.keyspace_commit_logs.contains_key(keyspace).then_some(keyspace);
+            if let Err(e) = CoreDB::purge_commit_log_segments(&self.keyspaces, &self.engine_tables, &self.query_engine, commit_log, dedicated,
+                                                              &self.config.commitlog_keyspace_directories, &self.config.data_directory).await {
+                tracing::warn!("Failed to purge commit log segments: {}", e);
+            }
+        }
+        Ok(true)
+    }
+    
+    /// 메모리 테이블 플러시
+    ///
+    /// 현재 메모리 테이블을 고정해 대기열로 옮기고 새 메모리 테이블로 바로 교체한 뒤
+    /// SSTable 쓰기는 백그라운드 작업으로 수행 (쓰는 동안에도 대기열에서 읽을 수 있음)
+    async fn flush(&self, keyspace: &str, table: &str) -> Result<()> {
+        if self.config.ephemeral {
+            return Ok(());
+        }
+        
+        // 교체부터 대기열 등록까지 잠가 wait_for_flushes가 시작된 플러시를 놓치지 않게 함
+        let mut pending = self.pending.lock().await;
+        let (frozen, flushed_until) = {
+            let keyspaces = self.keyspaces.read().await;
+            let Some(ks) = keyspaces.get(keyspace) else { return Ok(()) };
+            let mut tables = ks.tables.write().await;
+            let Some(tbl) = tables.get_mut(table) else { return Ok(()) };
+            if tbl.current_memtable.partition_count() == 0 {
+                return Ok(());
+            }
+            let new_memtable = Arc::new(Memtable::new(tbl.schema.clone()));
+            let old_memtable = std::mem::replace(&mut tbl.current_memtable, new_memtable);
+            old_memtable.freeze();
+            tbl.memtables.push(old_memtable.clone());
+            // 테이블 쓰기 잠금 중이라 이 위치 앞의 이 테이블 쓰기는 모두 고정된 메모리 테이블까지에 들어 있음
+            let flushed_until = self.commit_log_for(keyspace).map(|commit_log| commit_log.position());
+            (old_memtable, flushed_until)
+        };
+        
+        // 플러시되는 파티션 중 임계값을 넘은 것 기록 (재생 등 쓰기 경로 밖에서 커진 파티션 포함)
+        let large_partitions = frozen.partitions_larger_than(self.config.large_partition_warning_threshold_mb * 1024 * 1024);
+        if !large_partitions.is_empty() {
+            let mut engine = self.query_engine.write().await;
+            for (partition_key, size) in large_partitions {
+                engine.record_large_partition(keyspace, table, &partition_key, size).await?;
+            }
+        }
+        
+        let keyspaces = self.keyspaces.clone();
+        let engine_tables = self.engine_tables.clone();
+        let query_engine = self.query_engine.clone();
+        let compaction_manager = self.compaction_manager.clone();
+        let commit_log = self.commit_log_for(keyspace).cloned();
+        let dedicated = self.keyspace_commit_logs.contains_key(keyspace).then(|| keyspace.to_string());
+        let keyspace_directories = self.config.commitlog_keyspace_directories.clone();
+        let data_directory = self.config.data_directory.clone();
+        let sstable_dir = data_directory.join(keyspace).join(table);
+        let read_path = self.read_path.clone();
+        let metrics = self.metrics.clone();
+        let flushed_bytes = frozen.memory().data_bytes;
+        // 쓰기 요청과 상관없는 백그라운드 작업이라 새 추적으로 시작
+        let span = tracing::info_span!(parent: None, "flush", db.namespace = keyspace, db.table = table, bytes = flushed_bytes);
+        let (keyspace, table) = (keyspace.to_string(), table.to_string());
+        let table_name = (keyspace.clone(), table.clone());
+        let handle = tokio::spawn(async move {
+            let started = std::time::Instant::now();
+            let result = CoreDB::write_flushed_memtable(&keyspaces, &sstable_dir, &keyspace, &table, frozen, flushed_until, &read_path).await;
+            metrics.flushes.record(started.elapsed(), result.is_ok());
+            if result.is_ok() {
+                metrics.flushed_bytes.fetch_add(flushed_bytes, Ordering::Relaxed);
+            }
+            match &result {
+                // 실패한 메모리 테이블은 대기열에 남아 계속 읽을 수 있고 커밋 로그로 복구 가능
+                Err(e) => tracing::error!("Failed to flush memtable {}.{}: {}", keyspace, table, e),
+                Ok(()) => {
+                    compaction_manager.schedule_compaction(&keyspace, &table).await;
+                    if let Some(commit_log) = &commit_log {
+                        if let Err(e) = CoreDB::purge_commit_log_segments(&keyspaces, &engine_tables, &query_engine, commit_log, dedicated.as_deref(), &keyspace_directories, &data_directory).await {
+                            tracing::warn!("Failed to purge commit log segments: {}", e);
+                        }
+                    }
+                },
+            }
+            result
+        }.instrument(span));
+        
+        pending.retain(|(_, handle)| !handle.is_finished());
+        pending.push((table_name, handle));
+        Ok(())
+    }
+    
+    /// 시작된 백그라운드 플러시가 모두 끝날 때까지 대기
+    async fn wait_for_flushes(&self) -> Result<()> {
+        let handles: Vec<_> = self.pending.lock().await.drain(..).collect();
+        Self::join_flushes(handles.into_iter().map(|(_, handle)| handle)).await
+    }
+    
+    /// 한 테이블의 백그라운드 플러시만 대기 (다른 테이블 플러시는 기다리지 않음)
+    async fn wait_for_table_flushes(&self, keyspace: &str, table: &str) -> Result<()> {
+        let handles: Vec<_> = {
+            let mut pending = self.pending.lock().await;
+            let (matching, others) = pending.drain(..).partition(|((k, t), _)| k == keyspace && t == table);
+            *pending = others;
+            matching
+        };
+        Self::join_flushes(handles.into_iter().map(|(_, handle)| handle)).await
+    }
+    
+    async fn join_flushes(handles: impl Iterator<Item = JoinHandle<Result<()>>>) -> Result<()> {
+        for handle in handles {
+            handle.await.map_err(|e| CoreDBError::Generic { message: format!("Flush task failed: {}", e) })??;
+        }
+        Ok(())
+    }
 }
 
 /// CoreDB 메인 클래스
 pub struct CoreDB {
     pub keyspaces: Arc<RwLock<HashMap<String, Keyspace>>>,
-    pub commit_log: Arc<RwLock<CommitLog>>,
+    /// 커밋 로그 (메모리 전용 모드에서는 None)
+    pub commit_log: Option<Arc<CommitLog>>,
+    /// 전용 디렉토리를 쓰는 키스페이스의 커밋 로그
+    pub keyspace_commit_logs: HashMap<String, Arc<CommitLog>>,
     pub query_engine: Arc<RwLock<QueryEngine>>,
     pub config: DatabaseConfig,
     pub compaction_manager: Arc<CompactionManager>,
+    read_admission: Arc<AdmissionControl>,
+    write_admission: Arc<AdmissionControl>,
+    /// 열거나 새로 쓴 SSTable에 붙이는 읽기 방식과 공유 캐시
+    read_path: SSTableReadPath,
+    /// 메모리 테이블 플러시 (백그라운드 플러시 스케줄러와 공유)
+    flusher: Flusher,
+    /// 플러시 스케줄러를 바로 깨움 (스케줄러는 약한 참조만 들고 있어 CoreDB가 사라지면 멈춤)
+    flush_wakeup: Arc<tokio::sync::Notify>,
+    /// 느린 쿼리 경고 기준 (ms, 0이면 끔)
+    slow_query_threshold_ms: AtomicU64,
+    /// 디스크 사용률 감시 (메모리 전용 모드에서는 None)
+    disk_monitor: Option<Arc<DiskMonitor>>,
+    /// drain을 시작한 뒤로는 쓰기를 거부
+    draining: AtomicBool,
+    /// 키스페이스별 한도 (한도를 둔 키스페이스만)
+    quotas: KeyspaceQuotas,
+    /// 마지막 스키마 버전 (처음 쓸 때 읽음, 스키마 문은 이 잠금을 쥔 채 실행해 버전 순서가 적용 순서와 같음)
+    schema_version: Mutex<Option<u64>>,
+    /// 마이그레이션을 한 번에 하나씩만 적용
+    migration_lock: Mutex<()>,
+    /// system.local의 host_id (프로세스를 시작할 때마다 새로 만듦)
+    host_id: Uuid,
+    /// system.local에 알리는 클라이언트 접속 주소 (서버가 리슨을 시작할 때 정함)
+    rpc_address: OnceLock<SocketAddr>,
+    /// 서버에 연결된 클라이언트 (system_views.clients)
+    clients: ClientRegistry,
+    /// 쿼리, 행 읽기/쓰기, 플러시 지표
+    metrics: Arc<Metrics>,
 }
 
 impl CoreDB {
     /// 새 데이터베이스 인스턴스 생성
     pub async fn new(config: DatabaseConfig) -> Result<Self> {
-        // 디렉토리 생성
-        tokio::fs::create_dir_all(&config.data_directory).await?;
-        tokio::fs::create_dir_all(&config.commitlog_directory).await?;
-        
-        let commit_log = CommitLog::new(config.commitlog_directory.clone()).await?;
-        let query_engine = QueryEngine::new();
+        // 디렉토리 생성 (메모리 전용 모드는 디스크를 건드리지 않음)
+        let mut keyspace_commit_logs = HashMap::new();
+        let commit_log = if config.ephemeral {
+            None
+        } else {
+            tokio::fs::create_dir_all(&config.data_directory).await?;
+            Self::check_integrity(&config).await?;
+            for (keyspace, directory) in &config.commitlog_keyspace_directories {
+                let shared = *directory == config.commitlog_directory
+                    || config.commitlog_keyspace_directories.iter().any(|(other, other_directory)| other != keyspace && other_directory == directory);
+                if shared {
+                    return Err(CoreDBError::CommitLogError {
+                        message: format!("Commit log directory {} of keyspace {} is used by another commit log", directory.display(), keyspace),
+                    });
+                }
+                keyspace_commit_logs.insert(keyspace.clone(), Self::open_commit_log(&config, directory, None).await?);
+            }
+            Some(Self::open_commit_log(&config, &config.commitlog_directory, config.commitlog_archiver.clone()).await?)
+        };
+        let mut query_engine = QueryEngine::new();
+        query_engine.set_large_partition_threshold(config.large_partition_warning_threshold_mb * 1024 * 1024);
+        if !config.ephemeral {
+            query_engine.set_data_directory(config.data_directory.clone());
+        }
         
         let compaction_config = CompactionConfig {
             throughput_mb_per_sec: config.compaction_throughput_mb_per_sec,
-            max_concurrent_compactions: 2,
-            strategy: crate::compaction::CompactionStrategy::SizeTiered {
+            max_concurrent_compactions: config.concurrent_compactors,
+            write_mode: config.compaction_write_mode,
+            strategy: CompactionStrategy::SizeTiered {
                 min_threshold: 4,
                 max_threshold: 32,
             },
@@ -81,56 +633,369 @@ impl CoreDB {
         
         let compaction_manager = CompactionManager::new(compaction_config);
         
+        let read_admission = AdmissionControl::new("read", config.concurrent_reads, config.max_queued_requests);
+        let write_admission = AdmissionControl::new("write", config.concurrent_writes, config.max_queued_requests);
+        let read_path = SSTableReadPath {
+            access_mode: config.disk_access_mode,
+            key_cache: (config.key_cache_entries > 0).then(|| Arc::new(KeyCache::new(config.key_cache_entries))),
+            chunk_cache: (config.chunk_cache_size_mb > 0).then(|| Arc::new(ChunkCache::new(config.chunk_cache_size_mb * 1024 * 1024))),
+        };
+        
+        let keyspaces = Arc::new(RwLock::new(HashMap::new()));
+        let query_engine = Arc::new(RwLock::new(query_engine));
+        let compaction_manager = Arc::new(compaction_manager);
+        let metrics = Arc::new(Metrics::new());
+        let flusher = Flusher {
+            keyspaces: keyspaces.clone(),
+            commit_log: commit_log.clone(),
+            keyspace_commit_logs: keyspace_commit_logs.clone(),
+            query_engine: query_engine.clone(),
+            compaction_manager: compaction_manager.clone(),
+            read_path: read_path.clone(),
+            config: config.clone(),
+            flush_threshold_mb: Arc::new(AtomicU64::new(config.memtable_flush_threshold_mb)),
+            max_age_secs: Arc::new(AtomicU64::new(config.memtable_max_age_secs)),
+            pending: Arc::default(),
+            metrics: metrics.clone(),
+            engine_tables: Arc::default(),
+        };
+        let slow_query_threshold_ms = AtomicU64::new(config.slow_query_threshold_ms);
+        let disk_monitor = if config.ephemeral {
+            None
+        } else {
+            let directories = [&config.data_directory, &config.commitlog_directory].into_iter()
+                .chain(config.commitlog_keyspace_directories.values())
+                .cloned()
+                .collect();
+            let monitor = DiskMonitor::new(directories, config.disk_usage_warn_percent, config.disk_usage_fail_percent);
+            // 디스크가 이미 찬 채로 시작하면 첫 쓰기부터 거부
+            monitor.check().await;
+            Some(Arc::new(monitor))
+        };
+        let quotas = KeyspaceQuotas::new(&config.keyspace_quotas);
         let mut db = Self {
-            keyspaces: Arc::new(RwLock::new(HashMap::new())),
-            commit_log: Arc::new(RwLock::new(commit_log)),
-            query_engine: Arc::new(RwLock::new(query_engine)),
+            keyspaces,
+            commit_log,
+            keyspace_commit_logs,
+            query_engine,
             config,
-            compaction_manager: Arc::new(compaction_manager),
+            compaction_manager,
+            read_admission: Arc::new(read_admission),
+            write_admission: Arc::new(write_admission),
+            read_path,
+            flusher,
+            flush_wakeup: Arc::new(tokio::sync::Notify::new()),
+            slow_query_threshold_ms,
+            disk_monitor,
+            draining: AtomicBool::new(false),
+            quotas,
+            schema_version: Mutex::new(None),
+            migration_lock: Mutex::new(()),
+            host_id: Uuid::new_v4(),
+            rpc_address: OnceLock::new(),
+            clients: ClientRegistry::new(),
+            metrics,
         };
         
         // 시스템 키스페이스 초기화
         db.create_system_keyspaces().await?;
         
+        // 이전 실행에서 만든 키스페이스와 테이블을 쿼리를 받기 전에 되살림
+        let loaded = db.load_schema().await?;
+        if loaded > 0 {
+            tracing::info!("Loaded {} schema changes from {}.{}", loaded, SYSTEM_SCHEMA_KEYSPACE, SCHEMA_VERSIONS_TABLE);
+        }
+        
         // 백그라운드 작업 시작
         db.start_background_tasks().await;
         
         Ok(db)
     }
     
+    /// 테이블을 열기 전에 데이터 디렉토리 검사 (`--fsck`)
+    async fn check_integrity(config: &DatabaseConfig) -> Result<()> {
+        let repair = match config.integrity_check {
+            IntegrityCheckMode::Off => return Ok(()),
+            IntegrityCheckMode::Check => false,
+            IntegrityCheckMode::Repair => true,
+        };
+        let report = check_data_directory(&config.data_directory, repair).await?;
+        let unresolved = report.unresolved().count();
+        if unresolved > 0 {
+            return Err(CoreDBError::IntegrityCheckFailed { issues: unresolved, report: report.to_string() });
+        }
+        if report.is_clean() {
+            tracing::info!("Startup integrity check passed: {}", report);
+        } else {
+            tracing::warn!("Startup integrity check repaired data directory: {}", report);
+        }
+        Ok(())
+    }
+    
+    /// directory의 커밋 로그 열기 (세그먼트 크기, fsync 방식, 암호화는 모든 커밋 로그가 공유)
+    async fn open_commit_log(config: &DatabaseConfig, directory: &Path, archiver: Option<Arc<dyn CommitLogArchiver>>) -> Result<Arc<CommitLog>> {
+        tokio::fs::create_dir_all(directory).await?;
+        let segment_size = config.commitlog_segment_size_mb.max(1) * 1024 * 1024;
+        let mut commit_log = CommitLog::open_with_encryption(directory.to_path_buf(), segment_size, config.commitlog_encryption.clone()).await?
+            .with_sync(config.commitlog_sync);
+        if let Some(archiver) = archiver {
+            commit_log = commit_log.with_archiver(archiver);
+        }
+        Ok(Arc::new(commit_log))
+    }
+    
+    /// keyspace의 쓰기를 기록하는 커밋 로그 (메모리 전용 모드에서는 None)
+    pub fn commit_log_for(&self, keyspace: &str) -> Option<&Arc<CommitLog>> {
+        self.keyspace_commit_logs.get(keyspace).or(self.commit_log.as_ref())
+    }
+    
+    /// 공용 커밋 로그와 전용 커밋 로그 모두
+    fn commit_logs(&self) -> impl Iterator<Item = &Arc<CommitLog>> {
+        self.commit_log.iter().chain(self.keyspace_commit_logs.values())
+    }
+    
     /// CQL 쿼리 실행
     pub async fn execute_cql(&self, query: &str) -> Result<QueryResult> {
-        let parsed = crate::query::parser::CqlParser::parse(query)?;
+        let (result, _, _) = self.run_cql(query, None, None, false).await?;
+        Ok(result)
+    }
+    
+    /// 로그인한 역할의 권한으로 CQL 쿼리 실행
+    pub async fn execute_cql_as(&self, role: &Role, query: &str) -> Result<QueryResult> {
+        let (result, _, _) = self.run_cql(query, None, Some(role), false).await?;
+        Ok(result)
+    }
+    
+    /// 이 요청만 추적하며 CQL 쿼리 실행 (단계를 system_traces에 기록하고 추적 세션 ID를 함께 반환)
+    pub async fn execute_cql_traced(&self, query: &str) -> Result<(QueryResult, Option<Uuid>)> {
+        let (result, _, trace_id) = self.run_cql(query, None, None, true).await?;
+        Ok((result, trace_id))
+    }
+    
+    /// 로그인한 역할의 권한으로 실행하는 execute_cql_traced
+    pub async fn execute_cql_traced_as(&self, role: &Role, query: &str) -> Result<(QueryResult, Option<Uuid>)> {
+        let (result, _, trace_id) = self.run_cql(query, None, Some(role), true).await?;
+        Ok((result, trace_id))
+    }
+    
+    /// 페이지 단위 CQL 쿼리 실행 (다음 페이지 상태를 함께 반환)
+    pub async fn execute_cql_paged(&self, query: &str, page_size: usize, paging_state: Option<PagingState>) -> Result<(QueryResult, Option<PagingState>)> {
+        let (result, next_state, _) = self.run_cql(query, Some((page_size, paging_state)), None, false).await?;
+        Ok((result, next_state))
+    }
+    
+    /// 테이블에 쓰기 트리거 등록 (CQL 쓰기마다 메모리 테이블 반영 전후로 호출)
+    pub async fn register_trigger(&self, keyspace: &str, table: &str, name: &str, trigger: impl Trigger + 'static) -> Result<()> {
+        let mut engine = self.query_engine.write().await;
+        engine.register_trigger(keyspace, table, name, Arc::new(trigger))
+    }
+    
+    /// 트리거 제거 (제거되었으면 true)
+    pub async fn drop_trigger(&self, keyspace: &str, table: &str, name: &str) -> bool {
+        let mut engine = self.query_engine.write().await;
+        engine.drop_trigger(keyspace, table, name)
+    }
+    
+    /// role이 None이면 권한을 확인하지 않음 (내장 사용, 로컬 셸, 익명 허용 서버)
+    async fn run_cql(&self, query: &str, paging: Option<(usize, Option<PagingState>)>, role: Option<&Role>, traced: bool) -> Result<(QueryResult, Option<PagingState>, Option<Uuid>)> {
+        let started = std::time::Instant::now();
+        let parsed = crate::query::parser::CqlParser::parse(query);
+        let kind = parsed.as_ref().map_or("invalid", CqlStatement::kind);
+        let span = tracing::info_span!("cql",
+            db.system = "coredb",
+            db.operation = kind,
+            db.namespace = parsed.as_ref().ok().and_then(CqlStatement::data_keyspace),
+            db.statement = %redact_passwords(query),
+            db.user = role.map(|role| role.name.as_str()),
+            otel.status_code = tracing::field::Empty,
+            otel.status_message = tracing::field::Empty,
+        );
+        let result = match parsed {
+            Ok(parsed) => self.dispatch_cql(query, parsed, paging, role, traced).instrument(span.clone()).await,
+            Err(e) => Err(e),
+        };
+        self.metrics.record_query(kind, started.elapsed(), result.is_ok());
+        if let Err(e) = &result {
+            span.record("otel.status_code", "ERROR");
+            span.record("otel.status_message", e.to_string());
+        }
+        
+        let threshold_ms = self.slow_query_threshold_ms.load(Ordering::Relaxed);
+        let elapsed = started.elapsed();
+        if threshold_ms > 0 && elapsed.as_millis() >= threshold_ms as u128 {
+            tracing::warn!("Slow query ({} ms, threshold {} ms{}): {}",
+                           elapsed.as_millis(), threshold_ms, if result.is_err() { ", failed" } else { "" }, redact_passwords(query));
+        }
+        result
+    }
+    
+    async fn dispatch_cql(&self, query: &str, parsed: CqlStatement, paging: Option<(usize, Option<PagingState>)>, role: Option<&Role>, traced: bool) -> Result<(QueryResult, Option<PagingState>, Option<Uuid>)> {
+        let mut trace = traced.then(|| QueryTrace::new(&redact_passwords(query)));
+        if let Some(trace) = trace.as_mut() {
+            trace.record(format!("Parsed statement: {}", redact_passwords(query)));
+        }
+        if let Some(role) = role {
+            self.authorize(role, &parsed).await?;
+        }
+        if let Some(result) = self.execute_role_statement(&parsed).await? {
+            return Ok((result, None, None));
+        }
+        let target = match &parsed {
+            CqlStatement::Insert { keyspace, table, .. }
+            | CqlStatement::Update { keyspace, table, .. }
+            | CqlStatement::Delete { keyspace, table, .. }
+            | CqlStatement::CreateIndex { keyspace, table, .. } => Some((keyspace, table)),
+            CqlStatement::CreateTable { keyspace, name, .. }
+            | CqlStatement::AlterTable { keyspace, name, .. }
+            | CqlStatement::DropTable { keyspace, name } => Some((keyspace, name)),
+            _ => None,
+        };
+        if let Some((keyspace, table)) = target.filter(|(keyspace, table)| virtual_table(keyspace, table).is_some()) {
+            return Err(CoreDBError::ReadOnlyTable { keyspace: keyspace.clone(), table: table.clone() });
+        }
+        let mut schema_version = match parsed.is_schema_change() {
+            true => Some(self.lock_schema_version().await?),
+            false => None,
+        };
+        // 슈퍼유저가 아닌 역할이 새로 만든 키스페이스와 테이블은 만든 역할이 모든 권한을 가짐
+        let created = match (&parsed, role.filter(|role| !role.is_superuser)) {
+            (CqlStatement::CreateKeyspace { name, .. }, Some(role)) if self.query_engine.read().await.keyspace_definition(name).is_none() => {
+                Some((Resource::Keyspace(name.clone()), role))
+            },
+            (CqlStatement::CreateTable { keyspace, name, .. }, Some(role)) if !self.query_engine.read().await.has_table(keyspace, name) => {
+                Some((Resource::Table(keyspace.clone(), name.clone()), role))
+            },
+            _ => None,
+        };
+        
+        // 키스페이스 한도는 전역 허가보다 먼저 확인해 한도를 넘은 쿼리가 실행 자리를 차지하지 않게 함
+        let _quota_permit = match parsed.data_keyspace().and_then(|keyspace| self.quotas.limiter(keyspace)) {
+            Some(limiter) => limiter.try_acquire_query()?,
+            None => None,
+        };
+        if let (Some(keyspace), false) = (parsed.data_keyspace(), matches!(parsed, CqlStatement::Select { .. })) {
+            self.check_write_quota(keyspace).await?;
+        }
+        
+        // 읽기/쓰기 동시 실행 수 제한 (허가는 실행이 끝날 때까지 유지)
+        let _permit = match &parsed {
+            CqlStatement::Select { .. } => Some(self.read_admission.acquire().await?),
+            statement if self.is_mutation(statement) => Some(self.write_admission.acquire().await?),
+            _ => None,
+        };
         
-        // 커밋 로그에 기록 (변경 작업인 경우)
+        // 커밋 로그에 기록 (변경 작업인 경우, 쿼리 엔진에 반영할 때까지 쿼리 엔진 테이블 플러시를 막음)
+        let mut logged = None;
+        let _engine_write = match self.is_mutation(&parsed) {
+            true => Some(self.flusher.engine_tables.writes.read().await),
+            false => None,
+        };
         if self.is_mutation(&parsed) {
-            self.log_mutation(&parsed).await?;
+            self.check_not_draining()?;
+            self.check_disk_space()?;
+            logged = self.log_mutation(&parsed, query).await?;
+            if let Some(trace) = trace.as_mut() {
+                trace.record("Appended mutation to commit log");
+            }
         }
         
-        // 쿼리 엔진에서 실행
-        let mut engine = self.query_engine.write().await;
-        let result = engine.execute(parsed).await?;
+        // 컴팩션 전략 변경은 진행 중인 컴팩션을 기다려야 하므로 쿼리 엔진 실행을 거치지 않음
+        if let CqlStatement::AlterTable { keyspace, name, compaction_strategy } = &parsed {
+            self.set_compaction_strategy(keyspace, name, compaction_strategy.clone()).await?;
+            if let Some(version) = schema_version.as_mut() {
+                self.record_schema_change(version, query).await?;
+            }
+            return Ok((QueryResult::success(), None, None));
+        }
         
-        // 메모리 테이블 플러시 체크
-        self.check_memtable_flush().await?;
+        // 가상 테이블은 조회할 때마다 현재 상태로 다시 채움
+        let virtual_rows = match &parsed {
+            CqlStatement::Select { keyspace, table, .. } => match virtual_table(keyspace, table) {
+                Some(table) => Some((table, self.virtual_table_rows(table).await?)),
+                None => None,
+            },
+            _ => None,
+        };
         
-        Ok(result)
+        // CQL 테이블의 디스크 디렉토리는 쿼리 엔진에서 만들거나 지운 뒤 열거나 지움
+        let engine_table_change = match &parsed {
+            CqlStatement::CreateTable { keyspace, name, .. } => Some((true, keyspace.clone(), Some(name.clone()))),
+            CqlStatement::DropTable { keyspace, name } => Some((false, keyspace.clone(), Some(name.clone()))),
+            CqlStatement::DropKeyspace { name } => Some((false, name.clone(), None)),
+            _ => None,
+        };
+        
+        // 쿼리 엔진에서 실행. 데이터 변경과 SELECT는 엔진 읽기 잠금으로 다른 요청과 동시에 실행하고
+        // 스키마 변경과 가상 테이블 조회만 쓰기 잠금을 잡음 (추적 기록은 요청마다 따로 쌓임)
+        let shared = logged.is_some() || (matches!(parsed, CqlStatement::Select { .. }) && virtual_rows.is_none());
+        let (executed, trace) = QueryTrace::scope(trace, async {
+            if !shared {
+                let mut engine = self.query_engine.write().await;
+                if let Some((table, rows)) = virtual_rows {
+                    engine.refresh_virtual_table(table, rows).await?;
+                }
+                return match paging {
+                    Some((page_size, paging_state)) => engine.execute_paged(parsed, page_size, paging_state).await,
+                    None => engine.execute(parsed).await.map(|result| (result, None)),
+                };
+            }
+            let engine = self.query_engine.read().await;
+            let Some((keyspace, table, mutation)) = logged else {
+                return engine.select(parsed, paging).await;
+            };
+            // 기록한 행을 그대로 반영해 재생 결과와 같게 함
+            let large_partition = engine.write_mutation(&keyspace, &table, mutation).await?;
+            drop(engine);
+            if let Some((partition_key, size)) = large_partition {
+                self.query_engine.write().await.record_large_partition(&keyspace, &table, &partition_key, size).await?;
+            }
+            Ok((QueryResult::success(), None))
+        }).await;
+        // 실패한 쿼리도 추적 기록은 남김
+        let trace_id = match trace {
+            Some(trace) => Some(self.query_engine.write().await.finish_trace(trace).await?),
+            None => None,
+        };
+        let (result, next_state) = executed?;
+        match engine_table_change {
+            Some((true, keyspace, Some(table))) => self.open_engine_table(&keyspace, &table, true).await?,
+            Some((false, keyspace, table)) => {
+                self.drop_engine_tables(|dropped_keyspace, dropped_table| dropped_keyspace == keyspace && table.as_ref().is_none_or(|table| table == dropped_table)).await?;
+            },
+            _ => {},
+        }
+        if let Some(version) = schema_version.as_mut() {
+            self.record_schema_change(version, query).await?;
+        }
+        
+        if let Some((resource, role)) = created {
+            let row = resource.permissions_row(&role.name, &Permission::ALL, true, chrono::Utc::now().timestamp_micros());
+            self.insert_row(SYSTEM_AUTH_KEYSPACE, ROLE_PERMISSIONS_TABLE, row).await?;
+        }
+        
+        Ok((result, next_state, trace_id))
     }
     
     /// 키스페이스 생성
     pub async fn create_keyspace(&self, name: String, replication_factor: u32) -> Result<()> {
+        self.create_keyspace_with_definition(KeyspaceDefinition {
+            name,
+            replication_factor,
+            strategy: ReplicationStrategy::SimpleStrategy,
+            durable_writes: true,
+        }).await
+    }
+    
+    /// 정의로 키스페이스 생성 (durable_writes = false면 이 키스페이스의 쓰기는 커밋 로그를 거치지 않음)
+    pub async fn create_keyspace_with_definition(&self, definition: KeyspaceDefinition) -> Result<()> {
         let keyspace = Keyspace {
-            name: name.clone(),
-            definition: KeyspaceDefinition {
-                name: name.clone(),
-                replication_factor,
-                strategy: ReplicationStrategy::SimpleStrategy,
-            },
+            name: definition.name.clone(),
+            definition,
             tables: Arc::new(RwLock::new(HashMap::new())),
         };
         
         let mut keyspaces = self.keyspaces.write().await;
-        keyspaces.insert(name, keyspace);
+        keyspaces.insert(keyspace.name.clone(), keyspace);
         
         Ok(())
     }
@@ -139,356 +1004,5031 @@ impl CoreDB {
     pub async fn create_table(&self, keyspace: String, table: String, schema: TableSchema) -> Result<()> {
         schema.validate()?;
         
+        // 이전 실행에서 플러시된 SSTable을 매니페스트 기준으로 다시 연결
+        let (manifest, sstables) = if self.config.ephemeral {
+            (None, Vec::new())
+        } else {
+            let sstable_dir = self.config.data_directory.join(&keyspace).join(&table);
+            let (manifest, sstables) = Manifest::open_table(&sstable_dir).await?;
+            let sstables = sstables.into_iter()
+                .map(|sstable| Arc::new(self.read_path.apply(sstable)))
+                .collect();
+            (Some(manifest), sstables)
+        };
+        
         let memtable = Arc::new(Memtable::new(Arc::new(schema.clone())));
+        if let (Some(commit_log), Some(manifest)) = (self.commit_log_for(&keyspace), &manifest) {
+            // 보관된 세그먼트는 공용 커밋 로그의 것이라 전용 로그를 쓰는 키스페이스에는 적용하지 않음
+            let restore = self.config.commitlog_restore.as_ref().filter(|_| !self.keyspace_commit_logs.contains_key(&keyspace));
+            let rows = Self::replay_commit_log(commit_log, restore, manifest.replay_position, &keyspace, &table).await?;
+            let replayed = rows.len();
+            for row in rows {
+                memtable.put(row)?;
+            }
+            if replayed > 0 {
+                tracing::info!("Replayed {} commit log entries into {}.{}", replayed, keyspace, table);
+            }
+        }
+        let reopened = !sstables.is_empty();
         let table_struct = Table {
+            row_cache: Table::build_row_cache(&schema),
             schema: Arc::new(schema),
             memtables: Vec::new(),
-            sstables: Vec::new(),
+            sstables,
             current_memtable: memtable,
+            manifest,
+            metrics: Arc::new(TableMetrics::default()),
         };
         
         let keyspaces = self.keyspaces.read().await;
         if let Some(ks) = keyspaces.get(&keyspace) {
             let mut tables = ks.tables.write().await;
-            tables.insert(table, table_struct);
+            tables.insert(table.clone(), table_struct);
         } else {
             return Err(CoreDBError::KeyspaceNotFound { keyspace });
         }
         
+        // 다시 연 SSTable도 다음 플러시를 기다리지 않고 현재 전략으로 평가
+        if reopened {
+            self.compaction_manager.schedule_compaction(&keyspace, &table).await;
+        }
+        
         Ok(())
     }
     
-    /// 행 삽입
-    pub async fn insert_row(&self, keyspace: &str, table: &str, row: crate::schema::Row) -> Result<()> {
-        // 커밋 로그에 기록
-        let commit_entry = crate::wal::CommitLogEntry {
-            keyspace: keyspace.to_string(),
-            table: table.to_string(),
-            mutation: Mutation::Insert(row.clone()),
-            timestamp: chrono::Utc::now().timestamp_micros(),
-        };
-        
-        self.commit_log.write().await.append(commit_entry).await?;
+    /// 플러시되지 않은 테이블의 커밋 로그 엔트리 중 메모리 테이블에 다시 반영할 행 (기록 순서대로)
+    ///
+    /// 매니페스트의 재생 위치 앞은 이미 SSTable에 있으므로 건너뜀. 같은 쓰기를 두 번 반영해도
+    /// 타임스탬프가 같아 결과는 같음. 시점 복구 중이면 보관된 세그먼트를 먼저 재생하고 복구 시각 뒤의 쓰기는 건너뜀
+    async fn replay_commit_log(commit_log: &CommitLog, restore: Option<&CommitLogRestore>, since: CommitLogPosition, keyspace: &str, table: &str) -> Result<Vec<crate::schema::Row>> {
+        let mut entries: Vec<CommitLogEntry> = Vec::new();
+        if let Some(restore) = restore {
+            entries = CommitLog::replay_directory(&restore.directory, since, commit_log.encryption()).await?;
+        }
+        entries.extend(commit_log.replay_since(since).await?);
+        let point_in_time = restore.and_then(|restore| restore.point_in_time).unwrap_or(i64::MAX);
         
-        // 메모리 테이블에 추가
-        let keyspaces = self.keyspaces.read().await;
-        if let Some(ks) = keyspaces.get(keyspace) {
-            let tables = ks.tables.read().await;
-            if let Some(tbl) = tables.get(table) {
-                tbl.current_memtable.put(row)?;
-            } else {
-                return Err(CoreDBError::TableNotFound { table: table.to_string() });
+        let mut rows = Vec::new();
+        for entry in entries {
+            if entry.keyspace != keyspace || entry.table != table || entry.timestamp > point_in_time {
+                continue;
+            }
+            match entry.mutation {
+                Mutation::Insert(row) => rows.push(row),
+                // 쓰기 경로가 아직 기록하지 않는 뮤테이션
+                Mutation::Delete { .. } | Mutation::PartitionDelete { .. } => {
+                    tracing::warn!("Skipping unsupported {:?} in commit log of {}.{}", entry.mutation, keyspace, table);
+                },
+                // 저장 계층 테이블의 스키마는 매니페스트에서, CQL 스키마는 system_schema.schema_versions에서 읽음
+                Mutation::Schema { .. } => {},
             }
-        } else {
-            return Err(CoreDBError::KeyspaceNotFound { keyspace: keyspace.to_string() });
         }
-        
-        // 메모리 테이블 크기 체크 및 플러시
-        self.check_memtable_flush().await?;
-        
-        Ok(())
+        Ok(rows)
     }
     
-    /// 행 조회
-    pub async fn get_row(&self, keyspace: &str, table: &str, partition_key: &crate::schema::PartitionKey, clustering_key: &Option<crate::schema::ClusteringKey>) -> Result<Option<crate::schema::Row>> {
-        let keyspaces = self.keyspaces.read().await;
-        if let Some(ks) = keyspaces.get(keyspace) {
-            let tables = ks.tables.read().await;
-            if let Some(tbl) = tables.get(table) {
-                // 메모리 테이블에서 먼저 검색
-                if let Some(row) = tbl.current_memtable.get(partition_key, clustering_key) {
-                    return Ok(Some(row));
-                }
-                
-                // SSTable에서 검색
-                for sstable in &tbl.sstables {
-                    if let Some(partition) = sstable.read_partition(partition_key).await? {
-                        // 클러스터링 키가 있다면 해당 행만 반환
-                        if let Some(ref ck) = clustering_key {
-                            // 파티션 내에서 클러스터링 키로 검색
-                            if let Some(row_entry) = partition.rows.get(&Some(ck.clone())) {
-                                return Ok(Some(row_entry.value().clone()));
-                            }
-                        } else {
-                            // 클러스터링 키가 없으면 첫 번째 행 반환
-                            if let Some(row_entry) = partition.rows.iter().next() {
-                                return Ok(Some(row_entry.value().clone()));
-                            }
-                        }
-                    }
-                }
-            }
+    /// CQL로 만든 쿼리 엔진 테이블의 디렉토리를 열어 플러시된 SSTable을 붙이고 커밋 로그의 쓰기를 다시 반영
+    ///
+    /// created면 방금 만든 테이블이라 재생 위치를 커밋 로그 끝으로 옮김 (지웠다 같은 이름으로 다시 만든
+    /// 테이블이 지운 테이블의 쓰기를 재생하지 않도록). 같은 이름의 저장 계층 테이블이 있으면 아무것도 하지 않음
+    async fn open_engine_table(&self, keyspace: &str, table: &str, created: bool) -> Result<()> {
+        if self.config.ephemeral {
+            return Ok(());
+        }
+        let stored = match self.keyspaces.read().await.get(keyspace) {
+            Some(ks) => ks.tables.read().await.contains_key(table),
+            None => false,
+        };
+        if stored {
+            return Ok(());
         }
         
-        Ok(None)
-    }
-    
-    /// 메모리 테이블 플러시 체크
-    async fn check_memtable_flush(&self) -> Result<()> {
-        let keyspaces = self.keyspaces.read().await;
+        if self.flusher.engine_tables.manifests.lock().await.contains_key(&(keyspace.to_string(), table.to_string())) {
+            return Ok(());
+        }
         
-        for (keyspace_name, keyspace) in keyspaces.iter() {
-            let tables = keyspace.tables.read().await;
-            
-            for (table_name, table) in tables.iter() {
-                if table.current_memtable.size_bytes() > self.config.memtable_flush_threshold_mb * 1024 * 1024 {
-                    self.flush_memtable(keyspace_name, table_name).await?;
-                }
+        let directory = self.config.data_directory.join(keyspace).join(table);
+        let (mut manifest, sstables) = Manifest::open_table(&directory).await?;
+        // 지운 같은 이름 테이블의 전문 인덱스가 남아 있으면 새 테이블 것으로 열리지 않게 지움
+        #[cfg(feature = "fulltext")]
+        if created {
+            match tokio::fs::remove_dir_all(directory.join(crate::storage::FULLTEXT_DIRECTORY)).await {
+                Ok(()) => {},
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {},
+                Err(e) => return Err(e.into()),
             }
         }
+        let commit_log = self.commit_log_for(keyspace);
+        let rows = match commit_log {
+            Some(commit_log) if created => {
+                manifest.advance_replay_position(commit_log.position()).await?;
+                Vec::new()
+            },
+            Some(commit_log) => {
+                let restore = self.config.commitlog_restore.as_ref().filter(|_| !self.keyspace_commit_logs.contains_key(keyspace));
+                Self::replay_commit_log(commit_log, restore, manifest.replay_position, keyspace, table).await?
+            },
+            None => Vec::new(),
+        };
         
+        let mut manifests = self.flusher.engine_tables.manifests.lock().await;
+        let mut engine = self.query_engine.write().await;
+        for sstable in sstables {
+            engine.add_sstable(keyspace.to_string(), table.to_string(), Arc::new(self.read_path.apply(sstable)));
+        }
+        // 기록된 행은 트리거를 거친 뒤의 행이라 그대로 반영
+        let replayed = rows.len();
+        for row in rows {
+            engine.apply_mutation(keyspace.to_string(), table.to_string(), Mutation::Insert(row)).await?;
+        }
+        if replayed > 0 {
+            tracing::info!("Replayed {} commit log entries into {}.{}", replayed, keyspace, table);
+        }
+        // 인덱스는 메모리에만 있으므로 SSTable과 재생한 행으로 다시 채움
+        engine.rebuild_indexes(keyspace, table).await?;
+        manifests.insert((keyspace.to_string(), table.to_string()), manifest);
         Ok(())
     }
     
-    /// 메모리 테이블 플러시
-    async fn flush_memtable(&self, keyspace: &str, table: &str) -> Result<()> {
-        let mut keyspaces = self.keyspaces.write().await;
-        if let Some(ks) = keyspaces.get_mut(keyspace) {
-            let mut tables = ks.tables.write().await;
-            if let Some(tbl) = tables.get_mut(table) {
-                // 새 메모리 테이블 생성
-                let new_memtable = Arc::new(Memtable::new(tbl.schema.clone()));
-                let old_memtable = std::mem::replace(&mut tbl.current_memtable, new_memtable);
-                
-                // 기존 메모리 테이블을 SSTable로 변환
-                let sstable_dir = self.config.data_directory
-                    .join(keyspace)
-                    .join(table);
-                tokio::fs::create_dir_all(&sstable_dir).await?;
-                
-                let sstable = SSTable::create_from_memtable(
-                    &old_memtable,
-                    &sstable_dir,
-                    crate::storage::sstable::CompressionType::LZ4
-                ).await?;
-                
-                tbl.sstables.push(Arc::new(sstable));
-                
-                // 컴팩션 트리거
-                self.compaction_manager.schedule_compaction(keyspace, table).await;
+    /// 지운 쿼리 엔진 테이블의 디렉토리 삭제 (CQL로 만든 테이블만)
+    async fn drop_engine_tables(&self, dropped: impl Fn(&str, &str) -> bool) -> Result<()> {
+        let mut manifests = self.flusher.engine_tables.manifests.lock().await;
+        let tables: Vec<_> = manifests.keys().filter(|(keyspace, table)| dropped(keyspace, table)).cloned().collect();
+        for key in tables {
+            manifests.remove(&key);
+            let directory = self.config.data_directory.join(&key.0).join(&key.1);
+            match tokio::fs::remove_dir_all(&directory).await {
+                Ok(()) => {},
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {},
+                Err(e) => return Err(e.into()),
             }
         }
-        
         Ok(())
     }
     
-    /// 시스템 키스페이스 생성
-    async fn create_system_keyspaces(&mut self) -> Result<()> {
+    /// 테이블별 메모리 테이블 플러시 임계값 변경 (None이면 전역 설정 사용)
+    pub async fn set_memtable_flush_threshold(&self, keyspace: &str, table: &str, threshold_mb: Option<u64>) -> Result<()> {
+        if threshold_mb == Some(0) {
+            return Err(CoreDBError::InvalidSchema {
+                message: "memtable_flush_threshold_mb must be greater than 0".to_string(),
+            });
+        }
+        
+        {
+            let keyspaces = self.keyspaces.read().await;
+            let ks = keyspaces.get(keyspace).ok_or_else(|| CoreDBError::KeyspaceNotFound { keyspace: keyspace.to_string() })?;
+            let mut tables = ks.tables.write().await;
+            let tbl = tables.get_mut(table).ok_or_else(|| CoreDBError::TableNotFound { table: table.to_string() })?;
+            let mut schema = (*tbl.schema).clone();
+            schema.options.memtable_flush_threshold_mb = threshold_mb;
+            tbl.schema = Arc::new(schema);
+        }
+        
+        // 임계값을 낮춘 경우 바로 플러시될 수 있음
+        self.flush_wakeup.notify_one();
+        Ok(())
+    }
+    
+    /// 테이블별 행 캐시 크기 변경 (None이나 0이면 행 캐시를 끔, 기존 캐시 내용은 버림)
+    pub async fn set_row_cache_size(&self, keyspace: &str, table: &str, size_mb: Option<u64>) -> Result<()> {
+        let keyspaces = self.keyspaces.read().await;
+        let ks = keyspaces.get(keyspace).ok_or_else(|| CoreDBError::KeyspaceNotFound { keyspace: keyspace.to_string() })?;
+        let mut tables = ks.tables.write().await;
+        let tbl = tables.get_mut(table).ok_or_else(|| CoreDBError::TableNotFound { table: table.to_string() })?;
+        let mut schema = (*tbl.schema).clone();
+        schema.options.row_cache_size_mb = size_mb;
+        tbl.row_cache = Table::build_row_cache(&schema);
+        tbl.schema = Arc::new(schema);
+        Ok(())
+    }
+    
+    /// 컴팩션 처리량 한도 (MB/s, 0이면 제한 없음)
+    pub fn compaction_throughput(&self) -> u64 {
+        self.compaction_manager.throughput_mb_per_sec()
+    }
+    
+    /// 컴팩션 처리량 한도 변경 (진행 중인 컴팩션에도 바로 적용, 0이면 제한 없음)
+    pub fn set_compaction_throughput(&self, mb_per_sec: u64) {
+        self.compaction_manager.set_throughput_mb_per_sec(mb_per_sec);
+    }
+    
+    /// 현재 적용 중인 조정 가능 설정 (꺼진 캐시는 0)
+    pub fn settings(&self) -> TunableSettings {
+        TunableSettings {
+            memtable_flush_threshold_mb: Some(self.flusher.flush_threshold_mb.load(Ordering::Relaxed)),
+            memtable_max_age_secs: Some(self.flusher.max_age_secs.load(Ordering::Relaxed)),
+            compaction_throughput_mb_per_sec: Some(self.compaction_throughput()),
+            slow_query_threshold_ms: Some(self.slow_query_threshold_ms.load(Ordering::Relaxed)),
+            key_cache_entries: Some(self.read_path.key_cache.as_ref().map_or(0, |cache| cache.stats().capacity)),
+            chunk_cache_size_mb: Some(self.read_path.chunk_cache.as_ref().map_or(0, |cache| cache.stats().capacity_bytes / (1024 * 1024))),
+        }
+    }
+    
+    /// 조정 가능 설정을 재시작 없이 적용 (하나라도 잘못되면 아무것도 바꾸지 않음)
+    pub fn apply_settings(&self, settings: &TunableSettings) -> Result<()> {
+        if settings.memtable_flush_threshold_mb == Some(0) {
+            return Err(CoreDBError::Generic { message: "memtable_flush_threshold_mb must be greater than 0".to_string() });
+        }
+        if settings.key_cache_entries.is_some_and(|entries| entries > 0) && self.read_path.key_cache.is_none() {
+            return Err(CoreDBError::Generic { message: "key cache was disabled at startup and cannot be enabled at runtime".to_string() });
+        }
+        if settings.chunk_cache_size_mb.is_some_and(|size_mb| size_mb > 0) && self.read_path.chunk_cache.is_none() {
+            return Err(CoreDBError::Generic { message: "chunk cache was disabled at startup and cannot be enabled at runtime".to_string() });
+        }
+        
+        if let Some(threshold_mb) = settings.memtable_flush_threshold_mb {
+            self.flusher.flush_threshold_mb.store(threshold_mb, Ordering::Relaxed);
+        }
+        if let Some(max_age_secs) = settings.memtable_max_age_secs {
+            self.flusher.max_age_secs.store(max_age_secs, Ordering::Relaxed);
+        }
+        if let Some(mb_per_sec) = settings.compaction_throughput_mb_per_sec {
+            self.set_compaction_throughput(mb_per_sec);
+        }
+        if let Some(threshold_ms) = settings.slow_query_threshold_ms {
+            self.slow_query_threshold_ms.store(threshold_ms, Ordering::Relaxed);
+        }
+        if let (Some(entries), Some(cache)) = (settings.key_cache_entries, &self.read_path.key_cache) {
+            cache.set_capacity(entries);
+        }
+        if let (Some(size_mb), Some(cache)) = (settings.chunk_cache_size_mb, &self.read_path.chunk_cache) {
+            cache.set_capacity(size_mb * 1024 * 1024);
+        }
+        // 임계값을 낮춘 경우 바로 플러시될 수 있음
+        self.flush_wakeup.notify_one();
+        Ok(())
+    }
+    
+    /// 행 삽입
+    pub async fn insert_row(&self, keyspace: &str, table: &str, row: crate::schema::Row) -> Result<()> {
+        let started = std::time::Instant::now();
+        let result = async {
+            self.check_write_quota(keyspace).await?;
+            let permit = self.write_admission.acquire().await?;
+            self.write_row(keyspace, table, row, Some(permit)).await
+        }.await;
+        self.metrics.writes.record(started.elapsed(), result.is_ok());
+        result
+    }
+    
+    /// 쓰기 허가를 얻은 뒤의 행 삽입 (permit이 None이면 이미 허가를 쥔 문이 남기는 내부 기록)
+    async fn write_row(&self, keyspace: &str, table: &str, row: crate::schema::Row, permit: Option<tokio::sync::SemaphorePermit<'_>>) -> Result<()> {
+        self.check_not_draining()?;
+        self.check_disk_space()?;
+        self.check_write_backlog(keyspace, table).await?;
+        
+        let started = std::time::Instant::now();
+        let partition_key = row.partition_key.clone();
+        let keyspaces = self.keyspaces.read().await;
+        let ks = keyspaces.get(keyspace).ok_or_else(|| CoreDBError::KeyspaceNotFound { keyspace: keyspace.to_string() })?;
+        let tables = ks.tables.read().await;
+        let tbl = tables.get(table).ok_or_else(|| CoreDBError::TableNotFound { table: table.to_string() })?;
+        
+        // 커밋 로그에 기록 (테이블 잠금을 쥔 채 기록해 플러시가 기록과 반영 사이에 끼지 않음)
+        let mut wait_for_sync = None;
+        if let (Some(commit_log), true) = (self.commit_log_for(keyspace), ks.definition.durable_writes) {
+            let commit_entry = crate::wal::CommitLogEntry {
+                keyspace: keyspace.to_string(),
+                table: table.to_string(),
+                mutation: Mutation::Insert(row.clone()),
+                timestamp: chrono::Utc::now().timestamp_micros(),
+            };
+            let started = std::time::Instant::now();
+            wait_for_sync = Some((commit_log, commit_log.append(commit_entry).await?, started));
+        }
+        
+        // 메모리 테이블에 추가
+        tbl.current_memtable.put(row)?;
+        if let Some(row_cache) = &tbl.row_cache {
+            row_cache.invalidate(&partition_key);
+        }
+        let partition_size = tbl.current_memtable.partition_size(&partition_key);
+        let flush_due = self.flusher.memtable_due(tbl) || self.flusher.commit_log_over_limit(keyspace).is_some();
+        let metrics = tbl.metrics.clone();
+        drop(tables);
+        drop(keyspaces);
+        
+        // Batch 방식이면 이 쓰기가 fsync될 때까지 응답하지 않음 (기다리는 동안 테이블 잠금은 놓음)
+        if let Some((commit_log, end, started)) = wait_for_sync {
+            commit_log.wait_for_sync(end, started).await?;
+        }
+        drop(permit);
+        metrics.write_latency.record(started.elapsed());
+        
+        // 큰 파티션 경고
+        if let Some(size) = partition_size.filter(|size| size.bytes > self.large_partition_threshold_bytes()) {
+            self.query_engine.write().await.record_large_partition(keyspace, table, &partition_key, size).await?;
+        }
+        
+        // 플러시할 때가 됐으면 스케줄러를 깨움 (플러시는 백그라운드에서)
+        if flush_due {
+            self.flush_wakeup.notify_one();
+        }
+        
+        Ok(())
+    }
+    
+    /// drain 중이거나 끝났으면 쓰기 거부 (쓰기 허가를 얻은 뒤 확인해 drain이 실행 중인 쓰기를 기다릴 수 있게 함)
+    fn check_not_draining(&self) -> Result<()> {
+        if self.draining.load(Ordering::SeqCst) {
+            return Err(CoreDBError::Draining);
+        }
+        Ok(())
+    }
+    
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+    
+    /// 디스크 사용률이 한계 수위를 넘었으면 쓰기 거부 (커밋 로그나 플러시가 도중에 공간 부족으로 실패하지 않도록)
+    fn check_disk_space(&self) -> Result<()> {
+        self.disk_monitor.as_ref().map_or(Ok(()), |monitor| monitor.check_writes())
+    }
+    
+    /// 키스페이스의 초당 쓰기 수나 데이터 크기 한도를 넘었으면 쓰기 거부
+    async fn check_write_quota(&self, keyspace: &str) -> Result<()> {
+        let Some(limiter) = self.quotas.limiter(keyspace) else { return Ok(()) };
+        let used_bytes = if limiter.quota().max_disk_mb > 0 { self.keyspace_size_bytes(keyspace).await } else { 0 };
+        limiter.check_write(used_bytes).await
+    }
+    
+    /// 키스페이스 데이터 크기 (저장 계층 테이블과 CQL 테이블의 메모리 테이블 데이터와 SSTable 합)
+    pub async fn keyspace_size_bytes(&self, keyspace: &str) -> u64 {
+        let mut size_bytes = self.query_engine.read().await.keyspace_size_bytes(keyspace);
+        if let Some(ks) = self.keyspaces.read().await.get(keyspace) {
+            for table in ks.tables.read().await.values() {
+                size_bytes += std::iter::once(&table.current_memtable).chain(&table.memtables)
+                    .map(|memtable| memtable.memory().data_bytes)
+                    .sum::<u64>();
+                size_bytes += table.sstables.iter().map(|sstable| sstable.size_bytes).sum::<u64>();
+            }
+        }
+        size_bytes
+    }
+    
+    /// 키스페이스 한도 변경 (모두 0이면 한도 제거, 실행 중인 쿼리는 이전 한도로 끝남)
+    pub fn set_keyspace_quota(&self, keyspace: &str, quota: KeyspaceQuota) {
+        self.quotas.set(keyspace, quota);
+    }
+    
+    /// 플러시나 컴팩션이 밀린 테이블의 쓰기 거부 (읽기 증폭과 메모리가 끝없이 늘지 않도록)
+    async fn check_write_backlog(&self, keyspace: &str, table: &str) -> Result<()> {
+        let keyspaces = self.keyspaces.read().await;
+        let Some(ks) = keyspaces.get(keyspace) else { return Ok(()) };
+        let tables = ks.tables.read().await;
+        let Some(tbl) = tables.get(table) else { return Ok(()) };
+        for (operation, queued, limit) in [
+            ("flush", tbl.memtables.len(), self.config.max_pending_flushes),
+            ("compaction", tbl.sstables.len(), self.config.max_sstables_per_table),
+        ] {
+            if limit > 0 && queued >= limit {
+                tracing::warn!("Rejecting write to {}.{}: {} SSTables and {} memtables pending", keyspace, table, tbl.sstables.len(), tbl.memtables.len());
+                return Err(CoreDBError::Overloaded { operation: operation.to_string(), queued, limit });
+            }
+        }
+        Ok(())
+    }
+    
+    /// Apache Cassandra SSTable 가져오기 (`coredb import-sstables`)
+    ///
+    /// 디렉토리의 big 형식 SSTable을 헤더가 테이블 스키마와 맞는지 확인한 뒤 파일마다 별도 스레드에서
+    /// 읽고, 읽은 행은 원래 타임스탬프와 TTL 그대로 일반 쓰기 경로로 넣음
+    pub async fn import_cassandra_sstables(&self, keyspace: &str, table: &str, directory: &Path) -> Result<CassandraImportStats> {
+        let schema = {
+            let keyspaces = self.keyspaces.read().await;
+            let ks = keyspaces.get(keyspace).ok_or_else(|| CoreDBError::KeyspaceNotFound { keyspace: keyspace.to_string() })?;
+            let tables = ks.tables.read().await;
+            tables.get(table).ok_or_else(|| CoreDBError::TableNotFound { table: table.to_string() })?.schema.clone()
+        };
+        let paths = CassandraSSTable::find(directory)?;
+        if paths.is_empty() {
+            return Err(CoreDBError::Generic {
+                message: format!("No Cassandra SSTables (*-big-Data.db) in {}", directory.display()),
+            });
+        }
+        
+        let mut stats = CassandraImportStats::default();
+        for path in paths {
+            let sstable = CassandraSSTable::open(&path)?;
+            sstable.header.validate_schema(&schema)?;
+            tracing::info!("Importing Cassandra SSTable {} into {}.{}", path.display(), keyspace, table);
+            
+            let (sender, mut receiver) = tokio::sync::mpsc::channel(64);
+            let reader = tokio::task::spawn_blocking(move || -> Result<()> {
+                for partition in sstable.partitions()? {
+                    if sender.blocking_send(partition?).is_err() {
+                        break;
+                    }
+                }
+                Ok(())
+            });
+            while let Some(partition) = receiver.recv().await {
+                stats.partitions += 1;
+                stats.skipped_partition_deletions += partition.partition_deletion.is_some() as u64;
+                stats.skipped_range_tombstones += partition.range_tombstone_markers;
+                for row in partition.rows {
+                    self.insert_row(keyspace, table, row).await?;
+                    stats.rows += 1;
+                }
+            }
+            reader.await.map_err(|e| CoreDBError::Generic { message: format!("Cassandra SSTable reader failed: {}", e) })??;
+            stats.sstables += 1;
+        }
+        Ok(stats)
+    }
+    
+    /// 행 조회
+    pub async fn get_row(&self, keyspace: &str, table: &str, partition_key: &crate::schema::PartitionKey, clustering_key: &Option<crate::schema::ClusteringKey>) -> Result<Option<crate::schema::Row>> {
+        let keys = [(partition_key.clone(), clustering_key.clone())];
+        match self.multiget(keyspace, table, &keys).await {
+            Ok(mut rows) => Ok(rows.pop().flatten()),
+            Err(CoreDBError::KeyspaceNotFound { .. }) | Err(CoreDBError::TableNotFound { .. }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+    
+    /// 여러 행을 한 번에 조회 (결과는 입력 순서와 같음)
+    ///
+    /// SSTable마다 파일을 한 번만 열고 블룸 필터도 파티션 키당 한 번만 확인하며,
+    /// SSTable 읽기는 병렬로 수행
+    pub async fn multiget(&self, keyspace: &str, table: &str, keys: &[(crate::schema::PartitionKey, Option<crate::schema::ClusteringKey>)]) -> Result<Vec<Option<crate::schema::Row>>> {
+        let started = std::time::Instant::now();
+        let result = async {
+            let _permit = self.read_admission.acquire().await?;
+            self.read_rows(keyspace, table, keys).await
+        }.await;
+        self.metrics.reads.record(started.elapsed(), result.is_ok());
+        result
+    }
+    
+    /// 읽기 허가 없이 여러 행 조회 (내부 기록을 읽을 때)
+    #[tracing::instrument(name = "storage.read", skip_all, fields(db.namespace = keyspace, db.table = table, keys = keys.len()))]
+    async fn read_rows(&self, keyspace: &str, table: &str, keys: &[(crate::schema::PartitionKey, Option<crate::schema::ClusteringKey>)]) -> Result<Vec<Option<crate::schema::Row>>> {
+        
+        // 읽기 대상만 복사하고 잠금은 바로 해제
+        let started = std::time::Instant::now();
+        let (memtables, sstables, row_cache, metrics) = {
+            let keyspaces = self.keyspaces.read().await;
+            let ks = keyspaces.get(keyspace).ok_or_else(|| CoreDBError::KeyspaceNotFound { keyspace: keyspace.to_string() })?;
+            let tables = ks.tables.read().await;
+            let tbl = tables.get(table).ok_or_else(|| CoreDBError::TableNotFound { table: table.to_string() })?;
+            // 플러시 대기 중인 메모리 테이블도 함께 조회
+            let mut memtables = vec![tbl.current_memtable.clone()];
+            memtables.extend(tbl.memtables.iter().cloned());
+            (memtables, tbl.sstables.clone(), tbl.row_cache.clone(), tbl.metrics.clone())
+        };
+        if let Some(row_cache) = row_cache {
+            let rows = Self::multiget_cached(&row_cache, &memtables, sstables, keys, &metrics).await?;
+            metrics.read_latency.record(started.elapsed());
+            return Ok(rows);
+        }
+        
+        let partition_keys: Vec<crate::schema::PartitionKey> = keys.iter()
+            .map(|(partition_key, _)| partition_key.clone())
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        
+        let mut reads = tokio::task::JoinSet::new();
+        for sstable in sstables {
+            let partition_keys = partition_keys.clone();
+            reads.spawn(async move {
+                sstable.read_partitions(&partition_keys, &crate::storage::ReadOptions::all()).await
+            });
+        }
+        
+        let mut found: HashMap<crate::schema::PartitionKey, Vec<crate::storage::Partition>> = HashMap::new();
+        while let Some(read) = reads.join_next().await {
+            let partitions = read.map_err(|e| CoreDBError::Generic { message: format!("SSTable read task failed: {}", e) })??;
+            for (partition_key, partition) in partitions {
+                found.entry(partition_key).or_default().push(partition);
+            }
+        }
+        
+        // 모든 소스의 버전을 모아 셀 단위로 병합 (조회 순서와 무관한 결과)
+        let rows = keys.iter().map(|(partition_key, clustering_key)| {
+            let mut merged: Option<crate::schema::Row> = None;
+            let mut tombstones = 0;
+            for row in memtables.iter().filter_map(|memtable| memtable.get(partition_key, clustering_key)) {
+                tombstones += row.tombstone_count();
+                match merged.as_mut() {
+                    Some(existing) => existing.merge(row),
+                    None => merged = Some(row),
+                }
+            }
+            // 메모리 테이블과 같이 클러스터링 키가 같은 행만 병합 (다른 행의 셀이 섞이지 않도록)
+            for partition in found.get(partition_key).into_iter().flatten() {
+                if let Some(row) = partition.rows.get(clustering_key).map(|entry| entry.value().clone()) {
+                    tombstones += row.tombstone_count();
+                    match merged.as_mut() {
+                        Some(existing) => existing.merge(row),
+                        None => merged = Some(row),
+                    }
+                }
+            }
+            metrics.sstables_per_read.record_value(found.get(partition_key).map_or(0, Vec::len) as u64);
+            metrics.tombstones_per_read.record_value(tombstones);
+            merged
+        }).collect();
+        
+        metrics.read_latency.record(started.elapsed());
+        Ok(rows)
+    }
+    
+    /// 행 캐시를 거친 multiget (캐시에 없는 파티션은 모든 소스를 병합해 캐시에 넣음)
+    ///
+    /// 캐시 없는 조회와 같이 클러스터링 키가 같은 행만 반환
+    async fn multiget_cached(row_cache: &RowCache, memtables: &[Arc<Memtable>], sstables: Vec<Arc<SSTable>>, keys: &[(crate::schema::PartitionKey, Option<crate::schema::ClusteringKey>)], metrics: &TableMetrics) -> Result<Vec<Option<crate::schema::Row>>> {
+        // 캐시에 없어 SSTable에서 읽은 파티션마다 그 파티션이 있던 SSTable 수
+        let mut sstable_reads: HashMap<crate::schema::PartitionKey, u64> = HashMap::new();
+        let mut cached: HashMap<crate::schema::PartitionKey, Arc<crate::storage::CachedPartition>> = HashMap::new();
+        let mut missing = std::collections::BTreeSet::new();
+        for (partition_key, _) in keys {
+            if cached.contains_key(partition_key) || missing.contains(partition_key) {
+                continue;
+            }
+            match row_cache.get(partition_key) {
+                Some(partition) => {
+                    cached.insert(partition_key.clone(), partition);
+                },
+                None => {
+                    missing.insert(partition_key.clone());
+                },
+            }
+        }
+        
+        if !missing.is_empty() {
+            // 소스를 읽기 전의 세대 (읽는 동안 쓰기가 들어오면 결과를 캐시에 넣지 않음)
+            let generation = row_cache.generation();
+            let partition_keys: Vec<crate::schema::PartitionKey> = missing.into_iter().collect();
+            let mut merged: HashMap<crate::schema::PartitionKey, crate::storage::CachedPartition> = HashMap::new();
+            let mut add = |row: crate::schema::Row| {
+                let partition = merged.entry(row.partition_key.clone()).or_default();
+                match partition.get_mut(&row.clustering_key) {
+                    Some(existing) => existing.merge(row),
+                    None => {
+                        partition.insert(row.clustering_key.clone(), row);
+                    },
+                }
+            };
+            for memtable in memtables {
+                for partition_key in &partition_keys {
+                    memtable.range_scan(partition_key, &None, &None).into_iter().for_each(&mut add);
+                }
+            }
+            
+            let mut reads = tokio::task::JoinSet::new();
+            for sstable in sstables {
+                let partition_keys = partition_keys.clone();
+                reads.spawn(async move {
+                    sstable.read_partitions(&partition_keys, &crate::storage::ReadOptions::all()).await
+                });
+            }
+            while let Some(read) = reads.join_next().await {
+                let partitions = read.map_err(|e| CoreDBError::Generic { message: format!("SSTable read task failed: {}", e) })??;
+                for (partition_key, partition) in partitions {
+                    *sstable_reads.entry(partition_key).or_default() += 1;
+                    partition.rows.into_iter().for_each(|(_, row)| add(row));
+                }
+            }
+            
+            for partition_key in partition_keys {
+                let partition = Arc::new(merged.remove(&partition_key).unwrap_or_default());
+                row_cache.insert(partition_key.clone(), partition.clone(), generation);
+                cached.insert(partition_key, partition);
+            }
+        }
+        
+        Ok(keys.iter().map(|(partition_key, clustering_key)| {
+            metrics.sstables_per_read.record_value(sstable_reads.get(partition_key).copied().unwrap_or(0));
+            let row = cached.get(partition_key).and_then(|partition| partition.get(clustering_key).cloned());
+            // 캐시된 파티션은 이미 병합돼 있어 남은 툼스톤만 셈
+            metrics.tombstones_per_read.record_value(row.as_ref().map_or(0, crate::schema::Row::tombstone_count));
+            row
+        }).collect())
+    }
+    
+    /// 메모리 테이블 플러시 (크기나 나이와 관계없이 바로, 테스트와 checkpoint에서 사용)
+    pub(crate) async fn flush_memtable(&self, keyspace: &str, table: &str) -> Result<()> {
+        self.flusher.flush(keyspace, table).await?;
+        self.flusher.flush_engine_table(keyspace, table).await.map(|_| ())
+    }
+    
+    /// 고정된 메모리 테이블을 SSTable로 쓰고 대기열에서 SSTable 목록으로 교체
+    ///
+    /// 대기열에서 가장 오래된 메모리 테이블일 때만 flushed_until을 재생 시작 위치로 기록함
+    /// (앞선 메모리 테이블이 아직 쓰이는 중이면 그 내용도 재생해야 하므로)
+    async fn write_flushed_memtable(keyspaces: &RwLock<HashMap<String, Keyspace>>, sstable_dir: &std::path::Path, keyspace: &str, table: &str, memtable: Arc<Memtable>, flushed_until: Option<CommitLogPosition>, read_path: &SSTableReadPath) -> Result<()> {
+        tokio::fs::create_dir_all(sstable_dir).await?;
+        let sstable = read_path.apply(SSTable::create_from_memtable(
+            &memtable,
+            sstable_dir,
+            memtable.table_schema().options.compression
+        ).await?);
+        
+        // SSTable 추가와 대기열 제거를 한 번에 수행해 읽기에서 데이터가 사라지는 순간이 없도록 함
+        let keyspaces = keyspaces.read().await;
+        if let Some(ks) = keyspaces.get(keyspace) {
+            let mut tables = ks.tables.write().await;
+            if let Some(tbl) = tables.get_mut(table) {
+                // 매니페스트에 기록된 뒤에만 공개 (실패하면 파일은 다음 시작 때 정리됨)
+                if let Some(manifest) = tbl.manifest.as_mut() {
+                    fault_injection::kill_point(FaultPoint::Flush, sstable_dir)?;
+                    let oldest = tbl.memtables.first().is_some_and(|queued| Arc::ptr_eq(queued, &memtable));
+                    manifest.record_flush(&sstable, flushed_until.filter(|_| oldest)).await?;
+                }
+                tbl.memtables.retain(|queued| !Arc::ptr_eq(queued, &memtable));
+                tbl.sstables.push(Arc::new(sstable));
+            }
+        }
+        Ok(())
+    }
+    
+    /// 커밋 로그 변경 구독 (CDC, keyspace가 None이면 공용 커밋 로그, from은 받은 마지막 변경의 next, 처음이면 기본값)
+    pub async fn commit_log_changes(&self, keyspace: Option<&str>, from: CommitLogPosition) -> Result<CommitLogTail> {
+        let commit_log = match keyspace {
+            Some(keyspace) => self.commit_log_for(keyspace),
+            None => self.commit_log.as_ref(),
+        };
+        let commit_log = commit_log.ok_or_else(|| CoreDBError::CommitLogError {
+            message: "Commit log is disabled in ephemeral mode".to_string(),
+        })?;
+        commit_log.tail(from).await
+    }
+    
+    /// 모든 테이블의 데이터가 SSTable에 들어간 커밋 로그 세그먼트 정리 (모든 커밋 로그에서 정리한 세그먼트 수 반환, 플러시마다 자동으로 실행됨)
+    pub async fn purge_commit_log(&self) -> Result<usize> {
+        let mut retired = 0;
+        if let Some(commit_log) = &self.commit_log {
+            retired += Self::purge_commit_log_segments(&self.keyspaces, &self.flusher.engine_tables, &self.query_engine, commit_log, None, &self.config.commitlog_keyspace_directories, &self.config.data_directory).await?;
+        }
+        for (keyspace, commit_log) in &self.keyspace_commit_logs {
+            retired += Self::purge_commit_log_segments(&self.keyspaces, &self.flusher.engine_tables, &self.query_engine, commit_log, Some(keyspace), &self.config.commitlog_keyspace_directories, &self.config.data_directory).await?;
+        }
+        Ok(retired)
+    }
+    
+    /// 플러시되지 않은 쓰기가 있는 테이블은 매니페스트의 재생 위치부터 남김 (CQL로 만든 쿼리 엔진 테이블 포함).
+    /// 이번 실행에서 아직 열지 않은 테이블은 디스크의 매니페스트 기준으로 남김 (다음에 열 때 재생해야 하므로)
+    ///
+    /// dedicated가 있으면 그 키스페이스의 전용 커밋 로그, 없으면 전용 디렉토리가 없는 키스페이스의 공용 커밋 로그
+    #[allow(clippy::too_many_arguments)]
+    async fn purge_commit_log_segments(keyspaces: &RwLock<HashMap<String, Keyspace>>, engine_tables: &EngineTables, query_engine: &RwLock<QueryEngine>, commit_log: &CommitLog, dedicated: Option<&str>, keyspace_directories: &HashMap<String, PathBuf>, data_directory: &Path) -> Result<usize> {
+        let logged_here = |keyspace: &str| match dedicated {
+            Some(dedicated) => dedicated == keyspace,
+            None => !keyspace_directories.contains_key(keyspace),
+        };
+        // 아래에서 테이블을 훑는 동안 기록된 쓰기는 이 위치 뒤에 있음
+        let mut keep_from = commit_log.position();
+        let mut opened = std::collections::HashSet::new();
+        {
+            let keyspaces = keyspaces.read().await;
+            for (keyspace_name, keyspace) in keyspaces.iter().filter(|(keyspace_name, _)| logged_here(keyspace_name)) {
+                // 쓰기 잠금으로 커밋 로그에 기록만 하고 메모리 테이블에는 아직 반영하지 않은 쓰기가 끝나길 기다림
+                let tables = keyspace.tables.write().await;
+                for (table_name, tbl) in tables.iter() {
+                    opened.insert((keyspace_name.clone(), table_name.clone()));
+                    let unflushed = tbl.current_memtable.partition_count() > 0 || !tbl.memtables.is_empty();
+                    let unflushed = unflushed && keyspace.definition.durable_writes;
+                    if let (true, Some(manifest)) = (unflushed, &tbl.manifest) {
+                        keep_from = keep_from.min(manifest.replay_position);
+                    }
+                }
+            }
+        }
+        {
+            let _writes = engine_tables.writes.write().await;
+            let manifests = engine_tables.manifests.lock().await;
+            let engine = query_engine.read().await;
+            for ((keyspace_name, table_name), manifest) in manifests.iter().filter(|((keyspace_name, _), _)| logged_here(keyspace_name)) {
+                opened.insert((keyspace_name.clone(), table_name.clone()));
+                let unflushed = engine.get_memtable(keyspace_name, table_name).is_ok_and(|memtable| memtable.partition_count() > 0)
+                    || !engine.flushing_memtables(keyspace_name, table_name).is_empty();
+                if unflushed && engine.durable_writes(keyspace_name) {
+                    keep_from = keep_from.min(manifest.replay_position);
+                }
+            }
+        }
+        for (keyspace_name, table_name, directory) in Manifest::find_tables(data_directory).await? {
+            if logged_here(&keyspace_name) && !opened.contains(&(keyspace_name, table_name)) {
+                let replay_position = Manifest::load(&directory).await?.map(|manifest| manifest.replay_position).unwrap_or_default();
+                keep_from = keep_from.min(replay_position);
+            }
+        }
+        
+        let retired = commit_log.retire_segments_before(keep_from.segment_id).await?;
+        if retired > 0 {
+            tracing::debug!("Purged {} commit log segments before segment {}", retired, keep_from.segment_id);
+        }
+        Ok(retired)
+    }
+    
+    fn large_partition_threshold_bytes(&self) -> u64 {
+        self.config.large_partition_warning_threshold_mb * 1024 * 1024
+    }
+    
+    /// 진행 중인 백그라운드 플러시가 모두 끝날 때까지 대기
+    pub async fn wait_for_flushes(&self) -> Result<()> {
+        self.flusher.wait_for_flushes().await
+    }
+    
+    /// 키스페이스의 모든 테이블 스냅샷 (메모리 테이블을 플러시한 뒤 SSTable을 snapshots/<name>/에 하드 링크)
+    pub async fn snapshot_keyspace(&self, keyspace: &str, name: &str) -> Result<Vec<SnapshotInfo>> {
+        Self::take_keyspace_snapshot(&self.flusher, keyspace, name).await
+    }
+    
+    /// 테이블 하나의 스냅샷 (그 테이블의 메모리 테이블만 플러시한 뒤 SSTable을 snapshots/<name>/에 하드 링크)
+    pub async fn snapshot_table(&self, keyspace: &str, table: &str, name: &str) -> Result<SnapshotInfo> {
+        Self::check_snapshot(&self.flusher, name)?;
+        self.flusher.flush(keyspace, table).await?;
+        self.flusher.wait_for_table_flushes(keyspace, table).await?;
+        let mut snapshots = Self::link_snapshots(&self.flusher, keyspace, &[table.to_string()], name).await?;
+        snapshots.pop().ok_or_else(|| CoreDBError::TableNotFound { table: table.to_string() })
+    }
+    
+    /// 키스페이스 테이블들의 스냅샷 (테이블, 이름 순)
+    pub async fn list_snapshots(&self, keyspace: &str) -> Result<Vec<SnapshotInfo>> {
+        let mut snapshots = Vec::new();
+        for table in Self::table_names(&self.flusher.keyspaces, keyspace).await? {
+            snapshots.extend(list_table_snapshots(&self.config.data_directory.join(keyspace).join(&table)).await?);
+        }
+        Ok(snapshots)
+    }
+    
+    /// 스냅샷 삭제 (table이 없으면 키스페이스의 모든 테이블에서, 지운 테이블 스냅샷 수 반환)
+    pub async fn clear_snapshot(&self, keyspace: &str, table: Option<&str>, name: &str) -> Result<usize> {
+        let tables = match table {
+            Some(table) => vec![table.to_string()],
+            None => Self::table_names(&self.flusher.keyspaces, keyspace).await?,
+        };
+        let mut removed = 0;
+        for table in &tables {
+            if remove_table_snapshot(&self.config.data_directory.join(keyspace).join(table), name).await? {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+    
+    /// 스냅샷을 찍어 backup_url로 올리고 로컬 스냅샷은 지움 (table이 없으면 키스페이스의 모든 테이블)
+    ///
+    /// 이전 백업에 이미 있는 SSTable은 다시 올리지 않음
+    pub async fn backup(&self, keyspace: &str, table: Option<&str>) -> Result<Vec<BackupManifest>> {
+        let url = self.config.backup_url.as_deref()
+            .ok_or_else(|| CoreDBError::Backup { message: "No backup URL configured".to_string() })?;
+        let store = BackupStore::from_url(url)?;
+        let name = default_snapshot_name();
+        let snapshots = match table {
+            Some(table) => vec![self.snapshot_table(keyspace, table, &name).await?],
+            None => self.snapshot_keyspace(keyspace, &name).await?,
+        };
+        let mut backups = Vec::new();
+        let mut uploaded = Ok(());
+        for info in &snapshots {
+            match store.upload_snapshot(&self.config.data_directory.join(keyspace).join(&info.table), &name).await {
+                Ok(backup) => backups.push(backup),
+                Err(e) => {
+                    uploaded = Err(e);
+                    break;
+                },
+            }
+        }
+        self.clear_snapshot(keyspace, table, &name).await?;
+        uploaded.map(|()| backups)
+    }
+    
+    /// 키스페이스의 테이블 이름 (이름 순)
+    async fn table_names(keyspaces: &RwLock<HashMap<String, Keyspace>>, keyspace: &str) -> Result<Vec<String>> {
+        let keyspaces = keyspaces.read().await;
+        let ks = keyspaces.get(keyspace).ok_or_else(|| CoreDBError::KeyspaceNotFound { keyspace: keyspace.to_string() })?;
+        let mut tables: Vec<String> = ks.tables.read().await.keys().cloned().collect();
+        tables.sort();
+        Ok(tables)
+    }
+    
+    /// 모든 테이블을 플러시하고 그 시점의 SSTable로 같은 이름의 테이블 스냅샷을 만듦
+    ///
+    /// 플러시 뒤에 들어온 쓰기는 포함하지 않음. 한 테이블이라도 실패하면 이미 만든 테이블 스냅샷도 지움
+    async fn take_keyspace_snapshot(flusher: &Flusher, keyspace: &str, name: &str) -> Result<Vec<SnapshotInfo>> {
+        Self::check_snapshot(flusher, name)?;
+        let table_names = Self::table_names(&flusher.keyspaces, keyspace).await?;
+        for table in &table_names {
+            flusher.flush(keyspace, table).await?;
+        }
+        for table in &table_names {
+            flusher.wait_for_table_flushes(keyspace, table).await?;
+        }
+        Self::link_snapshots(flusher, keyspace, &table_names, name).await
+    }
+    
+    fn check_snapshot(flusher: &Flusher, name: &str) -> Result<()> {
+        validate_snapshot_name(name)?;
+        if flusher.config.ephemeral {
+            return Err(CoreDBError::Snapshot { message: "Cannot snapshot an ephemeral database".to_string() });
+        }
+        Ok(())
+    }
+    
+    /// 테이블들의 현재 SSTable로 같은 이름의 테이블 스냅샷을 만듦 (없는 테이블은 건너뜀)
+    async fn link_snapshots(flusher: &Flusher, keyspace: &str, table_names: &[String], name: &str) -> Result<Vec<SnapshotInfo>> {
+        // 테이블 잠금을 쥐는 동안에는 컴팩션이 SSTable을 지우지 못함
+        let created_at = chrono::Utc::now().timestamp_micros();
+        let keyspaces = flusher.keyspaces.read().await;
+        let ks = keyspaces.get(keyspace).ok_or_else(|| CoreDBError::KeyspaceNotFound { keyspace: keyspace.to_string() })?;
+        let tables = ks.tables.read().await;
+        let mut snapshots = Vec::new();
+        for table_name in table_names {
+            let Some(table) = tables.get(table_name) else { continue };
+            let Some(manifest) = &table.manifest else { continue };
+            let directory = flusher.config.data_directory.join(keyspace).join(table_name);
+            let info = SnapshotInfo {
+                name: name.to_string(),
+                keyspace: keyspace.to_string(),
+                table: table_name.clone(),
+                created_at,
+                sstables: Vec::new(),
+                size_bytes: 0,
+            };
+            match create_table_snapshot(&directory, manifest, info).await {
+                Ok(info) => snapshots.push(info),
+                Err(e) => {
+                    for created in &snapshots {
+                        remove_table_snapshot(&flusher.config.data_directory.join(keyspace).join(&created.table), name).await.ok();
+                    }
+                    return Err(e);
+                },
+            }
+        }
+        Ok(snapshots)
+    }
+    
+    /// 주기 스냅샷 하나를 찍고 보관 정책을 넘은 이전 자동 스냅샷을 지움 (at은 예정 시각, 초)
+    async fn run_scheduled_snapshot(flusher: &Flusher, keyspace: &str, schedule: &SnapshotSchedule, at: i64) {
+        let name = scheduled_snapshot_name(chrono::DateTime::from_timestamp(at, 0).unwrap_or_default());
+        match Self::take_keyspace_snapshot(flusher, keyspace, &name).await {
+            Ok(snapshots) => tracing::info!("Took scheduled snapshot {} of keyspace {} ({} tables, {} SSTables)",
+                                            name, keyspace, snapshots.len(), snapshots.iter().map(|info| info.sstables.len()).sum::<usize>()),
+            // 키스페이스가 아직 만들어지지 않았으면 다음 차례에 다시 시도
+            Err(CoreDBError::KeyspaceNotFound { .. }) => {
+                tracing::debug!("Skipping scheduled snapshot of missing keyspace {}", keyspace);
+                return;
+            },
+            Err(e) => tracing::error!("Scheduled snapshot {} of keyspace {} failed: {}", name, keyspace, e),
+        }
+        
+        let pruned = async {
+            let mut created = std::collections::BTreeMap::new();
+            let table_names = Self::table_names(&flusher.keyspaces, keyspace).await?;
+            for table in &table_names {
+                for info in list_table_snapshots(&flusher.config.data_directory.join(keyspace).join(table)).await? {
+                    created.insert(info.name, info.created_at);
+                }
+            }
+            let expired = schedule.expired(&created.into_iter().collect::<Vec<_>>(), chrono::Utc::now().timestamp_micros());
+            for name in &expired {
+                for table in &table_names {
+                    remove_table_snapshot(&flusher.config.data_directory.join(keyspace).join(table), name).await?;
+                }
+                tracing::info!("Removed expired snapshot {} of keyspace {}", name, keyspace);
+            }
+            Ok::<_, CoreDBError>(())
+        }.await;
+        if let Err(e) = pruned {
+            tracing::error!("Failed to remove expired snapshots of keyspace {}: {}", keyspace, e);
+        }
+    }
+    
+    /// 시스템 키스페이스 생성
+    async fn create_system_keyspaces(&mut self) -> Result<()> {
         // 시스템 키스페이스 생성
         self.create_keyspace("system".to_string(), 1).await?;
         self.create_keyspace("system_schema".to_string(), 1).await?;
+        self.create_table(SYSTEM_SCHEMA_KEYSPACE.to_string(), SCHEMA_VERSIONS_TABLE.to_string(), SchemaVersion::table_schema()).await?;
+        self.create_table(SYSTEM_SCHEMA_KEYSPACE.to_string(), MIGRATIONS_TABLE.to_string(), AppliedMigration::table_schema()).await?;
+        
+        // 역할은 저장 계층 테이블에 두어 커밋 로그와 SSTable로 재시작 뒤에도 남게 함
+        self.create_keyspace(SYSTEM_AUTH_KEYSPACE.to_string(), 1).await?;
+        self.create_table(SYSTEM_AUTH_KEYSPACE.to_string(), ROLES_TABLE.to_string(), Role::table_schema()).await?;
+        self.create_table(SYSTEM_AUTH_KEYSPACE.to_string(), ROLE_PERMISSIONS_TABLE.to_string(), Resource::permissions_table_schema()).await?;
+        
+        Ok(())
+    }
+    
+    /// 마지막 스키마 버전 잠금 (처음이면 system_schema.schema_versions에서 마지막 번호를 찾음)
+    async fn lock_schema_version(&self) -> Result<tokio::sync::MutexGuard<'_, Option<u64>>> {
+        let mut version = self.schema_version.lock().await;
+        if version.is_none() {
+            // 번호는 1부터 빈틈없이 붙으므로 처음 비어 있는 번호 바로 앞이 마지막
+            let mut last = 0;
+            loop {
+                let keys: Vec<_> = (last + 1..=last + 128).map(|version| (SchemaVersion::partition_key(version), None)).collect();
+                let rows = self.read_rows(SYSTEM_SCHEMA_KEYSPACE, SCHEMA_VERSIONS_TABLE, &keys).await?;
+                let found = rows.iter().take_while(|row| row.is_some()).count() as u64;
+                last += found;
+                if found < 128 {
+                    break;
+                }
+            }
+            *version = Some(last);
+        }
+        Ok(version)
+    }
+    
+    /// 성공한 스키마 문을 다음 버전으로 기록
+    async fn record_schema_change(&self, version: &mut tokio::sync::MutexGuard<'_, Option<u64>>, statement: &str) -> Result<SchemaVersion> {
+        let change = SchemaVersion {
+            version: version.unwrap_or_default() + 1,
+            id: Uuid::new_v4(),
+            statement: statement.trim().to_string(),
+            applied_at: chrono::Utc::now().timestamp_micros(),
+        };
+        // 스키마 문이 이미 쓰기 허가를 쥐고 있음
+        self.write_row(SYSTEM_SCHEMA_KEYSPACE, SCHEMA_VERSIONS_TABLE, change.to_row(), None).await?;
+        **version = Some(change.version);
+        Ok(change)
+    }
+    
+    /// system_schema.schema_versions의 스키마 문을 버전 순서대로 쿼리 엔진에 다시 적용 (적용한 수 반환)
+    ///
+    /// 커밋 로그와 스키마 버전은 다시 기록하지 않음. 저장 계층 테이블은 create_table로 다시 만들 때 전략을
+    /// 정하므로 쿼리 엔진에 없는 테이블의 ALTER TABLE은 건너뜀. 스키마를 모두 적용한 뒤 남은 테이블마다
+    /// 플러시된 SSTable을 붙이고 커밋 로그의 쓰기를 다시 반영함
+    async fn load_schema(&self) -> Result<usize> {
+        let history = self.schema_history().await?;
+        let mut engine = self.query_engine.write().await;
+        let mut loaded = 0;
+        let mut tables = std::collections::BTreeSet::new();
+        for change in history {
+            let statement = match crate::query::parser::CqlParser::parse(&change.statement) {
+                Ok(CqlStatement::AlterTable { keyspace, name, .. }) if !engine.has_table(&keyspace, &name) => continue,
+                Ok(statement) => statement,
+                Err(e) => {
+                    tracing::warn!("Skipping schema version {} ({}): {}", change.version, change.statement, e);
+                    continue;
+                },
+            };
+            if let Err(e) = engine.execute(statement.clone()).await {
+                tracing::warn!("Skipping schema version {} ({}): {}", change.version, change.statement, e);
+                continue;
+            }
+            loaded += 1;
+            match statement {
+                CqlStatement::CreateTable { keyspace, name, .. } => { tables.insert((keyspace, name)); },
+                CqlStatement::DropTable { keyspace, name } => { tables.remove(&(keyspace, name)); },
+                CqlStatement::DropKeyspace { name } => tables.retain(|(keyspace, _)| *keyspace != name),
+                _ => {},
+            }
+        }
+        drop(engine);
+        
+        for (keyspace, table) in tables {
+            self.open_engine_table(&keyspace, &table, false).await?;
+        }
+        Ok(loaded)
+    }
+    
+    /// 현재 스키마 버전 (CQL 스키마 문을 한 번도 실행하지 않았으면 None)
+    pub async fn schema_version(&self) -> Result<Option<SchemaVersion>> {
+        let last = self.lock_schema_version().await?.unwrap_or_default();
+        if last == 0 {
+            return Ok(None);
+        }
+        // system.local을 조회하는 쿼리가 이미 읽기 허가를 쥐고 있어 허가 없이 읽음
+        let row = self.read_rows(SYSTEM_SCHEMA_KEYSPACE, SCHEMA_VERSIONS_TABLE, &[(SchemaVersion::partition_key(last), None)]).await?;
+        Ok(row.into_iter().flatten().next().and_then(|row| SchemaVersion::from_row(last, &row)))
+    }
+    
+    /// system.local의 host_id
+    pub fn host_id(&self) -> Uuid {
+        self.host_id
+    }
+    
+    /// system.local에 알릴 클라이언트 접속 주소 (처음 한 번만 정할 수 있고, 정하지 않으면 127.0.0.1:9042)
+    pub fn set_rpc_address(&self, address: SocketAddr) {
+        if self.rpc_address.set(address).is_err() {
+            tracing::warn!("RPC address is already set, ignoring {}", address);
+        }
+    }
+    
+    /// 서버에 연결된 클라이언트 (서버가 연결마다 등록)
+    pub fn clients(&self) -> &ClientRegistry {
+        &self.clients
+    }
+    
+    /// 통계 전체를 Prometheus 텍스트 형식으로 (/metrics)
+    pub async fn prometheus_metrics(&self) -> String {
+        render_prometheus(&self.get_stats().await)
+    }
+    
+    /// 가상 테이블의 현재 행
+    async fn virtual_table_rows(&self, table: &VirtualTable) -> Result<Vec<VirtualRow>> {
+        let text = |value: &str| CassandraValue::Text(value.to_string());
+        let big_int = |value: u64| CassandraValue::BigInt(value as i64);
+        let rows = match table.name {
+            LOCAL_TABLE => {
+                let schema_version = self.schema_version().await?.map_or(Uuid::nil(), |version| version.id);
+                let rpc_address = self.rpc_address.get().copied().unwrap_or_else(|| SocketAddr::from(([127, 0, 0, 1], 9042)));
+                let address = text(&rpc_address.ip().to_string());
+                vec![vec![
+                    ("key", text("local")),
+                    ("bootstrapped", text("COMPLETED")),
+                    ("broadcast_address", address.clone()),
+                    ("cluster_name", text("CoreDB Cluster")),
+                    ("cql_version", text("3.4.6")),
+                    ("data_center", text("datacenter1")),
+                    ("host_id", CassandraValue::UUID(self.host_id)),
+                    ("listen_address", address.clone()),
+                    ("native_protocol_version", text("4")),
+                    ("partitioner", text(PARTITIONER)),
+                    ("rack", text("rack1")),
+                    ("release_version", text(CASSANDRA_RELEASE_VERSION)),
+                    ("rpc_address", address),
+                    ("rpc_port", CassandraValue::Int(rpc_address.port() as i32)),
+                    ("schema_version", CassandraValue::UUID(schema_version)),
+                    // 단일 노드라 토큰 하나가 전체 링을 가짐
+                    ("tokens", CassandraValue::Set(vec![text("0")])),
+                ]]
+            },
+            CLIENTS_TABLE => self.clients.clients().into_iter().map(|client| vec![
+                ("address", text(&client.address.ip().to_string())),
+                ("port", CassandraValue::Int(client.address.port() as i32)),
+                ("connected_at", CassandraValue::Timestamp(client.connected_at)),
+                ("driver_name", client.driver_name.as_deref().map_or(CassandraValue::Null, text)),
+                ("request_count", big_int(client.request_count)),
+                ("ssl_enabled", CassandraValue::Boolean(client.ssl_protocol.is_some())),
+                ("ssl_protocol", client.ssl_protocol.as_deref().map_or(CassandraValue::Null, text)),
+                ("username", client.username.as_deref().map_or(CassandraValue::Null, text)),
+            ]).collect(),
+            CACHES_TABLE => {
+                let key_cache = self.read_path.key_cache.as_ref().map(|cache| cache.stats()).unwrap_or_default();
+                let chunk_cache = self.read_path.chunk_cache.as_ref().map(|cache| cache.stats()).unwrap_or_default();
+                let mut row_cache = RowCacheStats::default();
+                for keyspace in self.keyspaces.read().await.values() {
+                    for table in keyspace.tables.read().await.values() {
+                        if let Some(cache) = &table.row_cache {
+                            row_cache.merge(&cache.stats());
+                        }
+                    }
+                }
+                let cache = |name: &str, capacity: u64, entries: usize, size_bytes: Option<u64>, hits: u64, misses: u64, hit_rate: f64| vec![
+                    ("name", text(name)),
+                    ("capacity", big_int(capacity)),
+                    ("entry_count", big_int(entries as u64)),
+                    ("hit_count", big_int(hits)),
+                    ("hit_ratio", CassandraValue::Double(hit_rate)),
+                    ("request_count", big_int(hits + misses)),
+                    ("size_bytes", size_bytes.map_or(CassandraValue::Null, big_int)),
+                ];
+                vec![
+                    // 키 캐시 한도는 항목 수
+                    cache("keys", key_cache.capacity as u64, key_cache.entries, None, key_cache.hits, key_cache.misses, key_cache.hit_rate()),
+                    cache("chunks", chunk_cache.capacity_bytes, chunk_cache.chunks, Some(chunk_cache.size_bytes), chunk_cache.hits, chunk_cache.misses, chunk_cache.hit_rate()),
+                    cache("rows", row_cache.capacity_bytes, row_cache.partitions, Some(row_cache.size_bytes), row_cache.hits, row_cache.misses, row_cache.hit_rate()),
+                ]
+            },
+            TABLES_TABLE | COLUMNS_TABLE | INDEXES_TABLE | TRIGGERS_TABLE => self.query_engine.read().await.schema_table_rows(table.name),
+            SSTABLE_TASKS_TABLE => self.compactions().into_iter().map(|info| vec![
+                ("keyspace_name", text(&info.keyspace)),
+                ("table_name", text(&info.table)),
+                ("kind", text(if info.major { "MAJOR_COMPACTION" } else { "COMPACTION" })),
+                ("progress", big_int(info.compacted_bytes)),
+                ("total", big_int(info.total_bytes)),
+                ("unit", text("bytes")),
+            ]).collect(),
+            _ => Vec::new(),
+        };
+        Ok(rows)
+    }
+    
+    /// 모든 스키마 변경 (버전 순서)
+    pub async fn schema_history(&self) -> Result<Vec<SchemaVersion>> {
+        let last = self.lock_schema_version().await?.unwrap_or_default();
+        let keys: Vec<_> = (1..=last).map(|version| (SchemaVersion::partition_key(version), None)).collect();
+        let rows = self.read_rows(SYSTEM_SCHEMA_KEYSPACE, SCHEMA_VERSIONS_TABLE, &keys).await?;
+        Ok(rows.iter().zip(1..).filter_map(|(row, version)| SchemaVersion::from_row(version, row.as_ref()?)).collect())
+    }
+    
+    /// 마이그레이션 적용 기록 (시작하지 않았으면 None)
+    pub async fn applied_migration(&self, name: &str) -> Result<Option<AppliedMigration>> {
+        let row = self.get_row(SYSTEM_SCHEMA_KEYSPACE, MIGRATIONS_TABLE, &AppliedMigration::partition_key(name), &None).await?;
+        Ok(row.and_then(|row| AppliedMigration::from_row(name, &row)))
+    }
+    
+    /// 마이그레이션을 주어진 순서대로 한 번씩만 적용하고 이번에 끝낸 마이그레이션 이름을 돌려줌
+    ///
+    /// 이미 끝난 마이그레이션은 건너뛰고, 중간에 실패한 마이그레이션은 다음 실행에서 실패한 문부터 이어서 적용함.
+    /// 이미 적용한 문이 바뀐 마이그레이션이 있으면 아무것도 실행하지 않고 오류 (실패한 문은 고쳐서 다시 실행할 수 있음)
+    pub async fn migrate(&self, migrations: &[Migration]) -> Result<Vec<String>> {
+        let _lock = self.migration_lock.lock().await;
+        let mut pending = Vec::new();
+        for migration in migrations {
+            match self.applied_migration(&migration.name).await? {
+                Some(applied) => {
+                    applied.check_unchanged(migration)?;
+                    if !applied.completed {
+                        pending.push((migration, applied));
+                    }
+                },
+                None => pending.push((migration, AppliedMigration::new(migration))),
+            }
+        }
+        
+        let mut completed = Vec::new();
+        for (migration, mut applied) in pending {
+            let statements = migration.statements();
+            for (index, statement) in statements.iter().enumerate().skip(applied.statements_applied) {
+                self.execute_cql(statement).await.map_err(|e| CoreDBError::MigrationFailed {
+                    name: migration.name.clone(),
+                    message: format!("statement {} failed: {}", index + 1, e),
+                })?;
+                applied.record_statement(&statements, chrono::Utc::now().timestamp_micros());
+                self.insert_row(SYSTEM_SCHEMA_KEYSPACE, MIGRATIONS_TABLE, applied.to_row()).await?;
+            }
+            applied.completed = true;
+            applied.schema_version = self.lock_schema_version().await?.unwrap_or_default();
+            applied.applied_at = chrono::Utc::now().timestamp_micros();
+            self.insert_row(SYSTEM_SCHEMA_KEYSPACE, MIGRATIONS_TABLE, applied.to_row()).await?;
+            tracing::info!("Applied migration {} (schema version {})", migration.name, applied.schema_version);
+            completed.push(migration.name.clone());
+        }
+        Ok(completed)
+    }
+    
+    /// system_auth.roles에서 역할 조회 (없거나 삭제되었으면 None)
+    ///
+    /// 기본 슈퍼유저는 한 번도 바꾸거나 지운 적이 없으면 행 없이 존재하는 것으로 봄 (시작할 때 쓰기를 하지 않도록)
+    pub async fn get_role(&self, name: &str) -> Result<Option<Role>> {
+        match self.get_row(SYSTEM_AUTH_KEYSPACE, ROLES_TABLE, &Role::partition_key(name), &None).await? {
+            Some(row) => Ok(Role::from_row(name, &row)),
+            None if name == DEFAULT_SUPERUSER => Ok(Some(Role::default_superuser())),
+            None => Ok(None),
+        }
+    }
+    
+    /// 이름과 비밀번호로 로그인 (없는 역할과 틀린 비밀번호는 구분하지 않음)
+    pub async fn authenticate(&self, username: &str, password: &str) -> Result<Role> {
+        let rejected = || CoreDBError::AuthenticationFailed { message: "Provided username and/or password are incorrect".to_string() };
+        let role = self.get_role(username).await?.ok_or_else(rejected)?;
+        let Some(salted_hash) = role.salted_hash.clone() else { return Err(rejected()) };
+        let password = password.to_string();
+        let matches = tokio::task::spawn_blocking(move || verify_password(&password, &salted_hash)).await
+            .map_err(|e| CoreDBError::Generic { message: format!("Password check failed: {}", e) })?;
+        if !matches {
+            return Err(rejected());
+        }
+        if !role.can_login {
+            return Err(CoreDBError::AuthenticationFailed { message: format!("{} is not permitted to log in", username) });
+        }
+        Ok(role)
+    }
+    
+    /// 검증된 클라이언트 인증서로 로그인 (인증서 CN과 같은 이름의 로그인 가능한 역할)
+    pub async fn authenticate_certificate(&self, identity: &str) -> Result<Role> {
+        match self.get_role(identity).await? {
+            Some(role) if role.can_login => Ok(role),
+            _ => Err(CoreDBError::AuthenticationFailed { message: format!("No login role matches client certificate {}", identity) }),
+        }
+    }
+    
+    /// 역할이 문을 실행할 수 있는지 확인 (슈퍼유저는 모두, 역할 관리와 GRANT/REVOKE는 슈퍼유저만,
+    /// 자기 비밀번호 변경은 누구나, 나머지는 대상이나 상위 대상에 부여된 권한으로)
+    async fn authorize(&self, role: &Role, statement: &CqlStatement) -> Result<()> {
+        if role.is_superuser {
+            return Ok(());
+        }
+        let Some((permission, resource)) = required_permission(statement) else {
+            return match statement {
+                CqlStatement::AlterRole { name, options }
+                    if *name == role.name && options.login.is_none() && options.superuser.is_none() => Ok(()),
+                CqlStatement::CreateRole { .. } | CqlStatement::AlterRole { .. } | CqlStatement::DropRole { .. }
+                | CqlStatement::Grant { .. } | CqlStatement::Revoke { .. } => Err(CoreDBError::Unauthorized {
+                    message: format!("Only superusers can manage roles and permissions ({} is not a superuser)", role.name),
+                }),
+                _ => Ok(()),
+            };
+        };
+        
+        let chain = resource.chain();
+        let keys: Vec<_> = chain.iter().map(|resource| (resource.permissions_key(&role.name), None)).collect();
+        let rows = self.multiget(SYSTEM_AUTH_KEYSPACE, ROLE_PERMISSIONS_TABLE, &keys).await?;
+        if rows.iter().flatten().any(|row| role.is_granted(row, permission)) {
+            return Ok(());
+        }
+        Err(CoreDBError::Unauthorized {
+            message: format!("User {} has no {} permission on {} or any of its parents", role.name, permission, resource),
+        })
+    }
+    
+    /// 역할 관리와 GRANT/REVOKE 문 실행 (비밀번호는 bcrypt 해시로만 저장하며 CQL 원문은 커밋 로그에 남기지 않음, 다른 문은 None)
+    async fn execute_role_statement(&self, statement: &CqlStatement) -> Result<Option<QueryResult>> {
+        let hash = |password: Option<String>| async move {
+            match password {
+                Some(password) => tokio::task::spawn_blocking(move || hash_password(&password)).await
+                    .map_err(|e| CoreDBError::Generic { message: format!("Password hashing failed: {}", e) })?
+                    .map(Some),
+                None => Ok(None),
+            }
+        };
+        let now = chrono::Utc::now().timestamp_micros();
+        let row = match statement {
+            CqlStatement::CreateRole { name, options, if_not_exists } => {
+                if self.get_role(name).await?.is_some() {
+                    if *if_not_exists {
+                        return Ok(Some(QueryResult::success()));
+                    }
+                    return Err(CoreDBError::InvalidSchema { message: format!("{} already exists", name) });
+                }
+                Role {
+                    name: name.clone(),
+                    salted_hash: hash(options.password.clone()).await?,
+                    is_superuser: options.superuser.unwrap_or(false),
+                    can_login: options.login.unwrap_or(false),
+                    created_at: now,
+                }.to_row(now)
+            },
+            CqlStatement::AlterRole { name, options } => {
+                let mut role = self.get_role(name).await?
+                    .ok_or_else(|| CoreDBError::InvalidSchema { message: format!("{} doesn't exist", name) })?;
+                if let Some(salted_hash) = hash(options.password.clone()).await? {
+                    role.salted_hash = Some(salted_hash);
+                }
+                role.is_superuser = options.superuser.unwrap_or(role.is_superuser);
+                role.can_login = options.login.unwrap_or(role.can_login);
+                role.to_row(now)
+            },
+            CqlStatement::DropRole { name, if_exists } => {
+                if self.get_role(name).await?.is_none() {
+                    if *if_exists {
+                        return Ok(Some(QueryResult::success()));
+                    }
+                    return Err(CoreDBError::InvalidSchema { message: format!("{} doesn't exist", name) });
+                }
+                Role::tombstone_row(name, now)
+            },
+            CqlStatement::Grant { permissions, resource, role } | CqlStatement::Revoke { permissions, resource, role } => {
+                if self.get_role(role).await?.is_none() {
+                    return Err(CoreDBError::InvalidSchema { message: format!("{} doesn't exist", role) });
+                }
+                let granted = matches!(statement, CqlStatement::Grant { .. });
+                let row = resource.permissions_row(role, permissions, granted, now);
+                self.insert_row(SYSTEM_AUTH_KEYSPACE, ROLE_PERMISSIONS_TABLE, row).await?;
+                return Ok(Some(QueryResult::success()));
+            },
+            _ => return Ok(None),
+        };
+        self.insert_row(SYSTEM_AUTH_KEYSPACE, ROLES_TABLE, row).await?;
+        Ok(Some(QueryResult::success()))
+    }
+    
+    /// 백그라운드 작업 시작
+    async fn start_background_tasks(&self) {
+        // 플러시 스케줄러 (주기적으로, 또는 쓰기가 임계값을 넘기면 바로 메모리 테이블 확인)
+        if !self.config.ephemeral {
+            let flusher = self.flusher.clone();
+            let wakeup = Arc::downgrade(&self.flush_wakeup);
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(FLUSH_CHECK_INTERVAL);
+                while let Some(notify) = wakeup.upgrade() {
+                    tokio::select! {
+                        _ = interval.tick() => {},
+                        _ = notify.notified() => {},
+                    }
+                    drop(notify);
+                    if let Err(e) = flusher.flush_due_memtables().await {
+                        tracing::error!("Failed to flush memtables: {}", e);
+                    }
+                }
+            });
+        }
+        
+        // 디스크 사용률 감시 (CoreDB가 사라지면 멈춤)
+        if let Some(monitor) = &self.disk_monitor {
+            let monitor = Arc::downgrade(monitor);
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(DISK_CHECK_INTERVAL);
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    let Some(monitor) = monitor.upgrade() else { break };
+                    monitor.check().await;
+                }
+            });
+        }
+        
+        // 컴팩션 스케줄러 (플러시가 끝날 때마다 요청됨)
+        let compaction_manager = self.compaction_manager.clone();
+        let keyspaces = self.keyspaces.clone();
+        let engine_tables = self.flusher.engine_tables.clone();
+        let query_engine = self.query_engine.clone();
+        let read_path = self.read_path.clone();
+        let handler_manager = compaction_manager.clone();
+        tokio::spawn(async move {
+            compaction_manager.run_compaction_loop(move |task| {
+                let (keyspaces, compaction_manager, read_path) = (keyspaces.clone(), handler_manager.clone(), read_path.clone());
+                let (engine_tables, query_engine) = (engine_tables.clone(), query_engine.clone());
+                async move {
+                    // CQL로 만든 테이블은 쿼리 엔진에 있음
+                    let engine_table = engine_tables.manifests.lock().await.contains_key(&(task.keyspace.clone(), task.table.clone()));
+                    let compacted = match engine_table {
+                        true => Self::compact_engine_table(&engine_tables, &query_engine, &compaction_manager, &read_path, &task.keyspace, &task.table).await?,
+                        false => Self::compact_table(&keyspaces, &compaction_manager, &read_path, &task.keyspace, &task.table).await?,
+                    };
+                    // 한 번 컴팩션한 뒤에도 다음 레벨이 넘칠 수 있으므로 다시 요청
+                    if compacted {
+                        compaction_manager.schedule_compaction(&task.keyspace, &task.table).await;
+                    }
+                    Ok(())
+                }
+            }).await;
+        });
+        
+        // 커밋 로그 fsync (Always 방식은 쓰기마다 하므로 제외)
+        let commit_logs: Vec<_> = self.commit_logs().cloned().collect();
+        if let (false, Some(period)) = (commit_logs.is_empty(), self.config.commitlog_sync.sync_interval()) {
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(period.max(std::time::Duration::from_millis(1)));
+                loop {
+                    interval.tick().await;
+                    for commit_log in &commit_logs {
+                        if let Err(e) = commit_log.sync().await {
+                            tracing::error!("Failed to sync commit log: {}", e);
+                        }
+                    }
+                }
+            });
+        }
+        
+        // 주기 스냅샷 (키스페이스마다 간격 경계에 맞춰, CoreDB가 사라지면 멈춤)
+        if !self.config.ephemeral {
+            for (keyspace, schedule) in self.config.snapshot_schedules.clone() {
+                let flusher = self.flusher.clone();
+                let alive = Arc::downgrade(&self.flush_wakeup);
+                tokio::spawn(async move {
+                    loop {
+                        let now = chrono::Utc::now().timestamp_micros();
+                        let next = schedule.next_run_after(now.div_euclid(1_000_000));
+                        tokio::time::sleep(std::time::Duration::from_micros((next * 1_000_000 - now).max(0) as u64)).await;
+                        if alive.upgrade().is_none() {
+                            break;
+                        }
+                        Self::run_scheduled_snapshot(&flusher, &keyspace, &schedule, next).await;
+                    }
+                });
+            }
+        }
+        
+        // TTL 정리 작업
+        let keyspaces = self.keyspaces.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                // TTL 만료된 데이터 정리
+                if let Err(e) = Self::cleanup_expired_data(&keyspaces).await {
+                    tracing::error!("Failed to drop expired SSTables: {}", e);
+                }
+            }
+        });
+    }
+    
+    /// 테이블 컴팩션을 한 번 실행 (컴팩션 전략이 고른 SSTable도 지운 시간 창도 없으면 false)
+    pub async fn compact(&self, keyspace: &str, table: &str) -> Result<bool> {
+        if self.config.ephemeral {
+            return Ok(false);
+        }
+        let table_lock = self.compaction_manager.table_lock(keyspace, table);
+        let _table = table_lock.lock().await;
+        Self::compact_table(&self.keyspaces, &self.compaction_manager, &self.read_path, keyspace, table).await
+    }
+    
+    /// 테이블을 다시 만들지 않고 컴팩션 전략 변경 (ALTER TABLE ... WITH compaction)
+    ///
+    /// 진행 중인 컴팩션이 끝나길 기다린 뒤 모든 SSTable을 L0으로 되돌리고 컴팩션을 예약하므로,
+    /// 기존 SSTable은 새 전략으로 다시 배치됨 (Leveled로 바꾸면 L0부터 다시 레벨을 매김).
+    /// 저장 계층에 없는 테이블은 CQL로 만든 쿼리 엔진 테이블에서 찾음
+    pub async fn set_compaction_strategy(&self, keyspace: &str, table: &str, strategy: crate::schema::CompactionStrategy) -> Result<()> {
+        let table_lock = self.compaction_manager.table_lock(keyspace, table);
+        let _table = table_lock.lock().await;
+        let stored = match self.keyspaces.read().await.get(keyspace) {
+            Some(ks) => ks.tables.read().await.contains_key(table),
+            None => false,
+        };
+        if stored {
+            let keyspaces = self.keyspaces.read().await;
+            let ks = keyspaces.get(keyspace).ok_or_else(|| CoreDBError::KeyspaceNotFound { keyspace: keyspace.to_string() })?;
+            let mut tables = ks.tables.write().await;
+            let tbl = tables.get_mut(table).ok_or_else(|| CoreDBError::TableNotFound { table: table.to_string() })?;
+            if tbl.schema.options.compaction_strategy == strategy {
+                return Ok(());
+            }
+            tracing::info!("Switching compaction strategy of {}.{} from {:?} to {:?}", keyspace, table, tbl.schema.options.compaction_strategy, strategy);
+            if let Some(manifest) = tbl.manifest.as_mut() {
+                manifest.reset_levels().await?;
+            }
+            let mut schema = (*tbl.schema).clone();
+            schema.options.compaction_strategy = strategy;
+            tbl.schema = Arc::new(schema);
+        } else {
+            let mut manifests = self.flusher.engine_tables.manifests.lock().await;
+            let mut engine = self.query_engine.write().await;
+            let current = engine.get_memtable(keyspace, table)?.table_schema().options.compaction_strategy.clone();
+            if current == strategy {
+                return Ok(());
+            }
+            tracing::info!("Switching compaction strategy of {}.{} from {:?} to {:?}", keyspace, table, current, strategy);
+            if let Some(manifest) = manifests.get_mut(&(keyspace.to_string(), table.to_string())) {
+                manifest.reset_levels().await?;
+            }
+            engine.set_compaction_strategy(keyspace, table, strategy)?;
+        }
+        if !self.config.ephemeral {
+            self.compaction_manager.schedule_compaction(keyspace, table).await;
+        }
+        Ok(())
+    }
+    
+    /// 테이블의 SSTable을 모두 하나로 합치는 메이저 컴팩션 (SSTable이 없으면 false)
+    ///
+    /// 대량 삭제 뒤나 마지막 백업 전에 씀. 같은 테이블의 다른 컴팩션이 끝날 때까지 기다리고,
+    /// 진행 상황은 compactions로 보며 cancel_compaction으로 취소할 수 있음 (취소되면 CompactionError)
+    pub async fn major_compact(&self, keyspace: &str, table: &str) -> Result<bool> {
+        if self.config.ephemeral {
+            return Ok(false);
+        }
+        let table_lock = self.compaction_manager.table_lock(keyspace, table);
+        let _table = table_lock.lock().await;
+        let task = {
+            let keyspaces = self.keyspaces.read().await;
+            let ks = keyspaces.get(keyspace).ok_or_else(|| CoreDBError::KeyspaceNotFound { keyspace: keyspace.to_string() })?;
+            let tables = ks.tables.read().await;
+            let tbl = tables.get(table).ok_or_else(|| CoreDBError::TableNotFound { table: table.to_string() })?;
+            if tbl.sstables.is_empty() {
+                return Ok(false);
+            }
+            CompactionTask {
+                keyspace: keyspace.to_string(),
+                table: table.to_string(),
+                input_sstables: tbl.sstables.clone(),
+                strategy: self.compaction_manager.strategy_for(&tbl.schema.options.compaction_strategy),
+                major: true,
+                progress: Arc::new(CompactionProgress::new(keyspace, table, true)),
+                compression: tbl.schema.options.compression,
+                // 결과는 키 범위 전체를 덮으므로 입력 중 가장 높은 레벨에 하나만 둠
+                level: tbl.sstable_levels().iter().map(|(_, level)| *level).max().unwrap_or(0),
+                tombstone_gc: Some(tbl.tombstone_gc(&tbl.sstables)),
+            }
+        };
+        Self::run_compaction(&self.keyspaces, &self.compaction_manager, &self.read_path, task).await
+    }
+    
+    /// 실행 중인 컴팩션의 진행 상황
+    pub fn compactions(&self) -> Vec<CompactionInfo> {
+        self.compaction_manager.running_compactions()
+    }
+    
+    /// 테이블에서 실행 중인 컴팩션 취소 (실행 중인 것이 없으면 false)
+    pub fn cancel_compaction(&self, keyspace: &str, table: &str) -> bool {
+        self.compaction_manager.cancel_compaction(keyspace, table)
+    }
+    
+    /// 컴팩션 전략으로 입력 SSTable을 골라 병합하고 결과로 교체
+    ///
+    /// 병합하는 동안에는 잠금 없이 입력 SSTable을 그대로 읽을 수 있고, 매니페스트 기록과
+    /// SSTable 목록 교체는 테이블 잠금 안에서 한 번에 수행한 뒤 입력 파일을 지움.
+    /// 그 사이 다른 작업이 입력을 치웠으면 결과를 버림. Time-Window 테이블은 먼저 만료된 시간 창을
+    /// 다시 쓰지 않고 통째로 지움
+    async fn compact_table(keyspaces: &RwLock<HashMap<String, Keyspace>>, compaction_manager: &CompactionManager, read_path: &SSTableReadPath, keyspace: &str, table: &str) -> Result<bool> {
+        let task = {
+            let keyspaces = keyspaces.read().await;
+            let Some(ks) = keyspaces.get(keyspace) else { return Ok(false) };
+            let mut tables = ks.tables.write().await;
+            let Some(tbl) = tables.get_mut(table) else { return Ok(false) };
+            let strategy = compaction_manager.strategy_for(&tbl.schema.options.compaction_strategy);
+            let mut dropped = 0;
+            if let CompactionStrategy::TimeWindow { .. } = strategy {
+                dropped = tbl.drop_expired_sstables(keyspace, table, chrono::Utc::now().timestamp()).await?;
+            }
+            let Some((input_sstables, level)) = Self::select_compaction(&strategy, &tbl.sstable_levels(), &tbl.schema) else { return Ok(dropped > 0) };
+            let tombstone_gc = tbl.tombstone_gc(&input_sstables);
+            CompactionTask {
+                keyspace: keyspace.to_string(),
+                table: table.to_string(),
+                input_sstables,
+                strategy,
+                major: false,
+                progress: Arc::new(CompactionProgress::new(keyspace, table, false)),
+                compression: tbl.schema.options.compression,
+                level,
+                tombstone_gc: Some(tombstone_gc),
+            }
+        };
+        Self::run_compaction(keyspaces, compaction_manager, read_path, task).await
+    }
+    
+    /// 전략으로 컴팩션 입력과 결과 레벨을 고름 (합칠 SSTable이 없으면 툼스톤이 많은 SSTable 하나를 다시 써서 공간을 되찾음)
+    fn select_compaction(strategy: &CompactionStrategy, sstables: &[(Arc<SSTable>, u32)], schema: &TableSchema) -> Option<(Vec<Arc<SSTable>>, u32)> {
+        let options = &schema.options;
+        strategy.select(sstables).or_else(|| {
+            tombstone_compaction_candidate(sstables, options.tombstone_threshold, std::time::Duration::from_secs(options.tombstone_compaction_interval))
+                .map(|(sstable, level)| (vec![sstable], level))
+        })
+    }
+    
+    /// 쿼리 엔진 테이블의 SSTable과 매니페스트에 기록된 컴팩션 레벨
+    fn engine_sstable_levels(engine: &QueryEngine, manifest: &Manifest, keyspace: &str, table: &str) -> Vec<(Arc<SSTable>, u32)> {
+        engine.sstables(keyspace, table).into_iter()
+            .map(|sstable| {
+                let level = manifest.level(&sstable.id).unwrap_or(0);
+                (sstable, level)
+            })
+            .collect()
+    }
+    
+    /// CQL로 만든 쿼리 엔진 테이블의 컴팩션 (compact_table과 같은 방식, 레벨은 테이블 매니페스트에 기록)
+    ///
+    /// 결과로 교체한 뒤 바뀐 SSTable 목록으로 디스크 인덱스를 commit해 다시 열 때 전체를 다시 만들지 않게 함
+    async fn compact_engine_table(engine_tables: &EngineTables, query_engine: &RwLock<QueryEngine>, compaction_manager: &CompactionManager, read_path: &SSTableReadPath, keyspace: &str, table: &str) -> Result<bool> {
+        let key = (keyspace.to_string(), table.to_string());
+        let task = {
+            let mut manifests = engine_tables.manifests.lock().await;
+            let Some(manifest) = manifests.get_mut(&key) else { return Ok(false) };
+            let mut engine = query_engine.write().await;
+            let Ok(memtable) = engine.get_memtable(keyspace, table) else { return Ok(false) };
+            let schema = memtable.table_schema().clone();
+            let strategy = compaction_manager.strategy_for(&schema.options.compaction_strategy);
+            let mut dropped = 0;
+            if let CompactionStrategy::TimeWindow { .. } = strategy {
+                let now = chrono::Utc::now().timestamp();
+                let expired = fully_expired_sstables(&engine.sstables(keyspace, table), engine.min_memtable_timestamp(keyspace, table), schema.options.gc_grace_seconds, now);
+                if !expired.is_empty() {
+                    let ids: Vec<String> = expired.iter().map(|sstable| sstable.id.clone()).collect();
+                    manifest.record_compaction(&ids, &[], 0).await?;
+                    engine.replace_sstables(keyspace, table, &expired, Vec::new());
+                    for sstable in &expired {
+                        tracing::info!("Dropping fully expired SSTable {} of {}.{}", sstable.id, keyspace, table);
+                        sstable.delete().await?;
+                    }
+                    dropped = expired.len();
+                }
+            }
+            let sstables = Self::engine_sstable_levels(&engine, manifest, keyspace, table);
+            let Some((input_sstables, level)) = Self::select_compaction(&strategy, &sstables, &schema) else { return Ok(dropped > 0) };
+            let tombstone_gc = TombstoneGc {
+                gc_before: chrono::Utc::now().timestamp() - schema.options.gc_grace_seconds as i64,
+                other_sstables: sstables.into_iter()
+                    .map(|(sstable, _)| sstable)
+                    .filter(|sstable| !input_sstables.iter().any(|input| Arc::ptr_eq(input, sstable)))
+                    .collect(),
+                min_memtable_timestamp: engine.min_memtable_timestamp(keyspace, table),
+            };
+            CompactionTask {
+                keyspace: keyspace.to_string(),
+                table: table.to_string(),
+                input_sstables,
+                strategy,
+                major: false,
+                progress: Arc::new(CompactionProgress::new(keyspace, table, false)),
+                compression: schema.options.compression,
+                level,
+                tombstone_gc: Some(tombstone_gc),
+            }
+        };
+        
+        tracing::info!("Compacting {} SSTables of {}.{} into level {}", task.input_sstables.len(), keyspace, table, task.level);
+        let outputs: Vec<SSTable> = compaction_manager.execute_compaction(&task).await?
+            .into_iter()
+            .map(|sstable| read_path.apply(sstable))
+            .collect();
+        let inputs = &task.input_sstables;
+        {
+            let mut manifests = engine_tables.manifests.lock().await;
+            let mut engine = query_engine.write().await;
+            let current = engine.sstables(keyspace, table);
+            let manifest = manifests.get_mut(&key)
+                .filter(|_| inputs.iter().all(|input| current.iter().any(|sstable| Arc::ptr_eq(input, sstable))));
+            let Some(manifest) = manifest else {
+                tracing::warn!("Discarding compaction of {}.{}: its inputs were removed meanwhile", keyspace, table);
+                for output in &outputs {
+                    output.delete().await?;
+                }
+                return Ok(false);
+            };
+            let ids: Vec<String> = inputs.iter().map(|sstable| sstable.id.clone()).collect();
+            manifest.record_compaction(&ids, &outputs, task.level).await?;
+            engine.replace_sstables(keyspace, table, inputs, outputs.into_iter().map(Arc::new).collect());
+            engine.commit_indexes(keyspace, table, &engine.sstable_ids(keyspace, table))?;
+        }
+        for sstable in inputs {
+            sstable.delete().await?;
+        }
+        Ok(true)
+    }
+    
+    /// 컴팩션 작업을 실행하고 결과로 입력 SSTable을 교체 (입력이 그 사이 치워졌으면 결과를 버리고 false)
+    #[tracing::instrument(name = "compaction", skip_all, err, fields(db.namespace = %task.keyspace, db.table = %task.table, level = task.level, inputs = task.input_sstables.len(), major = task.major))]
+    async fn run_compaction(keyspaces: &RwLock<HashMap<String, Keyspace>>, compaction_manager: &CompactionManager, read_path: &SSTableReadPath, task: CompactionTask) -> Result<bool> {
+        let (keyspace, table) = (task.keyspace.as_str(), task.table.as_str());
+        tracing::info!("Compacting {} SSTables of {}.{} into level {}", task.input_sstables.len(), keyspace, table, task.level);
+        let outputs: Vec<SSTable> = compaction_manager.execute_compaction(&task).await?
+            .into_iter()
+            .map(|sstable| read_path.apply(sstable))
+            .collect();
+        let inputs = &task.input_sstables;
+        {
+            let keyspaces = keyspaces.read().await;
+            let mut tables = match keyspaces.get(keyspace) {
+                Some(ks) => Some(ks.tables.write().await),
+                None => None,
+            };
+            let current = tables.as_mut()
+                .and_then(|tables| tables.get_mut(table))
+                .filter(|tbl| inputs.iter().all(|input| tbl.sstables.iter().any(|sstable| Arc::ptr_eq(input, sstable))));
+            let Some(tbl) = current else {
+                tracing::warn!("Discarding compaction of {}.{}: its inputs were removed meanwhile", keyspace, table);
+                for output in &outputs {
+                    output.delete().await?;
+                }
+                return Ok(false);
+            };
+            if let Some(manifest) = tbl.manifest.as_mut() {
+                let directory = inputs.first().and_then(|sstable| sstable.file_path.parent()).unwrap_or(Path::new("."));
+                fault_injection::kill_point(FaultPoint::Compaction, directory)?;
+                let ids: Vec<String> = inputs.iter().map(|sstable| sstable.id.clone()).collect();
+                manifest.record_compaction(&ids, &outputs, task.level).await?;
+                fault_injection::kill_point(FaultPoint::CompactionCleanup, directory)?;
+            }
+            tbl.sstables.retain(|sstable| !inputs.iter().any(|input| Arc::ptr_eq(input, sstable)));
+            tbl.sstables.extend(outputs.into_iter().map(Arc::new));
+        }
+        for sstable in inputs {
+            sstable.delete().await?;
+        }
+        Ok(true)
+    }
+    
+    /// 테이블의 컴팩션 레벨별 SSTable 수와 크기 (Leveled가 아닌 테이블은 모두 L0)
+    pub async fn compaction_levels(&self, keyspace: &str, table: &str) -> Result<Vec<LevelStats>> {
+        let keyspaces = self.keyspaces.read().await;
+        let ks = keyspaces.get(keyspace).ok_or_else(|| CoreDBError::KeyspaceNotFound { keyspace: keyspace.to_string() })?;
+        let tables = ks.tables.read().await;
+        let tbl = tables.get(table).ok_or_else(|| CoreDBError::TableNotFound { table: table.to_string() })?;
+        let (max_levels, level_size_multiplier, sstable_size_mb) = match self.compaction_manager.strategy_for(&tbl.schema.options.compaction_strategy) {
+            CompactionStrategy::Leveled { max_levels, level_size_multiplier, sstable_size_mb } => (max_levels, level_size_multiplier, sstable_size_mb),
+            CompactionStrategy::SizeTiered { .. } | CompactionStrategy::TimeWindow { .. } => (1, 10.0, DEFAULT_LEVELED_SSTABLE_SIZE_MB),
+        };
+        let mut levels = LevelManager::new(max_levels, level_size_multiplier).with_sstable_size(sstable_size_mb * 1024 * 1024);
+        for (sstable, level) in tbl.sstable_levels() {
+            levels.add_sstable(sstable, level as usize);
+        }
+        Ok(levels.level_stats())
+    }
+    
+    /// 테이블("keyspace.table")별로 전략대로 정리하는 데 남은 컴팩션 수 (남은 것이 있는 테이블만)
+    async fn pending_compactions(&self) -> std::collections::BTreeMap<String, usize> {
+        let mut pending = std::collections::BTreeMap::new();
+        let mut add = |keyspace: &str, table: &str, strategy: &crate::schema::CompactionStrategy, sstables: &[(Arc<SSTable>, u32)]| {
+            let tasks = self.compaction_manager.strategy_for(strategy).estimated_tasks(sstables);
+            if tasks > 0 {
+                pending.insert(format!("{}.{}", keyspace, table), tasks);
+            }
+        };
+        {
+            let keyspaces = self.keyspaces.read().await;
+            for (keyspace_name, keyspace) in keyspaces.iter() {
+                for (table_name, table) in keyspace.tables.read().await.iter() {
+                    add(keyspace_name, table_name, &table.schema.options.compaction_strategy, &table.sstable_levels());
+                }
+            }
+        }
+        let manifests = self.flusher.engine_tables.manifests.lock().await;
+        let engine = self.query_engine.read().await;
+        for ((keyspace_name, table_name), manifest) in manifests.iter() {
+            let Ok(memtable) = engine.get_memtable(keyspace_name, table_name) else { continue };
+            let sstables = Self::engine_sstable_levels(&engine, manifest, keyspace_name, table_name);
+            add(keyspace_name, table_name, &memtable.table_schema().options.compaction_strategy, &sstables);
+        }
+        pending
+    }
+    
+    /// 모든 데이터가 만료된 SSTable 정리 (지운 SSTable 수 반환)
+    pub async fn drop_expired_sstables(&self) -> Result<usize> {
+        Self::cleanup_expired_data(&self.keyspaces).await
+    }
+    
+    /// 만료된 데이터 정리
+    ///
+    /// 모든 데이터가 만료되고 gc_grace_seconds가 지난 SSTable은 컴팩션으로 다시 쓰지 않고
+    /// 매니페스트와 SSTable 목록에서 빼고 파일을 지움
+    async fn cleanup_expired_data(keyspaces: &RwLock<HashMap<String, Keyspace>>) -> Result<usize> {
+        let now = chrono::Utc::now().timestamp();
+        let mut dropped = 0;
+        let keyspaces = keyspaces.read().await;
+        for (keyspace_name, keyspace) in keyspaces.iter() {
+            let mut tables = keyspace.tables.write().await;
+            for (table_name, table) in tables.iter_mut() {
+                dropped += table.drop_expired_sstables(keyspace_name, table_name, now).await?;
+            }
+        }
+        Ok(dropped)
+    }
+    
+    /// 뮤테이션인지 확인
+    fn is_mutation(&self, statement: &CqlStatement) -> bool {
+        matches!(statement, 
+            CqlStatement::Insert { .. } | 
+            CqlStatement::Update { .. } | 
+            CqlStatement::Delete { .. } |
+            CqlStatement::CreateKeyspace { .. } |
+            CqlStatement::CreateTable { .. } |
+            CqlStatement::CreateIndex { .. } |
+            CqlStatement::AlterTable { .. } |
+            CqlStatement::DropTable { .. } |
+            CqlStatement::DropKeyspace { .. }
+        )
+    }
+    
+    /// 커밋 로그에 뮤테이션 기록 (반영 전에 기록)
+    ///
+    /// 데이터 변경은 쿼리 엔진이 만든 행을, 스키마 변경은 CQL 원문을 기록함. 데이터 뮤테이션은 기록하지
+    /// 않았어도 대상 테이블과 함께 돌려주어 호출한 쪽이 같은 행을 반영하게 함
+    async fn log_mutation(&self, statement: &CqlStatement, query: &str) -> Result<Option<(String, String, Mutation)>> {
+        let (keyspace, table) = match statement {
+            CqlStatement::Insert { keyspace, table, .. } |
+            CqlStatement::Update { keyspace, table, .. } |
+            CqlStatement::Delete { keyspace, table, .. } |
+            CqlStatement::CreateIndex { keyspace, table, .. } => (keyspace.clone(), table.clone()),
+            CqlStatement::CreateTable { keyspace, name, .. } |
+            CqlStatement::AlterTable { keyspace, name, .. } |
+            CqlStatement::DropTable { keyspace, name } => (keyspace.clone(), name.clone()),
+            CqlStatement::CreateKeyspace { name, .. } |
+            CqlStatement::DropKeyspace { name } => (name.clone(), String::new()),
+            _ => return Ok(None),
+        };
+        let (data, durable_writes) = {
+            let engine = self.query_engine.read().await;
+            (engine.mutation_for(statement)?, engine.durable_writes(&keyspace))
+        };
+        let commit_log = self.commit_log_for(&keyspace);
+        // durable_writes = false거나 커밋 로그가 없으면 데이터 변경은 기록하지 않음 (스키마 변경은 재시작 뒤
+        // 키스페이스와 테이블을 되살리도록 기록)
+        let Some(commit_log) = commit_log.filter(|_| data.is_none() || durable_writes) else {
+            return Ok(data.map(|mutation| (keyspace, table, mutation)));
+        };
+        let (mutation, timestamp) = match &data {
+            Some(Mutation::Insert(row)) => (Mutation::Insert(row.clone()), row.timestamp),
+            Some(mutation) => (mutation.clone(), chrono::Utc::now().timestamp_micros()),
+            None => (Mutation::Schema { statement: query.trim().to_string() }, chrono::Utc::now().timestamp_micros()),
+        };
+        let started = std::time::Instant::now();
+        let end = commit_log.append(CommitLogEntry {
+            keyspace: keyspace.clone(),
+            table: table.clone(),
+            mutation,
+            timestamp,
+        }).await?;
+        commit_log.wait_for_sync(end, started).await?;
+        
+        Ok(data.map(|mutation| (keyspace, table, mutation)))
+    }
+    
+    /// 데이터베이스 통계
+    pub async fn get_stats(&self) -> DatabaseStats {
+        let keyspaces = self.keyspaces.read().await;
+        let mut total_tables = 0;
+        let mut total_memtables = 0;
+        let mut total_sstables = 0;
+        let mut total_size_bytes = 0u64;
+        let mut memtable_data_bytes = 0u64;
+        let mut memtable_overhead_bytes = 0u64;
+        let mut sstable_statistics = SSTableStatistics::default();
+        let mut row_cache = RowCacheStats::default();
+        let mut table_stats = Vec::new();
+        
+        for (keyspace_name, keyspace) in keyspaces.iter() {
+            let tables = keyspace.tables.read().await;
+            total_tables += tables.len();
+            
+            for (table_name, table) in tables.iter() {
+                let mut stats = TableStats {
+                    keyspace: keyspace_name.clone(),
+                    table: table_name.clone(),
+                    sstable_count: table.sstables.len(),
+                    sstable_size_bytes: 0,
+                    memtable_data_bytes: 0,
+                    statistics: SSTableStatistics::default(),
+                    metrics: table.metrics.snapshot(),
+                };
+                total_memtables += 1 + table.memtables.len();
+                total_sstables += table.sstables.len();
+                for memtable in std::iter::once(&table.current_memtable).chain(&table.memtables) {
+                    let memory = memtable.memory();
+                    total_size_bytes += memory.allocated_bytes();
+                    memtable_overhead_bytes += memory.overhead_bytes();
+                    stats.memtable_data_bytes += memory.data_bytes;
+                }
+                
+                for sstable in &table.sstables {
+                    stats.sstable_size_bytes += sstable.size_bytes;
+                    stats.statistics.merge(&sstable.statistics);
+                }
+                if let Some(cache) = &table.row_cache {
+                    row_cache.merge(&cache.stats());
+                }
+                total_size_bytes += stats.sstable_size_bytes;
+                memtable_data_bytes += stats.memtable_data_bytes;
+                sstable_statistics.merge(&stats.statistics);
+                table_stats.push(stats);
+            }
+        }
+        table_stats.sort_by(|a, b| (&a.keyspace, &a.table).cmp(&(&b.keyspace, &b.table)));
+        
+        DatabaseStats {
+            keyspace_count: keyspaces.len(),
+            table_count: total_tables,
+            memtable_count: total_memtables,
+            sstable_count: total_sstables,
+            total_size_bytes,
+            memtable_data_bytes,
+            memtable_overhead_bytes,
+            sstable_statistics,
+            key_cache: self.read_path.key_cache.as_ref().map(|cache| cache.stats()).unwrap_or_default(),
+            chunk_cache: self.read_path.chunk_cache.as_ref().map(|cache| cache.stats()).unwrap_or_default(),
+            row_cache,
+            reads: self.read_admission.stats(),
+            writes: self.write_admission.stats(),
+            compaction: self.compaction_manager.get_compaction_stats(self.pending_compactions().await).await,
+            commit_log: self.commit_log.as_ref().map(|commit_log| commit_log.stats()),
+            keyspace_commit_logs: self.keyspace_commit_logs.iter()
+                .map(|(keyspace, commit_log)| (keyspace.clone(), commit_log.stats()))
+                .collect(),
+            disk_usage: self.disk_monitor.as_ref().map(|monitor| monitor.stats()).unwrap_or_default(),
+            draining: self.is_draining(),
+            quotas: self.quotas.stats(),
+            operations: self.metrics.snapshot(),
+            connected_clients: self.clients.clients().len(),
+            tables: table_stats,
+        }
+    }
+    
+    /// 데이터베이스를 디스크에 저장 (checkpoint와 같음)
+    pub async fn save_to_disk(&self) -> Result<()> {
+        self.checkpoint().await.map(|_| ())
+    }
+    
+    /// 전체 체크포인트: 모든 메모리 테이블을 플러시하고 테이블마다 checkpoint-<시각> 스냅샷을 찍은 뒤
+    /// 스키마, SSTable 목록, 커밋 로그 위치를 data_directory의 스냅샷 파일에 기록
+    ///
+    /// CQL로 만든 테이블도 같은 이름으로 스냅샷하며, 그 스키마는 함께 스냅샷되는 system_schema.schema_versions에
+    /// 들어 있음. 이전 세대에서도 밀려난 체크포인트의 테이블 스냅샷은 지움
+    pub async fn checkpoint(&self) -> Result<Checkpoint> {
+        if self.config.ephemeral {
+            return Err(CoreDBError::Generic {
+                message: "Cannot save an ephemeral database to disk".to_string(),
+            });
+        }
+        let name = format!("{}{}", CHECKPOINT_SNAPSHOT_PREFIX, chrono::Utc::now().timestamp_millis());
+        validate_snapshot_name(&name)?;
+        self.flush_all_memtables().await?;
+        
+        let mut definitions: Vec<_> = {
+            let keyspaces = self.keyspaces.read().await;
+            keyspaces.values().map(|keyspace| keyspace.definition.clone()).collect()
+        };
+        definitions.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut tables = Vec::new();
+        for definition in &definitions {
+            let keyspace = &definition.name;
+            let table_names = Self::table_names(&self.flusher.keyspaces, keyspace).await?;
+            for info in Self::link_snapshots(&self.flusher, keyspace, &table_names, &name).await? {
+                let schema = {
+                    let keyspaces = self.keyspaces.read().await;
+                    let Some(ks) = keyspaces.get(keyspace) else { continue };
+                    let tables = ks.tables.read().await;
+                    let Some(table) = tables.get(&info.table) else { continue };
+                    (*table.schema).clone()
+                };
+                tables.push(CheckpointTable { keyspace: keyspace.clone(), table: info.table, schema, sstables: info.sstables });
+            }
+        }
+        for (keyspace, table) in self.engine_table_names().await {
+            let Ok(memtable) = self.query_engine.read().await.get_memtable(&keyspace, &table) else { continue };
+            let directory = self.config.data_directory.join(&keyspace).join(&table);
+            let info = snapshot_table_directory(&directory, &keyspace, &table, &name).await?;
+            tables.push(CheckpointTable { keyspace, table, schema: (**memtable.table_schema()).clone(), sstables: info.sstables });
+        }
+        let checkpoint = Checkpoint {
+            name,
+            created_at: chrono::Utc::now().timestamp_micros(),
+            schema_version: self.schema_version().await?.map_or(0, |change| change.version),
+            keyspaces: definitions,
+            tables,
+            commit_log_position: self.commit_log.as_ref().map(|commit_log| commit_log.position()),
+            keyspace_commit_log_positions: self.keyspace_commit_logs.iter()
+                .map(|(keyspace, commit_log)| (keyspace.clone(), commit_log.position()))
+                .collect(),
+        };
+        checkpoint.save(&self.config.data_directory)?;
+        
+        let retained = Checkpoint::retained_names(&self.config.data_directory);
+        for table in &checkpoint.tables {
+            let directory = self.config.data_directory.join(&table.keyspace).join(&table.table);
+            for info in list_table_snapshots(&directory).await? {
+                if info.name.starts_with(CHECKPOINT_SNAPSHOT_PREFIX) && !retained.contains(&info.name) {
+                    remove_table_snapshot(&directory, &info.name).await?;
+                }
+            }
+        }
+        tracing::info!("Checkpoint {} saved with {} tables", checkpoint.name, checkpoint.tables.len());
+        Ok(checkpoint)
+    }
+    
+    /// 마지막 체크포인트 상태로 데이터베이스 열기 (서버를 멈춘 상태에서)
+    ///
+    /// 테이블마다 체크포인트 스냅샷으로 되돌리고 체크포인트 시각까지의 커밋 로그만 반영한 뒤 기록된 키스페이스와
+    /// 테이블을 다시 만듦. 체크포인트 뒤의 SSTable은 pre-restore 스냅샷으로 남고, 체크포인트 뒤에 만든 테이블은 열지 않음
+    pub async fn open_checkpoint(config: DatabaseConfig) -> Result<(Self, Checkpoint)> {
+        let checkpoint = Checkpoint::load(&config.data_directory)?;
+        for table in &checkpoint.tables {
+            let restore = PointInTimeRestore {
+                archive_directory: None,
+                commitlog_directory: config.commitlog_keyspace_directories.get(&table.keyspace).unwrap_or(&config.commitlog_directory).clone(),
+                encryption: config.commitlog_encryption.clone(),
+                target: checkpoint.created_at,
+            };
+            let directory = config.data_directory.join(&table.keyspace).join(&table.table);
+            restore.restore_table(&directory, &table.keyspace, &table.table, &checkpoint.name).await?;
+        }
+        
+        // 시스템 테이블과 CQL로 만든 테이블은 CoreDB::new가 다시 엶
+        let db = Self::new(config).await?;
+        for definition in &checkpoint.keyspaces {
+            if !db.keyspaces.read().await.contains_key(&definition.name) {
+                db.create_keyspace_with_definition(definition.clone()).await?;
+            }
+        }
+        for table in &checkpoint.tables {
+            let exists = match db.keyspaces.read().await.get(&table.keyspace) {
+                Some(ks) => ks.tables.read().await.contains_key(&table.table),
+                None => false,
+            } || db.query_engine.read().await.has_table(&table.keyspace, &table.table);
+            if !exists {
+                db.create_table(table.keyspace.clone(), table.table.clone(), table.schema.clone()).await?;
+            }
+        }
+        Ok((db, checkpoint))
+    }
+    
+    /// 키스페이스를 다른 머신에서 가져올 수 있는 아카이브(tar.zst)로 내보냄 (`coredb export`)
+    ///
+    /// 그 키스페이스의 CQL 스키마 문, 테이블마다 SSTable과 스키마를 담음. 열린 테이블은 플러시한 뒤 스냅샷을 찍고,
+    /// 디스크에만 있는 테이블은 매니페스트의 SSTable을 그대로 담음. CQL로 만든 테이블은 플러시한 뒤 SSTable만 담고
+    /// 가져올 때 스키마 문으로 다시 만듦
+    pub async fn export_keyspace(&self, keyspace: &str, out: &Path) -> Result<KeyspaceArchive> {
+        if self.config.ephemeral {
+            return Err(CoreDBError::Archive { message: "Cannot export from an ephemeral database".to_string() });
+        }
+        let schema: Vec<String> = self.schema_history().await?.into_iter()
+            .filter(|change| crate::query::parser::CqlParser::parse(&change.statement)
+                .is_ok_and(|statement| statement.schema_keyspace() == Some(keyspace)))
+            .map(|change| change.statement)
+            .collect();
+        let checkpoint = Checkpoint::load(&self.config.data_directory).ok();
+        let open_definition = self.keyspaces.read().await.get(keyspace).map(|ks| ks.definition.clone());
+        let definition = match open_definition {
+            Some(definition) => Some(definition),
+            None => self.query_engine.read().await.keyspace_definition(keyspace).cloned()
+                .or_else(|| checkpoint.as_ref()?.keyspaces.iter().find(|definition| definition.name == keyspace).cloned()),
+        };
+        
+        let name = format!("export-{}", chrono::Utc::now().timestamp_millis());
+        let open_tables: Vec<_> = match Self::take_keyspace_snapshot(&self.flusher, keyspace, &name).await {
+            Ok(snapshots) => snapshots.into_iter().map(|info| info.table).collect(),
+            Err(CoreDBError::KeyspaceNotFound { .. }) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        for (_, table) in self.engine_table_names().await.into_iter().filter(|(ks, _)| ks == keyspace) {
+            self.flusher.flush_engine_table(keyspace, &table).await?;
+        }
+        // 열린 테이블은 방금 플러시해 매니페스트가 있음
+        let directories: Vec<_> = Manifest::find_tables(&self.config.data_directory).await?.into_iter()
+            .filter(|(ks, _, _)| ks == keyspace)
+            .map(|(_, table, directory)| (table, directory))
+            .collect();
+        if definition.is_none() && schema.is_empty() && directories.is_empty() {
+            return Err(CoreDBError::KeyspaceNotFound { keyspace: keyspace.to_string() });
+        }
+        let mut snapshotted = Vec::new();
+        let mut tables = Vec::new();
+        let collected = async {
+            for (table, directory) in &directories {
+                if !open_tables.contains(table) {
+                    snapshot_table_directory(directory, keyspace, table, &name).await?;
+                }
+                snapshotted.push(directory.clone());
+                let snapshot = snapshot_directory(directory, &name);
+                let sstables = Manifest::load(&snapshot).await?.map(|manifest| manifest.sstables).unwrap_or_default();
+                let open_schema = match self.keyspaces.read().await.get(keyspace) {
+                    Some(ks) => ks.tables.read().await.get(table).map(|table| (*table.schema).clone()),
+                    None => None,
+                };
+                let schema = open_schema.or_else(|| checkpoint.as_ref()?.tables.iter()
+                    .find(|checkpointed| checkpointed.keyspace == keyspace && &checkpointed.table == table)
+                    .map(|checkpointed| checkpointed.schema.clone()));
+                tables.push((table.clone(), schema, sstables, snapshot));
+            }
+            Ok::<_, CoreDBError>(())
+        }.await;
+        
+        let archive = KeyspaceArchive::new(keyspace, definition, schema);
+        let written = match collected {
+            Ok(()) => {
+                let out = out.to_path_buf();
+                tokio::task::spawn_blocking(move || write_keyspace_archive(&out, archive, tables)).await
+                    .map_err(|e| CoreDBError::Archive { message: format!("Export task failed: {}", e) })
+                    .and_then(|written| written)
+            },
+            Err(e) => Err(e),
+        };
+        for directory in &snapshotted {
+            remove_table_snapshot(directory, &name).await?;
+        }
+        let archive = written?;
+        tracing::info!("Exported keyspace {} to {} ({} tables, {} bytes of SSTables)", keyspace, out.display(), archive.tables.len(), archive.size_bytes());
+        Ok(archive)
+    }
+    
+    /// export_keyspace로 만든 아카이브를 가져옴 (`coredb import`, 서버를 멈춘 상태에서)
+    ///
+    /// 버전을 확인하고 SSTable을 풀어 매니페스트를 새로 쓴 뒤 저장 계층 테이블을 열고 스키마 문을 다시 실행함.
+    /// keyspace를 주면 그 이름으로 바꿔 가져오며, 같은 이름의 키스페이스가 이미 있으면 아무것도 바꾸지 않고 실패함
+    pub async fn import_keyspace(&self, path: &Path, keyspace: Option<&str>) -> Result<KeyspaceArchive> {
+        if self.config.ephemeral {
+            return Err(CoreDBError::Archive { message: "Cannot import into an ephemeral database".to_string() });
+        }
+        let archive = {
+            let path = path.to_path_buf();
+            tokio::task::spawn_blocking(move || read_archive_manifest(&path)).await
+                .map_err(|e| CoreDBError::Archive { message: format!("Import task failed: {}", e) })??
+        };
+        archive.validate()?;
+        let archive = match keyspace {
+            Some(keyspace) => archive.renamed(keyspace)?,
+            None => archive,
+        };
+        let target = archive.keyspace.clone();
+        let exists = self.keyspaces.read().await.contains_key(&target)
+            || self.query_engine.read().await.keyspace_definition(&target).is_some()
+            || Manifest::find_tables(&self.config.data_directory).await?.iter().any(|(ks, _, _)| *ks == target);
+        if exists {
+            return Err(CoreDBError::Archive { message: format!("Keyspace {} already exists", target) });
+        }
+        
+        let directory = self.config.data_directory.join(&target);
+        // 다른 노드의 커밋 로그 위치 대신 이 노드의 현재 위치부터 재생
+        let replay_position = self.keyspace_commit_logs.get(&target).or(self.commit_log.as_ref())
+            .map(|commit_log| commit_log.position())
+            .unwrap_or_default();
+        // 풀다가 실패하면 extract_keyspace_archive가 남긴 것 없이 정리함
+        let extract = (path.to_path_buf(), archive.clone(), directory.clone());
+        tokio::task::spawn_blocking(move || extract_keyspace_archive(&extract.0, &extract.1, &extract.2)).await
+            .map_err(|e| CoreDBError::Archive { message: format!("Import task failed: {}", e) })??;
+        let placed = async {
+            for table in &archive.tables {
+                Manifest::rebuild(&directory.join(&table.table), &table.sstables, replay_position).await?;
+            }
+            Ok::<_, CoreDBError>(())
+        }.await;
+        if let Err(e) = placed {
+            for table in &archive.tables {
+                tokio::fs::remove_dir_all(directory.join(&table.table)).await.ok();
+            }
+            return Err(e);
+        }
+        
+        // 스키마가 없는 테이블은 SSTable만 놓이고 열리지 않음
+        if archive.tables.iter().any(|table| table.schema.is_some()) {
+            let definition = archive.definition.clone().unwrap_or_else(|| KeyspaceDefinition {
+                name: target.clone(),
+                replication_factor: 1,
+                strategy: ReplicationStrategy::SimpleStrategy,
+                durable_writes: true,
+            });
+            self.create_keyspace_with_definition(definition).await?;
+            for table in &archive.tables {
+                if let Some(schema) = &table.schema {
+                    self.create_table(target.clone(), table.table.clone(), schema.clone()).await?;
+                }
+            }
+        }
+        for statement in &archive.schema {
+            self.execute_cql(statement).await?;
+        }
+        tracing::info!("Imported keyspace {} from {} ({} tables, {} bytes of SSTables)", target, path.display(), archive.tables.len(), archive.size_bytes());
+        Ok(archive)
+    }
+    
+    /// 모든 메모리 테이블 플러시 (CQL로 만든 쿼리 엔진 테이블 포함, 읽기 잠금을 놓은 뒤 수행, 데이터가 있던 테이블 수 반환)
+    async fn flush_all_memtables(&self) -> Result<usize> {
+        let mut targets = Vec::new();
+        {
+            let keyspaces = self.keyspaces.read().await;
+            for (keyspace_name, keyspace) in keyspaces.iter() {
+                let tables = keyspace.tables.read().await;
+                for (table_name, table) in tables.iter() {
+                    targets.push((keyspace_name.clone(), table_name.clone(), table.current_memtable.partition_count() > 0));
+                }
+            }
+        }
+        
+        let mut flushed = 0;
+        for (keyspace_name, table_name, has_data) in targets {
+            self.flush_memtable(&keyspace_name, &table_name).await?;
+            flushed += usize::from(has_data);
+        }
+        for (keyspace_name, table_name) in self.engine_table_names().await {
+            flushed += usize::from(self.flusher.flush_engine_table(&keyspace_name, &table_name).await?);
+        }
+        self.wait_for_flushes().await?;
+        Ok(flushed)
+    }
+    
+    /// 디스크에 디렉토리를 둔 쿼리 엔진 테이블 (키스페이스, 테이블 순)
+    async fn engine_table_names(&self) -> Vec<(String, String)> {
+        let mut tables: Vec<_> = self.flusher.engine_tables.manifests.lock().await.keys().cloned().collect();
+        tables.sort();
+        tables
+    }
+    
+    /// 재시작이나 업그레이드 전 정리 (`POST /drain`)
+    ///
+    /// 새 쓰기를 거부하고 실행 중인 쓰기가 끝나길 기다린 뒤 모든 메모리 테이블을 플러시하고,
+    /// 커밋 로그를 fsync해 플러시된 세그먼트를 정리함. 끝나면 다시 시작할 때 재생할 엔트리가 없음.
+    /// 읽기는 계속 받으며, 쓰기는 프로세스를 다시 시작해야 받음
+    pub async fn drain(&self) -> Result<DrainStats> {
+        self.draining.store(true, Ordering::SeqCst);
+        tracing::info!("Draining: no longer accepting writes");
+        self.write_admission.wait_idle().await;
+        
+        let flushed_memtables = self.flush_all_memtables().await?;
+        let commit_logs = self.commit_log.iter().map(|commit_log| (None, commit_log))
+            .chain(self.keyspace_commit_logs.iter().map(|(keyspace, commit_log)| (Some(keyspace.as_str()), commit_log)));
+        let mut retired_segments = 0;
+        for (dedicated, commit_log) in commit_logs {
+            commit_log.sync().await?;
+            retired_segments += Self::purge_commit_log_segments(&self.keyspaces, &self.flusher.engine_tables, &self.query_engine, commit_log, dedicated,
+                                                                &self.config.commitlog_keyspace_directories, &self.config.data_directory).await?;
+        }
+        
+        tracing::info!("Drained: flushed {} memtables, retired {} commit log segments", flushed_memtables, retired_segments);
+        Ok(DrainStats { flushed_memtables, retired_segments })
+    }
+    
+    /// 데이터베이스 종료
+    pub async fn shutdown(&self) -> Result<()> {
+        self.flush_all_memtables().await?;
+        for commit_log in self.commit_logs() {
+            commit_log.sync().await?;
+        }
+        
+        Ok(())
+    }
+}
+
+/// drain 결과
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DrainStats {
+    /// 플러시한 메모리 테이블 수
+    pub flushed_memtables: usize,
+    /// 정리한 커밋 로그 세그먼트 수
+    pub retired_segments: usize,
+}
+
+/// 데이터베이스 통계
+#[derive(Debug)]
+pub struct DatabaseStats {
+    pub keyspace_count: usize,
+    pub table_count: usize,
+    pub memtable_count: usize,
+    pub sstable_count: usize,
+    pub total_size_bytes: u64,
+    /// 메모리 테이블의 유효 데이터 바이트
+    pub memtable_data_bytes: u64,
+    /// 메모리 테이블의 힙 오버헤드 (아레나 여유 공간, 이전 버전, 인덱스 구조)
+    pub memtable_overhead_bytes: u64,
+    /// 모든 SSTable 통계 합계 (행/셀/툼스톤 수, 파티션 크기 분포, 압축률)
+    pub sstable_statistics: SSTableStatistics,
+    /// 파티션 키 캐시 적중률과 항목 수
+    pub key_cache: KeyCacheStats,
+    /// 청크 캐시 적중률과 사용량
+    pub chunk_cache: ChunkCacheStats,
+    /// 모든 테이블 행 캐시 합계
+    pub row_cache: RowCacheStats,
+    pub reads: AdmissionStats,
+    pub writes: AdmissionStats,
+    /// 컴팩션 대기열 길이와 실행 중인 컴팩션 수
+    pub compaction: CompactionStats,
+    /// 커밋 지연 시간 분포와 fsync 횟수 (커밋 로그가 없으면 None)
+    pub commit_log: Option<CommitLogStats>,
+    /// 전용 커밋 로그의 통계 (키스페이스별)
+    pub keyspace_commit_logs: HashMap<String, CommitLogStats>,
+    /// 디스크 사용 수위와 디렉토리별 사용률
+    pub disk_usage: DiskUsageStats,
+    /// drain을 시작해 쓰기를 거부하는 중
+    pub draining: bool,
+    /// 키스페이스별 한도와 거부 횟수
+    pub quotas: HashMap<String, QuotaStats>,
+    /// 쿼리, 행 읽기/쓰기, 플러시의 실행 시간 분포와 실패 수
+    pub operations: MetricsSnapshot,
+    pub connected_clients: usize,
+    /// 테이블별 통계 (키스페이스, 테이블 이름 순)
+    pub tables: Vec<TableStats>,
+}
+
+/// 테이블 하나의 크기와 읽기/쓰기 분포 (tablestats)
+#[derive(Debug, Clone)]
+pub struct TableStats {
+    pub keyspace: String,
+    pub table: String,
+    pub sstable_count: usize,
+    /// SSTable 파일 크기 합
+    pub sstable_size_bytes: u64,
+    /// 메모리 테이블의 유효 데이터 바이트 (플러시 대기 중인 것 포함)
+    pub memtable_data_bytes: u64,
+    /// 이 테이블 SSTable 통계 합계 (파티션 크기 분포 포함)
+    pub statistics: SSTableStatistics,
+    pub metrics: TableMetricsSnapshot,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{ColumnDefinition, CassandraDataType, TableSchema};
+    
+    
+    #[tokio::test]
+    async fn test_coredb_creation() {
+        let config = DatabaseConfig::ephemeral();
+        let db = CoreDB::new(config).await.unwrap();
+        
+        let stats = db.get_stats().await;
+        assert!(stats.keyspace_count >= 2); // system keyspaces
+    }
+    
+    #[tokio::test]
+    async fn test_keyspace_creation() {
+        let config = DatabaseConfig::ephemeral();
+        let db = CoreDB::new(config).await.unwrap();
+        
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        
+        let stats = db.get_stats().await;
+        assert!(stats.keyspace_count >= 3); // system + test_ks
+    }
+    
+    #[tokio::test]
+    async fn test_table_creation() {
+        // 테이블 디렉토리(매니페스트)가 생기므로 작업 디렉토리 대신 임시 디렉토리 사용
+        let test_dir = std::env::temp_dir().join("coredb_test_table_creation");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        
+        let schema = TableSchema::new(
+            "test_table".to_string(),
+            "test_ks".to_string(),
+            vec![ColumnDefinition {
+                name: "id".to_string(),
+                data_type: CassandraDataType::Int,
+                is_static: false,
+            }],
+            vec![],
+            vec![ColumnDefinition {
+                name: "name".to_string(),
+                data_type: CassandraDataType::Text,
+                is_static: false,
+            }],
+            vec![],
+        );
+        
+        db.create_table("test_ks".to_string(), "test_table".to_string(), schema).await.unwrap();
+        
+        let stats = db.get_stats().await;
+        assert!(stats.table_count >= 1);
+        assert!(test_dir.join("data/test_ks/test_table/MANIFEST").exists());
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_cql_execution() {
+        let config = DatabaseConfig::ephemeral();
+        let db = CoreDB::new(config).await.unwrap();
+        
+        let result = db.execute_cql("CREATE KEYSPACE test_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        assert!(result.is_success());
+        
+        let result = db.execute_cql("CREATE TABLE test_ks.test_table (id INT PRIMARY KEY, name TEXT)").await.unwrap();
+        assert!(result.is_success());
+        
+        let result = db.execute_cql("INSERT INTO test_ks.test_table (id, name) VALUES (1, 'John')").await.unwrap();
+        assert!(result.is_success());
+        
+        let QueryResult::Rows(rows) = db.execute_cql("SELECT * FROM test_ks.test_table WHERE id = 1").await.unwrap() else { panic!("expected rows") };
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get_column("name"), Some(&CassandraValue::Text("John".to_string())));
+    }
+    
+    #[tokio::test]
+    async fn test_multiget_merges_memtable_and_sstables() {
+        use crate::schema::{CassandraValue, Cell, Cells, ClusteringKey, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_multiget");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let schema = TableSchema::new(
+            "users".to_string(),
+            "test_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![],
+            vec![column("name", CassandraDataType::Text)],
+            vec![],
+        );
+        db.create_table("test_ks".to_string(), "users".to_string(), schema).await.unwrap();
+        
+        let key = |id: i32| PartitionKey { components: vec![CassandraValue::Int(id)] };
+        let row = |id: i32, name: &str, timestamp: i64| {
+            let mut cells = Cells::new();
+            cells.insert("name", Cell {
+                value: CassandraValue::Text(name.to_string()),
+                timestamp,
+                ttl: None,
+                is_deleted: false,
+            });
+            Row { partition_key: key(id), clustering_key: None, cells, timestamp }
+        };
+        
+        for id in 1..=3 {
+            db.insert_row("test_ks", "users", row(id, "old", 1)).await.unwrap();
+        }
+        let clustered = Row { clustering_key: Some(ClusteringKey { components: vec![CassandraValue::Int(1)] }), ..row(4, "clustered", 5) };
+        db.insert_row("test_ks", "users", clustered).await.unwrap();
+        db.shutdown().await.unwrap();
+        // 메모리 테이블의 최신 버전이 SSTable 버전보다 우선
+        db.insert_row("test_ks", "users", row(2, "new", 2)).await.unwrap();
+        // 클러스터링 키가 다른 SSTable 행은 더 최신이어도 섞이지 않음
+        db.insert_row("test_ks", "users", row(4, "plain", 2)).await.unwrap();
+        let plain = db.get_row("test_ks", "users", &key(4), &None).await.unwrap().unwrap();
+        assert_eq!(plain.cells["name"].value, CassandraValue::Text("plain".to_string()));
+        
+        let keys = vec![(key(3), None), (key(2), None), (key(9), None), (key(1), None)];
+        let rows = db.multiget("test_ks", "users", &keys).await.unwrap();
+        let names: Vec<Option<CassandraValue>> = rows.iter()
+            .map(|row| row.as_ref().map(|r| r.cells["name"].value.clone()))
+            .collect();
+        assert_eq!(names, vec![
+            Some(CassandraValue::Text("old".to_string())),
+            Some(CassandraValue::Text("new".to_string())),
+            None,
+            Some(CassandraValue::Text("old".to_string())),
+        ]);
+        
+        assert!(db.multiget("test_ks", "missing", &keys).await.is_err());
+        assert!(db.get_row("test_ks", "missing", &key(1), &None).await.unwrap().is_none());
+        
+        // 행 캐시를 켜도 같은 결과이고, 두 번째 조회부터는 캐시에서 읽음
+        db.set_row_cache_size("test_ks", "users", Some(1)).await.unwrap();
+        for _ in 0..2 {
+            let cached: Vec<Option<CassandraValue>> = db.multiget("test_ks", "users", &keys).await.unwrap().iter()
+                .map(|row| row.as_ref().map(|r| r.cells["name"].value.clone()))
+                .collect();
+            assert_eq!(cached, names);
+        }
+        let row_cache = db.get_stats().await.row_cache;
+        assert_eq!((row_cache.partitions, row_cache.hits, row_cache.misses), (4, 4, 4));
+        
+        // 쓰기는 해당 파티션만 무효화
+        db.insert_row("test_ks", "users", row(1, "newest", 3)).await.unwrap();
+        let row = db.get_row("test_ks", "users", &key(1), &None).await.unwrap().unwrap();
+        assert_eq!(row.cells["name"].value, CassandraValue::Text("newest".to_string()));
+        let row_cache = db.get_stats().await.row_cache;
+        assert_eq!((row_cache.partitions, row_cache.misses), (4, 5));
+        
+        db.set_row_cache_size("test_ks", "users", None).await.unwrap();
+        assert_eq!(db.get_stats().await.row_cache, RowCacheStats::default());
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_background_flush_keeps_rows_readable() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_background_flush");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let schema = TableSchema::new(
+            "users".to_string(),
+            "test_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![],
+            vec![column("name", CassandraDataType::Text)],
+            vec![],
+        );
+        db.create_table("test_ks".to_string(), "users".to_string(), schema).await.unwrap();
+        
+        let key = PartitionKey { components: vec![CassandraValue::Int(1)] };
+        let mut cells = Cells::new();
+        cells.insert("name", Cell {
+            value: CassandraValue::Text("a".to_string()),
+            timestamp: 1,
+            ttl: None,
+            is_deleted: false,
+        });
+        db.insert_row("test_ks", "users", Row { partition_key: key.clone(), clustering_key: None, cells, timestamp: 1 }).await.unwrap();
+        
+        // 플러시 요청 직후에도 (대기열 또는 SSTable에서) 행을 읽을 수 있어야 함
+        db.flush_memtable("test_ks", "users").await.unwrap();
+        assert!(db.get_row("test_ks", "users", &key, &None).await.unwrap().is_some());
+        
+        db.wait_for_flushes().await.unwrap();
+        let stats = db.get_stats().await;
+        assert_eq!(stats.sstable_count, 1);
+        {
+            let keyspaces = db.keyspaces.read().await;
+            let tables = keyspaces["test_ks"].tables.read().await;
+            assert!(tables["users"].memtables.is_empty());
+        }
+        assert!(db.get_row("test_ks", "users", &key, &None).await.unwrap().is_some());
+        
+        // 빈 메모리 테이블은 플러시하지 않음
+        db.shutdown().await.unwrap();
+        assert_eq!(db.get_stats().await.sstable_count, 1);
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_per_table_flush_threshold() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_table_flush_threshold");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            memtable_flush_threshold_mb: 64,
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let schema = |name: &str| TableSchema::new(
+            name.to_string(),
+            "test_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![],
+            vec![column("payload", CassandraDataType::Text)],
+            vec![],
+        );
+        let mut small = schema("metadata");
+        small.options.memtable_flush_threshold_mb = Some(1);
+        db.create_table("test_ks".to_string(), "metadata".to_string(), small).await.unwrap();
+        db.create_table("test_ks".to_string(), "series".to_string(), schema("series")).await.unwrap();
+        assert!(db.set_memtable_flush_threshold("test_ks", "series", Some(0)).await.is_err());
+        
+        // 두 테이블에 같은 양(약 1.2MB)을 쓰면 임계값이 1MB인 테이블만 플러시됨
+        let payload = "x".repeat(2048);
+        for table in ["metadata", "series"] {
+            for id in 0..600 {
+                let mut cells = Cells::new();
+                cells.insert("payload", Cell {
+                    value: CassandraValue::Text(payload.clone()),
+                    timestamp: 1,
+                    ttl: None,
+                    is_deleted: false,
+                });
+                let key = PartitionKey { components: vec![CassandraValue::Int(id)] };
+                db.insert_row("test_ks", table, Row { partition_key: key, clustering_key: None, cells, timestamp: 1 }).await.unwrap();
+            }
+        }
+        let flush_due = || async {
+            db.flusher.flush_due_memtables().await.unwrap();
+            db.wait_for_flushes().await.unwrap();
+        };
+        flush_due().await;
+        let sstable_counts = || async {
+            let keyspaces = db.keyspaces.read().await;
+            let tables = keyspaces["test_ks"].tables.read().await;
+            (tables["metadata"].sstables.len(), tables["series"].sstables.len())
+        };
+        assert_eq!(sstable_counts().await, (1, 0));
+        
+        // 관리 API로 임계값을 낮추면 다음 확인에서 플러시
+        db.set_memtable_flush_threshold("test_ks", "series", Some(1)).await.unwrap();
+        flush_due().await;
+        assert_eq!(sstable_counts().await, (1, 1));
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_flush_scheduler_flushes_old_memtables_in_background() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_flush_scheduler");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            memtable_max_age_secs: 1,
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let schema = TableSchema::new(
+            "events".to_string(),
+            "test_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![],
+            vec![column("payload", CassandraDataType::Text)],
+            vec![],
+        );
+        db.create_table("test_ks".to_string(), "events".to_string(), schema).await.unwrap();
+        let sstable_count = || async {
+            let keyspaces = db.keyspaces.read().await;
+            let tables = keyspaces["test_ks"].tables.read().await;
+            tables["events"].sstables.len()
+        };
+        
+        // 쓰기 경로는 플러시하지 않고, 임계값보다 작아도 최대 나이가 지나면 스케줄러가 플러시
+        let mut cells = Cells::new();
+        cells.insert("payload", Cell { value: CassandraValue::Text("x".to_string()), timestamp: 1, ttl: None, is_deleted: false });
+        db.insert_row("test_ks", "events", Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(1)] }, clustering_key: None, cells, timestamp: 1 }).await.unwrap();
+        assert_eq!(sstable_count().await, 0);
+        let started = std::time::Instant::now();
+        while sstable_count().await == 0 {
+            assert!(started.elapsed() < std::time::Duration::from_secs(10), "memtable was not flushed by the scheduler");
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        assert!(started.elapsed() >= std::time::Duration::from_millis(500));
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_apply_settings_at_runtime() {
+        let db = CoreDB::new(DatabaseConfig { chunk_cache_size_mb: 0, ..DatabaseConfig::ephemeral() }).await.unwrap();
+        assert_eq!(db.settings(), TunableSettings {
+            memtable_flush_threshold_mb: Some(64),
+            memtable_max_age_secs: Some(0),
+            compaction_throughput_mb_per_sec: Some(16),
+            slow_query_threshold_ms: Some(500),
+            key_cache_entries: Some(DEFAULT_KEY_CACHE_ENTRIES),
+            chunk_cache_size_mb: Some(0),
+        });
+        
+        // 지정한 항목만 바뀜
+        db.apply_settings(&TunableSettings {
+            memtable_flush_threshold_mb: Some(8),
+            compaction_throughput_mb_per_sec: Some(0),
+            slow_query_threshold_ms: Some(50),
+            key_cache_entries: Some(1600),
+            ..Default::default()
+        }).unwrap();
+        let settings = db.settings();
+        assert_eq!(settings.memtable_flush_threshold_mb, Some(8));
+        assert_eq!(settings.memtable_max_age_secs, Some(0));
+        assert_eq!(settings.compaction_throughput_mb_per_sec, Some(0));
+        assert_eq!(settings.slow_query_threshold_ms, Some(50));
+        assert_eq!(db.get_stats().await.key_cache.capacity, 1600);
+        
+        // 잘못된 항목이 있으면 나머지도 적용하지 않음
+        let invalid = [
+            TunableSettings { memtable_flush_threshold_mb: Some(0), slow_query_threshold_ms: Some(1), ..Default::default() },
+            TunableSettings { chunk_cache_size_mb: Some(64), slow_query_threshold_ms: Some(1), ..Default::default() },
+        ];
+        for settings in &invalid {
+            assert!(db.apply_settings(settings).is_err());
+        }
+        assert_eq!(db.settings(), settings);
+    }
+    
+    #[tokio::test]
+    async fn test_flushed_sstables_reopened_after_restart() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_reopen_sstables");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let schema = TableSchema::new(
+            "users".to_string(),
+            "test_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![],
+            vec![column("name", CassandraDataType::Text)],
+            vec![],
+        );
+        let key = PartitionKey { components: vec![CassandraValue::Int(7)] };
+        
+        {
+            let db = CoreDB::new(config.clone()).await.unwrap();
+            db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+            db.create_table("test_ks".to_string(), "users".to_string(), schema.clone()).await.unwrap();
+            let mut cells = Cells::new();
+            cells.insert("name", Cell {
+                value: CassandraValue::Text("kim".to_string()),
+                timestamp: 1,
+                ttl: None,
+                is_deleted: false,
+            });
+            db.insert_row("test_ks", "users", Row { partition_key: key.clone(), clustering_key: None, cells, timestamp: 1 }).await.unwrap();
+            db.shutdown().await.unwrap();
+        }
+        
+        // 매니페스트에 없는 파일은 중단된 플러시의 잔여물로 보고 삭제
+        let orphan = test_dir.join("data/test_ks/users/orphan-Data.db");
+        std::fs::write(&orphan, b"partial").unwrap();
+        
+        // 새 인스턴스에서 테이블을 다시 만들면 매니페스트의 SSTable이 연결됨
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        db.create_table("test_ks".to_string(), "users".to_string(), schema).await.unwrap();
+        let stats = db.get_stats().await;
+        assert_eq!(stats.sstable_count, 1);
+        assert_eq!((stats.sstable_statistics.row_count, stats.sstable_statistics.cell_count), (1, 1));
+        assert_eq!(stats.sstable_statistics.partition_sizes.count(), 1);
+        assert!(!orphan.exists());
+        let row = db.get_row("test_ks", "users", &key, &None).await.unwrap().unwrap();
+        assert_eq!(row.cells["name"].value, CassandraValue::Text("kim".to_string()));
+        
+        // 두 번째 읽기는 키 캐시에서 위치를 찾음
+        db.get_row("test_ks", "users", &key, &None).await.unwrap().unwrap();
+        let key_cache = db.get_stats().await.key_cache;
+        assert_eq!((key_cache.entries, key_cache.hits, key_cache.misses), (1, 1, 1));
+        // 청크도 두 번째 읽기에서는 캐시에서 풀린 상태로 가져옴
+        let chunk_cache = db.get_stats().await.chunk_cache;
+        assert_eq!((chunk_cache.chunks, chunk_cache.hits, chunk_cache.misses), (1, 1, 1));
+
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_ephemeral_mode_never_touches_disk() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_ephemeral");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            // 쓰기마다 임계값을 넘지만 플러시하지 않아야 함
+            memtable_flush_threshold_mb: 0,
+            ..DatabaseConfig::ephemeral()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        assert!(db.commit_log.is_none());
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let schema = TableSchema::new(
+            "users".to_string(),
+            "test_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![],
+            vec![column("name", CassandraDataType::Text)],
+            vec![],
+        );
+        db.create_table("test_ks".to_string(), "users".to_string(), schema).await.unwrap();
+        
+        for id in 0..10 {
+            let mut cells = Cells::new();
+            cells.insert("name", Cell {
+                value: CassandraValue::Text(format!("user{}", id)),
+                timestamp: 1,
+                ttl: None,
+                is_deleted: false,
+            });
+            let key = PartitionKey { components: vec![CassandraValue::Int(id)] };
+            db.insert_row("test_ks", "users", Row { partition_key: key, clustering_key: None, cells, timestamp: 1 }).await.unwrap();
+        }
+        db.shutdown().await.unwrap();
+        
+        let key = PartitionKey { components: vec![CassandraValue::Int(3)] };
+        assert!(db.get_row("test_ks", "users", &key, &None).await.unwrap().is_some());
+        assert_eq!(db.get_stats().await.sstable_count, 0);
+        assert!(db.save_to_disk().await.is_err());
+        assert!(!test_dir.exists());
+    }
+    
+    #[tokio::test]
+    async fn test_write_admission_rejects_when_queue_full() {
+        let test_dir = std::env::temp_dir().join("coredb_test_admission");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            concurrent_writes: 1,
+            max_queued_requests: 0,
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.execute_cql("CREATE KEYSPACE test_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql("CREATE TABLE test_ks.users (id INT PRIMARY KEY, name TEXT)").await.unwrap();
+        
+        // 쓰기 허가를 모두 점유한 상태에서는 대기 없이 거부
+        let permit = db.write_admission.acquire().await.unwrap();
+        let err = db.execute_cql("INSERT INTO test_ks.users (id, name) VALUES (1, 'a')").await.unwrap_err();
+        assert!(matches!(err, CoreDBError::Overloaded { ref operation, .. } if operation == "write"));
+        // 읽기 경로는 별도 한도
+        assert!(db.execute_cql("SELECT * FROM test_ks.users").await.is_ok());
+        drop(permit);
+        
+        db.execute_cql("INSERT INTO test_ks.users (id, name) VALUES (1, 'a')").await.unwrap();
+        let stats = db.get_stats().await;
+        assert_eq!(stats.writes.rejected, 1);
+        assert_eq!(stats.writes.active, 0);
+        assert_eq!(stats.reads.admitted, 1);
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_writes_bounds_parallel_cql_writes() {
+        use crate::schema::Row;
+        use std::sync::atomic::AtomicUsize;
+        
+        // 반영 후 트리거 안에 동시에 들어온 쓰기 수의 최댓값을 기록
+        struct Probe {
+            inside: Arc<AtomicUsize>,
+            peak: Arc<AtomicUsize>,
+        }
+        impl Trigger for Probe {
+            fn after_write(&self, _row: &Row) {
+                let inside = self.inside.fetch_add(1, Ordering::SeqCst) + 1;
+                self.peak.fetch_max(inside, Ordering::SeqCst);
+                std::thread::sleep(std::time::Duration::from_millis(50));
+                self.inside.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+        
+        // 쓰기는 엔진 읽기 잠금으로 동시에 실행되므로 동시 실행 수는 concurrent_writes가 정함
+        let db = Arc::new(CoreDB::new(DatabaseConfig { concurrent_writes: 2, ..DatabaseConfig::ephemeral() }).await.unwrap());
+        db.execute_cql("CREATE KEYSPACE cql_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        let inside = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let tables: Vec<String> = (0..6).map(|i| format!("items_{}", i)).collect();
+        for table in &tables {
+            db.execute_cql(&format!("CREATE TABLE cql_ks.{} (id INT PRIMARY KEY, name TEXT)", table)).await.unwrap();
+            db.register_trigger("cql_ks", table, "probe", Probe { inside: inside.clone(), peak: peak.clone() }).await.unwrap();
+        }
+        
+        let writes: Vec<_> = tables.iter().map(|table| {
+            let db = db.clone();
+            let query = format!("INSERT INTO cql_ks.{} (id, name) VALUES (1, 'a')", table);
+            tokio::spawn(async move { db.execute_cql(&query).await })
+        }).collect();
+        for write in writes {
+            write.await.unwrap().unwrap();
+        }
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        assert_eq!(db.get_stats().await.writes.active, 0);
+    }
+    
+    #[tokio::test]
+    async fn test_fully_expired_sstables_dropped_without_compaction() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_drop_expired_sstables");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let mut schema = TableSchema::new(
+            "events".to_string(),
+            "test_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![],
+            vec![column("name", CassandraDataType::Text)],
+            vec![],
+        );
+        schema.options.gc_grace_seconds = 60;
+        db.create_table("test_ks".to_string(), "events".to_string(), schema).await.unwrap();
+        
+        let hours_ago = |hours: i64| chrono::Utc::now().timestamp_micros() - hours * 3_600_000_000;
+        let write = |id: i32, timestamp: i64, ttl: Option<u32>, is_deleted: bool| {
+            let mut cells = Cells::new();
+            cells.insert("name", Cell { value: CassandraValue::Text("x".to_string()), timestamp, ttl, is_deleted });
+            let row = Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] }, clustering_key: None, cells, timestamp };
+            let db = &db;
+            async move {
+                db.insert_row("test_ks", "events", row).await.unwrap();
+                db.flush_memtable("test_ks", "events").await.unwrap();
+                db.wait_for_flushes().await.unwrap();
+            }
+        };
+        let data_files = || std::fs::read_dir(test_dir.join("data/test_ks/events")).unwrap()
+            .filter(|entry| entry.as_ref().unwrap().file_name().to_string_lossy().ends_with("-Data.db"))
+            .count();
+        
+        // 툼스톤만 있는 SSTable은 gc_grace_seconds가 지나면 통째로 지움
+        write(1, hours_ago(2), None, true).await;
+        assert_eq!((db.get_stats().await.sstable_count, data_files()), (1, 1));
+        assert_eq!(db.drop_expired_sstables().await.unwrap(), 1);
+        assert_eq!((db.get_stats().await.sstable_count, data_files()), (0, 0));
+        
+        // 더 오래된 데이터가 다른 SSTable에 있으면 툼스톤이 가릴 수 있으므로 남김
+        write(1, hours_ago(2), Some(60), false).await;
+        write(2, hours_ago(3), None, false).await;
+        assert_eq!(db.drop_expired_sstables().await.unwrap(), 0);
+        assert_eq!((db.get_stats().await.sstable_count, data_files()), (2, 2));
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_flushes_trigger_size_tiered_compaction() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_size_tiered_compaction");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let schema = TableSchema::new(
+            "users".to_string(),
+            "test_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![],
+            vec![column("name", CassandraDataType::Text)],
+            vec![],
+        );
+        let key = |id: i32| PartitionKey { components: vec![CassandraValue::Int(id)] };
+        let name = |row: Option<Row>| row.map(|row| row.cells["name"].value.clone());
+        let data_files = || std::fs::read_dir(test_dir.join("data/test_ks/users")).unwrap()
+            .filter(|entry| entry.as_ref().unwrap().file_name().to_string_lossy().ends_with("-Data.db"))
+            .count();
+        
+        {
+            let db = CoreDB::new(config.clone()).await.unwrap();
+            db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+            db.create_table("test_ks".to_string(), "users".to_string(), schema.clone()).await.unwrap();
+            
+            // 플러시마다 파티션 0을 덮어쓰므로 SSTable 네 개에 같은 파티션이 있음
+            for flush in 0..4 {
+                for id in [0, flush + 1] {
+                    let mut cells = Cells::new();
+                    cells.insert("name", Cell { value: CassandraValue::Text(format!("v{}", flush)), timestamp: flush as i64, ttl: None, is_deleted: false });
+                    db.insert_row("test_ks", "users", Row { partition_key: key(id), clustering_key: None, cells, timestamp: flush as i64 }).await.unwrap();
+                }
+                db.flush_memtable("test_ks", "users").await.unwrap();
+                db.wait_for_flushes().await.unwrap();
+            }
+            
+            // 네 번째 플러시가 요청한 백그라운드 컴팩션이 하나로 합침
+            for _ in 0..100 {
+                if db.get_stats().await.sstable_count == 1 {
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            }
+            assert_eq!((db.get_stats().await.sstable_count, data_files()), (1, 1));
+            assert!(!db.compact("test_ks", "users").await.unwrap());
+            assert_eq!(name(db.get_row("test_ks", "users", &key(0), &None).await.unwrap()), Some(CassandraValue::Text("v3".to_string())));
+            db.shutdown().await.unwrap();
+        }
+        
+        // 매니페스트에는 컴팩션 결과만 남아 다시 열어도 그대로
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        db.create_table("test_ks".to_string(), "users".to_string(), schema).await.unwrap();
+        assert_eq!((db.get_stats().await.sstable_count, data_files()), (1, 1));
+        for id in 0..5 {
+            let expected = if id == 0 { "v3".to_string() } else { format!("v{}", id - 1) };
+            assert_eq!(name(db.get_row("test_ks", "users", &key(id), &None).await.unwrap()), Some(CassandraValue::Text(expected)));
+        }
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_flushes_trigger_compaction_of_cql_tables() {
+        let test_dir = std::env::temp_dir().join("coredb_test_cql_compaction");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let data_files = || std::fs::read_dir(test_dir.join("data/cql_ks/items")).unwrap()
+            .filter(|entry| entry.as_ref().unwrap().file_name().to_string_lossy().ends_with("-Data.db"))
+            .count();
+        let name = |result: QueryResult| match result {
+            QueryResult::Rows(rows) => rows.first().map(|row| row.columns["name"].clone()),
+            _ => panic!("expected rows"),
+        };
+        
+        {
+            let db = CoreDB::new(config.clone()).await.unwrap();
+            db.execute_cql("CREATE KEYSPACE cql_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+            db.execute_cql("CREATE TABLE cql_ks.items (id INT PRIMARY KEY, name TEXT)").await.unwrap();
+            for flush in 0..4 {
+                for id in [0, flush + 1] {
+                    db.execute_cql(&format!("INSERT INTO cql_ks.items (id, name) VALUES ({}, 'v{}')", id, flush)).await.unwrap();
+                }
+                db.flush_memtable("cql_ks", "items").await.unwrap();
+            }
+            
+            // 네 번째 플러시가 요청한 백그라운드 컴팩션이 하나로 합침
+            for _ in 0..100 {
+                if db.query_engine.read().await.sstables("cql_ks", "items").len() == 1 {
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            }
+            assert_eq!((db.query_engine.read().await.sstables("cql_ks", "items").len(), data_files()), (1, 1));
+            let result = db.execute_cql("SELECT * FROM cql_ks.items WHERE id = 0").await.unwrap();
+            assert_eq!(name(result), Some(CassandraValue::Text("v3".to_string())));
+            db.shutdown().await.unwrap();
+        }
+        
+        // 매니페스트에는 컴팩션 결과만 남아 다시 열어도 그대로
+        let db = CoreDB::new(config).await.unwrap();
+        assert_eq!((db.query_engine.read().await.sstables("cql_ks", "items").len(), data_files()), (1, 1));
+        for id in 0..5 {
+            let expected = if id == 0 { "v3".to_string() } else { format!("v{}", id - 1) };
+            let result = db.execute_cql(&format!("SELECT * FROM cql_ks.items WHERE id = {}", id)).await.unwrap();
+            assert_eq!(name(result), Some(CassandraValue::Text(expected)));
+        }
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_leveled_compaction_moves_flushes_to_level_one() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_leveled_compaction");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        let query = "CREATE TABLE test_ks.users (id INT PRIMARY KEY, name TEXT) WITH compaction = {'class': 'LeveledCompactionStrategy'}";
+        let schema = crate::query::parser::CqlParser::parse(query).unwrap().table_schema().unwrap();
+        db.create_table("test_ks".to_string(), "users".to_string(), schema).await.unwrap();
+        
+        let level_counts = || async {
+            db.compaction_levels("test_ks", "users").await.unwrap().iter().map(|level| level.sstable_count).take(3).collect::<Vec<_>>()
+        };
+        let wait_for_level_zero = || async {
+            for _ in 0..100 {
+                if level_counts().await[0] == 0 {
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            }
+        };
+        for round in 0..2 {
+            for flush in 0..4 {
+                let id = round * 4 + flush;
+                for key in [0, id + 1] {
+                    let mut cells = Cells::new();
+                    cells.insert("name", Cell { value: CassandraValue::Text(format!("v{}", id)), timestamp: id as i64, ttl: None, is_deleted: false });
+                    db.insert_row("test_ks", "users", Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(key)] }, clustering_key: None, cells, timestamp: id as i64 }).await.unwrap();
+                }
+                db.flush_memtable("test_ks", "users").await.unwrap();
+                db.wait_for_flushes().await.unwrap();
+            }
+            // L0 네 개는 L1로 병합되고, 두 번째에는 키 범위가 겹치는 L1 SSTable도 함께 병합
+            wait_for_level_zero().await;
+            assert_eq!(level_counts().await, vec![0, 1, 0]);
+        }
+        
+        let keyspaces = db.keyspaces.read().await;
+        let tables = keyspaces["test_ks"].tables.read().await;
+        let table = &tables["users"];
+        assert_eq!(table.sstable_levels().iter().map(|(_, level)| *level).collect::<Vec<_>>(), vec![1]);
+        drop(tables);
+        drop(keyspaces);
+        let row = db.get_row("test_ks", "users", &PartitionKey { components: vec![CassandraValue::Int(0)] }, &None).await.unwrap().unwrap();
+        assert_eq!(row.cells["name"].value, CassandraValue::Text("v7".to_string()));
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_time_window_compaction_drops_expired_windows() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_time_window_compaction");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        let query = "CREATE TABLE test_ks.events (id INT PRIMARY KEY, name TEXT) WITH compaction = {'class': 'TimeWindowCompactionStrategy', 'compaction_window_unit': 'HOURS', 'compaction_window_size': 1}";
+        let mut schema = crate::query::parser::CqlParser::parse(query).unwrap().table_schema().unwrap();
+        schema.options.gc_grace_seconds = 60;
+        db.create_table("test_ks".to_string(), "events".to_string(), schema).await.unwrap();
+        
+        let hours_ago = |hours: i64| chrono::Utc::now().timestamp_micros() - hours * 3_600_000_000;
+        for (id, timestamp, ttl) in [(1, hours_ago(3), Some(60)), (2, hours_ago(0), None)] {
+            let mut cells = Cells::new();
+            cells.insert("name", Cell { value: CassandraValue::Text("x".to_string()), timestamp, ttl, is_deleted: false });
+            db.insert_row("test_ks", "events", Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] }, clustering_key: None, cells, timestamp }).await.unwrap();
+            db.flush_memtable("test_ks", "events").await.unwrap();
+            db.wait_for_flushes().await.unwrap();
+        }
+        
+        // 만료된 시간 창은 컴팩션에서 다시 쓰지 않고 통째로 지움 (백그라운드 컴팩션이 먼저 지울 수도 있음)
+        db.compact("test_ks", "events").await.unwrap();
+        assert_eq!(db.get_stats().await.sstable_count, 1);
+        let row = db.get_row("test_ks", "events", &PartitionKey { components: vec![CassandraValue::Int(2)] }, &None).await.unwrap();
+        assert!(row.is_some());
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_compaction_stats_count_pending_tasks_per_table() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_pending_compactions");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        for query in [
+            "CREATE TABLE test_ks.events (id INT PRIMARY KEY, name TEXT) WITH compaction = {'class': 'TimeWindowCompactionStrategy', 'compaction_window_unit': 'HOURS', 'compaction_window_size': 1}",
+            "CREATE TABLE test_ks.users (id INT PRIMARY KEY, name TEXT)",
+        ] {
+            let schema = crate::query::parser::CqlParser::parse(query).unwrap().table_schema().unwrap();
+            db.create_table("test_ks".to_string(), schema.name.clone(), schema).await.unwrap();
+        }
+        
+        // 컴팩션을 막아 둔 채 SSTable을 쌓음
+        let (events_lock, users_lock) = (db.compaction_manager.table_lock("test_ks", "events"), db.compaction_manager.table_lock("test_ks", "users"));
+        let (events_guard, users_guard) = (events_lock.lock().await, users_lock.lock().await);
+        let hours_ago = |hours: i64| chrono::Utc::now().timestamp_micros() - hours * 3_600_000_000;
+        let flushes = [("events", hours_ago(3)), ("events", hours_ago(3)), ("events", hours_ago(2)), ("events", hours_ago(2)), ("events", hours_ago(0))]
+            .into_iter()
+            .chain((0..4).map(|_| ("users", hours_ago(0))));
+        for (id, (table, timestamp)) in flushes.enumerate() {
+            let mut cells = Cells::new();
+            cells.insert("name", Cell { value: CassandraValue::Text("x".to_string()), timestamp, ttl: None, is_deleted: false });
+            db.insert_row("test_ks", table, Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id as i32)] }, clustering_key: None, cells, timestamp }).await.unwrap();
+            db.flush_memtable("test_ks", table).await.unwrap();
+            db.wait_for_flushes().await.unwrap();
+        }
+        
+        // 지난 시간 창 둘은 각각 한 번, Size-Tiered 버킷 하나는 한 번
+        let compaction = db.get_stats().await.compaction;
+        let expected: std::collections::BTreeMap<String, usize> = [("test_ks.events".to_string(), 2), ("test_ks.users".to_string(), 1)].into();
+        assert_eq!((compaction.pending_by_table, compaction.pending_tasks, compaction.queued_tables), (expected, 3, 2));
+        
+        drop((events_guard, users_guard));
+        for _ in 0..100 {
+            if db.get_stats().await.compaction.pending_tasks == 0 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        assert!(db.get_stats().await.compaction.pending_by_table.is_empty());
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_reopened_tables_are_compacted_with_their_strategy() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_reopen_compaction");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let open = |compaction: &str| {
+            let config = config.clone();
+            let query = format!("CREATE TABLE test_ks.events (id INT PRIMARY KEY, name TEXT) WITH compaction = {}", compaction);
+            async move {
+                let db = CoreDB::new(config).await.unwrap();
+                db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+                let schema = crate::query::parser::CqlParser::parse(&query).unwrap().table_schema().unwrap();
+                db.create_table("test_ks".to_string(), "events".to_string(), schema).await.unwrap();
+                db
+            }
+        };
+        
+        // Size-Tiered 임계값보다 적은 SSTable 두 개는 지난 시간 창에, 하나는 현재 창에 씀
+        let db = open("{'class': 'SizeTieredCompactionStrategy'}").await;
+        let now = chrono::Utc::now().timestamp_micros();
+        for (id, timestamp) in [(0, now - 3 * 3_600_000_000), (1, now - 3 * 3_600_000_000 + 1), (2, now)] {
+            let mut cells = Cells::new();
+            cells.insert("name", Cell { value: CassandraValue::Text("x".to_string()), timestamp, ttl: None, is_deleted: false });
+            db.insert_row("test_ks", "events", Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] }, clustering_key: None, cells, timestamp }).await.unwrap();
+            db.flush_memtable("test_ks", "events").await.unwrap();
+            db.wait_for_flushes().await.unwrap();
+        }
+        assert_eq!(db.get_stats().await.sstable_count, 3);
+        drop(db);
+        
+        // 시간 창 전략으로 다시 열면 플러시 없이도 지난 창의 SSTable을 합침
+        let db = open("{'class': 'TimeWindowCompactionStrategy', 'compaction_window_unit': 'HOURS', 'compaction_window_size': 1}").await;
+        for _ in 0..100 {
+            if db.get_stats().await.sstable_count == 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        assert_eq!(db.get_stats().await.sstable_count, 2);
+        for id in 0..3 {
+            assert!(db.get_row("test_ks", "events", &PartitionKey { components: vec![CassandraValue::Int(id)] }, &None).await.unwrap().is_some());
+        }
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_major_compaction_merges_all_sstables() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_major_compaction");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let schema = TableSchema::new(
+            "users".to_string(),
+            "test_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![],
+            vec![column("name", CassandraDataType::Text)],
+            vec![],
+        );
+        db.create_table("test_ks".to_string(), "users".to_string(), schema).await.unwrap();
+        assert!(!db.major_compact("test_ks", "users").await.unwrap());
+        assert!(matches!(db.major_compact("test_ks", "missing").await, Err(CoreDBError::TableNotFound { .. })));
+        
+        // Size-Tiered 임계값보다 적은 SSTable도 모두 합침
+        for id in 0..3 {
+            let mut cells = Cells::new();
+            cells.insert("name", Cell { value: CassandraValue::Text(format!("v{}", id)), timestamp: id as i64, ttl: None, is_deleted: false });
+            db.insert_row("test_ks", "users", Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] }, clustering_key: None, cells, timestamp: id as i64 }).await.unwrap();
+            db.flush_memtable("test_ks", "users").await.unwrap();
+            db.wait_for_flushes().await.unwrap();
+        }
+        assert_eq!(db.get_stats().await.sstable_count, 3);
+        assert!(db.major_compact("test_ks", "users").await.unwrap());
+        let stats = db.get_stats().await;
+        assert_eq!(stats.sstable_count, 1);
+        assert_eq!((stats.compaction.totals.completed, stats.compaction.totals.cells_written, stats.compaction.totals.cells_dropped), (1, 3, 0));
+        assert!(stats.compaction.running.is_empty());
+        assert!(db.compactions().is_empty());
+        assert!(!db.cancel_compaction("test_ks", "users"));
+        for id in 0..3 {
+            let row = db.get_row("test_ks", "users", &PartitionKey { components: vec![CassandraValue::Int(id)] }, &None).await.unwrap().unwrap();
+            assert_eq!(row.cells["name"].value, CassandraValue::Text(format!("v{}", id)));
+        }
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_tombstone_heavy_sstable_is_rewritten_alone() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_tombstone_compaction");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let mut schema = TableSchema::new(
+            "users".to_string(),
+            "test_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![],
+            vec![column("name", CassandraDataType::Text)],
+            vec![],
+        );
+        schema.options.gc_grace_seconds = 0;
+        schema.options.tombstone_compaction_interval = 0;
+        db.create_table("test_ks".to_string(), "users".to_string(), schema).await.unwrap();
+        
+        // SSTable 하나뿐이라 Size-Tiered로는 합칠 대상이 없지만 셀의 절반 가까이가 지울 수 있는 툼스톤
+        let timestamp = chrono::Utc::now().timestamp_micros() - 10_000_000;
+        for id in 0..9 {
+            let mut cells = Cells::new();
+            let deleted = id >= 5;
+            cells.insert("name", Cell { value: if deleted { CassandraValue::Null } else { CassandraValue::Text(format!("v{}", id)) }, timestamp, ttl: None, is_deleted: deleted });
+            db.insert_row("test_ks", "users", Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] }, clustering_key: None, cells, timestamp }).await.unwrap();
+        }
+        db.flush_memtable("test_ks", "users").await.unwrap();
+        db.wait_for_flushes().await.unwrap();
+        
+        // 플러시 뒤 백그라운드 컴팩션이 먼저 다시 쓸 수도 있음
+        db.compact("test_ks", "users").await.unwrap();
+        for _ in 0..100 {
+            if db.get_stats().await.sstable_statistics.tombstone_count == 0 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        let stats = db.get_stats().await;
+        assert_eq!(stats.sstable_count, 1);
+        assert_eq!((stats.sstable_statistics.tombstone_count, stats.sstable_statistics.cell_count), (0, 5));
+        assert_eq!(stats.compaction.totals.cells_dropped, 4);
+        
+        // 다시 쓴 결과는 툼스톤이 없으므로 더 고르지 않음
+        assert!(!db.compact("test_ks", "users").await.unwrap());
+        let row = db.get_row("test_ks", "users", &PartitionKey { components: vec![CassandraValue::Int(1)] }, &None).await.unwrap().unwrap();
+        assert_eq!(row.cells["name"].value, CassandraValue::Text("v1".to_string()));
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_alter_table_switches_compaction_strategy() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_alter_compaction");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        let query = "CREATE TABLE test_ks.users (id INT PRIMARY KEY, name TEXT) WITH compaction = {'class': 'LeveledCompactionStrategy'}";
+        let schema = crate::query::parser::CqlParser::parse(query).unwrap().table_schema().unwrap();
+        db.create_table("test_ks".to_string(), "users".to_string(), schema).await.unwrap();
+        
+        let levels = || async {
+            let keyspaces = db.keyspaces.read().await;
+            let tables = keyspaces["test_ks"].tables.read().await;
+            let mut levels: Vec<u32> = tables["users"].sstable_levels().iter().map(|(_, level)| *level).collect();
+            levels.sort();
+            levels
+        };
+        for id in 0..5 {
+            let mut cells = Cells::new();
+            cells.insert("name", Cell { value: CassandraValue::Text(format!("v{}", id)), timestamp: id as i64, ttl: None, is_deleted: false });
+            db.insert_row("test_ks", "users", Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] }, clustering_key: None, cells, timestamp: id as i64 }).await.unwrap();
+            db.flush_memtable("test_ks", "users").await.unwrap();
+            db.wait_for_flushes().await.unwrap();
+            // L0 네 개는 L1로 병합되고 다섯 번째는 L0에 남음
+            if id == 3 {
+                for _ in 0..100 {
+                    if levels().await == vec![1] {
+                        break;
+                    }
+                    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                }
+            }
+        }
+        assert_eq!(levels().await, vec![0, 1]);
+        
+        // 전략을 바꾸면 레벨을 모두 되돌려 새 전략이 처음부터 배치함
+        db.execute_cql("ALTER TABLE test_ks.users WITH compaction = {'class': 'SizeTieredCompactionStrategy'}").await.unwrap();
+        assert_eq!(levels().await, vec![0, 0]);
+        let keyspaces = db.keyspaces.read().await;
+        let tables = keyspaces["test_ks"].tables.read().await;
+        assert_eq!(tables["users"].schema.options.compaction_strategy, crate::schema::CompactionStrategy::SizeTiered);
+        drop(tables);
+        drop(keyspaces);
+        assert!(db.execute_cql("ALTER TABLE test_ks.missing WITH compaction = {'class': 'LeveledCompactionStrategy'}").await.is_err());
+        for id in 0..5 {
+            assert!(db.get_row("test_ks", "users", &PartitionKey { components: vec![CassandraValue::Int(id)] }, &None).await.unwrap().is_some());
+        }
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_alter_table_switches_compaction_strategy_of_cql_tables() {
+        let test_dir = std::env::temp_dir().join("coredb_test_alter_cql_compaction");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let levels = |db: &CoreDB| {
+            let (engine_tables, query_engine) = (db.flusher.engine_tables.clone(), db.query_engine.clone());
+            async move {
+                let manifests = engine_tables.manifests.lock().await;
+                let manifest = &manifests[&("cql_ks".to_string(), "users".to_string())];
+                let mut levels: Vec<u32> = query_engine.read().await.sstables("cql_ks", "users").iter()
+                    .map(|sstable| manifest.level(&sstable.id).unwrap_or(0))
+                    .collect();
+                levels.sort();
+                levels
+            }
+        };
+        let strategy = |db: &CoreDB| {
+            let query_engine = db.query_engine.clone();
+            async move { query_engine.read().await.get_memtable("cql_ks", "users").unwrap().table_schema().options.compaction_strategy.clone() }
+        };
+        
+        {
+            let db = CoreDB::new(config.clone()).await.unwrap();
+            db.execute_cql("CREATE KEYSPACE cql_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+            db.execute_cql("CREATE TABLE cql_ks.users (id INT PRIMARY KEY, name TEXT) WITH compaction = {'class': 'LeveledCompactionStrategy'}").await.unwrap();
+            for id in 0..5 {
+                db.execute_cql(&format!("INSERT INTO cql_ks.users (id, name) VALUES ({}, 'v{}')", id, id)).await.unwrap();
+                db.flush_memtable("cql_ks", "users").await.unwrap();
+                // L0 네 개는 L1로 병합되고 다섯 번째는 L0에 남음
+                if id == 3 {
+                    for _ in 0..100 {
+                        if levels(&db).await == vec![1] {
+                            break;
+                        }
+                        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                    }
+                }
+            }
+            assert_eq!(levels(&db).await, vec![0, 1]);
+            
+            // 전략을 바꾸면 레벨을 모두 되돌려 새 전략이 처음부터 배치함
+            db.execute_cql("ALTER TABLE cql_ks.users WITH compaction = {'class': 'SizeTieredCompactionStrategy'}").await.unwrap();
+            assert_eq!(levels(&db).await, vec![0, 0]);
+            assert_eq!(strategy(&db).await, crate::schema::CompactionStrategy::SizeTiered);
+            db.shutdown().await.unwrap();
+        }
+        
+        // 바뀐 전략은 스키마 기록으로 다시 열어도 유지됨
+        let db = CoreDB::new(config).await.unwrap();
+        assert_eq!(strategy(&db).await, crate::schema::CompactionStrategy::SizeTiered);
+        assert_eq!(levels(&db).await, vec![0, 0]);
+        let QueryResult::Rows(rows) = db.execute_cql("SELECT * FROM cql_ks.users").await.unwrap() else { panic!("expected rows") };
+        assert_eq!(rows.len(), 5);
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_writes_rejected_while_compaction_backlog_is_high() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_write_backlog");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            max_sstables_per_table: 3,
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let schema = TableSchema::new(
+            "users".to_string(),
+            "test_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![],
+            vec![column("name", CassandraDataType::Text)],
+            vec![],
+        );
+        db.create_table("test_ks".to_string(), "users".to_string(), schema).await.unwrap();
+        let row = |id: i32| {
+            let mut cells = Cells::new();
+            cells.insert("name", Cell { value: CassandraValue::Text(format!("v{}", id)), timestamp: id as i64, ttl: None, is_deleted: false });
+            Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] }, clustering_key: None, cells, timestamp: id as i64 }
+        };
+        
+        // Size-Tiered 임계값보다 적어 컴팩션되지 않는 SSTable이 한도에 닿으면 쓰기를 거부
+        for id in 0..3 {
+            db.insert_row("test_ks", "users", row(id)).await.unwrap();
+            db.flush_memtable("test_ks", "users").await.unwrap();
+            db.wait_for_flushes().await.unwrap();
+        }
+        let err = db.insert_row("test_ks", "users", row(3)).await.unwrap_err();
+        assert!(matches!(err, CoreDBError::Overloaded { ref operation, queued: 3, limit: 3 } if operation == "compaction"));
+        assert!(db.get_row("test_ks", "users", &PartitionKey { components: vec![CassandraValue::Int(3)] }, &None).await.unwrap().is_none());
+        
+        // 컴팩션으로 밀린 SSTable이 줄면 다시 받음
+        assert!(db.major_compact("test_ks", "users").await.unwrap());
+        db.insert_row("test_ks", "users", row(3)).await.unwrap();
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_unflushed_writes_replayed_from_commit_log() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_commit_log_replay");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let open = || async {
+            let db = CoreDB::new(config.clone()).await.unwrap();
+            db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+            let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+            for name in ["users", "other"] {
+                let schema = TableSchema::new(
+                    name.to_string(),
+                    "test_ks".to_string(),
+                    vec![column("id", CassandraDataType::Int)],
+                    vec![],
+                    vec![column("name", CassandraDataType::Text)],
+                    vec![],
+                );
+                db.create_table("test_ks".to_string(), name.to_string(), schema).await.unwrap();
+            }
+            db
+        };
+        let row = |id: i32| {
+            let mut cells = Cells::new();
+            cells.insert("name", Cell { value: CassandraValue::Text(format!("v{}", id)), timestamp: id as i64, ttl: None, is_deleted: false });
+            Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] }, clustering_key: None, cells, timestamp: id as i64 }
+        };
+        let memtable_partitions = |db: &CoreDB, table: &'static str| {
+            let keyspaces = db.keyspaces.clone();
+            async move {
+                let keyspaces = keyspaces.read().await;
+                let tables = keyspaces["test_ks"].tables.read().await;
+                tables[table].current_memtable.partition_count()
+            }
+        };
+        
+        // 두 행은 플러시하고 나머지 두 행은 메모리 테이블에만 둔 채 멈춤
+        let db = open().await;
+        for id in 0..2 {
+            db.insert_row("test_ks", "users", row(id)).await.unwrap();
+        }
+        db.flush_memtable("test_ks", "users").await.unwrap();
+        db.wait_for_flushes().await.unwrap();
+        for id in 2..4 {
+            db.insert_row("test_ks", "users", row(id)).await.unwrap();
+        }
+        db.insert_row("test_ks", "other", row(9)).await.unwrap();
+        drop(db);
+        
+        // 다시 열면 플러시되지 않은 쓰기만 해당 테이블의 메모리 테이블로 재생
+        let db = open().await;
+        assert_eq!(memtable_partitions(&db, "users").await, 2);
+        assert_eq!(memtable_partitions(&db, "other").await, 1);
+        for id in 0..4 {
+            let row = db.get_row("test_ks", "users", &PartitionKey { components: vec![CassandraValue::Int(id)] }, &None).await.unwrap().unwrap();
+            assert_eq!(row.cells["name"].value, CassandraValue::Text(format!("v{}", id)));
+        }
+        
+        // 재생한 쓰기가 플러시되면 다음에는 다시 재생하지 않음
+        db.flush_memtable("test_ks", "users").await.unwrap();
+        db.wait_for_flushes().await.unwrap();
+        drop(db);
+        let db = open().await;
+        assert_eq!(memtable_partitions(&db, "users").await, 0);
+        assert_eq!(db.get_stats().await.sstable_count, 2);
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_commit_log_sync_modes() {
+        use crate::schema::{CassandraValue, Cells, PartitionKey, Row};
+        use std::time::Duration;
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_commit_log_sync");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let row = |id: i32| Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] }, clustering_key: None, cells: Cells::new(), timestamp: 1 };
+        
+        for (name, sync) in [
+            ("always", CommitLogSync::Always),
+            ("batch", CommitLogSync::Batch { max_delay: Duration::from_millis(5) }),
+            ("group", CommitLogSync::Group { max_latency: Duration::from_millis(5) }),
+            ("periodic", CommitLogSync::Periodic { interval: Duration::from_millis(50) }),
+        ] {
+            let config = DatabaseConfig {
+                data_directory: test_dir.join(name).join("data"),
+                commitlog_directory: test_dir.join(name).join("commitlog"),
+                commitlog_sync: sync,
+                ..Default::default()
+            };
+            let db = CoreDB::new(config).await.unwrap();
+            db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+            let schema = TableSchema::new("t".to_string(), "test_ks".to_string(), vec![column("id", CassandraDataType::Int)], vec![], vec![], vec![]);
+            db.create_table("test_ks".to_string(), "t".to_string(), schema).await.unwrap();
+            db.insert_row("test_ks", "t", row(1)).await.unwrap();
+            
+            // Always, Batch, Group은 fsync된 뒤에 응답하고, Periodic은 다음 주기에 fsync
+            let commit_log = db.commit_log.clone().unwrap();
+            let (synced, end) = (*commit_log.subscribe_synced().borrow(), commit_log.position());
+            if !matches!(sync, CommitLogSync::Periodic { .. }) {
+                assert_eq!(synced, end, "{}", name);
+            }
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            assert_eq!(*commit_log.subscribe_synced().borrow(), end, "{}", name);
+            assert_eq!(db.get_stats().await.commit_log.unwrap().commit_latency.count(), 1, "{}", name);
+        }
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_restore_replays_archived_commit_log_up_to_point_in_time() {
+        use crate::schema::{CassandraValue, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_commit_log_restore");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let schema = || TableSchema::new("t".to_string(), "test_ks".to_string(), vec![column("id", CassandraDataType::Int)], vec![], vec![], vec![]);
+        let row = |id: i32| Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] }, clustering_key: None, cells: Cells::new(), timestamp: 1 };
+        let open = |name: &str, restore: Option<CommitLogRestore>| {
+            let config = DatabaseConfig {
+                data_directory: test_dir.join(name).join("data"),
+                commitlog_directory: test_dir.join(name).join("commitlog"),
+                commitlog_restore: restore,
+                ..Default::default()
+            };
+            async move {
+                let db = CoreDB::new(config).await.unwrap();
+                db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+                db.create_table("test_ks".to_string(), "t".to_string(), schema()).await.unwrap();
+                db
+            }
+        };
+        
+        // 원본: 두 행을 쓴 시각을 복구 시점으로 잡고 두 행을 더 씀
+        let db = open("source", None).await;
+        for id in 0..2 {
+            db.insert_row("test_ks", "t", row(id)).await.unwrap();
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        let point_in_time = chrono::Utc::now().timestamp_micros();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        for id in 2..4 {
+            db.insert_row("test_ks", "t", row(id)).await.unwrap();
+        }
+        db.commit_log.as_ref().unwrap().sync().await.unwrap();
+        drop(db);
+        
+        // 보관된 세그먼트 (원본 커밋 로그 복사본)
+        let archive_dir = test_dir.join("archive");
+        std::fs::create_dir_all(&archive_dir).unwrap();
+        for entry in std::fs::read_dir(test_dir.join("source").join("commitlog")).unwrap() {
+            let entry = entry.unwrap();
+            std::fs::copy(entry.path(), archive_dir.join(entry.file_name())).unwrap();
+        }
+        
+        // 빈 데이터 디렉토리 위에 복구 시점까지만 재생
+        let restored = open("restored", Some(CommitLogRestore { directory: archive_dir.clone(), point_in_time: Some(point_in_time) })).await;
+        let get = |db: &CoreDB, id: i32| {
+            let keyspaces = db.keyspaces.clone();
+            async move {
+                let keyspaces = keyspaces.read().await;
+                let tables = keyspaces["test_ks"].tables.read().await;
+                tables["t"].current_memtable.get(&PartitionKey { components: vec![CassandraValue::Int(id)] }, &None).is_some()
+            }
+        };
+        assert!(get(&restored, 0).await && get(&restored, 1).await);
+        assert!(!get(&restored, 2).await && !get(&restored, 3).await);
+        drop(restored);
+        
+        // 복구 시점이 없으면 끝까지 재생
+        let restored = open("latest", Some(CommitLogRestore { directory: archive_dir, point_in_time: None })).await;
+        assert!(get(&restored, 3).await);
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_commit_log_segments_purged_once_every_table_is_flushed() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_commit_log_purge");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let commitlog_dir = test_dir.join("commitlog");
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: commitlog_dir.clone(),
+            commitlog_segment_size_mb: 1,
+            ..Default::default()
+        };
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let open = || async {
+            let db = CoreDB::new(config.clone()).await.unwrap();
+            db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+            for name in ["big", "small"] {
+                let schema = TableSchema::new(
+                    name.to_string(),
+                    "test_ks".to_string(),
+                    vec![column("id", CassandraDataType::Int)],
+                    vec![],
+                    vec![column("payload", CassandraDataType::Blob)],
+                    vec![],
+                );
+                db.create_table("test_ks".to_string(), name.to_string(), schema).await.unwrap();
+            }
+            db
+        };
+        let row = |id: i32, bytes: usize| {
+            let mut cells = Cells::new();
+            cells.insert("payload", Cell { value: CassandraValue::Blob(vec![7; bytes]), timestamp: 1, ttl: None, is_deleted: false });
+            Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] }, clustering_key: None, cells, timestamp: 1 }
+        };
+        let segment_exists = |segment_id: u64| commitlog_dir.join(format!("commitlog-{}.log", segment_id)).exists();
+        
+        // small의 쓰기는 첫 세그먼트에, big의 쓰기는 여러 세그먼트에 걸침
+        let db = open().await;
+        db.insert_row("test_ks", "small", row(0, 10)).await.unwrap();
+        let mut id = 0;
+        while db.commit_log.as_ref().unwrap().current_segment_id() < 3 {
+            db.insert_row("test_ks", "big", row(id, 64 * 1024)).await.unwrap();
+            id += 1;
+        }
+        
+        // small이 아직 플러시되지 않아 big만 플러시해서는 정리되지 않음
+        db.flush_memtable("test_ks", "big").await.unwrap();
+        db.wait_for_flushes().await.unwrap();
+        assert!(segment_exists(0));
+        
+        // 모든 테이블이 플러시되면 쓰는 중인 세그먼트 앞은 모두 정리
+        db.flush_memtable("test_ks", "small").await.unwrap();
+        db.wait_for_flushes().await.unwrap();
+        assert!((0..3).all(|segment_id| !segment_exists(segment_id)));
+        assert!(segment_exists(3));
+        assert_eq!(db.purge_commit_log().await.unwrap(), 0);
+        drop(db);
+        
+        // 정리한 세그먼트의 데이터는 SSTable에서 읽힘
+        let db = open().await;
+        for (table, id) in [("small", 0), ("big", id - 1)] {
+            assert!(db.get_row("test_ks", table, &PartitionKey { components: vec![CassandraValue::Int(id)] }, &None).await.unwrap().is_some());
+        }
+        
+        // CQL로 만든 테이블도 플러시되기 전에는 자기 쓰기가 있는 세그먼트를 붙잡음
+        db.execute_cql("CREATE KEYSPACE cql_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql("CREATE TABLE cql_ks.items (id INT PRIMARY KEY, name TEXT)").await.unwrap();
+        db.flush_memtable(SYSTEM_SCHEMA_KEYSPACE, SCHEMA_VERSIONS_TABLE).await.unwrap();
+        db.execute_cql("INSERT INTO cql_ks.items (id, name) VALUES (1, 'a')").await.unwrap();
+        let pinned = db.commit_log.as_ref().unwrap().current_segment_id();
+        while db.commit_log.as_ref().unwrap().current_segment_id() < pinned + 2 {
+            db.insert_row("test_ks", "big", row(id, 64 * 1024)).await.unwrap();
+            id += 1;
+        }
+        db.flush_memtable("test_ks", "big").await.unwrap();
+        db.wait_for_flushes().await.unwrap();
+        assert!(segment_exists(pinned));
+        db.flush_memtable("cql_ks", "items").await.unwrap();
+        assert!(!segment_exists(pinned));
+        let QueryResult::Rows(rows) = db.execute_cql("SELECT * FROM cql_ks.items WHERE id = 1").await.unwrap() else { panic!("expected rows") };
+        assert_eq!(rows.len(), 1);
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_commit_log_total_space_forces_flush_of_oldest_tables() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_commit_log_total_space");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let commitlog_dir = test_dir.join("commitlog");
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: commitlog_dir.clone(),
+            commitlog_segment_size_mb: 1,
+            commitlog_total_space_mb: 2,
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        for name in ["hot", "cold"] {
+            let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+            let schema = TableSchema::new(
+                name.to_string(),
+                "test_ks".to_string(),
+                vec![column("id", CassandraDataType::Int)],
+                vec![],
+                vec![column("payload", CassandraDataType::Blob)],
+                vec![],
+            );
+            db.create_table("test_ks".to_string(), name.to_string(), schema).await.unwrap();
+        }
+        let row = |id: i32, bytes: usize| {
+            let mut cells = Cells::new();
+            cells.insert("payload", Cell { value: CassandraValue::Blob(vec![7; bytes]), timestamp: 1, ttl: None, is_deleted: false });
+            Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] }, clustering_key: None, cells, timestamp: 1 }
+        };
+        let commit_log = db.commit_log.clone().unwrap();
+        
+        // 거의 쓰지 않는 cold의 쓰기 하나가 첫 세그먼트를 붙잡고 있고 어느 테이블도 임계값(64MB)에 닿지 않음
+        db.insert_row("test_ks", "cold", row(0, 10)).await.unwrap();
+        let mut id = 0;
+        while commit_log.current_segment_id() < 6 {
+            db.insert_row("test_ks", "hot", row(id, 64 * 1024)).await.unwrap();
+            id += 1;
+        }
+        db.flusher.flush_due_memtables().await.unwrap();
+        db.wait_for_flushes().await.unwrap();
+        
+        // 한도를 넘을 때마다 가장 오래된 세그먼트를 붙잡은 테이블이 플러시되어 세그먼트가 정리됨
+        assert!(!commitlog_dir.join("commitlog-0.log").exists());
+        assert!(commit_log.oldest_segment_id() > 0);
+        assert!(commit_log.total_size() < 7 * 1024 * 1024);
+        assert_eq!(db.get_stats().await.commit_log.unwrap().total_size_bytes, commit_log.total_size());
+        let keyspaces = db.keyspaces.read().await;
+        let tables = keyspaces["test_ks"].tables.read().await;
+        assert_eq!(tables["cold"].sstables.len(), 1);
+        drop(tables);
+        drop(keyspaces);
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_keyspace_commit_log_directories() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_keyspace_commit_logs");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let critical_dir = test_dir.join("critical_commitlog");
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            commitlog_segment_size_mb: 1,
+            commitlog_keyspace_directories: HashMap::from([("critical".to_string(), critical_dir.clone())]),
+            ..Default::default()
+        };
+        let open = || async {
+            let db = CoreDB::new(config.clone()).await.unwrap();
+            for keyspace in ["critical", "bulk"] {
+                db.create_keyspace(keyspace.to_string(), 1).await.unwrap();
+                let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+                let schema = TableSchema::new(
+                    "events".to_string(),
+                    keyspace.to_string(),
+                    vec![column("id", CassandraDataType::Int)],
+                    vec![],
+                    vec![column("payload", CassandraDataType::Blob)],
+                    vec![],
+                );
+                db.create_table(keyspace.to_string(), "events".to_string(), schema).await.unwrap();
+            }
+            db
+        };
+        let row = |id: i32, bytes: usize| {
+            let mut cells = Cells::new();
+            cells.insert("payload", Cell { value: CassandraValue::Blob(vec![7; bytes]), timestamp: 1, ttl: None, is_deleted: false });
+            Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] }, clustering_key: None, cells, timestamp: 1 }
+        };
+        
+        // 전용 디렉토리가 있는 키스페이스의 쓰기는 그 커밋 로그에만 기록됨
+        let db = open().await;
+        db.insert_row("critical", "events", row(1, 10)).await.unwrap();
+        db.insert_row("bulk", "events", row(2, 10)).await.unwrap();
+        let critical_log = db.commit_log_for("critical").unwrap().clone();
+        let shared_log = db.commit_log.clone().unwrap();
+        assert!(!Arc::ptr_eq(&critical_log, &shared_log));
+        let keyspaces_of = |entries: Vec<CommitLogEntry>| entries.into_iter().map(|entry| entry.keyspace).collect::<Vec<_>>();
+        assert_eq!(keyspaces_of(critical_log.replay_all().await.unwrap()), vec!["critical"]);
+        assert!(!keyspaces_of(shared_log.replay_all().await.unwrap()).contains(&"critical".to_string()));
+        critical_log.sync().await.unwrap();
+        let mut changes = db.commit_log_changes(Some("critical"), CommitLogPosition::default()).await.unwrap();
+        assert_eq!(changes.next().await.unwrap().entry.keyspace, "critical");
+        
+        // 전용 커밋 로그는 다른 키스페이스의 플러시와 관계없이 정리됨
+        let mut id = 10;
+        while critical_log.current_segment_id() < 2 {
+            db.insert_row("critical", "events", row(id, 64 * 1024)).await.unwrap();
+            id += 1;
+        }
+        db.flush_memtable("critical", "events").await.unwrap();
+        db.wait_for_flushes().await.unwrap();
+        assert!(!critical_dir.join("commitlog-0.log").exists());
+        assert!(test_dir.join("commitlog").join("commitlog-0.log").exists());
+        assert!(db.get_stats().await.keyspace_commit_logs.contains_key("critical"));
+        db.insert_row("critical", "events", row(3, 10)).await.unwrap();
+        for commit_log in [&critical_log, &shared_log] {
+            commit_log.sync().await.unwrap();
+        }
+        drop(db);
+        
+        // 다시 열면 각 테이블이 자기 커밋 로그에서 재생됨
+        let db = open().await;
+        for (keyspace, id) in [("critical", 1), ("critical", 3), ("bulk", 2)] {
+            assert!(db.get_row(keyspace, "events", &PartitionKey { components: vec![CassandraValue::Int(id)] }, &None).await.unwrap().is_some());
+        }
+        drop(db);
+        
+        // 다른 커밋 로그와 디렉토리를 함께 쓸 수 없음
+        let shared = DatabaseConfig {
+            commitlog_keyspace_directories: HashMap::from([("critical".to_string(), config.commitlog_directory.clone())]),
+            ..config.clone()
+        };
+        assert!(CoreDB::new(shared).await.is_err());
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_cql_mutations_logged_before_applying() {
+        use crate::schema::CassandraValue;
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_cql_commit_log");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.execute_cql("CREATE KEYSPACE cql_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql("CREATE TABLE cql_ks.users (id INT PRIMARY KEY, name TEXT)").await.unwrap();
+        db.execute_cql("INSERT INTO cql_ks.users (id, name) VALUES (1, 'John')").await.unwrap();
+        // 테이블이 없는 INSERT는 기록하지 않고 실패
+        assert!(db.execute_cql("INSERT INTO cql_ks.missing (id) VALUES (1)").await.is_err());
+        
+        let mut entries = db.commit_log.clone().unwrap().replay_all().await.unwrap();
+        // 스키마 버전 기록 제외
+        entries.retain(|entry| entry.keyspace != SYSTEM_SCHEMA_KEYSPACE);
+        assert_eq!(entries.len(), 3);
+        assert!(matches!(&entries[0].mutation, Mutation::Schema { statement } if statement.starts_with("CREATE KEYSPACE cql_ks")));
+        assert_eq!((entries[0].keyspace.as_str(), entries[0].table.as_str()), ("cql_ks", ""));
+        assert!(matches!(&entries[1].mutation, Mutation::Schema { statement } if statement.starts_with("CREATE TABLE cql_ks.users")));
+        assert_eq!(entries[1].table, "users");
+        
+        // 기록한 행이 그대로 반영됨
+        let Mutation::Insert(logged) = &entries[2].mutation else { panic!("expected insert, got {:?}", entries[2].mutation) };
+        assert_eq!(logged.partition_key.components, vec![CassandraValue::Int(1)]);
+        assert_eq!(entries[2].timestamp, logged.timestamp);
+        let QueryResult::Rows(rows) = db.execute_cql("SELECT * FROM cql_ks.users WHERE id = 1").await.unwrap() else { panic!("expected rows") };
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get_column("name"), logged.cells.get("name").map(|cell| &cell.value));
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_cql_triggers_run_before_logging() {
+        use crate::schema::{Cell, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_cql_triggers_logged");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config.clone()).await.unwrap();
+        db.execute_cql("CREATE KEYSPACE cql_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql("CREATE TABLE cql_ks.items (id INT PRIMARY KEY, price INT, quantity INT, total INT)").await.unwrap();
+        db.register_trigger("cql_ks", "items", "compute_total", |row: &mut Row| {
+            let value = |name: &str| match row.cells.get(name).map(|cell| &cell.value) {
+                Some(CassandraValue::Int(value)) => Ok(*value),
+                _ => Err(format!("{} is required", name)),
+            };
+            let total = value("price")? * value("quantity")?;
+            let timestamp = row.timestamp;
+            row.cells.insert("total", Cell { value: CassandraValue::Int(total), timestamp, ttl: None, is_deleted: false });
+            Ok(())
+        }).await.unwrap();
+        
+        // 트리거가 채운 컬럼까지 기록되고, 거부된 쓰기는 기록되지 않음
+        db.execute_cql("INSERT INTO cql_ks.items (id, price, quantity) VALUES (1, 3, 4)").await.unwrap();
+        assert!(matches!(db.execute_cql("INSERT INTO cql_ks.items (id, price) VALUES (2, 5)").await, Err(CoreDBError::TriggerRejected { .. })));
+        let entries: Vec<_> = db.commit_log.clone().unwrap().replay_all().await.unwrap().into_iter()
+            .filter(|entry| entry.table == "items" && matches!(entry.mutation, Mutation::Insert(_)))
+            .collect();
+        assert_eq!(entries.len(), 1);
+        let Mutation::Insert(logged) = &entries[0].mutation else { unreachable!() };
+        assert_eq!(logged.cells.get("total").map(|cell| &cell.value), Some(&CassandraValue::Int(12)));
+        db.commit_log.as_ref().unwrap().sync().await.unwrap();
+        drop(db);
+        
+        // 재생한 행은 트리거 없이도 파생 컬럼을 가짐
+        let db = CoreDB::new(config).await.unwrap();
+        let QueryResult::Rows(rows) = db.execute_cql("SELECT * FROM cql_ks.items WHERE id = 1").await.unwrap() else { panic!("expected rows") };
+        assert_eq!(rows[0].get_column("total"), Some(&CassandraValue::Int(12)));
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_cql_writes_survive_restart() {
+        let test_dir = std::env::temp_dir().join("coredb_test_cql_restart");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let name_of = |rows: QueryResult| match rows {
+            QueryResult::Rows(rows) if rows.len() == 1 => rows[0].get_column("name").cloned(),
+            QueryResult::Rows(rows) if rows.is_empty() => None,
+            other => panic!("expected at most one row, got {:?}", other),
+        };
+        
+        // 1은 SSTable에, 2와 1을 덮어쓴 행은 커밋 로그에만 있음
+        let db = CoreDB::new(config.clone()).await.unwrap();
+        db.execute_cql("CREATE KEYSPACE cql_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql("CREATE TABLE cql_ks.users (id INT PRIMARY KEY, name TEXT)").await.unwrap();
+        db.execute_cql("CREATE TABLE cql_ks.dropped (id INT PRIMARY KEY, name TEXT)").await.unwrap();
+        db.execute_cql("INSERT INTO cql_ks.users (id, name) VALUES (1, 'John')").await.unwrap();
+        db.execute_cql("INSERT INTO cql_ks.dropped (id, name) VALUES (1, 'old')").await.unwrap();
+        db.flush_memtable("cql_ks", "users").await.unwrap();
+        db.flush_memtable("cql_ks", "dropped").await.unwrap();
+        assert!(test_dir.join("data/cql_ks/users/MANIFEST").exists());
+        db.execute_cql("INSERT INTO cql_ks.users (id, name) VALUES (2, 'Jane')").await.unwrap();
+        db.execute_cql("INSERT INTO cql_ks.users (id, name) VALUES (1, 'Johnny')").await.unwrap();
+        // 지웠다 다시 만든 테이블은 지운 테이블의 SSTable과 커밋 로그를 쓰지 않음
+        db.execute_cql("DROP TABLE cql_ks.dropped").await.unwrap();
+        assert!(!test_dir.join("data/cql_ks/dropped").exists());
+        db.execute_cql("CREATE TABLE cql_ks.dropped (id INT PRIMARY KEY, name TEXT)").await.unwrap();
+        db.commit_log.as_ref().unwrap().sync().await.unwrap();
+        drop(db);
+        
+        let db = CoreDB::new(config).await.unwrap();
+        assert_eq!(name_of(db.execute_cql("SELECT * FROM cql_ks.users WHERE id = 1").await.unwrap()), Some(CassandraValue::Text("Johnny".to_string())));
+        assert_eq!(name_of(db.execute_cql("SELECT * FROM cql_ks.users WHERE id = 2").await.unwrap()), Some(CassandraValue::Text("Jane".to_string())));
+        assert_eq!(name_of(db.execute_cql("SELECT * FROM cql_ks.dropped WHERE id = 1").await.unwrap()), None);
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_tracing_is_per_request() {
+        let test_dir = std::env::temp_dir().join("coredb_test_tracing_per_request");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.execute_cql("CREATE KEYSPACE cql_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql("CREATE TABLE cql_ks.users (id INT PRIMARY KEY, name TEXT)").await.unwrap();
+        
+        // TRACING ON은 다른 요청의 추적 여부를 바꾸지 않음
+        assert!(db.execute_cql("TRACING ON").await.is_err());
+        let (_, trace_id) = db.execute_cql_traced("INSERT INTO cql_ks.users (id, name) VALUES (1, 'Kim')").await.unwrap();
+        let trace_id = trace_id.expect("trace id");
+        db.execute_cql("SELECT * FROM cql_ks.users WHERE id = 1").await.unwrap();
+        
+        let QueryResult::Rows(sessions) = db.execute_cql("SELECT * FROM system_traces.sessions").await.unwrap() else { panic!("expected rows") };
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].get_column("session_id"), Some(&CassandraValue::UUID(trace_id)));
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_cql_index_rebuilt_from_sstables() {
+        let test_dir = std::env::temp_dir().join("coredb_test_cql_index_rebuild");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let ids_of = |rows: QueryResult| match rows {
+            QueryResult::Rows(rows) => {
+                let mut ids: Vec<_> = rows.iter().filter_map(|row| row.get_column("id").cloned()).collect();
+                ids.sort_by_key(|id| format!("{:?}", id));
+                ids
+            },
+            other => panic!("expected rows, got {:?}", other),
+        };
+        
+        // 인덱스는 SSTable로 내려간 행으로도 만들어짐
+        let db = CoreDB::new(config.clone()).await.unwrap();
+        db.execute_cql("CREATE KEYSPACE cql_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql("CREATE TABLE cql_ks.users (id INT PRIMARY KEY, city TEXT)").await.unwrap();
+        db.execute_cql("INSERT INTO cql_ks.users (id, city) VALUES (1, 'Salt AND Pepper')").await.unwrap();
+        db.flush_memtable("cql_ks", "users").await.unwrap();
+        db.execute_cql("CREATE INDEX ON cql_ks.users (city)").await.unwrap();
+        db.execute_cql("INSERT INTO cql_ks.users (id, city) VALUES (2, 'Salt AND Pepper')").await.unwrap();
+        db.execute_cql("CREATE CUSTOM INDEX ON cql_ks.users (city) USING 'fulltext'").await.unwrap();
+        let query = "SELECT * FROM cql_ks.users WHERE city = 'Salt AND Pepper'";
+        let match_query = "SELECT * FROM cql_ks.users WHERE MATCH(city, 'pepper')";
+        assert_eq!(ids_of(db.execute_cql(query).await.unwrap()), vec![CassandraValue::Int(1), CassandraValue::Int(2)]);
+        assert_eq!(ids_of(db.execute_cql(match_query).await.unwrap()), vec![CassandraValue::Int(1), CassandraValue::Int(2)]);
+        db.flush_memtable("cql_ks", "users").await.unwrap();
+        db.execute_cql("INSERT INTO cql_ks.users (id, city) VALUES (3, 'Salt AND Pepper')").await.unwrap();
+        db.commit_log.as_ref().unwrap().sync().await.unwrap();
+        drop(db);
+        
+        // 다시 열면 SSTable과 재생한 커밋 로그로 인덱스를 다시 채움
+        let db = CoreDB::new(config).await.unwrap();
+        assert_eq!(ids_of(db.execute_cql(query).await.unwrap()), vec![CassandraValue::Int(1), CassandraValue::Int(2), CassandraValue::Int(3)]);
+        assert_eq!(ids_of(db.execute_cql(match_query).await.unwrap()), vec![CassandraValue::Int(1), CassandraValue::Int(2), CassandraValue::Int(3)]);
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[cfg(feature = "fulltext")]
+    #[tokio::test]
+    async fn test_fulltext_index_persisted_with_flush() {
+        let test_dir = std::env::temp_dir().join("coredb_test_fulltext_persisted");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let match_count = |rows: QueryResult| match rows {
+            QueryResult::Rows(rows) => rows.len(),
+            other => panic!("expected rows, got {:?}", other),
+        };
+        let index_dir = test_dir.join("data").join("cql_ks").join("posts").join(crate::storage::FULLTEXT_DIRECTORY);
+        
+        let db = CoreDB::new(config.clone()).await.unwrap();
+        db.execute_cql("CREATE KEYSPACE cql_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql("CREATE TABLE cql_ks.posts (id INT PRIMARY KEY, body TEXT)").await.unwrap();
+        db.execute_cql("CREATE CUSTOM INDEX posts_body_idx ON cql_ks.posts (body) USING 'fulltext'").await.unwrap();
+        db.execute_cql("INSERT INTO cql_ks.posts (id, body) VALUES (1, 'Tantivy keeps the index')").await.unwrap();
+        db.flush_memtable("cql_ks", "posts").await.unwrap();
+        db.execute_cql("INSERT INTO cql_ks.posts (id, body) VALUES (2, 'the index after the flush')").await.unwrap();
+        db.commit_log.as_ref().unwrap().sync().await.unwrap();
+        drop(db);
+        
+        // 플러시 때 commit된 인덱스가 SSTable 옆에 남음
+        assert!(index_dir.join("posts_body_idx").join("meta.json").exists());
+        let db = CoreDB::new(config.clone()).await.unwrap();
+        assert_eq!(match_count(db.execute_cql("SELECT * FROM cql_ks.posts WHERE MATCH(body, 'index')").await.unwrap()), 2);
+        assert_eq!(match_count(db.execute_cql("SELECT * FROM cql_ks.posts WHERE MATCH(body, 'tantivy')").await.unwrap()), 1);
+        
+        // 지웠다 다시 만든 테이블은 이전 인덱스를 쓰지 않음
+        db.execute_cql("DROP TABLE cql_ks.posts").await.unwrap();
+        db.execute_cql("CREATE TABLE cql_ks.posts (id INT PRIMARY KEY, body TEXT)").await.unwrap();
+        db.execute_cql("CREATE CUSTOM INDEX posts_body_idx ON cql_ks.posts (body) USING 'fulltext'").await.unwrap();
+        assert_eq!(match_count(db.execute_cql("SELECT * FROM cql_ks.posts WHERE MATCH(body, 'index')").await.unwrap()), 0);
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_cql_writes_to_different_tables_run_concurrently() {
+        use crate::schema::Row;
+        use std::sync::atomic::AtomicUsize;
+        
+        // 다른 테이블의 쓰기도 반영 중이 될 때까지 반영 후 트리거에서 기다림 (쓰기가 엔진 전체를 잠그면 만나지 못함)
+        struct Rendezvous {
+            arrived: Arc<AtomicUsize>,
+            met: Arc<AtomicUsize>,
+        }
+        impl Trigger for Rendezvous {
+            fn after_write(&self, _row: &Row) {
+                self.arrived.fetch_add(1, Ordering::SeqCst);
+                let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+                while std::time::Instant::now() < deadline {
+                    if self.arrived.load(Ordering::SeqCst) >= 2 {
+                        self.met.fetch_add(1, Ordering::SeqCst);
+                        return;
+                    }
+                    std::thread::sleep(std::time::Duration::from_millis(1));
+                }
+            }
+        }
         
-        Ok(())
+        let db = Arc::new(CoreDB::new(DatabaseConfig::ephemeral()).await.unwrap());
+        db.execute_cql("CREATE KEYSPACE cql_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        let arrived = Arc::new(AtomicUsize::new(0));
+        let met = Arc::new(AtomicUsize::new(0));
+        for table in ["east", "west"] {
+            db.execute_cql(&format!("CREATE TABLE cql_ks.{} (id INT PRIMARY KEY, city TEXT)", table)).await.unwrap();
+            db.execute_cql(&format!("CREATE INDEX ON cql_ks.{} (city)", table)).await.unwrap();
+            db.register_trigger("cql_ks", table, "rendezvous", Rendezvous { arrived: arrived.clone(), met: met.clone() }).await.unwrap();
+        }
+        
+        let writes = ["east", "west"].map(|table| {
+            let db = db.clone();
+            tokio::spawn(async move { db.execute_cql(&format!("INSERT INTO cql_ks.{} (id, city) VALUES (1, 'Seoul')", table)).await })
+        });
+        for write in writes {
+            write.await.unwrap().unwrap();
+        }
+        assert_eq!(met.load(Ordering::SeqCst), 2);
+        
+        // 동시에 반영한 쓰기도 각 테이블의 인덱스에 들어감
+        for table in ["east", "west"] {
+            let QueryResult::Rows(rows) = db.execute_cql(&format!("SELECT * FROM cql_ks.{} WHERE city = 'Seoul'", table)).await.unwrap() else { panic!("expected rows") };
+            assert_eq!(rows.len(), 1);
+        }
     }
     
-    /// 백그라운드 작업 시작
-    async fn start_background_tasks(&self) {
-        // 컴팩션 스케줄러
-        let compaction_manager = self.compaction_manager.clone();
-        tokio::spawn(async move {
-            compaction_manager.run_compaction_loop().await;
-        });
+    #[tokio::test]
+    async fn test_durable_writes_false_skips_commit_log() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
         
-        // TTL 정리 작업
-        let keyspaces = self.keyspaces.clone();
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
-            loop {
-                interval.tick().await;
-                // TTL 만료된 데이터 정리
-                Self::cleanup_expired_data(&keyspaces).await;
-            }
-        });
+        let test_dir = std::env::temp_dir().join("coredb_test_durable_writes");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        
+        // CQL 경로: 스키마 변경만 기록되고 데이터 변경은 기록되지 않음
+        db.execute_cql("CREATE KEYSPACE scratch WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1} AND durable_writes = false").await.unwrap();
+        db.execute_cql("CREATE TABLE scratch.items (id INT PRIMARY KEY, name TEXT)").await.unwrap();
+        db.execute_cql("INSERT INTO scratch.items (id, name) VALUES (1, 'a')").await.unwrap();
+        let QueryResult::Rows(rows) = db.execute_cql("SELECT * FROM scratch.items WHERE id = 1").await.unwrap() else { panic!("expected rows") };
+        assert_eq!(rows.len(), 1);
+        
+        // 저장 계층 경로
+        db.create_keyspace_with_definition(KeyspaceDefinition {
+            name: "scratch_store".to_string(),
+            replication_factor: 1,
+            strategy: ReplicationStrategy::SimpleStrategy,
+            durable_writes: false,
+        }).await.unwrap();
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let schema = TableSchema::new(
+            "events".to_string(),
+            "scratch_store".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![],
+            vec![column("payload", CassandraDataType::Text)],
+            vec![],
+        );
+        db.create_table("scratch_store".to_string(), "events".to_string(), schema).await.unwrap();
+        let mut cells = Cells::new();
+        cells.insert("payload", Cell { value: CassandraValue::Text("x".to_string()), timestamp: 1, ttl: None, is_deleted: false });
+        db.insert_row("scratch_store", "events", Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(1)] }, clustering_key: None, cells, timestamp: 1 }).await.unwrap();
+        
+        let mut entries = db.commit_log.clone().unwrap().replay_all().await.unwrap();
+        entries.retain(|entry| entry.keyspace != SYSTEM_SCHEMA_KEYSPACE);
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|entry| matches!(entry.mutation, Mutation::Schema { .. })));
+        
+        // system_schema.keyspaces에 플래그가 보임
+        let QueryResult::Rows(rows) = db.execute_cql("SELECT * FROM system_schema.keyspaces WHERE keyspace_name = 'scratch'").await.unwrap() else { panic!("expected rows") };
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get_column("durable_writes"), Some(&CassandraValue::Boolean(false)));
+        db.execute_cql("DROP KEYSPACE scratch").await.unwrap();
+        let QueryResult::Rows(rows) = db.execute_cql("SELECT * FROM system_schema.keyspaces WHERE keyspace_name = 'scratch'").await.unwrap() else { panic!("expected rows") };
+        assert!(rows.is_empty());
+        
+        std::fs::remove_dir_all(&test_dir).ok();
     }
     
-    /// 만료된 데이터 정리
-    async fn cleanup_expired_data(keyspaces: &Arc<RwLock<HashMap<String, Keyspace>>>) {
-        // TTL 만료된 데이터 정리 로직
-        // 현재는 플레이스홀더
-        let _keyspaces = keyspaces.read().await;
-        // TODO: TTL 체크 및 삭제 로직 구현
+    #[tokio::test]
+    async fn test_writes_rejected_above_disk_usage_limit() {
+        use crate::disk_usage::{DirectoryUsage, DiskSpace, DiskUsageLevel};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_disk_usage");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.execute_cql("CREATE KEYSPACE disk_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql("CREATE TABLE disk_ks.items (id INT PRIMARY KEY, name TEXT)").await.unwrap();
+        db.execute_cql("INSERT INTO disk_ks.items (id, name) VALUES (1, 'a')").await.unwrap();
+        assert!(!db.get_stats().await.disk_usage.directories.is_empty());
+        
+        // 한계 수위를 넘으면 쓰기만 거부
+        let monitor = db.disk_monitor.clone().unwrap();
+        let usage = |used_percent: u64| vec![DirectoryUsage {
+            directory: test_dir.join("data"),
+            space: DiskSpace { total_bytes: 100, available_bytes: 100 - used_percent },
+        }];
+        assert_eq!(monitor.update(usage(97)), DiskUsageLevel::Full);
+        assert!(matches!(db.execute_cql("INSERT INTO disk_ks.items (id, name) VALUES (2, 'b')").await,
+                         Err(CoreDBError::InsufficientDiskSpace { used_percent: 97, limit: 95, .. })));
+        let QueryResult::Rows(rows) = db.execute_cql("SELECT * FROM disk_ks.items WHERE id = 1").await.unwrap() else { panic!("expected rows") };
+        assert_eq!(rows.len(), 1);
+        assert_eq!(db.get_stats().await.disk_usage.rejected_writes, 1);
+        
+        // 공간이 확보되면 다시 받음
+        assert_eq!(monitor.update(usage(50)), DiskUsageLevel::Normal);
+        db.execute_cql("INSERT INTO disk_ks.items (id, name) VALUES (2, 'b')").await.unwrap();
+        
+        std::fs::remove_dir_all(&test_dir).ok();
     }
     
-    /// 뮤테이션인지 확인
-    fn is_mutation(&self, statement: &CqlStatement) -> bool {
-        matches!(statement, 
-            CqlStatement::Insert { .. } | 
-            CqlStatement::Update { .. } | 
-            CqlStatement::Delete { .. } |
-            CqlStatement::CreateKeyspace { .. } |
-            CqlStatement::CreateTable { .. } |
-            CqlStatement::DropTable { .. } |
-            CqlStatement::DropKeyspace { .. }
-        )
+    #[tokio::test]
+    async fn test_schema_versions_and_migrations_survive_restart() {
+        let test_dir = std::env::temp_dir().join("coredb_test_migrations");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let migrations = vec![
+            Migration::new("001_init.cql", "CREATE KEYSPACE app WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1};\n\
+                                            CREATE TABLE app.users (id INT PRIMARY KEY, name TEXT);"),
+            Migration::new("002_notes.cql", "CREATE TABLE app.notes (id INT PRIMARY KEY, body TEXT); CREATE TABLE app.broken (id INT);"),
+        ];
+        
+        let db = CoreDB::new(config.clone()).await.unwrap();
+        assert_eq!(db.schema_version().await.unwrap(), None);
+        db.execute_cql("CREATE KEYSPACE other WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        let first = db.schema_version().await.unwrap().unwrap();
+        assert_eq!(first.version, 1);
+        assert!(first.statement.starts_with("CREATE KEYSPACE other"));
+        
+        // 실패한 문 앞까지만 적용하고, 실패한 스키마 문은 버전을 올리지 않음
+        assert!(matches!(db.migrate(&migrations).await, Err(CoreDBError::MigrationFailed { ref name, .. }) if name == "002_notes.cql"));
+        assert_eq!(db.schema_version().await.unwrap().unwrap().version, 4);
+        let partial = db.applied_migration("002_notes.cql").await.unwrap().unwrap();
+        assert_eq!((partial.statements_applied, partial.completed), (1, false));
+        db.shutdown().await.unwrap();
+        
+        // 재시작한 뒤에도 버전이 이어지고, 고친 마이그레이션은 거부하며 끝난 마이그레이션은 다시 적용하지 않음
+        let db = CoreDB::new(config.clone()).await.unwrap();
+        let history = db.schema_history().await.unwrap();
+        assert_eq!(history.iter().map(|change| change.version).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(history[3].statement, "CREATE TABLE app.notes (id INT PRIMARY KEY, body TEXT)");
+        let mut changed = migrations.clone();
+        changed[1] = Migration::new("002_notes.cql", "CREATE TABLE app.notes (id INT PRIMARY KEY, body BLOB); CREATE TABLE app.broken (id INT PRIMARY KEY);");
+        assert!(matches!(db.migrate(&changed).await, Err(CoreDBError::MigrationFailed { .. })));
+        assert_eq!(db.schema_version().await.unwrap().unwrap().version, 4);
+        
+        // 실패한 문을 고치면 그 문부터 이어서 적용 (앞서 만든 키스페이스와 테이블은 재시작 뒤에도 있음)
+        let mut fixed = migrations.clone();
+        fixed[1] = Migration::new("002_notes.cql", "CREATE TABLE app.notes (id INT PRIMARY KEY, body TEXT);\nCREATE TABLE app.broken (id INT PRIMARY KEY);");
+        assert_eq!(db.migrate(&fixed).await.unwrap(), vec!["002_notes.cql".to_string()]);
+        let applied = db.applied_migration("002_notes.cql").await.unwrap().unwrap();
+        assert_eq!((applied.statements_applied, applied.completed, applied.schema_version), (2, true, 5));
+        assert!(db.migrate(&fixed).await.unwrap().is_empty());
+        db.shutdown().await.unwrap();
+        
+        std::fs::remove_dir_all(&test_dir).ok();
     }
     
-    /// 커밋 로그에 뮤테이션 기록
-    async fn log_mutation(&self, statement: &CqlStatement) -> Result<()> {
-        // 현재는 단순화된 버전으로 로깅만 수행
-        // 실제 구현에서는 INSERT/UPDATE/DELETE를 Mutation으로 변환해야 함
-        match statement {
-            CqlStatement::Insert { .. } |
-            CqlStatement::Update { .. } |
-            CqlStatement::Delete { .. } => {
-                // WAL 기록 (현재는 건너뜀 - 단순화된 버전)
-                // 실제로는 statement를 Mutation으로 변환하여 CommitLog에 기록
-            },
-            _ => {}
+    #[tokio::test]
+    async fn test_schema_catalog_reloaded_after_restart() {
+        let test_dir = std::env::temp_dir().join("coredb_test_schema_catalog");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        
+        let db = CoreDB::new(config.clone()).await.unwrap();
+        for statement in [
+            "CREATE KEYSPACE app WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}",
+            "CREATE TABLE app.users (id INT PRIMARY KEY, name TEXT)",
+            "CREATE INDEX users_name ON app.users (name)",
+            "CREATE TABLE app.scratch (id INT PRIMARY KEY)",
+            "DROP TABLE app.scratch",
+        ] {
+            db.execute_cql(statement).await.unwrap();
         }
-        Ok(())
+        db.shutdown().await.unwrap();
+        drop(db);
+        
+        // 스키마 문은 한 번 플러시된 뒤에도 (커밋 로그 없이) 다시 적용됨
+        std::fs::remove_dir_all(test_dir.join("commitlog")).unwrap();
+        let db = CoreDB::new(config).await.unwrap();
+        {
+            let engine = db.query_engine.read().await;
+            assert!(engine.keyspace_definition("app").is_some());
+            assert!(engine.has_table("app", "users") && !engine.has_table("app", "scratch"));
+        }
+        db.execute_cql("INSERT INTO app.users (id, name) VALUES (1, 'kim')").await.unwrap();
+        let QueryResult::Rows(rows) = db.execute_cql("SELECT * FROM app.users WHERE name = 'kim'").await.unwrap() else { panic!("expected rows") };
+        assert_eq!(rows.len(), 1);
+        assert_eq!(db.schema_version().await.unwrap().unwrap().version, 5);
+        db.shutdown().await.unwrap();
+        
+        std::fs::remove_dir_all(&test_dir).ok();
     }
     
-    /// 데이터베이스 통계
-    pub async fn get_stats(&self) -> DatabaseStats {
-        let keyspaces = self.keyspaces.read().await;
-        let mut total_tables = 0;
-        let mut total_memtables = 0;
-        let mut total_sstables = 0;
-        let mut total_size_bytes = 0u64;
+    #[tokio::test]
+    async fn test_checkpoint_reopens_exactly_the_saved_state() {
+        use crate::schema::{Cells, PartitionKey, Row};
         
-        for keyspace in keyspaces.values() {
-            let tables = keyspace.tables.read().await;
-            total_tables += tables.len();
-            
-            for table in tables.values() {
-                total_memtables += 1; // current_memtable
-                total_sstables += table.sstables.len();
-                total_size_bytes += table.current_memtable.size_bytes();
-                
-                for sstable in &table.sstables {
-                    total_size_bytes += sstable.size_bytes;
-                }
-            }
+        let test_dir = std::env::temp_dir().join("coredb_test_checkpoint");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let schema = TableSchema::new("rows".to_string(), "app".to_string(),
+                                      vec![ColumnDefinition { name: "id".to_string(), data_type: CassandraDataType::Int, is_static: false }],
+                                      vec![], vec![], vec![]);
+        let row = |id: i32| Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] }, clustering_key: None, cells: Cells::new(), timestamp: 1 };
+        
+        let db = CoreDB::new(config.clone()).await.unwrap();
+        db.create_keyspace("app".to_string(), 1).await.unwrap();
+        db.create_table("app".to_string(), "rows".to_string(), schema).await.unwrap();
+        db.execute_cql("CREATE KEYSPACE cql_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql("CREATE TABLE cql_ks.items (id INT PRIMARY KEY)").await.unwrap();
+        db.insert_row("app", "rows", row(1)).await.unwrap();
+        db.execute_cql("INSERT INTO cql_ks.items (id) VALUES (1)").await.unwrap();
+        let checkpoint = db.checkpoint().await.unwrap();
+        assert_eq!(checkpoint.schema_version, 2);
+        for (keyspace, table) in [("app", "rows"), ("cql_ks", "items")] {
+            assert!(checkpoint.tables.iter().any(|checkpointed| (checkpointed.keyspace.as_str(), checkpointed.table.as_str(), checkpointed.sstables.len()) == (keyspace, table, 1)));
         }
         
-        DatabaseStats {
-            keyspace_count: keyspaces.len(),
-            table_count: total_tables,
-            memtable_count: total_memtables,
-            sstable_count: total_sstables,
-            total_size_bytes,
+        // 체크포인트 뒤의 쓰기와 스키마 변경은 SSTable까지 내려가도 되돌려짐
+        tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+        db.insert_row("app", "rows", row(2)).await.unwrap();
+        db.execute_cql("INSERT INTO cql_ks.items (id) VALUES (2)").await.unwrap();
+        db.execute_cql("CREATE TABLE cql_ks.later (id INT PRIMARY KEY)").await.unwrap();
+        db.shutdown().await.unwrap();
+        drop(db);
+        
+        let (db, loaded) = CoreDB::open_checkpoint(config.clone()).await.unwrap();
+        assert_eq!(loaded.name, checkpoint.name);
+        assert!(db.get_row("app", "rows", &row(1).partition_key, &None).await.unwrap().is_some());
+        assert!(db.get_row("app", "rows", &row(2).partition_key, &None).await.unwrap().is_none());
+        {
+            let engine = db.query_engine.read().await;
+            assert!(engine.has_table("cql_ks", "items") && !engine.has_table("cql_ks", "later"));
+        }
+        let count = |result: QueryResult| match result {
+            QueryResult::Rows(rows) => rows.len(),
+            other => panic!("expected rows, got {:?}", other),
+        };
+        assert_eq!(count(db.execute_cql("SELECT * FROM cql_ks.items WHERE id = 1").await.unwrap()), 1);
+        assert_eq!(count(db.execute_cql("SELECT * FROM cql_ks.items WHERE id = 2").await.unwrap()), 0);
+        let snapshots: Vec<_> = db.list_snapshots("app").await.unwrap().into_iter().map(|info| info.name).collect();
+        assert!(snapshots.contains(&checkpoint.name) && snapshots.iter().any(|name| name.starts_with(crate::restore::PRE_RESTORE_SNAPSHOT_PREFIX)));
+        
+        // 현재와 이전 세대가 가리키는 체크포인트 스냅샷만 남음
+        for _ in 0..crate::persistence::DEFAULT_SNAPSHOT_GENERATIONS + 2 {
+            tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+            db.checkpoint().await.unwrap();
         }
+        let checkpoints = db.list_snapshots("app").await.unwrap().into_iter()
+            .filter(|info| info.name.starts_with(CHECKPOINT_SNAPSHOT_PREFIX))
+            .count();
+        assert_eq!(checkpoints, crate::persistence::DEFAULT_SNAPSHOT_GENERATIONS + 1);
+        db.shutdown().await.unwrap();
+        
+        std::fs::remove_dir_all(&test_dir).ok();
     }
     
-    /// 데이터베이스를 디스크에 저장
-    pub async fn save_to_disk(&self) -> Result<()> {
-        use crate::persistence::Snapshot;
+    #[tokio::test]
+    async fn test_keyspace_snapshots_hardlink_sstables_and_prune_scheduled_ones() {
+        use crate::schema::{Cell, Cells, PartitionKey, Row};
+        use std::os::unix::fs::MetadataExt;
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_snapshots");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        let schema = TableSchema::new(
+            "rows".to_string(),
+            "app".to_string(),
+            vec![ColumnDefinition { name: "id".to_string(), data_type: CassandraDataType::Int, is_static: false }],
+            vec![],
+            vec![ColumnDefinition { name: "name".to_string(), data_type: CassandraDataType::Text, is_static: false }],
+            vec![],
+        );
+        db.create_keyspace("app".to_string(), 1).await.unwrap();
+        db.create_table("app".to_string(), "rows".to_string(), schema.clone()).await.unwrap();
+        let key = PartitionKey { components: vec![CassandraValue::Int(1)] };
+        let mut cells = Cells::new();
+        cells.insert("name", Cell { value: CassandraValue::Text("a".to_string()), timestamp: 1, ttl: None, is_deleted: false });
+        db.insert_row("app", "rows", Row { partition_key: key.clone(), clustering_key: None, cells, timestamp: 1 }).await.unwrap();
         
-        let snapshot = Snapshot::new(self.config.data_directory.to_string_lossy().to_string());
+        // 메모리 테이블의 쓰기도 플러시돼 스냅샷에 들어가고, 파일은 복사가 아닌 하드 링크
+        let snapshots = db.snapshot_keyspace("app", "before-upgrade").await.unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].sstables.len(), 1);
+        let table_dir = test_dir.join("data/app/rows");
+        let snapshot_dir = table_dir.join("snapshots/before-upgrade");
+        let data_file = format!("{}-Data.db", snapshots[0].sstables[0]);
+        assert_eq!(std::fs::metadata(table_dir.join(&data_file)).unwrap().ino(), std::fs::metadata(snapshot_dir.join(&data_file)).unwrap().ino());
+        assert!(matches!(db.snapshot_keyspace("app", "before-upgrade").await, Err(CoreDBError::Snapshot { .. })));
+        assert!(db.snapshot_keyspace("app", "../escape").await.is_err());
         
-        // 현재 데이터베이스 상태를 텍스트로 변환
-        let keyspaces = self.keyspaces.read().await;
-        let mut snapshot_data = String::new();
+        // 자동 스냅샷은 보관 정책에 따라 지워지고 직접 만든 스냅샷은 남음
+        let schedule = SnapshotSchedule::parse("interval=1h,keep=1").unwrap();
+        CoreDB::run_scheduled_snapshot(&db.flusher, "app", &schedule, 3600).await;
+        CoreDB::run_scheduled_snapshot(&db.flusher, "app", &schedule, 7200).await;
+        let names: Vec<String> = db.list_snapshots("app").await.unwrap().into_iter().map(|info| info.name).collect();
+        assert_eq!(names, vec!["auto-19700101T020000Z".to_string(), "before-upgrade".to_string()]);
+        db.shutdown().await.unwrap();
+        drop(db);
         
-        for (ks_name, keyspace) in keyspaces.iter() {
-            snapshot_data.push_str(&format!("KEYSPACE:{}\n", ks_name));
-            
-            let tables = keyspace.tables.read().await;
-            for (table_name, _table) in tables.iter() {
-                snapshot_data.push_str(&format!("TABLE:{}\n", table_name));
-            }
+        // 스냅샷 디렉토리를 테이블 디렉토리로 옮기면 그대로 열림
+        let restored_dir = test_dir.join("restored");
+        std::fs::create_dir_all(restored_dir.join("data/app/rows")).unwrap();
+        for entry in std::fs::read_dir(&snapshot_dir).unwrap() {
+            let entry = entry.unwrap();
+            std::fs::copy(entry.path(), restored_dir.join("data/app/rows").join(entry.file_name())).unwrap();
         }
+        let restored = CoreDB::new(DatabaseConfig {
+            data_directory: restored_dir.join("data"),
+            commitlog_directory: restored_dir.join("commitlog"),
+            ..Default::default()
+        }).await.unwrap();
+        restored.create_keyspace("app".to_string(), 1).await.unwrap();
+        restored.create_table("app".to_string(), "rows".to_string(), schema).await.unwrap();
+        assert!(restored.get_row("app", "rows", &key, &None).await.unwrap().is_some());
+        restored.shutdown().await.unwrap();
         
-        snapshot.save_text(&snapshot_data)?;
-        Ok(())
+        std::fs::remove_dir_all(&test_dir).ok();
     }
     
-    /// 데이터베이스 종료
-    pub async fn shutdown(&self) -> Result<()> {
-        // 모든 메모리 테이블 플러시
-        let keyspaces = self.keyspaces.read().await;
-        for (keyspace_name, keyspace) in keyspaces.iter() {
-            let tables = keyspace.tables.read().await;
-            for (table_name, _) in tables.iter() {
-                self.flush_memtable(keyspace_name, table_name).await?;
-            }
+    #[tokio::test]
+    async fn test_table_snapshot_flushes_only_that_table() {
+        use crate::schema::{Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_table_snapshot");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("app".to_string(), 1).await.unwrap();
+        for table in ["events", "users"] {
+            let schema = TableSchema::new(
+                table.to_string(),
+                "app".to_string(),
+                vec![ColumnDefinition { name: "id".to_string(), data_type: CassandraDataType::Int, is_static: false }],
+                vec![],
+                vec![ColumnDefinition { name: "name".to_string(), data_type: CassandraDataType::Text, is_static: false }],
+                vec![],
+            );
+            db.create_table("app".to_string(), table.to_string(), schema).await.unwrap();
+            let mut cells = Cells::new();
+            cells.insert("name", Cell { value: CassandraValue::Text(table.to_string()), timestamp: 1, ttl: None, is_deleted: false });
+            let key = PartitionKey { components: vec![CassandraValue::Int(1)] };
+            db.insert_row("app", table, Row { partition_key: key, clustering_key: None, cells, timestamp: 1 }).await.unwrap();
         }
         
-        Ok(())
+        // 스냅샷한 테이블만 플러시되고 다른 테이블의 메모리 테이블은 그대로
+        let info = db.snapshot_table("app", "events", "nightly").await.unwrap();
+        assert_eq!((info.table.as_str(), info.sstables.len()), ("events", 1));
+        assert!(info.size_bytes > 0);
+        assert_eq!(db.get_stats().await.tables.iter()
+                       .filter(|stats| stats.keyspace == "app")
+                       .map(|stats| (stats.table.as_str(), stats.sstable_count))
+                       .collect::<Vec<_>>(), vec![("events", 1), ("users", 0)]);
+        assert!(matches!(db.snapshot_table("app", "missing", "nightly").await, Err(CoreDBError::TableNotFound { .. })));
+        
+        // 서버 밖에서는 매니페스트의 SSTable로 스냅샷을 만듦
+        let table_dir = test_dir.join("data/app/events");
+        let offline = crate::snapshots::snapshot_table_directory(&table_dir, "app", "events", "offline").await.unwrap();
+        assert_eq!(offline.sstables, info.sstables);
+        let names: Vec<_> = db.list_snapshots("app").await.unwrap().into_iter().map(|info| (info.table, info.name)).collect();
+        assert_eq!(names, vec![("events".to_string(), "nightly".to_string()), ("events".to_string(), "offline".to_string())]);
+        
+        assert_eq!(db.clear_snapshot("app", None, "nightly").await.unwrap(), 1);
+        assert_eq!(db.clear_snapshot("app", Some("events"), "nightly").await.unwrap(), 0);
+        assert_eq!(db.list_snapshots("app").await.unwrap().len(), 1);
+        db.shutdown().await.unwrap();
+        
+        std::fs::remove_dir_all(&test_dir).ok();
     }
-}
-
-/// 데이터베이스 통계
-#[derive(Debug)]
-pub struct DatabaseStats {
-    pub keyspace_count: usize,
-    pub table_count: usize,
-    pub memtable_count: usize,
-    pub sstable_count: usize,
-    pub total_size_bytes: u64,
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::schema::{ColumnDefinition, CassandraDataType, TableSchema, PartitionKey, CassandraValue};
-    use std::collections::HashMap;
     
     #[tokio::test]
-    async fn test_coredb_creation() {
-        let config = DatabaseConfig::default();
+    async fn test_table_stats_track_reads_and_writes_per_table() {
+        use crate::schema::{Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_table_stats");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
         let db = CoreDB::new(config).await.unwrap();
+        let schema = TableSchema::new(
+            "rows".to_string(),
+            "app".to_string(),
+            vec![ColumnDefinition { name: "id".to_string(), data_type: CassandraDataType::Int, is_static: false }],
+            vec![],
+            vec![ColumnDefinition { name: "name".to_string(), data_type: CassandraDataType::Text, is_static: false }],
+            vec![],
+        );
+        db.create_keyspace("app".to_string(), 1).await.unwrap();
+        db.create_table("app".to_string(), "rows".to_string(), schema).await.unwrap();
+        let row = |id: i32, timestamp: i64, is_deleted: bool| {
+            let mut cells = Cells::new();
+            cells.insert("name", Cell { value: CassandraValue::Text("a".to_string()), timestamp, ttl: None, is_deleted });
+            Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] }, clustering_key: None, cells, timestamp }
+        };
+        
+        // 1번 키는 두 SSTable에 나뉘고 그중 하나는 툼스톤, 2번 키는 메모리 테이블에만
+        db.insert_row("app", "rows", row(1, 1, false)).await.unwrap();
+        db.flush_memtable("app", "rows").await.unwrap();
+        db.insert_row("app", "rows", row(1, 2, true)).await.unwrap();
+        db.flush_memtable("app", "rows").await.unwrap();
+        db.wait_for_flushes().await.unwrap();
+        db.insert_row("app", "rows", row(2, 3, false)).await.unwrap();
+        for id in [1, 2] {
+            db.get_row("app", "rows", &PartitionKey { components: vec![CassandraValue::Int(id)] }, &None).await.unwrap();
+        }
         
         let stats = db.get_stats().await;
-        assert!(stats.keyspace_count >= 2); // system keyspaces
+        let table = stats.tables.iter().find(|table| (table.keyspace.as_str(), table.table.as_str()) == ("app", "rows")).unwrap();
+        assert_eq!((table.sstable_count, table.statistics.tombstone_count, table.statistics.partition_sizes.count()), (2, 1, 2));
+        assert!(table.sstable_size_bytes > 0 && table.memtable_data_bytes > 0);
+        assert_eq!((table.metrics.write_latency.count(), table.metrics.read_latency.count()), (3, 2));
+        assert_eq!((table.metrics.sstables_per_read.count(), table.metrics.sstables_per_read.percentile(0.5), table.metrics.sstables_per_read.max_micros), (2, 0, 2));
+        assert_eq!(table.metrics.tombstones_per_read.max_micros, 1);
+        db.shutdown().await.unwrap();
+        
+        std::fs::remove_dir_all(&test_dir).ok();
     }
     
     #[tokio::test]
-    async fn test_keyspace_creation() {
-        let config = DatabaseConfig::default();
+    async fn test_prometheus_metrics_track_operations() {
+        use crate::schema::{Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_metrics");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
         let db = CoreDB::new(config).await.unwrap();
+        db.execute_cql("CREATE KEYSPACE app WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql("CREATE TABLE app.items (id INT PRIMARY KEY, name TEXT)").await.unwrap();
+        db.execute_cql("INSERT INTO app.items (id, name) VALUES (1, 'a')").await.unwrap();
+        db.execute_cql("SELECT * FROM app.items WHERE id = 1").await.unwrap();
+        assert!(db.execute_cql("SELECT * FROM app.missing WHERE id = 1").await.is_err());
+        assert!(db.execute_cql("SELEKT nothing").await.is_err());
         
-        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        // 행 API 쓰기와 읽기, 플러시
+        let schema = TableSchema::new(
+            "rows".to_string(),
+            "app".to_string(),
+            vec![ColumnDefinition { name: "id".to_string(), data_type: CassandraDataType::Int, is_static: false }],
+            vec![],
+            vec![ColumnDefinition { name: "name".to_string(), data_type: CassandraDataType::Text, is_static: false }],
+            vec![],
+        );
+        db.create_keyspace("app".to_string(), 1).await.unwrap();
+        db.create_table("app".to_string(), "rows".to_string(), schema).await.unwrap();
+        let key = PartitionKey { components: vec![CassandraValue::Int(1)] };
+        let mut cells = Cells::new();
+        cells.insert("name", Cell { value: CassandraValue::Text("a".to_string()), timestamp: 1, ttl: None, is_deleted: false });
+        db.insert_row("app", "rows", Row { partition_key: key.clone(), clustering_key: None, cells, timestamp: 1 }).await.unwrap();
+        db.flush_memtable("app", "rows").await.unwrap();
+        db.wait_for_flushes().await.unwrap();
+        assert!(db.get_row("app", "rows", &key, &None).await.unwrap().is_some());
         
         let stats = db.get_stats().await;
-        assert!(stats.keyspace_count >= 3); // system + test_ks
+        let operations = &stats.operations;
+        assert_eq!((operations.queries["select"].duration.count(), operations.queries["select"].failures), (2, 1));
+        assert_eq!(operations.queries["invalid"].failures, 1);
+        assert_eq!((operations.writes.duration.count(), operations.reads.duration.count(), operations.flushes.duration.count()), (1, 1, 1));
+        assert!(operations.flushed_bytes > 0);
+        
+        let text = db.prometheus_metrics().await;
+        assert!(text.contains("# TYPE coredb_cql_query_duration_seconds histogram\n"));
+        assert!(text.contains("coredb_cql_query_duration_seconds_count{statement=\"insert\"} 1\n"));
+        assert!(text.contains("coredb_cql_query_failures_total{statement=\"select\"} 1\n"));
+        assert!(text.contains("coredb_flush_duration_seconds_count 1\n"));
+        assert!(text.contains("coredb_commitlog_syncs_total{commitlog=\"shared\"}"));
+        assert!(text.contains("coredb_cache_hits_total{cache=\"keys\"}"));
+        db.shutdown().await.unwrap();
+        
+        std::fs::remove_dir_all(&test_dir).ok();
     }
     
     #[tokio::test]
-    async fn test_table_creation() {
-        let config = DatabaseConfig::default();
+    async fn test_virtual_tables_reflect_node_state() {
+        let db = CoreDB::new(DatabaseConfig::ephemeral()).await.unwrap();
+        db.execute_cql("CREATE KEYSPACE app WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        let select = |query: &'static str| {
+            let db = &db;
+            async move {
+                let QueryResult::Rows(rows) = db.execute_cql(query).await.unwrap() else { panic!("expected rows") };
+                rows
+            }
+        };
+        
+        // 노드 정보 (스키마 버전은 마지막 스키마 변경)
+        let local = select("SELECT * FROM system.local WHERE key = 'local'").await;
+        assert_eq!(local.len(), 1);
+        assert_eq!(local[0].columns.get("host_id"), Some(&CassandraValue::UUID(db.host_id())));
+        let schema_version = db.schema_version().await.unwrap().unwrap();
+        assert_eq!(local[0].columns.get("schema_version"), Some(&CassandraValue::UUID(schema_version.id)));
+        
+        // 연결된 클라이언트는 읽을 때마다 다시 계산
+        assert!(select("SELECT * FROM system_views.clients").await.is_empty());
+        let session = db.clients().connect("10.0.0.1:50000".parse().unwrap(), Some("TLSv1_3".to_string()));
+        session.connection().set_username("reporting");
+        let clients = select("SELECT * FROM system_views.clients").await;
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].columns.get("username"), Some(&CassandraValue::Text("reporting".to_string())));
+        assert_eq!(clients[0].columns.get("ssl_enabled"), Some(&CassandraValue::Boolean(true)));
+        drop(session);
+        assert!(select("SELECT * FROM system_views.clients").await.is_empty());
+        
+        let caches = select("SELECT * FROM system_views.caches").await;
+        let mut names: Vec<_> = caches.iter().filter_map(|row| match row.columns.get("name") {
+            Some(CassandraValue::Text(name)) => Some(name.clone()),
+            _ => None,
+        }).collect();
+        names.sort();
+        assert_eq!(names, vec!["chunks", "keys", "rows"]);
+        assert!(select("SELECT * FROM system_views.sstable_tasks").await.is_empty());
+        
+        // 가상 테이블은 읽기 전용
+        assert!(matches!(db.execute_cql("INSERT INTO system.local (key, rack) VALUES ('local', 'rack2')").await,
+                         Err(CoreDBError::ReadOnlyTable { .. })));
+        assert!(matches!(db.execute_cql("DROP TABLE system_views.clients").await, Err(CoreDBError::ReadOnlyTable { .. })));
+    }
+    
+    #[tokio::test]
+    async fn test_driver_metadata_queries() {
+        let db = CoreDB::new(DatabaseConfig::ephemeral()).await.unwrap();
+        db.set_rpc_address("10.0.0.5:9042".parse().unwrap());
+        db.execute_cql("CREATE KEYSPACE app WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql("CREATE TABLE app.events (tenant TEXT, ts BIGINT, body TEXT, PRIMARY KEY (tenant, ts)) WITH compaction = {'class': 'LeveledCompactionStrategy'}").await.unwrap();
+        db.execute_cql("CREATE INDEX events_body ON app.events (body)").await.unwrap();
+        let select = |query: &'static str| {
+            let db = &db;
+            async move {
+                let QueryResult::Rows(rows) = db.execute_cql(query).await.unwrap() else { panic!("expected rows") };
+                rows
+            }
+        };
+        let text = |value: &str| CassandraValue::Text(value.to_string());
+        
+        // 연결할 때 읽는 노드 정보 (단일 노드라 peers는 비어 있음)
+        let local = select("SELECT * FROM system.local WHERE key='local'").await;
+        assert_eq!(local[0].get_column("rpc_address"), Some(&text("10.0.0.5")));
+        assert_eq!(local[0].get_column("partitioner"), Some(&text(PARTITIONER)));
+        assert_eq!(local[0].get_column("release_version"), Some(&text(CASSANDRA_RELEASE_VERSION)));
+        assert_eq!(local[0].get_column("tokens"), Some(&CassandraValue::Set(vec![text("0")])));
+        assert!(select("SELECT * FROM system.peers").await.is_empty());
+        assert!(select("SELECT peer, data_center, host_id, rack, release_version, rpc_address, schema_version, tokens FROM system.peers").await.is_empty());
+        assert!(select("SELECT * FROM system.peers_v2").await.is_empty());
+        
+        // 메타데이터 갱신에 쓰는 system_schema 테이블
+        let keyspaces = select("SELECT * FROM system_schema.keyspaces WHERE keyspace_name = 'app'").await;
+        let Some(CassandraValue::Map(replication)) = keyspaces[0].get_column("replication") else { panic!("expected replication map") };
+        assert_eq!(replication.get("class"), Some(&text("org.apache.cassandra.locator.SimpleStrategy")));
+        assert_eq!(replication.get("replication_factor"), Some(&text("1")));
+        
+        let tables = select("SELECT * FROM system_schema.tables WHERE keyspace_name = 'app'").await;
+        assert_eq!(tables.len(), 1);
+        let Some(CassandraValue::Map(compaction)) = tables[0].get_column("compaction") else { panic!("expected compaction map") };
+        assert_eq!(compaction.get("class"), Some(&text("org.apache.cassandra.db.compaction.LeveledCompactionStrategy")));
+        
+        let columns = select("SELECT * FROM system_schema.columns WHERE keyspace_name = 'app' AND table_name = 'events'").await;
+        let mut described: Vec<_> = columns.iter().map(|row| (
+            row.get_column("column_name").cloned(),
+            row.get_column("kind").cloned(),
+            row.get_column("position").cloned(),
+            row.get_column("type").cloned(),
+        )).collect();
+        described.sort_by_key(|(name, ..)| name.clone());
+        assert_eq!(described, vec![
+            (Some(text("body")), Some(text("regular")), Some(CassandraValue::Int(-1)), Some(text("text"))),
+            (Some(text("tenant")), Some(text("partition_key")), Some(CassandraValue::Int(0)), Some(text("text"))),
+            (Some(text("ts")), Some(text("clustering")), Some(CassandraValue::Int(0)), Some(text("bigint"))),
+        ]);
+        
+        let indexes = select("SELECT * FROM system_schema.indexes").await;
+        assert_eq!(indexes.len(), 1);
+        assert_eq!(indexes[0].get_column("index_name"), Some(&text("events_body")));
+        // 가상 테이블은 system_schema.tables에 나오지 않음
+        let all_tables = select("SELECT * FROM system_schema.tables").await;
+        assert!(all_tables.iter().all(|row| row.get_column("keyspace_name") != Some(&text("system"))));
+        for query in ["SELECT * FROM system_schema.types", "SELECT * FROM system_schema.functions", "SELECT * FROM system_schema.aggregates",
+                      "SELECT * FROM system_schema.views", "SELECT * FROM system_schema.triggers"] {
+            assert!(select(query).await.is_empty(), "{}", query);
+        }
+        assert!(matches!(db.execute_cql("INSERT INTO system_schema.tables (keyspace_name, table_name) VALUES ('app', 'x')").await,
+                         Err(CoreDBError::ReadOnlyTable { .. })));
+    }
+    
+    #[tokio::test]
+    async fn test_keyspace_quotas_reject_writes_over_limit() {
+        let config = DatabaseConfig {
+            keyspace_quotas: HashMap::from([("tenant".to_string(), KeyspaceQuota { max_writes_per_sec: 3, ..Default::default() })]),
+            ..DatabaseConfig::ephemeral()
+        };
         let db = CoreDB::new(config).await.unwrap();
+        for keyspace in ["tenant", "other"] {
+            db.execute_cql(&format!("CREATE KEYSPACE {} WITH REPLICATION = {{'class': 'SimpleStrategy', 'replication_factor': 1}}", keyspace)).await.unwrap();
+            db.execute_cql(&format!("CREATE TABLE {}.items (id INT PRIMARY KEY, name TEXT)", keyspace)).await.unwrap();
+        }
+        let over_quota = |result: Result<QueryResult>, expected: &str| {
+            matches!(result, Err(CoreDBError::QuotaExceeded { ref keyspace, ref quota, .. }) if keyspace == "tenant" && quota == expected)
+        };
         
-        db.create_keyspace("test_ks".to_string(), 1).await.unwrap();
+        // 초당 쓰기 한도를 넘은 쓰기는 거부하고, 다른 키스페이스와 읽기는 그대로
+        for id in 0..3 {
+            db.execute_cql(&format!("INSERT INTO tenant.items (id, name) VALUES ({}, 'a')", id)).await.unwrap();
+        }
+        assert!(over_quota(db.execute_cql("INSERT INTO tenant.items (id, name) VALUES (3, 'a')").await, "max_writes_per_sec"));
+        for id in 0..10 {
+            db.execute_cql(&format!("INSERT INTO other.items (id, name) VALUES ({}, 'a')", id)).await.unwrap();
+        }
+        let QueryResult::Rows(rows) = db.execute_cql("SELECT * FROM tenant.items WHERE id = 1").await.unwrap() else { panic!("expected rows") };
+        assert_eq!(rows.len(), 1);
+        
+        // 데이터 크기 한도는 이미 넘은 뒤의 쓰기를 거부
+        db.set_keyspace_quota("tenant", KeyspaceQuota { max_disk_mb: 1, ..Default::default() });
+        let large = "x".repeat(1024 * 1024);
+        db.execute_cql(&format!("INSERT INTO tenant.items (id, name) VALUES (4, '{}')", large)).await.unwrap();
+        assert!(db.keyspace_size_bytes("tenant").await >= 1024 * 1024);
+        assert!(over_quota(db.execute_cql("INSERT INTO tenant.items (id, name) VALUES (5, 'a')").await, "max_disk_mb"));
+        db.execute_cql("INSERT INTO other.items (id, name) VALUES (10, 'a')").await.unwrap();
         
+        let stats = db.get_stats().await.quotas;
+        assert_eq!(stats["tenant"].rejected_writes, 1);
+        assert!(!stats.contains_key("other"));
+        db.set_keyspace_quota("tenant", KeyspaceQuota::default());
+        db.execute_cql("INSERT INTO tenant.items (id, name) VALUES (5, 'a')").await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_startup_integrity_check_refuses_or_repairs() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_startup_fsck");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config.clone()).await.unwrap();
+        db.create_keyspace("fsck_ks".to_string(), 1).await.unwrap();
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
         let schema = TableSchema::new(
-            "test_table".to_string(),
-            "test_ks".to_string(),
-            vec![ColumnDefinition {
-                name: "id".to_string(),
-                data_type: CassandraDataType::Int,
-                is_static: false,
-            }],
+            "items".to_string(),
+            "fsck_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
             vec![],
-            vec![ColumnDefinition {
-                name: "name".to_string(),
-                data_type: CassandraDataType::Text,
-                is_static: false,
-            }],
+            vec![column("name", CassandraDataType::Text)],
             vec![],
         );
+        db.create_table("fsck_ks".to_string(), "items".to_string(), schema).await.unwrap();
+        let mut cells = Cells::new();
+        cells.insert("name", Cell { value: CassandraValue::Text("a".to_string()), timestamp: 1, ttl: None, is_deleted: false });
+        db.insert_row("fsck_ks", "items", Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(1)] }, clustering_key: None, cells, timestamp: 1 }).await.unwrap();
+        db.flush_memtable("fsck_ks", "items").await.unwrap();
+        db.wait_for_flushes().await.unwrap();
+        drop(db);
         
-        db.create_table("test_ks".to_string(), "test_table".to_string(), schema).await.unwrap();
+        // 중단된 플러시가 남긴 파일
+        let orphan = test_dir.join("data").join("fsck_ks").join("items").join("crashed-Data.db");
+        std::fs::write(&orphan, b"partial").unwrap();
         
-        let stats = db.get_stats().await;
-        assert!(stats.table_count >= 1);
+        let check = DatabaseConfig { integrity_check: IntegrityCheckMode::Check, ..config.clone() };
+        match CoreDB::new(check).await {
+            Err(CoreDBError::IntegrityCheckFailed { issues, report }) => {
+                assert_eq!(issues, 1, "{}", report);
+                assert!(report.contains("1 SSTables"));
+                assert!(report.contains("crashed-Data.db"));
+            },
+            other => panic!("expected IntegrityCheckFailed, got {:?}", other.err()),
+        }
+        assert!(orphan.exists());
+        
+        let repair = DatabaseConfig { integrity_check: IntegrityCheckMode::Repair, ..config };
+        CoreDB::new(repair).await.unwrap();
+        assert!(!orphan.exists());
+        
+        std::fs::remove_dir_all(&test_dir).ok();
     }
     
     #[tokio::test]
-    async fn test_cql_execution() {
-        let config = DatabaseConfig::default();
+    async fn test_drain_flushes_everything_and_rejects_writes() {
+        use crate::schema::{CassandraValue, Cell, Cells, PartitionKey, Row};
+        
+        let test_dir = std::env::temp_dir().join("coredb_test_drain");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        db.create_keyspace("drain_ks".to_string(), 1).await.unwrap();
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let schema = TableSchema::new(
+            "items".to_string(),
+            "drain_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![],
+            vec![column("name", CassandraDataType::Text)],
+            vec![],
+        );
+        db.create_table("drain_ks".to_string(), "items".to_string(), schema).await.unwrap();
+        let row = |id: i32| {
+            let mut cells = Cells::new();
+            cells.insert("name", Cell { value: CassandraValue::Text("a".to_string()), timestamp: 1, ttl: None, is_deleted: false });
+            Row { partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] }, clustering_key: None, cells, timestamp: 1 }
+        };
+        for id in 0..10 {
+            db.insert_row("drain_ks", "items", row(id)).await.unwrap();
+        }
+        db.execute_cql("CREATE KEYSPACE cql_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql("CREATE TABLE cql_ks.items (id INT PRIMARY KEY, name TEXT)").await.unwrap();
+        db.execute_cql("INSERT INTO cql_ks.items (id, name) VALUES (1, 'a')").await.unwrap();
+        
+        // 스키마 버전 기록도 플러시됨
+        let stats = db.drain().await.unwrap();
+        assert_eq!(stats.flushed_memtables, 3);
+        assert!(db.get_stats().await.draining);
+        
+        // 재생할 커밋 로그가 남지 않음
+        let commit_log = db.commit_log.clone().unwrap();
+        let replay_position = {
+            let keyspaces = db.keyspaces.read().await;
+            let tables = keyspaces["drain_ks"].tables.read().await;
+            let table = &tables["items"];
+            assert_eq!(table.current_memtable.partition_count(), 0);
+            assert!(table.memtables.is_empty());
+            table.manifest.as_ref().unwrap().replay_position
+        };
+        assert_eq!(replay_position, commit_log.position());
+        let engine_replay_position = db.flusher.engine_tables.manifests.lock().await[&("cql_ks".to_string(), "items".to_string())].replay_position;
+        assert_eq!(engine_replay_position, commit_log.position());
+        assert_eq!(db.query_engine.read().await.get_memtable("cql_ks", "items").unwrap().partition_count(), 0);
+        assert!(commit_log.replay_since(replay_position).await.unwrap().is_empty());
+        assert_eq!(commit_log.oldest_segment_id(), commit_log.current_segment_id());
+        
+        // 쓰기는 거부하고 읽기는 계속 받음
+        assert!(matches!(db.insert_row("drain_ks", "items", row(10)).await, Err(CoreDBError::Draining)));
+        assert!(matches!(db.execute_cql("CREATE KEYSPACE late WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await,
+                         Err(CoreDBError::Draining)));
+        let key = PartitionKey { components: vec![CassandraValue::Int(3)] };
+        assert!(db.get_row("drain_ks", "items", &key, &None).await.unwrap().is_some());
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_roles_are_created_hashed_and_persisted() {
+        let test_dir = std::env::temp_dir().join("coredb_test_roles");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config.clone()).await.unwrap();
+        
+        // 기본 슈퍼유저
+        assert!(db.authenticate(DEFAULT_SUPERUSER, DEFAULT_SUPERUSER).await.unwrap().is_superuser);
+        
+        db.execute_cql("CREATE ROLE analyst WITH PASSWORD = 'it''s secret' AND LOGIN = true").await.unwrap();
+        let role = db.get_role("analyst").await.unwrap().unwrap();
+        assert!(role.can_login && !role.is_superuser);
+        assert!(!role.salted_hash.as_deref().unwrap().contains("secret"));
+        assert_eq!(db.authenticate("analyst", "it's secret").await.unwrap().name, "analyst");
+        assert!(matches!(db.authenticate("analyst", "wrong").await, Err(CoreDBError::AuthenticationFailed { .. })));
+        assert!(matches!(db.authenticate("nobody", "wrong").await, Err(CoreDBError::AuthenticationFailed { .. })));
+        assert!(db.execute_cql("CREATE ROLE analyst").await.is_err());
+        db.execute_cql("CREATE ROLE IF NOT EXISTS analyst").await.unwrap();
+        
+        // 로그인 권한이 없는 역할
+        db.execute_cql("CREATE ROLE readers WITH PASSWORD = 'x'").await.unwrap();
+        assert!(matches!(db.authenticate("readers", "x").await, Err(CoreDBError::AuthenticationFailed { .. })));
+        
+        db.execute_cql("ALTER USER cassandra WITH PASSWORD 'changed'").await.unwrap();
+        db.execute_cql("DROP ROLE readers").await.unwrap();
+        assert!(db.get_role("readers").await.unwrap().is_none());
+        assert!(db.execute_cql("DROP ROLE readers").await.is_err());
+        db.execute_cql("DROP ROLE IF EXISTS readers").await.unwrap();
+        db.shutdown().await.unwrap();
+        drop(db);
+        
+        // 재시작해도 남고, 바뀐 기본 슈퍼유저 비밀번호는 다시 만들지 않음
         let db = CoreDB::new(config).await.unwrap();
+        assert!(db.authenticate("analyst", "it's secret").await.is_ok());
+        assert!(db.authenticate(DEFAULT_SUPERUSER, DEFAULT_SUPERUSER).await.is_err());
+        assert!(db.authenticate(DEFAULT_SUPERUSER, "changed").await.is_ok());
+        assert!(db.get_role("readers").await.unwrap().is_none());
         
-        let result = db.execute_cql("CREATE KEYSPACE test_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
-        assert!(result.is_success());
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[tokio::test]
+    async fn test_permissions_checked_for_session_role() {
+        let test_dir = std::env::temp_dir().join("coredb_test_permissions");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let config = DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            ..Default::default()
+        };
+        let db = CoreDB::new(config).await.unwrap();
+        let superuser = db.authenticate(DEFAULT_SUPERUSER, DEFAULT_SUPERUSER).await.unwrap();
+        db.execute_cql_as(&superuser, "CREATE KEYSPACE prod WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql_as(&superuser, "CREATE TABLE prod.orders (id INT PRIMARY KEY, total INT)").await.unwrap();
+        db.execute_cql_as(&superuser, "CREATE ROLE reporting WITH PASSWORD = 'pw' AND LOGIN = true").await.unwrap();
+        let reporting = db.authenticate("reporting", "pw").await.unwrap();
+        let unauthorized = |result: Result<QueryResult>| matches!(result, Err(CoreDBError::Unauthorized { .. }));
         
-        let result = db.execute_cql("CREATE TABLE test_ks.test_table (id INT PRIMARY KEY, name TEXT)").await.unwrap();
-        assert!(result.is_success());
+        assert!(unauthorized(db.execute_cql_as(&reporting, "SELECT * FROM prod.orders").await));
+        assert!(unauthorized(db.execute_cql_as(&reporting, "DROP TABLE prod.orders").await));
+        // 드라이버가 연결할 때 읽는 메타데이터는 권한 없이 읽음 (스키마 기록은 아님)
+        assert!(db.execute_cql_as(&reporting, "SELECT * FROM system.peers").await.is_ok());
+        assert!(db.execute_cql_as(&reporting, "SELECT * FROM system_schema.columns").await.is_ok());
+        assert!(unauthorized(db.execute_cql_as(&reporting, "SELECT * FROM system_schema.schema_versions").await));
         
-        let result = db.execute_cql("INSERT INTO test_ks.test_table (id, name) VALUES (1, 'John')").await.unwrap();
-        assert!(result.is_success());
+        // 키스페이스 권한은 그 안의 테이블에도 적용
+        db.execute_cql_as(&superuser, "GRANT SELECT ON KEYSPACE prod TO reporting").await.unwrap();
+        assert!(db.execute_cql_as(&reporting, "SELECT * FROM prod.orders").await.is_ok());
+        assert!(unauthorized(db.execute_cql_as(&reporting, "INSERT INTO prod.orders (id, total) VALUES (1, 10)").await));
+        assert!(unauthorized(db.execute_cql_as(&reporting, "DROP TABLE prod.orders").await));
+        assert!(unauthorized(db.execute_cql_as(&reporting, "DROP KEYSPACE prod").await));
+        db.execute_cql_as(&superuser, "GRANT MODIFY ON prod.orders TO reporting").await.unwrap();
+        db.execute_cql_as(&reporting, "INSERT INTO prod.orders (id, total) VALUES (1, 10)").await.unwrap();
+        db.execute_cql_as(&superuser, "REVOKE SELECT ON KEYSPACE prod FROM reporting").await.unwrap();
+        assert!(unauthorized(db.execute_cql_as(&reporting, "SELECT * FROM prod.orders").await));
         
-        let result = db.execute_cql("SELECT * FROM test_ks.test_table WHERE id = 1").await.unwrap();
-        assert!(result.is_success());
+        // 역할과 권한 관리는 슈퍼유저만, 자기 비밀번호는 바꿀 수 있음
+        assert!(unauthorized(db.execute_cql_as(&reporting, "GRANT DROP ON KEYSPACE prod TO reporting").await));
+        assert!(unauthorized(db.execute_cql_as(&reporting, "ALTER ROLE reporting WITH SUPERUSER = true").await));
+        assert!(unauthorized(db.execute_cql_as(&reporting, "CREATE ROLE other").await));
+        db.execute_cql_as(&reporting, "ALTER ROLE reporting WITH PASSWORD = 'new'").await.unwrap();
+        assert!(db.execute_cql_as(&superuser, "GRANT SELECT ON KEYSPACE prod TO nobody").await.is_err());
+        
+        // 새로 만든 키스페이스는 만든 역할이 모든 권한을 가짐 (이미 있는 키스페이스는 아님)
+        db.execute_cql_as(&superuser, "GRANT CREATE ON ALL KEYSPACES TO reporting").await.unwrap();
+        db.execute_cql_as(&reporting, "CREATE KEYSPACE scratch WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql_as(&reporting, "CREATE TABLE scratch.notes (id INT PRIMARY KEY, body TEXT)").await.unwrap();
+        db.execute_cql_as(&reporting, "DROP TABLE scratch.notes").await.unwrap();
+        db.execute_cql_as(&reporting, "CREATE KEYSPACE prod WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        assert!(unauthorized(db.execute_cql_as(&reporting, "DROP KEYSPACE prod").await));
+        
+        // 지웠다가 같은 이름으로 만든 역할은 이전 권한을 받지 않음
+        db.execute_cql_as(&superuser, "DROP ROLE reporting").await.unwrap();
+        db.execute_cql_as(&superuser, "CREATE ROLE reporting WITH PASSWORD = 'pw' AND LOGIN = true").await.unwrap();
+        let recreated = db.authenticate("reporting", "pw").await.unwrap();
+        assert!(unauthorized(db.execute_cql_as(&recreated, "INSERT INTO prod.orders (id, total) VALUES (2, 20)").await));
+        
+        // 역할 없이 실행하면 (내장 사용) 확인하지 않음
+        db.execute_cql("DROP TABLE prod.orders").await.unwrap();
+        
+        std::fs::remove_dir_all(&test_dir).ok();
     }
 }
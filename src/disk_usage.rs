@@ -0,0 +1,241 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use crate::error::{CoreDBError, Result};
+
+/// 파일 시스템 크기와 일반 사용자가 쓸 수 있는 여유 공간 (바이트)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DiskSpace {
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+impl DiskSpace {
+    /// 사용률 (%, root 예약 공간은 사용 중으로 봄)
+    pub fn used_percent(&self) -> f64 {
+        if self.total_bytes == 0 {
+            return 0.0;
+        }
+        self.total_bytes.saturating_sub(self.available_bytes) as f64 * 100.0 / self.total_bytes as f64
+    }
+}
+
+/// path가 있는 파일 시스템의 크기와 여유 공간
+#[cfg(target_os = "linux")]
+pub fn disk_space(path: &Path) -> std::io::Result<Option<DiskSpace>> {
+    use std::os::unix::ffi::OsStrExt;
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    // SAFETY: path는 NUL로 끝나는 문자열이고 stat은 statvfs가 채우는 버퍼
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    let fragment_size = stat.f_frsize as u64;
+    Ok(Some(DiskSpace {
+        total_bytes: stat.f_blocks as u64 * fragment_size,
+        available_bytes: stat.f_bavail as u64 * fragment_size,
+    }))
+}
+
+/// 리눅스 외에는 여유 공간을 확인하지 않음 (감시하지 않음)
+#[cfg(not(target_os = "linux"))]
+pub fn disk_space(_path: &Path) -> std::io::Result<Option<DiskSpace>> {
+    Ok(None)
+}
+
+/// 디스크 사용 수위
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiskUsageLevel {
+    #[default]
+    Normal,
+    /// 경고 수위를 넘음 (쓰기는 계속 받음)
+    Warning,
+    /// 한계 수위를 넘음 (쓰기를 거부)
+    Full,
+}
+
+impl DiskUsageLevel {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Warning,
+            2 => Self::Full,
+            _ => Self::Normal,
+        }
+    }
+}
+
+/// 디렉토리 하나의 마지막 확인 결과
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectoryUsage {
+    pub directory: PathBuf,
+    pub space: DiskSpace,
+}
+
+/// 디스크 사용률 통계
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiskUsageStats {
+    pub level: DiskUsageLevel,
+    pub warn_percent: u64,
+    pub fail_percent: u64,
+    pub directories: Vec<DirectoryUsage>,
+    /// 한계 수위 때문에 거부한 쓰기 수
+    pub rejected_writes: u64,
+}
+
+/// 데이터와 커밋 로그 디렉토리의 디스크 사용률 감시
+///
+/// 가장 찬 디렉토리 기준으로 경고 수위를 넘으면 경고 로그를 남기고, 한계 수위를 넘으면 쓰기를 거부함
+/// (읽기와 플러시는 계속됨). 주기적으로 다시 확인하므로 공간이 확보되면 쓰기가 자동으로 재개됨
+#[derive(Debug)]
+pub struct DiskMonitor {
+    directories: Vec<PathBuf>,
+    /// 경고 수위 (사용률 %, 0이면 끔)
+    warn_percent: u64,
+    /// 쓰기 거부 수위 (사용률 %, 0이면 끔)
+    fail_percent: u64,
+    level: AtomicU8,
+    usage: Mutex<Vec<DirectoryUsage>>,
+    rejected_writes: AtomicU64,
+}
+
+impl DiskMonitor {
+    pub fn new(directories: Vec<PathBuf>, warn_percent: u64, fail_percent: u64) -> Self {
+        Self {
+            directories,
+            warn_percent,
+            fail_percent,
+            level: AtomicU8::new(DiskUsageLevel::Normal as u8),
+            usage: Mutex::new(Vec::new()),
+            rejected_writes: AtomicU64::new(0),
+        }
+    }
+
+    /// 모든 디렉토리의 사용률을 다시 확인 (확인할 수 없는 디렉토리는 건너뜀)
+    pub async fn check(&self) -> DiskUsageLevel {
+        let directories = self.directories.clone();
+        let usage = tokio::task::spawn_blocking(move || {
+            directories.into_iter()
+                .filter_map(|directory| match disk_space(&directory) {
+                    Ok(space) => space.map(|space| DirectoryUsage { directory, space }),
+                    Err(e) => {
+                        tracing::warn!("Failed to check free space of {}: {}", directory.display(), e);
+                        None
+                    },
+                })
+                .collect()
+        }).await.unwrap_or_default();
+        self.update(usage)
+    }
+
+    /// 확인 결과 반영 (수위가 바뀔 때만 로그)
+    pub(crate) fn update(&self, usage: Vec<DirectoryUsage>) -> DiskUsageLevel {
+        let fullest = Self::fullest(&usage).map(|fullest| (fullest.directory.display().to_string(), fullest.space.used_percent()));
+        let over = |limit: u64| limit > 0 && fullest.as_ref().is_some_and(|(_, used)| *used >= limit as f64);
+        let level = if over(self.fail_percent) {
+            DiskUsageLevel::Full
+        } else if over(self.warn_percent) {
+            DiskUsageLevel::Warning
+        } else {
+            DiskUsageLevel::Normal
+        };
+        *self.usage.lock().unwrap() = usage;
+
+        let previous = DiskUsageLevel::from_u8(self.level.swap(level as u8, Ordering::AcqRel));
+        if previous != level {
+            let (directory, used) = fullest.unwrap_or_default();
+            match level {
+                DiskUsageLevel::Full => tracing::error!("Disk usage of {} is {:.1}%, above the {}% limit; rejecting writes until space is freed",
+                                                        directory, used, self.fail_percent),
+                DiskUsageLevel::Warning => tracing::warn!("Disk usage of {} is {:.1}%, above the {}% warning threshold",
+                                                          directory, used, self.warn_percent),
+                DiskUsageLevel::Normal => tracing::info!("Disk usage is back to {:.1}%", used),
+            }
+        }
+        level
+    }
+
+    fn fullest(usage: &[DirectoryUsage]) -> Option<&DirectoryUsage> {
+        usage.iter().max_by(|a, b| a.space.used_percent().total_cmp(&b.space.used_percent()))
+    }
+
+    pub fn level(&self) -> DiskUsageLevel {
+        DiskUsageLevel::from_u8(self.level.load(Ordering::Acquire))
+    }
+
+    /// 한계 수위를 넘었으면 쓰기 거부
+    pub fn check_writes(&self) -> Result<()> {
+        if self.level() != DiskUsageLevel::Full {
+            return Ok(());
+        }
+        self.rejected_writes.fetch_add(1, Ordering::Relaxed);
+        let usage = self.usage.lock().unwrap();
+        let fullest = Self::fullest(&usage);
+        Err(CoreDBError::InsufficientDiskSpace {
+            directory: fullest.map(|fullest| fullest.directory.display().to_string()).unwrap_or_default(),
+            used_percent: fullest.map_or(0, |fullest| fullest.space.used_percent() as u64),
+            limit: self.fail_percent,
+        })
+    }
+
+    pub fn stats(&self) -> DiskUsageStats {
+        DiskUsageStats {
+            level: self.level(),
+            warn_percent: self.warn_percent,
+            fail_percent: self.fail_percent,
+            directories: self.usage.lock().unwrap().clone(),
+            rejected_writes: self.rejected_writes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(directory: &str, used_percent: u64) -> DirectoryUsage {
+        DirectoryUsage {
+            directory: PathBuf::from(directory),
+            space: DiskSpace { total_bytes: 100, available_bytes: 100 - used_percent },
+        }
+    }
+
+    #[test]
+    fn test_disk_monitor_watermarks() {
+        let monitor = DiskMonitor::new(vec![PathBuf::from("data"), PathBuf::from("commitlog")], 80, 90);
+        assert_eq!(monitor.update(vec![usage("data", 50), usage("commitlog", 10)]), DiskUsageLevel::Normal);
+        assert!(monitor.check_writes().is_ok());
+
+        // 가장 찬 디렉토리 기준
+        assert_eq!(monitor.update(vec![usage("data", 50), usage("commitlog", 85)]), DiskUsageLevel::Warning);
+        assert!(monitor.check_writes().is_ok());
+
+        assert_eq!(monitor.update(vec![usage("data", 95), usage("commitlog", 85)]), DiskUsageLevel::Full);
+        match monitor.check_writes() {
+            Err(CoreDBError::InsufficientDiskSpace { directory, used_percent, limit }) => {
+                assert_eq!((directory.as_str(), used_percent, limit), ("data", 95, 90));
+            },
+            other => panic!("expected InsufficientDiskSpace, got {:?}", other),
+        }
+        assert_eq!(monitor.stats().rejected_writes, 1);
+
+        // 공간이 확보되면 다시 받음
+        assert_eq!(monitor.update(vec![usage("data", 40), usage("commitlog", 10)]), DiskUsageLevel::Normal);
+        assert!(monitor.check_writes().is_ok());
+
+        // 수위가 0이면 끔
+        let disabled = DiskMonitor::new(vec![PathBuf::from("data")], 0, 0);
+        assert_eq!(disabled.update(vec![usage("data", 100)]), DiskUsageLevel::Normal);
+    }
+
+    #[tokio::test]
+    async fn test_disk_monitor_reads_real_file_system() {
+        let monitor = DiskMonitor::new(vec![std::env::temp_dir()], 0, 0);
+        monitor.check().await;
+        let stats = monitor.stats();
+        if cfg!(target_os = "linux") {
+            assert_eq!(stats.directories.len(), 1);
+            assert!(stats.directories[0].space.total_bytes > 0);
+        }
+    }
+}
@@ -53,9 +53,60 @@ pub enum CoreDBError {
     #[error("Compaction error: {message}")]
     CompactionError { message: String },
     
+    #[error("Write rejected by trigger {trigger}: {message}")]
+    TriggerRejected { trigger: String, message: String },
+    
+    #[error("Overloaded: {operation} queue has {queued} pending (limit {limit})")]
+    Overloaded { operation: String, queued: usize, limit: usize },
+    
+    #[error("Authentication failed: {message}")]
+    AuthenticationFailed { message: String },
+    
+    #[error("Unauthorized: {message}")]
+    Unauthorized { message: String },
+    
+    #[error("TLS error: {message}")]
+    Tls { message: String },
+    
+    #[error("Telemetry error: {message}")]
+    Telemetry { message: String },
+    
+    #[error("Quota exceeded for keyspace {keyspace}: {quota} is {limit}")]
+    QuotaExceeded { keyspace: String, quota: String, limit: u64 },
+    
+    #[error("Migration {name} failed: {message}")]
+    MigrationFailed { name: String, message: String },
+    
+    #[error("Table {keyspace}.{table} is a read-only virtual table")]
+    ReadOnlyTable { keyspace: String, table: String },
+    
+    #[error("Database is drained and no longer accepts writes")]
+    Draining,
+    
+    #[error("Insufficient disk space: {directory} is {used_percent}% full, writes are rejected above {limit}%")]
+    InsufficientDiskSpace { directory: String, used_percent: u64, limit: u64 },
+    
+    #[error("Startup integrity check found {issues} unresolved issues: {report}")]
+    IntegrityCheckFailed { issues: usize, report: String },
+    
+    #[error("Corruption in {file} at offset {offset}: {message}")]
+    Corruption { file: String, offset: u64, message: String },
+    
     #[error("Commit log error: {message}")]
     CommitLogError { message: String },
     
+    #[error("Snapshot error: {message}")]
+    Snapshot { message: String },
+    
+    #[error("Backup error: {message}")]
+    Backup { message: String },
+    
+    #[error("Archive error: {message}")]
+    Archive { message: String },
+    
+    #[error("Object store error: {0}")]
+    ObjectStore(#[from] object_store::Error),
+    
     #[error("Generic error: {message}")]
     Generic { message: String },
 }
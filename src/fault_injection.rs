@@ -0,0 +1,477 @@
+use std::io;
+use std::path::Path;
+
+/// 장애를 넣을 수 있는 쓰기 경로 지점
+///
+/// fault-injection 기능을 켠 빌드에서만 FaultInjector로 장애를 넣을 수 있고, 아니면 훅은 아무것도 하지 않음
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FaultPoint {
+    /// 커밋 로그 엔트리를 세그먼트에 쓰기 직전
+    CommitLogAppend,
+    /// 커밋 로그 세그먼트 fsync 직전 (엔트리는 파일에 쓰였지만 디스크에 남았는지는 모름)
+    CommitLogSync,
+    /// SSTable 구성 파일을 모두 쓰고 fsync하기 직전
+    SSTableSync,
+    /// 매니페스트 임시 파일을 fsync하기 직전 (rename 전이라 이전 매니페스트가 그대로)
+    ManifestCommit,
+    /// 플러시한 SSTable을 매니페스트에 기록하기 직전
+    Flush,
+    /// 컴팩션 결과를 매니페스트에 기록하기 직전
+    Compaction,
+    /// 컴팩션 결과를 매니페스트에 기록한 뒤 입력 SSTable을 지우기 직전
+    CompactionCleanup,
+}
+
+#[cfg(feature = "fault-injection")]
+pub use injector::*;
+
+/// 파일을 바꾸지 않는 지점 (크래시만 넣을 수 있음)
+#[inline]
+pub(crate) fn kill_point(point: FaultPoint, path: &Path) -> io::Result<()> {
+    #[cfg(feature = "fault-injection")]
+    return injector::with_state(path, |state| state.hit(point));
+    #[cfg(not(feature = "fault-injection"))]
+    {
+        let _ = (point, path);
+        Ok(())
+    }
+}
+
+/// path의 end까지 쓰기 직전 (fsync 전까지는 크래시 때 잃을 수 있음)
+#[inline]
+pub(crate) fn append(point: FaultPoint, path: &Path, end: u64) -> io::Result<()> {
+    #[cfg(feature = "fault-injection")]
+    return injector::with_state(path, |state| {
+        state.hit(point)?;
+        state.wrote(path, end);
+        Ok(())
+    });
+    #[cfg(not(feature = "fault-injection"))]
+    {
+        let _ = (point, path, end);
+        Ok(())
+    }
+}
+
+/// path를 end까지 fsync하기 직전
+#[inline]
+pub(crate) fn sync(point: FaultPoint, path: &Path, end: u64) -> io::Result<()> {
+    #[cfg(feature = "fault-injection")]
+    return injector::with_state(path, |state| {
+        state.wrote(path, end);
+        state.hit(point)?;
+        state.synced(point, path, end);
+        Ok(())
+    });
+    #[cfg(not(feature = "fault-injection"))]
+    {
+        let _ = (point, path, end);
+        Ok(())
+    }
+}
+
+/// 다 쓴 파일을 fsync하기 직전 (크기는 지금 파일 크기)
+#[inline]
+pub(crate) fn sync_file(point: FaultPoint, path: &Path) -> io::Result<()> {
+    #[cfg(feature = "fault-injection")]
+    return injector::with_state(path, |state| state.sync_files(point, [path.to_path_buf()]));
+    #[cfg(not(feature = "fault-injection"))]
+    {
+        let _ = (point, path);
+        Ok(())
+    }
+}
+
+/// 다 쓴 SSTable의 구성 파일을 fsync하기 직전
+#[inline]
+pub(crate) fn sync_sstable(point: FaultPoint, directory: &Path, id: &str) -> io::Result<()> {
+    #[cfg(feature = "fault-injection")]
+    return injector::with_state(directory, |state| {
+        let paths = crate::storage::COMPONENT_SUFFIXES.iter()
+            .map(|suffix| crate::storage::SSTable::component_path(directory, id, suffix));
+        state.sync_files(point, paths)
+    });
+    #[cfg(not(feature = "fault-injection"))]
+    {
+        let _ = (point, directory, id);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "fault-injection")]
+mod injector {
+    use std::collections::{BTreeMap, HashMap, HashSet};
+    use std::io::{self, Seek, SeekFrom, Write};
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
+    use super::FaultPoint;
+    use crate::database::CoreDB;
+    use crate::error::{CoreDBError, Result};
+    use crate::schema::PartitionKey;
+    use crate::storage::check_data_directory;
+
+    /// 설치된 주입기 (루트 디렉토리 아래 경로에만 적용되어 병렬로 도는 다른 테스트에는 영향이 없음)
+    static INJECTORS: Mutex<Vec<(PathBuf, Arc<Mutex<State>>)>> = Mutex::new(Vec::new());
+
+    /// path에 적용되는 주입기 상태로 f 실행 (없으면 Ok)
+    pub(super) fn with_state(path: &Path, f: impl FnOnce(&mut State) -> io::Result<()>) -> io::Result<()> {
+        let state = {
+            let injectors = INJECTORS.lock().unwrap_or_else(|e| e.into_inner());
+            injectors.iter()
+                .filter(|(root, _)| path.starts_with(root))
+                .max_by_key(|(root, _)| root.as_os_str().len())
+                .map(|(_, state)| state.clone())
+        };
+        match state {
+            Some(state) => f(&mut state.lock().unwrap_or_else(|e| e.into_inner())),
+            None => Ok(()),
+        }
+    }
+
+    /// 파일마다 쓴 끝과 fsync된 끝
+    #[derive(Debug, Default)]
+    struct FileState {
+        written: u64,
+        durable: u64,
+    }
+
+    #[derive(Debug, Default)]
+    pub(super) struct State {
+        /// 지점마다 크래시까지 남은 통과 횟수
+        crash_points: HashMap<FaultPoint, u64>,
+        dropped_syncs: HashSet<FaultPoint>,
+        hits: HashMap<FaultPoint, u64>,
+        crashed: Option<FaultPoint>,
+        files: BTreeMap<PathBuf, FileState>,
+    }
+
+    impl State {
+        /// 지점 통과 (크래시할 차례거나 이미 크래시했으면 실패)
+        pub(super) fn hit(&mut self, point: FaultPoint) -> io::Result<()> {
+            if let Some(crashed) = self.crashed {
+                return Err(crash_error(crashed));
+            }
+            *self.hits.entry(point).or_default() += 1;
+            if let Some(remaining) = self.crash_points.get_mut(&point) {
+                *remaining -= 1;
+                if *remaining == 0 {
+                    self.crash_points.remove(&point);
+                    self.crashed = Some(point);
+                    tracing::warn!("Injected crash at {:?}", point);
+                    return Err(crash_error(point));
+                }
+            }
+            Ok(())
+        }
+
+        pub(super) fn wrote(&mut self, path: &Path, end: u64) {
+            let file = self.files.entry(path.to_path_buf()).or_default();
+            file.written = file.written.max(end);
+        }
+
+        pub(super) fn synced(&mut self, point: FaultPoint, path: &Path, end: u64) {
+            if self.dropped_syncs.contains(&point) {
+                return;
+            }
+            let file = self.files.entry(path.to_path_buf()).or_default();
+            file.durable = file.durable.max(end);
+        }
+
+        /// 파일을 지금 크기까지 쓴 것으로 기록한 뒤 지점을 통과하면 fsync된 것으로 기록
+        pub(super) fn sync_files(&mut self, point: FaultPoint, paths: impl IntoIterator<Item = PathBuf>) -> io::Result<()> {
+            let mut sizes = Vec::new();
+            for path in paths {
+                match std::fs::metadata(&path) {
+                    Ok(metadata) => sizes.push((path, metadata.len())),
+                    // 이전 형식 SSTable에는 없는 구성 파일이 있음
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {},
+                    Err(e) => return Err(e),
+                }
+            }
+            for (path, size) in &sizes {
+                self.wrote(path, *size);
+            }
+            self.hit(point)?;
+            for (path, size) in &sizes {
+                self.synced(point, path, *size);
+            }
+            Ok(())
+        }
+    }
+
+    fn crash_error(point: FaultPoint) -> io::Error {
+        io::Error::other(format!("injected crash at {:?}", point))
+    }
+
+    /// 크래시 뒤 fsync되지 않아 잘린 파일
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct TornWrite {
+        pub path: PathBuf,
+        /// 남은 바이트 (이 앞은 그대로)
+        pub kept: u64,
+        /// 잃은 바이트
+        pub lost: u64,
+    }
+
+    /// 크래시 복구 테스트용 장애 주입기 (fault-injection 기능)
+    ///
+    /// root 아래의 커밋 로그, SSTable, 매니페스트 쓰기에 프로세스가 죽는 지점과 fsync 무시를 넣고,
+    /// crash로 전원이 나간 것처럼 fsync되지 않은 쓰기를 seed로 정해지는 위치에서 찢음. drop하면 해제됨
+    pub struct FaultInjector {
+        root: PathBuf,
+        seed: u64,
+        state: Arc<Mutex<State>>,
+    }
+
+    impl FaultInjector {
+        pub fn install(root: impl Into<PathBuf>, seed: u64) -> Self {
+            let root = root.into();
+            let state = Arc::new(Mutex::new(State::default()));
+            INJECTORS.lock().unwrap_or_else(|e| e.into_inner()).push((root.clone(), state.clone()));
+            Self { root, seed, state }
+        }
+
+        fn state(&self) -> std::sync::MutexGuard<'_, State> {
+            self.state.lock().unwrap_or_else(|e| e.into_inner())
+        }
+
+        /// 지금부터 point를 nth번째(1부터) 지날 때 프로세스가 죽은 것처럼 실패하고, 그 뒤로 root 아래 쓰기는 모두 실패
+        pub fn crash_at(&self, point: FaultPoint, nth: u64) {
+            self.state().crash_points.insert(point, nth.max(1));
+        }
+
+        /// point의 fsync를 무시 (디스크가 fsync를 거짓으로 끝낸 것처럼 그 데이터는 크래시 때 잃을 수 있음)
+        pub fn drop_syncs(&self, point: FaultPoint) {
+            self.state().dropped_syncs.insert(point);
+        }
+
+        /// 주입한 크래시 지점 (아직 없으면 None)
+        pub fn crashed(&self) -> Option<FaultPoint> {
+            self.state().crashed
+        }
+
+        /// 설치하거나 마지막으로 crash한 뒤 point를 지난 횟수
+        pub fn hits(&self, point: FaultPoint) -> u64 {
+            self.state().hits.get(&point).copied().unwrap_or_default()
+        }
+
+        /// 전원이 나간 것처럼 fsync되지 않은 쓰기를 찢고 주입 상태를 처음으로 되돌림 (데이터베이스를 drop한 뒤 호출)
+        ///
+        /// 파일마다 fsync된 끝과 쓴 끝 사이의 한 위치까지만 남김. 미리 할당한 커밋 로그 세그먼트는 그 뒤를 0으로 덮고,
+        /// 나머지 파일은 그 위치로 자름. 지워지거나 이름이 바뀐 파일은 건너뜀
+        pub fn crash(&self) -> io::Result<Vec<TornWrite>> {
+            let files = {
+                let mut state = self.state();
+                let files = std::mem::take(&mut state.files);
+                *state = State::default();
+                files
+            };
+            let mut random = self.seed;
+            let mut torn = Vec::new();
+            for (path, file) in files {
+                if file.written <= file.durable {
+                    continue;
+                }
+                let length = match std::fs::metadata(&path) {
+                    Ok(metadata) => metadata.len(),
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                    Err(e) => return Err(e),
+                };
+                let kept = (file.durable + split_mix(&mut random) % (file.written - file.durable + 1)).min(length);
+                let mut handle = std::fs::OpenOptions::new().write(true).open(&path)?;
+                if length > file.written {
+                    handle.seek(SeekFrom::Start(kept))?;
+                    handle.write_all(&vec![0; (file.written - kept) as usize])?;
+                } else {
+                    handle.set_len(kept)?;
+                }
+                handle.sync_all()?;
+                torn.push(TornWrite { lost: file.written.min(length) - kept, path, kept });
+            }
+            tracing::info!("Simulated crash under {}: tore {} files", self.root.display(), torn.len());
+            Ok(torn)
+        }
+    }
+
+    impl Drop for FaultInjector {
+        fn drop(&mut self) {
+            INJECTORS.lock().unwrap_or_else(|e| e.into_inner()).retain(|(_, state)| !Arc::ptr_eq(state, &self.state));
+        }
+    }
+
+    fn split_mix(state: &mut u64) -> u64 {
+        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// 크래시 뒤 다시 연 데이터베이스 확인: 응답을 받은 쓰기는 모두 읽히고 데이터 디렉토리의 매니페스트와 SSTable이 온전해야 함
+    ///
+    /// 확인할 테이블은 미리 열어 둬야 함 (열 때 커밋 로그를 재생하고 고아 SSTable을 정리함)
+    pub async fn verify_recovery(db: &CoreDB, data_directory: &Path, keyspace: &str, table: &str, acknowledged: &[PartitionKey]) -> Result<()> {
+        let report = check_data_directory(data_directory, false).await?;
+        if !report.is_clean() {
+            return Err(CoreDBError::IntegrityCheckFailed { issues: report.issues.len(), report: report.to_string() });
+        }
+        let mut missing = Vec::new();
+        for partition_key in acknowledged {
+            if db.get_row(keyspace, table, partition_key, &None).await?.is_none() {
+                missing.push(format!("{:?}", partition_key.components));
+            }
+        }
+        if !missing.is_empty() {
+            return Err(CoreDBError::Generic {
+                message: format!("Lost {} acknowledged writes to {}.{}: {}", missing.len(), keyspace, table, missing.join(", ")),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "fault-injection"))]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use crate::database::{CoreDB, DatabaseConfig};
+    use crate::error::CoreDBError;
+    use crate::schema::{CassandraDataType, CassandraValue, Cell, Cells, ColumnDefinition, PartitionKey, Row, TableSchema};
+    use crate::wal::CommitLogSync;
+
+    fn config(test_dir: &Path) -> DatabaseConfig {
+        DatabaseConfig {
+            data_directory: test_dir.join("data"),
+            commitlog_directory: test_dir.join("commitlog"),
+            commitlog_sync: CommitLogSync::Always,
+            ..Default::default()
+        }
+    }
+
+    async fn open(test_dir: &Path) -> CoreDB {
+        let db = CoreDB::new(config(test_dir)).await.unwrap();
+        let schema = TableSchema::new("rows".to_string(), "app".to_string(),
+                                      vec![ColumnDefinition { name: "id".to_string(), data_type: CassandraDataType::Int, is_static: false }],
+                                      vec![], vec![ColumnDefinition { name: "name".to_string(), data_type: CassandraDataType::Text, is_static: false }], vec![]);
+        db.create_keyspace("app".to_string(), 1).await.unwrap();
+        // 다시 열 때 커밋 로그를 재생하고 고아 SSTable을 정리함
+        db.create_table("app".to_string(), "rows".to_string(), schema).await.unwrap();
+        db
+    }
+
+    fn key(id: i32) -> PartitionKey {
+        PartitionKey { components: vec![CassandraValue::Int(id)] }
+    }
+
+    fn row(id: i32) -> Row {
+        let mut cells = Cells::new();
+        cells.insert("name", Cell { value: CassandraValue::Text(format!("v{}", id)), timestamp: id as i64, ttl: None, is_deleted: false });
+        Row { partition_key: key(id), clustering_key: None, cells, timestamp: id as i64 }
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let test_dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&test_dir);
+        test_dir
+    }
+
+    #[tokio::test]
+    async fn test_commit_log_crashes_keep_acknowledged_writes() {
+        for (case, point) in [FaultPoint::CommitLogAppend, FaultPoint::CommitLogSync].into_iter().enumerate() {
+            let test_dir = test_dir(&format!("coredb_test_fault_commit_log_{}", case));
+            let injector = FaultInjector::install(&test_dir, 7);
+            let db = open(&test_dir).await;
+            for id in 1..=5 {
+                db.insert_row("app", "rows", row(id)).await.unwrap();
+            }
+            // 크래시한 쓰기와 그 뒤의 쓰기는 응답을 받지 못함
+            injector.crash_at(point, 1);
+            assert!(db.insert_row("app", "rows", row(6)).await.is_err());
+            assert!(db.insert_row("app", "rows", row(7)).await.is_err());
+            assert_eq!(injector.crashed(), Some(point));
+            drop(db);
+            let torn = injector.crash().unwrap();
+            // fsync 직전에 죽었으면 쓰인 엔트리가 찢어질 수 있음
+            assert_eq!(torn.is_empty(), point == FaultPoint::CommitLogAppend, "{:?}", point);
+
+            let db = open(&test_dir).await;
+            let acknowledged: Vec<_> = (1..=5).map(key).collect();
+            verify_recovery(&db, &test_dir.join("data"), "app", "rows", &acknowledged).await.unwrap();
+            assert!(db.get_row("app", "rows", &key(7), &None).await.unwrap().is_none());
+            db.shutdown().await.unwrap();
+            drop(injector);
+            std::fs::remove_dir_all(&test_dir).ok();
+        }
+
+        // fsync를 무시하면 응답한 쓰기도 잃지만 남는 쓰기는 언제나 앞에서부터 이어짐
+        let test_dir = test_dir("coredb_test_fault_dropped_syncs");
+        let injector = FaultInjector::install(&test_dir, 11);
+        let db = open(&test_dir).await;
+        injector.drop_syncs(FaultPoint::CommitLogSync);
+        for id in 1..=20 {
+            db.insert_row("app", "rows", row(id)).await.unwrap();
+        }
+        assert!(injector.hits(FaultPoint::CommitLogSync) >= 20);
+        drop(db);
+        assert!(!injector.crash().unwrap().is_empty());
+        let db = open(&test_dir).await;
+        let mut present = Vec::new();
+        for id in 1..=20 {
+            present.push(db.get_row("app", "rows", &key(id), &None).await.unwrap().is_some());
+        }
+        assert!(present.windows(2).all(|pair| pair[0] || !pair[1]), "{:?}", present);
+        verify_recovery(&db, &test_dir.join("data"), "app", "rows", &[]).await.unwrap();
+        db.shutdown().await.unwrap();
+        drop(injector);
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_flush_and_compaction_crashes_recover_every_row() {
+        let points = [
+            (FaultPoint::SSTableSync, false),
+            (FaultPoint::ManifestCommit, false),
+            (FaultPoint::Flush, false),
+            (FaultPoint::SSTableSync, true),
+            (FaultPoint::ManifestCommit, true),
+            (FaultPoint::Compaction, true),
+            (FaultPoint::CompactionCleanup, true),
+        ];
+        for (case, (point, compaction)) in points.into_iter().enumerate() {
+            let test_dir = test_dir(&format!("coredb_test_fault_flush_{}", case));
+            let injector = FaultInjector::install(&test_dir, case as u64);
+            let db = open(&test_dir).await;
+            for batch in 0..2 {
+                for id in batch * 3..batch * 3 + 3 {
+                    db.insert_row("app", "rows", row(id)).await.unwrap();
+                }
+                if compaction {
+                    db.flush_memtable("app", "rows").await.unwrap();
+                    db.wait_for_flushes().await.unwrap();
+                }
+            }
+
+            injector.crash_at(point, 1);
+            let result = if compaction {
+                db.major_compact("app", "rows").await.map(|_| ())
+            } else {
+                db.flush_memtable("app", "rows").await.unwrap();
+                db.wait_for_flushes().await
+            };
+            assert!(result.is_err(), "{:?}", point);
+            assert_eq!(injector.crashed(), Some(point));
+            // 죽은 뒤의 쓰기는 응답을 받지 못함
+            assert!(matches!(db.insert_row("app", "rows", row(9)).await, Err(CoreDBError::Io(_))));
+            drop(db);
+            injector.crash().unwrap();
+
+            let db = open(&test_dir).await;
+            let acknowledged: Vec<_> = (0..6).map(key).collect();
+            verify_recovery(&db, &test_dir.join("data"), "app", "rows", &acknowledged).await
+                .unwrap_or_else(|e| panic!("{:?} (compaction: {}): {}", point, compaction, e));
+            db.shutdown().await.unwrap();
+            drop(injector);
+            std::fs::remove_dir_all(&test_dir).ok();
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::BTreeSet;
+use crate::schema::{CassandraValue, Cell, Row};
+
+const GEOHASH_ALPHABET: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// 위도/경도 사각 영역 (west > east이면 날짜 변경선을 넘는 영역)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub south: f64,
+    pub west: f64,
+    pub north: f64,
+    pub east: f64,
+}
+
+impl BoundingBox {
+    pub fn new(south: f64, west: f64, north: f64, east: f64) -> Self {
+        Self { south, west, north, east }
+    }
+
+    /// 점이 영역 안(경계 포함)에 있는지 여부
+    pub fn contains(&self, lat: f64, lon: f64) -> bool {
+        if lat < self.south || lat > self.north {
+            return false;
+        }
+        if self.west <= self.east {
+            lon >= self.west && lon <= self.east
+        } else {
+            lon >= self.west || lon <= self.east
+        }
+    }
+
+    /// WHERE 조건 값 표현 ([south, west, north, east])
+    pub fn to_value(&self) -> CassandraValue {
+        CassandraValue::List(vec![
+            CassandraValue::Double(self.south),
+            CassandraValue::Double(self.west),
+            CassandraValue::Double(self.north),
+            CassandraValue::Double(self.east),
+        ])
+    }
+
+    pub fn from_value(value: &CassandraValue) -> Option<Self> {
+        match value {
+            CassandraValue::List(items) if items.len() == 4 => {
+                let mut bounds = [0.0f64; 4];
+                for (bound, item) in bounds.iter_mut().zip(items) {
+                    *bound = match item {
+                        CassandraValue::Double(v) => *v,
+                        CassandraValue::Int(v) => *v as f64,
+                        CassandraValue::BigInt(v) => *v as f64,
+                        _ => return None,
+                    };
+                }
+                Some(Self::new(bounds[0], bounds[1], bounds[2], bounds[3]))
+            },
+            _ => None,
+        }
+    }
+}
+
+/// 위도/경도를 지오해시 문자열로 인코딩
+///
+/// 같은 접두사를 공유하는 점은 가까우므로 클러스터링 컬럼에 넣으면
+/// 인접한 위치가 디스크에서도 함께 정렬됨
+pub fn geohash(lat: f64, lon: f64, precision: usize) -> String {
+    let mut lat_range = (-90.0, 90.0);
+    let mut lon_range = (-180.0, 180.0);
+    let mut hash = String::with_capacity(precision);
+    let mut bits = 0u8;
+    let mut bit_count = 0;
+    let mut even = true;
+
+    while hash.len() < precision {
+        let (range, value) = if even { (&mut lon_range, lon) } else { (&mut lat_range, lat) };
+        let mid = (range.0 + range.1) / 2.0;
+        bits <<= 1;
+        if value >= mid {
+            bits |= 1;
+            range.0 = mid;
+        } else {
+            range.1 = mid;
+        }
+        even = !even;
+        bit_count += 1;
+
+        if bit_count == 5 {
+            hash.push(GEOHASH_ALPHABET[bits as usize] as char);
+            bits = 0;
+            bit_count = 0;
+        }
+    }
+
+    hash
+}
+
+/// POINT 컬럼에서 지오해시 컬럼을 채우는 쓰기 트리거
+///
+/// `db.register_trigger(ks, table, "geohash", geohash_trigger("location", "cell", 6))`처럼
+/// 등록하고 지오해시 컬럼에 인덱스를 두면 영역 조회 대상을 셀 단위로 좁힐 수 있음
+pub fn geohash_trigger(point_column: &str, hash_column: &str, precision: usize) -> impl Fn(&mut Row) -> std::result::Result<(), String> + Send + Sync {
+    let point_column = point_column.to_string();
+    let hash_column = hash_column.to_string();
+    move |row: &mut Row| {
+        let (hash, timestamp) = match row.cells.get(&point_column) {
+            Some(Cell { value: CassandraValue::Point(lat, lon), timestamp, is_deleted: false, .. }) => {
+                (geohash(*lat, *lon, precision), *timestamp)
+            },
+            _ => return Ok(()),
+        };
+        row.cells.insert(&hash_column, Cell {
+            value: CassandraValue::Text(hash),
+            timestamp,
+            ttl: None,
+            is_deleted: false,
+        });
+        Ok(())
+    }
+}
+
+/// 영역을 덮는 지오해시 셀 목록 (셀 크기 간격으로 샘플링)
+pub fn geohash_cover(bbox: &BoundingBox, precision: usize) -> Vec<String> {
+    // 정밀도별 셀 크기: 경도 비트 = ceil(5p/2), 위도 비트 = floor(5p/2)
+    let lon_bits = (5 * precision).div_ceil(2) as i32;
+    let lat_bits = (5 * precision / 2) as i32;
+    let lat_step = 180.0 / 2f64.powi(lat_bits);
+    let lon_step = 360.0 / 2f64.powi(lon_bits);
+
+    let east = if bbox.west <= bbox.east { bbox.east } else { bbox.east + 360.0 };
+    let mut cells = BTreeSet::new();
+    let mut lat = bbox.south;
+    loop {
+        let mut lon = bbox.west;
+        loop {
+            let wrapped = if lon > 180.0 { lon - 360.0 } else { lon };
+            cells.insert(geohash(lat.min(bbox.north), wrapped, precision));
+            if lon >= east {
+                break;
+            }
+            lon = (lon + lon_step).min(east);
+        }
+        if lat >= bbox.north {
+            break;
+        }
+        lat = (lat + lat_step).min(bbox.north);
+    }
+
+    cells.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geohash_and_bounding_box() {
+        // 알려진 값: (57.64911, 10.40744) → u4pruydqqvj
+        assert_eq!(geohash(57.64911, 10.40744, 11), "u4pruydqqvj");
+
+        let seoul = BoundingBox::new(37.4, 126.8, 37.7, 127.2);
+        assert!(seoul.contains(37.5665, 126.9780));
+        assert!(!seoul.contains(35.1796, 129.0756));
+
+        // 날짜 변경선을 넘는 영역
+        let pacific = BoundingBox::new(-10.0, 170.0, 10.0, -170.0);
+        assert!(pacific.contains(0.0, 179.5));
+        assert!(pacific.contains(0.0, -175.0));
+        assert!(!pacific.contains(0.0, 0.0));
+
+        let cover = geohash_cover(&seoul, 4);
+        assert!(cover.contains(&geohash(37.5665, 126.9780, 4)));
+        assert!(!cover.contains(&geohash(35.1796, 129.0756, 4)));
+    }
+}
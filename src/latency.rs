@@ -0,0 +1,139 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// 2의 거듭제곱 구간마다 나누는 버킷 수 (상대 오차 25% 이하)
+const SUB_BUCKETS: u64 = 4;
+/// 0 ~ 2^64 마이크로초를 덮는 버킷 수
+const BUCKETS: usize = 64 * SUB_BUCKETS as usize;
+
+/// 지연 시간 분포 (마이크로초, 여러 작업이 잠금 없이 동시에 기록)
+///
+/// 버킷은 [2^k, 2^(k+1)) 구간을 4등분하므로 분위수는 실제 값보다 최대 25% 크게 추정됨
+#[derive(Debug)]
+pub struct LatencyHistogram {
+    buckets: Vec<AtomicU64>,
+    max_micros: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+/// 지연 시간 분포의 한 시점 복사본
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LatencySnapshot {
+    pub buckets: Vec<u64>,
+    pub max_micros: u64,
+    /// 기록한 지연 시간 합 (마이크로초)
+    pub sum_micros: u64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            buckets: (0..BUCKETS).map(|_| AtomicU64::new(0)).collect(),
+            max_micros: AtomicU64::new(0),
+            sum_micros: AtomicU64::new(0),
+        }
+    }
+}
+
+impl LatencyHistogram {
+    pub fn record(&self, latency: Duration) {
+        self.record_value(latency.as_micros().min(u64::MAX as u128) as u64);
+    }
+
+    /// 지연 시간이 아닌 값의 분포 (읽기당 SSTable 수 등, 8보다 작은 값은 정확히 기록됨)
+    pub fn record_value(&self, micros: u64) {
+        self.buckets[bucket_of(micros)].fetch_add(1, Ordering::Relaxed);
+        self.max_micros.fetch_max(micros, Ordering::Relaxed);
+        self.sum_micros.fetch_add(micros, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> LatencySnapshot {
+        LatencySnapshot {
+            buckets: self.buckets.iter().map(|bucket| bucket.load(Ordering::Relaxed)).collect(),
+            max_micros: self.max_micros.load(Ordering::Relaxed),
+            sum_micros: self.sum_micros.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl LatencySnapshot {
+    pub fn count(&self) -> u64 {
+        self.buckets.iter().sum()
+    }
+
+    /// upper 마이크로초 이하인 기록 수 (upper가 2^k - 1이면 버킷 경계와 맞아 정확함)
+    pub fn count_at_most(&self, upper: u64) -> u64 {
+        self.buckets.iter().enumerate()
+            .take_while(|(bucket, _)| bucket_upper_bound(*bucket) <= upper)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// 분위수 (0.0 ~ 1.0)에 해당하는 지연 시간 상한 (마이크로초, 버킷 경계 기준 추정, 최대값을 넘지 않음)
+    pub fn percentile(&self, quantile: f64) -> u64 {
+        let total = self.count();
+        if total == 0 {
+            return 0;
+        }
+        let target = ((total as f64 * quantile.clamp(0.0, 1.0)).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bucket, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= target {
+                return bucket_upper_bound(bucket).min(self.max_micros);
+            }
+        }
+        self.max_micros
+    }
+}
+
+fn bucket_of(micros: u64) -> usize {
+    if micros < SUB_BUCKETS {
+        return micros as usize;
+    }
+    // 최상위 비트 다음 두 비트로 구간 안의 위치를 정함
+    let log = micros.ilog2() as u64;
+    let sub = (micros >> (log - 2)) & (SUB_BUCKETS - 1);
+    ((log - 1) * SUB_BUCKETS + sub) as usize
+}
+
+fn bucket_upper_bound(bucket: usize) -> u64 {
+    let bucket = bucket as u64;
+    if bucket < SUB_BUCKETS {
+        return bucket;
+    }
+    let log = bucket / SUB_BUCKETS + 1;
+    let lower = (SUB_BUCKETS + bucket % SUB_BUCKETS) << (log - 2);
+    lower.saturating_add((1 << (log - 2)) - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_histogram_percentiles() {
+        let histogram = LatencyHistogram::default();
+        for micros in 1..=100 {
+            histogram.record(Duration::from_micros(micros));
+        }
+        histogram.record(Duration::from_millis(50));
+
+        let snapshot = histogram.snapshot();
+        assert_eq!(snapshot.count(), 101);
+        // 51번째 값(51us)은 [48, 55] 버킷
+        assert_eq!(snapshot.percentile(0.5), 55);
+        assert_eq!(snapshot.percentile(0.99), 111);
+        assert_eq!(snapshot.percentile(1.0), 50_000);
+        assert_eq!(LatencySnapshot::default().percentile(0.99), 0);
+        assert_eq!(snapshot.sum_micros, 5050 + 50_000);
+        // 1~63us와 1~127us (버킷 경계)
+        assert_eq!((snapshot.count_at_most(63), snapshot.count_at_most(127), snapshot.count_at_most(u64::MAX)), (63, 100, 101));
+
+        // 버킷 경계는 빈틈없이 이어짐
+        for micros in [0, 3, 4, 7, 8, 1000, u64::MAX] {
+            assert!(bucket_upper_bound(bucket_of(micros)) >= micros);
+            assert!(bucket_of(micros) == 0 || bucket_upper_bound(bucket_of(micros) - 1) < micros);
+        }
+    }
+}
@@ -6,6 +6,23 @@ pub mod compaction;
 pub mod wal;
 pub mod database;
 pub mod persistence;
+pub mod admission;
+pub mod throttle;
+pub mod latency;
+pub mod disk_usage;
+pub mod geo;
+pub mod auth;
+pub mod tls;
+pub mod quota;
+pub mod migration;
+pub mod clients;
+pub mod metrics;
+pub mod telemetry;
+pub mod snapshots;
+pub mod backup;
+pub mod restore;
+pub mod archive;
+pub mod fault_injection;
 
 pub use error::*;
 pub use schema::*;
@@ -15,6 +32,25 @@ pub use compaction::*;
 pub use wal::*;
 pub use database::*;
 pub use persistence::*;
+pub use admission::*;
+pub use throttle::*;
+pub use latency::*;
+pub use disk_usage::*;
+pub use auth::*;
+pub use tls::*;
+pub use quota::*;
+pub use migration::*;
+pub use clients::*;
+pub use metrics::*;
+pub use telemetry::*;
+pub use snapshots::*;
+pub use backup::*;
+pub use restore::*;
+pub use archive::*;
+pub use fault_injection::*;
+
+// 같은 이름이 여러 모듈에 있는 타입은 스키마 정의를 기본으로 노출
+pub use schema::{Row, TableOptions, CompactionStrategy};
 
 #[cfg(test)]
 mod tests {
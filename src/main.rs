@@ -1,10 +1,15 @@
-use clap::{Parser, Subcommand};
-use coredb::{CoreDB, DatabaseConfig, DatabaseStats};
-use std::path::PathBuf;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use coredb::{CommandArchiver, CommitLogArchiver, CommitLogEncryption, CommitLogRestore, CommitLogSync, CopyArchiver, CoreDB, DatabaseConfig, DatabaseStats, DiskAccessMode, DiskWriteMode, IntegrityCheckMode, KeyspaceQuota, OtlpConfig, OtlpProtocol, SnapshotSchedule, TlsConfig, TunableSettings};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process;
-use std::sync::Arc;
-use tokio;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 use tracing::{info, error, warn};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{reload, Registry};
 
 /// CoreDB - Single node Cassandra-like database
 #[derive(Parser)]
@@ -15,33 +20,535 @@ struct Cli {
     #[command(subcommand)]
     command: Commands,
     
+    /// TOML configuration file (keys are the long option names with underscores); options given on the
+    /// command line or through COREDB_* environment variables override its values
+    #[arg(long, env = "COREDB_CONFIG")]
+    config: Option<PathBuf>,
+    
     /// Data directory
-    #[arg(long, default_value = "./data")]
+    #[arg(long, env = "COREDB_DATA_DIR", default_value = "./data")]
     data_dir: PathBuf,
     
     /// Commit log directory
-    #[arg(long, default_value = "./commitlog")]
+    #[arg(long, env = "COREDB_COMMITLOG_DIR", default_value = "./commitlog")]
     commitlog_dir: PathBuf,
     
+    /// When commit log writes are fsynced: every write, on a timer, or in batches/groups that writers wait for
+    #[arg(long, env = "COREDB_COMMITLOG_SYNC", value_enum, default_value = "periodic")]
+    commitlog_sync: CommitLogSyncArg,
+    
+    /// Commit log fsync interval in ms (periodic mode)
+    #[arg(long, env = "COREDB_COMMITLOG_SYNC_PERIOD_MS", default_value = "10000")]
+    commitlog_sync_period_ms: u64,
+    
+    /// Longest time in ms a write waits for its batch to be fsynced (batch mode)
+    #[arg(long, env = "COREDB_COMMITLOG_SYNC_BATCH_WINDOW_MS", default_value = "2")]
+    commitlog_sync_batch_window_ms: u64,
+    
+    /// Latency target in ms for a group of writes to be fsynced, counted from the group's first write (group mode)
+    #[arg(long, env = "COREDB_COMMITLOG_SYNC_GROUP_WINDOW_MS", default_value = "2")]
+    commitlog_sync_group_window_ms: u64,
+    
+    /// Commit log segment size in MB (segments are preallocated and purged once all their data is flushed)
+    #[arg(long, env = "COREDB_COMMITLOG_SEGMENT_SIZE", default_value = "32")]
+    commitlog_segment_size: u64,
+    
+    /// Total commit log size cap in MB; tables pinning the oldest segment are flushed when exceeded (0 = unlimited)
+    #[arg(long, env = "COREDB_COMMITLOG_TOTAL_SPACE", default_value = "8192")]
+    commitlog_total_space: u64,
+    
+    /// Dedicated commit log directory for a keyspace as KEYSPACE=PATH (repeatable, e.g. to put a critical keyspace on another device)
+    #[arg(long = "commitlog-keyspace-dir", env = "COREDB_COMMITLOG_KEYSPACE_DIR", value_delimiter = ',', value_parser = parse_keyspace_directory)]
+    commitlog_keyspace_dirs: Vec<(String, PathBuf)>,
+    
+    /// Copy closed commit log segments into this directory
+    #[arg(long, env = "COREDB_COMMITLOG_ARCHIVE_DIR")]
+    commitlog_archive_dir: Option<PathBuf>,
+    
+    /// Archive closed commit log segments with a shell command (%path: segment path, %name: file name)
+    #[arg(long, env = "COREDB_COMMITLOG_ARCHIVE_COMMAND", conflicts_with = "commitlog_archive_dir")]
+    commitlog_archive_command: Option<String>,
+    
+    /// Replay archived commit log segments from this directory when tables are opened (point-in-time recovery)
+    #[arg(long, env = "COREDB_COMMITLOG_RESTORE_DIR")]
+    commitlog_restore_dir: Option<PathBuf>,
+    
+    /// Skip restored writes made after this time (RFC 3339, e.g. 2024-05-01T12:00:00Z)
+    #[arg(long, env = "COREDB_COMMITLOG_RESTORE_POINT_IN_TIME", requires = "commitlog_restore_dir", value_parser = parse_point_in_time)]
+    commitlog_restore_point_in_time: Option<i64>,
+    
+    /// Encrypt commit log segments with AES-256-GCM using this key file (32 raw bytes or 64 hex characters,
+    /// key id = file name without extension). Repeat to keep older keys for reading segments written with them;
+    /// the first key encrypts new segments unless --commitlog-encryption-key-command is given
+    #[arg(long, env = "COREDB_COMMITLOG_ENCRYPTION_KEY_FILE", value_delimiter = ',')]
+    commitlog_encryption_key_file: Vec<PathBuf>,
+    
+    /// Shell command printing the commit log encryption key as 64 hex characters (e.g. a KMS decrypt call)
+    #[arg(long, env = "COREDB_COMMITLOG_ENCRYPTION_KEY_COMMAND", requires = "commitlog_encryption_key_id")]
+    commitlog_encryption_key_command: Option<String>,
+    
+    /// Key id stored in segment headers for the key printed by --commitlog-encryption-key-command
+    #[arg(long, env = "COREDB_COMMITLOG_ENCRYPTION_KEY_ID", requires = "commitlog_encryption_key_command")]
+    commitlog_encryption_key_id: Option<String>,
+    
     /// Memtable flush threshold in MB
-    #[arg(long, default_value = "64")]
+    #[arg(long, env = "COREDB_MEMTABLE_FLUSH_THRESHOLD", default_value = "64")]
     memtable_flush_threshold: u64,
     
+    /// Flush memtables this many seconds after their first write regardless of size (0 = no limit)
+    #[arg(long, env = "COREDB_MEMTABLE_MAX_AGE", default_value = "0")]
+    memtable_max_age: u64,
+    
+    /// Partition size in MB above which a large partition warning is logged
+    #[arg(long, env = "COREDB_LARGE_PARTITION_WARNING_THRESHOLD", default_value = "100")]
+    large_partition_warning_threshold: u64,
+    
+    /// Keep all data in memory only (no commit log, no SSTable flush)
+    #[arg(long, env = "COREDB_EPHEMERAL")]
+    ephemeral: bool,
+    
+    /// Serve HTTP requests without authentication (by default every request needs a role's credentials
+    /// through HTTP Basic auth)
+    #[arg(long, env = "COREDB_ALLOW_ANONYMOUS")]
+    allow_anonymous: bool,
+    
+    /// Serve clients over TLS with this certificate chain (PEM)
+    #[arg(long, env = "COREDB_TLS_CERT", requires = "tls_key")]
+    tls_cert: Option<PathBuf>,
+    
+    /// Private key (PEM) of --tls-cert
+    #[arg(long, env = "COREDB_TLS_KEY", requires = "tls_cert")]
+    tls_key: Option<PathBuf>,
+    
+    /// CA certificate (PEM) to verify client certificates with; a verified certificate logs in as the role
+    /// named by its common name
+    #[arg(long, env = "COREDB_TLS_CA", requires = "tls_cert")]
+    tls_ca: Option<PathBuf>,
+    
+    /// Reject TLS connections without a client certificate signed by --tls-ca
+    #[arg(long, env = "COREDB_TLS_REQUIRE_CLIENT_AUTH", requires = "tls_ca")]
+    tls_require_client_auth: bool,
+    
+    /// Read SSTables through memory-mapped files instead of buffered reads
+    #[arg(long, env = "COREDB_MMAP")]
+    mmap: bool,
+    
+    /// Read SSTables through io_uring (needs the io-uring feature on Linux, otherwise buffered reads)
+    #[arg(long, env = "COREDB_IO_URING", conflicts_with = "mmap")]
+    io_uring: bool,
+    
+    /// Write compaction output with O_DIRECT, bypassing the page cache (falls back to buffered writes where unsupported)
+    #[arg(long, env = "COREDB_DIRECT_COMPACTION_WRITES")]
+    direct_compaction_writes: bool,
+    
+    /// Partition key cache size in entries (0 disables the cache)
+    #[arg(long, env = "COREDB_KEY_CACHE_ENTRIES", default_value = "100000")]
+    key_cache_entries: usize,
+    
+    /// Decompressed SSTable chunk cache size in MB, shared by all tables (0 disables the cache)
+    #[arg(long, env = "COREDB_CHUNK_CACHE_SIZE_MB", default_value = "128")]
+    chunk_cache_size_mb: u64,
+    
+    /// Compaction read/write throughput limit in MB/s (0 disables throttling)
+    #[arg(long, env = "COREDB_COMPACTION_THROUGHPUT", default_value = "16")]
+    compaction_throughput: u64,
+    
+    /// Number of tables compacted at the same time
+    #[arg(long, env = "COREDB_CONCURRENT_COMPACTORS", default_value = "2")]
+    concurrent_compactors: usize,
+    
+    /// Number of reads executed at the same time
+    #[arg(long, env = "COREDB_CONCURRENT_READS", default_value = "32")]
+    concurrent_reads: usize,
+    
+    /// Number of writes executed at the same time
+    #[arg(long, env = "COREDB_CONCURRENT_WRITES", default_value = "32")]
+    concurrent_writes: usize,
+    
+    /// Requests queued per read/write path before new ones are rejected as overloaded
+    #[arg(long, env = "COREDB_MAX_QUEUED_REQUESTS", default_value = "1024")]
+    max_queued_requests: usize,
+    
+    /// Memtables waiting to be flushed per table before writes are rejected as overloaded (0 = unlimited)
+    #[arg(long, env = "COREDB_MAX_PENDING_FLUSHES", default_value = "4")]
+    max_pending_flushes: usize,
+    
+    /// SSTables per table before writes are rejected as overloaded (0 = unlimited)
+    #[arg(long, env = "COREDB_MAX_SSTABLES_PER_TABLE", default_value = "256")]
+    max_sstables_per_table: usize,
+    
+    /// Warn when the data or commit log file system is this full, in percent (0 = off)
+    #[arg(long, env = "COREDB_DISK_USAGE_WARN_PERCENT", default_value = "90")]
+    disk_usage_warn_percent: u64,
+    
+    /// Reject writes (reads keep working) while the data or commit log file system is this full, in percent (0 = off)
+    #[arg(long, env = "COREDB_DISK_USAGE_FAIL_PERCENT", default_value = "95")]
+    disk_usage_fail_percent: u64,
+    
+    /// Verify the data directory on startup: check refuses to start on any problem, repair deletes leftover
+    /// files and scrubs corrupt SSTables and refuses to start only if problems remain
+    #[arg(long, env = "COREDB_FSCK", value_enum, default_value = "off")]
+    fsck: FsckArg,
+    
+    /// Per-keyspace limits as KEYSPACE:max_disk_mb=N,max_writes_per_sec=N,max_concurrent_queries=N
+    /// (repeatable, omitted limits are unlimited); queries over a limit fail right away instead of queueing
+    #[arg(long = "keyspace-quota", env = "COREDB_KEYSPACE_QUOTA", value_parser = parse_keyspace_quota)]
+    keyspace_quotas: Vec<(String, KeyspaceQuota)>,
+    
+    /// Periodic keyspace snapshots as KEYSPACE:interval=6h,keep=28,max_age=7d (repeatable); snapshots are taken on
+    /// interval boundaries and older automatic snapshots beyond keep/max_age are removed
+    #[arg(long = "snapshot-schedule", env = "COREDB_SNAPSHOT_SCHEDULE", value_parser = parse_snapshot_schedule)]
+    snapshot_schedules: Vec<(String, SnapshotSchedule)>,
+    
+    /// Object store for backups, e.g. s3://bucket/coredb or file:///mnt/backups (S3 credentials, region and
+    /// endpoint come from the usual AWS_* environment variables)
+    #[arg(long, env = "COREDB_BACKUP_URL")]
+    backup_url: Option<String>,
+    
+    /// Log CQL queries taking longer than this many ms as slow (0 = off)
+    #[arg(long, env = "COREDB_SLOW_QUERY_THRESHOLD", default_value = "500")]
+    slow_query_threshold: u64,
+    
+    /// OTLP collector to export tracing spans to, e.g. http://localhost:4317 for gRPC or http://localhost:4318
+    /// for HTTP (plain text, unset = spans are not exported)
+    #[arg(long, env = "COREDB_OTLP_ENDPOINT")]
+    otlp_endpoint: Option<String>,
+    
+    /// OTLP transport
+    #[arg(long, env = "COREDB_OTLP_PROTOCOL", value_enum, default_value = "grpc")]
+    otlp_protocol: OtlpProtocolArg,
+    
+    /// service.name reported with exported spans
+    #[arg(long, env = "COREDB_OTLP_SERVICE_NAME", default_value = "coredb")]
+    otlp_service_name: String,
+    
+    /// Fraction of requests without an incoming traceparent header to trace (0.0 - 1.0); requests that carry
+    /// one follow the caller's sampling decision
+    #[arg(long, env = "COREDB_OTLP_SAMPLE_RATIO", default_value = "1.0")]
+    otlp_sample_ratio: f64,
+    
     /// Log level
-    #[arg(long, default_value = "info")]
+    #[arg(long, env = "COREDB_LOG_LEVEL", default_value = "info")]
     log_level: String,
 }
 
+impl Cli {
+    /// 실행 중에 다시 적용할 수 있는 옵션 (SIGHUP으로 설정 파일을 다시 읽을 때)
+    fn tunable_settings(&self) -> TunableSettings {
+        TunableSettings {
+            memtable_flush_threshold_mb: Some(self.memtable_flush_threshold),
+            memtable_max_age_secs: Some(self.memtable_max_age),
+            compaction_throughput_mb_per_sec: Some(self.compaction_throughput),
+            slow_query_threshold_ms: Some(self.slow_query_threshold),
+            key_cache_entries: Some(self.key_cache_entries),
+            chunk_cache_size_mb: Some(self.chunk_cache_size_mb),
+        }
+    }
+}
+
+/// 설정 파일 (키는 같은 이름의 옵션과 같은 뜻, 없는 키는 옵션 기본값)
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    data_dir: Option<PathBuf>,
+    commitlog_dir: Option<PathBuf>,
+    commitlog_sync: Option<CommitLogSyncArg>,
+    commitlog_sync_period_ms: Option<u64>,
+    commitlog_sync_batch_window_ms: Option<u64>,
+    commitlog_sync_group_window_ms: Option<u64>,
+    commitlog_segment_size: Option<u64>,
+    commitlog_total_space: Option<u64>,
+    /// 키스페이스 = 전용 커밋 로그 디렉토리
+    commitlog_keyspace_dir: Option<HashMap<String, PathBuf>>,
+    commitlog_archive_dir: Option<PathBuf>,
+    commitlog_archive_command: Option<String>,
+    commitlog_restore_dir: Option<PathBuf>,
+    /// RFC 3339 시각
+    commitlog_restore_point_in_time: Option<String>,
+    commitlog_encryption_key_file: Option<Vec<PathBuf>>,
+    commitlog_encryption_key_command: Option<String>,
+    commitlog_encryption_key_id: Option<String>,
+    memtable_flush_threshold: Option<u64>,
+    memtable_max_age: Option<u64>,
+    large_partition_warning_threshold: Option<u64>,
+    ephemeral: Option<bool>,
+    allow_anonymous: Option<bool>,
+    tls_cert: Option<PathBuf>,
+    tls_key: Option<PathBuf>,
+    tls_ca: Option<PathBuf>,
+    tls_require_client_auth: Option<bool>,
+    mmap: Option<bool>,
+    io_uring: Option<bool>,
+    direct_compaction_writes: Option<bool>,
+    key_cache_entries: Option<usize>,
+    chunk_cache_size_mb: Option<u64>,
+    compaction_throughput: Option<u64>,
+    concurrent_compactors: Option<usize>,
+    concurrent_reads: Option<usize>,
+    concurrent_writes: Option<usize>,
+    max_queued_requests: Option<usize>,
+    max_pending_flushes: Option<usize>,
+    max_sstables_per_table: Option<usize>,
+    disk_usage_warn_percent: Option<u64>,
+    disk_usage_fail_percent: Option<u64>,
+    fsck: Option<FsckArg>,
+    /// 키스페이스 = 한도
+    keyspace_quota: Option<HashMap<String, KeyspaceQuota>>,
+    /// 키스페이스 = "interval=6h,keep=28"
+    snapshot_schedule: Option<HashMap<String, SnapshotSchedule>>,
+    backup_url: Option<String>,
+    slow_query_threshold: Option<u64>,
+    otlp_endpoint: Option<String>,
+    otlp_protocol: Option<OtlpProtocolArg>,
+    otlp_service_name: Option<String>,
+    otlp_sample_ratio: Option<f64>,
+    log_level: Option<String>,
+    /// start 명령의 포트와 주소
+    port: Option<u16>,
+    host: Option<String>,
+}
+
+impl ConfigFile {
+    fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        toml::from_str(&text).map_err(|e| format!("Invalid configuration file {}: {}", path.display(), e))
+    }
+    
+    /// 명령줄이나 환경 변수로 지정하지 않은 옵션을 파일 값으로 채움
+    fn apply(self, cli: &mut Cli, matches: &ArgMatches) -> Result<(), String> {
+        let unset_in = |matches: &ArgMatches, id: &str| {
+            !matches!(matches.value_source(id), Some(ValueSource::CommandLine | ValueSource::EnvVariable))
+        };
+        let unset = |id: &str| unset_in(matches, id);
+        macro_rules! fill {
+            ($($field:ident),* $(,)?) => {
+                $(if let (Some(value), true) = (self.$field, unset(stringify!($field))) {
+                    cli.$field = value;
+                })*
+            };
+        }
+        macro_rules! fill_optional {
+            ($($field:ident),* $(,)?) => {
+                $(if let (Some(value), true) = (self.$field, unset(stringify!($field))) {
+                    cli.$field = Some(value);
+                })*
+            };
+        }
+        
+        fill!(
+            data_dir, commitlog_dir, commitlog_sync, commitlog_sync_period_ms, commitlog_sync_batch_window_ms,
+            commitlog_sync_group_window_ms, commitlog_segment_size, commitlog_total_space, commitlog_encryption_key_file,
+            memtable_flush_threshold, memtable_max_age, large_partition_warning_threshold, ephemeral, allow_anonymous, tls_require_client_auth, direct_compaction_writes, key_cache_entries, chunk_cache_size_mb,
+            compaction_throughput, concurrent_compactors, concurrent_reads, concurrent_writes, max_queued_requests,
+            max_pending_flushes, max_sstables_per_table, disk_usage_warn_percent, disk_usage_fail_percent, fsck, slow_query_threshold, log_level,
+            otlp_protocol, otlp_service_name, otlp_sample_ratio,
+        );
+        fill_optional!(commitlog_restore_dir, commitlog_encryption_key_command, commitlog_encryption_key_id, tls_cert, tls_key, tls_ca, otlp_endpoint, backup_url);
+        if let (Some(directories), true) = (self.commitlog_keyspace_dir, unset("commitlog_keyspace_dirs")) {
+            cli.commitlog_keyspace_dirs = directories.into_iter().collect();
+        }
+        if let (Some(quotas), true) = (self.keyspace_quota, unset("keyspace_quotas")) {
+            cli.keyspace_quotas = quotas.into_iter().collect();
+        }
+        if let (Some(schedules), true) = (self.snapshot_schedule, unset("snapshot_schedules")) {
+            cli.snapshot_schedules = schedules.into_iter().collect();
+        }
+        if let (Some(time), true) = (self.commitlog_restore_point_in_time, unset("commitlog_restore_point_in_time")) {
+            cli.commitlog_restore_point_in_time = Some(parse_point_in_time(&time)?);
+        }
+        // 서로 배타적인 옵션은 명령줄에서 한쪽을 지정했으면 파일의 다른 쪽을 쓰지 않음
+        if unset("commitlog_archive_dir") && unset("commitlog_archive_command") {
+            cli.commitlog_archive_dir = self.commitlog_archive_dir.or(cli.commitlog_archive_dir.take());
+            cli.commitlog_archive_command = self.commitlog_archive_command.or(cli.commitlog_archive_command.take());
+        }
+        if unset("mmap") && unset("io_uring") {
+            cli.mmap = self.mmap.unwrap_or(cli.mmap);
+            cli.io_uring = self.io_uring.unwrap_or(cli.io_uring);
+        }
+        if let (Commands::Start { port, host }, Some(start)) = (&mut cli.command, matches.subcommand_matches("start")) {
+            if let (Some(value), true) = (self.port, unset_in(start, "port")) {
+                *port = value;
+            }
+            if let (Some(value), true) = (self.host, unset_in(start, "host")) {
+                *host = value;
+            }
+        }
+        
+        if cli.commitlog_archive_dir.is_some() && cli.commitlog_archive_command.is_some() {
+            return Err("commitlog_archive_dir and commitlog_archive_command cannot be used together".to_string());
+        }
+        if cli.mmap && cli.io_uring {
+            return Err("mmap and io_uring cannot be used together".to_string());
+        }
+        if cli.commitlog_encryption_key_command.is_some() != cli.commitlog_encryption_key_id.is_some() {
+            return Err("commitlog_encryption_key_command and commitlog_encryption_key_id must be given together".to_string());
+        }
+        if cli.commitlog_restore_point_in_time.is_some() && cli.commitlog_restore_dir.is_none() {
+            return Err("commitlog_restore_point_in_time requires commitlog_restore_dir".to_string());
+        }
+        if cli.tls_cert.is_some() != cli.tls_key.is_some() {
+            return Err("tls_cert and tls_key must be given together".to_string());
+        }
+        if cli.tls_ca.is_some() && cli.tls_cert.is_none() {
+            return Err("tls_ca requires tls_cert".to_string());
+        }
+        if cli.tls_require_client_auth && cli.tls_ca.is_none() {
+            return Err("tls_require_client_auth requires tls_ca".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// 명령줄, 환경 변수(COREDB_*), 설정 파일 순으로 옵션을 정함 (설정 파일을 다시 읽을 때 쓰도록 명령줄도 돌려줌)
+fn parse_cli() -> (Cli, ArgMatches) {
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    if let Some(path) = cli.config.clone() {
+        if let Err(message) = ConfigFile::load(&path).and_then(|file| file.apply(&mut cli, &matches)) {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+    }
+    (cli, matches)
+}
+
+/// 설정 파일을 다시 읽어 조정 가능한 설정과 로그 수준을 구함 (명령줄과 환경 변수 값은 그대로 우선)
+fn reload_tunable_settings(path: &Path, matches: &ArgMatches) -> Result<(TunableSettings, String), String> {
+    let mut cli = Cli::from_arg_matches(matches).map_err(|e| e.to_string())?;
+    ConfigFile::load(path)?.apply(&mut cli, matches)?;
+    Ok((cli.tunable_settings(), cli.log_level))
+}
+
+/// 시작할 때 적용된 설정 요약 (암호화 키는 키 ID만)
+fn log_effective_config(config: &DatabaseConfig, config_file: Option<&Path>, host: &str, port: u16, allow_anonymous: bool, tls: Option<&TlsConfig>) {
+    info!("Effective configuration{}:", config_file.map(|path| format!(" (file: {})", path.display())).unwrap_or_default());
+    info!("  server: {}:{} (authentication: {})", host, port, if allow_anonymous { "off, anonymous access" } else { "required" });
+    info!("  tls: {}", match tls {
+        Some(tls) => format!("{} (client certificates: {})", tls.cert_file.display(), match (&tls.ca_file, tls.require_client_auth) {
+            (Some(ca_file), true) => format!("required, CA {}", ca_file.display()),
+            (Some(ca_file), false) => format!("optional, CA {}", ca_file.display()),
+            (None, _) => "off".to_string(),
+        }),
+        None => "off".to_string(),
+    });
+    info!("  slow query threshold: {} ms", config.slow_query_threshold_ms);
+    info!("  disk usage watermarks: warn {}%, reject writes {}%", config.disk_usage_warn_percent, config.disk_usage_fail_percent);
+    info!("  data_directory: {} (startup check: {:?})", config.data_directory.display(), config.integrity_check);
+    info!("  ephemeral: {}, disk_access_mode: {:?}", config.ephemeral, config.disk_access_mode);
+    info!("  commitlog: {} (sync {:?}, segment {} MB, total space {} MB)",
+          config.commitlog_directory.display(), config.commitlog_sync, config.commitlog_segment_size_mb, config.commitlog_total_space_mb);
+    for (keyspace, directory) in &config.commitlog_keyspace_directories {
+        info!("  commitlog [{}]: {}", keyspace, directory.display());
+    }
+    info!("  commitlog archive: {}, restore: {}, encryption key: {}",
+          config.commitlog_archiver.as_ref().map(|archiver| format!("{:?}", archiver)).unwrap_or_else(|| "off".to_string()),
+          config.commitlog_restore.as_ref().map(|restore| restore.directory.display().to_string()).unwrap_or_else(|| "off".to_string()),
+          config.commitlog_encryption.as_ref().map(|encryption| encryption.key_id()).unwrap_or("off"));
+    info!("  memtable_flush_threshold: {} MB, memtable_max_age: {} s, max_pending_flushes: {}, large_partition_warning_threshold: {} MB",
+          config.memtable_flush_threshold_mb, config.memtable_max_age_secs, config.max_pending_flushes, config.large_partition_warning_threshold_mb);
+    info!("  compaction: {} MB/s, {} concurrent compactors, max {} SSTables per table",
+          config.compaction_throughput_mb_per_sec, config.concurrent_compactors, config.max_sstables_per_table);
+    info!("  concurrent_reads: {}, concurrent_writes: {}, max_queued_requests: {}",
+          config.concurrent_reads, config.concurrent_writes, config.max_queued_requests);
+    info!("  key_cache_entries: {}, chunk_cache_size: {} MB", config.key_cache_entries, config.chunk_cache_size_mb);
+    for (keyspace, quota) in &config.keyspace_quotas {
+        info!("  quota [{}]: max_disk {} MB, max_writes {}/s, max_concurrent_queries {}",
+              keyspace, quota.max_disk_mb, quota.max_writes_per_sec, quota.max_concurrent_queries);
+    }
+    for (keyspace, schedule) in &config.snapshot_schedules {
+        info!("  snapshot schedule [{}]: every {:?}, keep {}, max age {:?}",
+              keyspace, schedule.interval, schedule.keep_last, schedule.max_age);
+    }
+    info!("  backup_url: {}", config.backup_url.as_deref().unwrap_or("off"));
+}
+
+/// RFC 3339 시각을 마이크로초로
+fn parse_point_in_time(value: &str) -> Result<i64, String> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|time| time.timestamp_micros())
+        .map_err(|e| format!("invalid RFC 3339 time: {}", e))
+}
+
+/// KEYSPACE=PATH
+fn parse_keyspace_directory(value: &str) -> Result<(String, PathBuf), String> {
+    match value.split_once('=') {
+        Some((keyspace, path)) if !keyspace.is_empty() && !path.is_empty() => Ok((keyspace.to_string(), PathBuf::from(path))),
+        _ => Err("expected KEYSPACE=PATH".to_string()),
+    }
+}
+
+/// KEYSPACE:max_disk_mb=N,max_writes_per_sec=N,max_concurrent_queries=N
+fn parse_keyspace_quota(value: &str) -> Result<(String, KeyspaceQuota), String> {
+    match value.split_once(':') {
+        Some((keyspace, limits)) if !keyspace.is_empty() => Ok((keyspace.to_string(), KeyspaceQuota::parse(limits)?)),
+        _ => Err("expected KEYSPACE:LIMIT=N,...".to_string()),
+    }
+}
+
+fn parse_snapshot_schedule(value: &str) -> Result<(String, SnapshotSchedule), String> {
+    match value.split_once(':') {
+        Some((keyspace, schedule)) if !keyspace.is_empty() => Ok((keyspace.to_string(), SnapshotSchedule::parse(schedule)?)),
+        _ => Err("expected KEYSPACE:interval=DURATION,...".to_string()),
+    }
+}
+
+/// 커밋 로그 암호화 키 읽기 (키 명령이 있으면 그 키로, 없으면 첫 키 파일로 새 세그먼트를 암호화)
+async fn load_commitlog_encryption(key_files: &[PathBuf], key_command: Option<(&str, &str)>) -> Option<CommitLogEncryption> {
+    let mut keys = Vec::new();
+    if let Some((command, key_id)) = key_command {
+        keys.push(CommitLogEncryption::from_command(key_id, command).await);
+    }
+    for key_file in key_files {
+        keys.push(CommitLogEncryption::from_key_file(key_file).await);
+    }
+    let mut encryption: Option<CommitLogEncryption> = None;
+    for key in keys {
+        let key = key.unwrap_or_else(|e| {
+            error!("Failed to load commit log encryption key: {}", e);
+            process::exit(1);
+        });
+        encryption = Some(match encryption {
+            Some(current) => current.with_previous(key),
+            None => key,
+        });
+    }
+    encryption
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum CommitLogSyncArg {
+    Always,
+    Periodic,
+    Batch,
+    Group,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum OtlpProtocolArg {
+    Grpc,
+    HttpProtobuf,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum FsckArg {
+    Off,
+    Check,
+    Repair,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Start the database server
     Start {
         /// Port to listen on
-        #[arg(short, long, default_value = "9042")]
+        #[arg(short, long, env = "COREDB_PORT", default_value = "9042")]
         port: u16,
         
         /// Host to bind to
-        #[arg(long, default_value = "127.0.0.1")]
+        #[arg(long, env = "COREDB_HOST", default_value = "127.0.0.1")]
         host: String,
     },
     /// Execute a CQL query
@@ -53,30 +560,324 @@ enum Commands {
     Shell,
     /// Show database statistics
     Stats,
+    /// Show per-table size, partition size, latency, SSTables-per-read and tombstones-per-read statistics
+    /// (latencies cover this process only; use /stats on a running server for live numbers)
+    Tablestats {
+        /// Only tables of this keyspace, or one table as keyspace.table
+        filter: Option<String>,
+    },
     /// Initialize database
     Init,
+    /// Bulk-load Apache Cassandra SSTables (3.0 to 4.x big format) into a CoreDB table
+    ImportSstables {
+        /// Directory with the exported SSTables (<version>-<generation>-big-Data.db and components)
+        directory: PathBuf,
+        
+        /// CREATE TABLE statement of the target table (must match the Cassandra table)
+        #[arg(long)]
+        schema: String,
+    },
+    /// Rewrite SSTables written in an older format version into the current one (run while the server is stopped)
+    UpgradeSstables {
+        /// Only upgrade tables of this keyspace
+        keyspace: Option<String>,
+        
+        /// Only upgrade this table
+        #[arg(requires = "keyspace")]
+        table: Option<String>,
+        
+        /// Also rewrite SSTables that are already in the current format
+        #[arg(short = 'a', long)]
+        include_all: bool,
+    },
+    /// Rewrite corrupted SSTables from their readable chunks and move the originals to <table>/quarantine/
+    /// (run while the server is stopped)
+    Scrub {
+        /// Only scrub tables of this keyspace
+        keyspace: Option<String>,
+        
+        /// Only scrub this table
+        #[arg(requires = "keyspace")]
+        table: Option<String>,
+    },
+    /// Merge all SSTables of a table into one (run while the server is stopped; Ctrl-C cancels)
+    Compact {
+        keyspace: String,
+        
+        table: String,
+    },
+    /// Inspect SSTable files
+    Sstable {
+        #[command(subcommand)]
+        command: SstableCommands,
+    },
+    /// Inspect commit log segments
+    Wal {
+        #[command(subcommand)]
+        command: WalCommands,
+    },
+    /// Manage table snapshots (hard links of the current SSTables under <table>/snapshots/<name>/)
+    Snapshot {
+        #[command(subcommand)]
+        command: SnapshotCommands,
+    },
+    /// Upload snapshots to, prune and restore from the object store given by --backup-url
+    Backup {
+        #[command(subcommand)]
+        command: BackupCommands,
+    },
+    /// Restore the tables of a keyspace to a point in time from a snapshot (or a backup) and the archived commit log
+    /// (run while the server is stopped; the current SSTables are kept as a pre-restore-<ms> snapshot)
+    Restore {
+        keyspace: String,
+        
+        /// Only restore this table
+        table: Option<String>,
+        
+        /// RFC 3339 time; only writes up to this time are replayed
+        #[arg(long, value_parser = parse_point_in_time)]
+        to_timestamp: i64,
+        
+        /// Local snapshot to start from (must be taken before --to-timestamp)
+        #[arg(short = 's', long, required_unless_present = "backup", conflicts_with = "backup")]
+        snapshot: Option<String>,
+        
+        /// Backup in --backup-url to start from; downloaded as a local snapshot of the same name
+        #[arg(short = 'b', long)]
+        backup: Option<String>,
+        
+        /// Archived commit log segments (defaults to --commitlog-archive-dir)
+        #[arg(long)]
+        archive_dir: Option<PathBuf>,
+    },
+    /// Export the schema DDL, SSTables and metadata of a keyspace into one tar.zst archive that can be imported
+    /// on another machine (run while the server is stopped)
+    Export {
+        #[arg(short, long)]
+        keyspace: String,
+        
+        /// Archive to write (replaced if it exists)
+        #[arg(short, long)]
+        out: PathBuf,
+    },
+    /// Import a keyspace archive written by export: checks versions, places the SSTables with new manifests and
+    /// recreates the schema (run while the server is stopped; fails if the keyspace already exists)
+    Import {
+        archive: PathBuf,
+        
+        /// Import under this keyspace name instead of the exported one
+        #[arg(short, long)]
+        keyspace: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum BackupCommands {
+    /// Upload a snapshot of the tables of a keyspace; SSTables already uploaded by an earlier backup are skipped.
+    /// Without --snapshot a new snapshot is taken (server stopped) and removed after the upload
+    Upload {
+        keyspace: String,
+        
+        /// Only back up this table
+        table: Option<String>,
+        
+        /// Upload this existing snapshot (e.g. one taken with POST /snapshots on a running server)
+        #[arg(short = 's', long)]
+        snapshot: Option<String>,
+    },
+    /// List backups with their SSTable count and size
+    List {
+        /// Only backups of this keyspace
+        keyspace: Option<String>,
+        
+        /// Only backups of this table
+        #[arg(requires = "keyspace")]
+        table: Option<String>,
+    },
+    /// Remove backups beyond a retention policy and the SSTables no remaining backup uses
+    /// (do not run while uploading to the same tables)
+    Prune {
+        /// Only prune backups of this keyspace
+        keyspace: Option<String>,
+        
+        /// Only prune backups of this table
+        #[arg(requires = "keyspace")]
+        table: Option<String>,
+        
+        /// keep=N (most recent backups to keep) and/or max_age=30d (s/m/h/d/w)
+        #[arg(short = 'r', long, value_parser = coredb::BackupRetention::parse)]
+        retention: coredb::BackupRetention,
+    },
+    /// Download a backup into the table directory under --data-dir (run while the server is stopped;
+    /// the table must have no SSTables)
+    Restore {
+        keyspace: String,
+        
+        table: String,
+        
+        /// Backup name (see backup list)
+        #[arg(short = 'n', long)]
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum SnapshotCommands {
+    /// Snapshot the tables of a keyspace without copying data (run while the server is stopped; writes still
+    /// only in the commit log are not included, so drain first or use POST /snapshots on a running server)
+    Create {
+        keyspace: String,
+        
+        /// Only snapshot this table
+        table: Option<String>,
+        
+        /// Snapshot name (defaults to the current time in milliseconds)
+        #[arg(short = 'n', long)]
+        name: Option<String>,
+    },
+    /// List snapshots with their SSTable count and size
+    List {
+        /// Only snapshots of this keyspace
+        keyspace: Option<String>,
+    },
+    /// Remove a snapshot (the table's own SSTables are kept)
+    Clear {
+        keyspace: String,
+        
+        /// Only remove the snapshot of this table
+        table: Option<String>,
+        
+        #[arg(short = 'n', long)]
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum WalCommands {
+    /// Print commit log entries (position, time, keyspace, table, mutation type and key) in the order
+    /// they were written, one JSON object per line
+    Dump {
+        /// Segment file (commitlog-<id>.log) or directory of segments (defaults to --commitlog-dir)
+        path: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SstableCommands {
+    /// Print partitions, rows, cells, timestamps and tombstones of an SSTable as JSON
+    Dump {
+        /// SSTable data file (<id>-Data.db)
+        file: PathBuf,
+    },
+    /// Show SSTable metadata: format, compression, counts, timestamps, bloom filter and component sizes
+    Info {
+        /// SSTable data file (<id>-Data.db)
+        file: PathBuf,
+    },
+    /// Split an oversized SSTable into smaller ones along partition boundaries, replacing the original
+    /// (run while the server is stopped)
+    Split {
+        /// SSTable data file (<id>-Data.db)
+        file: PathBuf,
+        
+        /// Number of SSTables to split into
+        #[arg(short = 'n', long, default_value = "2")]
+        count: usize,
+    },
 }
 
 #[tokio::main]
 async fn main() {
-    let cli = Cli::parse();
+    let (cli, matches) = parse_cli();
+    
+    // 로깅 초기화 (sstable, wal 명령은 stdout을 출력에 쓰므로 로그는 stderr로)
+    let otlp = cli.otlp_endpoint.clone().map(|endpoint| OtlpConfig {
+        endpoint,
+        protocol: match cli.otlp_protocol {
+            OtlpProtocolArg::Grpc => OtlpProtocol::Grpc,
+            OtlpProtocolArg::HttpProtobuf => OtlpProtocol::HttpProtobuf,
+        },
+        service_name: cli.otlp_service_name.clone(),
+        sample_ratio: cli.otlp_sample_ratio,
+    });
+    let tracer_provider = init_logging(&cli.log_level, matches!(cli.command, Commands::Sstable { .. } | Commands::Wal { .. } | Commands::Snapshot { .. } | Commands::Backup { .. } | Commands::Restore { .. } | Commands::Export { .. } | Commands::Import { .. }), otlp.as_ref());
+    if let Some(otlp) = &otlp {
+        info!("Exporting traces to {} ({:?}, service {}, sample ratio {})", otlp.endpoint, otlp.protocol, otlp.service_name, otlp.sample_ratio);
+    }
+    
+    let commitlog_encryption = load_commitlog_encryption(
+        &cli.commitlog_encryption_key_file,
+        cli.commitlog_encryption_key_command.as_deref().zip(cli.commitlog_encryption_key_id.as_deref()),
+    ).await;
     
-    // 로깅 초기화
-    init_logging(&cli.log_level);
+    // 시점 복구의 기본 보관 디렉토리
+    let commitlog_archive_dir = cli.commitlog_archive_dir.clone();
     
     // 데이터베이스 설정
     let config = DatabaseConfig {
         data_directory: cli.data_dir,
         commitlog_directory: cli.commitlog_dir,
+        commitlog_sync: match cli.commitlog_sync {
+            CommitLogSyncArg::Always => CommitLogSync::Always,
+            CommitLogSyncArg::Periodic => CommitLogSync::Periodic { interval: Duration::from_millis(cli.commitlog_sync_period_ms) },
+            CommitLogSyncArg::Batch => CommitLogSync::Batch { max_delay: Duration::from_millis(cli.commitlog_sync_batch_window_ms) },
+            CommitLogSyncArg::Group => CommitLogSync::Group { max_latency: Duration::from_millis(cli.commitlog_sync_group_window_ms) },
+        },
+        commitlog_segment_size_mb: cli.commitlog_segment_size,
+        commitlog_total_space_mb: cli.commitlog_total_space,
+        commitlog_keyspace_directories: cli.commitlog_keyspace_dirs.into_iter().collect(),
+        commitlog_archiver: match (cli.commitlog_archive_dir, cli.commitlog_archive_command) {
+            (Some(directory), _) => Some(Arc::new(CopyArchiver { directory }) as Arc<dyn CommitLogArchiver>),
+            (_, Some(command)) => Some(Arc::new(CommandArchiver { command })),
+            _ => None,
+        },
+        commitlog_restore: cli.commitlog_restore_dir.map(|directory| CommitLogRestore {
+            directory,
+            point_in_time: cli.commitlog_restore_point_in_time,
+        }),
+        commitlog_encryption,
         memtable_flush_threshold_mb: cli.memtable_flush_threshold,
-        compaction_throughput_mb_per_sec: 16,
-        concurrent_reads: 32,
-        concurrent_writes: 32,
+        memtable_max_age_secs: cli.memtable_max_age,
+        compaction_throughput_mb_per_sec: cli.compaction_throughput,
+        concurrent_compactors: cli.concurrent_compactors,
+        concurrent_reads: cli.concurrent_reads,
+        concurrent_writes: cli.concurrent_writes,
+        max_queued_requests: cli.max_queued_requests,
+        max_pending_flushes: cli.max_pending_flushes,
+        max_sstables_per_table: cli.max_sstables_per_table,
+        large_partition_warning_threshold_mb: cli.large_partition_warning_threshold,
+        ephemeral: cli.ephemeral,
+        disk_access_mode: match (cli.mmap, cli.io_uring) {
+            (true, _) => DiskAccessMode::Mmap,
+            (_, true) => DiskAccessMode::IoUring,
+            _ => DiskAccessMode::Standard,
+        },
+        compaction_write_mode: if cli.direct_compaction_writes { DiskWriteMode::Direct } else { DiskWriteMode::Buffered },
+        key_cache_entries: cli.key_cache_entries,
+        chunk_cache_size_mb: cli.chunk_cache_size_mb,
+        slow_query_threshold_ms: cli.slow_query_threshold,
+        disk_usage_warn_percent: cli.disk_usage_warn_percent,
+        disk_usage_fail_percent: cli.disk_usage_fail_percent,
+        integrity_check: match cli.fsck {
+            FsckArg::Off => IntegrityCheckMode::Off,
+            FsckArg::Check => IntegrityCheckMode::Check,
+            FsckArg::Repair => IntegrityCheckMode::Repair,
+        },
+        keyspace_quotas: cli.keyspace_quotas.into_iter().collect(),
+        snapshot_schedules: cli.snapshot_schedules.into_iter().collect(),
+        backup_url: cli.backup_url,
     };
     
     match cli.command {
         Commands::Start { port, host } => {
-            start_server(config, host, port).await;
+            let tls = cli.tls_cert.zip(cli.tls_key).map(|(cert_file, key_file)| TlsConfig {
+                cert_file,
+                key_file,
+                ca_file: cli.tls_ca,
+                require_client_auth: cli.tls_require_client_auth,
+            });
+            log_effective_config(&config, cli.config.as_deref(), &host, port, cli.allow_anonymous, tls.as_ref());
+            start_server(config, host, port, cli.allow_anonymous, tls, cli.config.map(|path| (path, matches))).await;
         },
         Commands::Query { query } => {
             execute_query(config, query).await;
@@ -87,30 +888,159 @@ async fn main() {
         Commands::Stats => {
             show_stats(config).await;
         },
+        Commands::Tablestats { filter } => {
+            show_table_stats(config, filter).await;
+        },
         Commands::Init => {
             init_database(config).await;
         },
+        Commands::ImportSstables { directory, schema } => {
+            import_sstables(config, directory, schema).await;
+        },
+        Commands::UpgradeSstables { keyspace, table, include_all } => {
+            upgrade_sstables(config, keyspace, table, include_all).await;
+        },
+        Commands::Scrub { keyspace, table } => {
+            scrub_tables(config, keyspace, table).await;
+        },
+        Commands::Compact { keyspace, table } => {
+            compact_table(config, keyspace, table).await;
+        },
+        Commands::Sstable { command: SstableCommands::Dump { file } } => {
+            dump_sstable(file).await;
+        },
+        Commands::Sstable { command: SstableCommands::Info { file } } => {
+            show_sstable_info(file).await;
+        },
+        Commands::Sstable { command: SstableCommands::Split { file, count } } => {
+            split_sstable(file, count).await;
+        },
+        Commands::Wal { command: WalCommands::Dump { path } } => {
+            let path = path.unwrap_or(config.commitlog_directory);
+            dump_commit_log(path, config.commitlog_encryption.as_ref()).await;
+        },
+        Commands::Snapshot { command: SnapshotCommands::Create { keyspace, table, name } } => {
+            create_snapshot(config, keyspace, table, name.unwrap_or_else(coredb::default_snapshot_name)).await;
+        },
+        Commands::Snapshot { command: SnapshotCommands::List { keyspace } } => {
+            list_snapshots(config, keyspace).await;
+        },
+        Commands::Snapshot { command: SnapshotCommands::Clear { keyspace, table, name } } => {
+            clear_snapshot(config, keyspace, table, name).await;
+        },
+        Commands::Backup { command } => {
+            let store = open_backup_store(&config);
+            match command {
+                BackupCommands::Upload { keyspace, table, snapshot } => upload_backup(config, &store, keyspace, table, snapshot).await,
+                BackupCommands::List { keyspace, table } => list_backups(&store, keyspace, table).await,
+                BackupCommands::Prune { keyspace, table, retention } => prune_backups(&store, keyspace, table, retention).await,
+                BackupCommands::Restore { keyspace, table, name } => restore_backup(config, &store, keyspace, table, name).await,
+            }
+        },
+        Commands::Restore { keyspace, table, to_timestamp, snapshot, backup, archive_dir } => {
+            restore_to_timestamp(config, archive_dir.or(commitlog_archive_dir), keyspace, table, to_timestamp, snapshot, backup).await;
+        },
+        Commands::Export { keyspace, out } => {
+            export_keyspace(config, keyspace, out).await;
+        },
+        Commands::Import { archive, keyspace } => {
+            import_keyspace(config, archive, keyspace).await;
+        },
+    }
+    
+    // 아직 보내지 않은 스팬을 내보냄
+    if let Some(provider) = tracer_provider {
+        if let Err(e) = provider.shutdown() {
+            eprintln!("Failed to flush traces: {}", e);
+        }
     }
 }
 
-fn init_logging(level: &str) {
-    let log_level = match level.to_lowercase().as_str() {
-        "trace" => tracing::Level::TRACE,
-        "debug" => tracing::Level::DEBUG,
-        "info" => tracing::Level::INFO,
-        "warn" => tracing::Level::WARN,
-        "error" => tracing::Level::ERROR,
-        _ => tracing::Level::INFO,
+/// 실행 중에 로그 수준을 바꾸는 핸들 (SIGHUP이나 POST /settings)
+static LOG_LEVEL: OnceLock<reload::Handle<LevelFilter, Registry>> = OnceLock::new();
+
+fn parse_log_level(level: &str) -> Option<LevelFilter> {
+    match level.to_lowercase().as_str() {
+        "trace" => Some(LevelFilter::TRACE),
+        "debug" => Some(LevelFilter::DEBUG),
+        "info" => Some(LevelFilter::INFO),
+        "warn" => Some(LevelFilter::WARN),
+        "error" => Some(LevelFilter::ERROR),
+        _ => None,
+    }
+}
+
+/// 로그 출력과 (otlp가 있으면) 스팬 내보내기 초기화
+///
+/// 로그 수준은 로그 줄에만 적용되고 스팬은 로그 줄에 붙이지 않음. 스팬은 INFO 이상을 내보냄
+fn init_logging(level: &str, to_stderr: bool, otlp: Option<&OtlpConfig>) -> Option<opentelemetry_sdk::trace::TracerProvider> {
+    use tracing_subscriber::filter::FilterExt;
+    let (filter, handle) = reload::Layer::new(parse_log_level(level).unwrap_or(LevelFilter::INFO));
+    let filter = filter.and(tracing_subscriber::filter::filter_fn(|metadata| metadata.is_event()));
+    let fmt = if to_stderr {
+        tracing_subscriber::fmt::layer().with_writer(std::io::stderr).with_filter(filter).boxed()
+    } else {
+        tracing_subscriber::fmt::layer().with_filter(filter).boxed()
     };
-    
-    tracing_subscriber::fmt()
-        .with_max_level(log_level)
-        .init();
+    let provider = otlp.map(|otlp| otlp.tracer_provider().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(2);
+    }));
+    let spans = provider.as_ref().map(|provider| coredb::tracing_layer(provider).with_filter(LevelFilter::INFO));
+    tracing_subscriber::registry().with(fmt).with(spans).init();
+    LOG_LEVEL.set(handle).ok();
+    provider
+}
+
+fn current_log_level() -> String {
+    LOG_LEVEL.get()
+        .and_then(|handle| handle.clone_current())
+        .map(|level| level.to_string().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// 조정 가능한 설정과 로그 수준을 함께 적용 (잘못된 값이 있으면 아무것도 바꾸지 않음)
+fn apply_runtime_settings(db: &CoreDB, settings: &TunableSettings, log_level: Option<&str>) -> Result<(), String> {
+    let level = log_level
+        .map(|level| parse_log_level(level).ok_or_else(|| format!("invalid log level '{}'", level)))
+        .transpose()?;
+    db.apply_settings(settings).map_err(|e| e.to_string())?;
+    if let (Some(level), Some(handle)) = (level, LOG_LEVEL.get()) {
+        handle.modify(|filter| *filter = level).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// SIGHUP을 받으면 설정 파일을 다시 읽어 조정 가능한 설정을 적용 (나머지 옵션은 재시작해야 바뀜)
+#[cfg(unix)]
+async fn reload_on_sighup(db: Arc<CoreDB>, config_file: PathBuf, matches: ArgMatches) {
+    let mut hangup = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(e) => {
+            warn!("Failed to install SIGHUP handler: {}", e);
+            return;
+        }
+    };
+    while hangup.recv().await.is_some() {
+        info!("Reloading settings from {}", config_file.display());
+        let reloaded = reload_tunable_settings(&config_file, &matches)
+            .and_then(|(settings, log_level)| apply_runtime_settings(&db, &settings, Some(&log_level)));
+        match reloaded {
+            Ok(()) => info!("Settings reloaded: {:?}, log level {}", db.settings(), current_log_level()),
+            Err(e) => error!("Failed to reload settings, keeping current ones: {}", e),
+        }
+    }
 }
 
-async fn start_server(config: DatabaseConfig, host: String, port: u16) {
+async fn start_server(config: DatabaseConfig, host: String, port: u16, allow_anonymous: bool, tls: Option<TlsConfig>, config_file: Option<(PathBuf, ArgMatches)>) {
     info!("Starting CoreDB server on {}:{}", host, port);
     
+    // 데이터베이스를 열기 전에 인증서와 키 확인
+    let tls_acceptor = tls.map(|tls| tls.acceptor().unwrap_or_else(|e| {
+        error!("Failed to load TLS configuration: {}", e);
+        process::exit(1);
+    }));
+    
     // 데이터베이스 초기화
     let db = match CoreDB::new(config).await {
         Ok(db) => {
@@ -127,15 +1057,115 @@ async fn start_server(config: DatabaseConfig, host: String, port: u16) {
     
     // 간단한 HTTP 서버 (CQL 프로토콜 대신)
     let db_arc = Arc::new(db);
-    let app = axum::Router::new()
+    #[cfg(unix)]
+    if let Some((path, matches)) = config_file {
+        tokio::spawn(reload_on_sighup(db_arc.clone(), path, matches));
+    }
+    #[cfg(not(unix))]
+    drop(config_file);
+    if allow_anonymous {
+        warn!("Anonymous access is enabled; HTTP requests are not authenticated");
+    } else if db_arc.authenticate(coredb::DEFAULT_SUPERUSER, coredb::DEFAULT_SUPERUSER).await.is_ok() {
+        warn!("Default superuser '{}' still has password '{}'; change it with ALTER ROLE", coredb::DEFAULT_SUPERUSER, coredb::DEFAULT_SUPERUSER);
+    }
+    // 운영 작업과 전체 변경 스트림은 슈퍼유저만
+    let admin = axum::Router::new()
+        .route("/compact", axum::routing::post(compact_handler))
+        .route("/compactions/cancel", axum::routing::post(cancel_compaction_handler))
+        .route("/cdc", axum::routing::get(cdc_handler))
+        .route("/settings", axum::routing::get(settings_handler).post(update_settings_handler))
+        .route("/drain", axum::routing::post(drain_handler))
+        .route("/migrations", axum::routing::post(migrations_handler))
+        .route("/snapshots", axum::routing::get(list_snapshots_handler).post(snapshot_handler))
+        .route("/snapshots/clear", axum::routing::post(clear_snapshot_handler))
+        .route("/backups", axum::routing::post(backup_handler))
+        .route_layer(axum::middleware::from_fn(require_superuser));
+    let mut app = axum::Router::new()
         .route("/query", axum::routing::post(query_handler))
         .route("/stats", axum::routing::get(stats_handler))
-        .with_state(db_arc);
+        .route("/metrics", axum::routing::get(metrics_handler))
+        .route("/compactions", axum::routing::get(compactions_handler))
+        .route("/schema/version", axum::routing::get(schema_version_handler))
+        .merge(admin);
+    if !allow_anonymous {
+        app = app.route_layer(axum::middleware::from_fn_with_state(db_arc.clone(), require_authentication));
+    }
+    let app = app.route_layer(axum::middleware::from_fn(trace_request));
+    let clients = db_arc.clone();
+    let app = app.with_state(db_arc);
     
     let listener = tokio::net::TcpListener::bind(format!("{}:{}", host, port)).await.unwrap();
-    info!("Server listening on http://{}:{}", host, port);
-    
-    axum::serve(listener, app).await.unwrap();
+    if let Ok(address) = listener.local_addr() {
+        clients.set_rpc_address(address);
+    }
+    let scheme = if tls_acceptor.is_some() { "https" } else { "http" };
+    info!("Server listening on {}://{}:{}", scheme, host, port);
+    serve(listener, tls_acceptor, app, clients).await;
+}
+
+/// 검증된 클라이언트 인증서의 CN (인증 미들웨어가 역할 이름으로 씀)
+#[derive(Clone)]
+struct ClientCertificate(String);
+
+/// 연결마다 (TLS면 핸드셰이크 후) 라우터로 처리하고 system_views.clients에 등록 (웹소켓 업그레이드 포함)
+async fn serve(listener: tokio::net::TcpListener, acceptor: Option<tokio_rustls::TlsAcceptor>, app: axum::Router, db: std::sync::Arc<CoreDB>) {
+    loop {
+        let (stream, remote) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                // 파일 디스크립터가 모자랄 때 바로 다시 시도하지 않도록 잠시 쉼
+                warn!("Failed to accept connection: {}", e);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                continue;
+            },
+        };
+        let (acceptor, app, db) = (acceptor.clone(), app.clone(), db.clone());
+        tokio::spawn(async move {
+            let Some(acceptor) = acceptor else {
+                let session = db.clients().connect(remote, None);
+                serve_connection(stream, remote, session, None, app).await;
+                return;
+            };
+            let stream = match acceptor.accept(stream).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    warn!("TLS handshake with {} failed: {}", remote, e);
+                    return;
+                },
+            };
+            let connection = stream.get_ref().1;
+            let certificate = connection.peer_certificates()
+                .and_then(coredb::client_certificate_identity)
+                .map(ClientCertificate);
+            let protocol = connection.protocol_version().map(|version| format!("{:?}", version));
+            let session = db.clients().connect(remote, protocol);
+            serve_connection(stream, remote, session, certificate, app).await;
+        });
+    }
+}
+
+/// 연결 하나를 끝날 때까지 처리 (요청 확장에 연결 정보와 클라이언트 인증서를 넣음)
+async fn serve_connection<S>(stream: S, remote: std::net::SocketAddr, session: coredb::ClientSession, certificate: Option<ClientCertificate>, app: axum::Router)
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
+{
+    let connection = session.connection().clone();
+    let service = hyper::service::service_fn(move |mut request: hyper::Request<hyper::body::Incoming>| {
+        let user_agent = request.headers().get(axum::http::header::USER_AGENT).and_then(|value| value.to_str().ok());
+        connection.record_request(user_agent);
+        request.extensions_mut().insert(connection.clone());
+        if let Some(certificate) = certificate.clone() {
+            request.extensions_mut().insert(certificate);
+        }
+        // Router는 항상 준비되어 있어 poll_ready 없이 호출
+        tower::Service::call(&mut app.clone(), request)
+    });
+    let builder = hyper_util::server::conn::auto::Builder::new(hyper_util::rt::TokioExecutor::new());
+    if let Err(e) = builder.serve_connection_with_upgrades(hyper_util::rt::TokioIo::new(stream), service).await {
+        tracing::debug!("Connection from {} closed with error: {}", remote, e);
+    }
+    // 연결이 끝나면 클라이언트 목록에서 빠짐
+    drop(session);
 }
 
 async fn execute_query(config: DatabaseConfig, query: String) {
@@ -193,6 +1223,8 @@ async fn start_shell(config: DatabaseConfig) {
     println!("Type 'help' for available commands");
     println!();
     
+    // TRACING ON/OFF는 이 셸 세션에만 적용 (켜져 있으면 요청마다 추적)
+    let mut tracing_on = false;
     loop {
         print!("coredb> ");
         use std::io::{self, Write};
@@ -223,8 +1255,32 @@ async fn start_shell(config: DatabaseConfig) {
                     continue;
                 }
                 
-                match db.execute_cql(query).await {
-                    Ok(result) => {
+                if let Some(throughput) = query.strip_prefix("compactionthroughput") {
+                    match throughput.trim() {
+                        "" => println!("Compaction throughput: {} MB/s", db.compaction_throughput()),
+                        value => match value.parse() {
+                            Ok(mb_per_sec) => {
+                                db.set_compaction_throughput(mb_per_sec);
+                                println!("✓ Compaction throughput set to {} MB/s", mb_per_sec);
+                            },
+                            Err(_) => println!("✗ Error: invalid throughput '{}'", value),
+                        },
+                    }
+                    continue;
+                }
+                
+                if let Ok(coredb::query::parser::CqlStatement::Tracing { enabled }) = coredb::query::parser::CqlParser::parse(query) {
+                    tracing_on = enabled;
+                    println!("✓ Tracing {}", if enabled { "enabled" } else { "disabled" });
+                    continue;
+                }
+                
+                let executed = match tracing_on {
+                    true => db.execute_cql_traced(query).await,
+                    false => db.execute_cql(query).await.map(|result| (result, None)),
+                };
+                match executed {
+                    Ok((result, trace_id)) => {
                         match result {
                             coredb::query::result::QueryResult::Success => {
                                 println!("✓ Query executed successfully");
@@ -248,6 +1304,9 @@ async fn start_shell(config: DatabaseConfig) {
                                 println!("✗ Error: {}", message);
                             },
                         }
+                        if let Some(trace_id) = trace_id {
+                            println!("Tracing session: {}", trace_id);
+                        }
                     },
                     Err(e) => {
                         println!("✗ Query failed: {}", e);
@@ -275,8 +1334,62 @@ async fn show_stats(config: DatabaseConfig) {
     print_stats(&stats);
 }
 
-async fn init_database(config: DatabaseConfig) {
-    info!("Initializing CoreDB database");
+async fn show_table_stats(config: DatabaseConfig, filter: Option<String>) {
+    let db = match CoreDB::new(config).await {
+        Ok(db) => db,
+        Err(e) => {
+            error!("Failed to initialize database: {}", e);
+            process::exit(1);
+        }
+    };
+    
+    let tables: Vec<_> = db.get_stats().await.tables.into_iter()
+        .filter(|stats| filter.as_deref().is_none_or(|filter| table_matches(filter, stats)))
+        .collect();
+    if tables.is_empty() {
+        error!("No table matches {}", filter.unwrap_or_default());
+        process::exit(1);
+    }
+    let mut keyspace = None;
+    for stats in &tables {
+        if keyspace != Some(&stats.keyspace) {
+            println!("Keyspace: {}", stats.keyspace);
+            keyspace = Some(&stats.keyspace);
+        }
+        print_table_stats(stats);
+    }
+}
+
+/// keyspace 또는 keyspace.table
+fn table_matches(filter: &str, stats: &coredb::TableStats) -> bool {
+    match filter.split_once('.') {
+        Some((keyspace, table)) => stats.keyspace == keyspace && stats.table == table,
+        None => stats.keyspace == filter,
+    }
+}
+
+fn print_table_stats(stats: &coredb::TableStats) {
+    let metrics = &stats.metrics;
+    let statistics = &stats.statistics;
+    println!("  Table: {}", stats.table);
+    println!("    SSTable Count: {}", stats.sstable_count);
+    println!("    Space Used: {:.2} MB on disk, {:.2} MB in memtables",
+             stats.sstable_size_bytes as f64 / 1024.0 / 1024.0, stats.memtable_data_bytes as f64 / 1024.0 / 1024.0);
+    println!("    SSTable Rows: {} ({} cells, {} tombstones)", statistics.row_count, statistics.cell_count, statistics.tombstone_count);
+    println!("    Partition Size: p50 {} B, p99 {} B, max {} B ({} partitions)",
+             statistics.partition_sizes.percentile(0.5), statistics.partition_sizes.percentile(0.99),
+             statistics.partition_sizes.max, statistics.partition_sizes.count());
+    for (name, latency) in [("Read Latency", &metrics.read_latency), ("Write Latency", &metrics.write_latency)] {
+        println!("    {}: p50 {} us, p99 {} us, max {} us ({} operations)",
+                 name, latency.percentile(0.5), latency.percentile(0.99), latency.max_micros, latency.count());
+    }
+    for (name, counts) in [("SSTables Per Read", &metrics.sstables_per_read), ("Tombstones Per Read", &metrics.tombstones_per_read)] {
+        println!("    {}: p50 {}, p99 {}, max {}", name, counts.percentile(0.5), counts.percentile(0.99), counts.max_micros);
+    }
+}
+
+async fn init_database(config: DatabaseConfig) {
+    info!("Initializing CoreDB database");
     
     match CoreDB::new(config).await {
         Ok(db) => {
@@ -309,15 +1422,517 @@ async fn init_database(config: DatabaseConfig) {
     }
 }
 
+async fn import_sstables(config: DatabaseConfig, directory: PathBuf, schema: String) {
+    let schema = match coredb::query::CqlParser::parse(&schema).map(|statement| statement.table_schema()) {
+        Ok(Some(schema)) => schema,
+        Ok(None) => {
+            error!("--schema must be a CREATE TABLE statement");
+            process::exit(1);
+        },
+        Err(e) => {
+            error!("Failed to parse --schema: {}", e);
+            process::exit(1);
+        }
+    };
+    let (keyspace, table) = (schema.keyspace.clone(), schema.name.clone());
+    
+    let db = match CoreDB::new(config).await {
+        Ok(db) => db,
+        Err(e) => {
+            error!("Failed to initialize database: {}", e);
+            process::exit(1);
+        }
+    };
+    let result = async {
+        db.create_keyspace(keyspace.clone(), 1).await?;
+        db.create_table(keyspace.clone(), table.clone(), schema).await?;
+        let stats = db.import_cassandra_sstables(&keyspace, &table, &directory).await?;
+        db.shutdown().await?;
+        Ok::<_, coredb::CoreDBError>(stats)
+    }.await;
+    match result {
+        Ok(stats) => {
+            println!("Imported {} SSTables into {}.{}: {} partitions, {} rows", stats.sstables, keyspace, table, stats.partitions, stats.rows);
+            if stats.skipped_partition_deletions > 0 || stats.skipped_range_tombstones > 0 {
+                warn!("Skipped {} partition deletions and {} range tombstone markers", stats.skipped_partition_deletions, stats.skipped_range_tombstones);
+            }
+        },
+        Err(e) => {
+            error!("Failed to import SSTables from {}: {}", directory.display(), e);
+            process::exit(1);
+        }
+    }
+}
+
+/// 데이터 디렉토리의 테이블 디렉토리 중 keyspace, table에 맞는 것 (실패하면 종료)
+async fn find_table_directories(config: &DatabaseConfig, keyspace: Option<&str>, table: Option<&str>) -> Vec<(String, String, PathBuf)> {
+    let tables = match coredb::storage::Manifest::find_tables(&config.data_directory).await {
+        Ok(tables) => tables,
+        Err(e) => {
+            error!("Failed to list tables in {}: {}", config.data_directory.display(), e);
+            process::exit(1);
+        }
+    };
+    tables.into_iter()
+        .filter(|(ks, tbl, _)| keyspace.is_none_or(|keyspace| keyspace == ks) && table.is_none_or(|table| table == tbl))
+        .collect()
+}
+
+async fn upgrade_sstables(config: DatabaseConfig, keyspace: Option<String>, table: Option<String>, include_all: bool) {
+    let tables = find_table_directories(&config, keyspace.as_deref(), table.as_deref()).await;
+    if tables.is_empty() {
+        warn!("No tables to upgrade in {}", config.data_directory.display());
+    }
+    
+    let mut total = 0;
+    for (ks, tbl, directory) in tables {
+        match coredb::storage::Manifest::upgrade_table(&directory, include_all).await {
+            Ok(count) => {
+                println!("{}.{}: rewrote {} SSTables", ks, tbl, count);
+                total += count;
+            },
+            Err(e) => {
+                error!("Failed to upgrade SSTables of {}.{}: {}", ks, tbl, e);
+                process::exit(1);
+            }
+        }
+    }
+    println!("Upgraded {} SSTables to format version {}", total, coredb::storage::SSTABLE_FORMAT_VERSION);
+}
+
+async fn scrub_tables(config: DatabaseConfig, keyspace: Option<String>, table: Option<String>) {
+    let tables = find_table_directories(&config, keyspace.as_deref(), table.as_deref()).await;
+    if tables.is_empty() {
+        warn!("No tables to scrub in {}", config.data_directory.display());
+    }
+    
+    let mut scrubbed = 0;
+    for (ks, tbl, directory) in tables {
+        match coredb::storage::scrub_table(&directory).await {
+            Ok(reports) => {
+                for report in reports {
+                    if !report.stats.is_clean() {
+                        scrubbed += 1;
+                    }
+                    println!("{}.{}: {}", ks, tbl, report);
+                }
+            },
+            Err(e) => {
+                error!("Failed to scrub SSTables of {}.{}: {}", ks, tbl, e);
+                process::exit(1);
+            }
+        }
+    }
+    println!("Scrubbed {} corrupted SSTables", scrubbed);
+}
+
+async fn create_snapshot(config: DatabaseConfig, keyspace: String, table: Option<String>, name: String) {
+    let tables = find_table_directories(&config, Some(&keyspace), table.as_deref()).await;
+    if tables.is_empty() {
+        error!("No tables of {} in {}", table.map_or(keyspace.clone(), |table| format!("{}.{}", keyspace, table)), config.data_directory.display());
+        process::exit(1);
+    }
+    for (ks, tbl, directory) in tables {
+        match coredb::snapshot_table_directory(&directory, &ks, &tbl, &name).await {
+            Ok(info) => println!("{}.{}: snapshot {} with {} SSTables ({} bytes)", ks, tbl, name, info.sstables.len(), info.size_bytes),
+            Err(e) => {
+                error!("Failed to snapshot {}.{}: {}", ks, tbl, e);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+async fn list_snapshots(config: DatabaseConfig, keyspace: Option<String>) {
+    for (ks, tbl, directory) in find_table_directories(&config, keyspace.as_deref(), None).await {
+        let snapshots = match coredb::list_table_snapshots(&directory).await {
+            Ok(snapshots) => snapshots,
+            Err(e) => {
+                error!("Failed to list snapshots of {}.{}: {}", ks, tbl, e);
+                process::exit(1);
+            }
+        };
+        for info in snapshots {
+            let created_at = chrono::DateTime::from_timestamp_micros(info.created_at).unwrap_or_default();
+            println!("{}.{}\t{}\t{}\t{} SSTables\t{} bytes", ks, tbl, info.name, created_at.to_rfc3339(), info.sstables.len(), info.size_bytes);
+        }
+    }
+}
+
+async fn clear_snapshot(config: DatabaseConfig, keyspace: String, table: Option<String>, name: String) {
+    let mut removed = 0;
+    for (ks, tbl, directory) in find_table_directories(&config, Some(&keyspace), table.as_deref()).await {
+        match coredb::remove_table_snapshot(&directory, &name).await {
+            Ok(true) => removed += 1,
+            Ok(false) => {},
+            Err(e) => {
+                error!("Failed to remove snapshot {} of {}.{}: {}", name, ks, tbl, e);
+                process::exit(1);
+            }
+        }
+    }
+    println!("Removed snapshot {} from {} tables", name, removed);
+}
+
+/// --backup-url의 오브젝트 스토어 (없거나 열지 못하면 종료)
+fn open_backup_store(config: &DatabaseConfig) -> coredb::BackupStore {
+    let Some(url) = &config.backup_url else {
+        error!("--backup-url is required");
+        process::exit(2);
+    };
+    coredb::BackupStore::from_url(url).unwrap_or_else(|e| {
+        error!("Failed to open backup store {}: {}", url, e);
+        process::exit(1);
+    })
+}
+
+async fn upload_backup(config: DatabaseConfig, store: &coredb::BackupStore, keyspace: String, table: Option<String>, snapshot: Option<String>) {
+    let tables = find_table_directories(&config, Some(&keyspace), table.as_deref()).await;
+    if tables.is_empty() {
+        error!("No tables of {} in {}", table.map_or(keyspace.clone(), |table| format!("{}.{}", keyspace, table)), config.data_directory.display());
+        process::exit(1);
+    }
+    let temporary = snapshot.is_none();
+    let name = snapshot.unwrap_or_else(coredb::default_snapshot_name);
+    for (ks, tbl, directory) in tables {
+        let uploaded = async {
+            if temporary {
+                coredb::snapshot_table_directory(&directory, &ks, &tbl, &name).await?;
+            } else if !coredb::snapshot_directory(&directory, &name).is_dir() {
+                // 스냅샷 뒤에 만든 테이블
+                return Ok(None);
+            }
+            let backup = store.upload_snapshot(&directory, &name).await;
+            if temporary {
+                coredb::remove_table_snapshot(&directory, &name).await?;
+            }
+            backup.map(Some)
+        }.await;
+        match uploaded {
+            Ok(Some(backup)) => println!("{}.{}: backup {} with {} SSTables ({} bytes, {} uploaded)",
+                                         ks, tbl, backup.name, backup.sstables.len(), backup.size_bytes(), backup.uploaded_bytes),
+            Ok(None) => warn!("{}.{} has no snapshot {}", ks, tbl, name),
+            Err(e) => {
+                error!("Failed to back up {}.{}: {}", ks, tbl, e);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// 백업이 있는 테이블 (실패하면 종료)
+async fn backup_tables(store: &coredb::BackupStore, keyspace: Option<String>, table: Option<String>) -> Vec<(String, String)> {
+    match store.list_tables(keyspace.as_deref()).await {
+        Ok(tables) => tables.into_iter().filter(|(_, tbl)| table.as_ref().is_none_or(|table| table == tbl)).collect(),
+        Err(e) => {
+            error!("Failed to list backups: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+async fn list_backups(store: &coredb::BackupStore, keyspace: Option<String>, table: Option<String>) {
+    for (ks, tbl) in backup_tables(store, keyspace, table).await {
+        let backups = match store.list_backups(&ks, &tbl).await {
+            Ok(backups) => backups,
+            Err(e) => {
+                error!("Failed to list backups of {}.{}: {}", ks, tbl, e);
+                process::exit(1);
+            }
+        };
+        for backup in backups {
+            let created_at = chrono::DateTime::from_timestamp_micros(backup.created_at).unwrap_or_default();
+            println!("{}.{}\t{}\t{}\t{} SSTables\t{} bytes", ks, tbl, backup.name, created_at.to_rfc3339(), backup.sstables.len(), backup.size_bytes());
+        }
+    }
+}
+
+async fn prune_backups(store: &coredb::BackupStore, keyspace: Option<String>, table: Option<String>, retention: coredb::BackupRetention) {
+    let now = chrono::Utc::now().timestamp_micros();
+    for (ks, tbl) in backup_tables(store, keyspace, table).await {
+        match store.prune(&ks, &tbl, &retention, now).await {
+            Ok(removed) => println!("{}.{}: removed {} backups", ks, tbl, removed.len()),
+            Err(e) => {
+                error!("Failed to prune backups of {}.{}: {}", ks, tbl, e);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+async fn restore_backup(config: DatabaseConfig, store: &coredb::BackupStore, keyspace: String, table: String, name: String) {
+    let directory = config.data_directory.join(&keyspace).join(&table);
+    match store.restore_table(&keyspace, &table, &name, &directory).await {
+        Ok(backup) => println!("{}.{}: restored backup {} ({} SSTables, {} bytes) into {}",
+                               keyspace, table, name, backup.sstables.len(), backup.size_bytes(), directory.display()),
+        Err(e) => {
+            error!("Failed to restore {}.{} from backup {}: {}", keyspace, table, name, e);
+            process::exit(1);
+        }
+    }
+}
+
+async fn restore_to_timestamp(config: DatabaseConfig, archive_directory: Option<PathBuf>, keyspace: String, table: Option<String>,
+                              to_timestamp: i64, snapshot: Option<String>, backup: Option<String>) {
+    // 전용 커밋 로그를 쓰는 키스페이스의 세그먼트는 보관되지 않음
+    let (commitlog_directory, archive_directory) = match config.commitlog_keyspace_directories.get(&keyspace) {
+        Some(directory) => (directory.clone(), None),
+        None => (config.commitlog_directory.clone(), archive_directory),
+    };
+    if archive_directory.is_none() {
+        warn!("No commit log archive; replaying only the segments left in {}", commitlog_directory.display());
+    }
+    let restore = coredb::PointInTimeRestore {
+        archive_directory,
+        commitlog_directory,
+        encryption: config.commitlog_encryption.clone(),
+        target: to_timestamp,
+    };
+    
+    let (name, tables) = match (snapshot, backup) {
+        (Some(snapshot), _) => (snapshot, find_table_directories(&config, Some(&keyspace), table.as_deref()).await),
+        (None, Some(backup)) => {
+            // 빈 노드에도 복구할 수 있게 테이블 목록은 백업에서 가져옴
+            let store = open_backup_store(&config);
+            let mut tables = Vec::new();
+            for (ks, tbl) in backup_tables(&store, Some(keyspace.clone()), table.clone()).await {
+                let directory = config.data_directory.join(&ks).join(&tbl);
+                if !coredb::snapshot_directory(&directory, &backup).is_dir() {
+                    match store.download_snapshot(&ks, &tbl, &backup, &directory).await {
+                        Ok(_) => {},
+                        // 백업 뒤에 만든 테이블
+                        Err(coredb::CoreDBError::Backup { .. }) => {
+                            warn!("{}.{} has no backup {}", ks, tbl, backup);
+                            continue;
+                        },
+                        Err(e) => {
+                            error!("Failed to download backup {} of {}.{}: {}", backup, ks, tbl, e);
+                            process::exit(1);
+                        }
+                    }
+                }
+                tables.push((ks, tbl, directory));
+            }
+            (backup, tables)
+        },
+        (None, None) => unreachable!("clap requires --snapshot or --backup"),
+    };
+    let tables: Vec<_> = tables.into_iter()
+        .filter(|(_, _, directory)| coredb::snapshot_directory(directory, &name).is_dir())
+        .collect();
+    if tables.is_empty() {
+        error!("No tables of {} have snapshot {}", table.map_or(keyspace.clone(), |table| format!("{}.{}", keyspace, table)), name);
+        process::exit(1);
+    }
+    for (ks, tbl, directory) in tables {
+        match restore.restore_table(&directory, &ks, &tbl, &name).await {
+            Ok(restored) => println!("{}.{}: restored snapshot {} ({} SSTables) and replayed {} commit log entries{}",
+                                     ks, tbl, name, restored.snapshot_sstables, restored.replayed,
+                                     restored.previous_snapshot.map_or(String::new(), |previous| format!(", previous SSTables kept as {}", previous))),
+            Err(e) => {
+                error!("Failed to restore {}.{}: {}", ks, tbl, e);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+async fn export_keyspace(config: DatabaseConfig, keyspace: String, out: PathBuf) {
+    let db = match CoreDB::new(config).await {
+        Ok(db) => db,
+        Err(e) => {
+            error!("Failed to initialize database: {}", e);
+            process::exit(1);
+        }
+    };
+    match db.export_keyspace(&keyspace, &out).await {
+        Ok(archive) => {
+            for table in &archive.tables {
+                println!("{}.{}: {} SSTables ({} bytes)", keyspace, table.table, table.sstables.len(),
+                         table.files.iter().map(|(_, size)| size).sum::<u64>());
+            }
+            println!("Exported keyspace {} ({} schema statements, {} tables) to {}", keyspace, archive.schema.len(), archive.tables.len(), out.display());
+        },
+        Err(e) => {
+            error!("Failed to export keyspace {}: {}", keyspace, e);
+            process::exit(1);
+        }
+    }
+    if let Err(e) = db.shutdown().await {
+        error!("Failed to shut down database: {}", e);
+        process::exit(1);
+    }
+}
+
+async fn import_keyspace(config: DatabaseConfig, archive: PathBuf, keyspace: Option<String>) {
+    let db = match CoreDB::new(config).await {
+        Ok(db) => db,
+        Err(e) => {
+            error!("Failed to initialize database: {}", e);
+            process::exit(1);
+        }
+    };
+    match db.import_keyspace(&archive, keyspace.as_deref()).await {
+        Ok(imported) => {
+            for table in &imported.tables {
+                let opened = if table.schema.is_some() { "" } else { ", no schema so not opened" };
+                println!("{}.{}: {} SSTables{}", imported.keyspace, table.table, table.sstables.len(), opened);
+            }
+            println!("Imported keyspace {} ({} schema statements, {} tables) from {}", imported.keyspace, imported.schema.len(), imported.tables.len(), archive.display());
+        },
+        Err(e) => {
+            error!("Failed to import {}: {}", archive.display(), e);
+            process::exit(1);
+        }
+    }
+    if let Err(e) = db.shutdown().await {
+        error!("Failed to shut down database: {}", e);
+        process::exit(1);
+    }
+}
+
+async fn compact_table(config: DatabaseConfig, keyspace: String, table: String) {
+    let directory = config.data_directory.join(&keyspace).join(&table);
+    if !directory.is_dir() {
+        error!("Table directory {} does not exist", directory.display());
+        process::exit(1);
+    }
+    
+    let progress = Arc::new(coredb::CompactionProgress::new(&keyspace, &table, true));
+    let compaction = {
+        let progress = progress.clone();
+        tokio::spawn(async move { coredb::compact_directory(&directory, &progress).await })
+    };
+    tokio::pin!(compaction);
+    let mut ticker = tokio::time::interval(std::time::Duration::from_secs(1));
+    let result = loop {
+        tokio::select! {
+            result = &mut compaction => break result,
+            _ = ticker.tick() => {
+                let info = progress.info();
+                eprintln!("{}.{}: {}/{} partitions ({:.1}%)", keyspace, table, info.merged_partitions, info.total_partitions, info.fraction() * 100.0);
+            },
+            _ = tokio::signal::ctrl_c(), if !progress.is_cancelled() => {
+                warn!("Cancelling compaction of {}.{}", keyspace, table);
+                progress.cancel();
+            },
+        }
+    };
+    match result {
+        Ok(Ok(0)) => println!("{}.{}: nothing to compact", keyspace, table),
+        Ok(Ok(count)) => println!("{}.{}: compacted {} SSTables into one", keyspace, table, count),
+        Ok(Err(e)) => {
+            error!("Failed to compact {}.{}: {}", keyspace, table, e);
+            process::exit(1);
+        },
+        Err(e) => {
+            error!("Compaction task of {}.{} failed: {}", keyspace, table, e);
+            process::exit(1);
+        },
+    }
+}
+
+/// 데이터베이스를 열지 않고 SSTable 파일만 열기 (실패하면 종료)
+async fn open_sstable(file: &std::path::Path) -> Arc<coredb::storage::SSTable> {
+    match coredb::storage::SSTable::open(file).await {
+        Ok(sstable) => Arc::new(sstable),
+        Err(e) => {
+            error!("Failed to open SSTable {}: {}", file.display(), e);
+            process::exit(1);
+        }
+    }
+}
+
+async fn dump_sstable(file: PathBuf) {
+    let sstable = open_sstable(&file).await;
+    if let Err(e) = coredb::storage::dump_sstable(&sstable, &mut std::io::stdout().lock()).await {
+        error!("Failed to dump SSTable {}: {}", file.display(), e);
+        process::exit(1);
+    }
+}
+
+async fn dump_commit_log(path: PathBuf, encryption: Option<&CommitLogEncryption>) {
+    if let Err(e) = coredb::dump_commit_log(&path, encryption, &mut std::io::stdout().lock()).await {
+        error!("Failed to dump commit log {}: {}", path.display(), e);
+        process::exit(1);
+    }
+}
+
+async fn split_sstable(file: PathBuf, count: usize) {
+    let sstable = open_sstable(&file).await;
+    match coredb::split_sstable(&sstable, count).await {
+        Ok(outputs) => {
+            for output in &outputs {
+                println!("{} ({} partitions, {} B)", output.file_path.display(), output.partition_count, output.size_bytes);
+            }
+            println!("Split {} into {} SSTables", file.display(), outputs.len());
+        },
+        Err(e) => {
+            error!("Failed to split SSTable {}: {}", file.display(), e);
+            process::exit(1);
+        }
+    }
+}
+
+async fn show_sstable_info(file: PathBuf) {
+    let sstable = open_sstable(&file).await;
+    let component_sizes = match sstable.component_sizes().await {
+        Ok(sizes) => sizes,
+        Err(e) => {
+            error!("Failed to read SSTable components of {}: {}", file.display(), e);
+            process::exit(1);
+        }
+    };
+    
+    let timestamp = |micros: i64| chrono::DateTime::from_timestamp_micros(micros)
+        .map_or_else(|| "-".to_string(), |time| time.to_rfc3339());
+    let statistics = &sstable.statistics;
+    let compression = &sstable.compression_info;
+    let bloom = &sstable.bloom_filter;
+    println!("SSTable: {}", sstable.file_path.display());
+    println!("  ID: {}", sstable.id);
+    println!("  Format Version: {}", sstable.format_version);
+    println!("  Compression: {:?} ({} KB chunks, {} chunks, ratio {:.2})",
+             sstable.compression, compression.chunk_length / 1024, compression.chunk_offsets.len(), statistics.compression_ratio());
+    if sstable.compression == coredb::storage::CompressionType::ZSTDDictionary {
+        println!("  Compression Dictionary: {} B", compression.dictionary.len());
+    }
+    println!("  Data Size: {} B compressed, {} B uncompressed", statistics.compressed_bytes, statistics.uncompressed_bytes);
+    println!("  Partitions: {}", sstable.partition_count);
+    println!("  Rows: {} ({} cells, {} tombstones)", statistics.row_count, statistics.cell_count, statistics.tombstone_count);
+    println!("  Partition Size: p50 {} B, p99 {} B, max {} B",
+             statistics.partition_sizes.percentile(0.5), statistics.partition_sizes.percentile(0.99), statistics.partition_sizes.max);
+    println!("  Min Timestamp: {} ({})", sstable.min_timestamp, timestamp(sstable.min_timestamp));
+    println!("  Max Timestamp: {} ({})", sstable.max_timestamp, timestamp(sstable.max_timestamp));
+    match statistics.max_deletion_time {
+        i64::MAX => println!("  Max Deletion Time: none (contains live data)"),
+        seconds => println!("  Max Deletion Time: {} ({})", seconds, timestamp(seconds.saturating_mul(1_000_000))),
+    }
+    println!("  Clustering Bounds: {:?} .. {:?}",
+             sstable.clustering_bounds.min.as_ref().map(|key| &key.components),
+             sstable.clustering_bounds.max.as_ref().map(|key| &key.components));
+    println!("  Bloom Filter: {} bits, {} hashes, {} B, expected false positive rate {:.4}",
+             bloom.num_bits(), bloom.num_hashes(), bloom.size_bytes(), bloom.expected_false_positive_rate(sstable.partition_count));
+    println!("  Summary Samples: {}", sstable.summary_index.len());
+    println!("  Components:");
+    for (suffix, size) in component_sizes {
+        println!("    {}{}: {} B", sstable.id, suffix, size);
+    }
+}
+
 fn print_help() {
     println!("Available commands:");
     println!("  CREATE KEYSPACE <name> WITH REPLICATION = {{'class': 'SimpleStrategy', 'replication_factor': 1}}");
     println!("  CREATE TABLE <keyspace>.<table> (<columns>)");
+    println!("  CREATE INDEX [<name>] ON <keyspace>.<table> (<column>)");
     println!("  INSERT INTO <keyspace>.<table> (<columns>) VALUES (<values>)");
     println!("  SELECT <columns> FROM <keyspace>.<table> [WHERE <condition>] [LIMIT <n>]");
+    println!("  ALTER TABLE <keyspace>.<table> WITH compaction = {{'class': '<strategy>'}}");
     println!("  DROP TABLE <keyspace>.<table>");
     println!("  DROP KEYSPACE <name>");
+    println!("  TRACING ON | OFF  - Record each query's steps into system_traces for this shell session");
     println!("  stats  - Show database statistics");
+    println!("  compactionthroughput [<MB/s>]  - Show or change the compaction throughput limit (0 = unthrottled)");
     println!("  help   - Show this help message");
     println!("  exit   - Exit the shell");
 }
@@ -329,20 +1944,192 @@ fn print_stats(stats: &DatabaseStats) {
     println!("  Memtables: {}", stats.memtable_count);
     println!("  SSTables: {}", stats.sstable_count);
     println!("  Total Size: {:.2} MB", stats.total_size_bytes as f64 / 1024.0 / 1024.0);
+    println!("  Memtable Data: {:.2} MB (+{:.2} MB overhead)",
+             stats.memtable_data_bytes as f64 / 1024.0 / 1024.0,
+             stats.memtable_overhead_bytes as f64 / 1024.0 / 1024.0);
+    let sstables = &stats.sstable_statistics;
+    println!("  SSTable Rows: {} ({} cells, {} tombstones)", sstables.row_count, sstables.cell_count, sstables.tombstone_count);
+    println!("  Partition Size: p50 {} B, p99 {} B, max {} B",
+             sstables.partition_sizes.percentile(0.5), sstables.partition_sizes.percentile(0.99), sstables.partition_sizes.max);
+    println!("  Compression Ratio: {:.2}", sstables.compression_ratio());
+    println!("  Key Cache: {}/{} entries, {:.1}% hit rate ({} hits, {} misses)",
+             stats.key_cache.entries, stats.key_cache.capacity, stats.key_cache.hit_rate() * 100.0,
+             stats.key_cache.hits, stats.key_cache.misses);
+    println!("  Chunk Cache: {} chunks, {:.2}/{:.2} MB, {:.1}% hit rate",
+             stats.chunk_cache.chunks,
+             stats.chunk_cache.size_bytes as f64 / 1024.0 / 1024.0,
+             stats.chunk_cache.capacity_bytes as f64 / 1024.0 / 1024.0,
+             stats.chunk_cache.hit_rate() * 100.0);
+    println!("  Row Cache: {} partitions, {:.2}/{:.2} MB, {:.1}% hit rate",
+             stats.row_cache.partitions,
+             stats.row_cache.size_bytes as f64 / 1024.0 / 1024.0,
+             stats.row_cache.capacity_bytes as f64 / 1024.0 / 1024.0,
+             stats.row_cache.hit_rate() * 100.0);
+    let compaction = &stats.compaction;
+    println!("  Compactions: {}/{} active, {} pending, {} completed",
+             compaction.active_compactions, compaction.max_concurrent_compactions, compaction.pending_tasks, compaction.totals.completed);
+    println!("  Compacted: {:.2} MB read, {:.2} MB written, {} cells written, {} cells dropped",
+             compaction.totals.bytes_compacted as f64 / 1024.0 / 1024.0,
+             compaction.totals.bytes_written as f64 / 1024.0 / 1024.0,
+             compaction.totals.cells_written,
+             compaction.totals.cells_dropped);
+    for (table, pending) in &compaction.pending_by_table {
+        println!("    {}: {} pending", table, pending);
+    }
+    for info in &compaction.running {
+        println!("    {}.{}: {}{:.1}% ({:.2} MB remaining)",
+                 info.keyspace, info.table, if info.major { "major, " } else { "" },
+                 info.fraction() * 100.0, info.remaining_bytes() as f64 / 1024.0 / 1024.0);
+    }
+    for (name, admission) in [("Reads", &stats.reads), ("Writes", &stats.writes)] {
+        println!("  {}: {}/{} active, {} queued, {} admitted, {} rejected",
+                 name, admission.active, admission.max_concurrent, admission.queued, admission.admitted, admission.rejected);
+    }
+    if let Some(commit_log) = &stats.commit_log {
+        let latency = &commit_log.commit_latency;
+        println!("  Commit Latency: p50 {} us, p99 {} us, max {} us ({} commits, {} fsyncs)",
+                 latency.percentile(0.5), latency.percentile(0.99), latency.max_micros, latency.count(), commit_log.syncs);
+        println!("  Commit Log Size: {:.2} MB", commit_log.total_size_bytes as f64 / 1024.0 / 1024.0);
+    }
+    for (keyspace, commit_log) in &stats.keyspace_commit_logs {
+        println!("  Commit Log [{}]: {:.2} MB, p99 {} us ({} fsyncs)",
+                 keyspace, commit_log.total_size_bytes as f64 / 1024.0 / 1024.0, commit_log.commit_latency.percentile(0.99), commit_log.syncs);
+    }
+    for usage in &stats.disk_usage.directories {
+        println!("  Disk Usage [{}]: {:.1}% ({:.2} GB free)",
+                 usage.directory.display(), usage.space.used_percent(), usage.space.available_bytes as f64 / 1024.0 / 1024.0 / 1024.0);
+    }
+    if !stats.disk_usage.directories.is_empty() {
+        println!("  Disk Watermark: {:?} (warn {}%, reject writes {}%, {} writes rejected)",
+                 stats.disk_usage.level, stats.disk_usage.warn_percent, stats.disk_usage.fail_percent, stats.disk_usage.rejected_writes);
+    }
+    for (keyspace, quota) in &stats.quotas {
+        println!("  Quota [{}]: {} active queries, {} writes rejected, {} queries rejected",
+                 keyspace, quota.active_queries, quota.rejected_writes, quota.rejected_queries);
+    }
+    for (kind, query) in &stats.operations.queries {
+        println!("  Query [{}]: {} executed, {} failed, p99 {} us",
+                 kind, query.duration.count(), query.failures, query.duration.percentile(0.99));
+    }
+}
+
+/// 요청마다 서버 스팬 (traceparent 헤더가 있으면 호출한 서비스의 추적에 이어 붙임)
+async fn trace_request(request: axum::extract::Request, next: axum::middleware::Next) -> axum::response::Response {
+    use tracing::Instrument;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    let route = request.extensions().get::<axum::extract::MatchedPath>()
+        .map_or_else(|| request.uri().path().to_string(), |path| path.as_str().to_string());
+    let span = tracing::info_span!(
+        "http.request",
+        otel.name = format!("{} {}", request.method(), route),
+        otel.kind = "server",
+        http.request.method = %request.method(),
+        http.route = route,
+        http.response.status_code = tracing::field::Empty,
+        otel.status_code = tracing::field::Empty,
+    );
+    span.set_parent(remote_trace_context(request.headers()));
+    let response = next.run(request).instrument(span.clone()).await;
+    span.record("http.response.status_code", response.status().as_u16());
+    if response.status().is_server_error() {
+        span.record("otel.status_code", "ERROR");
+    }
+    response
+}
+
+/// W3C traceparent/tracestate 헤더의 상위 추적
+fn remote_trace_context(headers: &axum::http::HeaderMap) -> opentelemetry::Context {
+    use opentelemetry::propagation::TextMapPropagator;
+    struct Headers<'a>(&'a axum::http::HeaderMap);
+    impl opentelemetry::propagation::Extractor for Headers<'_> {
+        fn get(&self, key: &str) -> Option<&str> {
+            self.0.get(key).and_then(|value| value.to_str().ok())
+        }
+        fn keys(&self) -> Vec<&str> {
+            self.0.keys().map(|key| key.as_str()).collect()
+        }
+    }
+    opentelemetry_sdk::propagation::TraceContextPropagator::new().extract(&Headers(headers))
+}
+
+/// HTTP Basic 인증 (로그인한 역할은 요청 확장에 넣어 핸들러에서 쓸 수 있게 함)
+async fn require_authentication(
+    axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
+    mut request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+    let credentials = request.headers().get(axum::http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_basic_credentials);
+    // Basic 인증이 없으면 검증된 클라이언트 인증서로 로그인
+    let certificate = request.extensions().get::<ClientCertificate>().cloned();
+    let result = match (credentials, certificate) {
+        (Some((username, password)), _) => db.authenticate(&username, &password).await,
+        (None, Some(ClientCertificate(identity))) => db.authenticate_certificate(&identity).await,
+        (None, None) => Err(coredb::CoreDBError::AuthenticationFailed { message: "Authentication required".to_string() }),
+    };
+    match result {
+        Ok(role) => {
+            if let Some(connection) = request.extensions().get::<std::sync::Arc<coredb::ClientConnection>>() {
+                connection.set_username(&role.name);
+            }
+            request.extensions_mut().insert(role);
+            next.run(request).await
+        },
+        Err(e) => (
+            axum::http::StatusCode::UNAUTHORIZED,
+            [(axum::http::header::WWW_AUTHENTICATE, "Basic realm=\"coredb\"")],
+            axum::response::Json(serde_json::json!({"status": "error", "message": e.to_string()})),
+        ).into_response(),
+    }
+}
+
+/// 로그인한 역할이 슈퍼유저인지 확인 (익명 허용이면 역할이 없으므로 통과)
+async fn require_superuser(request: axum::extract::Request, next: axum::middleware::Next) -> axum::response::Response {
+    use axum::response::IntoResponse;
+    match request.extensions().get::<coredb::Role>() {
+        Some(role) if !role.is_superuser => (
+            axum::http::StatusCode::FORBIDDEN,
+            axum::response::Json(serde_json::json!({"status": "error", "message": format!("{} is not a superuser", role.name)})),
+        ).into_response(),
+        _ => next.run(request).await,
+    }
+}
+
+/// Authorization: Basic base64(username:password)
+fn parse_basic_credentials(header: &str) -> Option<(String, String)> {
+    use base64::Engine;
+    let (scheme, encoded) = header.trim().split_once(' ')?;
+    if !scheme.eq_ignore_ascii_case("basic") {
+        return None;
+    }
+    let decoded = base64::engine::general_purpose::STANDARD.decode(encoded.trim()).ok()?;
+    let (username, password) = std::str::from_utf8(&decoded).ok()?.split_once(':')?;
+    Some((username.to_string(), password.to_string()))
 }
 
 // HTTP 핸들러들
 async fn query_handler(
     axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
+    role: Option<axum::Extension<coredb::Role>>,
     axum::extract::Json(payload): axum::extract::Json<serde_json::Value>,
 ) -> axum::response::Json<serde_json::Value> {
     let query = payload.get("query")
         .and_then(|v| v.as_str())
         .unwrap_or("");
     
-    match db.execute_cql(query).await {
-        Ok(result) => {
-            let response = match result {
+    // 추적은 요청 단위 ("tracing": true인 요청만)
+    let traced = payload.get("tracing").and_then(|v| v.as_bool()).unwrap_or(false);
+    let executed = match (role, traced) {
+        (Some(axum::Extension(role)), true) => db.execute_cql_traced_as(&role, query).await,
+        (Some(axum::Extension(role)), false) => db.execute_cql_as(&role, query).await.map(|result| (result, None)),
+        (None, true) => db.execute_cql_traced(query).await,
+        (None, false) => db.execute_cql(query).await.map(|result| (result, None)),
+    };
+    match executed {
+        Ok((result, trace_id)) => {
+            let mut response = match result {
                 coredb::query::result::QueryResult::Success => {
                     serde_json::json!({"status": "success", "message": "Query executed successfully"})
                 },
@@ -356,6 +2143,9 @@ async fn query_handler(
                     serde_json::json!({"status": "error", "message": message})
                 },
             };
+            if let Some(trace_id) = trace_id {
+                response["trace_id"] = serde_json::json!(trace_id);
+            }
             axum::response::Json(response)
         },
         Err(e) => {
@@ -371,16 +2161,499 @@ async fn stats_handler(
     axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
 ) -> axum::response::Json<serde_json::Value> {
     let stats = db.get_stats().await;
+    // json! 한 번에 넣으면 매크로 재귀 한도를 넘음
+    let quotas = stats.quotas.iter().map(|(keyspace, quota)| (keyspace.clone(), serde_json::json!({
+        "max_disk_mb": quota.quota.max_disk_mb,
+        "max_writes_per_sec": quota.quota.max_writes_per_sec,
+        "max_concurrent_queries": quota.quota.max_concurrent_queries,
+        "active_queries": quota.active_queries,
+        "rejected_writes": quota.rejected_writes,
+        "rejected_queries": quota.rejected_queries,
+    }))).collect::<serde_json::Map<_, _>>();
     axum::response::Json(serde_json::json!({
         "keyspaces": stats.keyspace_count,
         "tables": stats.table_count,
         "memtables": stats.memtable_count,
         "sstables": stats.sstable_count,
-        "total_size_bytes": stats.total_size_bytes
+        "total_size_bytes": stats.total_size_bytes,
+        "sstable_rows": stats.sstable_statistics.row_count,
+        "sstable_cells": stats.sstable_statistics.cell_count,
+        "sstable_tombstones": stats.sstable_statistics.tombstone_count,
+        "partition_size_p50": stats.sstable_statistics.partition_sizes.percentile(0.5),
+        "partition_size_p99": stats.sstable_statistics.partition_sizes.percentile(0.99),
+        "partition_size_max": stats.sstable_statistics.partition_sizes.max,
+        "compression_ratio": stats.sstable_statistics.compression_ratio(),
+        "key_cache_entries": stats.key_cache.entries,
+        "key_cache_hit_rate": stats.key_cache.hit_rate(),
+        "chunk_cache_bytes": stats.chunk_cache.size_bytes,
+        "chunk_cache_hit_rate": stats.chunk_cache.hit_rate(),
+        "row_cache_partitions": stats.row_cache.partitions,
+        "row_cache_hit_rate": stats.row_cache.hit_rate(),
+        "compaction_pending": stats.compaction.pending_tasks,
+        "compaction_pending_by_table": stats.compaction.pending_by_table,
+        "compaction_active": stats.compaction.active_compactions,
+        "compaction_running": stats.compaction.running.iter().map(compaction_json).collect::<Vec<_>>(),
+        "compaction_completed": stats.compaction.totals.completed,
+        "compaction_bytes_compacted": stats.compaction.totals.bytes_compacted,
+        "compaction_bytes_written": stats.compaction.totals.bytes_written,
+        "compaction_cells_written": stats.compaction.totals.cells_written,
+        "compaction_cells_dropped": stats.compaction.totals.cells_dropped,
+        "commit_latency_p50_micros": stats.commit_log.as_ref().map(|commit_log| commit_log.commit_latency.percentile(0.5)),
+        "commit_latency_p99_micros": stats.commit_log.as_ref().map(|commit_log| commit_log.commit_latency.percentile(0.99)),
+        "commit_latency_max_micros": stats.commit_log.as_ref().map(|commit_log| commit_log.commit_latency.max_micros),
+        "commit_log_syncs": stats.commit_log.as_ref().map(|commit_log| commit_log.syncs),
+        "commit_log_size_bytes": stats.commit_log.as_ref().map(|commit_log| commit_log.total_size_bytes),
+        "keyspace_commit_logs": stats.keyspace_commit_logs.iter().map(|(keyspace, commit_log)| (keyspace.clone(), serde_json::json!({
+            "size_bytes": commit_log.total_size_bytes,
+            "commit_latency_p99_micros": commit_log.commit_latency.percentile(0.99),
+            "syncs": commit_log.syncs,
+        }))).collect::<serde_json::Map<_, _>>(),
+        "draining": stats.draining,
+        "disk_usage_level": format!("{:?}", stats.disk_usage.level).to_lowercase(),
+        "disk_usage_rejected_writes": stats.disk_usage.rejected_writes,
+        "disk_usage": stats.disk_usage.directories.iter().map(|usage| serde_json::json!({
+            "directory": usage.directory.display().to_string(),
+            "used_percent": usage.space.used_percent(),
+            "available_bytes": usage.space.available_bytes,
+        })).collect::<Vec<_>>(),
+        "quotas": quotas,
+        "tables": stats.tables.iter().map(table_stats_json).collect::<Vec<_>>(),
     }))
 }
 
+/// 테이블 하나의 tablestats (지연 시간은 마이크로초, 읽기당 값은 개수)
+fn table_stats_json(stats: &coredb::TableStats) -> serde_json::Value {
+    let distribution = |snapshot: &coredb::LatencySnapshot| serde_json::json!({
+        "count": snapshot.count(),
+        "p50": snapshot.percentile(0.5),
+        "p99": snapshot.percentile(0.99),
+        "max": snapshot.max_micros,
+    });
+    let partition_sizes = &stats.statistics.partition_sizes;
+    serde_json::json!({
+        "keyspace": stats.keyspace,
+        "table": stats.table,
+        "sstables": stats.sstable_count,
+        "sstable_size_bytes": stats.sstable_size_bytes,
+        "memtable_data_bytes": stats.memtable_data_bytes,
+        "rows": stats.statistics.row_count,
+        "tombstones": stats.statistics.tombstone_count,
+        "partition_size": {
+            "count": partition_sizes.count(),
+            "p50": partition_sizes.percentile(0.5),
+            "p99": partition_sizes.percentile(0.99),
+            "max": partition_sizes.max,
+        },
+        "read_latency_micros": distribution(&stats.metrics.read_latency),
+        "write_latency_micros": distribution(&stats.metrics.write_latency),
+        "sstables_per_read": distribution(&stats.metrics.sstables_per_read),
+        "tombstones_per_read": distribution(&stats.metrics.tombstones_per_read),
+    })
+}
+
+/// 요청 본문의 keyspace와 table
+fn table_of(payload: &serde_json::Value) -> Option<(String, String)> {
+    let field = |name| payload.get(name).and_then(|v| v.as_str()).map(str::to_string);
+    Some((field("keyspace")?, field("table")?))
+}
+
+/// 메이저 컴팩션 시작 (끝날 때까지 기다리지 않음, 진행 상황은 /compactions)
+async fn compact_handler(
+    axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
+    axum::extract::Json(payload): axum::extract::Json<serde_json::Value>,
+) -> axum::response::Json<serde_json::Value> {
+    let Some((keyspace, table)) = table_of(&payload) else {
+        return axum::response::Json(serde_json::json!({"status": "error", "message": "keyspace and table are required"}));
+    };
+    tokio::spawn(async move {
+        match db.major_compact(&keyspace, &table).await {
+            Ok(_) => info!("Major compaction of {}.{} finished", keyspace, table),
+            Err(e) => error!("Major compaction of {}.{} failed: {}", keyspace, table, e),
+        }
+    });
+    axum::response::Json(serde_json::json!({"status": "success", "message": "Major compaction started"}))
+}
+
+/// 쓰기를 멈추고 모든 메모리 테이블을 플러시 (업그레이드나 재시작 전, 끝날 때까지 기다림)
+async fn drain_handler(
+    axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
+) -> axum::response::Json<serde_json::Value> {
+    match db.drain().await {
+        Ok(stats) => axum::response::Json(serde_json::json!({
+            "status": "success",
+            "message": "Drained; the process can be stopped",
+            "flushed_memtables": stats.flushed_memtables,
+            "retired_segments": stats.retired_segments,
+        })),
+        Err(e) => {
+            error!("Drain failed: {}", e);
+            axum::response::Json(serde_json::json!({"status": "error", "message": e.to_string()}))
+        },
+    }
+}
+
+/// 조정 가능한 설정과 로그 수준
+async fn settings_handler(
+    axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
+) -> axum::response::Json<serde_json::Value> {
+    axum::response::Json(serde_json::json!({"status": "success", "settings": settings_json(&db)}))
+}
+
+/// 조정 가능한 설정 변경 (주어진 키만 바뀜, 재시작하면 설정 파일과 옵션 값으로 돌아감)
+async fn update_settings_handler(
+    axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
+    axum::extract::Json(mut payload): axum::extract::Json<serde_json::Value>,
+) -> axum::response::Json<serde_json::Value> {
+    let log_level = payload.as_object_mut()
+        .and_then(|fields| fields.remove("log_level"))
+        .map(|level| level.as_str().map(str::to_string).unwrap_or_else(|| level.to_string()));
+    let updated = serde_json::from_value::<TunableSettings>(payload)
+        .map_err(|e| format!("invalid settings: {}", e))
+        .and_then(|settings| apply_runtime_settings(&db, &settings, log_level.as_deref()));
+    match updated {
+        Ok(()) => {
+            info!("Settings updated: {:?}, log level {}", db.settings(), current_log_level());
+            axum::response::Json(serde_json::json!({"status": "success", "settings": settings_json(&db)}))
+        },
+        Err(message) => axum::response::Json(serde_json::json!({"status": "error", "message": message})),
+    }
+}
+
+fn settings_json(db: &CoreDB) -> serde_json::Value {
+    let mut settings = serde_json::to_value(db.settings()).unwrap_or_default();
+    settings["log_level"] = serde_json::Value::String(current_log_level());
+    settings
+}
+
+async fn compactions_handler(
+    axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
+) -> axum::response::Json<serde_json::Value> {
+    let compactions: Vec<_> = db.compactions().iter().map(compaction_json).collect();
+    axum::response::Json(serde_json::json!({"status": "success", "compactions": compactions}))
+}
+
+/// 현재 스키마 버전 (스키마 변경이 없었으면 version 0)
+/// Prometheus 수집용 지표 (텍스트 형식 0.0.4)
+async fn metrics_handler(
+    axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
+) -> impl axum::response::IntoResponse {
+    ([(axum::http::header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")], db.prometheus_metrics().await)
+}
+
+async fn schema_version_handler(
+    axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
+) -> axum::response::Json<serde_json::Value> {
+    match db.schema_version().await {
+        Ok(version) => axum::response::Json(serde_json::json!({
+            "status": "success",
+            "version": version.as_ref().map_or(0, |version| version.version),
+            "id": version.as_ref().map(|version| version.id.to_string()),
+            "statement": version.as_ref().map(|version| version.statement.clone()),
+            "applied_at": version.as_ref().map(|version| version.applied_at),
+        })),
+        Err(e) => axum::response::Json(serde_json::json!({"status": "error", "message": e.to_string()})),
+    }
+}
+
+/// 마이그레이션 적용 ({"migrations": [{"name": "001_init.cql", "script": "..."}]}, 이름 순서대로 한 번씩만)
+async fn migrations_handler(
+    axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
+    axum::extract::Json(payload): axum::extract::Json<serde_json::Value>,
+) -> axum::response::Json<serde_json::Value> {
+    let migrations: Option<Vec<coredb::Migration>> = payload.get("migrations").and_then(|v| v.as_array()).and_then(|migrations| {
+        migrations.iter()
+            .map(|migration| Some(coredb::Migration::new(migration.get("name")?.as_str()?, migration.get("script")?.as_str()?)))
+            .collect()
+    });
+    let Some(mut migrations) = migrations else {
+        return axum::response::Json(serde_json::json!({"status": "error", "message": "migrations must be a list of {name, script}"}));
+    };
+    migrations.sort_by(|a, b| a.name.cmp(&b.name));
+    match db.migrate(&migrations).await {
+        Ok(applied) => {
+            let version = db.schema_version().await.ok().flatten().map_or(0, |version| version.version);
+            axum::response::Json(serde_json::json!({"status": "success", "applied": applied, "schema_version": version}))
+        },
+        Err(e) => {
+            error!("Migration failed: {}", e);
+            axum::response::Json(serde_json::json!({"status": "error", "message": e.to_string()}))
+        },
+    }
+}
+
+fn compaction_json(info: &coredb::CompactionInfo) -> serde_json::Value {
+    serde_json::json!({
+        "keyspace": info.keyspace,
+        "table": info.table,
+        "major": info.major,
+        "merged_partitions": info.merged_partitions,
+        "total_partitions": info.total_partitions,
+        "compacted_bytes": info.compacted_bytes,
+        "remaining_bytes": info.remaining_bytes(),
+        "progress": info.fraction(),
+        "cancelled": info.cancelled,
+    })
+}
+
+async fn cancel_compaction_handler(
+    axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
+    axum::extract::Json(payload): axum::extract::Json<serde_json::Value>,
+) -> axum::response::Json<serde_json::Value> {
+    let Some((keyspace, table)) = table_of(&payload) else {
+        return axum::response::Json(serde_json::json!({"status": "error", "message": "keyspace and table are required"}));
+    };
+    if db.cancel_compaction(&keyspace, &table) {
+        axum::response::Json(serde_json::json!({"status": "success", "message": "Compaction cancelled"}))
+    } else {
+        axum::response::Json(serde_json::json!({"status": "error", "message": format!("No compaction running for {}.{}", keyspace, table)}))
+    }
+}
+
+/// 스냅샷 만들기 ({"keyspace", "table"?, "name"?}, table이 없으면 키스페이스 전체, name이 없으면 현재 시각 밀리초)
+async fn snapshot_handler(
+    axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
+    axum::extract::Json(payload): axum::extract::Json<serde_json::Value>,
+) -> axum::response::Json<serde_json::Value> {
+    let field = |name| payload.get(name).and_then(|v| v.as_str()).map(str::to_string);
+    let Some(keyspace) = field("keyspace") else {
+        return axum::response::Json(serde_json::json!({"status": "error", "message": "keyspace is required"}));
+    };
+    let name = field("name").unwrap_or_else(coredb::default_snapshot_name);
+    let result = match field("table") {
+        Some(table) => db.snapshot_table(&keyspace, &table, &name).await.map(|info| vec![info]),
+        None => db.snapshot_keyspace(&keyspace, &name).await,
+    };
+    match result {
+        Ok(snapshots) => {
+            info!("Took snapshot {} of {} ({} tables)", name, keyspace, snapshots.len());
+            axum::response::Json(serde_json::json!({"status": "success", "name": name, "snapshots": snapshots}))
+        },
+        Err(e) => {
+            error!("Snapshot {} of {} failed: {}", name, keyspace, e);
+            axum::response::Json(serde_json::json!({"status": "error", "message": e.to_string()}))
+        },
+    }
+}
+
+/// 키스페이스의 스냅샷 목록 (?keyspace=)
+async fn list_snapshots_handler(
+    axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+) -> axum::response::Json<serde_json::Value> {
+    let Some(keyspace) = params.get("keyspace") else {
+        return axum::response::Json(serde_json::json!({"status": "error", "message": "keyspace is required"}));
+    };
+    match db.list_snapshots(keyspace).await {
+        Ok(snapshots) => axum::response::Json(serde_json::json!({"status": "success", "snapshots": snapshots})),
+        Err(e) => axum::response::Json(serde_json::json!({"status": "error", "message": e.to_string()})),
+    }
+}
+
+/// 스냅샷 삭제 ({"keyspace", "table"?, "name"}, 원본 SSTable은 그대로)
+async fn clear_snapshot_handler(
+    axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
+    axum::extract::Json(payload): axum::extract::Json<serde_json::Value>,
+) -> axum::response::Json<serde_json::Value> {
+    let field = |name| payload.get(name).and_then(|v| v.as_str());
+    let (Some(keyspace), Some(name)) = (field("keyspace"), field("name")) else {
+        return axum::response::Json(serde_json::json!({"status": "error", "message": "keyspace and name are required"}));
+    };
+    match db.clear_snapshot(keyspace, field("table"), name).await {
+        Ok(removed) => axum::response::Json(serde_json::json!({"status": "success", "removed": removed})),
+        Err(e) => axum::response::Json(serde_json::json!({"status": "error", "message": e.to_string()})),
+    }
+}
+
+/// 스냅샷을 찍어 --backup-url로 올림 ({"keyspace", "table"?}, 끝날 때까지 기다림)
+async fn backup_handler(
+    axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
+    axum::extract::Json(payload): axum::extract::Json<serde_json::Value>,
+) -> axum::response::Json<serde_json::Value> {
+    let field = |name| payload.get(name).and_then(|v| v.as_str());
+    let Some(keyspace) = field("keyspace") else {
+        return axum::response::Json(serde_json::json!({"status": "error", "message": "keyspace is required"}));
+    };
+    match db.backup(keyspace, field("table")).await {
+        Ok(backups) => {
+            info!("Backed up {} tables of {}", backups.len(), keyspace);
+            axum::response::Json(serde_json::json!({"status": "success", "backups": backups.iter().map(backup_json).collect::<Vec<_>>()}))
+        },
+        Err(e) => {
+            error!("Backup of {} failed: {}", keyspace, e);
+            axum::response::Json(serde_json::json!({"status": "error", "message": e.to_string()}))
+        },
+    }
+}
+
+fn backup_json(backup: &coredb::BackupManifest) -> serde_json::Value {
+    serde_json::json!({
+        "name": backup.name,
+        "keyspace": backup.keyspace,
+        "table": backup.table,
+        "created_at": backup.created_at,
+        "sstables": backup.sstables,
+        "size_bytes": backup.size_bytes(),
+        "uploaded_bytes": backup.uploaded_bytes,
+    })
+}
+
+/// 커밋 로그 변경 스트림 (WebSocket, ?segment=&offset=으로 받은 마지막 변경의 next부터 이어 받음,
+/// ?keyspace=는 그 키스페이스가 기록되는 커밋 로그)
+async fn cdc_handler(
+    axum::extract::State(db): axum::extract::State<std::sync::Arc<CoreDB>>,
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+    ws: axum::extract::ws::WebSocketUpgrade,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+    
+    let number = |name: &str| params.get(name).map(|value| value.parse::<u64>()).transpose();
+    let (Ok(segment_id), Ok(offset)) = (number("segment"), number("offset")) else {
+        return (axum::http::StatusCode::BAD_REQUEST, "segment and offset must be numbers").into_response();
+    };
+    let from = coredb::CommitLogPosition {
+        segment_id: segment_id.unwrap_or_default(),
+        offset: offset.unwrap_or_default(),
+    };
+    let keyspace = params.get("keyspace").cloned();
+    ws.on_upgrade(move |socket| stream_changes(db, keyspace, from, socket))
+}
+
+async fn stream_changes(db: std::sync::Arc<CoreDB>, keyspace: Option<String>, from: coredb::CommitLogPosition, mut socket: axum::extract::ws::WebSocket) {
+    use axum::extract::ws::Message;
+    
+    let error_message = |e: coredb::CoreDBError| Message::Text(serde_json::json!({"status": "error", "message": e.to_string()}).to_string());
+    let mut tail = match db.commit_log_changes(keyspace.as_deref(), from).await {
+        Ok(tail) => tail,
+        Err(e) => {
+            let _ = socket.send(error_message(e)).await;
+            return;
+        }
+    };
+    loop {
+        tokio::select! {
+            change = tail.next() => {
+                let message = match change.and_then(|change| coredb::change_json(&change)) {
+                    Ok(change) => Message::Text(change.to_string()),
+                    Err(e) => {
+                        let _ = socket.send(error_message(e)).await;
+                        return;
+                    }
+                };
+                if socket.send(message).await.is_err() {
+                    return;
+                }
+            }
+            // 클라이언트가 연결을 닫으면 끝냄 (보낸 메시지는 무시)
+            message = socket.recv() => {
+                if !matches!(message, Some(Ok(message)) if !matches!(message, Message::Close(_))) {
+                    return;
+                }
+            }
+        }
+    }
+}
+
 // Cargo.toml에 필요한 의존성 추가
 // axum = "0.7"
 // tower = "0.4"
 // tower-http = "0.5"
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_basic_credentials() {
+        // "cassandra:pa:ss"
+        assert_eq!(parse_basic_credentials("Basic Y2Fzc2FuZHJhOnBhOnNz"), Some(("cassandra".to_string(), "pa:ss".to_string())));
+        assert_eq!(parse_basic_credentials("basic Y2Fzc2FuZHJhOnBhOnNz").map(|(username, _)| username), Some("cassandra".to_string()));
+        assert_eq!(parse_basic_credentials("Bearer Y2Fzc2FuZHJhOnBhOnNz"), None);
+        assert_eq!(parse_basic_credentials("Basic not base64!"), None);
+        // 콜론 없음 ("cassandra")
+        assert_eq!(parse_basic_credentials("Basic Y2Fzc2FuZHJh"), None);
+    }
+    
+    #[test]
+    fn test_remote_trace_context_reads_traceparent() {
+        use opentelemetry::trace::TraceContextExt;
+        let mut headers = axum::http::HeaderMap::new();
+        assert!(!remote_trace_context(&headers).span().span_context().is_valid());
+        headers.insert("traceparent", "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".parse().unwrap());
+        let context = remote_trace_context(&headers);
+        let parent = context.span().span_context().clone();
+        assert_eq!(parent.trace_id().to_string(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(parent.span_id().to_string(), "00f067aa0ba902b7");
+        assert!(parent.is_sampled() && parent.is_remote());
+    }
+    
+    #[test]
+    fn test_config_file_fills_options_not_given_on_command_line() {
+        let file: ConfigFile = toml::from_str(r#"
+            memtable_flush_threshold = 32
+            concurrent_reads = 7
+            commitlog_sync = "batch"
+            mmap = true
+            port = 9500
+            host = "0.0.0.0"
+            commitlog_keyspace_dir = { critical = "/mnt/fast/commitlog" }
+            keyspace_quota = { tenant = { max_disk_mb = 1024, max_concurrent_queries = 4 } }
+            otlp_endpoint = "http://collector:4318"
+            otlp_protocol = "http-protobuf"
+            snapshot_schedule = { tenant = "interval=1h,keep=24" }
+            backup_url = "s3://backups/coredb"
+        "#).unwrap();
+        let matches = Cli::command()
+            .try_get_matches_from(["coredb", "--memtable-flush-threshold", "8", "--io-uring", "start", "--port", "9042"])
+            .unwrap();
+        let mut cli = Cli::from_arg_matches(&matches).unwrap();
+        file.apply(&mut cli, &matches).unwrap();
+        
+        // 명령줄 값이 파일 값보다 우선하고, 나머지는 파일 값으로 채워짐
+        assert_eq!(cli.memtable_flush_threshold, 8);
+        assert_eq!(cli.concurrent_reads, 7);
+        assert!(matches!(cli.commitlog_sync, CommitLogSyncArg::Batch));
+        assert_eq!(cli.concurrent_writes, 32);
+        assert_eq!(cli.commitlog_keyspace_dirs, vec![("critical".to_string(), PathBuf::from("/mnt/fast/commitlog"))]);
+        assert_eq!(cli.keyspace_quotas, vec![("tenant".to_string(), KeyspaceQuota { max_disk_mb: 1024, max_writes_per_sec: 0, max_concurrent_queries: 4 })]);
+        // 명령줄의 --io-uring이 파일의 mmap을 대신함
+        assert!(cli.io_uring && !cli.mmap);
+        assert_eq!(cli.otlp_endpoint.as_deref(), Some("http://collector:4318"));
+        assert!(matches!(cli.otlp_protocol, OtlpProtocolArg::HttpProtobuf));
+        assert_eq!(cli.snapshot_schedules, vec![("tenant".to_string(), SnapshotSchedule::parse("interval=1h,keep=24").unwrap())]);
+        assert_eq!(cli.backup_url.as_deref(), Some("s3://backups/coredb"));
+        let Commands::Start { port, host } = cli.command else { panic!("expected start") };
+        assert_eq!((port, host.as_str()), (9042, "0.0.0.0"));
+        
+        // 모르는 키와 잘못된 조합은 거부
+        assert!(toml::from_str::<ConfigFile>("memtable_flush = 1").is_err());
+        let matches = Cli::command().try_get_matches_from(["coredb", "stats"]).unwrap();
+        let mut cli = Cli::from_arg_matches(&matches).unwrap();
+        let file: ConfigFile = toml::from_str(r#"commitlog_encryption_key_command = "kms decrypt""#).unwrap();
+        assert!(file.apply(&mut cli, &matches).is_err());
+        let file: ConfigFile = toml::from_str(r#"tls_cert = "server.pem""#).unwrap();
+        assert!(file.apply(&mut cli, &matches).is_err());
+        let file: ConfigFile = toml::from_str("tls_cert = \"server.pem\"\ntls_key = \"server.key\"\ntls_require_client_auth = true").unwrap();
+        assert!(file.apply(&mut cli, &matches).is_err());
+    }
+    
+    #[test]
+    fn test_reload_reads_tunable_settings_from_config_file() {
+        let path = std::env::temp_dir().join("coredb_test_reload_settings.toml");
+        std::fs::write(&path, "memtable_flush_threshold = 32\ncompaction_throughput = 4\nslow_query_threshold = 100\nlog_level = \"debug\"\n").unwrap();
+        let matches = Cli::command()
+            .try_get_matches_from(["coredb", "--compaction-throughput", "8", "start"])
+            .unwrap();
+        
+        // 파일을 고친 뒤 다시 읽으면 새 값이 적용되고, 명령줄 값은 그대로 우선
+        let (settings, log_level) = reload_tunable_settings(&path, &matches).unwrap();
+        assert_eq!(settings.memtable_flush_threshold_mb, Some(32));
+        assert_eq!(settings.compaction_throughput_mb_per_sec, Some(8));
+        assert_eq!(settings.slow_query_threshold_ms, Some(100));
+        assert_eq!(settings.memtable_max_age_secs, Some(0));
+        assert_eq!(log_level, "debug");
+        
+        std::fs::write(&path, "memtable_flush = 1\n").unwrap();
+        assert!(reload_tunable_settings(&path, &matches).is_err());
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,323 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use dashmap::DashMap;
+use crate::database::DatabaseStats;
+use crate::latency::{LatencyHistogram, LatencySnapshot};
+
+/// Prometheus 히스토그램 버킷 경계 지수 (2^k - 1 마이크로초, 15us ~ 33.5s, LatencyHistogram 버킷 경계와 맞음)
+const HISTOGRAM_BOUND_EXPONENTS: std::ops::RangeInclusive<u32> = 4..=25;
+
+/// 작업 하나의 실행 시간 분포와 실패 수
+#[derive(Debug, Default)]
+pub struct OperationMetrics {
+    duration: LatencyHistogram,
+    failures: AtomicU64,
+}
+
+/// 작업 지표의 한 시점 복사본
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OperationSnapshot {
+    /// 실패한 작업을 포함한 실행 시간 분포
+    pub duration: LatencySnapshot,
+    pub failures: u64,
+}
+
+impl OperationMetrics {
+    pub fn record(&self, elapsed: Duration, succeeded: bool) {
+        self.duration.record(elapsed);
+        if !succeeded {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn snapshot(&self) -> OperationSnapshot {
+        OperationSnapshot {
+            duration: self.duration.snapshot(),
+            failures: self.failures.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// 데이터베이스 작업 지표 (요청 경로에서 잠금 없이 기록, 나머지 지표는 /metrics를 읽을 때 통계에서 계산)
+#[derive(Debug, Default)]
+pub struct Metrics {
+    /// CQL 문 종류별 (파싱에 실패한 문은 "invalid")
+    queries: DashMap<&'static str, OperationMetrics>,
+    /// 행 API 읽기 (get_row, multiget)
+    pub reads: OperationMetrics,
+    /// 행 API 쓰기 (insert_row)
+    pub writes: OperationMetrics,
+    /// 메모리 테이블 플러시 (SSTable 쓰기)
+    pub flushes: OperationMetrics,
+    /// 플러시한 메모리 테이블의 데이터 바이트
+    pub flushed_bytes: AtomicU64,
+}
+
+/// 작업 지표의 한 시점 복사본
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    pub queries: BTreeMap<String, OperationSnapshot>,
+    pub reads: OperationSnapshot,
+    pub writes: OperationSnapshot,
+    pub flushes: OperationSnapshot,
+    pub flushed_bytes: u64,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// CQL 문 하나 (처음 보는 종류만 쓰기 잠금을 잡음)
+    pub fn record_query(&self, kind: &'static str, elapsed: Duration, succeeded: bool) {
+        match self.queries.get(kind) {
+            Some(metrics) => metrics.record(elapsed, succeeded),
+            None => self.queries.entry(kind).or_default().record(elapsed, succeeded),
+        }
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            queries: self.queries.iter().map(|entry| (entry.key().to_string(), entry.value().snapshot())).collect(),
+            reads: self.reads.snapshot(),
+            writes: self.writes.snapshot(),
+            flushes: self.flushes.snapshot(),
+            flushed_bytes: self.flushed_bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// 테이블 하나의 읽기/쓰기 지표 (tablestats)
+#[derive(Debug, Default)]
+pub struct TableMetrics {
+    /// 행 읽기 한 번 (여러 키를 한 번에 읽어도 한 번)
+    pub read_latency: LatencyHistogram,
+    /// 메모리 테이블 반영까지의 행 쓰기 (커밋 로그 fsync 대기 포함)
+    pub write_latency: LatencyHistogram,
+    /// 읽은 키마다 그 파티션이 들어 있던 SSTable 수 (행 캐시 적중은 0)
+    pub sstables_per_read: LatencyHistogram,
+    /// 읽은 키마다 병합한 툼스톤 셀 수
+    pub tombstones_per_read: LatencyHistogram,
+}
+
+/// 테이블 지표의 한 시점 복사본 (sstables_per_read, tombstones_per_read는 마이크로초가 아닌 개수)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableMetricsSnapshot {
+    pub read_latency: LatencySnapshot,
+    pub write_latency: LatencySnapshot,
+    pub sstables_per_read: LatencySnapshot,
+    pub tombstones_per_read: LatencySnapshot,
+}
+
+impl TableMetrics {
+    pub fn snapshot(&self) -> TableMetricsSnapshot {
+        TableMetricsSnapshot {
+            read_latency: self.read_latency.snapshot(),
+            write_latency: self.write_latency.snapshot(),
+            sstables_per_read: self.sstables_per_read.snapshot(),
+            tombstones_per_read: self.tombstones_per_read.snapshot(),
+        }
+    }
+}
+
+/// 이름표 (이름, 값)
+type Labels<'a> = Vec<(&'a str, &'a str)>;
+
+/// Prometheus 텍스트 형식 (0.0.4) 작성
+#[derive(Debug, Default)]
+pub struct PrometheusEncoder {
+    output: String,
+}
+
+impl PrometheusEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn counter<'a>(&mut self, name: &str, help: &str, samples: impl IntoIterator<Item = (Labels<'a>, f64)>) {
+        self.family(name, "counter", help, samples);
+    }
+
+    pub fn gauge<'a>(&mut self, name: &str, help: &str, samples: impl IntoIterator<Item = (Labels<'a>, f64)>) {
+        self.family(name, "gauge", help, samples);
+    }
+
+    /// 지연 시간 분포를 초 단위 히스토그램으로 (버킷은 누적)
+    pub fn histogram<'a>(&mut self, name: &str, help: &str, samples: impl IntoIterator<Item = (Labels<'a>, &'a LatencySnapshot)>) {
+        self.header(name, "histogram", help);
+        for (labels, snapshot) in samples {
+            for exponent in HISTOGRAM_BOUND_EXPONENTS {
+                let upper = (1u64 << exponent) - 1;
+                let le = format!("{}", upper as f64 / 1_000_000.0);
+                self.sample(&format!("{}_bucket", name), &with_label(&labels, "le", &le), snapshot.count_at_most(upper) as f64);
+            }
+            self.sample(&format!("{}_bucket", name), &with_label(&labels, "le", "+Inf"), snapshot.count() as f64);
+            self.sample(&format!("{}_sum", name), &labels, snapshot.sum_micros as f64 / 1_000_000.0);
+            self.sample(&format!("{}_count", name), &labels, snapshot.count() as f64);
+        }
+    }
+
+    pub fn finish(self) -> String {
+        self.output
+    }
+
+    fn family<'a>(&mut self, name: &str, kind: &str, help: &str, samples: impl IntoIterator<Item = (Labels<'a>, f64)>) {
+        self.header(name, kind, help);
+        for (labels, value) in samples {
+            self.sample(name, &labels, value);
+        }
+    }
+
+    fn header(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.output, "# HELP {} {}", name, help.replace('\\', "\\\\").replace('\n', "\\n"));
+        let _ = writeln!(self.output, "# TYPE {} {}", name, kind);
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        self.output.push_str(name);
+        if !labels.is_empty() {
+            let labels: Vec<String> = labels.iter()
+                .map(|(label, value)| format!("{}=\"{}\"", label, value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")))
+                .collect();
+            let _ = write!(self.output, "{{{}}}", labels.join(","));
+        }
+        let _ = writeln!(self.output, " {}", value);
+    }
+}
+
+fn with_label<'a>(labels: &[(&'a str, &'a str)], name: &'a str, value: &'a str) -> Labels<'a> {
+    let mut labels = labels.to_vec();
+    labels.push((name, value));
+    labels
+}
+
+/// 통계 전체를 Prometheus 텍스트 형식으로 (/metrics)
+pub fn render_prometheus(stats: &DatabaseStats) -> String {
+    let mut encoder = PrometheusEncoder::new();
+    let one = |value: f64| [(Vec::new(), value)];
+
+    encoder.gauge("coredb_keyspaces", "Number of keyspaces", one(stats.keyspace_count as f64));
+    encoder.gauge("coredb_tables", "Number of tables", one(stats.table_count as f64));
+    encoder.gauge("coredb_memtables", "Number of memtables, including those waiting to be flushed", one(stats.memtable_count as f64));
+    encoder.gauge("coredb_sstables", "Number of live SSTables", one(stats.sstable_count as f64));
+    encoder.gauge("coredb_storage_bytes", "Memtable allocations plus SSTable size on disk", one(stats.total_size_bytes as f64));
+    encoder.gauge("coredb_memtable_data_bytes", "Live data held in memtables", one(stats.memtable_data_bytes as f64));
+    encoder.gauge("coredb_memtable_overhead_bytes", "Memtable heap overhead beyond live data", one(stats.memtable_overhead_bytes as f64));
+    encoder.gauge("coredb_connected_clients", "Open client connections", one(stats.connected_clients as f64));
+    encoder.gauge("coredb_draining", "1 while the node is draining and rejecting writes", one(if stats.draining { 1.0 } else { 0.0 }));
+
+    // CQL 문과 행 API
+    let operations = &stats.operations;
+    encoder.histogram("coredb_cql_query_duration_seconds", "CQL statement execution time by statement kind",
+                      operations.queries.iter().map(|(kind, query)| (vec![("statement", kind.as_str())], &query.duration)));
+    encoder.counter("coredb_cql_query_failures_total", "Failed CQL statements by statement kind",
+                    operations.queries.iter().map(|(kind, query)| (vec![("statement", kind.as_str())], query.failures as f64)));
+    encoder.histogram("coredb_read_duration_seconds", "Row API read time", [(Vec::new(), &operations.reads.duration)]);
+    encoder.counter("coredb_read_failures_total", "Failed row API reads", one(operations.reads.failures as f64));
+    encoder.histogram("coredb_write_duration_seconds", "Row API write time", [(Vec::new(), &operations.writes.duration)]);
+    encoder.counter("coredb_write_failures_total", "Failed row API writes", one(operations.writes.failures as f64));
+
+    // 입장 제어
+    let admission = [("read", &stats.reads), ("write", &stats.writes)];
+    encoder.gauge("coredb_admission_active", "Operations currently executing",
+                  admission.iter().map(|(operation, stats)| (vec![("operation", *operation)], stats.active as f64)));
+    encoder.gauge("coredb_admission_queued", "Operations waiting for admission",
+                  admission.iter().map(|(operation, stats)| (vec![("operation", *operation)], stats.queued as f64)));
+    encoder.counter("coredb_admission_admitted_total", "Admitted operations",
+                    admission.iter().map(|(operation, stats)| (vec![("operation", *operation)], stats.admitted as f64)));
+    encoder.counter("coredb_admission_rejected_total", "Operations rejected because the queue was full",
+                    admission.iter().map(|(operation, stats)| (vec![("operation", *operation)], stats.rejected as f64)));
+    encoder.counter("coredb_admission_wait_seconds_total", "Total time spent waiting for admission",
+                    admission.iter().map(|(operation, stats)| (vec![("operation", *operation)], stats.total_wait_micros as f64 / 1_000_000.0)));
+
+    // 플러시와 컴팩션
+    encoder.histogram("coredb_flush_duration_seconds", "Memtable flush time", [(Vec::new(), &operations.flushes.duration)]);
+    encoder.counter("coredb_flush_failures_total", "Failed memtable flushes", one(operations.flushes.failures as f64));
+    encoder.counter("coredb_flushed_bytes_total", "Memtable data written by flushes", one(operations.flushed_bytes as f64));
+    let compaction = &stats.compaction;
+    encoder.histogram("coredb_compaction_duration_seconds", "Completed compaction time", [(Vec::new(), &compaction.duration)]);
+    encoder.gauge("coredb_compactions_pending", "Estimated compactions needed to settle all tables", one(compaction.pending_tasks as f64));
+    encoder.gauge("coredb_compactions_active", "Compactions currently running", one(compaction.active_compactions as f64));
+    encoder.counter("coredb_compaction_read_bytes_total", "Input SSTable bytes of completed compactions", one(compaction.totals.bytes_compacted as f64));
+    encoder.counter("coredb_compaction_written_bytes_total", "Output SSTable bytes of completed compactions", one(compaction.totals.bytes_written as f64));
+    encoder.counter("coredb_compaction_dropped_cells_total", "Shadowed or purged cells dropped by compactions", one(compaction.totals.cells_dropped as f64));
+
+    // 커밋 로그 (공용 로그는 commitlog="shared")
+    let commit_logs: Vec<_> = stats.commit_log.iter().map(|commit_log| ("shared", commit_log))
+        .chain(stats.keyspace_commit_logs.iter().map(|(keyspace, commit_log)| (keyspace.as_str(), commit_log)))
+        .collect();
+    encoder.histogram("coredb_commitlog_commit_duration_seconds", "Time from commit log append to acknowledgement",
+                      commit_logs.iter().map(|(name, commit_log)| (vec![("commitlog", *name)], &commit_log.commit_latency)));
+    encoder.counter("coredb_commitlog_syncs_total", "Commit log fsyncs",
+                    commit_logs.iter().map(|(name, commit_log)| (vec![("commitlog", *name)], commit_log.syncs as f64)));
+    encoder.gauge("coredb_commitlog_size_bytes", "Size of commit log segments not yet purged",
+                  commit_logs.iter().map(|(name, commit_log)| (vec![("commitlog", *name)], commit_log.total_size_bytes as f64)));
+
+    // 캐시 (키 캐시 용량은 항목 수라 바이트 지표에서 뺌)
+    let caches = [
+        ("keys", stats.key_cache.hits, stats.key_cache.misses, stats.key_cache.entries),
+        ("chunks", stats.chunk_cache.hits, stats.chunk_cache.misses, stats.chunk_cache.chunks),
+        ("rows", stats.row_cache.hits, stats.row_cache.misses, stats.row_cache.partitions),
+    ];
+    encoder.counter("coredb_cache_hits_total", "Cache hits",
+                    caches.iter().map(|(cache, hits, _, _)| (vec![("cache", *cache)], *hits as f64)));
+    encoder.counter("coredb_cache_misses_total", "Cache misses",
+                    caches.iter().map(|(cache, _, misses, _)| (vec![("cache", *cache)], *misses as f64)));
+    encoder.gauge("coredb_cache_entries", "Cached keys, chunks or partitions",
+                  caches.iter().map(|(cache, _, _, entries)| (vec![("cache", *cache)], *entries as f64)));
+    let cache_bytes = [("chunks", stats.chunk_cache.size_bytes, stats.chunk_cache.capacity_bytes), ("rows", stats.row_cache.size_bytes, stats.row_cache.capacity_bytes)];
+    encoder.gauge("coredb_cache_size_bytes", "Bytes held by the cache",
+                  cache_bytes.iter().map(|(cache, size, _)| (vec![("cache", *cache)], *size as f64)));
+    encoder.gauge("coredb_cache_capacity_bytes", "Cache capacity",
+                  cache_bytes.iter().map(|(cache, _, capacity)| (vec![("cache", *cache)], *capacity as f64)));
+    encoder.gauge("coredb_key_cache_capacity_entries", "Key cache capacity", one(stats.key_cache.capacity as f64));
+
+    // 디스크와 키스페이스 한도
+    let directories: Vec<_> = stats.disk_usage.directories.iter()
+        .map(|usage| (usage.directory.to_string_lossy().into_owned(), usage.space.used_percent() / 100.0))
+        .collect();
+    encoder.gauge("coredb_disk_used_ratio", "Used fraction of the filesystem holding each data directory",
+                  directories.iter().map(|(directory, ratio)| (vec![("directory", directory.as_str())], *ratio)));
+    encoder.counter("coredb_disk_rejected_writes_total", "Writes rejected above the disk usage watermark", one(stats.disk_usage.rejected_writes as f64));
+    encoder.gauge("coredb_quota_active_queries", "Queries running under a keyspace quota",
+                  stats.quotas.iter().map(|(keyspace, quota)| (vec![("keyspace", keyspace.as_str())], quota.active_queries as f64)));
+    encoder.counter("coredb_quota_rejected_writes_total", "Writes rejected by a keyspace quota",
+                    stats.quotas.iter().map(|(keyspace, quota)| (vec![("keyspace", keyspace.as_str())], quota.rejected_writes as f64)));
+    encoder.counter("coredb_quota_rejected_queries_total", "Queries rejected by a keyspace quota",
+                    stats.quotas.iter().map(|(keyspace, quota)| (vec![("keyspace", keyspace.as_str())], quota.rejected_queries as f64)));
+
+    encoder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prometheus_encoder_writes_text_format() {
+        let metrics = Metrics::new();
+        metrics.record_query("select", Duration::from_micros(10), true);
+        metrics.record_query("select", Duration::from_micros(100), false);
+        metrics.record_query("insert", Duration::from_secs(60), true);
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.queries["select"].failures, 1);
+        assert_eq!(snapshot.queries["select"].duration.count(), 2);
+
+        let mut encoder = PrometheusEncoder::new();
+        encoder.counter("requests_total", "Requests\nserved", [(vec![("path", "/a\"b")], 3.0)]);
+        encoder.histogram("duration_seconds", "Duration",
+                          snapshot.queries.iter().map(|(kind, query)| (vec![("statement", kind.as_str())], &query.duration)));
+        let text = encoder.finish();
+        assert!(text.starts_with("# HELP requests_total Requests\\nserved\n# TYPE requests_total counter\nrequests_total{path=\"/a\\\"b\"} 3\n"));
+        assert_eq!(text.matches("# TYPE duration_seconds histogram").count(), 1);
+        // 버킷은 누적이고 경계를 넘는 값은 +Inf에만 들어감
+        assert!(text.contains("duration_seconds_bucket{statement=\"select\",le=\"0.000015\"} 1\n"));
+        assert!(text.contains("duration_seconds_bucket{statement=\"select\",le=\"0.000127\"} 2\n"));
+        assert!(text.contains("duration_seconds_bucket{statement=\"insert\",le=\"33.554431\"} 0\n"));
+        assert!(text.contains("duration_seconds_bucket{statement=\"insert\",le=\"+Inf\"} 1\n"));
+        assert!(text.contains("duration_seconds_sum{statement=\"select\"} 0.00011\n"));
+        assert!(text.contains("duration_seconds_count{statement=\"select\"} 2\n"));
+    }
+}
@@ -0,0 +1,289 @@
+use std::path::Path;
+use uuid::Uuid;
+use crate::error::{CoreDBError, Result};
+use crate::query::system_views::SYSTEM_SCHEMA_KEYSPACE;
+use crate::schema::{CassandraDataType, CassandraValue, Cell, Cells, ColumnDefinition, PartitionKey, Row, TableSchema};
+
+/// 스키마 변경 기록 (version, id, statement, applied_at)
+pub const SCHEMA_VERSIONS_TABLE: &str = "schema_versions";
+/// 적용한 마이그레이션 (name, checksum, statements_applied, completed, schema_version, applied_at)
+pub const MIGRATIONS_TABLE: &str = "migrations";
+
+fn column(name: &str, data_type: CassandraDataType) -> ColumnDefinition {
+    ColumnDefinition { name: name.to_string(), data_type, is_static: false }
+}
+
+fn live<'a>(row: &'a Row, column: &str) -> Option<&'a CassandraValue> {
+    row.cells.get(column).filter(|cell| !cell.is_deleted).map(|cell| &cell.value)
+}
+
+/// 스키마 변경 한 건 (CQL 스키마 문이 성공할 때마다 1부터 차례로 번호를 붙임)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaVersion {
+    pub version: u64,
+    /// 변경마다 새로 만드는 식별자 (배포 도구가 두 노드나 두 시점의 스키마가 같은지 비교할 때)
+    pub id: Uuid,
+    pub statement: String,
+    /// 적용 시각 (마이크로초)
+    pub applied_at: i64,
+}
+
+impl SchemaVersion {
+    /// system_schema.schema_versions 스키마
+    pub fn table_schema() -> TableSchema {
+        TableSchema::new(
+            SCHEMA_VERSIONS_TABLE.to_string(),
+            SYSTEM_SCHEMA_KEYSPACE.to_string(),
+            vec![column("version", CassandraDataType::BigInt)],
+            vec![],
+            vec![
+                column("id", CassandraDataType::UUID),
+                column("statement", CassandraDataType::Text),
+                column("applied_at", CassandraDataType::Timestamp),
+            ],
+            vec![],
+        )
+    }
+
+    pub fn partition_key(version: u64) -> PartitionKey {
+        PartitionKey { components: vec![CassandraValue::BigInt(version as i64)] }
+    }
+
+    pub fn from_row(version: u64, row: &Row) -> Option<Self> {
+        let (Some(CassandraValue::UUID(id)), Some(CassandraValue::Text(statement)), Some(CassandraValue::Timestamp(applied_at))) =
+            (live(row, "id"), live(row, "statement"), live(row, "applied_at")) else { return None };
+        Some(Self { version, id: *id, statement: statement.clone(), applied_at: *applied_at })
+    }
+
+    pub fn to_row(&self) -> Row {
+        let cell = |value| Cell { value, timestamp: self.applied_at, ttl: None, is_deleted: false };
+        let mut cells = Cells::new();
+        cells.insert("id", cell(CassandraValue::UUID(self.id)));
+        cells.insert("statement", cell(CassandraValue::Text(self.statement.clone())));
+        cells.insert("applied_at", cell(CassandraValue::Timestamp(self.applied_at)));
+        Row { partition_key: Self::partition_key(self.version), clustering_key: None, cells, timestamp: self.applied_at }
+    }
+}
+
+/// CQL 마이그레이션 (이름 순서대로 한 번씩만 적용)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Migration {
+    /// 적용 기록의 키 (디렉토리에서 읽으면 파일 이름, 예: 001_create_users.cql)
+    pub name: String,
+    /// ';'로 구분한 CQL 문 ('--'와 '//' 주석 사용 가능)
+    pub script: String,
+}
+
+impl Migration {
+    pub fn new(name: impl Into<String>, script: impl Into<String>) -> Self {
+        Self { name: name.into(), script: script.into() }
+    }
+
+    /// 디렉토리의 *.cql 파일을 이름 순서대로 읽음
+    pub async fn load_directory(directory: &Path) -> Result<Vec<Self>> {
+        let mut migrations = Vec::new();
+        let mut entries = tokio::fs::read_dir(directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|extension| extension == "cql") {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                migrations.push(Self::new(name, tokio::fs::read_to_string(&path).await?));
+            }
+        }
+        migrations.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(migrations)
+    }
+
+    /// 실행할 문 목록 (주석과 빈 문 제외)
+    pub fn statements(&self) -> Vec<String> {
+        split_statements(&self.script)
+    }
+
+    /// 적용한 뒤 파일이 바뀌었는지 확인하는 체크섬 (문 목록 기준이라 주석과 공백 변경은 무시)
+    pub fn checksum(&self) -> String {
+        statements_checksum(&self.statements())
+    }
+}
+
+fn statements_checksum(statements: &[String]) -> String {
+    let checksum = statements.iter()
+        .fold(0, |crc, statement| crc32c::crc32c_append(crc32c::crc32c_append(crc, statement.as_bytes()), b";"));
+    format!("{:08x}", checksum)
+}
+
+/// 마이그레이션 적용 상태 (문마다 진행 상황을 남겨 중간에 실패하면 다음 실행이 실패한 문부터 이어서 적용)
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppliedMigration {
+    pub name: String,
+    /// 적용한 문들의 체크섬 (끝나지 않았으면 실패한 문과 그 뒤의 문은 고칠 수 있음)
+    pub checksum: String,
+    pub statements_applied: usize,
+    pub completed: bool,
+    /// 모든 문을 적용한 뒤의 스키마 버전 (끝나지 않았으면 0)
+    pub schema_version: u64,
+    /// 마지막으로 기록한 시각 (마이크로초)
+    pub applied_at: i64,
+}
+
+impl AppliedMigration {
+    pub fn new(migration: &Migration) -> Self {
+        Self {
+            name: migration.name.clone(),
+            checksum: statements_checksum(&[]),
+            statements_applied: 0,
+            completed: false,
+            schema_version: 0,
+            applied_at: 0,
+        }
+    }
+
+    /// system_schema.migrations 스키마
+    pub fn table_schema() -> TableSchema {
+        TableSchema::new(
+            MIGRATIONS_TABLE.to_string(),
+            SYSTEM_SCHEMA_KEYSPACE.to_string(),
+            vec![column("name", CassandraDataType::Text)],
+            vec![],
+            vec![
+                column("checksum", CassandraDataType::Text),
+                column("statements_applied", CassandraDataType::Int),
+                column("completed", CassandraDataType::Boolean),
+                column("schema_version", CassandraDataType::BigInt),
+                column("applied_at", CassandraDataType::Timestamp),
+            ],
+            vec![],
+        )
+    }
+
+    pub fn partition_key(name: &str) -> PartitionKey {
+        PartitionKey { components: vec![CassandraValue::Text(name.to_string())] }
+    }
+
+    pub fn from_row(name: &str, row: &Row) -> Option<Self> {
+        let Some(CassandraValue::Text(checksum)) = live(row, "checksum") else { return None };
+        Some(Self {
+            name: name.to_string(),
+            checksum: checksum.clone(),
+            statements_applied: match live(row, "statements_applied") {
+                Some(CassandraValue::Int(count)) => *count as usize,
+                _ => 0,
+            },
+            completed: matches!(live(row, "completed"), Some(CassandraValue::Boolean(true))),
+            schema_version: match live(row, "schema_version") {
+                Some(CassandraValue::BigInt(version)) => *version as u64,
+                _ => 0,
+            },
+            applied_at: match live(row, "applied_at") {
+                Some(CassandraValue::Timestamp(applied_at)) => *applied_at,
+                _ => 0,
+            },
+        })
+    }
+
+    pub fn to_row(&self) -> Row {
+        let cell = |value| Cell { value, timestamp: self.applied_at, ttl: None, is_deleted: false };
+        let mut cells = Cells::new();
+        cells.insert("checksum", cell(CassandraValue::Text(self.checksum.clone())));
+        cells.insert("statements_applied", cell(CassandraValue::Int(self.statements_applied as i32)));
+        cells.insert("completed", cell(CassandraValue::Boolean(self.completed)));
+        cells.insert("schema_version", cell(CassandraValue::BigInt(self.schema_version as i64)));
+        cells.insert("applied_at", cell(CassandraValue::Timestamp(self.applied_at)));
+        Row { partition_key: Self::partition_key(&self.name), clustering_key: None, cells, timestamp: self.applied_at }
+    }
+
+    /// 문 하나를 적용한 뒤 진행 상황 갱신
+    pub fn record_statement(&mut self, statements: &[String], applied_at: i64) {
+        self.statements_applied += 1;
+        self.checksum = statements_checksum(&statements[..self.statements_applied]);
+        self.applied_at = applied_at;
+    }
+
+    /// 이미 적용한 문이 바뀌었거나 끝난 마이그레이션에 문이 늘었으면 오류
+    pub fn check_unchanged(&self, migration: &Migration) -> Result<()> {
+        let statements = migration.statements();
+        let changed = statements.len() < self.statements_applied
+            || (self.completed && statements.len() != self.statements_applied)
+            || statements_checksum(&statements[..self.statements_applied]) != self.checksum;
+        if changed {
+            return Err(CoreDBError::MigrationFailed {
+                name: self.name.clone(),
+                message: format!("changed after it was applied (first {} statements no longer match)", self.statements_applied),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// CQL 스크립트를 문 단위로 나눔 (문자열 안의 ';'와 주석 기호는 그대로 둠)
+pub fn split_statements(script: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut chars = script.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        match c {
+            // '' 이스케이프는 닫고 다시 여는 것과 같아 따로 처리하지 않음
+            '\'' => {
+                in_string = !in_string;
+                current.push(c);
+            },
+            '-' | '/' if !in_string && chars.peek() == Some(&c) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        current.push(c);
+                        break;
+                    }
+                }
+            },
+            ';' if !in_string => statements.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    statements.push(current);
+    statements.into_iter()
+        .map(|statement| statement.trim().to_string())
+        .filter(|statement| !statement.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_statements_and_checksum() {
+        let migration = Migration::new("001_init.cql", "
+            -- users
+            CREATE KEYSPACE app WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1};
+            CREATE TABLE app.notes (id INT PRIMARY KEY, body TEXT); // notes
+            INSERT INTO app.notes (id, body) VALUES (1, 'a;b -- it''s');
+            ;
+        ");
+        assert_eq!(migration.statements(), vec![
+            "CREATE KEYSPACE app WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}".to_string(),
+            "CREATE TABLE app.notes (id INT PRIMARY KEY, body TEXT)".to_string(),
+            "INSERT INTO app.notes (id, body) VALUES (1, 'a;b -- it''s')".to_string(),
+        ]);
+
+        // 주석과 공백만 바뀌면 체크섬이 같고, 문이 바뀌면 다름
+        let reformatted = Migration::new("001_init.cql", migration.statements().join(";\n"));
+        assert_eq!(reformatted.checksum(), migration.checksum());
+        let changed = Migration::new("001_init.cql", migration.script.replace("TEXT", "VARCHAR"));
+        assert_ne!(changed.checksum(), migration.checksum());
+
+        // 적용한 문이 바뀌면 거부하고, 아직 적용하지 않은 문은 고칠 수 있음
+        let mut applied = AppliedMigration::new(&migration);
+        applied.record_statement(&migration.statements(), 7);
+        applied.record_statement(&migration.statements(), 8);
+        assert!(applied.check_unchanged(&changed).is_err());
+        let fixed = Migration::new("001_init.cql", migration.script.replace("'a;b", "'c;d"));
+        assert!(applied.check_unchanged(&fixed).is_ok());
+        applied.record_statement(&fixed.statements(), 9);
+        applied.completed = true;
+        assert!(applied.check_unchanged(&fixed).is_ok());
+        assert!(applied.check_unchanged(&migration).is_err());
+        let extended = Migration::new("001_init.cql", format!("{};DROP TABLE app.notes", fixed.script));
+        assert!(applied.check_unchanged(&extended).is_err());
+        assert_eq!(AppliedMigration::from_row("001_init.cql", &applied.to_row()), Some(applied));
+    }
+}
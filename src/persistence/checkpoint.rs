@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::error::*;
+use crate::persistence::Snapshot;
+use crate::schema::{KeyspaceDefinition, TableSchema};
+use crate::wal::CommitLogPosition;
+
+/// 체크포인트가 테이블마다 남기는 스냅샷 이름 앞부분 (뒤는 만든 시각, 밀리초)
+pub const CHECKPOINT_SNAPSHOT_PREFIX: &str = "checkpoint-";
+
+/// 전체 데이터베이스 체크포인트 (data_directory의 스냅샷 파일에 JSON으로 기록)
+///
+/// 테이블 데이터는 같은 이름의 테이블 스냅샷(하드 링크)에 있고, 이 매니페스트는 그 스냅샷을 다시 여는 데 필요한
+/// 스키마와 SSTable 목록, 만들 때의 커밋 로그 위치를 담음
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// 테이블 스냅샷 이름
+    pub name: String,
+    /// 만든 시각 (마이크로초, 이 시각까지의 쓰기가 체크포인트에 들어감)
+    pub created_at: i64,
+    /// 만들 때의 스키마 버전 (CQL 스키마 문을 실행한 적이 없으면 0)
+    pub schema_version: u64,
+    /// 저장 계층 키스페이스 (이름 순)
+    pub keyspaces: Vec<KeyspaceDefinition>,
+    /// 저장 계층 테이블 (키스페이스, 테이블 순)
+    pub tables: Vec<CheckpointTable>,
+    /// 공용 커밋 로그의 끝 위치
+    pub commit_log_position: Option<CommitLogPosition>,
+    /// 전용 커밋 로그를 쓰는 키스페이스별 끝 위치
+    pub keyspace_commit_log_positions: BTreeMap<String, CommitLogPosition>,
+}
+
+/// 체크포인트에 들어간 테이블
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointTable {
+    pub keyspace: String,
+    pub table: String,
+    pub schema: TableSchema,
+    /// 스냅샷에 든 SSTable id
+    pub sstables: Vec<String>,
+}
+
+impl Checkpoint {
+    /// data_directory의 스냅샷 파일에 기록 (이전 체크포인트는 이전 세대로 남음)
+    pub fn save(&self, data_directory: &Path) -> Result<()> {
+        Snapshot::new(data_directory.to_string_lossy().to_string()).save_bytes(&serde_json::to_vec(self)?)
+    }
+
+    /// 마지막 체크포인트 (현재 스냅샷을 읽지 못하면 가장 최근의 온전한 이전 세대)
+    pub fn load(data_directory: &Path) -> Result<Self> {
+        let data = Snapshot::new(data_directory.to_string_lossy().to_string()).load_bytes()?;
+        serde_json::from_slice(&data)
+            .map_err(|e| CoreDBError::Snapshot { message: format!("Snapshot in {} is not a checkpoint: {}", data_directory.display(), e) })
+    }
+
+    /// 남아 있는 모든 세대의 체크포인트 이름 (읽지 못하는 세대는 건너뜀)
+    pub fn retained_names(data_directory: &Path) -> Vec<String> {
+        let snapshot = Snapshot::new(data_directory.to_string_lossy().to_string());
+        (0..)
+            .map(|generation| snapshot.generation_path(generation))
+            .take_while(|path| path.exists())
+            .filter_map(|path| Snapshot::read_generation(&path).ok())
+            .filter_map(|data| serde_json::from_slice::<Checkpoint>(&data).ok())
+            .map(|checkpoint| checkpoint.name)
+            .collect()
+    }
+}
@@ -1,4 +1,5 @@
 pub mod snapshot;
+pub mod checkpoint;
 
-pub use snapshot::{Snapshot, SnapshotFormat};
-
+pub use snapshot::{Snapshot, SnapshotCodec, SnapshotFormat, DEFAULT_SNAPSHOT_GENERATIONS, SNAPSHOT_FORMAT_VERSION};
+pub use checkpoint::{Checkpoint, CheckpointTable, CHECKPOINT_SNAPSHOT_PREFIX};
@@ -1,9 +1,23 @@
-use std::collections::HashMap;
 use std::fs::{File, OpenOptions, create_dir_all};
-use std::io::{Write, Read, BufReader, BufWriter};
-use std::path::Path;
+use std::io::{Write, Read, BufReader};
+use std::path::{Path, PathBuf};
 use crate::error::*;
 
+/// 스냅샷 파일 이름 (이전 세대는 뒤에 .1, .2, ...)
+pub const SNAPSHOT_FILE: &str = "db_snapshot.txt";
+
+/// 현재 스냅샷 형식 버전 (첫 줄 헤더에 기록)
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 2;
+/// 헤더에 압축 방식을 기록하기 시작한 형식 버전 (그 전은 압축하지 않은 텍스트)
+pub const SNAPSHOT_CODEC_VERSION: u32 = 2;
+/// 읽을 수 있는 가장 오래된 형식 버전
+pub const MIN_SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// 기본으로 남기는 이전 세대 수
+pub const DEFAULT_SNAPSHOT_GENERATIONS: usize = 3;
+
+const SNAPSHOT_HEADER_PREFIX: &str = "# CoreDB snapshot format ";
+
 /// 스냅샷 형식
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SnapshotFormat {
@@ -11,55 +25,183 @@ pub enum SnapshotFormat {
     Binary, // 바이너리 (빠르고 작음)
 }
 
+/// 스냅샷 본문 압축 방식 (헤더에 기록해 읽을 때 따름)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SnapshotCodec {
+    /// 압축하지 않음 (사람이 읽을 수 있음)
+    None,
+    #[default]
+    Zstd,
+}
+
+impl SnapshotCodec {
+    pub fn name(self) -> &'static str {
+        match self {
+            SnapshotCodec::None => "none",
+            SnapshotCodec::Zstd => "zstd",
+        }
+    }
+    
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(SnapshotCodec::None),
+            "zstd" => Some(SnapshotCodec::Zstd),
+            _ => None,
+        }
+    }
+    
+    fn encode(self, data: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            SnapshotCodec::None => Ok(data.to_vec()),
+            SnapshotCodec::Zstd => Ok(zstd::stream::encode_all(data, 3)?),
+        }
+    }
+    
+    fn decode(self, data: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            SnapshotCodec::None => Ok(data.to_vec()),
+            SnapshotCodec::Zstd => Ok(zstd::stream::decode_all(data)?),
+        }
+    }
+}
+
 /// 스냅샷 관리자
 pub struct Snapshot {
     data_directory: String,
+    /// 남기는 이전 세대 수
+    generations: usize,
+    /// 저장할 때 쓰는 압축 방식
+    codec: SnapshotCodec,
 }
 
 impl Snapshot {
     pub fn new(data_directory: String) -> Self {
         create_dir_all(&data_directory).expect("Failed to create data directory");
-        Self { data_directory }
+        Self { data_directory, generations: DEFAULT_SNAPSHOT_GENERATIONS, codec: SnapshotCodec::default() }
+    }
+    
+    /// 저장할 때 쓰는 압축 방식 (읽을 때는 헤더를 따름)
+    pub fn with_codec(mut self, codec: SnapshotCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+    
+    /// 이전 세대를 generations개까지 남김 (0이면 남기지 않음)
+    pub fn with_generations(mut self, generations: usize) -> Self {
+        self.generations = generations;
+        self
+    }
+    
+    /// 세대의 파일 경로 (0이 현재 스냅샷)
+    pub fn generation_path(&self, generation: usize) -> PathBuf {
+        let path = Path::new(&self.data_directory).join(SNAPSHOT_FILE);
+        match generation {
+            0 => path,
+            generation => path.with_extension(format!("txt.{}", generation)),
+        }
     }
     
     /// 데이터를 텍스트 파일로 저장
     pub fn save_text(&self, data: &str) -> Result<()> {
-        let file_path = format!("{}/db_snapshot.txt", self.data_directory);
-        
-        let mut file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(&file_path)?;
+        self.save_bytes(data.as_bytes())
+    }
+    
+    /// 데이터를 압축 방식대로 저장
+    ///
+    /// 임시 파일에 쓰고 rename으로 교체해 쓰는 중에 멈춰도 이전 스냅샷이 온전히 남음.
+    /// 교체 전에 지금 스냅샷을 .1로, .1을 .2로 밀어 이전 세대를 남김
+    pub fn save_bytes(&self, data: &[u8]) -> Result<()> {
+        let encoded = self.codec.encode(data)?;
+        let current = self.generation_path(0);
+        let temp_path = current.with_extension("txt.tmp");
+        {
+            let mut file = OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&temp_path)?;
+            writeln!(file, "{}{} codec {}", SNAPSHOT_HEADER_PREFIX, SNAPSHOT_FORMAT_VERSION, self.codec.name())?;
+            file.write_all(&encoded)?;
+            file.sync_all()?;
+        }
         
-        writeln!(file, "# CoreDB Persistent Database")?;
-        writeln!(file, "# Auto-generated snapshot")?;
-        writeln!(file, "")?;
-        writeln!(file, "{}", data)?;
+        if self.generations > 0 && current.exists() {
+            for generation in (1..self.generations).rev() {
+                let path = self.generation_path(generation);
+                if path.exists() {
+                    std::fs::rename(&path, self.generation_path(generation + 1))?;
+                }
+            }
+            // 하드 링크라 교체 중에 멈춰도 현재 스냅샷은 그대로 있음
+            let previous = self.generation_path(1);
+            std::fs::remove_file(&previous).ok();
+            std::fs::hard_link(&current, &previous)?;
+        }
+        std::fs::rename(&temp_path, &current)?;
         
         Ok(())
     }
     
     /// 텍스트 파일에서 데이터 로드
     pub fn load_text(&self) -> Result<String> {
-        let file_path = format!("{}/db_snapshot.txt", self.data_directory);
-        
-        if !Path::new(&file_path).exists() {
+        String::from_utf8(self.load_bytes()?)
+            .map_err(|e| CoreDBError::Snapshot { message: format!("Snapshot is not valid UTF-8: {}", e) })
+    }
+    
+    /// 압축을 푼 데이터 로드
+    ///
+    /// 현재 스냅샷을 읽지 못하면 경고를 남기고 가장 최근의 온전한 이전 세대를 읽음
+    pub fn load_bytes(&self) -> Result<Vec<u8>> {
+        let current = self.generation_path(0);
+        if !current.exists() {
             return Err(CoreDBError::Generic {
                 message: "No snapshot file found".to_string(),
             });
         }
         
-        let mut file = File::open(&file_path)?;
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
-        
-        // 헤더 라인 제거
-        let lines: Vec<&str> = contents.lines()
-            .filter(|line| !line.starts_with('#') && !line.trim().is_empty())
-            .collect();
+        let error = match Self::read_generation(&current) {
+            Ok(data) => return Ok(data),
+            Err(e) => e,
+        };
+        for generation in 1..=self.generations {
+            let path = self.generation_path(generation);
+            if !path.exists() {
+                continue;
+            }
+            match Self::read_generation(&path) {
+                Ok(data) => {
+                    tracing::warn!("Loaded previous snapshot {} because {} is unreadable: {}", path.display(), current.display(), error);
+                    return Ok(data);
+                },
+                Err(e) => tracing::warn!("Skipping unreadable snapshot {}: {}", path.display(), e),
+            }
+        }
+        Err(error)
+    }
+    
+    /// 한 세대를 읽어 형식 버전 헤더를 확인하고 압축을 풂
+    pub fn read_generation(path: &Path) -> Result<Vec<u8>> {
+        let mut contents = Vec::new();
+        File::open(path)?.read_to_end(&mut contents)?;
         
-        Ok(lines.join("\n"))
+        let invalid = |message: String| CoreDBError::Snapshot { message: format!("{} {}", path.display(), message) };
+        let header_end = contents.iter().position(|&byte| byte == b'\n').unwrap_or(contents.len());
+        let header = std::str::from_utf8(&contents[..header_end]).ok()
+            .and_then(|header| header.strip_prefix(SNAPSHOT_HEADER_PREFIX))
+            .ok_or_else(|| invalid("has no snapshot format header".to_string()))?;
+        let mut fields = header.split_whitespace();
+        let version = fields.next().and_then(|version| version.parse::<u32>().ok())
+            .ok_or_else(|| invalid("has no snapshot format header".to_string()))?;
+        if !(MIN_SNAPSHOT_FORMAT_VERSION..=SNAPSHOT_FORMAT_VERSION).contains(&version) {
+            return Err(invalid(format!("has unsupported snapshot format version {} (expected {} to {})",
+                                       version, MIN_SNAPSHOT_FORMAT_VERSION, SNAPSHOT_FORMAT_VERSION)));
+        }
+        let codec = match (fields.next(), fields.next()) {
+            (None, _) if version < SNAPSHOT_CODEC_VERSION => SnapshotCodec::None,
+            (Some("codec"), Some(name)) => SnapshotCodec::from_name(name).ok_or_else(|| invalid(format!("uses unknown codec {}", name)))?,
+            _ => return Err(invalid("has no codec in its snapshot header".to_string())),
+        };
+        codec.decode(contents.get(header_end + 1..).unwrap_or_default())
     }
     
     /// WAL에 작업 기록
@@ -130,6 +272,59 @@ mod tests {
         std::fs::remove_dir_all("./test_data").ok();
     }
     
+    #[test]
+    fn test_snapshot_generations_and_format_header() {
+        let test_dir = std::env::temp_dir().join("coredb_test_snapshot_generations");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let snapshot = Snapshot::new(test_dir.to_string_lossy().to_string()).with_generations(2);
+        
+        // 세 번 저장하면 현재와 이전 두 세대만 남음
+        for data in ["first", "second", "third"] {
+            snapshot.save_text(data).unwrap();
+        }
+        assert_eq!(snapshot.load_text().unwrap(), "third");
+        assert_eq!(Snapshot::read_generation(&snapshot.generation_path(1)).unwrap(), b"second");
+        assert_eq!(Snapshot::read_generation(&snapshot.generation_path(2)).unwrap(), b"first");
+        assert!(!snapshot.generation_path(3).exists());
+        assert!(!snapshot.generation_path(0).with_extension("txt.tmp").exists());
+        
+        // 헤더가 없거나 버전이 다르면 거부하고, 읽을 때는 이전 세대로 넘어감
+        std::fs::write(snapshot.generation_path(0), "# CoreDB Persistent Database\nthird").unwrap();
+        assert!(matches!(Snapshot::read_generation(&snapshot.generation_path(0)), Err(CoreDBError::Snapshot { .. })));
+        std::fs::write(snapshot.generation_path(1), format!("{}99\nsecond", SNAPSHOT_HEADER_PREFIX)).unwrap();
+        assert!(matches!(Snapshot::read_generation(&snapshot.generation_path(1)), Err(CoreDBError::Snapshot { .. })));
+        assert_eq!(snapshot.load_text().unwrap(), "first");
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
+    #[test]
+    fn test_snapshot_codec_recorded_in_header() {
+        let test_dir = std::env::temp_dir().join("coredb_test_snapshot_codec");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        let data = "KEYSPACE:demo\nTABLE:users\n".repeat(1000);
+        
+        // 기본은 zstd, 읽을 때는 저장한 쪽 설정과 관계없이 헤더의 압축 방식을 따름
+        let snapshot = Snapshot::new(test_dir.to_string_lossy().to_string());
+        snapshot.save_text(&data).unwrap();
+        let path = snapshot.generation_path(0);
+        assert!(std::fs::read(&path).unwrap().starts_with(format!("{}{} codec zstd\n", SNAPSHOT_HEADER_PREFIX, SNAPSHOT_FORMAT_VERSION).as_bytes()));
+        assert!(std::fs::metadata(&path).unwrap().len() < data.len() as u64 / 10);
+        let plain = Snapshot::new(test_dir.to_string_lossy().to_string()).with_codec(SnapshotCodec::None);
+        assert_eq!(plain.load_text().unwrap(), data);
+        plain.save_text(&data).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().ends_with(&data));
+        assert_eq!(snapshot.load_text().unwrap(), data);
+        
+        // 압축 방식이 없던 형식 1도 읽고, 모르는 압축 방식은 거부
+        std::fs::write(&path, format!("{}1\n{}", SNAPSHOT_HEADER_PREFIX, data)).unwrap();
+        assert_eq!(Snapshot::read_generation(&path).unwrap(), data.as_bytes());
+        std::fs::write(&path, format!("{}2 codec brotli\n{}", SNAPSHOT_HEADER_PREFIX, data)).unwrap();
+        assert!(matches!(Snapshot::read_generation(&path), Err(CoreDBError::Snapshot { .. })));
+        
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+    
     #[test]
     fn test_wal_operations() {
         let snapshot = Snapshot::new("./test_wal".to_string());
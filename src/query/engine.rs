@@ -1,22 +1,510 @@
-use crate::schema::{TableSchema, PartitionKey, ClusteringKey, CassandraValue, Row as SchemaRow, Cell};
-use crate::storage::{Memtable, SSTable};
-use crate::query::{CqlStatement, QueryResult, Row as QueryRow};
+use crate::schema::{TableSchema, PartitionKey, ClusteringKey, CassandraValue, CassandraDataType, ColumnDefinition, CompactionStrategy, KeyspaceDefinition, ReplicationStrategy, Row as SchemaRow, Cell, Cells};
+use crate::storage::{Memtable, SSTable, SecondaryIndex, VectorIndex, IndexKind, CompressionType, ReadOptions, ScanDirection, ClusteringSlice, tokenize, cosine_distance};
+use crate::query::{CqlStatement, QueryResult, PagingState, QueryTrace, Trigger, RegisteredTrigger, Row as QueryRow};
+use crate::query::tracing::{TRACES_KEYSPACE, SESSIONS_TABLE, EVENTS_TABLE};
+use crate::query::system_views::{SYSTEM_VIEWS_KEYSPACE, LARGE_PARTITIONS_TABLE, SYSTEM_SCHEMA_KEYSPACE, KEYSPACES_TABLE, TABLES_TABLE, COLUMNS_TABLE, INDEXES_TABLE, TRIGGERS_TABLE, DEFAULT_LARGE_PARTITION_THRESHOLD_BYTES, VirtualRow, VirtualTable, cql_type, partition_key_text, replication_map, text_map_value, virtual_table};
+use crate::storage::PartitionSize;
+use crate::query::parser::{ComparisonOperator, Condition, OrderBy, WhereClause, build_table_schema};
+use crate::geo::BoundingBox;
+use crate::wal::Mutation;
 use crate::error::*;
-use std::sync::Arc;
-use std::collections::{HashMap, BTreeMap};
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+use std::collections::{HashMap, HashSet, BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::ops::Bound;
 
 /// 쿼리 엔진
 pub struct QueryEngine {
+    /// 키스페이스 정의 (system_schema.keyspaces로도 조회 가능)
+    keyspaces: HashMap<String, KeyspaceDefinition>,
     memtables: HashMap<String, HashMap<String, Arc<Memtable>>>,
+    /// SSTable로 쓰이는 중인 고정된 메모리 테이블 (오래된 것부터, SSTable이 붙을 때까지 읽기에 포함)
+    flushing: HashMap<String, HashMap<String, Vec<Arc<Memtable>>>>,
     sstables: HashMap<String, HashMap<String, Vec<Arc<SSTable>>>>,
+    indexes: HashMap<String, HashMap<String, TableIndexes>>,
+    /// 테이블별 쓰기 트리거 (등록 순서대로 실행)
+    triggers: HashMap<String, HashMap<String, Vec<RegisteredTrigger>>>,
+    /// 큰 파티션 경고 임계값 (바이트)
+    large_partition_threshold_bytes: u64,
+    /// 이미 경고한 큰 파티션 (같은 파티션은 한 번만 로그)
+    warned_large_partitions: HashSet<(String, String, PartitionKey)>,
+    /// 테이블 디렉토리의 상위 디렉토리 (전문 인덱스를 디스크에 둘 곳, 없으면 메모리에만 유지)
+    data_directory: Option<PathBuf>,
+}
+
+/// 테이블의 보조 인덱스와 VECTOR 컬럼 ANN 인덱스
+///
+/// 쓰기는 엔진 읽기 잠금으로 동시에 실행되므로 인덱스는 잠금 안에 두고, 변경 전 행을 읽을 때부터
+/// 인덱스를 갱신할 때까지 같은 테이블의 쓰기를 writes로 직렬화함 (다른 테이블의 쓰기는 동시에 실행)
+#[derive(Default)]
+struct TableIndexes {
+    writes: tokio::sync::Mutex<()>,
+    secondary: RwLock<Vec<SecondaryIndex>>,
+    vector: RwLock<Vec<VectorIndex>>,
+}
+
+impl TableIndexes {
+    fn is_empty(&self) -> bool {
+        self.secondary.read().unwrap().is_empty() && self.vector.read().unwrap().is_empty()
+    }
+}
+
+/// SELECT 실행 계획
+enum ReadPlan {
+    /// 파티션 키 일치 → 해당 파티션만 조회
+    Partition(PartitionKey, Option<ClusteringKey>),
+    /// 보조 인덱스 조회로 얻은 행 키 (키 순서)
+    Index(Vec<(PartitionKey, Option<ClusteringKey>)>),
+    /// 전체 스캔
+    FullScan,
+}
+
+impl Default for QueryEngine {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl QueryEngine {
     pub fn new() -> Self {
         Self {
+            keyspaces: HashMap::new(),
             memtables: HashMap::new(),
+            flushing: HashMap::new(),
             sstables: HashMap::new(),
+            indexes: HashMap::new(),
+            triggers: HashMap::new(),
+            large_partition_threshold_bytes: DEFAULT_LARGE_PARTITION_THRESHOLD_BYTES,
+            warned_large_partitions: HashSet::new(),
+            data_directory: None,
+        }
+    }
+    
+    /// 키스페이스 정의
+    pub fn keyspace_definition(&self, keyspace: &str) -> Option<&KeyspaceDefinition> {
+        self.keyspaces.get(keyspace)
+    }
+    
+    pub fn has_table(&self, keyspace: &str, table: &str) -> bool {
+        self.memtables.get(keyspace).is_some_and(|tables| tables.contains_key(table))
+    }
+    
+    /// 키스페이스 데이터 크기 (메모리 테이블의 유효 데이터와 SSTable 파일 크기 합)
+    pub fn keyspace_size_bytes(&self, keyspace: &str) -> u64 {
+        let flushing = self.flushing.get(keyspace).into_iter().flat_map(|tables| tables.values().flatten());
+        let memtables: u64 = self.memtables.get(keyspace).into_iter()
+            .flat_map(|tables| tables.values())
+            .chain(flushing)
+            .map(|memtable| memtable.memory().data_bytes)
+            .sum();
+        let sstables: u64 = self.sstables.get(keyspace).into_iter()
+            .flat_map(|tables| tables.values().flatten())
+            .map(|sstable| sstable.size_bytes)
+            .sum();
+        memtables + sstables
+    }
+    
+    /// 키스페이스의 데이터 변경을 커밋 로그에 남기는지 (모르는 키스페이스는 남김)
+    pub fn durable_writes(&self, keyspace: &str) -> bool {
+        self.keyspaces.get(keyspace).is_none_or(|definition| definition.durable_writes)
+    }
+    
+    /// 큰 파티션 경고 임계값 설정 (바이트)
+    pub fn set_large_partition_threshold(&mut self, threshold_bytes: u64) {
+        self.large_partition_threshold_bytes = threshold_bytes;
+    }
+    
+    pub fn large_partition_threshold(&self) -> u64 {
+        self.large_partition_threshold_bytes
+    }
+    
+    /// 데이터 디렉토리 설정 (전문 인덱스를 <data>/<keyspace>/<table>/fulltext/에 유지)
+    pub fn set_data_directory(&mut self, data_directory: PathBuf) {
+        self.data_directory = Some(data_directory);
+    }
+    
+    /// 임계값을 넘은 파티션을 경고하고 system_views.large_partitions에 기록
+    ///
+    /// 경고 로그는 파티션마다 처음 한 번만 남기고, 테이블의 크기는 매번 갱신함
+    pub async fn record_large_partition(&mut self, keyspace: &str, table: &str, partition_key: &PartitionKey, size: PartitionSize) -> Result<()> {
+        if size.bytes <= self.large_partition_threshold_bytes || keyspace == SYSTEM_VIEWS_KEYSPACE {
+            return Ok(());
+        }
+        
+        let key_text = partition_key_text(partition_key);
+        if self.warned_large_partitions.insert((keyspace.to_string(), table.to_string(), partition_key.clone())) {
+            tracing::warn!(
+                "Large partition {}.{} ({}) is {} bytes with {} rows, exceeding the {} byte threshold",
+                keyspace, table, key_text, size.bytes, size.rows, self.large_partition_threshold_bytes,
+            );
+        }
+        
+        self.ensure_system_view_tables().await?;
+        let memtable = self.get_memtable(SYSTEM_VIEWS_KEYSPACE, LARGE_PARTITIONS_TABLE)?;
+        let now = chrono::Utc::now().timestamp_micros();
+        // CQL 쓰기처럼 키 컬럼도 셀로 함께 저장
+        let cells: Cells = [
+            ("keyspace_name", CassandraValue::Text(keyspace.to_string())),
+            ("table_name", CassandraValue::Text(table.to_string())),
+            ("partition_key", CassandraValue::Text(key_text.clone())),
+            ("partition_size", CassandraValue::BigInt(size.bytes as i64)),
+            ("rows", CassandraValue::BigInt(size.rows as i64)),
+            ("detected_at", CassandraValue::Timestamp(now)),
+        ].into_iter()
+            .map(|(column, value)| (column.to_string(), Cell { value, timestamp: now, ttl: None, is_deleted: false }))
+            .collect();
+        memtable.put(SchemaRow {
+            partition_key: PartitionKey { components: vec![
+                CassandraValue::Text(keyspace.to_string()),
+                CassandraValue::Text(table.to_string()),
+            ] },
+            clustering_key: Some(ClusteringKey { components: vec![CassandraValue::Text(key_text)] }),
+            cells,
+            timestamp: now,
+        })
+    }
+    
+    /// system_views 키스페이스와 large_partitions 테이블 생성 (없을 때만)
+    async fn ensure_system_view_tables(&mut self) -> Result<()> {
+        if self.get_memtable(SYSTEM_VIEWS_KEYSPACE, LARGE_PARTITIONS_TABLE).is_ok() {
+            return Ok(());
+        }
+        
+        let column = |name: &str, data_type: CassandraDataType| ColumnDefinition {
+            name: name.to_string(),
+            data_type,
+            is_static: false,
+        };
+        self.create_keyspace(SYSTEM_VIEWS_KEYSPACE.to_string(), crate::query::parser::KeyspaceOptions {
+            replication_factor: 1,
+            strategy: "SimpleStrategy".to_string(),
+            durable_writes: true,
+        }).await?;
+        self.create_table(SYSTEM_VIEWS_KEYSPACE.to_string(), LARGE_PARTITIONS_TABLE.to_string(), vec![
+            column("keyspace_name", CassandraDataType::Text),
+            column("table_name", CassandraDataType::Text),
+            column("partition_key", CassandraDataType::Text),
+            column("partition_size", CassandraDataType::BigInt),
+            column("rows", CassandraDataType::BigInt),
+            column("detected_at", CassandraDataType::Timestamp),
+        ], vec!["keyspace_name".to_string(), "table_name".to_string()], vec!["partition_key".to_string()], crate::query::parser::TableOptions {
+            compaction_strategy: None,
+            bloom_filter_fp_chance: 0.01,
+            default_time_to_live: None,
+            memtable_flush_threshold_mb: None,
+            compression: None,
+        }).await?;
+        
+        Ok(())
+    }
+    
+    /// 가상 테이블을 주어진 행으로 다시 채움 (처음이면 키스페이스와 테이블을 만듦)
+    pub async fn refresh_virtual_table(&mut self, table: &VirtualTable, rows: Vec<VirtualRow>) -> Result<()> {
+        if self.get_memtable(table.keyspace, table.name).is_err() {
+            self.create_keyspace(table.keyspace.to_string(), crate::query::parser::KeyspaceOptions {
+                replication_factor: 1,
+                strategy: "SimpleStrategy".to_string(),
+                durable_writes: true,
+            }).await?;
+            let columns = table.columns.iter()
+                .map(|(name, data_type)| ColumnDefinition { name: name.to_string(), data_type: data_type.clone(), is_static: false })
+                .collect();
+            let key = |columns: &[&str]| columns.iter().map(|column| column.to_string()).collect();
+            self.create_table(table.keyspace.to_string(), table.name.to_string(), columns, key(table.partition_key), key(table.clustering_key), crate::query::parser::TableOptions {
+                compaction_strategy: None,
+                bloom_filter_fp_chance: 0.01,
+                default_time_to_live: None,
+                memtable_flush_threshold_mb: None,
+                compression: None,
+            }).await?;
+        }
+        
+        let schema = self.get_memtable(table.keyspace, table.name)?.table_schema().clone();
+        let memtable = Memtable::new(schema);
+        let now = chrono::Utc::now().timestamp_micros();
+        for row in rows {
+            let key = |columns: &[&str]| columns.iter()
+                .map(|column| row.iter().find(|(name, _)| name == column).map_or(CassandraValue::Null, |(_, value)| value.clone()))
+                .collect::<Vec<_>>();
+            let partition_key = PartitionKey { components: key(table.partition_key) };
+            let clustering_key = (!table.clustering_key.is_empty()).then(|| ClusteringKey { components: key(table.clustering_key) });
+            // 값이 없는 컬럼(Null)은 쓰지 않음
+            let cells: Cells = row.into_iter()
+                .filter(|(_, value)| *value != CassandraValue::Null)
+                .map(|(column, value)| (column.to_string(), Cell { value, timestamp: now, ttl: None, is_deleted: false }))
+                .collect();
+            memtable.put(SchemaRow { partition_key, clustering_key, cells, timestamp: now })?;
+        }
+        self.replace_memtable(table.keyspace.to_string(), table.name.to_string(), Arc::new(memtable));
+        Ok(())
+    }
+    
+    /// system_schema.keyspaces를 현재 키스페이스 정의로 다시 채움 (키스페이스 수가 적어 통째로 다시 만듦)
+    async fn refresh_keyspace_schema(&mut self) -> Result<()> {
+        if self.get_memtable(SYSTEM_SCHEMA_KEYSPACE, KEYSPACES_TABLE).is_err() {
+            // create_keyspace를 거치면 다시 이 함수로 돌아오므로 직접 등록
+            self.keyspaces.entry(SYSTEM_SCHEMA_KEYSPACE.to_string()).or_insert_with(|| KeyspaceDefinition {
+                name: SYSTEM_SCHEMA_KEYSPACE.to_string(),
+                replication_factor: 1,
+                strategy: ReplicationStrategy::SimpleStrategy,
+                durable_writes: true,
+            });
+            self.memtables.entry(SYSTEM_SCHEMA_KEYSPACE.to_string()).or_default();
+            self.sstables.entry(SYSTEM_SCHEMA_KEYSPACE.to_string()).or_default();
+            let column = |name: &str, data_type: CassandraDataType| ColumnDefinition {
+                name: name.to_string(),
+                data_type,
+                is_static: false,
+            };
+            self.create_table(SYSTEM_SCHEMA_KEYSPACE.to_string(), KEYSPACES_TABLE.to_string(), vec![
+                column("keyspace_name", CassandraDataType::Text),
+                column("durable_writes", CassandraDataType::Boolean),
+                column("replication", CassandraDataType::Map(Box::new(CassandraDataType::Text), Box::new(CassandraDataType::Text))),
+            ], vec!["keyspace_name".to_string()], Vec::new(), crate::query::parser::TableOptions {
+                compaction_strategy: None,
+                bloom_filter_fp_chance: 0.01,
+                default_time_to_live: None,
+                memtable_flush_threshold_mb: None,
+                compression: None,
+            }).await?;
+        }
+        
+        let schema = self.get_memtable(SYSTEM_SCHEMA_KEYSPACE, KEYSPACES_TABLE)?.table_schema().clone();
+        let memtable = Memtable::new(schema);
+        let now = chrono::Utc::now().timestamp_micros();
+        for definition in self.keyspaces.values() {
+            let cells: Cells = [
+                ("keyspace_name", CassandraValue::Text(definition.name.clone())),
+                ("durable_writes", CassandraValue::Boolean(definition.durable_writes)),
+                ("replication", replication_map(definition)),
+            ].into_iter()
+                .map(|(column, value)| (column.to_string(), Cell { value, timestamp: now, ttl: None, is_deleted: false }))
+                .collect();
+            memtable.put(SchemaRow {
+                partition_key: PartitionKey { components: vec![CassandraValue::Text(definition.name.clone())] },
+                clustering_key: None,
+                cells,
+                timestamp: now,
+            })?;
+        }
+        self.replace_memtable(SYSTEM_SCHEMA_KEYSPACE.to_string(), KEYSPACES_TABLE.to_string(), Arc::new(memtable));
+        Ok(())
+    }
+    
+    /// system_schema의 테이블, 컬럼, 인덱스, 트리거 가상 테이블 행 (가상 테이블 자체는 넣지 않음)
+    pub fn schema_table_rows(&self, name: &str) -> Vec<VirtualRow> {
+        let text = |value: &str| CassandraValue::Text(value.to_string());
+        let mut schemas: Vec<_> = self.memtables.values()
+            .flat_map(|tables| tables.values())
+            .map(|memtable| memtable.table_schema().clone())
+            .filter(|schema| virtual_table(&schema.keyspace, &schema.name).is_none())
+            .collect();
+        schemas.sort_by(|a, b| (&a.keyspace, &a.name).cmp(&(&b.keyspace, &b.name)));
+        
+        let mut rows = Vec::new();
+        for schema in schemas {
+            let (keyspace, table) = (schema.keyspace.as_str(), schema.name.as_str());
+            match name {
+                TABLES_TABLE => {
+                    let options = &schema.options;
+                    let compaction = match options.compaction_strategy {
+                        CompactionStrategy::SizeTiered => vec![("class", "org.apache.cassandra.db.compaction.SizeTieredCompactionStrategy".to_string())],
+                        CompactionStrategy::Leveled => vec![("class", "org.apache.cassandra.db.compaction.LeveledCompactionStrategy".to_string())],
+                        CompactionStrategy::TimeWindow { window_seconds } => {
+                            let (unit, seconds) = match window_seconds {
+                                w if w % (24 * 60 * 60) == 0 => ("DAYS", 24 * 60 * 60),
+                                w if w % (60 * 60) == 0 => ("HOURS", 60 * 60),
+                                _ => ("MINUTES", 60),
+                            };
+                            vec![
+                                ("class", "org.apache.cassandra.db.compaction.TimeWindowCompactionStrategy".to_string()),
+                                ("compaction_window_size", (window_seconds / seconds).max(1).to_string()),
+                                ("compaction_window_unit", unit.to_string()),
+                            ]
+                        },
+                    };
+                    let compression = match options.compression {
+                        CompressionType::None => vec![("enabled", "false".to_string())],
+                        CompressionType::LZ4 => vec![("class", "org.apache.cassandra.io.compress.LZ4Compressor".to_string())],
+                        CompressionType::Snappy => vec![("class", "org.apache.cassandra.io.compress.SnappyCompressor".to_string())],
+                        CompressionType::ZSTD => vec![("class", "org.apache.cassandra.io.compress.ZstdCompressor".to_string())],
+                        CompressionType::ZSTDDictionary => vec![("class", "ZstdDictionaryCompressor".to_string())],
+                    };
+                    rows.push(vec![
+                        ("keyspace_name", text(keyspace)),
+                        ("table_name", text(table)),
+                        ("bloom_filter_fp_chance", CassandraValue::Double(options.bloom_filter_fp_chance)),
+                        ("caching", text_map_value([("keys", "ALL".to_string()), ("rows_per_partition", "NONE".to_string())])),
+                        ("comment", text("")),
+                        ("compaction", text_map_value(compaction)),
+                        ("compression", text_map_value(compression)),
+                        ("default_time_to_live", CassandraValue::Int(options.default_time_to_live.unwrap_or(0) as i32)),
+                        ("extensions", CassandraValue::Map(HashMap::new())),
+                        ("flags", CassandraValue::Set(vec![text("compound")])),
+                        ("gc_grace_seconds", CassandraValue::Int(options.gc_grace_seconds as i32)),
+                    ]);
+                },
+                COLUMNS_TABLE => {
+                    let keys = schema.partition_key.iter().enumerate().map(|(position, column)| (column, "partition_key", position as i32))
+                        .chain(schema.clustering_key.iter().enumerate().map(|(position, column)| (column, "clustering", position as i32)));
+                    // 일반 컬럼의 위치는 Cassandra처럼 -1
+                    let others = schema.static_columns.iter().map(|column| (column, "static", -1))
+                        .chain(schema.regular_columns.iter().map(|column| (column, "regular", -1)));
+                    for (column, kind, position) in keys.chain(others) {
+                        rows.push(vec![
+                            ("keyspace_name", text(keyspace)),
+                            ("table_name", text(table)),
+                            ("column_name", text(&column.name)),
+                            ("clustering_order", text(if kind == "clustering" { "asc" } else { "none" })),
+                            ("column_name_bytes", CassandraValue::Blob(column.name.as_bytes().to_vec())),
+                            ("kind", text(kind)),
+                            ("position", CassandraValue::Int(position)),
+                            ("type", text(&cql_type(&column.data_type))),
+                        ]);
+                    }
+                },
+                INDEXES_TABLE => {
+                    let mut listed = Vec::new();
+                    if let Some(indexes) = self.table_indexes(keyspace, table) {
+                        listed.extend(indexes.secondary.read().unwrap().iter()
+                            .map(|index| (index.name.clone(), index.column.clone(), match index.kind {
+                                IndexKind::Standard => None,
+                                IndexKind::FullText => Some("fulltext"),
+                                IndexKind::Vector => Some("hnsw"),
+                            })));
+                        listed.extend(indexes.vector.read().unwrap().iter()
+                            .map(|index| (index.name.clone(), index.column.clone(), Some("hnsw"))));
+                    }
+                    for (index, column, class) in listed {
+                        let mut options = vec![("target", column)];
+                        options.extend(class.map(|class| ("class_name", class.to_string())));
+                        rows.push(vec![
+                            ("keyspace_name", text(keyspace)),
+                            ("table_name", text(table)),
+                            ("index_name", text(&index)),
+                            ("kind", text(if class.is_some() { "CUSTOM" } else { "COMPOSITES" })),
+                            ("options", text_map_value(options)),
+                        ]);
+                    }
+                },
+                TRIGGERS_TABLE => {
+                    for trigger in self.triggers.get(keyspace).and_then(|tables| tables.get(table)).into_iter().flatten() {
+                        rows.push(vec![
+                            ("keyspace_name", text(keyspace)),
+                            ("table_name", text(table)),
+                            ("trigger_name", text(&trigger.name)),
+                            ("options", CassandraValue::Map(HashMap::new())),
+                        ]);
+                    }
+                },
+                _ => {},
+            }
+        }
+        rows
+    }
+    
+    /// 테이블에 쓰기 트리거 등록
+    pub fn register_trigger(&mut self, keyspace: &str, table: &str, name: &str, trigger: Arc<dyn Trigger>) -> Result<()> {
+        self.get_memtable(keyspace, table)?;
+        
+        let table_triggers = self.triggers
+            .entry(keyspace.to_string())
+            .or_default()
+            .entry(table.to_string())
+            .or_default();
+        if table_triggers.iter().any(|t| t.name == name) {
+            return Err(CoreDBError::InvalidSchema {
+                message: format!("Trigger {} already exists on {}.{}", name, keyspace, table),
+            });
         }
+        
+        table_triggers.push(RegisteredTrigger { name: name.to_string(), trigger });
+        Ok(())
+    }
+    
+    /// 트리거 제거 (제거되었으면 true)
+    pub fn drop_trigger(&mut self, keyspace: &str, table: &str, name: &str) -> bool {
+        match self.triggers.get_mut(keyspace).and_then(|tables| tables.get_mut(table)) {
+            Some(table_triggers) => {
+                let before = table_triggers.len();
+                table_triggers.retain(|t| t.name != name);
+                table_triggers.len() != before
+            },
+            None => false,
+        }
+    }
+    
+    /// 실행 중인 요청을 추적 중이면 단계 기록 (QueryTrace::scope 안에서 실행한 요청만)
+    fn trace(&self, activity: impl FnOnce() -> String) {
+        QueryTrace::record_current(activity);
+    }
+    
+    /// 끝난 요청의 추적을 system_traces에 기록하고 세션 ID 반환
+    pub async fn finish_trace(&mut self, mut trace: QueryTrace) -> Result<Uuid> {
+        trace.record("Request complete");
+        
+        self.ensure_trace_tables().await?;
+        
+        let session_id = CassandraValue::UUID(trace.session_id);
+        self.insert_row(TRACES_KEYSPACE.to_string(), SESSIONS_TABLE.to_string(), vec![
+            ("session_id".to_string(), session_id.clone()),
+            ("request".to_string(), CassandraValue::Text(trace.request.clone())),
+            ("started_at".to_string(), CassandraValue::Timestamp(trace.started_at)),
+            ("duration".to_string(), CassandraValue::BigInt(trace.elapsed_micros())),
+        ]).await?;
+        
+        for (event_id, event) in trace.events.iter().enumerate() {
+            self.insert_row(TRACES_KEYSPACE.to_string(), EVENTS_TABLE.to_string(), vec![
+                ("session_id".to_string(), session_id.clone()),
+                ("event_id".to_string(), CassandraValue::Int(event_id as i32)),
+                ("activity".to_string(), CassandraValue::Text(event.activity.clone())),
+                ("source_elapsed".to_string(), CassandraValue::BigInt(event.source_elapsed)),
+            ]).await?;
+        }
+        
+        Ok(trace.session_id)
+    }
+    
+    /// system_traces 키스페이스와 sessions / events 테이블 생성 (없을 때만)
+    async fn ensure_trace_tables(&mut self) -> Result<()> {
+        if self.get_memtable(TRACES_KEYSPACE, EVENTS_TABLE).is_ok() {
+            return Ok(());
+        }
+        
+        let column = |name: &str, data_type: CassandraDataType| ColumnDefinition {
+            name: name.to_string(),
+            data_type,
+            is_static: false,
+        };
+        let options = || crate::query::parser::TableOptions {
+            compaction_strategy: None,
+            bloom_filter_fp_chance: 0.01,
+            default_time_to_live: None,
+            memtable_flush_threshold_mb: None,
+            compression: None,
+        };
+        
+        self.create_keyspace(TRACES_KEYSPACE.to_string(), crate::query::parser::KeyspaceOptions {
+            replication_factor: 1,
+            strategy: "SimpleStrategy".to_string(),
+            durable_writes: true,
+        }).await?;
+        self.create_table(TRACES_KEYSPACE.to_string(), SESSIONS_TABLE.to_string(), vec![
+            column("session_id", CassandraDataType::UUID),
+            column("request", CassandraDataType::Text),
+            column("started_at", CassandraDataType::Timestamp),
+            column("duration", CassandraDataType::BigInt),
+        ], vec!["session_id".to_string()], Vec::new(), options()).await?;
+        // 이벤트 순서는 세션 내 일련번호로 유지
+        self.create_table(TRACES_KEYSPACE.to_string(), EVENTS_TABLE.to_string(), vec![
+            column("session_id", CassandraDataType::UUID),
+            column("event_id", CassandraDataType::Int),
+            column("activity", CassandraDataType::Text),
+            column("source_elapsed", CassandraDataType::BigInt),
+        ], vec!["session_id".to_string()], vec!["event_id".to_string()], options()).await?;
+        
+        Ok(())
     }
     
     /// CQL 문 실행
@@ -28,11 +516,14 @@ impl QueryEngine {
             CqlStatement::CreateTable { keyspace, name, columns, partition_key, clustering_key, options } => {
                 self.create_table(keyspace, name, columns, partition_key, clustering_key, options).await
             },
+            CqlStatement::CreateIndex { keyspace, table, name, column, kind } => {
+                self.create_index(keyspace, table, name, column, kind).await
+            },
             CqlStatement::Insert { keyspace, table, values } => {
                 self.insert_row(keyspace, table, values).await
             },
-            CqlStatement::Select { keyspace, table, columns, where_clause, limit } => {
-                self.select_rows(keyspace, table, columns, where_clause, limit).await
+            statement @ CqlStatement::Select { .. } => {
+                self.select(statement, None).await.map(|(result, _)| result)
             },
             CqlStatement::Update { keyspace, table, values, where_clause } => {
                 self.update_row(keyspace, table, values, where_clause).await
@@ -40,6 +531,10 @@ impl QueryEngine {
             CqlStatement::Delete { keyspace, table, where_clause } => {
                 self.delete_row(keyspace, table, where_clause).await
             },
+            CqlStatement::AlterTable { keyspace, name, compaction_strategy } => {
+                self.set_compaction_strategy(&keyspace, &name, compaction_strategy)?;
+                Ok(QueryResult::success())
+            },
             CqlStatement::DropTable { keyspace, name } => {
                 self.drop_table(keyspace, name).await
             },
@@ -49,45 +544,47 @@ impl QueryEngine {
             CqlStatement::Use { keyspace } => {
                 self.use_keyspace(keyspace).await
             },
+            // 추적 여부는 요청마다 호출한 쪽이 정함 (셸은 세션 설정으로 처리)
+            CqlStatement::Tracing { .. } => Err(CoreDBError::InvalidSchema {
+                message: "TRACING is a client session setting; trace individual requests instead".to_string(),
+            }),
+            // 역할과 권한은 CoreDB가 system_auth 키스페이스에 저장
+            CqlStatement::CreateRole { .. } | CqlStatement::AlterRole { .. } | CqlStatement::DropRole { .. }
+            | CqlStatement::Grant { .. } | CqlStatement::Revoke { .. } => Err(CoreDBError::InvalidSchema {
+                message: "Role and permission statements are executed by the database, not the query engine".to_string(),
+            }),
         }
     }
     
-    async fn create_keyspace(&mut self, name: String, _options: crate::query::parser::KeyspaceOptions) -> Result<QueryResult> {
-        // 키스페이스 생성 (단순화된 버전)
+    /// 페이지 단위 CQL 실행 (SELECT 외의 문과 ANN 정렬은 일반 실행과 동일)
+    pub async fn execute_paged(&mut self, statement: CqlStatement, page_size: usize, paging_state: Option<PagingState>) -> Result<(QueryResult, Option<PagingState>)> {
+        match statement {
+            statement @ CqlStatement::Select { .. } => self.select(statement, Some((page_size, paging_state))).await,
+            other => Ok((self.execute(other).await?, None)),
+        }
+    }
+    
+    async fn create_keyspace(&mut self, name: String, options: crate::query::parser::KeyspaceOptions) -> Result<QueryResult> {
+        // 키스페이스 생성 (단순화된 버전, 이미 있으면 기존 정의 유지)
         if !self.memtables.contains_key(&name) {
             self.memtables.insert(name.clone(), HashMap::new());
-            self.sstables.insert(name, HashMap::new());
+            self.sstables.insert(name.clone(), HashMap::new());
+        }
+        if !self.keyspaces.contains_key(&name) {
+            self.keyspaces.insert(name.clone(), KeyspaceDefinition {
+                name,
+                replication_factor: options.replication_factor,
+                strategy: ReplicationStrategy::SimpleStrategy,
+                durable_writes: options.durable_writes,
+            });
+            self.refresh_keyspace_schema().await?;
         }
         Ok(QueryResult::success())
     }
     
-    async fn create_table(&mut self, keyspace: String, name: String, columns: Vec<crate::schema::ColumnDefinition>, partition_key: Vec<String>, clustering_key: Vec<String>, _options: crate::query::parser::TableOptions) -> Result<QueryResult> {
+    async fn create_table(&mut self, keyspace: String, name: String, columns: Vec<crate::schema::ColumnDefinition>, partition_key: Vec<String>, clustering_key: Vec<String>, options: crate::query::parser::TableOptions) -> Result<QueryResult> {
         // 테이블 스키마 생성
-        let mut pk_columns = Vec::new();
-        let mut ck_columns = Vec::new();
-        let mut regular_columns = Vec::new();
-        let mut static_columns = Vec::new();
-        
-        for column in columns {
-            if partition_key.contains(&column.name) {
-                pk_columns.push(column);
-            } else if clustering_key.contains(&column.name) {
-                ck_columns.push(column);
-            } else if column.is_static {
-                static_columns.push(column);
-            } else {
-                regular_columns.push(column);
-            }
-        }
-        
-        let schema = Arc::new(TableSchema::new(
-            name.clone(),
-            keyspace.clone(),
-            pk_columns,
-            ck_columns,
-            regular_columns,
-            static_columns,
-        ));
+        let schema = Arc::new(build_table_schema(&keyspace, &name, &columns, &partition_key, &clustering_key, &options));
         
         // 스키마 검증
         schema.validate()?;
@@ -106,17 +603,207 @@ impl QueryEngine {
         Ok(QueryResult::success())
     }
     
+    async fn create_index(&mut self, keyspace: String, table: String, name: String, column: String, kind: IndexKind) -> Result<QueryResult> {
+        let memtable = self.get_memtable(&keyspace, &table)?;
+        let schema = memtable.table_schema().clone();
+        
+        let data_type = match schema.column(&column) {
+            Some(definition) => definition.data_type.clone(),
+            None => {
+                return Err(CoreDBError::InvalidSchema {
+                    message: format!("Unknown column {} in table {}.{}", column, keyspace, table),
+                });
+            },
+        };
+        
+        if kind == IndexKind::Vector {
+            let dimension = match data_type {
+                CassandraDataType::Vector(dimension) => dimension,
+                _ => {
+                    return Err(CoreDBError::InvalidSchema {
+                        message: format!("ANN index requires a VECTOR column, {} is not", column),
+                    });
+                },
+            };
+            let exists = self.table_indexes(&keyspace, &table)
+                .is_some_and(|indexes| indexes.vector.read().unwrap().iter().any(|index| index.column == column));
+            if exists {
+                return Ok(QueryResult::success());
+            }
+            
+            // 기존 데이터(메모리 테이블 + SSTable)로 인덱스 채우기
+            let mut index = VectorIndex::new(name, column, dimension);
+            for row in &self.table_rows(&keyspace, &table, &memtable).await? {
+                index.update(None, row);
+            }
+            self.indexes.entry(keyspace).or_default().entry(table).or_default().vector.get_mut().unwrap().push(index);
+            return Ok(QueryResult::success());
+        }
+        
+        // 같은 컬럼에 같은 종류의 인덱스가 이미 있으면 재생성하지 않음
+        let exists = self.table_indexes(&keyspace, &table)
+            .is_some_and(|indexes| indexes.secondary.read().unwrap().iter().any(|index| index.column == column && index.kind == kind));
+        if exists {
+            return Ok(QueryResult::success());
+        }
+        
+        let index = match kind {
+            #[cfg(feature = "fulltext")]
+            IndexKind::FullText => self.open_fulltext_index(&keyspace, &table, &memtable, name, column).await?,
+            _ => {
+                // 기존 데이터(메모리 테이블 + SSTable)로 인덱스 채우기
+                let mut index = SecondaryIndex::with_kind(name, column, kind);
+                for row in &self.table_rows(&keyspace, &table, &memtable).await? {
+                    index.update(None, row)?;
+                }
+                index
+            },
+        };
+        self.indexes.entry(keyspace).or_default().entry(table).or_default().secondary.get_mut().unwrap().push(index);
+        
+        Ok(QueryResult::success())
+    }
+    
+    /// 디스크의 전문 인덱스를 열거나 기존 데이터로 새로 만듦
+    ///
+    /// 이미 commit된 인덱스는 그대로 열어 두고, 테이블을 열 때 rebuild_indexes가 SSTable과 맞춰 갱신함
+    #[cfg(feature = "fulltext")]
+    async fn open_fulltext_index(&self, keyspace: &str, table: &str, memtable: &Memtable, name: String, column: String) -> Result<SecondaryIndex> {
+        let directory = self.data_directory.as_ref()
+            .map(|data_directory| data_directory.join(keyspace).join(table).join(crate::storage::FULLTEXT_DIRECTORY).join(&name));
+        let mut index = SecondaryIndex::open_fulltext(name, column, directory.as_deref())?;
+        if !index.is_committed() {
+            index.rebuild(&self.table_rows(keyspace, table, memtable).await?, &self.sstable_ids(keyspace, table))?;
+        }
+        Ok(index)
+    }
+    
+    /// 테이블의 SSTable ID
+    pub fn sstable_ids(&self, keyspace: &str, table: &str) -> Vec<String> {
+        self.sstables.get(keyspace).and_then(|tables| tables.get(table)).into_iter().flatten()
+            .map(|sstable| sstable.id.clone())
+            .collect()
+    }
+    
+    /// 테이블의 디스크 인덱스에 쌓인 쓰기를 남김 (플러시가 커밋 로그 재생 위치를 옮기기 전에 호출)
+    ///
+    /// sstables는 commit하는 쓰기를 모두 담은 SSTable (플러시 중이면 새로 쓴 SSTable 포함)
+    pub fn commit_indexes(&self, keyspace: &str, table: &str, sstables: &[String]) -> Result<()> {
+        if let Some(indexes) = self.table_indexes(keyspace, table) {
+            for index in indexes.secondary.write().unwrap().iter_mut() {
+                index.commit(sstables)?;
+            }
+        }
+        Ok(())
+    }
+    
+    /// 테이블의 인덱스를 메모리 테이블과 SSTable의 현재 행으로 다시 채움
+    ///
+    /// 테이블을 다시 열 때(SSTable 연결과 커밋 로그 재생 후) 호출. 마지막 commit이 지금 SSTable과 같은
+    /// 디스크 전문 인덱스는 메모리 테이블에만 있는 행만 다시 반영함
+    pub async fn rebuild_indexes(&mut self, keyspace: &str, table: &str) -> Result<()> {
+        if self.table_indexes(keyspace, table).is_none_or(|indexes| indexes.is_empty()) {
+            return Ok(());
+        }
+        
+        let memtable = self.get_memtable(keyspace, table)?;
+        let sstables = self.sstable_ids(keyspace, table);
+        let mut memtable_rows = Vec::new();
+        for partition_key in memtable.partition_keys() {
+            memtable_rows.extend(self.read_partition_rows(keyspace, table, &memtable, &partition_key, &ReadOptions::all()).await?);
+        }
+        // 모든 인덱스가 메모리 테이블 행만 반영하면 되면 SSTable 전체는 읽지 않음
+        let covered = self.table_indexes(keyspace, table).is_some_and(|indexes| {
+            indexes.vector.read().unwrap().is_empty() && indexes.secondary.read().unwrap().iter().all(|index| index.covers(&sstables))
+        });
+        let rows = if covered { Vec::new() } else { self.table_rows(keyspace, table, &memtable).await? };
+        
+        if let Some(indexes) = self.indexes.get_mut(keyspace).and_then(|tables| tables.get_mut(table)) {
+            for index in indexes.secondary.get_mut().unwrap().iter_mut() {
+                if index.covers(&sstables) {
+                    for row in &memtable_rows {
+                        index.update(None, row)?;
+                    }
+                } else {
+                    index.rebuild(&rows, &sstables)?;
+                }
+            }
+            for index in indexes.vector.get_mut().unwrap().iter_mut() {
+                let mut rebuilt = VectorIndex::new(index.name.clone(), index.column.clone(), index.dimension);
+                for row in &rows {
+                    rebuilt.update(None, row);
+                }
+                *index = rebuilt;
+            }
+        }
+        Ok(())
+    }
+    
+    /// 데이터 변경 문을 커밋 로그에 기록할 뮤테이션으로 변환 (스키마 변경과 SELECT 등은 None)
+    ///
+    /// UPDATE는 WHERE의 키 조건과 SET 값을 합친 INSERT로 기록 (Cassandra 스타일 upsert).
+    /// 기록한 뮤테이션은 apply_mutation으로 그대로 반영해야 재생 결과와 같아짐
+    pub fn mutation_for(&self, statement: &CqlStatement) -> Result<Option<Mutation>> {
+        match statement {
+            CqlStatement::Insert { keyspace, table, values } => {
+                Ok(Some(Mutation::Insert(self.build_row(keyspace, table, values.clone())?)))
+            },
+            CqlStatement::Update { keyspace, table, values, where_clause } => {
+                let values = Self::upsert_values(values.clone(), where_clause)?;
+                Ok(Some(Mutation::Insert(self.build_row(keyspace, table, values)?)))
+            },
+            // 쿼리 엔진이 아직 삭제를 반영하지 못하므로 기록하지 않음 (재생 때만 지워지지 않도록)
+            CqlStatement::Delete { .. } => Err(CoreDBError::QueryParsingError {
+                message: "DELETE not implemented yet".to_string(),
+            }),
+            _ => Ok(None),
+        }
+    }
+    
+    /// 커밋 로그에 기록한 뮤테이션 반영
+    pub async fn apply_mutation(&mut self, keyspace: String, table: String, mutation: Mutation) -> Result<QueryResult> {
+        if let Some((partition_key, size)) = self.write_mutation(&keyspace, &table, mutation).await? {
+            self.record_large_partition(&keyspace, &table, &partition_key, size).await?;
+        }
+        Ok(QueryResult::success())
+    }
+    
+    /// 커밋 로그에 기록한 뮤테이션을 엔진 읽기 잠금만으로 반영 (다른 요청과 동시에 실행됨)
+    ///
+    /// 반영한 파티션이 큰 파티션 임계값을 넘었으면 그 크기를 돌려주므로, 호출한 쪽이 쓰기 잠금을 잡고
+    /// record_large_partition으로 기록함
+    pub async fn write_mutation(&self, keyspace: &str, table: &str, mutation: Mutation) -> Result<Option<(PartitionKey, PartitionSize)>> {
+        match mutation {
+            Mutation::Insert(row) => {
+                let partition_key = row.partition_key.clone();
+                Ok(self.write_row(keyspace, table, row).await?.map(|size| (partition_key, size)))
+            },
+            Mutation::Delete { .. } | Mutation::PartitionDelete { .. } | Mutation::Schema { .. } => Err(CoreDBError::QueryParsingError {
+                message: format!("Cannot apply {:?} to {}.{}", mutation, keyspace, table),
+            }),
+        }
+    }
+    
     async fn insert_row(&mut self, keyspace: String, table: String, values: Vec<(String, CassandraValue)>) -> Result<QueryResult> {
+        let row = self.build_row(&keyspace, &table, values)?;
+        self.apply_mutation(keyspace, table, Mutation::Insert(row)).await
+    }
+    
+    /// INSERT 값으로 행 생성 (키 추출과 컬럼 타입 변환, 반영 전 트리거까지 적용)
+    ///
+    /// 트리거가 바꾼 행이 커밋 로그에 기록되고, 트리거가 거부하면 기록하기 전에 실패함
+    fn build_row(&self, keyspace: &str, table: &str, values: Vec<(String, CassandraValue)>) -> Result<SchemaRow> {
         // 테이블 찾기
-        let memtable = self.get_memtable(&keyspace, &table)?;
+        let memtable = self.get_memtable(keyspace, table)?;
         let schema = memtable.table_schema();
         
         // 파티션 키와 클러스터링 키 추출
         let (partition_key, clustering_key) = self.extract_keys_from_values(values.clone(), schema)?;
         
-        // 행 생성
-        let mut cells = HashMap::new();
+        // 행 생성 (메모리 테이블의 컬럼 배치 공유)
+        let mut cells = Cells::with_layout(memtable.column_layout());
         for (column_name, value) in values {
+            let value = Self::coerce_value(schema, &column_name, value)?;
             let cell = Cell {
                 value,
                 timestamp: chrono::Utc::now().timestamp_micros(),
@@ -126,80 +813,614 @@ impl QueryEngine {
             cells.insert(column_name, cell);
         }
         
-        let row = SchemaRow {
+        let mut row = SchemaRow {
             partition_key,
             clustering_key,
             cells,
             timestamp: chrono::Utc::now().timestamp_micros(),
         };
         
-        // 메모리 테이블에 삽입
+        // 반영 전 트리거: 파생 컬럼 추가 또는 거부
+        for registered in self.triggers.get(keyspace).and_then(|tables| tables.get(table)).into_iter().flatten() {
+            registered.trigger.before_write(&mut row).map_err(|message| CoreDBError::TriggerRejected {
+                trigger: registered.name.clone(),
+                message,
+            })?;
+            self.trace(|| format!("Executed before-write trigger {}", registered.name));
+        }
+        Ok(row)
+    }
+    
+    /// build_row로 만든 행 반영 (커밋 로그를 재생할 때는 반영 전 트리거를 다시 실행하지 않음)
+    ///
+    /// 파티션이 큰 파티션 임계값을 넘었으면 그 크기를 반환
+    async fn write_row(&self, keyspace: &str, table: &str, row: SchemaRow) -> Result<Option<PartitionSize>> {
+        let memtable = self.get_memtable(keyspace, table)?;
+        let table_triggers = self.triggers.get(keyspace)
+            .and_then(|tables| tables.get(table))
+            .cloned()
+            .unwrap_or_default();
+        
+        // 메모리 테이블에 삽입 (보조 인덱스는 SSTable까지 병합된 변경 전후 행 기준으로 갱신)
+        let row_key = (row.partition_key.clone(), row.clustering_key.clone());
+        let indexes = self.table_indexes(keyspace, table).filter(|indexes| !indexes.is_empty());
+        let _index_writes = match indexes {
+            Some(indexes) => Some(indexes.writes.lock().await),
+            None => None,
+        };
+        let previous = match indexes {
+            Some(_) => self.read_row(keyspace, table, &memtable, &row_key.0, &row_key.1, &ReadOptions::all()).await?,
+            None => None,
+        };
         memtable.put(row)?;
+        self.trace(|| format!("Applied mutation to memtable {}.{}", keyspace, table));
+        let current = match indexes {
+            Some(_) => self.read_row(keyspace, table, &memtable, &row_key.0, &row_key.1, &ReadOptions::all()).await?,
+            None => None,
+        };
         
-        Ok(QueryResult::success())
+        if let Some(indexes) = indexes {
+            let mut secondary = indexes.secondary.write().unwrap();
+            let mut vector = indexes.vector.write().unwrap();
+            if let Some(current) = &current {
+                for index in secondary.iter_mut() {
+                    index.update(previous.as_ref(), current)?;
+                }
+                for index in vector.iter_mut() {
+                    index.update(previous.as_ref(), current);
+                }
+            }
+            if !secondary.is_empty() {
+                let count = secondary.len();
+                self.trace(|| format!("Updated {} secondary index(es)", count));
+            }
+        }
+        
+        if !table_triggers.is_empty() {
+            if let Some(current) = memtable.get(&row_key.0, &row_key.1) {
+                for registered in &table_triggers {
+                    registered.trigger.after_write(&current);
+                    self.trace(|| format!("Executed after-write trigger {}", registered.name));
+                }
+            }
+        }
+        
+        Ok(memtable.partition_size(&row_key.0)
+            .filter(|size| size.bytes > self.large_partition_threshold_bytes && keyspace != SYSTEM_VIEWS_KEYSPACE))
+    }
+    
+    /// SELECT 실행 (엔진 읽기 잠금만으로 다른 요청과 동시에 실행됨). paging이 주어지면 한 페이지만 반환하고
+    /// 다음 페이지 상태를 돌려줌 (ANN 정렬은 페이지로 나누지 않음)
+    ///
+    /// 가상 테이블은 먼저 refresh_virtual_table로 현재 상태를 채워야 함
+    pub async fn select(&self, statement: CqlStatement, paging: Option<(usize, Option<PagingState>)>) -> Result<(QueryResult, Option<PagingState>)> {
+        match statement {
+            CqlStatement::Select { keyspace, table, columns, where_clause, order_by: Some(OrderBy::Ann { column, vector }), limit } => {
+                Ok((self.select_ann(&keyspace, &table, &columns, where_clause, &column, &vector, limit).await?, None))
+            },
+            CqlStatement::Select { keyspace, table, columns, where_clause, order_by, limit } => {
+                let (rows, next_state) = self.select_page(&keyspace, &table, &columns, where_clause, order_by, limit, paging).await?;
+                Ok((QueryResult::rows(rows), next_state))
+            },
+            other => Err(CoreDBError::QueryParsingError {
+                message: format!("Expected a SELECT statement, got {:?}", other),
+            }),
+        }
     }
     
-    async fn select_rows(&mut self, keyspace: String, table: String, columns: Vec<String>, where_clause: Option<crate::query::parser::WhereClause>, limit: Option<u32>) -> Result<QueryResult> {
+    /// SELECT 실행. paging이 주어지면 한 페이지만 반환하고 다음 페이지 상태를 돌려줌
+    #[allow(clippy::too_many_arguments)]
+    async fn select_page(&self, keyspace: &str, table: &str, columns: &[String], where_clause: Option<WhereClause>, order_by: Option<OrderBy>, limit: Option<u32>, paging: Option<(usize, Option<PagingState>)>) -> Result<(Vec<QueryRow>, Option<PagingState>)> {
         // 테이블 찾기
-        let memtable = self.get_memtable(&keyspace, &table)?;
-        let schema = memtable.table_schema();
+        let memtable = self.get_memtable(keyspace, table)?;
+        let conditions = where_clause.map(|w| w.conditions).unwrap_or_default();
+        
+        let (page_size, paging_state) = match paging {
+            Some((size, state)) => (Some(size), state),
+            None => (None, None),
+        };
+        let max_rows = match (limit.map(|l| l as usize), page_size) {
+            (Some(l), Some(p)) => Some(l.min(p)),
+            (l, p) => l.or(p),
+        };
+        
+        let schema = memtable.table_schema().clone();
+        let plan = self.plan_read(keyspace, table, &schema, &conditions)?;
+        self.trace(|| match &plan {
+            ReadPlan::Partition(_, _) => format!("Executing single-partition query on {}.{}", keyspace, table),
+            ReadPlan::Index(keys) => format!("Index lookup on {}.{} returned {} key(s)", keyspace, table, keys.len()),
+            ReadPlan::FullScan => format!("Executing full scan on {}.{}", keyspace, table),
+        });
+        let direction = Self::scan_direction(&schema, order_by.as_ref(), &plan)?;
         
-        let mut results = Vec::new();
+        // 필요한 컬럼만 저장소에서 읽음 (요청 컬럼 + 필터 조건 컬럼)
+        let mut read_options = Self::projection_for(columns, &conditions).with_direction(direction);
+        
+        // 파티션 단위로 읽을 때는 첫 번째 클러스터링 컬럼 범위 밖의 SSTable과 행을 건너뜀
+        if matches!(plan, ReadPlan::Partition(_, None) | ReadPlan::FullScan) {
+            read_options = read_options.with_clustering_slice(Self::clustering_slice(&schema, &conditions));
+        }
         
-        if let Some(where_clause) = where_clause {
-            // WHERE 절이 있는 경우
-            if where_clause.conditions.len() == 1 {
-                let condition = &where_clause.conditions[0];
-                if condition.column == schema.partition_key[0].name {
-                    // 파티션 키 조건인 경우
-                    let partition_key = PartitionKey {
-                        components: vec![condition.value.clone()],
+        // 잔여 필터가 없고 페이지 시작점이 없을 때만 행 수 제한을 저장소까지 전달
+        let push_limit = paging_state.is_none() && match &plan {
+            ReadPlan::Partition(_, clustering_key) => conditions.iter().all(|c| {
+                matches!(c.operator, ComparisonOperator::Equal)
+                    && (schema.partition_key.iter().any(|pk| pk.name == c.column)
+                        || (clustering_key.is_some() && schema.clustering_key.iter().any(|ck| ck.name == c.column)))
+            }),
+            ReadPlan::FullScan => conditions.is_empty(),
+            ReadPlan::Index(_) => false,
+        };
+        
+        let targets: Vec<(PartitionKey, Option<Option<ClusteringKey>>)> = match plan {
+            // 인덱스가 가리키는 행만 일반 읽기 경로로 조회
+            ReadPlan::Index(keys) => keys.into_iter()
+                .map(|(partition_key, clustering_key)| (partition_key, Some(clustering_key)))
+                .collect(),
+            ReadPlan::Partition(partition_key, Some(clustering_key)) => vec![(partition_key, Some(Some(clustering_key)))],
+            ReadPlan::Partition(partition_key, None) => vec![(partition_key, None)],
+            ReadPlan::FullScan => self.all_partition_keys(keyspace, table, &memtable).await?
+                .into_iter()
+                .map(|partition_key| (partition_key, None))
+                .collect(),
+        };
+        
+        let mut matched: Vec<SchemaRow> = Vec::new();
+        let mut has_more = false;
+        let mut scanned = 0usize;
+        
+        'targets: for (partition_key, row_key) in targets {
+            // 다음 페이지 판단을 위해 한 행 더 읽음
+            let limit = match (push_limit, max_rows) {
+                (true, Some(max)) => Some(max - matched.len() + 1),
+                _ => None,
+            };
+            let options = read_options.clone().with_limit(limit);
+            
+            let rows = match row_key {
+                Some(clustering_key) => {
+                    if !Self::is_after_paging_state(&partition_key, &clustering_key, &paging_state, direction) {
+                        continue;
+                    }
+                    self.read_row(keyspace, table, &memtable, &partition_key, &clustering_key, &options).await?
+                        .into_iter()
+                        .collect()
+                },
+                None => self.read_partition_rows(keyspace, table, &memtable, &partition_key, &options).await?,
+            };
+            
+            scanned += rows.len();
+            for row in rows {
+                if !Self::is_after_paging_state(&row.partition_key, &row.clustering_key, &paging_state, direction) {
+                    continue;
+                }
+                // 인덱스 항목이 오래된 경우도 여기서 걸러짐
+                if !Self::row_matches(&row, &conditions) {
+                    continue;
+                }
+                if max_rows.is_some_and(|max| matched.len() >= max) {
+                    has_more = true;
+                    break 'targets;
+                }
+                matched.push(row);
+            }
+        }
+        
+        let returned = matched.len();
+        self.trace(|| format!("Read {} live row(s), {} filtered out", returned, scanned - returned));
+        
+        // 다음 페이지가 있을 때만 페이지 상태 반환
+        let next_state = match (page_size, has_more, matched.last()) {
+            (Some(_), true, Some(last)) => Some(PagingState {
+                partition_key: last.partition_key.clone(),
+                clustering_key: last.clustering_key.clone(),
+            }),
+            _ => None,
+        };
+        
+        let results = matched.into_iter()
+            .map(|row| self.convert_schema_row_to_query_row(row, columns))
+            .collect();
+        
+        Ok((results, next_state))
+    }
+    
+    /// ORDER BY <column> ANN OF [..] LIMIT k 실행
+    ///
+    /// ANN 인덱스가 있으면 인덱스 후보를 정확한 거리로 재정렬하고,
+    /// 없으면 전체 스캔 후 거리순 정렬 (소규모 테이블용)
+    #[allow(clippy::too_many_arguments)]
+    async fn select_ann(&self, keyspace: &str, table: &str, columns: &[String], where_clause: Option<WhereClause>, column: &str, vector: &[f32], limit: Option<u32>) -> Result<QueryResult> {
+        let memtable = self.get_memtable(keyspace, table)?;
+        let conditions = where_clause.map(|w| w.conditions).unwrap_or_default();
+        
+        match memtable.table_schema().column(column).map(|c| &c.data_type) {
+            Some(CassandraDataType::Vector(dimension)) if *dimension == vector.len() => {},
+            Some(CassandraDataType::Vector(dimension)) => {
+                return Err(CoreDBError::InvalidDataType {
+                    message: format!("ANN OF vector has {} dimensions, column {} expects {}", vector.len(), column, dimension),
+                });
+            },
+            _ => {
+                return Err(CoreDBError::InvalidSchema {
+                    message: format!("ANN ordering requires a VECTOR column, {} is not", column),
+                });
+            },
+        }
+        let k = limit.ok_or_else(|| CoreDBError::QueryParsingError {
+            message: "ANN queries require a LIMIT".to_string(),
+        })? as usize;
+        
+        let mut read_options = Self::projection_for(columns, &conditions);
+        if let Some(selected) = read_options.columns.as_mut() {
+            selected.insert(column.to_string());
+        }
+        
+        let indexes = self.table_indexes(keyspace, table)
+            .filter(|indexes| indexes.vector.read().unwrap().iter().any(|index| index.column == column));
+        
+        let mut candidates: Vec<SchemaRow> = Vec::new();
+        match indexes {
+            Some(indexes) => {
+                // 필터로 빠지는 행이 있으면 후보 수를 늘려 다시 조회
+                let mut fetch = k;
+                loop {
+                    // 행을 읽는 동안 쓰기가 인덱스를 갱신할 수 있도록 검색 결과만 가지고 잠금을 놓음
+                    let (name, hits) = {
+                        let vector_indexes = indexes.vector.read().unwrap();
+                        let Some(index) = vector_indexes.iter().find(|index| index.column == column) else { break };
+                        (index.name.clone(), index.search(vector, fetch))
                     };
-                    
-                    if let Some(clustering_condition) = where_clause.conditions.get(1) {
-                        // 클러스터링 키 조건도 있는 경우
-                        let clustering_key = Some(ClusteringKey {
-                            components: vec![clustering_condition.value.clone()],
+                    self.trace(|| format!("ANN index {} returned {} candidate(s)", name, hits.len()));
+                    candidates.clear();
+                    for ((partition_key, clustering_key), _) in &hits {
+                        if let Some(row) = self.read_row(keyspace, table, &memtable, partition_key, clustering_key, &read_options).await? {
+                            if Self::row_matches(&row, &conditions) {
+                                candidates.push(row);
+                            }
+                        }
+                    }
+                    if candidates.len() >= k || hits.len() < fetch {
+                        break;
+                    }
+                    fetch *= 2;
+                }
+            },
+            None => {
+                self.trace(|| format!("Executing brute-force ANN scan on {}.{}", keyspace, table));
+                for partition_key in self.all_partition_keys(keyspace, table, &memtable).await? {
+                    for row in self.read_partition_rows(keyspace, table, &memtable, &partition_key, &read_options).await? {
+                        if Self::row_matches(&row, &conditions) {
+                            candidates.push(row);
+                        }
+                    }
+                }
+            },
+        }
+        
+        // 저장된 실제 벡터로 거리 계산 후 정렬
+        let mut scored: Vec<(f32, SchemaRow)> = candidates.into_iter()
+            .filter_map(|row| match row.cells.get(column) {
+                Some(cell) if !cell.is_deleted => match &cell.value {
+                    CassandraValue::Vector(v) => Some((cosine_distance(vector, v), row)),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        scored.sort_by(|a, b| a.0.total_cmp(&b.0));
+        scored.truncate(k);
+        
+        Ok(QueryResult::rows(scored.into_iter()
+            .map(|(_, row)| self.convert_schema_row_to_query_row(row, columns))
+            .collect()))
+    }
+    
+    /// 메모리 테이블과 SSTable의 모든 파티션 키 (정렬 순서)
+    async fn all_partition_keys(&self, keyspace: &str, table: &str, memtable: &Memtable) -> Result<BTreeSet<PartitionKey>> {
+        let mut partition_keys: BTreeSet<PartitionKey> = memtable.partition_keys().into_iter().collect();
+        for frozen in self.flushing_memtables(keyspace, table) {
+            partition_keys.extend(frozen.partition_keys());
+        }
+        if let Some(sstables) = self.sstables.get(keyspace).and_then(|tables| tables.get(table)) {
+            for sstable in sstables {
+                partition_keys.extend(sstable.partition_keys().await?);
+            }
+        }
+        Ok(partition_keys)
+    }
+    
+    /// 메모리 테이블과 SSTable을 합친 테이블의 모든 살아있는 행 (인덱스 구축용)
+    async fn table_rows(&self, keyspace: &str, table: &str, memtable: &Memtable) -> Result<Vec<SchemaRow>> {
+        let mut rows = Vec::new();
+        for partition_key in self.all_partition_keys(keyspace, table, memtable).await? {
+            rows.extend(self.read_partition_rows(keyspace, table, memtable, &partition_key, &ReadOptions::all()).await?);
+        }
+        Ok(rows)
+    }
+    
+    /// 컬럼 타입에 맞게 값 변환 (VECTOR 컬럼의 리스트 리터럴 → 벡터)
+    fn coerce_value(schema: &TableSchema, column: &str, value: CassandraValue) -> Result<CassandraValue> {
+        let dimension = match schema.column(column).map(|c| &c.data_type) {
+            Some(CassandraDataType::Vector(dimension)) => *dimension,
+            _ => return Ok(value),
+        };
+        
+        let vector: Option<Vec<f32>> = match &value {
+            CassandraValue::Vector(v) => Some(v.clone()),
+            CassandraValue::List(items) => items.iter()
+                .map(|item| match item {
+                    CassandraValue::Int(v) => Some(*v as f32),
+                    CassandraValue::BigInt(v) => Some(*v as f32),
+                    CassandraValue::Double(v) => Some(*v as f32),
+                    _ => None,
+                })
+                .collect(),
+            CassandraValue::Null => return Ok(value),
+            _ => None,
+        };
+        
+        match vector {
+            Some(v) if v.len() == dimension => Ok(CassandraValue::Vector(v)),
+            Some(v) => Err(CoreDBError::InvalidDataType {
+                message: format!("Column {} expects {} dimensions, got {}", column, dimension, v.len()),
+            }),
+            None => Err(CoreDBError::InvalidDataType {
+                message: format!("Column {} expects a numeric vector", column),
+            }),
+        }
+    }
+    
+    /// WHERE 조건으로 읽기 계획 선택: 파티션 키 → 보조 인덱스 → 전체 스캔
+    fn plan_read(&self, keyspace: &str, table: &str, schema: &TableSchema, conditions: &[Condition]) -> Result<ReadPlan> {
+        let equality = |column: &str| conditions.iter()
+            .find(|c| c.column == column && matches!(c.operator, ComparisonOperator::Equal))
+            .map(|c| c.value.clone());
+        
+        let partition_components: Option<Vec<CassandraValue>> = schema.partition_key.iter()
+            .map(|c| equality(&c.name))
+            .collect();
+        if let Some(components) = partition_components {
+            let clustering_components: Option<Vec<CassandraValue>> = schema.clustering_key.iter()
+                .map(|c| equality(&c.name))
+                .collect();
+            let clustering_key = clustering_components
+                .filter(|c| !c.is_empty())
+                .map(|components| ClusteringKey { components });
+            return Ok(ReadPlan::Partition(PartitionKey { components }, clustering_key));
+        }
+        
+        if let Some(table_indexes) = self.table_indexes(keyspace, table) {
+            for index in table_indexes.secondary.read().unwrap().iter() {
+                match index.kind {
+                    IndexKind::Standard => {
+                        if let Some(value) = equality(&index.column) {
+                            return Ok(ReadPlan::Index(index.lookup(&value)));
+                        }
+                    },
+                    IndexKind::FullText => {
+                        let query = conditions.iter().find_map(|c| match (&c.operator, &c.value) {
+                            (ComparisonOperator::Match, CassandraValue::Text(query)) if c.column == index.column => Some(query),
+                            _ => None,
                         });
-                        
-                        if let Some(row) = memtable.get(&partition_key, &clustering_key) {
-                            results.push(self.convert_schema_row_to_query_row(row, &columns));
+                        if let Some(query) = query {
+                            return Ok(ReadPlan::Index(index.search(query)?));
                         }
-                    } else {
-                        // 파티션 전체 스캔
-                        let partition_rows = memtable.range_scan(&partition_key, &None, &None);
-                        for row in partition_rows {
-                            results.push(self.convert_schema_row_to_query_row(row, &columns));
+                    },
+                    // ANN 인덱스는 별도 목록(TableIndexes::vector)에 보관됨
+                    IndexKind::Vector => {},
+                }
+            }
+        }
+        
+        Ok(ReadPlan::FullScan)
+    }
+    
+    /// 일반 읽기 경로: 메모리 테이블과 SSTable의 버전을 병합
+    async fn read_row(&self, keyspace: &str, table: &str, memtable: &Memtable, partition_key: &PartitionKey, clustering_key: &Option<ClusteringKey>, options: &ReadOptions) -> Result<Option<SchemaRow>> {
+        let mut merged = memtable.get_with(partition_key, clustering_key, options);
+        for row in self.flushing_memtables(keyspace, table).iter().filter_map(|frozen| frozen.get_with(partition_key, clustering_key, options)) {
+            match merged.as_mut() {
+                Some(existing) => existing.merge(row),
+                None => merged = Some(row),
+            }
+        }
+        
+        if let Some(sstables) = self.sstables.get(keyspace).and_then(|tables| tables.get(table)) {
+            let row_options = options.clone().with_limit(None);
+            for sstable in sstables {
+                let partition = sstable.read_partition_with(partition_key, &row_options).await?;
+                self.trace_sstable_read(sstable, partition.as_ref().map(|p| p.rows.len()));
+                if let Some(partition) = partition {
+                    if let Some(entry) = partition.rows.get(clustering_key) {
+                        let row = entry.value().clone();
+                        match merged.as_mut() {
+                            Some(existing) => existing.merge(row),
+                            None => merged = Some(row),
                         }
                     }
                 }
             }
-        } else {
-            // WHERE 절이 없는 경우 - 전체 테이블 스캔 (실제로는 비효율적)
-            let all_partitions = memtable.get_all_partitions();
-            for (_, partition) in all_partitions {
-                for row_entry in partition.rows.iter() {
-                    let row = row_entry.value();
-                    results.push(self.convert_schema_row_to_query_row(row.clone(), &columns));
+        }
+        
+        Ok(merged)
+    }
+    
+    /// 파티션 전체를 메모리 테이블과 SSTable에서 읽어 클러스터링 순서(역방향이면 역순)로 병합
+    ///
+    /// 각 소스는 순회 방향 기준 앞에서부터 limit개만 읽으면 충분함 (병합 결과의 앞 limit개는
+    /// 반드시 어느 한 소스의 앞 limit개 안에 있음)
+    async fn read_partition_rows(&self, keyspace: &str, table: &str, memtable: &Memtable, partition_key: &PartitionKey, options: &ReadOptions) -> Result<Vec<SchemaRow>> {
+        let mut merged: BTreeMap<Option<ClusteringKey>, SchemaRow> = BTreeMap::new();
+        
+        for row in memtable.range_scan_with(partition_key, &None, &None, options) {
+            merged.insert(row.clustering_key.clone(), row);
+        }
+        for frozen in self.flushing_memtables(keyspace, table) {
+            for row in frozen.range_scan_with(partition_key, &None, &None, options) {
+                match merged.get_mut(&row.clustering_key) {
+                    Some(existing) => existing.merge(row),
+                    None => {
+                        merged.insert(row.clustering_key.clone(), row);
+                    },
                 }
             }
         }
+        let memtable_rows = merged.len();
+        self.trace(|| format!("Read {} row(s) from memtable", memtable_rows));
         
-        // LIMIT 적용
-        if let Some(limit) = limit {
-            results.truncate(limit as usize);
+        if let Some(sstables) = self.sstables.get(keyspace).and_then(|tables| tables.get(table)) {
+            for sstable in sstables {
+                if !sstable.may_contain_slice(options) {
+                    self.trace(|| format!("Skipped SSTable {} (clustering range)", sstable.id));
+                    continue;
+                }
+                let partition = sstable.read_partition_with(partition_key, options).await?;
+                self.trace_sstable_read(sstable, partition.as_ref().map(|p| p.rows.len()));
+                if let Some(partition) = partition {
+                    for entry in partition.rows.iter() {
+                        let row = entry.value().clone();
+                        match merged.get_mut(&row.clustering_key) {
+                            Some(existing) => existing.merge(row),
+                            None => {
+                                merged.insert(row.clustering_key.clone(), row);
+                            },
+                        }
+                    }
+                }
+            }
         }
         
-        Ok(QueryResult::rows(results))
+        self.trace(|| format!("Merged {} row(s) from memtable and SSTables", merged.len()));
+        let mut rows: Vec<SchemaRow> = match options.direction {
+            ScanDirection::Forward => merged.into_values().collect(),
+            ScanDirection::Reverse => merged.into_values().rev().collect(),
+        };
+        if let Some(limit) = options.limit {
+            rows.truncate(limit);
+        }
+        Ok(rows)
     }
     
-    async fn update_row(&mut self, _keyspace: String, _table: String, _values: Vec<(String, CassandraValue)>, _where_clause: crate::query::parser::WhereClause) -> Result<QueryResult> {
-        // UPDATE는 INSERT로 구현 (Cassandra 스타일)
-        Err(CoreDBError::QueryParsingError {
-            message: "UPDATE not implemented yet".to_string(),
+    fn trace_sstable_read(&self, sstable: &SSTable, rows: Option<usize>) {
+        self.trace(|| match rows {
+            Some(rows) => format!("Read {} row(s) from SSTable {}", rows, sstable.id),
+            None => format!("Skipped SSTable {} (partition not present)", sstable.id),
+        });
+    }
+    
+    /// SELECT 컬럼 목록과 조건에서 저장소 읽기 컬럼 집합 계산
+    fn projection_for(columns: &[String], conditions: &[Condition]) -> ReadOptions {
+        if columns.iter().any(|c| c == "*") {
+            return ReadOptions::all();
+        }
+        ReadOptions::all().with_columns(
+            columns.iter().cloned().chain(conditions.iter().map(|c| c.column.clone()))
+        )
+    }
+    
+    /// 첫 번째 클러스터링 컬럼 조건(=, >, >=, <, <=)으로 읽을 클러스터링 범위 계산 (제한이 없으면 None)
+    fn clustering_slice(schema: &TableSchema, conditions: &[Condition]) -> Option<ClusteringSlice> {
+        let column = schema.clustering_key.first()?;
+        let mut slice = ClusteringSlice::all();
+        for condition in conditions.iter().filter(|c| c.column == column.name) {
+            let value = condition.value.clone();
+            match condition.operator {
+                ComparisonOperator::Equal => {
+                    slice.restrict_start(Bound::Included(value.clone()));
+                    slice.restrict_end(Bound::Included(value));
+                },
+                ComparisonOperator::GreaterThan => slice.restrict_start(Bound::Excluded(value)),
+                ComparisonOperator::GreaterThanOrEqual => slice.restrict_start(Bound::Included(value)),
+                ComparisonOperator::LessThan => slice.restrict_end(Bound::Excluded(value)),
+                ComparisonOperator::LessThanOrEqual => slice.restrict_end(Bound::Included(value)),
+                _ => {},
+            }
+        }
+        (!slice.is_unbounded()).then_some(slice)
+    }
+    
+    fn is_after_paging_state(partition_key: &PartitionKey, clustering_key: &Option<ClusteringKey>, paging_state: &Option<PagingState>, direction: ScanDirection) -> bool {
+        let Some(state) = paging_state else { return true };
+        let position = (partition_key, clustering_key);
+        let last = (&state.partition_key, &state.clustering_key);
+        match direction {
+            ScanDirection::Forward => position > last,
+            ScanDirection::Reverse => position < last,
+        }
+    }
+    
+    /// ORDER BY <clustering column> 검증 후 파티션 내 순회 방향 결정
+    ///
+    /// Cassandra와 같이 파티션 키가 등호로 지정된 단일 파티션 조회에서
+    /// 첫 번째 클러스터링 컬럼으로만 정렬할 수 있음
+    fn scan_direction(schema: &TableSchema, order_by: Option<&OrderBy>, plan: &ReadPlan) -> Result<ScanDirection> {
+        let Some(OrderBy::Clustering { column, descending }) = order_by else {
+            return Ok(ScanDirection::Forward);
+        };
+        if schema.clustering_key.first().is_none_or(|first| first.name != *column) {
+            return Err(CoreDBError::QueryParsingError {
+                message: format!("ORDER BY is only supported on the first clustering column, not {}", column),
+            });
+        }
+        if !matches!(plan, ReadPlan::Partition(_, _)) {
+            return Err(CoreDBError::QueryParsingError {
+                message: "ORDER BY is only supported when the partition key is restricted by an equality".to_string(),
+            });
+        }
+        Ok(if *descending { ScanDirection::Reverse } else { ScanDirection::Forward })
+    }
+    
+    fn row_matches(row: &SchemaRow, conditions: &[Condition]) -> bool {
+        conditions.iter().all(|condition| {
+            let value = match row.cells.get(&condition.column) {
+                Some(cell) if !cell.is_deleted => &cell.value,
+                _ => return false,
+            };
+            match condition.operator {
+                ComparisonOperator::Equal => *value == condition.value,
+                ComparisonOperator::NotEqual => *value != condition.value,
+                ComparisonOperator::GreaterThan => *value > condition.value,
+                ComparisonOperator::GreaterThanOrEqual => *value >= condition.value,
+                ComparisonOperator::LessThan => *value < condition.value,
+                ComparisonOperator::LessThanOrEqual => *value <= condition.value,
+                // 인덱스가 없어도 토큰 비교로 필터링
+                ComparisonOperator::Match => match (value, &condition.value) {
+                    (CassandraValue::Text(text), CassandraValue::Text(query)) => {
+                        let tokens = tokenize(text);
+                        tokenize(query).iter().all(|token| tokens.contains(token))
+                    },
+                    _ => false,
+                },
+                ComparisonOperator::Within => match (value, BoundingBox::from_value(&condition.value)) {
+                    (CassandraValue::Point(lat, lon), Some(bbox)) => bbox.contains(*lat, *lon),
+                    _ => false,
+                },
+                // 아직 지원하지 않는 연산자
+                ComparisonOperator::In | ComparisonOperator::Like => false,
+            }
         })
     }
     
+    async fn update_row(&mut self, keyspace: String, table: String, values: Vec<(String, CassandraValue)>, where_clause: WhereClause) -> Result<QueryResult> {
+        // UPDATE는 INSERT로 구현 (Cassandra 스타일)
+        let values = Self::upsert_values(values, &where_clause)?;
+        self.insert_row(keyspace, table, values).await
+    }
+    
+    /// UPDATE의 SET 값에 WHERE의 키 값을 더함 (키는 등호 조건으로만 지정)
+    fn upsert_values(mut values: Vec<(String, CassandraValue)>, where_clause: &WhereClause) -> Result<Vec<(String, CassandraValue)>> {
+        for condition in &where_clause.conditions {
+            if !matches!(condition.operator, ComparisonOperator::Equal) {
+                return Err(CoreDBError::QueryParsingError {
+                    message: format!("UPDATE requires equality on key column {}", condition.column),
+                });
+            }
+            values.push((condition.column.clone(), condition.value.clone()));
+        }
+        Ok(values)
+    }
+    
     async fn delete_row(&mut self, _keyspace: String, _table: String, _where_clause: crate::query::parser::WhereClause) -> Result<QueryResult> {
         // DELETE 구현
         Err(CoreDBError::QueryParsingError {
@@ -212,16 +1433,34 @@ impl QueryEngine {
             tables.remove(&name);
         }
         
+        if let Some(tables) = self.flushing.get_mut(&keyspace) {
+            tables.remove(&name);
+        }
+        
         if let Some(tables) = self.sstables.get_mut(&keyspace) {
             tables.remove(&name);
         }
         
+        if let Some(tables) = self.indexes.get_mut(&keyspace) {
+            tables.remove(&name);
+        }
+        
+        if let Some(tables) = self.triggers.get_mut(&keyspace) {
+            tables.remove(&name);
+        }
+        
         Ok(QueryResult::success())
     }
     
     async fn drop_keyspace(&mut self, name: String) -> Result<QueryResult> {
         self.memtables.remove(&name);
+        self.flushing.remove(&name);
         self.sstables.remove(&name);
+        self.indexes.remove(&name);
+        self.triggers.remove(&name);
+        if self.keyspaces.remove(&name).is_some() {
+            self.refresh_keyspace_schema().await?;
+        }
         Ok(QueryResult::success())
     }
     
@@ -230,13 +1469,17 @@ impl QueryEngine {
         Ok(QueryResult::success())
     }
     
-    fn get_memtable(&self, keyspace: &str, table: &str) -> Result<Arc<Memtable>> {
+    pub fn get_memtable(&self, keyspace: &str, table: &str) -> Result<Arc<Memtable>> {
         self.memtables
             .get(keyspace)
             .ok_or_else(|| CoreDBError::KeyspaceNotFound { keyspace: keyspace.to_string() })?
             .get(table)
             .ok_or_else(|| CoreDBError::TableNotFound { table: table.to_string() })
-            .map(|m| m.clone())
+            .cloned()
+    }
+    
+    fn table_indexes(&self, keyspace: &str, table: &str) -> Option<&TableIndexes> {
+        self.indexes.get(keyspace).and_then(|tables| tables.get(table))
     }
     
     fn extract_keys_from_values(&self, values: Vec<(String, CassandraValue)>, schema: &TableSchema) -> Result<(PartitionKey, Option<ClusteringKey>)> {
@@ -319,6 +1562,68 @@ impl QueryEngine {
             tables.insert(table, memtable);
         }
     }
+    
+    /// 테이블의 컴팩션 전략 변경 (매니페스트 레벨 초기화와 컴팩션 예약은 호출한 쪽에서)
+    ///
+    /// 스키마는 메모리 테이블이 들고 있으므로 같은 내용에 새 스키마를 붙인 메모리 테이블로 교체함
+    pub fn set_compaction_strategy(&mut self, keyspace: &str, table: &str, strategy: CompactionStrategy) -> Result<()> {
+        let memtable = self.get_memtable(keyspace, table)?;
+        let mut schema = (**memtable.table_schema()).clone();
+        schema.options.compaction_strategy = strategy;
+        self.replace_memtable(keyspace.to_string(), table.to_string(), Arc::new(memtable.with_schema(Arc::new(schema))));
+        Ok(())
+    }
+    
+    /// 테이블의 SSTable
+    pub fn sstables(&self, keyspace: &str, table: &str) -> Vec<Arc<SSTable>> {
+        self.sstables.get(keyspace).and_then(|tables| tables.get(table)).cloned().unwrap_or_default()
+    }
+    
+    /// SSTable로 쓰이는 중인 고정된 메모리 테이블 (오래된 것부터)
+    pub fn flushing_memtables(&self, keyspace: &str, table: &str) -> &[Arc<Memtable>] {
+        self.flushing.get(keyspace).and_then(|tables| tables.get(table)).map_or(&[], Vec::as_slice)
+    }
+    
+    /// 현재 메모리 테이블을 고정해 플러시 목록으로 옮기고 새 메모리 테이블로 교체 (비어 있으면 None)
+    pub fn freeze_memtable(&mut self, keyspace: &str, table: &str) -> Result<Option<Arc<Memtable>>> {
+        let memtable = self.get_memtable(keyspace, table)?;
+        if memtable.partition_count() == 0 {
+            return Ok(None);
+        }
+        memtable.freeze();
+        self.replace_memtable(keyspace.to_string(), table.to_string(), Arc::new(Memtable::new(memtable.table_schema().clone())));
+        self.flushing.entry(keyspace.to_string()).or_default().entry(table.to_string()).or_default().push(memtable.clone());
+        Ok(Some(memtable))
+    }
+    
+    /// 플러시가 끝난 메모리 테이블을 목록에서 빼고 그 SSTable을 붙임
+    pub fn finish_flush(&mut self, keyspace: &str, table: &str, frozen: &Arc<Memtable>, sstable: Arc<SSTable>) {
+        if let Some(flushing) = self.flushing.get_mut(keyspace).and_then(|tables| tables.get_mut(table)) {
+            flushing.retain(|memtable| !Arc::ptr_eq(memtable, frozen));
+        }
+        self.add_sstable(keyspace.to_string(), table.to_string(), sstable);
+    }
+    
+    /// 컴팩션 입력 SSTable을 결과로 교체 (입력이 그 사이 치워졌으면 바꾸지 않고 false)
+    pub fn replace_sstables(&mut self, keyspace: &str, table: &str, inputs: &[Arc<SSTable>], outputs: Vec<Arc<SSTable>>) -> bool {
+        let Some(sstables) = self.sstables.get_mut(keyspace).and_then(|tables| tables.get_mut(table)) else { return false };
+        if !inputs.iter().all(|input| sstables.iter().any(|sstable| Arc::ptr_eq(input, sstable))) {
+            return false;
+        }
+        sstables.retain(|sstable| !inputs.iter().any(|input| Arc::ptr_eq(input, sstable)));
+        sstables.extend(outputs);
+        true
+    }
+    
+    /// 아직 SSTable에 쓰이지 않은 메모리 테이블의 가장 작은 타임스탬프
+    pub fn min_memtable_timestamp(&self, keyspace: &str, table: &str) -> i64 {
+        let current = self.get_memtable(keyspace, table).ok();
+        self.flushing_memtables(keyspace, table).iter()
+            .chain(current.as_ref())
+            .map(|memtable| memtable.min_timestamp())
+            .min()
+            .unwrap_or(i64::MAX)
+    }
 }
 
 #[cfg(test)]
@@ -336,6 +1641,7 @@ mod tests {
             options: crate::query::parser::KeyspaceOptions {
                 replication_factor: 1,
                 strategy: "SimpleStrategy".to_string(),
+                durable_writes: true,
             },
         };
         
@@ -361,9 +1667,11 @@ mod tests {
             partition_key: vec!["id".to_string()],
             clustering_key: vec![],
             options: crate::query::parser::TableOptions {
-                compaction_strategy: "SizeTiered".to_string(),
+                compaction_strategy: None,
                 bloom_filter_fp_chance: 0.01,
                 default_time_to_live: None,
+                memtable_flush_threshold_mb: None,
+                compression: None,
             },
         };
         
@@ -381,6 +1689,7 @@ mod tests {
             options: crate::query::parser::KeyspaceOptions {
                 replication_factor: 1,
                 strategy: "SimpleStrategy".to_string(),
+                durable_writes: true,
             },
         }).await.unwrap();
         
@@ -402,9 +1711,11 @@ mod tests {
             partition_key: vec!["id".to_string()],
             clustering_key: vec![],
             options: crate::query::parser::TableOptions {
-                compaction_strategy: "SizeTiered".to_string(),
+                compaction_strategy: None,
                 bloom_filter_fp_chance: 0.01,
                 default_time_to_live: None,
+                memtable_flush_threshold_mb: None,
+                compression: None,
             },
         }).await.unwrap();
         
@@ -433,6 +1744,7 @@ mod tests {
                     value: CassandraValue::Int(1),
                 }],
             }),
+            order_by: None,
             limit: None,
         };
         
@@ -444,4 +1756,424 @@ mod tests {
             panic!("Expected rows result");
         }
     }
+    
+    #[tokio::test]
+    async fn test_select_through_secondary_index_with_paging() {
+        let mut engine = QueryEngine::new();
+        
+        for query in [
+            "CREATE KEYSPACE test_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}",
+            "CREATE TABLE test_ks.users (id INT PRIMARY KEY, name TEXT, city TEXT)",
+            "INSERT INTO test_ks.users (id, name, city) VALUES (1, 'Kim', 'Seoul')",
+            "INSERT INTO test_ks.users (id, name, city) VALUES (2, 'Lee', 'Busan')",
+            "CREATE INDEX ON test_ks.users (city)",
+            "INSERT INTO test_ks.users (id, name, city) VALUES (3, 'Park', 'Seoul')",
+            "INSERT INTO test_ks.users (id, name, city) VALUES (4, 'Choi', 'Seoul')",
+            // 값이 바뀐 행은 이전 인덱스 값으로 조회되면 안 됨
+            "INSERT INTO test_ks.users (id, name, city) VALUES (2, 'Lee', 'Seoul')",
+            "INSERT INTO test_ks.users (id, name, city) VALUES (4, 'Choi', 'Incheon')",
+        ] {
+            engine.execute(crate::query::parser::CqlParser::parse(query).unwrap()).await.unwrap();
+        }
+        
+        let select = || crate::query::parser::CqlParser::parse("SELECT name FROM test_ks.users WHERE city = 'Seoul'").unwrap();
+        
+        let (first, state) = engine.execute_paged(select(), 2, None).await.unwrap();
+        let first = match first { QueryResult::Rows(rows) => rows, _ => panic!("Expected rows result") };
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].get_column("name"), Some(&CassandraValue::Text("Kim".to_string())));
+        assert_eq!(first[1].get_column("name"), Some(&CassandraValue::Text("Lee".to_string())));
+        assert!(state.is_some());
+        
+        let (second, state) = engine.execute_paged(select(), 2, state).await.unwrap();
+        let second = match second { QueryResult::Rows(rows) => rows, _ => panic!("Expected rows result") };
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].get_column("name"), Some(&CassandraValue::Text("Park".to_string())));
+        assert!(state.is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_order_by_clustering_desc_with_paging() {
+        let mut engine = QueryEngine::new();
+        let parse = |query: &str| crate::query::parser::CqlParser::parse(query).unwrap();
+        
+        engine.execute(parse("CREATE KEYSPACE test_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}")).await.unwrap();
+        engine.execute(parse("CREATE TABLE test_ks.events (sensor INT, ts INT, reading INT, PRIMARY KEY (sensor, ts))")).await.unwrap();
+        for ts in 1..=5 {
+            engine.execute(parse(&format!("INSERT INTO test_ks.events (sensor, ts, reading) VALUES (1, {}, {})", ts, ts * 10))).await.unwrap();
+        }
+        engine.execute(parse("INSERT INTO test_ks.events (sensor, ts, reading) VALUES (2, 9, 90)")).await.unwrap();
+        
+        let readings = |result: QueryResult| match result {
+            QueryResult::Rows(rows) => rows.iter()
+                .map(|row| row.get_column("reading").cloned().unwrap())
+                .collect::<Vec<_>>(),
+            _ => panic!("Expected rows result"),
+        };
+        
+        // 최신 값 두 개 (tail 조회)
+        let result = engine.execute(parse("SELECT reading FROM test_ks.events WHERE sensor = 1 ORDER BY ts DESC LIMIT 2")).await.unwrap();
+        assert_eq!(readings(result), vec![CassandraValue::Int(50), CassandraValue::Int(40)]);
+        
+        let result = engine.execute(parse("SELECT reading FROM test_ks.events WHERE sensor = 1 ORDER BY ts ASC LIMIT 2")).await.unwrap();
+        assert_eq!(readings(result), vec![CassandraValue::Int(10), CassandraValue::Int(20)]);
+        
+        // 역순 페이징은 이전 페이지의 마지막 키보다 작은 행부터 이어감
+        let select = || parse("SELECT reading FROM test_ks.events WHERE sensor = 1 ORDER BY ts DESC");
+        let (first, state) = engine.execute_paged(select(), 3, None).await.unwrap();
+        assert_eq!(readings(first), vec![CassandraValue::Int(50), CassandraValue::Int(40), CassandraValue::Int(30)]);
+        let (second, state) = engine.execute_paged(select(), 3, state).await.unwrap();
+        assert_eq!(readings(second), vec![CassandraValue::Int(20), CassandraValue::Int(10)]);
+        assert!(state.is_none());
+        
+        // 파티션 키 없이 정렬하거나 첫 클러스터링 컬럼이 아닌 컬럼으로 정렬하면 거부
+        assert!(engine.execute(parse("SELECT reading FROM test_ks.events ORDER BY ts DESC")).await.is_err());
+        assert!(engine.execute(parse("SELECT reading FROM test_ks.events WHERE sensor = 1 ORDER BY reading DESC")).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_large_partitions_recorded_in_system_views() {
+        let mut engine = QueryEngine::new();
+        engine.set_large_partition_threshold(256);
+        let parse = |query: &str| crate::query::parser::CqlParser::parse(query).unwrap();
+        
+        engine.execute(parse("CREATE KEYSPACE test_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}")).await.unwrap();
+        engine.execute(parse("CREATE TABLE test_ks.events (sensor TEXT, ts INT, reading TEXT, PRIMARY KEY (sensor, ts))")).await.unwrap();
+        for ts in 0..8 {
+            engine.execute(parse(&format!("INSERT INTO test_ks.events (sensor, ts, reading) VALUES ('hot', {}, '{}')", ts, "x".repeat(64)))).await.unwrap();
+        }
+        engine.execute(parse("INSERT INTO test_ks.events (sensor, ts, reading) VALUES ('cold', 0, 'x')")).await.unwrap();
+        
+        let result = engine.execute(parse("SELECT * FROM system_views.large_partitions WHERE keyspace_name = 'test_ks' AND table_name = 'events'")).await.unwrap();
+        let rows = match result { QueryResult::Rows(rows) => rows, _ => panic!("Expected rows result") };
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get_column("partition_key"), Some(&CassandraValue::Text("hot".to_string())));
+        assert_eq!(rows[0].get_column("rows"), Some(&CassandraValue::BigInt(8)));
+        match rows[0].get_column("partition_size") {
+            Some(CassandraValue::BigInt(size)) => assert!(*size > 256),
+            other => panic!("Expected partition size, got {:?}", other),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_tracing_records_events_into_system_traces() {
+        let mut engine = QueryEngine::new();
+        let parse = |query: &str| crate::query::parser::CqlParser::parse(query).unwrap();
+        
+        for query in [
+            "CREATE KEYSPACE test_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}",
+            "CREATE TABLE test_ks.users (id INT PRIMARY KEY, name TEXT)",
+            "INSERT INTO test_ks.users (id, name) VALUES (1, 'Kim')",
+        ] {
+            engine.execute(parse(query)).await.unwrap();
+        }
+        
+        // 추적이 꺼져 있으면 기록하지 않음
+        engine.execute(parse("SELECT * FROM test_ks.users WHERE id = 1")).await.unwrap();
+        assert!(engine.get_memtable(TRACES_KEYSPACE, EVENTS_TABLE).is_err());
+        
+        // 엔진 전역 상태는 없음
+        assert!(engine.execute(parse("TRACING ON")).await.is_err());
+        
+        let query = "SELECT * FROM test_ks.users WHERE id = 1";
+        let (executed, trace) = QueryTrace::scope(Some(QueryTrace::new(query)), engine.execute(parse(query))).await;
+        executed.unwrap();
+        let session_id = engine.finish_trace(trace.expect("trace")).await.unwrap();
+        
+        let events = engine.execute(parse(&format!("SELECT activity FROM system_traces.events WHERE session_id = {}", session_id))).await.unwrap();
+        let activities: Vec<CassandraValue> = match events {
+            QueryResult::Rows(rows) => rows.into_iter().filter_map(|row| row.get_column("activity").cloned()).collect(),
+            _ => panic!("Expected rows result"),
+        };
+        assert_eq!(activities.first(), Some(&CassandraValue::Text("Executing single-partition query on test_ks.users".to_string())));
+        assert_eq!(activities.last(), Some(&CassandraValue::Text("Request complete".to_string())));
+        
+        let sessions = engine.execute(parse(&format!("SELECT request FROM system_traces.sessions WHERE session_id = {}", session_id))).await.unwrap();
+        match sessions {
+            QueryResult::Rows(rows) => assert_eq!(rows[0].get_column("request"), Some(&CassandraValue::Text(query.to_string()))),
+            _ => panic!("Expected rows result"),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_write_triggers_augment_and_reject() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        
+        struct CountWrites(Arc<AtomicUsize>);
+        impl Trigger for CountWrites {
+            fn after_write(&self, _row: &SchemaRow) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        
+        let mut engine = QueryEngine::new();
+        let parse = |query: &str| crate::query::parser::CqlParser::parse(query).unwrap();
+        
+        for query in [
+            "CREATE KEYSPACE test_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}",
+            "CREATE TABLE test_ks.items (id INT PRIMARY KEY, price INT, quantity INT, total INT)",
+        ] {
+            engine.execute(parse(query)).await.unwrap();
+        }
+        
+        // 파생 컬럼 계산
+        engine.register_trigger("test_ks", "items", "compute_total", Arc::new(|row: &mut SchemaRow| {
+            let value = |name: &str| match row.cells.get(name).map(|c| &c.value) {
+                Some(CassandraValue::Int(v)) => Ok(*v),
+                _ => Err(format!("{} is required", name)),
+            };
+            let total = value("price")? * value("quantity")?;
+            let timestamp = row.timestamp;
+            row.cells.insert("total", Cell {
+                value: CassandraValue::Int(total),
+                timestamp,
+                ttl: None,
+                is_deleted: false,
+            });
+            Ok(())
+        })).unwrap();
+        let writes = Arc::new(AtomicUsize::new(0));
+        engine.register_trigger("test_ks", "items", "count_writes", Arc::new(CountWrites(writes.clone()))).unwrap();
+        assert!(engine.register_trigger("test_ks", "items", "count_writes", Arc::new(CountWrites(writes.clone()))).is_err());
+        
+        engine.execute(parse("INSERT INTO test_ks.items (id, price, quantity) VALUES (1, 3, 4)")).await.unwrap();
+        let rejected = engine.execute(parse("INSERT INTO test_ks.items (id, price) VALUES (2, 5)")).await;
+        assert!(matches!(rejected, Err(CoreDBError::TriggerRejected { ref trigger, .. }) if trigger == "compute_total"));
+        assert_eq!(writes.load(Ordering::SeqCst), 1);
+        
+        match engine.execute(parse("SELECT * FROM test_ks.items")).await.unwrap() {
+            QueryResult::Rows(rows) => {
+                assert_eq!(rows.len(), 1);
+                assert_eq!(rows[0].get_column("total"), Some(&CassandraValue::Int(12)));
+            },
+            _ => panic!("Expected rows result"),
+        }
+        
+        assert!(engine.drop_trigger("test_ks", "items", "compute_total"));
+        engine.execute(parse("INSERT INTO test_ks.items (id, price) VALUES (2, 5)")).await.unwrap();
+        assert_eq!(writes.load(Ordering::SeqCst), 2);
+    }
+    
+    #[tokio::test]
+    async fn test_fulltext_match_through_custom_index() {
+        let mut engine = QueryEngine::new();
+        let parse = |query: &str| crate::query::parser::CqlParser::parse(query).unwrap();
+        
+        for query in [
+            "CREATE KEYSPACE test_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}",
+            "CREATE TABLE test_ks.posts (id INT PRIMARY KEY, body TEXT)",
+            "INSERT INTO test_ks.posts (id, body) VALUES (1, 'Rust storage engines')",
+            "CREATE CUSTOM INDEX ON test_ks.posts (body) USING 'fulltext'",
+            "INSERT INTO test_ks.posts (id, body) VALUES (2, 'Cassandra storage internals')",
+            "INSERT INTO test_ks.posts (id, body) VALUES (3, 'rust async runtimes')",
+        ] {
+            engine.execute(parse(query)).await.unwrap();
+        }
+        
+        let ids = |result: QueryResult| match result {
+            QueryResult::Rows(rows) => rows.into_iter().filter_map(|row| row.get_column("id").cloned()).collect::<Vec<_>>(),
+            _ => panic!("Expected rows result"),
+        };
+        
+        let result = engine.execute(parse("SELECT id FROM test_ks.posts WHERE match(body, 'RUST')")).await.unwrap();
+        assert_eq!(ids(result), vec![CassandraValue::Int(1), CassandraValue::Int(3)]);
+        
+        let result = engine.execute(parse("SELECT id FROM test_ks.posts WHERE match(body, 'storage rust')")).await.unwrap();
+        assert_eq!(ids(result), vec![CassandraValue::Int(1)]);
+    }
+    
+    #[tokio::test]
+    async fn test_ann_order_by_with_and_without_index() {
+        let mut engine = QueryEngine::new();
+        let parse = |query: &str| crate::query::parser::CqlParser::parse(query).unwrap();
+        
+        for query in [
+            "CREATE KEYSPACE test_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}",
+            "CREATE TABLE test_ks.docs (id INT PRIMARY KEY, category TEXT, embedding VECTOR<FLOAT, 3>)",
+            "INSERT INTO test_ks.docs (id, category, embedding) VALUES (1, 'a', [1.0, 0.0, 0.0])",
+            "INSERT INTO test_ks.docs (id, category, embedding) VALUES (2, 'b', [0.9, 0.1, 0.0])",
+            "INSERT INTO test_ks.docs (id, category, embedding) VALUES (3, 'a', [0.0, 1.0, 0.0])",
+            "INSERT INTO test_ks.docs (id, category, embedding) VALUES (4, 'a', [0.7, 0.7, 0.0])",
+        ] {
+            engine.execute(parse(query)).await.unwrap();
+        }
+        
+        // 차원이 맞지 않는 값은 거부
+        assert!(engine.execute(parse("INSERT INTO test_ks.docs (id, embedding) VALUES (5, [1.0, 0.0])")).await.is_err());
+        
+        let ids = |result: QueryResult| match result {
+            QueryResult::Rows(rows) => rows.into_iter().filter_map(|row| row.get_column("id").cloned()).collect::<Vec<_>>(),
+            _ => panic!("Expected rows result"),
+        };
+        let nearest = "SELECT id FROM test_ks.docs ORDER BY embedding ANN OF [1.0, 0.05, 0.0] LIMIT 3";
+        let filtered = "SELECT id FROM test_ks.docs WHERE category = 'a' ORDER BY embedding ANN OF [1.0, 0.05, 0.0] LIMIT 2";
+        
+        // 인덱스 없이 전체 스캔
+        assert_eq!(ids(engine.execute(parse(nearest)).await.unwrap()), vec![CassandraValue::Int(1), CassandraValue::Int(2), CassandraValue::Int(4)]);
+        
+        engine.execute(parse("CREATE CUSTOM INDEX ON test_ks.docs (embedding) USING 'hnsw'")).await.unwrap();
+        assert_eq!(ids(engine.execute(parse(nearest)).await.unwrap()), vec![CassandraValue::Int(1), CassandraValue::Int(2), CassandraValue::Int(4)]);
+        assert_eq!(ids(engine.execute(parse(filtered)).await.unwrap()), vec![CassandraValue::Int(1), CassandraValue::Int(4)]);
+        
+        assert!(engine.execute(parse("SELECT id FROM test_ks.docs ORDER BY embedding ANN OF [1.0, 0.0] LIMIT 1")).await.is_err());
+        assert!(engine.execute(parse("CREATE CUSTOM INDEX ON test_ks.docs (category) USING 'hnsw'")).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_within_bounding_box_filter() {
+        let mut engine = QueryEngine::new();
+        let parse = |query: &str| crate::query::parser::CqlParser::parse(query).unwrap();
+        
+        for query in [
+            "CREATE KEYSPACE test_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}",
+            "CREATE TABLE test_ks.events (id INT PRIMARY KEY, location POINT, cell TEXT)",
+        ] {
+            engine.execute(parse(query)).await.unwrap();
+        }
+        engine.register_trigger("test_ks", "events", "geohash", Arc::new(crate::geo::geohash_trigger("location", "cell", 5))).unwrap();
+        
+        for query in [
+            "INSERT INTO test_ks.events (id, location) VALUES (1, POINT(37.5665, 126.978))",
+            "INSERT INTO test_ks.events (id, location) VALUES (2, POINT(35.1796, 129.0756))",
+            "INSERT INTO test_ks.events (id, location) VALUES (3, POINT(37.4563, 126.7052))",
+        ] {
+            engine.execute(parse(query)).await.unwrap();
+        }
+        
+        let result = engine.execute(parse("SELECT id, cell FROM test_ks.events WHERE location WITHIN BOX(37.4, 126.8, 37.7, 127.2)")).await.unwrap();
+        match result {
+            QueryResult::Rows(rows) => {
+                assert_eq!(rows.len(), 1);
+                assert_eq!(rows[0].get_column("id"), Some(&CassandraValue::Int(1)));
+                assert_eq!(rows[0].get_column("cell"), Some(&CassandraValue::Text(crate::geo::geohash(37.5665, 126.978, 5))));
+            },
+            _ => panic!("Expected rows result"),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_clustering_slice_skips_sstables_outside_range() {
+        let mut engine = QueryEngine::new();
+        let parse = |query: &str| crate::query::parser::CqlParser::parse(query).unwrap();
+        let directory = std::env::temp_dir().join("coredb_test_clustering_slice");
+        let _ = tokio::fs::remove_dir_all(&directory).await;
+        tokio::fs::create_dir_all(&directory).await.unwrap();
+        
+        for query in [
+            "CREATE KEYSPACE test_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}",
+            "CREATE TABLE test_ks.events (sensor INT, ts INT, reading INT, PRIMARY KEY (sensor, ts))",
+        ] {
+            engine.execute(parse(query)).await.unwrap();
+        }
+        
+        // ts 1..=5와 6..=10을 각각 다른 SSTable로 플러시
+        let mut ids = Vec::new();
+        for range in [1..=5, 6..=10] {
+            for ts in range {
+                engine.execute(parse(&format!("INSERT INTO test_ks.events (sensor, ts, reading) VALUES (1, {}, {})", ts, ts * 10))).await.unwrap();
+            }
+            let memtable = engine.get_memtable("test_ks", "events").unwrap();
+            let sstable = SSTable::create_from_memtable(&memtable, &directory, crate::storage::CompressionType::None).await.unwrap();
+            ids.push(sstable.id.clone());
+            engine.add_sstable("test_ks".to_string(), "events".to_string(), Arc::new(sstable));
+            engine.replace_memtable("test_ks".to_string(), "events".to_string(), Arc::new(Memtable::new(memtable.table_schema().clone())));
+        }
+        
+        let query = "SELECT ts FROM test_ks.events WHERE sensor = 1 AND ts > 7";
+        let (executed, trace) = QueryTrace::scope(Some(QueryTrace::new(query)), engine.execute(parse(query))).await;
+        let rows = match executed.unwrap() {
+            QueryResult::Rows(rows) => rows,
+            _ => panic!("Expected rows result"),
+        };
+        let session_id = engine.finish_trace(trace.expect("trace")).await.unwrap();
+        let timestamps: Vec<CassandraValue> = rows.iter().filter_map(|row| row.get_column("ts").cloned()).collect();
+        assert_eq!(timestamps, vec![CassandraValue::Int(8), CassandraValue::Int(9), CassandraValue::Int(10)]);
+        
+        let events = engine.execute(parse(&format!("SELECT activity FROM system_traces.events WHERE session_id = {}", session_id))).await.unwrap();
+        let activities: Vec<CassandraValue> = match events {
+            QueryResult::Rows(rows) => rows.into_iter().filter_map(|row| row.get_column("activity").cloned()).collect(),
+            _ => panic!("Expected rows result"),
+        };
+        assert!(activities.contains(&CassandraValue::Text(format!("Skipped SSTable {} (clustering range)", ids[0]))));
+        assert!(activities.contains(&CassandraValue::Text(format!("Read 3 row(s) from SSTable {}", ids[1]))));
+        
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_frozen_memtable_readable_until_flush_finishes() {
+        let mut engine = QueryEngine::new();
+        let parse = |query: &str| crate::query::parser::CqlParser::parse(query).unwrap();
+        let directory = std::env::temp_dir().join("coredb_test_frozen_memtable_readable");
+        let _ = tokio::fs::remove_dir_all(&directory).await;
+        tokio::fs::create_dir_all(&directory).await.unwrap();
+        
+        for query in [
+            "CREATE KEYSPACE test_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}",
+            "CREATE TABLE test_ks.users (id INT PRIMARY KEY, name TEXT)",
+            "INSERT INTO test_ks.users (id, name) VALUES (1, 'kim')",
+            "INSERT INTO test_ks.users (id, name) VALUES (2, 'lee')",
+        ] {
+            engine.execute(parse(query)).await.unwrap();
+        }
+        let names = |result: QueryResult| match result {
+            QueryResult::Rows(rows) => rows.into_iter().filter_map(|row| row.get_column("name").cloned()).collect::<Vec<_>>(),
+            _ => panic!("Expected rows result"),
+        };
+        let text = |value: &str| CassandraValue::Text(value.to_string());
+        
+        // 고정된 메모리 테이블은 SSTable이 붙기 전에도 새 메모리 테이블의 쓰기와 병합되어 읽힘
+        let frozen = engine.freeze_memtable("test_ks", "users").unwrap().expect("memtable has rows");
+        assert!(engine.freeze_memtable("test_ks", "users").unwrap().is_none());
+        engine.execute(parse("INSERT INTO test_ks.users (id, name) VALUES (2, 'park')")).await.unwrap();
+        assert_eq!(names(engine.execute(parse("SELECT name FROM test_ks.users WHERE id = 1")).await.unwrap()), vec![text("kim")]);
+        assert_eq!(names(engine.execute(parse("SELECT name FROM test_ks.users WHERE id = 2")).await.unwrap()), vec![text("park")]);
+        assert_eq!(names(engine.execute(parse("SELECT name FROM test_ks.users")).await.unwrap()).len(), 2);
+        
+        let sstable = SSTable::create_from_memtable(&frozen, &directory, crate::storage::CompressionType::None).await.unwrap();
+        engine.finish_flush("test_ks", "users", &frozen, Arc::new(sstable));
+        assert!(engine.flushing_memtables("test_ks", "users").is_empty());
+        assert_eq!(engine.sstables("test_ks", "users").len(), 1);
+        assert_eq!(names(engine.execute(parse("SELECT name FROM test_ks.users WHERE id = 1")).await.unwrap()), vec![text("kim")]);
+        assert_eq!(names(engine.execute(parse("SELECT name FROM test_ks.users WHERE id = 2")).await.unwrap()), vec![text("park")]);
+        
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_index_update_after_flush_drops_old_value() {
+        let mut engine = QueryEngine::new();
+        let parse = |query: &str| crate::query::parser::CqlParser::parse(query).unwrap();
+        let directory = std::env::temp_dir().join("coredb_test_index_update_after_flush");
+        let _ = tokio::fs::remove_dir_all(&directory).await;
+        tokio::fs::create_dir_all(&directory).await.unwrap();
+        
+        for query in [
+            "CREATE KEYSPACE test_ks WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}",
+            "CREATE TABLE test_ks.users (id INT PRIMARY KEY, city TEXT, name TEXT)",
+            "CREATE INDEX ON test_ks.users (city)",
+            "INSERT INTO test_ks.users (id, city, name) VALUES (1, 'Seoul', 'kim')",
+        ] {
+            engine.execute(parse(query)).await.unwrap();
+        }
+        let memtable = engine.get_memtable("test_ks", "users").unwrap();
+        let sstable = SSTable::create_from_memtable(&memtable, &directory, crate::storage::CompressionType::None).await.unwrap();
+        engine.add_sstable("test_ks".to_string(), "users".to_string(), Arc::new(sstable));
+        engine.replace_memtable("test_ks".to_string(), "users".to_string(), Arc::new(Memtable::new(memtable.table_schema().clone())));
+        
+        // 이전 값이 SSTable에만 있어도 인덱스에서 빠져야 함
+        engine.execute(parse("INSERT INTO test_ks.users (id, city) VALUES (1, 'Busan')")).await.unwrap();
+        // 인덱스 컬럼을 건드리지 않는 쓰기는 인덱스를 바꾸지 않음
+        engine.execute(parse("INSERT INTO test_ks.users (id, name) VALUES (1, 'lee')")).await.unwrap();
+        
+        {
+            let indexes = engine.indexes["test_ks"]["users"].secondary.read().unwrap();
+            let index = &indexes[0];
+            assert_eq!(index.value_count(), 1);
+            assert!(index.lookup(&CassandraValue::Text("Seoul".to_string())).is_empty());
+            assert_eq!(index.lookup(&CassandraValue::Text("Busan".to_string())).len(), 1);
+        }
+        
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
 }
@@ -1,7 +1,12 @@
 pub mod parser;
 pub mod engine;
 pub mod result;
+pub mod tracing;
+pub mod system_views;
+pub mod trigger;
 
 pub use parser::*;
 pub use engine::*;
 pub use result::*;
+pub use tracing::QueryTrace;
+pub use trigger::{Trigger, RegisteredTrigger};
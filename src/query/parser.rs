@@ -1,4 +1,7 @@
-use crate::schema::{CassandraValue, CassandraDataType, ColumnDefinition};
+use crate::schema::{CassandraValue, CassandraDataType, ColumnDefinition, CompactionStrategy, TableSchema};
+use crate::storage::{CompressionType, IndexKind};
+use crate::geo::BoundingBox;
+use crate::auth::{Permission, Resource};
 use crate::error::*;
 
 /// CQL 문 타입
@@ -16,6 +19,13 @@ pub enum CqlStatement {
         clustering_key: Vec<String>,
         options: TableOptions,
     },
+    CreateIndex {
+        keyspace: String,
+        table: String,
+        name: String,
+        column: String,
+        kind: IndexKind,
+    },
     Insert {
         keyspace: String,
         table: String,
@@ -26,6 +36,7 @@ pub enum CqlStatement {
         table: String,
         columns: Vec<String>,
         where_clause: Option<WhereClause>,
+        order_by: Option<OrderBy>,
         limit: Option<u32>,
     },
     Update {
@@ -39,6 +50,12 @@ pub enum CqlStatement {
         table: String,
         where_clause: WhereClause,
     },
+    /// ALTER TABLE <ks>.<table> WITH compaction = {...}
+    AlterTable {
+        keyspace: String,
+        name: String,
+        compaction_strategy: CompactionStrategy,
+    },
     DropTable {
         keyspace: String,
         name: String,
@@ -49,6 +66,47 @@ pub enum CqlStatement {
     Use {
         keyspace: String,
     },
+    /// TRACING ON / OFF
+    Tracing {
+        enabled: bool,
+    },
+    /// CREATE ROLE / CREATE USER
+    CreateRole {
+        name: String,
+        options: RoleOptions,
+        if_not_exists: bool,
+    },
+    /// ALTER ROLE / ALTER USER (주어진 옵션만 바꿈)
+    AlterRole {
+        name: String,
+        options: RoleOptions,
+    },
+    /// DROP ROLE / DROP USER
+    DropRole {
+        name: String,
+        if_exists: bool,
+    },
+    /// GRANT <permission> ON <resource> TO <role>
+    Grant {
+        permissions: Vec<Permission>,
+        resource: Resource,
+        role: String,
+    },
+    /// REVOKE <permission> ON <resource> FROM <role>
+    Revoke {
+        permissions: Vec<Permission>,
+        resource: Resource,
+        role: String,
+    },
+}
+
+/// SELECT 정렬
+#[derive(Debug, Clone)]
+pub enum OrderBy {
+    /// ORDER BY <column> ANN OF [..]: 질의 벡터와 가까운 순
+    Ann { column: String, vector: Vec<f32> },
+    /// ORDER BY <clustering column> [ASC | DESC]: 파티션 내 클러스터링 순서
+    Clustering { column: String, descending: bool },
 }
 
 /// 키스페이스 옵션
@@ -56,14 +114,160 @@ pub enum CqlStatement {
 pub struct KeyspaceOptions {
     pub replication_factor: u32,
     pub strategy: String,
+    /// WITH ... AND durable_writes = false
+    pub durable_writes: bool,
+}
+
+/// 역할 옵션 (WITH PASSWORD = '..' AND LOGIN = true AND SUPERUSER = false, 없는 옵션은 None)
+#[derive(Clone, Default, PartialEq)]
+pub struct RoleOptions {
+    pub password: Option<String>,
+    pub login: Option<bool>,
+    pub superuser: Option<bool>,
+}
+
+impl std::fmt::Debug for RoleOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RoleOptions")
+            .field("password", &self.password.as_ref().map(|_| "***"))
+            .field("login", &self.login)
+            .field("superuser", &self.superuser)
+            .finish()
+    }
+}
+
+/// 비밀번호 리터럴 (PASSWORD = '..', CREATE USER 문법은 = 생략 가능)
+const PASSWORD_PATTERN: &str = r"(?i)\bPASSWORD(\s*=?\s*)'((?:[^']|'')*)'";
+
+/// 로그나 추적 기록에 남기기 전에 쿼리의 비밀번호를 가림
+pub fn redact_passwords(query: &str) -> std::borrow::Cow<'_, str> {
+    match regex::Regex::new(PASSWORD_PATTERN) {
+        Ok(re) => re.replace_all(query, "PASSWORD$1'***'"),
+        Err(_) => std::borrow::Cow::Borrowed(query),
+    }
 }
 
 /// 테이블 옵션
 #[derive(Debug, Clone)]
 pub struct TableOptions {
-    pub compaction_strategy: String,
+    /// WITH compaction = {'class': '<전략>', ...}
+    pub compaction_strategy: Option<CompactionStrategy>,
     pub bloom_filter_fp_chance: f64,
     pub default_time_to_live: Option<u32>,
+    /// WITH memtable_flush_threshold_mb = <n>
+    pub memtable_flush_threshold_mb: Option<u64>,
+    /// WITH compression = {'class': '<압축기>'}
+    pub compression: Option<CompressionType>,
+}
+
+/// CREATE TABLE 정의로 테이블 스키마 구성
+///
+/// 키 컬럼은 컬럼 선언 순서가 아니라 PRIMARY KEY 절의 순서를 따름
+pub fn build_table_schema(keyspace: &str, name: &str, columns: &[ColumnDefinition], partition_key: &[String], clustering_key: &[String], options: &TableOptions) -> TableSchema {
+    let mut pk_columns = Vec::new();
+    let mut ck_columns = Vec::new();
+    let mut regular_columns = Vec::new();
+    let mut static_columns = Vec::new();
+    
+    for column in columns.iter().cloned() {
+        if partition_key.contains(&column.name) {
+            pk_columns.push(column);
+        } else if clustering_key.contains(&column.name) {
+            ck_columns.push(column);
+        } else if column.is_static {
+            static_columns.push(column);
+        } else {
+            regular_columns.push(column);
+        }
+    }
+    pk_columns.sort_by_key(|column| partition_key.iter().position(|name| *name == column.name));
+    ck_columns.sort_by_key(|column| clustering_key.iter().position(|name| *name == column.name));
+    
+    let mut schema = TableSchema::new(
+        name.to_string(),
+        keyspace.to_string(),
+        pk_columns,
+        ck_columns,
+        regular_columns,
+        static_columns,
+    );
+    schema.options.memtable_flush_threshold_mb = options.memtable_flush_threshold_mb;
+    if let Some(compression) = options.compression {
+        schema.options.compression = compression;
+    }
+    if let Some(strategy) = &options.compaction_strategy {
+        schema.options.compaction_strategy = strategy.clone();
+    }
+    schema
+}
+
+impl CqlStatement {
+    /// CREATE TABLE 문의 테이블 스키마 (다른 문은 None)
+    pub fn table_schema(&self) -> Option<TableSchema> {
+        match self {
+            CqlStatement::CreateTable { keyspace, name, columns, partition_key, clustering_key, options } => {
+                Some(build_table_schema(keyspace, name, columns, partition_key, clustering_key, options))
+            },
+            _ => None,
+        }
+    }
+    
+    /// 문 종류 (지표 이름표)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CqlStatement::CreateKeyspace { .. } => "create_keyspace",
+            CqlStatement::CreateTable { .. } => "create_table",
+            CqlStatement::CreateIndex { .. } => "create_index",
+            CqlStatement::Insert { .. } => "insert",
+            CqlStatement::Select { .. } => "select",
+            CqlStatement::Update { .. } => "update",
+            CqlStatement::Delete { .. } => "delete",
+            CqlStatement::AlterTable { .. } => "alter_table",
+            CqlStatement::DropTable { .. } => "drop_table",
+            CqlStatement::DropKeyspace { .. } => "drop_keyspace",
+            CqlStatement::Use { .. } => "use",
+            CqlStatement::Tracing { .. } => "tracing",
+            CqlStatement::CreateRole { .. } => "create_role",
+            CqlStatement::AlterRole { .. } => "alter_role",
+            CqlStatement::DropRole { .. } => "drop_role",
+            CqlStatement::Grant { .. } => "grant",
+            CqlStatement::Revoke { .. } => "revoke",
+        }
+    }
+    
+    /// 키스페이스나 테이블 정의를 바꾸는 문 (스키마 버전을 올림)
+    pub fn is_schema_change(&self) -> bool {
+        matches!(self,
+            CqlStatement::CreateKeyspace { .. }
+            | CqlStatement::CreateTable { .. }
+            | CqlStatement::CreateIndex { .. }
+            | CqlStatement::AlterTable { .. }
+            | CqlStatement::DropTable { .. }
+            | CqlStatement::DropKeyspace { .. })
+    }
+    
+    /// 스키마 문이 바꾸는 키스페이스 (스키마 문이 아니면 None)
+    pub fn schema_keyspace(&self) -> Option<&str> {
+        match self {
+            CqlStatement::CreateKeyspace { name, .. } | CqlStatement::DropKeyspace { name } => Some(name),
+            CqlStatement::CreateTable { keyspace, .. }
+            | CqlStatement::CreateIndex { keyspace, .. }
+            | CqlStatement::AlterTable { keyspace, .. }
+            | CqlStatement::DropTable { keyspace, .. } => Some(keyspace),
+            _ => None,
+        }
+    }
+    
+    /// 행을 읽거나 쓰는 문의 키스페이스 (스키마와 역할 관리 문은 None)
+    pub fn data_keyspace(&self) -> Option<&str> {
+        match self {
+            CqlStatement::Insert { keyspace, .. }
+            | CqlStatement::Select { keyspace, .. }
+            | CqlStatement::Update { keyspace, .. }
+            | CqlStatement::Delete { keyspace, .. } => Some(keyspace),
+            _ => None,
+        }
+    }
 }
 
 /// WHERE 절 조건
@@ -91,6 +295,10 @@ pub enum ComparisonOperator {
     LessThanOrEqual,
     In,
     Like,
+    /// match(column, 'terms'): 모든 토큰을 포함하는 텍스트
+    Match,
+    /// column WITHIN BOX(south, west, north, east): 영역 안의 POINT
+    Within,
 }
 
 /// 간단한 CQL 파서 (실제 구현에서는 더 정교한 파서가 필요)
@@ -100,10 +308,21 @@ impl CqlParser {
     pub fn parse(query: &str) -> Result<CqlStatement> {
         let query = query.trim();
         
-        if query.to_uppercase().starts_with("CREATE KEYSPACE") {
+        let role = regex::Regex::new(r"(?i)^(CREATE|ALTER|DROP)\s+(ROLE|USER)\b")?;
+        if let Some(caps) = role.captures(query) {
+            let user = caps[2].eq_ignore_ascii_case("USER");
+            match caps[1].to_uppercase().as_str() {
+                "DROP" => Self::parse_drop_role(query),
+                _ => Self::parse_create_or_alter_role(query, user),
+            }
+        } else if regex::Regex::new(r"(?i)^(GRANT|REVOKE)\s")?.is_match(query) {
+            Self::parse_grant_or_revoke(query)
+        } else if query.to_uppercase().starts_with("CREATE KEYSPACE") {
             Self::parse_create_keyspace(query)
         } else if query.to_uppercase().starts_with("CREATE TABLE") {
             Self::parse_create_table(query)
+        } else if query.to_uppercase().starts_with("CREATE INDEX") || query.to_uppercase().starts_with("CREATE CUSTOM INDEX") {
+            Self::parse_create_index(query)
         } else if query.to_uppercase().starts_with("INSERT") {
             Self::parse_insert(query)
         } else if query.to_uppercase().starts_with("SELECT") {
@@ -112,12 +331,16 @@ impl CqlParser {
             Self::parse_update(query)
         } else if query.to_uppercase().starts_with("DELETE") {
             Self::parse_delete(query)
+        } else if query.to_uppercase().starts_with("ALTER TABLE") {
+            Self::parse_alter_table(query)
         } else if query.to_uppercase().starts_with("DROP TABLE") {
             Self::parse_drop_table(query)
         } else if query.to_uppercase().starts_with("DROP KEYSPACE") {
             Self::parse_drop_keyspace(query)
         } else if query.to_uppercase().starts_with("USE") {
             Self::parse_use(query)
+        } else if query.to_uppercase().starts_with("TRACING") {
+            Self::parse_tracing(query)
         } else {
             Err(CoreDBError::QueryParsingError {
                 message: format!("Unsupported query type: {}", query),
@@ -132,12 +355,16 @@ impl CqlParser {
         if let Some(caps) = re.captures(query) {
             let name = caps.get(1).unwrap().as_str().to_string();
             let replication_factor = caps.get(2).unwrap().as_str().parse::<u32>()?;
+            let durable_writes = regex::Regex::new(r"(?i)\bdurable_writes\s*=\s*(true|false)\b")?
+                .captures(query)
+                .is_none_or(|caps| caps[1].eq_ignore_ascii_case("true"));
             
             Ok(CqlStatement::CreateKeyspace {
                 name,
                 options: KeyspaceOptions {
                     replication_factor,
                     strategy: "SimpleStrategy".to_string(),
+                    durable_writes,
                 },
             })
         } else {
@@ -147,6 +374,75 @@ impl CqlParser {
         }
     }
     
+    /// WITH compression = {'class': '<압축기>'} (Cassandra 압축기 이름, 'enabled': 'false'면 압축하지 않음)
+    fn parse_compression(options: &str) -> Result<Option<CompressionType>> {
+        let Some(body) = Self::map_option(options, "compression")? else {
+            return Ok(None);
+        };
+        let option = |name: &str| Self::map_entry(body, name);
+        if option("enabled")?.is_some_and(|enabled| enabled.eq_ignore_ascii_case("false")) {
+            return Ok(Some(CompressionType::None));
+        }
+        let class = option("class")?.ok_or_else(|| CoreDBError::QueryParsingError {
+            message: "compression needs a 'class'".to_string(),
+        })?;
+        let compression = match class.rsplit('.').next().unwrap_or(&class) {
+            "LZ4Compressor" => CompressionType::LZ4,
+            "SnappyCompressor" => CompressionType::Snappy,
+            "ZstdCompressor" => CompressionType::ZSTD,
+            "ZstdDictionaryCompressor" => CompressionType::ZSTDDictionary,
+            "NoopCompressor" => CompressionType::None,
+            other => return Err(CoreDBError::QueryParsingError {
+                message: format!("Unsupported compression class: {}", other),
+            }),
+        };
+        Ok(Some(compression))
+    }
+    
+    /// WITH compaction = {'class': '<전략>'} (Cassandra 전략 클래스 이름)
+    ///
+    /// TimeWindowCompactionStrategy는 'compaction_window_unit'(MINUTES/HOURS/DAYS, 기본 DAYS)과
+    /// 'compaction_window_size'(기본 1)로 시간 창 크기를 정함
+    fn parse_compaction(options: &str) -> Result<Option<CompactionStrategy>> {
+        let Some(body) = Self::map_option(options, "compaction")? else {
+            return Ok(None);
+        };
+        let invalid = |message: String| CoreDBError::QueryParsingError { message };
+        let class = Self::map_entry(body, "class")?.ok_or_else(|| invalid("compaction needs a 'class'".to_string()))?;
+        let strategy = match class.rsplit('.').next().unwrap_or(&class) {
+            "SizeTieredCompactionStrategy" => CompactionStrategy::SizeTiered,
+            "LeveledCompactionStrategy" => CompactionStrategy::Leveled,
+            "TimeWindowCompactionStrategy" => {
+                let unit_seconds = match Self::map_entry(body, "compaction_window_unit")?.as_deref().map(str::to_ascii_uppercase).as_deref() {
+                    Some("MINUTES") => 60,
+                    Some("HOURS") => 60 * 60,
+                    Some("DAYS") | None => 24 * 60 * 60,
+                    Some(other) => return Err(invalid(format!("Unsupported compaction_window_unit: {}", other))),
+                };
+                let size = match Self::map_entry(body, "compaction_window_size")? {
+                    Some(size) => size.parse::<u64>().ok().filter(|size| *size > 0)
+                        .ok_or_else(|| invalid(format!("Invalid compaction_window_size: {}", size)))?,
+                    None => 1,
+                };
+                CompactionStrategy::TimeWindow { window_seconds: unit_seconds * size }
+            },
+            other => return Err(invalid(format!("Unsupported compaction strategy: {}", other))),
+        };
+        Ok(Some(strategy))
+    }
+    
+    /// WITH <name> = {...} 맵 옵션의 본문
+    fn map_option<'a>(options: &'a str, name: &str) -> Result<Option<&'a str>> {
+        let re = regex::Regex::new(&format!(r"(?i)\b{}\s*=\s*\{{([^}}]*)\}}", name))?;
+        Ok(re.captures(options).map(|caps| caps.get(1).unwrap().as_str()))
+    }
+    
+    /// 맵 옵션 본문의 '<key>': '<value>' 값 (따옴표 없는 숫자도 허용)
+    fn map_entry(body: &str, key: &str) -> Result<Option<String>> {
+        let re = regex::Regex::new(&format!(r"(?i)'{}'\s*:\s*(?:'([^']*)'|([\w.]+))", key))?;
+        Ok(re.captures(body).and_then(|caps| caps.get(1).or(caps.get(2))).map(|value| value.as_str().to_string()))
+    }
+    
     fn parse_create_table(query: &str) -> Result<CqlStatement> {
         // 매우 간단한 파싱 - 실제로는 더 정교한 파서가 필요
         let re = regex::Regex::new(r"CREATE\s+TABLE\s+(\w+)\.(\w+)\s*\((.*)\)")?;
@@ -155,17 +451,37 @@ impl CqlParser {
             let keyspace = caps.get(1).unwrap().as_str().to_string();
             let name = caps.get(2).unwrap().as_str().to_string();
             let columns_str = caps.get(3).unwrap().as_str();
+            let options_str = &query[caps.get(0).unwrap().end()..];
             
             // 컬럼 파싱 (매우 간단한 버전)
             let mut columns = Vec::new();
             let mut partition_key = Vec::new();
             let mut clustering_key = Vec::new();
             
-            for column_def in columns_str.split(',') {
-                let parts: Vec<&str> = column_def.trim().split_whitespace().collect();
+            // VECTOR<FLOAT, n>처럼 타입 안의 쉼표는 컬럼 구분자가 아님
+            let type_re = regex::Regex::new(r"(?i)^\s*\w+\s+(\w+\s*<[^>]*>|\w+)")?;
+            let primary_key_re = regex::Regex::new(r"(?i)^PRIMARY\s+KEY\s*\((.*)\)$")?;
+            for column_def in Self::split_top_level(columns_str) {
+                // PRIMARY KEY ((pk1, pk2), ck1, ck2) 또는 PRIMARY KEY (pk, ck1)
+                if let Some(caps) = primary_key_re.captures(column_def) {
+                    let mut components = Self::split_top_level(caps.get(1).unwrap().as_str()).into_iter();
+                    partition_key = components.next()
+                        .map(|first| first.trim_start_matches('(').trim_end_matches(')')
+                            .split(',')
+                            .map(|column| column.trim().to_string())
+                            .filter(|column| !column.is_empty())
+                            .collect())
+                        .unwrap_or_default();
+                    clustering_key = components.map(|column| column.to_string()).collect();
+                    continue;
+                }
+                let parts: Vec<&str> = column_def.split_whitespace().collect();
                 if parts.len() >= 2 {
                     let column_name = parts[0].to_string();
-                    let data_type = Self::parse_data_type(parts[1])?;
+                    let type_str = type_re.captures(column_def)
+                        .map(|caps| caps.get(1).unwrap().as_str())
+                        .unwrap_or(parts[1]);
+                    let data_type = Self::parse_data_type(type_str)?;
                     
                     let is_static = parts.contains(&"STATIC");
                     let is_partition_key = parts.contains(&"PRIMARY") || parts.contains(&"KEY");
@@ -189,9 +505,17 @@ impl CqlParser {
                 partition_key,
                 clustering_key,
                 options: TableOptions {
-                    compaction_strategy: "SizeTieredCompactionStrategy".to_string(),
+                    compaction_strategy: Self::parse_compaction(options_str)?,
                     bloom_filter_fp_chance: 0.01,
                     default_time_to_live: None,
+                    memtable_flush_threshold_mb: regex::Regex::new(r"(?i)\bmemtable_flush_threshold_mb\s*=\s*(\d+)")?
+                        .captures(options_str)
+                        .map(|caps| caps.get(1).unwrap().as_str().parse())
+                        .transpose()
+                        .map_err(|_| CoreDBError::QueryParsingError {
+                            message: "Invalid memtable_flush_threshold_mb".to_string(),
+                        })?,
+                    compression: Self::parse_compression(options_str)?,
                 },
             })
         } else {
@@ -201,9 +525,52 @@ impl CqlParser {
         }
     }
     
+    fn parse_create_index(query: &str) -> Result<CqlStatement> {
+        let re = regex::Regex::new(r"(?i)CREATE\s+(CUSTOM\s+)?INDEX\s+(?:IF\s+NOT\s+EXISTS\s+)?(?:(\w+)\s+)?ON\s+(\w+)\.(\w+)\s*\(\s*(\w+)\s*\)(?:\s+USING\s+'([^']+)')?")?;
+        
+        if let Some(caps) = re.captures(query) {
+            let keyspace = caps.get(3).unwrap().as_str().to_string();
+            let table = caps.get(4).unwrap().as_str().to_string();
+            let column = caps.get(5).unwrap().as_str().to_string();
+            // 이름이 없으면 Cassandra 기본 규칙 (<table>_<column>_idx)
+            let name = caps.get(2)
+                .map(|m| m.as_str().to_string())
+                .unwrap_or_else(|| format!("{}_{}_idx", table, column));
+            
+            let kind = match (caps.get(1).is_some(), caps.get(6).map(|m| m.as_str())) {
+                (false, None) => IndexKind::Standard,
+                (true, Some(class)) if class.eq_ignore_ascii_case("fulltext") => IndexKind::FullText,
+                (true, Some(class)) if class.eq_ignore_ascii_case("hnsw") => IndexKind::Vector,
+                (true, Some(class)) => {
+                    return Err(CoreDBError::QueryParsingError {
+                        message: format!("Unsupported custom index class: {}", class),
+                    });
+                },
+                _ => {
+                    return Err(CoreDBError::QueryParsingError {
+                        message: "CREATE CUSTOM INDEX requires USING '<class>'".to_string(),
+                    });
+                },
+            };
+            
+            Ok(CqlStatement::CreateIndex {
+                keyspace,
+                table,
+                name,
+                column,
+                kind,
+            })
+        } else {
+            Err(CoreDBError::QueryParsingError {
+                message: "Invalid CREATE INDEX syntax".to_string(),
+            })
+        }
+    }
+    
     fn parse_insert(query: &str) -> Result<CqlStatement> {
         // 간단한 INSERT 파싱
-        let re = regex::Regex::new(r"INSERT\s+INTO\s+(\w+)\.(\w+)\s*\(([^)]+)\)\s*VALUES\s*\(([^)]+)\)")?;
+        // 값 목록은 POINT(..) 같은 괄호를 포함할 수 있으므로 마지막 닫는 괄호까지
+        let re = regex::Regex::new(r"(?s)INSERT\s+INTO\s+(\w+)\.(\w+)\s*\(([^)]+)\)\s*VALUES\s*\((.*)\)")?;
         
         if let Some(caps) = re.captures(query) {
             let keyspace = caps.get(1).unwrap().as_str().to_string();
@@ -212,7 +579,7 @@ impl CqlParser {
             let values_str = caps.get(4).unwrap().as_str();
             
             let columns: Vec<&str> = columns_str.split(',').map(|s| s.trim()).collect();
-            let values: Vec<&str> = values_str.split(',').map(|s| s.trim()).collect();
+            let values: Vec<&str> = Self::split_top_level(values_str);
             
             if columns.len() != values.len() {
                 return Err(CoreDBError::QueryParsingError {
@@ -260,6 +627,27 @@ impl CqlParser {
                 None
             };
             
+            // ORDER BY <column> ANN OF [..] 파싱
+            let order_by = match regex::Regex::new(r"(?i)ORDER\s+BY\s+(\w+)\s+ANN\s+OF\s+(\[[^\]]*\])")?.captures(query) {
+                Some(caps) => {
+                    let vector = match Self::parse_value(caps.get(2).unwrap().as_str())? {
+                        CassandraValue::List(items) => items.iter().map(Self::as_f32).collect::<Option<Vec<f32>>>(),
+                        _ => None,
+                    };
+                    Some(OrderBy::Ann {
+                        column: caps.get(1).unwrap().as_str().to_string(),
+                        vector: vector.ok_or_else(|| CoreDBError::QueryParsingError {
+                            message: "ANN OF requires a numeric vector literal".to_string(),
+                        })?,
+                    })
+                },
+                None => regex::Regex::new(r"(?i)ORDER\s+BY\s+(\w+)(?:\s+(ASC|DESC))?")?.captures(query)
+                    .map(|caps| OrderBy::Clustering {
+                        column: caps.get(1).unwrap().as_str().to_string(),
+                        descending: caps.get(2).is_some_and(|m| m.as_str().eq_ignore_ascii_case("DESC")),
+                    }),
+            };
+            
             // LIMIT 파싱
             let limit = if let Some(limit_match) = regex::Regex::new(r"LIMIT\s+(\d+)")?.captures(query) {
                 Some(limit_match.get(1).unwrap().as_str().parse::<u32>()?)
@@ -272,6 +660,7 @@ impl CqlParser {
                 table,
                 columns,
                 where_clause,
+                order_by,
                 limit,
             })
         } else {
@@ -281,20 +670,35 @@ impl CqlParser {
         }
     }
     
-    fn parse_update(query: &str) -> Result<CqlStatement> {
+    fn parse_update(_query: &str) -> Result<CqlStatement> {
         // 간단한 UPDATE 파싱
         Err(CoreDBError::QueryParsingError {
             message: "UPDATE not implemented yet".to_string(),
         })
     }
     
-    fn parse_delete(query: &str) -> Result<CqlStatement> {
+    fn parse_delete(_query: &str) -> Result<CqlStatement> {
         // 간단한 DELETE 파싱
         Err(CoreDBError::QueryParsingError {
             message: "DELETE not implemented yet".to_string(),
         })
     }
     
+    /// 지금은 컴팩션 전략 변경만 지원
+    fn parse_alter_table(query: &str) -> Result<CqlStatement> {
+        let re = regex::Regex::new(r"(?i)ALTER\s+TABLE\s+(\w+)\.(\w+)\s+WITH\s+(.*)")?;
+        
+        let invalid = || CoreDBError::QueryParsingError {
+            message: "Invalid ALTER TABLE syntax (expected ALTER TABLE <keyspace>.<table> WITH compaction = {...})".to_string(),
+        };
+        let caps = re.captures(query).ok_or_else(invalid)?;
+        Ok(CqlStatement::AlterTable {
+            keyspace: caps.get(1).unwrap().as_str().to_string(),
+            name: caps.get(2).unwrap().as_str().to_string(),
+            compaction_strategy: Self::parse_compaction(caps.get(3).unwrap().as_str())?.ok_or_else(invalid)?,
+        })
+    }
+    
     fn parse_drop_table(query: &str) -> Result<CqlStatement> {
         let re = regex::Regex::new(r"DROP\s+TABLE\s+(\w+)\.(\w+)")?;
         
@@ -338,28 +742,184 @@ impl CqlParser {
         }
     }
     
-    fn parse_where_clause(query: &str) -> Result<WhereClause> {
-        let re = regex::Regex::new(r"WHERE\s+(\w+)\s*=\s*([^\\s]+)")?;
+    fn parse_tracing(query: &str) -> Result<CqlStatement> {
+        let re = regex::Regex::new(r"(?i)^TRACING\s+(ON|OFF)\s*;?$")?;
         
         if let Some(caps) = re.captures(query) {
-            let column = caps.get(1).unwrap().as_str().to_string();
-            let value_str = caps.get(2).unwrap().as_str();
-            let value = Self::parse_value(value_str)?;
-            
-            Ok(WhereClause {
-                conditions: vec![Condition {
-                    column,
-                    operator: ComparisonOperator::Equal,
-                    value,
-                }],
+            Ok(CqlStatement::Tracing {
+                enabled: caps.get(1).unwrap().as_str().eq_ignore_ascii_case("ON"),
             })
         } else {
             Err(CoreDBError::QueryParsingError {
-                message: "Invalid WHERE clause syntax".to_string(),
+                message: "Invalid TRACING syntax (expected TRACING ON or TRACING OFF)".to_string(),
             })
         }
     }
     
+    /// CREATE|ALTER ROLE <name> [WITH PASSWORD = '..' AND LOGIN = .. AND SUPERUSER = ..]
+    /// CREATE|ALTER USER <name> [WITH PASSWORD '..'] [SUPERUSER | NOSUPERUSER] (사용자는 로그인 가능한 역할)
+    fn parse_create_or_alter_role(query: &str, user: bool) -> Result<CqlStatement> {
+        let re = regex::Regex::new(r"(?is)^(CREATE|ALTER)\s+(?:ROLE|USER)\s+(IF\s+NOT\s+EXISTS\s+)?(\w+|'[^']+')(.*?)\s*;?$")?;
+        let invalid = || CoreDBError::QueryParsingError { message: format!("Invalid role syntax: {}", redact_passwords(query)) };
+        let caps = re.captures(query).ok_or_else(invalid)?;
+        let create = caps[1].eq_ignore_ascii_case("CREATE");
+        let if_not_exists = caps.get(2).is_some();
+        if if_not_exists && !create {
+            return Err(invalid());
+        }
+        let name = caps[3].trim_matches('\'').to_string();
+        
+        // 비밀번호를 먼저 떼어 내 비밀번호 안의 글자가 다른 옵션으로 읽히지 않게 함
+        let mut options = RoleOptions::default();
+        let password_re = regex::Regex::new(PASSWORD_PATTERN)?;
+        let mut rest = caps[4].to_string();
+        if let Some(password) = password_re.captures(&rest) {
+            if !user && !password[1].contains('=') {
+                return Err(invalid());
+            }
+            options.password = Some(password[2].replace("''", "'"));
+            rest = password_re.replace(&rest, " ").into_owned();
+        }
+        let flag_re = regex::Regex::new(r"(?i)\b(LOGIN|SUPERUSER)\s*=\s*(true|false)\b")?;
+        for flag in flag_re.captures_iter(&rest) {
+            let value = Some(flag[2].eq_ignore_ascii_case("true"));
+            match flag[1].to_uppercase().as_str() {
+                "LOGIN" => options.login = value,
+                _ => options.superuser = value,
+            }
+        }
+        rest = flag_re.replace_all(&rest, " ").into_owned();
+        if user {
+            let superuser_re = regex::Regex::new(r"(?i)\b(NO)?SUPERUSER\b")?;
+            if let Some(flag) = superuser_re.captures(&rest) {
+                options.superuser = Some(flag.get(1).is_none());
+            }
+            rest = superuser_re.replace(&rest, " ").into_owned();
+            if create {
+                options.login.get_or_insert(true);
+            }
+        }
+        // 남은 것은 WITH와 AND뿐이어야 함
+        if regex::Regex::new(r"(?i)\b(WITH|AND)\b")?.replace_all(&rest, " ").trim() != "" {
+            return Err(invalid());
+        }
+        
+        Ok(if create {
+            CqlStatement::CreateRole { name, options, if_not_exists }
+        } else {
+            CqlStatement::AlterRole { name, options }
+        })
+    }
+    
+    fn parse_drop_role(query: &str) -> Result<CqlStatement> {
+        let re = regex::Regex::new(r"(?is)^DROP\s+(?:ROLE|USER)\s+(IF\s+EXISTS\s+)?(\w+|'[^']+')\s*;?$")?;
+        
+        if let Some(caps) = re.captures(query) {
+            Ok(CqlStatement::DropRole {
+                name: caps[2].trim_matches('\'').to_string(),
+                if_exists: caps.get(1).is_some(),
+            })
+        } else {
+            Err(CoreDBError::QueryParsingError {
+                message: "Invalid DROP ROLE syntax".to_string(),
+            })
+        }
+    }
+    
+    fn parse_grant_or_revoke(query: &str) -> Result<CqlStatement> {
+        let re = regex::Regex::new(r"(?is)^(GRANT|REVOKE)\s+(ALL(?:\s+PERMISSIONS)?|\w+(?:\s+PERMISSION)?)\s+ON\s+(ALL\s+KEYSPACES|KEYSPACE\s+\w+|(?:TABLE\s+)?\w+\.\w+)\s+(TO|FROM)\s+(\w+|'[^']+')\s*;?$")?;
+        let invalid = || CoreDBError::QueryParsingError { message: format!("Invalid {} syntax", query.split_whitespace().next().unwrap_or_default().to_uppercase()) };
+        let caps = re.captures(query).ok_or_else(invalid)?;
+        let grant = caps[1].eq_ignore_ascii_case("GRANT");
+        if caps[4].eq_ignore_ascii_case("TO") != grant {
+            return Err(invalid());
+        }
+        
+        let permission = caps[2].split_whitespace().next().unwrap_or_default();
+        let permissions = if permission.eq_ignore_ascii_case("ALL") {
+            Permission::ALL.to_vec()
+        } else {
+            vec![Permission::parse(permission).ok_or_else(|| CoreDBError::QueryParsingError { message: format!("Unknown permission: {}", permission) })?]
+        };
+        let resource_words: Vec<&str> = caps[3].split_whitespace().collect();
+        let resource = match resource_words.as_slice() {
+            [all, _] if all.eq_ignore_ascii_case("ALL") => Resource::AllKeyspaces,
+            [kind, keyspace] if kind.eq_ignore_ascii_case("KEYSPACE") => Resource::Keyspace(keyspace.to_string()),
+            [.., name] => {
+                let (keyspace, table) = name.split_once('.').ok_or_else(invalid)?;
+                Resource::Table(keyspace.to_string(), table.to_string())
+            },
+            [] => return Err(invalid()),
+        };
+        let role = caps[5].trim_matches('\'').to_string();
+        
+        Ok(if grant {
+            CqlStatement::Grant { permissions, resource, role }
+        } else {
+            CqlStatement::Revoke { permissions, resource, role }
+        })
+    }
+    
+    fn parse_where_clause(query: &str) -> Result<WhereClause> {
+        // WHERE 이후부터 LIMIT / ALLOW FILTERING 이전까지가 조건 영역
+        let re = regex::Regex::new(r"(?is)WHERE\s+(.+?)(?:\s+ORDER\s+BY\s+\w+(?:\s+ANN\s+OF\s+\[[^\]]*\]|\s+(?:ASC|DESC))?|\s+LIMIT\s+\d+|\s+ALLOW\s+FILTERING|\s*;)*\s*$")?;
+        let condition_re = regex::Regex::new(r"^(\w+)\s*(<=|>=|!=|=|<|>)\s*('(?:[^']|'')*'|\S+)$")?;
+        let number = r"(-?\d+(?:\.\d+)?)";
+        let within_re = regex::Regex::new(&format!(r"(?i)^(\w+)\s+WITHIN\s+BOX\s*\(\s*{n}\s*,\s*{n}\s*,\s*{n}\s*,\s*{n}\s*\)$", n = number))?;
+        let match_re = regex::Regex::new(r"(?i)^MATCH\s*\(\s*(\w+)\s*,\s*('(?:[^']|'')*')\s*\)$")?;
+        let body = match re.captures(query) {
+            Some(caps) => caps.get(1).unwrap().as_str(),
+            None => {
+                return Err(CoreDBError::QueryParsingError {
+                    message: "Invalid WHERE clause syntax".to_string(),
+                });
+            }
+        };
+        
+        let mut conditions = Vec::new();
+        for part in Self::split_conditions(body)? {
+            if let Some(caps) = within_re.captures(part.trim()) {
+                let bound = |i: usize| caps.get(i).unwrap().as_str().parse::<f64>();
+                conditions.push(Condition {
+                    column: caps.get(1).unwrap().as_str().to_string(),
+                    operator: ComparisonOperator::Within,
+                    value: BoundingBox::new(bound(2)?, bound(3)?, bound(4)?, bound(5)?).to_value(),
+                });
+                continue;
+            }
+            
+            if let Some(caps) = match_re.captures(part.trim()) {
+                conditions.push(Condition {
+                    column: caps.get(1).unwrap().as_str().to_string(),
+                    operator: ComparisonOperator::Match,
+                    value: Self::parse_value(caps.get(2).unwrap().as_str())?,
+                });
+                continue;
+            }
+            
+            let caps = condition_re.captures(part.trim()).ok_or_else(|| CoreDBError::QueryParsingError {
+                message: format!("Invalid WHERE condition: {}", part),
+            })?;
+            
+            let operator = match caps.get(2).unwrap().as_str() {
+                "=" => ComparisonOperator::Equal,
+                "!=" => ComparisonOperator::NotEqual,
+                ">" => ComparisonOperator::GreaterThan,
+                ">=" => ComparisonOperator::GreaterThanOrEqual,
+                "<" => ComparisonOperator::LessThan,
+                _ => ComparisonOperator::LessThanOrEqual,
+            };
+            
+            conditions.push(Condition {
+                column: caps.get(1).unwrap().as_str().to_string(),
+                operator,
+                value: Self::parse_value(caps.get(3).unwrap().as_str())?,
+            });
+        }
+        
+        Ok(WhereClause { conditions })
+    }
+    
     fn parse_data_type(type_str: &str) -> Result<CassandraDataType> {
         match type_str.to_uppercase().as_str() {
             "TEXT" | "VARCHAR" => Ok(CassandraDataType::Text),
@@ -370,9 +930,76 @@ impl CqlParser {
             "BOOLEAN" | "BOOL" => Ok(CassandraDataType::Boolean),
             "DOUBLE" | "FLOAT" => Ok(CassandraDataType::Double),
             "BLOB" => Ok(CassandraDataType::Blob),
-            _ => Err(CoreDBError::QueryParsingError {
-                message: format!("Unsupported data type: {}", type_str),
-            }),
+            "POINT" => Ok(CassandraDataType::Point),
+            _ => {
+                let vector_re = regex::Regex::new(r"(?i)^VECTOR\s*<\s*FLOAT\s*,\s*(\d+)\s*>$")?;
+                match vector_re.captures(type_str.trim()) {
+                    Some(caps) => Ok(CassandraDataType::Vector(caps.get(1).unwrap().as_str().parse::<usize>()?)),
+                    None => Err(CoreDBError::QueryParsingError {
+                        message: format!("Unsupported data type: {}", type_str),
+                    }),
+                }
+            },
+        }
+    }
+    
+    /// 괄호 / 대괄호 / 꺾쇠 / 따옴표 바깥의 쉼표로만 분리
+    fn split_top_level(input: &str) -> Vec<&str> {
+        let mut parts = Vec::new();
+        let mut depth = 0i32;
+        let mut in_quote = false;
+        let mut start = 0;
+        for (i, c) in input.char_indices() {
+            match c {
+                '\'' => in_quote = !in_quote,
+                '(' | '[' | '<' | '{' if !in_quote => depth += 1,
+                ')' | ']' | '>' | '}' if !in_quote => depth -= 1,
+                ',' if !in_quote && depth == 0 => {
+                    parts.push(input[start..i].trim());
+                    start = i + 1;
+                },
+                _ => {},
+            }
+        }
+        parts.push(input[start..].trim());
+        parts
+    }
+    
+    /// 괄호 / 대괄호 / 따옴표 바깥의 AND로만 WHERE 조건 분리 ('a AND b' 같은 리터럴 보존)
+    fn split_conditions(input: &str) -> Result<Vec<&str>> {
+        let and_re = regex::Regex::new(r"(?i)^\s+AND\s+")?;
+        let mut parts = Vec::new();
+        let mut depth = 0i32;
+        let mut in_quote = false;
+        let mut start = 0;
+        let mut i = 0;
+        while let Some(c) = input[i..].chars().next() {
+            match c {
+                '\'' => in_quote = !in_quote,
+                '(' | '[' if !in_quote => depth += 1,
+                ')' | ']' if !in_quote => depth -= 1,
+                c if c.is_whitespace() && !in_quote && depth == 0 => {
+                    if let Some(m) = and_re.find(&input[i..]) {
+                        parts.push(&input[start..i]);
+                        i += m.end();
+                        start = i;
+                        continue;
+                    }
+                },
+                _ => {},
+            }
+            i += c.len_utf8();
+        }
+        parts.push(&input[start..]);
+        Ok(parts)
+    }
+    
+    fn as_f32(value: &CassandraValue) -> Option<f32> {
+        match value {
+            CassandraValue::Int(v) => Some(*v as f32),
+            CassandraValue::BigInt(v) => Some(*v as f32),
+            CassandraValue::Double(v) => Some(*v as f32),
+            _ => None,
         }
     }
     
@@ -381,6 +1008,25 @@ impl CqlParser {
         
         if value == "NULL" {
             Ok(CassandraValue::Null)
+        } else if let Some(caps) = regex::Regex::new(r"(?i)^POINT\s*\(\s*(-?\d+(?:\.\d+)?)\s*,\s*(-?\d+(?:\.\d+)?)\s*\)$")?.captures(value) {
+            // POINT(위도, 경도)
+            let lat = caps.get(1).unwrap().as_str().parse::<f64>()?;
+            let lon = caps.get(2).unwrap().as_str().parse::<f64>()?;
+            if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+                return Err(CoreDBError::InvalidDataType {
+                    message: format!("POINT out of range: ({}, {})", lat, lon),
+                });
+            }
+            Ok(CassandraValue::Point(lat, lon))
+        } else if value.starts_with('[') && value.ends_with(']') {
+            // 리스트 / 벡터 리터럴 (벡터 변환은 컬럼 타입을 아는 쪽에서 수행)
+            let inner = value[1..value.len()-1].trim();
+            if inner.is_empty() {
+                return Ok(CassandraValue::List(Vec::new()));
+            }
+            Ok(CassandraValue::List(
+                Self::split_top_level(inner).into_iter().map(Self::parse_value).collect::<Result<Vec<_>>>()?
+            ))
         } else if value.starts_with('\'') && value.ends_with('\'') {
             // 문자열
             let string_value = value[1..value.len()-1].to_string();
@@ -415,6 +1061,49 @@ mod tests {
         if let Ok(CqlStatement::CreateKeyspace { name, options }) = result {
             assert_eq!(name, "test_ks");
             assert_eq!(options.replication_factor, 1);
+            assert!(options.durable_writes);
+        }
+        
+        let query = "CREATE KEYSPACE scratch WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1} AND DURABLE_WRITES = false";
+        let Ok(CqlStatement::CreateKeyspace { options, .. }) = CqlParser::parse(query) else { panic!("expected CREATE KEYSPACE") };
+        assert!(!options.durable_writes);
+    }
+    
+    #[test]
+    fn test_parse_role_statements() {
+        let query = "CREATE ROLE IF NOT EXISTS reporting WITH PASSWORD = 'it''s AND login = true' AND LOGIN = true";
+        let Ok(CqlStatement::CreateRole { name, options, if_not_exists }) = CqlParser::parse(query) else { panic!("expected CREATE ROLE") };
+        assert_eq!((name.as_str(), if_not_exists), ("reporting", true));
+        assert_eq!(options, RoleOptions { password: Some("it's AND login = true".to_string()), login: Some(true), superuser: None });
+        assert!(!format!("{:?}", options).contains("it's"));
+        assert_eq!(redact_passwords(query), "CREATE ROLE IF NOT EXISTS reporting WITH PASSWORD = '***' AND LOGIN = true");
+        
+        // 사용자는 로그인 가능한 역할
+        let Ok(CqlStatement::CreateRole { options, .. }) = CqlParser::parse("CREATE USER admin WITH PASSWORD 'pw' SUPERUSER") else { panic!("expected CREATE USER") };
+        assert_eq!(options, RoleOptions { password: Some("pw".to_string()), login: Some(true), superuser: Some(true) });
+        let Ok(CqlStatement::AlterRole { name, options }) = CqlParser::parse("ALTER USER admin NOSUPERUSER") else { panic!("expected ALTER USER") };
+        assert_eq!(name, "admin");
+        assert_eq!(options, RoleOptions { password: None, login: None, superuser: Some(false) });
+        let Ok(CqlStatement::DropRole { name, if_exists }) = CqlParser::parse("DROP ROLE IF EXISTS reporting;") else { panic!("expected DROP ROLE") };
+        assert_eq!((name.as_str(), if_exists), ("reporting", true));
+        
+        for invalid in ["CREATE ROLE r WITH PASSWORD 'pw'", "CREATE ROLE r WITH OPTIONS = {}", "ALTER ROLE IF NOT EXISTS r WITH LOGIN = true"] {
+            assert!(CqlParser::parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+    
+    #[test]
+    fn test_parse_grant_and_revoke() {
+        let Ok(CqlStatement::Grant { permissions, resource, role }) = CqlParser::parse("GRANT SELECT ON KEYSPACE sales TO reporting;") else { panic!("expected GRANT") };
+        assert_eq!((permissions, resource, role.as_str()), (vec![Permission::Select], Resource::Keyspace("sales".to_string()), "reporting"));
+        let Ok(CqlStatement::Grant { permissions, resource, .. }) = CqlParser::parse("grant all permissions on all keyspaces to admin") else { panic!("expected GRANT") };
+        assert_eq!((permissions, resource), (Permission::ALL.to_vec(), Resource::AllKeyspaces));
+        let Ok(CqlStatement::Revoke { permissions, resource, role }) = CqlParser::parse("REVOKE MODIFY PERMISSION ON TABLE sales.orders FROM 'reporting'") else { panic!("expected REVOKE") };
+        assert_eq!((permissions, resource, role.as_str()), (vec![Permission::Modify], Resource::Table("sales".to_string(), "orders".to_string()), "reporting"));
+        assert!(matches!(CqlParser::parse("REVOKE DROP ON sales.orders FROM reporting"), Ok(CqlStatement::Revoke { .. })));
+        
+        for invalid in ["GRANT SELECT ON sales.orders FROM reporting", "GRANT EXECUTE ON KEYSPACE sales TO r", "REVOKE SELECT ON KEYSPACE sales TO r"] {
+            assert!(CqlParser::parse(invalid).is_err(), "{}", invalid);
         }
     }
     
@@ -450,7 +1139,7 @@ mod tests {
         let result = CqlParser::parse(query);
         assert!(result.is_ok());
         
-        if let Ok(CqlStatement::Select { keyspace, table, columns, where_clause, limit }) = result {
+        if let Ok(CqlStatement::Select { keyspace, table, columns, where_clause, limit, .. }) = result {
             assert_eq!(keyspace, "test_ks");
             assert_eq!(table, "test_table");
             assert_eq!(columns, vec!["*"]);
@@ -458,4 +1147,183 @@ mod tests {
             assert_eq!(limit, Some(10));
         }
     }
+    
+    #[test]
+    fn test_parse_create_index() {
+        let result = CqlParser::parse("CREATE INDEX ON test_ks.users (city)").unwrap();
+        if let CqlStatement::CreateIndex { keyspace, table, name, column, kind } = result {
+            assert_eq!(keyspace, "test_ks");
+            assert_eq!(table, "users");
+            assert_eq!(name, "users_city_idx");
+            assert_eq!(column, "city");
+            assert_eq!(kind, IndexKind::Standard);
+        } else {
+            panic!("Expected CREATE INDEX");
+        }
+        
+        let result = CqlParser::parse("CREATE INDEX city_lookup ON test_ks.users (city)").unwrap();
+        assert!(matches!(result, CqlStatement::CreateIndex { name, .. } if name == "city_lookup"));
+        
+        let result = CqlParser::parse("CREATE CUSTOM INDEX ON test_ks.posts (body) USING 'fulltext'").unwrap();
+        assert!(matches!(result, CqlStatement::CreateIndex { kind: IndexKind::FullText, .. }));
+        assert!(CqlParser::parse("CREATE CUSTOM INDEX ON test_ks.posts (body) USING 'unknown'").is_err());
+        
+        let query = "SELECT * FROM test_ks.posts WHERE match(body, 'rust database') AND id > 3";
+        if let CqlStatement::Select { where_clause, .. } = CqlParser::parse(query).unwrap() {
+            let conditions = where_clause.unwrap().conditions;
+            assert!(matches!(conditions[0].operator, ComparisonOperator::Match));
+            assert_eq!(conditions[0].column, "body");
+            assert_eq!(conditions[0].value, CassandraValue::Text("rust database".to_string()));
+        } else {
+            panic!("Expected SELECT");
+        }
+    }
+    
+    #[test]
+    fn test_parse_vector_column_and_ann_ordering() {
+        let query = "CREATE TABLE test_ks.docs (id INT PRIMARY KEY, title TEXT, embedding VECTOR<FLOAT, 3>)";
+        if let CqlStatement::CreateTable { columns, .. } = CqlParser::parse(query).unwrap() {
+            assert_eq!(columns.len(), 3);
+            assert_eq!(columns[2].data_type, CassandraDataType::Vector(3));
+        } else {
+            panic!("Expected CREATE TABLE");
+        }
+        
+        let query = "INSERT INTO test_ks.docs (id, title, embedding) VALUES (1, 'a, b', [0.1, 0.2, 0.3])";
+        if let CqlStatement::Insert { values, .. } = CqlParser::parse(query).unwrap() {
+            assert_eq!(values[1].1, CassandraValue::Text("a, b".to_string()));
+            assert_eq!(values[2].1, CassandraValue::List(vec![
+                CassandraValue::Double(0.1), CassandraValue::Double(0.2), CassandraValue::Double(0.3),
+            ]));
+        } else {
+            panic!("Expected INSERT");
+        }
+        
+        let query = "SELECT id FROM test_ks.docs WHERE title = 'a' ORDER BY embedding ANN OF [1, 0.5, 0] LIMIT 5";
+        if let CqlStatement::Select { where_clause, order_by, limit, .. } = CqlParser::parse(query).unwrap() {
+            assert_eq!(where_clause.unwrap().conditions.len(), 1);
+            match order_by {
+                Some(OrderBy::Ann { column, vector }) => {
+                    assert_eq!(column, "embedding");
+                    assert_eq!(vector, vec![1.0, 0.5, 0.0]);
+                },
+                _ => panic!("Expected ANN ordering"),
+            }
+            assert_eq!(limit, Some(5));
+        } else {
+            panic!("Expected SELECT");
+        }
+    }
+    
+    #[test]
+    fn test_parse_primary_key_clause_and_clustering_order() {
+        let query = "CREATE TABLE test_ks.events (sensor INT, day TEXT, ts INT, reading INT, PRIMARY KEY ((sensor, day), ts))";
+        if let CqlStatement::CreateTable { columns, partition_key, clustering_key, .. } = CqlParser::parse(query).unwrap() {
+            assert_eq!(columns.len(), 4);
+            assert_eq!(partition_key, vec!["sensor", "day"]);
+            assert_eq!(clustering_key, vec!["ts"]);
+        } else {
+            panic!("Expected CREATE TABLE");
+        }
+        
+        let query = "CREATE TABLE test_ks.events (sensor INT, ts INT, reading INT, PRIMARY KEY (sensor, ts)) WITH memtable_flush_threshold_mb = 256";
+        if let CqlStatement::CreateTable { partition_key, clustering_key, options, .. } = CqlParser::parse(query).unwrap() {
+            assert_eq!(partition_key, vec!["sensor"]);
+            assert_eq!(clustering_key, vec!["ts"]);
+            assert_eq!(options.memtable_flush_threshold_mb, Some(256));
+            assert_eq!(options.compression, None);
+        } else {
+            panic!("Expected CREATE TABLE");
+        }
+        
+        let query = "CREATE TABLE test_ks.logs (id INT PRIMARY KEY, message TEXT) WITH compression = {'class': 'ZstdDictionaryCompressor'}";
+        let schema = CqlParser::parse(query).unwrap().table_schema().unwrap();
+        assert_eq!(schema.options.compression, CompressionType::ZSTDDictionary);
+        let query = "CREATE TABLE test_ks.logs (id INT PRIMARY KEY, message TEXT) WITH compression = {'enabled': 'false'}";
+        assert_eq!(CqlParser::parse(query).unwrap().table_schema().unwrap().options.compression, CompressionType::None);
+        assert!(CqlParser::parse("CREATE TABLE test_ks.logs (id INT PRIMARY KEY) WITH compression = {'class': 'DeflateCompressor'}").is_err());
+        
+        let query = "CREATE TABLE test_ks.logs (id INT PRIMARY KEY, message TEXT) WITH compaction = {'class': 'org.apache.cassandra.db.compaction.LeveledCompactionStrategy'} AND compression = {'class': 'LZ4Compressor'}";
+        let schema = CqlParser::parse(query).unwrap().table_schema().unwrap();
+        assert_eq!(schema.options.compaction_strategy, CompactionStrategy::Leveled);
+        assert_eq!(schema.options.compression, CompressionType::LZ4);
+        assert!(CqlParser::parse("CREATE TABLE test_ks.logs (id INT PRIMARY KEY) WITH compaction = {'class': 'DateTieredCompactionStrategy'}").is_err());
+        let query = "CREATE TABLE test_ks.metrics (id INT PRIMARY KEY, value DOUBLE) WITH compaction = {'class': 'TimeWindowCompactionStrategy', 'compaction_window_unit': 'HOURS', 'compaction_window_size': 6}";
+        let schema = CqlParser::parse(query).unwrap().table_schema().unwrap();
+        assert_eq!(schema.options.compaction_strategy, CompactionStrategy::TimeWindow { window_seconds: 6 * 60 * 60 });
+        let query = "CREATE TABLE test_ks.metrics (id INT PRIMARY KEY) WITH compaction = {'class': 'TimeWindowCompactionStrategy'}";
+        assert_eq!(CqlParser::parse(query).unwrap().table_schema().unwrap().options.compaction_strategy, CompactionStrategy::TimeWindow { window_seconds: 86_400 });
+        assert!(CqlParser::parse("CREATE TABLE test_ks.metrics (id INT PRIMARY KEY) WITH compaction = {'class': 'TimeWindowCompactionStrategy', 'compaction_window_unit': 'WEEKS'}").is_err());
+        
+        let query = "ALTER TABLE test_ks.metrics WITH compaction = {'class': 'LeveledCompactionStrategy'};";
+        assert!(matches!(CqlParser::parse(query).unwrap(), CqlStatement::AlterTable { name, compaction_strategy: CompactionStrategy::Leveled, .. } if name == "metrics"));
+        assert!(CqlParser::parse("ALTER TABLE test_ks.metrics WITH gc_grace_seconds = 0").is_err());
+        
+        let query = "SELECT reading FROM test_ks.events WHERE sensor = 1 ORDER BY ts DESC LIMIT 3";
+        if let CqlStatement::Select { where_clause, order_by, limit, .. } = CqlParser::parse(query).unwrap() {
+            assert_eq!(where_clause.unwrap().conditions.len(), 1);
+            assert!(matches!(order_by, Some(OrderBy::Clustering { column, descending: true }) if column == "ts"));
+            assert_eq!(limit, Some(3));
+        } else {
+            panic!("Expected SELECT");
+        }
+    }
+    
+    #[test]
+    fn test_parse_point_and_within() {
+        let query = "INSERT INTO test_ks.events (id, location) VALUES (1, POINT(37.5665, 126.978))";
+        if let CqlStatement::Insert { values, .. } = CqlParser::parse(query).unwrap() {
+            assert_eq!(values[1].1, CassandraValue::Point(37.5665, 126.978));
+        } else {
+            panic!("Expected INSERT");
+        }
+        assert!(CqlParser::parse("INSERT INTO test_ks.events (id, location) VALUES (1, POINT(91, 0))").is_err());
+        
+        let query = "SELECT * FROM test_ks.events WHERE location WITHIN BOX(37.4, 126.8, 37.7, 127.2) AND id > 0";
+        if let CqlStatement::Select { where_clause, .. } = CqlParser::parse(query).unwrap() {
+            let conditions = where_clause.unwrap().conditions;
+            assert!(matches!(conditions[0].operator, ComparisonOperator::Within));
+            assert_eq!(BoundingBox::from_value(&conditions[0].value), Some(BoundingBox::new(37.4, 126.8, 37.7, 127.2)));
+        } else {
+            panic!("Expected SELECT");
+        }
+    }
+    
+    #[test]
+    fn test_parse_tracing() {
+        assert!(matches!(CqlParser::parse("TRACING ON").unwrap(), CqlStatement::Tracing { enabled: true }));
+        assert!(matches!(CqlParser::parse("tracing off;").unwrap(), CqlStatement::Tracing { enabled: false }));
+        assert!(CqlParser::parse("TRACING MAYBE").is_err());
+    }
+    
+    #[test]
+    fn test_parse_where_multiple_conditions() {
+        let query = "SELECT * FROM test_ks.users WHERE city = 'New York' AND age >= 30 LIMIT 5";
+        if let CqlStatement::Select { where_clause, limit, .. } = CqlParser::parse(query).unwrap() {
+            let conditions = where_clause.unwrap().conditions;
+            assert_eq!(conditions.len(), 2);
+            assert_eq!(conditions[0].column, "city");
+            assert_eq!(conditions[0].value, CassandraValue::Text("New York".to_string()));
+            assert_eq!(conditions[1].column, "age");
+            assert!(matches!(conditions[1].operator, ComparisonOperator::GreaterThanOrEqual));
+            assert_eq!(conditions[1].value, CassandraValue::Int(30));
+            assert_eq!(limit, Some(5));
+        } else {
+            panic!("Expected SELECT");
+        }
+    }
+    
+    #[test]
+    fn test_parse_where_and_inside_literal() {
+        let query = "SELECT * FROM test_ks.users WHERE name = 'Tom AND Jerry' AND MATCH(bio, 'cats and dogs')";
+        if let CqlStatement::Select { where_clause, .. } = CqlParser::parse(query).unwrap() {
+            let conditions = where_clause.unwrap().conditions;
+            assert_eq!(conditions.len(), 2);
+            assert_eq!(conditions[0].value, CassandraValue::Text("Tom AND Jerry".to_string()));
+            assert!(matches!(conditions[1].operator, ComparisonOperator::Match));
+            assert_eq!(conditions[1].value, CassandraValue::Text("cats and dogs".to_string()));
+        } else {
+            panic!("Expected SELECT");
+        }
+    }
 }
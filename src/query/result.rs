@@ -1,6 +1,6 @@
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
-use crate::schema::CassandraValue;
+use crate::schema::{CassandraValue, PartitionKey, ClusteringKey};
 
 /// 쿼리 결과
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +27,13 @@ pub struct ColumnMetadata {
     pub is_static: bool,
 }
 
+/// 페이지 조회 상태 (마지막으로 반환한 행의 키, 다음 페이지는 그 이후부터)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PagingState {
+    pub partition_key: PartitionKey,
+    pub clustering_key: Option<ClusteringKey>,
+}
+
 impl QueryResult {
     pub fn success() -> Self {
         QueryResult::Success
@@ -0,0 +1,372 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use crate::schema::{CassandraDataType, CassandraValue, KeyspaceDefinition, PartitionKey, ReplicationStrategy};
+
+/// 운영 정보를 조회용 테이블로 노출하는 키스페이스
+pub const SYSTEM_VIEWS_KEYSPACE: &str = "system_views";
+/// 크기 임계값을 넘은 파티션 목록
+pub const LARGE_PARTITIONS_TABLE: &str = "large_partitions";
+
+/// 스키마 정보를 조회용 테이블로 노출하는 키스페이스
+pub const SYSTEM_SCHEMA_KEYSPACE: &str = "system_schema";
+/// 키스페이스 정의 목록 (복제 설정과 durable_writes)
+pub const KEYSPACES_TABLE: &str = "keyspaces";
+/// 테이블 정의 목록 (테이블 옵션)
+pub const TABLES_TABLE: &str = "tables";
+/// 테이블 컬럼 목록 (키 종류, 순서, CQL 타입)
+pub const COLUMNS_TABLE: &str = "columns";
+/// 보조 인덱스 목록
+pub const INDEXES_TABLE: &str = "indexes";
+/// 쓰기 트리거 목록
+pub const TRIGGERS_TABLE: &str = "triggers";
+/// 드라이버가 메타데이터를 읽을 때 함께 조회하는 테이블 (CoreDB에는 사용자 정의 타입, 함수, 집계, 뷰가 없어 항상 비어 있음)
+pub const TYPES_TABLE: &str = "types";
+pub const FUNCTIONS_TABLE: &str = "functions";
+pub const AGGREGATES_TABLE: &str = "aggregates";
+pub const VIEWS_TABLE: &str = "views";
+
+/// 노드 정보를 노출하는 키스페이스
+pub const SYSTEM_KEYSPACE: &str = "system";
+/// 이 노드의 정보 (행 하나, key = 'local')
+pub const LOCAL_TABLE: &str = "local";
+/// 다른 노드 목록 (단일 노드라 항상 비어 있음, peers_v2는 Cassandra 4 드라이버용)
+pub const PEERS_TABLE: &str = "peers";
+pub const PEERS_V2_TABLE: &str = "peers_v2";
+/// 연결된 클라이언트
+pub const CLIENTS_TABLE: &str = "clients";
+/// 키/청크/행 캐시 사용량과 적중률
+pub const CACHES_TABLE: &str = "caches";
+/// 실행 중인 컴팩션
+pub const SSTABLE_TASKS_TABLE: &str = "sstable_tasks";
+
+/// system.local에 알리는 Cassandra 버전 (드라이버는 이 버전으로 스키마 조회 방식을 고르며, 3.x면 system_schema를 읽음)
+pub const CASSANDRA_RELEASE_VERSION: &str = "3.11.4";
+/// system.local에 알리는 파티셔너 (단일 노드라 토큰 하나가 전체 링을 가짐)
+pub const PARTITIONER: &str = "org.apache.cassandra.dht.Murmur3Partitioner";
+
+/// 조회할 때마다 메모리 상태로 다시 채우는 읽기 전용 테이블
+#[derive(Debug)]
+pub struct VirtualTable {
+    pub keyspace: &'static str,
+    pub name: &'static str,
+    pub columns: Vec<(&'static str, CassandraDataType)>,
+    pub partition_key: &'static [&'static str],
+    pub clustering_key: &'static [&'static str],
+}
+
+/// 가상 테이블 행 (컬럼 이름, 값)
+pub type VirtualRow = Vec<(&'static str, CassandraValue)>;
+
+fn text_map() -> CassandraDataType {
+    CassandraDataType::Map(Box::new(CassandraDataType::Text), Box::new(CassandraDataType::Text))
+}
+
+fn text_set() -> CassandraDataType {
+    CassandraDataType::Set(Box::new(CassandraDataType::Text))
+}
+
+fn text_list() -> CassandraDataType {
+    CassandraDataType::List(Box::new(CassandraDataType::Text))
+}
+
+/// 가상 테이블 목록 (컬렉션 타입은 const로 만들 수 없어 처음 쓸 때 만듦)
+pub static VIRTUAL_TABLES: LazyLock<Vec<VirtualTable>> = LazyLock::new(|| vec![
+    VirtualTable {
+        keyspace: SYSTEM_KEYSPACE,
+        name: LOCAL_TABLE,
+        columns: vec![
+            ("key", CassandraDataType::Text),
+            ("bootstrapped", CassandraDataType::Text),
+            ("broadcast_address", CassandraDataType::Text),
+            ("cluster_name", CassandraDataType::Text),
+            ("cql_version", CassandraDataType::Text),
+            ("data_center", CassandraDataType::Text),
+            ("host_id", CassandraDataType::UUID),
+            ("listen_address", CassandraDataType::Text),
+            ("native_protocol_version", CassandraDataType::Text),
+            ("partitioner", CassandraDataType::Text),
+            ("rack", CassandraDataType::Text),
+            ("release_version", CassandraDataType::Text),
+            ("rpc_address", CassandraDataType::Text),
+            ("rpc_port", CassandraDataType::Int),
+            ("schema_version", CassandraDataType::UUID),
+            ("tokens", text_set()),
+        ],
+        partition_key: &["key"],
+        clustering_key: &[],
+    },
+    VirtualTable {
+        keyspace: SYSTEM_KEYSPACE,
+        name: PEERS_TABLE,
+        columns: vec![
+            ("peer", CassandraDataType::Text),
+            ("data_center", CassandraDataType::Text),
+            ("host_id", CassandraDataType::UUID),
+            ("preferred_ip", CassandraDataType::Text),
+            ("rack", CassandraDataType::Text),
+            ("release_version", CassandraDataType::Text),
+            ("rpc_address", CassandraDataType::Text),
+            ("schema_version", CassandraDataType::UUID),
+            ("tokens", text_set()),
+        ],
+        partition_key: &["peer"],
+        clustering_key: &[],
+    },
+    VirtualTable {
+        keyspace: SYSTEM_KEYSPACE,
+        name: PEERS_V2_TABLE,
+        columns: vec![
+            ("peer", CassandraDataType::Text),
+            ("peer_port", CassandraDataType::Int),
+            ("data_center", CassandraDataType::Text),
+            ("host_id", CassandraDataType::UUID),
+            ("native_address", CassandraDataType::Text),
+            ("native_port", CassandraDataType::Int),
+            ("preferred_ip", CassandraDataType::Text),
+            ("preferred_port", CassandraDataType::Int),
+            ("rack", CassandraDataType::Text),
+            ("release_version", CassandraDataType::Text),
+            ("schema_version", CassandraDataType::UUID),
+            ("tokens", text_set()),
+        ],
+        partition_key: &["peer"],
+        clustering_key: &["peer_port"],
+    },
+    VirtualTable {
+        keyspace: SYSTEM_VIEWS_KEYSPACE,
+        name: CLIENTS_TABLE,
+        columns: vec![
+            ("address", CassandraDataType::Text),
+            ("port", CassandraDataType::Int),
+            ("connected_at", CassandraDataType::Timestamp),
+            ("driver_name", CassandraDataType::Text),
+            ("request_count", CassandraDataType::BigInt),
+            ("ssl_enabled", CassandraDataType::Boolean),
+            ("ssl_protocol", CassandraDataType::Text),
+            ("username", CassandraDataType::Text),
+        ],
+        partition_key: &["address"],
+        clustering_key: &["port"],
+    },
+    VirtualTable {
+        keyspace: SYSTEM_VIEWS_KEYSPACE,
+        name: CACHES_TABLE,
+        columns: vec![
+            ("name", CassandraDataType::Text),
+            ("capacity", CassandraDataType::BigInt),
+            ("entry_count", CassandraDataType::BigInt),
+            ("hit_count", CassandraDataType::BigInt),
+            ("hit_ratio", CassandraDataType::Double),
+            ("request_count", CassandraDataType::BigInt),
+            ("size_bytes", CassandraDataType::BigInt),
+        ],
+        partition_key: &["name"],
+        clustering_key: &[],
+    },
+    VirtualTable {
+        keyspace: SYSTEM_VIEWS_KEYSPACE,
+        name: SSTABLE_TASKS_TABLE,
+        columns: vec![
+            ("keyspace_name", CassandraDataType::Text),
+            ("table_name", CassandraDataType::Text),
+            ("kind", CassandraDataType::Text),
+            ("progress", CassandraDataType::BigInt),
+            ("total", CassandraDataType::BigInt),
+            ("unit", CassandraDataType::Text),
+        ],
+        partition_key: &["keyspace_name"],
+        clustering_key: &["table_name"],
+    },
+    VirtualTable {
+        keyspace: SYSTEM_SCHEMA_KEYSPACE,
+        name: TABLES_TABLE,
+        columns: vec![
+            ("keyspace_name", CassandraDataType::Text),
+            ("table_name", CassandraDataType::Text),
+            ("bloom_filter_fp_chance", CassandraDataType::Double),
+            ("caching", text_map()),
+            ("comment", CassandraDataType::Text),
+            ("compaction", text_map()),
+            ("compression", text_map()),
+            ("default_time_to_live", CassandraDataType::Int),
+            ("extensions", CassandraDataType::Map(Box::new(CassandraDataType::Text), Box::new(CassandraDataType::Blob))),
+            ("flags", text_set()),
+            ("gc_grace_seconds", CassandraDataType::Int),
+        ],
+        partition_key: &["keyspace_name"],
+        clustering_key: &["table_name"],
+    },
+    VirtualTable {
+        keyspace: SYSTEM_SCHEMA_KEYSPACE,
+        name: COLUMNS_TABLE,
+        columns: vec![
+            ("keyspace_name", CassandraDataType::Text),
+            ("table_name", CassandraDataType::Text),
+            ("column_name", CassandraDataType::Text),
+            ("clustering_order", CassandraDataType::Text),
+            ("column_name_bytes", CassandraDataType::Blob),
+            ("kind", CassandraDataType::Text),
+            ("position", CassandraDataType::Int),
+            ("type", CassandraDataType::Text),
+        ],
+        partition_key: &["keyspace_name"],
+        clustering_key: &["table_name", "column_name"],
+    },
+    VirtualTable {
+        keyspace: SYSTEM_SCHEMA_KEYSPACE,
+        name: INDEXES_TABLE,
+        columns: vec![
+            ("keyspace_name", CassandraDataType::Text),
+            ("table_name", CassandraDataType::Text),
+            ("index_name", CassandraDataType::Text),
+            ("kind", CassandraDataType::Text),
+            ("options", text_map()),
+        ],
+        partition_key: &["keyspace_name"],
+        clustering_key: &["table_name", "index_name"],
+    },
+    VirtualTable {
+        keyspace: SYSTEM_SCHEMA_KEYSPACE,
+        name: TRIGGERS_TABLE,
+        columns: vec![
+            ("keyspace_name", CassandraDataType::Text),
+            ("table_name", CassandraDataType::Text),
+            ("trigger_name", CassandraDataType::Text),
+            ("options", text_map()),
+        ],
+        partition_key: &["keyspace_name"],
+        clustering_key: &["table_name", "trigger_name"],
+    },
+    VirtualTable {
+        keyspace: SYSTEM_SCHEMA_KEYSPACE,
+        name: TYPES_TABLE,
+        columns: vec![
+            ("keyspace_name", CassandraDataType::Text),
+            ("type_name", CassandraDataType::Text),
+            ("field_names", text_list()),
+            ("field_types", text_list()),
+        ],
+        partition_key: &["keyspace_name"],
+        clustering_key: &["type_name"],
+    },
+    VirtualTable {
+        keyspace: SYSTEM_SCHEMA_KEYSPACE,
+        name: FUNCTIONS_TABLE,
+        columns: vec![
+            ("keyspace_name", CassandraDataType::Text),
+            ("function_name", CassandraDataType::Text),
+            ("argument_types", text_list()),
+            ("argument_names", text_list()),
+            ("body", CassandraDataType::Text),
+            ("called_on_null_input", CassandraDataType::Boolean),
+            ("language", CassandraDataType::Text),
+            ("return_type", CassandraDataType::Text),
+        ],
+        partition_key: &["keyspace_name"],
+        clustering_key: &["function_name", "argument_types"],
+    },
+    VirtualTable {
+        keyspace: SYSTEM_SCHEMA_KEYSPACE,
+        name: AGGREGATES_TABLE,
+        columns: vec![
+            ("keyspace_name", CassandraDataType::Text),
+            ("aggregate_name", CassandraDataType::Text),
+            ("argument_types", text_list()),
+            ("final_func", CassandraDataType::Text),
+            ("initcond", CassandraDataType::Text),
+            ("return_type", CassandraDataType::Text),
+            ("state_func", CassandraDataType::Text),
+            ("state_type", CassandraDataType::Text),
+        ],
+        partition_key: &["keyspace_name"],
+        clustering_key: &["aggregate_name", "argument_types"],
+    },
+    VirtualTable {
+        keyspace: SYSTEM_SCHEMA_KEYSPACE,
+        name: VIEWS_TABLE,
+        columns: vec![
+            ("keyspace_name", CassandraDataType::Text),
+            ("view_name", CassandraDataType::Text),
+            ("base_table_id", CassandraDataType::UUID),
+            ("base_table_name", CassandraDataType::Text),
+            ("include_all_columns", CassandraDataType::Boolean),
+            ("where_clause", CassandraDataType::Text),
+        ],
+        partition_key: &["keyspace_name"],
+        clustering_key: &["view_name"],
+    },
+]);
+
+/// 드라이버가 연결하고 메타데이터를 갱신할 때 읽는 테이블 (로그인한 역할이면 권한 없이 읽을 수 있음)
+pub const DRIVER_METADATA_TABLES: &[(&str, &str)] = &[
+    (SYSTEM_KEYSPACE, LOCAL_TABLE),
+    (SYSTEM_KEYSPACE, PEERS_TABLE),
+    (SYSTEM_KEYSPACE, PEERS_V2_TABLE),
+    (SYSTEM_SCHEMA_KEYSPACE, KEYSPACES_TABLE),
+    (SYSTEM_SCHEMA_KEYSPACE, TABLES_TABLE),
+    (SYSTEM_SCHEMA_KEYSPACE, COLUMNS_TABLE),
+    (SYSTEM_SCHEMA_KEYSPACE, INDEXES_TABLE),
+    (SYSTEM_SCHEMA_KEYSPACE, TRIGGERS_TABLE),
+    (SYSTEM_SCHEMA_KEYSPACE, TYPES_TABLE),
+    (SYSTEM_SCHEMA_KEYSPACE, FUNCTIONS_TABLE),
+    (SYSTEM_SCHEMA_KEYSPACE, AGGREGATES_TABLE),
+    (SYSTEM_SCHEMA_KEYSPACE, VIEWS_TABLE),
+];
+
+/// 가상 테이블 정의 (가상 테이블이 아니면 None)
+pub fn virtual_table(keyspace: &str, table: &str) -> Option<&'static VirtualTable> {
+    VIRTUAL_TABLES.iter().find(|virtual_table| virtual_table.keyspace == keyspace && virtual_table.name == table)
+}
+
+/// 큰 파티션 경고 기본 임계값 (100MB)
+pub const DEFAULT_LARGE_PARTITION_THRESHOLD_BYTES: u64 = 100 * 1024 * 1024;
+
+/// 키스페이스 복제 설정을 system_schema.keyspaces의 replication 맵으로 (드라이버가 읽는 전체 클래스 이름)
+pub fn replication_map(definition: &KeyspaceDefinition) -> CassandraValue {
+    let class = match definition.strategy {
+        ReplicationStrategy::SimpleStrategy => "org.apache.cassandra.locator.SimpleStrategy",
+    };
+    text_map_value([("class", class.to_string()), ("replication_factor", definition.replication_factor.to_string())])
+}
+
+/// 문자열 쌍을 map<text, text> 값으로
+pub fn text_map_value<'a>(entries: impl IntoIterator<Item = (&'a str, String)>) -> CassandraValue {
+    CassandraValue::Map(entries.into_iter()
+        .map(|(key, value)| (key.to_string(), CassandraValue::Text(value)))
+        .collect::<HashMap<_, _>>())
+}
+
+/// system_schema.columns에 쓰는 CQL 타입 이름
+pub fn cql_type(data_type: &CassandraDataType) -> String {
+    match data_type {
+        CassandraDataType::Text => "text".to_string(),
+        CassandraDataType::Int => "int".to_string(),
+        CassandraDataType::BigInt => "bigint".to_string(),
+        CassandraDataType::UUID => "uuid".to_string(),
+        CassandraDataType::Timestamp => "timestamp".to_string(),
+        CassandraDataType::Boolean => "boolean".to_string(),
+        CassandraDataType::Double => "double".to_string(),
+        CassandraDataType::Blob => "blob".to_string(),
+        CassandraDataType::Map(key, value) => format!("map<{}, {}>", cql_type(key), cql_type(value)),
+        CassandraDataType::List(element) => format!("list<{}>", cql_type(element)),
+        CassandraDataType::Set(element) => format!("set<{}>", cql_type(element)),
+        CassandraDataType::Vector(dimension) => format!("vector<float, {}>", dimension),
+        CassandraDataType::Point => "point".to_string(),
+    }
+}
+
+/// 파티션 키를 사람이 읽을 수 있는 문자열로 (복합 키는 ':'로 연결)
+pub fn partition_key_text(partition_key: &PartitionKey) -> String {
+    partition_key.components.iter()
+        .map(|component| match component {
+            CassandraValue::Text(value) => value.clone(),
+            CassandraValue::Int(value) => value.to_string(),
+            CassandraValue::BigInt(value) => value.to_string(),
+            CassandraValue::UUID(value) => value.to_string(),
+            CassandraValue::Timestamp(value) => value.to_string(),
+            CassandraValue::Boolean(value) => value.to_string(),
+            other => format!("{:?}", other),
+        })
+        .collect::<Vec<_>>()
+        .join(":")
+}
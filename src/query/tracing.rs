@@ -0,0 +1,110 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::time::Instant;
+use uuid::Uuid;
+
+/// 추적 결과가 저장되는 키스페이스
+pub const TRACES_KEYSPACE: &str = "system_traces";
+/// 쿼리 단위 요약 테이블
+pub const SESSIONS_TABLE: &str = "sessions";
+/// 단계별 이벤트 테이블
+pub const EVENTS_TABLE: &str = "events";
+
+tokio::task_local! {
+    /// 이 태스크에서 실행 중인 요청의 추적 기록 (요청들이 쿼리 엔진을 동시에 실행하므로 요청마다 따로 둠)
+    static CURRENT_TRACE: RefCell<Option<QueryTrace>>;
+}
+
+/// 추적 중인 쿼리 하나의 기록 (추적하는 요청마다 생성)
+#[derive(Debug)]
+pub struct QueryTrace {
+    pub session_id: Uuid,
+    pub request: String,
+    /// 시작 시각 (epoch 마이크로초)
+    pub started_at: i64,
+    started: Instant,
+    pub events: Vec<TraceEvent>,
+}
+
+/// 추적 이벤트 (단계 설명 + 시작 이후 경과 시간)
+#[derive(Debug, Clone)]
+pub struct TraceEvent {
+    pub activity: String,
+    /// 세션 시작 이후 경과 시간 (마이크로초)
+    pub source_elapsed: i64,
+}
+
+impl QueryTrace {
+    pub fn new(request: &str) -> Self {
+        Self {
+            session_id: Uuid::new_v4(),
+            request: request.to_string(),
+            started_at: chrono::Utc::now().timestamp_micros(),
+            started: Instant::now(),
+            events: Vec::new(),
+        }
+    }
+
+    /// 현재 시점의 단계 기록
+    pub fn record(&mut self, activity: impl Into<String>) {
+        self.events.push(TraceEvent {
+            activity: activity.into(),
+            source_elapsed: self.elapsed_micros(),
+        });
+    }
+
+    /// 시작 이후 경과 시간 (마이크로초)
+    pub fn elapsed_micros(&self) -> i64 {
+        self.started.elapsed().as_micros() as i64
+    }
+
+    /// trace를 이 요청의 추적으로 두고 future를 실행한 뒤, 결과와 그동안 쌓인 추적을 함께 반환
+    pub async fn scope<F: Future>(trace: Option<QueryTrace>, future: F) -> (F::Output, Option<QueryTrace>) {
+        CURRENT_TRACE.scope(RefCell::new(trace), async move {
+            let output = future.await;
+            (output, CURRENT_TRACE.with(|current| current.borrow_mut().take()))
+        }).await
+    }
+
+    /// 이 요청을 추적 중이면 단계 기록 (추적하지 않거나 scope 밖이면 메시지를 만들지 않음)
+    pub fn record_current(activity: impl FnOnce() -> String) {
+        let _ = CURRENT_TRACE.try_with(|current| {
+            if let Some(trace) = current.borrow_mut().as_mut() {
+                trace.record(activity());
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_events_are_monotonic() {
+        let mut trace = QueryTrace::new("SELECT * FROM ks.t");
+        trace.record("Parsing statement");
+        trace.record("Executing statement");
+
+        assert_eq!(trace.events.len(), 2);
+        assert!(trace.events[0].source_elapsed <= trace.events[1].source_elapsed);
+        assert!(trace.elapsed_micros() >= trace.events[1].source_elapsed);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_request_traces_do_not_mix() {
+        let traced = |name: &'static str| QueryTrace::scope(Some(QueryTrace::new(name)), async move {
+            QueryTrace::record_current(|| format!("{} started", name));
+            tokio::task::yield_now().await;
+            QueryTrace::record_current(|| format!("{} finished", name));
+        });
+        let ((_, first), (_, second)) = tokio::join!(traced("first"), traced("second"));
+
+        let activities = |trace: Option<QueryTrace>| trace.unwrap().events.into_iter().map(|event| event.activity).collect::<Vec<_>>();
+        assert_eq!(activities(first), vec!["first started", "first finished"]);
+        assert_eq!(activities(second), vec!["second started", "second finished"]);
+        // 추적하지 않는 요청은 메시지를 만들지 않음
+        QueryTrace::scope(None, async { QueryTrace::record_current(|| unreachable!()) }).await;
+        QueryTrace::record_current(|| unreachable!());
+    }
+}
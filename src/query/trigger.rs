@@ -0,0 +1,36 @@
+use std::sync::Arc;
+use crate::schema::Row;
+
+/// 쓰기 경로 트리거 (테이블 단위로 등록, 뮤테이션마다 호출)
+pub trait Trigger: Send + Sync {
+    /// 메모리 테이블 반영 전 호출. 행에 파생 컬럼을 추가하거나 Err(사유)로 쓰기를 거부
+    fn before_write(&self, _row: &mut Row) -> std::result::Result<(), String> {
+        Ok(())
+    }
+
+    /// 메모리 테이블 반영 후 호출 (기존 버전과 병합된 최종 행)
+    fn after_write(&self, _row: &Row) {}
+}
+
+/// 클로저는 반영 전 트리거로 사용
+impl<F> Trigger for F
+where
+    F: Fn(&mut Row) -> std::result::Result<(), String> + Send + Sync,
+{
+    fn before_write(&self, row: &mut Row) -> std::result::Result<(), String> {
+        self(row)
+    }
+}
+
+/// 이름이 붙은 등록된 트리거
+#[derive(Clone)]
+pub struct RegisteredTrigger {
+    pub name: String,
+    pub trigger: Arc<dyn Trigger>,
+}
+
+impl std::fmt::Debug for RegisteredTrigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RegisteredTrigger").field("name", &self.name).finish()
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use crate::error::{CoreDBError, Result};
+use crate::throttle::RateLimiter;
+
+/// 키스페이스 자원 한도 (0이면 제한 없음)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeyspaceQuota {
+    /// SSTable과 메모리 테이블 데이터 크기 합 (MB, 넘으면 쓰기 거부)
+    pub max_disk_mb: u64,
+    /// 초당 쓰기 수 (넘는 쓰기는 기다리지 않고 거부)
+    pub max_writes_per_sec: u64,
+    /// 동시에 실행하는 CQL 쿼리 수 (넘는 쿼리는 기다리지 않고 거부)
+    pub max_concurrent_queries: usize,
+}
+
+impl KeyspaceQuota {
+    /// max_disk_mb=1024,max_writes_per_sec=500,max_concurrent_queries=8 (주지 않은 항목은 제한 없음)
+    pub fn parse(value: &str) -> std::result::Result<Self, String> {
+        let mut quota = Self::default();
+        for item in value.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let (key, limit) = item.split_once('=').ok_or_else(|| format!("expected KEY=LIMIT, got {}", item))?;
+            let limit = limit.trim().parse::<u64>().map_err(|e| format!("invalid limit for {}: {}", key, e))?;
+            match key.trim() {
+                "max_disk_mb" => quota.max_disk_mb = limit,
+                "max_writes_per_sec" => quota.max_writes_per_sec = limit,
+                "max_concurrent_queries" => quota.max_concurrent_queries = limit as usize,
+                other => return Err(format!("unknown quota {}", other)),
+            }
+        }
+        Ok(quota)
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// 키스페이스 한도 적용 통계
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuotaStats {
+    pub quota: KeyspaceQuota,
+    pub active_queries: usize,
+    /// 초당 쓰기 수나 디스크 한도 때문에 거부한 쓰기 수
+    pub rejected_writes: u64,
+    pub rejected_queries: u64,
+}
+
+/// 키스페이스 하나의 한도 적용
+///
+/// 전역 입장 제어보다 먼저 확인해 한 키스페이스의 부하가 다른 키스페이스의 실행 자리를 차지하지 않게 함
+#[derive(Debug)]
+pub struct QuotaLimiter {
+    keyspace: String,
+    quota: KeyspaceQuota,
+    writes: RateLimiter,
+    queries: Arc<Semaphore>,
+    rejected_writes: AtomicU64,
+    rejected_queries: AtomicU64,
+}
+
+impl QuotaLimiter {
+    pub fn new(keyspace: &str, quota: KeyspaceQuota) -> Self {
+        Self {
+            keyspace: keyspace.to_string(),
+            quota,
+            writes: RateLimiter::new(quota.max_writes_per_sec),
+            queries: Arc::new(Semaphore::new(quota.max_concurrent_queries)),
+            rejected_writes: AtomicU64::new(0),
+            rejected_queries: AtomicU64::new(0),
+        }
+    }
+
+    pub fn quota(&self) -> KeyspaceQuota {
+        self.quota
+    }
+
+    /// 쿼리 실행 허가 (동시 쿼리 한도가 없으면 None, 가득 찼으면 거부)
+    pub fn try_acquire_query(&self) -> Result<Option<OwnedSemaphorePermit>> {
+        if self.quota.max_concurrent_queries == 0 {
+            return Ok(None);
+        }
+        match self.queries.clone().try_acquire_owned() {
+            Ok(permit) => Ok(Some(permit)),
+            Err(_) => {
+                self.rejected_queries.fetch_add(1, Ordering::Relaxed);
+                Err(self.exceeded("max_concurrent_queries", self.quota.max_concurrent_queries as u64))
+            },
+        }
+    }
+
+    /// 쓰기 한 건 확인 (used_bytes는 키스페이스의 현재 데이터 크기)
+    pub async fn check_write(&self, used_bytes: u64) -> Result<()> {
+        if self.quota.max_disk_mb > 0 && used_bytes >= self.quota.max_disk_mb.saturating_mul(1024 * 1024) {
+            self.rejected_writes.fetch_add(1, Ordering::Relaxed);
+            return Err(self.exceeded("max_disk_mb", self.quota.max_disk_mb));
+        }
+        if !self.writes.try_acquire(1).await {
+            self.rejected_writes.fetch_add(1, Ordering::Relaxed);
+            return Err(self.exceeded("max_writes_per_sec", self.quota.max_writes_per_sec));
+        }
+        Ok(())
+    }
+
+    fn exceeded(&self, quota: &str, limit: u64) -> CoreDBError {
+        CoreDBError::QuotaExceeded { keyspace: self.keyspace.clone(), quota: quota.to_string(), limit }
+    }
+
+    pub fn stats(&self) -> QuotaStats {
+        QuotaStats {
+            quota: self.quota,
+            active_queries: self.quota.max_concurrent_queries - self.queries.available_permits().min(self.quota.max_concurrent_queries),
+            rejected_writes: self.rejected_writes.load(Ordering::Relaxed),
+            rejected_queries: self.rejected_queries.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// 키스페이스별 한도 (한도를 둔 키스페이스만)
+#[derive(Debug, Default)]
+pub struct KeyspaceQuotas {
+    limiters: RwLock<HashMap<String, Arc<QuotaLimiter>>>,
+}
+
+impl KeyspaceQuotas {
+    pub fn new(quotas: &HashMap<String, KeyspaceQuota>) -> Self {
+        let limiters = Self::default();
+        for (keyspace, quota) in quotas {
+            limiters.set(keyspace, *quota);
+        }
+        limiters
+    }
+
+    /// 한도 변경 (모두 0이면 제거, 실행 중인 쿼리는 이전 한도로 끝남)
+    pub fn set(&self, keyspace: &str, quota: KeyspaceQuota) {
+        let mut limiters = self.limiters.write().unwrap();
+        if quota.is_unlimited() {
+            limiters.remove(keyspace);
+        } else {
+            limiters.insert(keyspace.to_string(), Arc::new(QuotaLimiter::new(keyspace, quota)));
+        }
+    }
+
+    pub fn limiter(&self, keyspace: &str) -> Option<Arc<QuotaLimiter>> {
+        self.limiters.read().unwrap().get(keyspace).cloned()
+    }
+
+    pub fn stats(&self) -> HashMap<String, QuotaStats> {
+        self.limiters.read().unwrap().iter()
+            .map(|(keyspace, limiter)| (keyspace.clone(), limiter.stats()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_quota_limiter_rejects_over_limits() {
+        let quota = KeyspaceQuota::parse("max_disk_mb=1, max_writes_per_sec=2,max_concurrent_queries=1").unwrap();
+        assert_eq!(quota, KeyspaceQuota { max_disk_mb: 1, max_writes_per_sec: 2, max_concurrent_queries: 1 });
+        assert!(KeyspaceQuota::parse("max_reads=1").is_err());
+        assert!(KeyspaceQuota::parse("max_disk_mb").is_err());
+        let limiter = QuotaLimiter::new("tenant", quota);
+
+        // 초당 쓰기 수
+        limiter.check_write(0).await.unwrap();
+        limiter.check_write(0).await.unwrap();
+        assert!(matches!(limiter.check_write(0).await,
+                         Err(CoreDBError::QuotaExceeded { ref quota, limit: 2, .. }) if quota == "max_writes_per_sec"));
+        // 디스크 한도
+        assert!(matches!(limiter.check_write(1024 * 1024).await,
+                         Err(CoreDBError::QuotaExceeded { ref quota, .. }) if quota == "max_disk_mb"));
+
+        // 동시 쿼리 수
+        let permit = limiter.try_acquire_query().unwrap();
+        assert!(permit.is_some());
+        assert!(limiter.try_acquire_query().is_err());
+        assert_eq!(limiter.stats().active_queries, 1);
+        drop(permit);
+        assert!(limiter.try_acquire_query().unwrap().is_some());
+        assert_eq!((limiter.stats().rejected_writes, limiter.stats().rejected_queries), (2, 1));
+
+        // 한도가 없는 키스페이스는 확인하지 않음
+        let quotas = KeyspaceQuotas::new(&HashMap::from([("tenant".to_string(), quota)]));
+        assert!(quotas.limiter("tenant").is_some());
+        assert!(quotas.limiter("other").is_none());
+        quotas.set("tenant", KeyspaceQuota::default());
+        assert!(quotas.limiter("tenant").is_none());
+    }
+}
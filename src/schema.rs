@@ -1,6 +1,8 @@
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use uuid::Uuid;
+use crate::storage::CompressionType;
 use crate::error::*;
 
 /// Cassandra 데이터 타입 정의
@@ -17,6 +19,10 @@ pub enum CassandraDataType {
     Map(Box<CassandraDataType>, Box<CassandraDataType>),
     List(Box<CassandraDataType>),
     Set(Box<CassandraDataType>),
+    /// VECTOR<FLOAT, n> (고정 차원 임베딩)
+    Vector(usize),
+    /// POINT (위도, 경도)
+    Point,
 }
 
 /// 컬럼 정의
@@ -34,14 +40,27 @@ pub struct TableOptions {
     pub bloom_filter_fp_chance: f64,
     pub default_time_to_live: Option<u32>,
     pub gc_grace_seconds: u32,
+    /// 테이블별 메모리 테이블 플러시 임계값 (MB, 없으면 전역 설정 사용)
+    pub memtable_flush_threshold_mb: Option<u64>,
+    /// 테이블별 행 캐시 크기 (MB, 없으면 행 캐시를 쓰지 않음)
+    pub row_cache_size_mb: Option<u64>,
+    /// 플러시하는 SSTable의 압축 방식
+    pub compression: CompressionType,
+    /// 셀 중 툼스톤 비율이 이보다 높은 SSTable은 함께 합칠 SSTable이 없어도 혼자 다시 씀
+    pub tombstone_threshold: f64,
+    /// 혼자 다시 쓰기 전에 SSTable이 쓰인 뒤 지나야 하는 시간 (초)
+    pub tombstone_compaction_interval: u64,
 }
 
 /// 컴팩션 전략
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CompactionStrategy {
     SizeTiered,
     Leveled,
-    TimeWindow,
+    /// 가장 큰 타임스탬프가 같은 시간 창에 드는 SSTable끼리만 컴팩션 (TTL이 있는 시계열 테이블용)
+    TimeWindow {
+        window_seconds: u64,
+    },
 }
 
 /// 테이블 스키마
@@ -71,6 +90,9 @@ pub enum CassandraValue {
     Map(HashMap<String, CassandraValue>),  // HashMap doesn't implement Ord
     List(Vec<CassandraValue>),
     Set(Vec<CassandraValue>),
+    Vector(Vec<f32>),
+    /// 위도, 경도
+    Point(f64, f64),
 }
 
 // Custom Eq implementation for CassandraValue
@@ -79,35 +101,58 @@ impl Eq for CassandraValue {}
 // Custom PartialOrd implementation
 impl PartialOrd for CassandraValue {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        use std::cmp::Ordering;
-        use CassandraValue::*;
-        
-        match (self, other) {
-            (Text(a), Text(b)) => a.partial_cmp(b),
-            (Int(a), Int(b)) => a.partial_cmp(b),
-            (BigInt(a), BigInt(b)) => a.partial_cmp(b),
-            (UUID(a), UUID(b)) => a.partial_cmp(b),
-            (Timestamp(a), Timestamp(b)) => a.partial_cmp(b),
-            (Boolean(a), Boolean(b)) => a.partial_cmp(b),
-            (Double(a), Double(b)) => a.partial_cmp(b),
-            (Blob(a), Blob(b)) => a.partial_cmp(b),
-            (List(a), List(b)) => a.partial_cmp(b),
-            (Set(a), Set(b)) => a.partial_cmp(b),
-            (Null, Null) => Some(Ordering::Equal),
-            (Map(_), Map(_)) => Some(Ordering::Equal), // Maps cannot be ordered
-            _ => None,
-        }
+        Some(self.cmp(other))
     }
 }
 
 // Custom Ord implementation
 impl Ord for CassandraValue {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap_or(std::cmp::Ordering::Equal)
+        use std::cmp::Ordering;
+        use CassandraValue::*;
+
+        match (self, other) {
+            (Text(a), Text(b)) => a.cmp(b),
+            (Int(a), Int(b)) => a.cmp(b),
+            (BigInt(a), BigInt(b)) => a.cmp(b),
+            (UUID(a), UUID(b)) => a.cmp(b),
+            (Timestamp(a), Timestamp(b)) => a.cmp(b),
+            (Boolean(a), Boolean(b)) => a.cmp(b),
+            (Double(a), Double(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+            (Blob(a), Blob(b)) => a.cmp(b),
+            (List(a), List(b)) => a.cmp(b),
+            (Set(a), Set(b)) => a.cmp(b),
+            (Vector(a), Vector(b)) => a.iter()
+                .zip(b)
+                .map(|(x, y)| x.total_cmp(y))
+                .find(|ordering| *ordering != Ordering::Equal)
+                .unwrap_or_else(|| a.len().cmp(&b.len())),
+            (Point(lat_a, lon_a), Point(lat_b, lon_b)) => lat_a.total_cmp(lat_b).then(lon_a.total_cmp(lon_b)),
+            (Null, Null) => Ordering::Equal,
+            (Map(_), Map(_)) => Ordering::Equal, // Maps cannot be ordered
+            _ => Ordering::Equal,
+        }
     }
 }
 
 impl CassandraValue {
+    /// 값이 힙에 따로 할당한 바이트 (enum 자체 크기 제외)
+    pub fn heap_size(&self) -> u64 {
+        let element = std::mem::size_of::<CassandraValue>() as u64;
+        match self {
+            CassandraValue::Text(s) => s.capacity() as u64,
+            CassandraValue::Blob(b) => b.capacity() as u64,
+            CassandraValue::Map(m) => m.iter()
+                .map(|(k, v)| std::mem::size_of::<String>() as u64 + k.capacity() as u64 + element + v.heap_size())
+                .sum(),
+            CassandraValue::List(items) | CassandraValue::Set(items) => {
+                items.capacity() as u64 * element + items.iter().map(|item| item.heap_size()).sum::<u64>()
+            },
+            CassandraValue::Vector(v) => 4 * v.capacity() as u64,
+            _ => 0,
+        }
+    }
+    
     pub fn serialized_size(&self) -> u64 {
         match self {
             CassandraValue::Text(s) => 8 + s.len() as u64,
@@ -140,7 +185,105 @@ impl CassandraValue {
                 }
                 size
             },
+            CassandraValue::Vector(v) => 8 + 4 * v.len() as u64,
+            CassandraValue::Point(_, _) => 16,
+        }
+    }
+
+    /// 타임스탬프 동률 시 사전순 비교에 쓰이는 결정적 바이트 표현
+    ///
+    /// 같은 타입의 스칼라 값은 바이트 순서가 값 순서와 같고, 모든 값이 스스로 끝을 알 수 있어
+    /// 컬렉션 안에서 항목을 이어 붙여도 다른 값과 겹치지 않음 (보조 인덱스 키로도 사용)
+    pub fn comparable_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write_comparable_bytes(&mut bytes);
+        bytes
+    }
+
+    fn write_comparable_bytes(&self, out: &mut Vec<u8>) {
+        match self {
+            CassandraValue::Null => out.push(0),
+            CassandraValue::Text(s) => {
+                out.push(1);
+                Self::write_terminated_bytes(s.as_bytes(), out);
+            },
+            CassandraValue::Int(i) => {
+                out.push(2);
+                out.extend_from_slice(&i.to_be_bytes());
+            },
+            CassandraValue::BigInt(i) => {
+                out.push(3);
+                out.extend_from_slice(&i.to_be_bytes());
+            },
+            CassandraValue::UUID(u) => {
+                out.push(4);
+                out.extend_from_slice(u.as_bytes());
+            },
+            CassandraValue::Timestamp(t) => {
+                out.push(5);
+                out.extend_from_slice(&t.to_be_bytes());
+            },
+            CassandraValue::Boolean(b) => {
+                out.push(6);
+                out.push(*b as u8);
+            },
+            CassandraValue::Double(d) => {
+                out.push(7);
+                out.extend_from_slice(&d.to_bits().to_be_bytes());
+            },
+            CassandraValue::Blob(b) => {
+                out.push(8);
+                Self::write_terminated_bytes(b, out);
+            },
+            CassandraValue::Map(m) => {
+                out.push(9);
+                // HashMap 순회 순서에 의존하지 않도록 키 정렬
+                let mut keys: Vec<&String> = m.keys().collect();
+                keys.sort();
+                out.extend_from_slice(&(keys.len() as u32).to_be_bytes());
+                for k in keys {
+                    Self::write_terminated_bytes(k.as_bytes(), out);
+                    m[k].write_comparable_bytes(out);
+                }
+            },
+            CassandraValue::List(l) => {
+                out.push(10);
+                out.extend_from_slice(&(l.len() as u32).to_be_bytes());
+                for item in l {
+                    item.write_comparable_bytes(out);
+                }
+            },
+            CassandraValue::Set(s) => {
+                out.push(11);
+                out.extend_from_slice(&(s.len() as u32).to_be_bytes());
+                for item in s {
+                    item.write_comparable_bytes(out);
+                }
+            },
+            CassandraValue::Vector(v) => {
+                out.push(12);
+                out.extend_from_slice(&(v.len() as u32).to_be_bytes());
+                for x in v {
+                    out.extend_from_slice(&x.to_bits().to_be_bytes());
+                }
+            },
+            CassandraValue::Point(lat, lon) => {
+                out.push(13);
+                out.extend_from_slice(&lat.to_bits().to_be_bytes());
+                out.extend_from_slice(&lon.to_bits().to_be_bytes());
+            },
+        }
+    }
+
+    /// 0x00을 0x00 0xFF로 바꾸고 0x00 0x00으로 끝냄 (바이트 순서를 유지하면서 끝을 표시)
+    fn write_terminated_bytes(bytes: &[u8], out: &mut Vec<u8>) {
+        for &byte in bytes {
+            out.push(byte);
+            if byte == 0 {
+                out.push(0xFF);
+            }
         }
+        out.extend_from_slice(&[0, 0]);
     }
 }
 
@@ -185,21 +328,347 @@ pub struct Cell {
     pub is_deleted: bool,
 }
 
+impl Cell {
+    /// 같은 컬럼의 다른 버전보다 이 셀이 우선하는지 여부 (Cassandra 규칙)
+    /// 1. 타임스탬프가 큰 쪽 2. 같으면 툼스톤 3. 그래도 같으면 사전순으로 큰 값
+    pub fn supersedes(&self, other: &Cell) -> bool {
+        use std::cmp::Ordering;
+
+        match self.timestamp.cmp(&other.timestamp) {
+            Ordering::Greater => true,
+            Ordering::Less => false,
+            Ordering::Equal => match (self.is_deleted, other.is_deleted) {
+                (true, false) => true,
+                (false, true) => false,
+                _ => self.value.comparable_bytes() > other.value.comparable_bytes(),
+            },
+        }
+    }
+
+    /// 두 셀 중 승자 선택
+    pub fn reconcile(a: Cell, b: Cell) -> Cell {
+        if b.supersedes(&a) { b } else { a }
+    }
+
+    /// 셀이 죽은 시각 (초, 툼스톤은 쓴 시각, TTL 셀은 만료 시각, 그 외는 i64::MAX)
+    pub fn deletion_time(&self) -> i64 {
+        let written_at = self.timestamp / 1_000_000;
+        match (self.is_deleted, self.ttl) {
+            (true, _) => written_at,
+            (false, Some(ttl)) => written_at.saturating_add(ttl as i64),
+            (false, None) => i64::MAX,
+        }
+    }
+}
+
+/// 컬럼 번호 배치
+///
+/// 스키마 컬럼 순서(파티션 키, 클러스터링 키, 일반, 정적)대로 번호를 부여하고,
+/// 스키마에 없는 컬럼은 처음 쓰일 때 뒤에 추가함
+#[derive(Debug, Clone, Default)]
+pub struct ColumnLayout {
+    names: Vec<Arc<str>>,
+    ids: HashMap<Arc<str>, u16>,
+}
+
+impl ColumnLayout {
+    pub fn new<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut layout = Self::default();
+        for name in names {
+            layout.push(name.as_ref());
+        }
+        layout
+    }
+
+    /// 컬럼 번호 조회
+    pub fn id(&self, name: &str) -> Option<u16> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: u16) -> &str {
+        &self.names[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// 번호 순서의 컬럼 이름
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(|name| name.as_ref())
+    }
+
+    /// 컬럼을 추가하고 번호 반환 (이미 있으면 기존 번호)
+    pub fn push(&mut self, name: &str) -> u16 {
+        if let Some(id) = self.id(name) {
+            return id;
+        }
+        let id = u16::try_from(self.names.len()).expect("column layout is limited to 65536 columns");
+        let name: Arc<str> = Arc::from(name);
+        self.ids.insert(name.clone(), id);
+        self.names.push(name);
+        id
+    }
+}
+
+/// 행의 셀 목록
+///
+/// 셀마다 컬럼 이름 문자열을 두는 대신 테이블이 공유하는 컬럼 배치의 번호로 인덱싱하는
+/// 압축 벡터. 이름으로 조회하는 HashMap 비슷한 API를 제공하며 순회는 컬럼 번호 순서
+#[derive(Debug, Clone, Default)]
+pub struct Cells {
+    layout: Arc<ColumnLayout>,
+    values: Vec<Option<Cell>>,
+    len: usize,
+}
+
+impl Cells {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 컬럼 배치를 공유하는 빈 셀 목록
+    pub fn with_layout(layout: Arc<ColumnLayout>) -> Self {
+        Self { layout, values: Vec::new(), len: 0 }
+    }
+
+    pub fn layout(&self) -> &Arc<ColumnLayout> {
+        &self.layout
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Cell> {
+        self.get_by_id(self.layout.id(name)?)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Cell> {
+        let id = self.layout.id(name)?;
+        self.values.get_mut(id as usize)?.as_mut()
+    }
+
+    pub fn get_by_id(&self, id: u16) -> Option<&Cell> {
+        self.values.get(id as usize)?.as_ref()
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// 셀 저장 (배치에 없는 컬럼이면 배치를 복사해 추가)
+    pub fn insert(&mut self, name: impl AsRef<str>, cell: Cell) -> Option<Cell> {
+        let name = name.as_ref();
+        let id = match self.layout.id(name) {
+            Some(id) => id,
+            None => Arc::make_mut(&mut self.layout).push(name),
+        };
+        self.insert_by_id(id, cell)
+    }
+
+    /// 번호로 셀 저장 (번호는 이 목록의 배치 기준)
+    pub fn insert_by_id(&mut self, id: u16, cell: Cell) -> Option<Cell> {
+        let index = id as usize;
+        if self.values.len() <= index {
+            self.values.resize(index + 1, None);
+        }
+        let previous = self.values[index].replace(cell);
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Cell> {
+        let id = self.layout.id(name)?;
+        let removed = self.values.get_mut(id as usize)?.take();
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// 조건을 만족하는 셀만 남김
+    pub fn retain(&mut self, mut keep: impl FnMut(&str, &Cell) -> bool) {
+        for (id, value) in self.values.iter_mut().enumerate() {
+            if value.as_ref().is_some_and(|cell| !keep(self.layout.name(id as u16), cell)) {
+                *value = None;
+                self.len -= 1;
+            }
+        }
+    }
+
+    /// (컬럼 이름, 셀)을 컬럼 번호 순서로 순회
+    pub fn iter(&self) -> CellsIter<'_> {
+        CellsIter { layout: &self.layout, values: self.values.iter().enumerate() }
+    }
+
+    /// (컬럼 번호, 셀)을 번호 순서로 순회
+    pub fn iter_ids(&self) -> impl Iterator<Item = (u16, &Cell)> {
+        self.values.iter()
+            .enumerate()
+            .filter_map(|(id, cell)| Some((id as u16, cell.as_ref()?)))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.iter().map(|(name, _)| name)
+    }
+
+    /// 선택한 컬럼만 남긴 복사본 (같은 배치 공유)
+    pub fn filtered(&self, mut keep: impl FnMut(&str) -> bool) -> Self {
+        let mut cells = Self::with_layout(self.layout.clone());
+        for (id, cell) in self.iter_ids() {
+            if keep(self.layout.name(id)) {
+                cells.insert_by_id(id, cell.clone());
+            }
+        }
+        cells
+    }
+}
+
+/// `Cells::iter` 반복자
+pub struct CellsIter<'a> {
+    layout: &'a ColumnLayout,
+    values: std::iter::Enumerate<std::slice::Iter<'a, Option<Cell>>>,
+}
+
+impl<'a> Iterator for CellsIter<'a> {
+    type Item = (&'a str, &'a Cell);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.values.by_ref().find_map(|(id, cell)| Some((self.layout.name(id as u16), cell.as_ref()?)))
+    }
+}
+
+impl<'a> IntoIterator for &'a Cells {
+    type Item = (&'a str, &'a Cell);
+    type IntoIter = CellsIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl std::ops::Index<&str> for Cells {
+    type Output = Cell;
+
+    fn index(&self, name: &str) -> &Cell {
+        self.get(name).unwrap_or_else(|| panic!("no cell for column {}", name))
+    }
+}
+
+impl IntoIterator for Cells {
+    type Item = (String, Cell);
+    type IntoIter = std::vec::IntoIter<(String, Cell)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let layout = self.layout;
+        self.values.into_iter()
+            .enumerate()
+            .filter_map(|(id, cell)| Some((layout.name(id as u16).to_string(), cell?)))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<(S, Cell)> for Cells {
+    fn from_iter<I: IntoIterator<Item = (S, Cell)>>(iter: I) -> Self {
+        let mut cells = Self::new();
+        for (name, cell) in iter {
+            cells.insert(name, cell);
+        }
+        cells
+    }
+}
+
+impl From<HashMap<String, Cell>> for Cells {
+    fn from(map: HashMap<String, Cell>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+// 직렬화 형식은 이름 → 셀 맵 (커밋 로그 등 스키마 없이 읽는 곳과 호환)
+impl Serialize for Cells {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.len))?;
+        for (name, cell) in self.iter() {
+            map.serialize_entry(name, cell)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Cells {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        Ok(HashMap::<String, Cell>::deserialize(deserializer)?.into())
+    }
+}
+
 /// 행 데이터
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Row {
     pub partition_key: PartitionKey,
     pub clustering_key: Option<ClusteringKey>,
-    pub cells: HashMap<String, Cell>,
+    pub cells: Cells,
     pub timestamp: i64, // write timestamp
 }
 
+impl Row {
+    /// 삭제 표시된 셀 수
+    pub fn tombstone_count(&self) -> u64 {
+        self.cells.iter().filter(|(_, cell)| cell.is_deleted).count() as u64
+    }
+
+    /// 같은 행의 다른 버전을 셀 단위로 병합
+    pub fn merge(&mut self, other: Row) {
+        // 같은 컬럼 배치를 공유하면 번호로 바로 병합
+        if Arc::ptr_eq(self.cells.layout(), other.cells.layout()) {
+            for (id, cell) in other.cells.values.into_iter().enumerate() {
+                let Some(cell) = cell else { continue };
+                let merged = match self.cells.values.get_mut(id).and_then(Option::take) {
+                    Some(existing) => {
+                        self.cells.len -= 1;
+                        Cell::reconcile(existing, cell)
+                    },
+                    None => cell,
+                };
+                self.cells.insert_by_id(id as u16, merged);
+            }
+        } else {
+            for (column, cell) in other.cells {
+                let merged = match self.cells.remove(&column) {
+                    Some(existing) => Cell::reconcile(existing, cell),
+                    None => cell,
+                };
+                self.cells.insert(column, merged);
+            }
+        }
+        self.timestamp = self.timestamp.max(other.timestamp);
+    }
+}
+
 /// 키스페이스 정의
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeyspaceDefinition {
     pub name: String,
     pub replication_factor: u32,
     pub strategy: ReplicationStrategy,
+    /// false면 데이터 변경을 커밋 로그에 남기지 않음 (플러시 전에 멈추면 잃어도 되는 임시 키스페이스용)
+    pub durable_writes: bool,
 }
 
 /// 복제 전략 (단일 노드에서는 단순화)
@@ -215,6 +684,11 @@ impl Default for TableOptions {
             bloom_filter_fp_chance: 0.01,
             default_time_to_live: None,
             gc_grace_seconds: 864000, // 10 days
+            memtable_flush_threshold_mb: None,
+            row_cache_size_mb: None,
+            compression: CompressionType::LZ4,
+            tombstone_threshold: 0.2,
+            tombstone_compaction_interval: 86400, // 1 day
         }
     }
 }
@@ -239,6 +713,24 @@ impl TableSchema {
         }
     }
     
+    /// 스키마 컬럼 순서의 컬럼 번호 배치
+    pub fn column_layout(&self) -> ColumnLayout {
+        ColumnLayout::new(self.partition_key.iter()
+            .chain(&self.clustering_key)
+            .chain(&self.regular_columns)
+            .chain(&self.static_columns)
+            .map(|column| column.name.as_str()))
+    }
+    
+    /// 이름으로 컬럼 정의 조회
+    pub fn column(&self, name: &str) -> Option<&ColumnDefinition> {
+        self.partition_key.iter()
+            .chain(&self.clustering_key)
+            .chain(&self.regular_columns)
+            .chain(&self.static_columns)
+            .find(|c| c.name == name)
+    }
+    
     pub fn validate(&self) -> Result<()> {
         if self.partition_key.is_empty() {
             return Err(CoreDBError::InvalidSchema {
@@ -265,6 +757,12 @@ impl TableSchema {
             }
         }
         
+        if self.options.memtable_flush_threshold_mb == Some(0) {
+            return Err(CoreDBError::InvalidSchema {
+                message: "memtable_flush_threshold_mb must be greater than 0".to_string(),
+            });
+        }
+        
         Ok(())
     }
 }
@@ -304,4 +802,95 @@ mod tests {
         
         assert!(schema.validate().is_err());
     }
+
+    fn cell(value: CassandraValue, timestamp: i64, is_deleted: bool) -> Cell {
+        Cell { value, timestamp, ttl: None, is_deleted }
+    }
+
+    #[test]
+    fn test_cell_reconcile_tie_breaking() {
+        // 타임스탬프가 큰 쪽이 승리
+        let older = cell(CassandraValue::Text("z".to_string()), 1, false);
+        let newer = cell(CassandraValue::Text("a".to_string()), 2, false);
+        assert_eq!(Cell::reconcile(older.clone(), newer.clone()).timestamp, 2);
+        assert_eq!(Cell::reconcile(newer, older).timestamp, 2);
+
+        // 동률이면 툼스톤이 승리
+        let live = cell(CassandraValue::Text("z".to_string()), 5, false);
+        let tombstone = cell(CassandraValue::Null, 5, true);
+        assert!(Cell::reconcile(live.clone(), tombstone.clone()).is_deleted);
+        assert!(Cell::reconcile(tombstone, live).is_deleted);
+
+        // 그래도 동률이면 사전순으로 큰 값이 승리 (입력 순서와 무관)
+        let a = cell(CassandraValue::Text("apple".to_string()), 5, false);
+        let b = cell(CassandraValue::Text("banana".to_string()), 5, false);
+        assert_eq!(Cell::reconcile(a.clone(), b.clone()).value, CassandraValue::Text("banana".to_string()));
+        assert_eq!(Cell::reconcile(b, a).value, CassandraValue::Text("banana".to_string()));
+    }
+
+    #[test]
+    fn test_comparable_bytes_do_not_collide() {
+        let text = |s: &str| CassandraValue::Text(s.to_string());
+        let distinct = [
+            (CassandraValue::List(vec![text("ab"), text("c")]), CassandraValue::List(vec![text("a"), text("bc")])),
+            (CassandraValue::Set(vec![text("ab"), text("c")]), CassandraValue::Set(vec![text("a"), text("bc")])),
+            (CassandraValue::List(vec![CassandraValue::List(vec![text("a")]), text("b")]),
+             CassandraValue::List(vec![CassandraValue::List(vec![text("a"), text("b")])])),
+            (CassandraValue::List(vec![text("a\0")]), CassandraValue::List(vec![text("a"), CassandraValue::Null])),
+            (CassandraValue::Blob(vec![1, 0]), CassandraValue::Blob(vec![1])),
+            (CassandraValue::Map(HashMap::from([("a".to_string(), text("bc"))])),
+             CassandraValue::Map(HashMap::from([("ab".to_string(), text("c"))]))),
+        ];
+        for (left, right) in distinct {
+            assert_ne!(left.comparable_bytes(), right.comparable_bytes(), "{:?} vs {:?}", left, right);
+        }
+
+        // 같은 타입의 텍스트/블롭은 바이트 순서가 값 순서와 같음
+        let ordered = ["", "a", "a\0", "a\0b", "ab", "b"];
+        for pair in ordered.windows(2) {
+            assert!(text(pair[0]).comparable_bytes() < text(pair[1]).comparable_bytes(), "{:?} < {:?}", pair[0], pair[1]);
+        }
+    }
+    
+    #[test]
+    fn test_cells_share_layout_and_merge() {
+        let text = |v: &str, ts: i64| Cell { value: CassandraValue::Text(v.to_string()), timestamp: ts, ttl: None, is_deleted: false };
+        let layout = Arc::new(ColumnLayout::new(["id", "name", "email"]));
+        
+        let mut older = Cells::with_layout(layout.clone());
+        older.insert("email", text("old@example.com", 1));
+        older.insert("name", text("old", 1));
+        // 순회는 스키마 컬럼 순서
+        assert_eq!(older.keys().collect::<Vec<_>>(), vec!["name", "email"]);
+        assert!(Arc::ptr_eq(older.layout(), &layout));
+        
+        // 배치에 없는 컬럼은 이 행의 배치만 복사해 추가
+        let mut extra = Cells::with_layout(layout.clone());
+        extra.insert("nickname", text("n", 2));
+        assert!(!Arc::ptr_eq(extra.layout(), &layout));
+        assert_eq!(layout.len(), 3);
+        
+        let key = PartitionKey { components: vec![CassandraValue::Int(1)] };
+        let row = |cells: Cells, timestamp| Row { partition_key: key.clone(), clustering_key: None, cells, timestamp };
+        let mut newer = Cells::with_layout(layout.clone());
+        newer.insert("name", text("new", 2));
+        
+        let mut merged = row(older, 1);
+        merged.merge(row(newer, 2));
+        merged.merge(row(extra, 2));
+        assert_eq!(merged.cells.len(), 3);
+        assert_eq!(merged.cells["name"].value, CassandraValue::Text("new".to_string()));
+        assert_eq!(merged.cells["email"].value, CassandraValue::Text("old@example.com".to_string()));
+        assert!(merged.cells.contains_key("nickname"));
+        
+        // 직렬화는 이름 기반이므로 배치 없이도 복원 가능
+        let bytes = bincode::serialize(&merged).unwrap();
+        let decoded: Row = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded.cells.len(), 3);
+        assert_eq!(decoded.cells["nickname"].value, CassandraValue::Text("n".to_string()));
+        
+        assert!(merged.cells.remove("email").is_some());
+        assert_eq!(merged.cells.len(), 2);
+        assert!(merged.cells.get("email").is_none());
+    }
 }
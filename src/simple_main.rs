@@ -1,6 +1,4 @@
-use std::path::PathBuf;
 use std::collections::HashMap;
-use serde::{Serialize, Deserialize};
 
 /// 간단한 데이터베이스 구조
 #[derive(Debug)]
@@ -8,6 +6,12 @@ pub struct SimpleDB {
     data: HashMap<String, HashMap<String, HashMap<String, String>>>,
 }
 
+impl Default for SimpleDB {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SimpleDB {
     pub fn new() -> Self {
         Self {
@@ -47,7 +51,7 @@ impl SimpleDB {
             .get(keyspace)?
             .get(table)?
             .get(key)
-            .map(|v| v.clone())
+            .cloned()
     }
     
     pub fn list_keyspaces(&self) -> Vec<String> {
@@ -0,0 +1,253 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::error::{CoreDBError, Result};
+use crate::storage::{Manifest, SSTable, COMPONENT_SUFFIXES};
+
+/// 테이블 디렉토리 안의 스냅샷 디렉토리 (<keyspace>/<table>/snapshots/<name>/)
+pub const SNAPSHOTS_DIRECTORY: &str = "snapshots";
+/// 스냅샷 정보 파일 (만든 시각과 SSTable 목록)
+pub const SNAPSHOT_INFO_FILE: &str = "snapshot.json";
+/// 스케줄러가 만든 스냅샷 이름 앞부분 (보관 정책은 이 스냅샷에만 적용)
+pub const SCHEDULED_SNAPSHOT_PREFIX: &str = "auto-";
+
+/// 테이블 하나의 스냅샷
+///
+/// 스냅샷 디렉토리에는 SSTable 하드 링크와 그 목록만 담은 매니페스트가 있어 테이블 디렉토리로 옮기면 그대로 열 수 있음
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotInfo {
+    pub name: String,
+    pub keyspace: String,
+    pub table: String,
+    /// 만든 시각 (마이크로초)
+    pub created_at: i64,
+    pub sstables: Vec<String>,
+    /// 구성 파일 크기 합 (하드 링크라 원본 SSTable이 남아 있는 동안은 공간을 더 쓰지 않음)
+    pub size_bytes: u64,
+}
+
+/// 키스페이스 주기 스냅샷 (interval 경계마다 찍고 keep_last, max_age를 넘는 이전 자동 스냅샷은 지움)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct SnapshotSchedule {
+    /// 유닉스 시각 0부터 이 간격의 배수마다 (1h면 매 정각)
+    pub interval: Duration,
+    /// 남길 최근 자동 스냅샷 수 (0이면 제한 없음)
+    pub keep_last: usize,
+    /// 이보다 오래된 자동 스냅샷은 지움 (None이면 제한 없음)
+    pub max_age: Option<Duration>,
+}
+
+impl SnapshotSchedule {
+    /// interval=6h,keep=28,max_age=7d (간격은 s/m/h/d/w 단위, 보관 항목은 생략 가능)
+    pub fn parse(value: &str) -> std::result::Result<Self, String> {
+        let mut interval = None;
+        let mut keep_last = 0;
+        let mut max_age = None;
+        for item in value.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let (key, setting) = item.split_once('=').ok_or_else(|| format!("expected KEY=VALUE, got {}", item))?;
+            let setting = setting.trim();
+            match key.trim() {
+                "interval" => interval = Some(parse_duration(setting)?),
+                "keep" => keep_last = setting.parse().map_err(|e| format!("invalid keep: {}", e))?,
+                "max_age" => max_age = Some(parse_duration(setting)?),
+                other => return Err(format!("unknown snapshot setting {}", other)),
+            }
+        }
+        let interval = interval.ok_or("interval is required")?;
+        if interval.is_zero() {
+            return Err("interval must be positive".to_string());
+        }
+        Ok(Self { interval, keep_last, max_age })
+    }
+
+    /// now(초) 뒤의 첫 간격 경계 (초)
+    pub fn next_run_after(&self, now: i64) -> i64 {
+        let interval = self.interval.as_secs().max(1) as i64;
+        (now.div_euclid(interval) + 1) * interval
+    }
+
+    /// 보관 정책을 넘은 자동 스냅샷 이름 (snapshots는 (이름, 만든 시각 마이크로초), now는 마이크로초)
+    pub fn expired(&self, snapshots: &[(String, i64)], now: i64) -> Vec<String> {
+        let snapshots: Vec<_> = snapshots.iter()
+            .filter(|(name, _)| name.starts_with(SCHEDULED_SNAPSHOT_PREFIX))
+            .cloned()
+            .collect();
+        expired_by_retention(snapshots, self.keep_last, self.max_age, now)
+    }
+}
+
+/// 최근 keep_last개를 넘거나 max_age보다 오래된 항목 이름 (최근 것부터, keep_last 0은 제한 없음)
+pub(crate) fn expired_by_retention(mut entries: Vec<(String, i64)>, keep_last: usize, max_age: Option<Duration>, now: i64) -> Vec<String> {
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| b.0.cmp(&a.0)));
+    entries.into_iter().enumerate()
+        .filter(|(rank, (_, created_at))| {
+            let too_many = keep_last > 0 && *rank >= keep_last;
+            let too_old = max_age.is_some_and(|max_age| now.saturating_sub(*created_at) > max_age.as_micros() as i64);
+            too_many || too_old
+        })
+        .map(|(_, (name, _))| name)
+        .collect()
+}
+
+impl TryFrom<String> for SnapshotSchedule {
+    type Error = String;
+
+    fn try_from(value: String) -> std::result::Result<Self, String> {
+        Self::parse(&value)
+    }
+}
+
+/// 스케줄러가 만드는 스냅샷 이름 (auto-20240101T060000Z, 시각은 초 단위 UTC)
+pub fn scheduled_snapshot_name(at: chrono::DateTime<chrono::Utc>) -> String {
+    format!("{}{}", SCHEDULED_SNAPSHOT_PREFIX, at.format("%Y%m%dT%H%M%SZ"))
+}
+
+/// 이름을 주지 않은 스냅샷의 이름 (현재 시각, 밀리초)
+pub fn default_snapshot_name() -> String {
+    chrono::Utc::now().timestamp_millis().to_string()
+}
+
+/// 90s, 15m, 6h, 7d, 2w
+pub(crate) fn parse_duration(value: &str) -> std::result::Result<Duration, String> {
+    let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount.parse().map_err(|_| format!("invalid duration {}", value))?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(format!("invalid duration {} (use s, m, h, d or w)", value)),
+    };
+    Ok(Duration::from_secs(amount * seconds))
+}
+
+/// 테이블 디렉토리의 스냅샷 디렉토리
+pub fn snapshot_directory(table_directory: &Path, name: &str) -> PathBuf {
+    table_directory.join(SNAPSHOTS_DIRECTORY).join(name)
+}
+
+/// 스냅샷 이름 확인 (디렉토리 이름으로 쓰므로 경로 구분자와 . 으로 시작하는 이름은 거부)
+pub fn validate_snapshot_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(CoreDBError::Snapshot { message: format!("Invalid snapshot name: {:?}", name) });
+    }
+    Ok(())
+}
+
+/// 매니페스트의 SSTable 구성 파일을 하드 링크하고 매니페스트와 정보 파일을 써서 테이블 스냅샷을 만듦
+///
+/// 데이터를 복사하지 않아 파일 수에 비례하는 시간만 걸림. 실행 중인 데이터베이스에서는 SSTable이 지워지지 않도록
+/// 테이블 잠금을 쥐고 호출해야 하며, 같은 이름의 스냅샷이 이미 있으면 실패함
+pub async fn create_table_snapshot(table_directory: &Path, manifest: &Manifest, mut info: SnapshotInfo) -> Result<SnapshotInfo> {
+    let directory = snapshot_directory(table_directory, &info.name);
+    if tokio::fs::try_exists(&directory).await? {
+        return Err(CoreDBError::Snapshot { message: format!("Snapshot {} of {}.{} already exists", info.name, info.keyspace, info.table) });
+    }
+    tokio::fs::create_dir_all(&directory).await?;
+
+    for entry in &manifest.sstables {
+        for suffix in COMPONENT_SUFFIXES {
+            let source = SSTable::component_path(table_directory, &entry.id, suffix);
+            let target = SSTable::component_path(&directory, &entry.id, suffix);
+            match tokio::fs::hard_link(&source, &target).await {
+                Ok(()) => info.size_bytes += tokio::fs::metadata(&target).await?.len(),
+                // 이전 형식 SSTable에는 없는 구성 파일이 있음
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {},
+                Err(e) => return Err(e.into()),
+            }
+        }
+        info.sstables.push(entry.id.clone());
+    }
+    manifest.write_to(&directory).await?;
+    tokio::fs::write(directory.join(SNAPSHOT_INFO_FILE), serde_json::to_vec_pretty(&info)?).await?;
+    Ok(info)
+}
+
+/// 서버를 멈춘 상태에서 테이블 디렉토리의 스냅샷을 만듦 (`coredb snapshot create`)
+///
+/// 매니페스트에 있는 SSTable만 담으므로 커밋 로그에만 있는 쓰기는 빠짐 (먼저 drain 하거나 실행 중인 서버의 POST /snapshots 사용)
+pub async fn snapshot_table_directory(table_directory: &Path, keyspace: &str, table: &str, name: &str) -> Result<SnapshotInfo> {
+    validate_snapshot_name(name)?;
+    let manifest = Manifest::load(table_directory).await?
+        .ok_or_else(|| CoreDBError::Snapshot { message: format!("{} has no manifest", table_directory.display()) })?;
+    let info = SnapshotInfo {
+        name: name.to_string(),
+        keyspace: keyspace.to_string(),
+        table: table.to_string(),
+        created_at: chrono::Utc::now().timestamp_micros(),
+        sstables: Vec::new(),
+        size_bytes: 0,
+    };
+    create_table_snapshot(table_directory, &manifest, info).await
+}
+
+/// 테이블의 스냅샷 목록 (이름 순, 정보 파일이 없는 디렉토리는 건너뜀)
+pub async fn list_table_snapshots(table_directory: &Path) -> Result<Vec<SnapshotInfo>> {
+    let mut entries = match tokio::fs::read_dir(table_directory.join(SNAPSHOTS_DIRECTORY)).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut snapshots = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        match tokio::fs::read(entry.path().join(SNAPSHOT_INFO_FILE)).await {
+            Ok(data) => snapshots.push(serde_json::from_slice::<SnapshotInfo>(&data)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {},
+            Err(e) => return Err(e.into()),
+        }
+    }
+    snapshots.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(snapshots)
+}
+
+/// 테이블 스냅샷 삭제 (없으면 false, 원본 SSTable은 그대로)
+pub async fn remove_table_snapshot(table_directory: &Path, name: &str) -> Result<bool> {
+    validate_snapshot_name(name)?;
+    match tokio::fs::remove_dir_all(snapshot_directory(table_directory, name)).await {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_schedule_parse_and_retention() {
+        let schedule = SnapshotSchedule::parse("interval=6h, keep=2, max_age=1d").unwrap();
+        assert_eq!(schedule, SnapshotSchedule { interval: Duration::from_secs(6 * 3600), keep_last: 2, max_age: Some(Duration::from_secs(86400)) });
+        assert_eq!(SnapshotSchedule::parse("interval=15m").unwrap().keep_last, 0);
+        assert!(SnapshotSchedule::parse("keep=3").is_err());
+        assert!(SnapshotSchedule::parse("interval=0s").is_err());
+        assert!(SnapshotSchedule::parse("interval=5x").is_err());
+        assert!(SnapshotSchedule::parse("interval=1h,retain=2").is_err());
+
+        // 간격 경계에 맞춤 (6시간이면 0, 6, 12, 18시)
+        assert_eq!(schedule.next_run_after(0), 6 * 3600);
+        assert_eq!(schedule.next_run_after(6 * 3600), 12 * 3600);
+        assert_eq!(schedule.next_run_after(6 * 3600 + 1), 12 * 3600);
+
+        // 최근 2개만 남기고 하루가 지난 것도 지움, 직접 만든 스냅샷은 건드리지 않음
+        let hour = 3_600_000_000i64;
+        let now = 100 * hour;
+        let snapshots = vec![
+            ("auto-a".to_string(), now - 30 * hour),
+            ("auto-b".to_string(), now - 12 * hour),
+            ("auto-c".to_string(), now - 6 * hour),
+            ("auto-d".to_string(), now),
+            ("before-upgrade".to_string(), now - 50 * hour),
+        ];
+        assert_eq!(schedule.expired(&snapshots, now), vec!["auto-b".to_string(), "auto-a".to_string()]);
+        let schedule = SnapshotSchedule { keep_last: 0, ..schedule };
+        assert_eq!(schedule.expired(&snapshots, now), vec!["auto-a".to_string()]);
+
+        let at = chrono::DateTime::from_timestamp(1_704_088_800, 0).unwrap();
+        assert_eq!(scheduled_snapshot_name(at), "auto-20240101T060000Z");
+        assert!(validate_snapshot_name("../data").is_err() && validate_snapshot_name("").is_err());
+    }
+}
@@ -0,0 +1,271 @@
+use std::sync::{Arc, RwLock};
+use crate::schema::{CassandraValue, Cell, Cells, ClusteringKey, ColumnLayout, PartitionKey, Row};
+use crate::storage::ReadOptions;
+use crate::error::*;
+
+/// 아레나 청크 크기
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// 아레나에 저장된 행 바이트 위치
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArenaRef {
+    chunk: u32,
+    offset: u32,
+    len: u32,
+}
+
+impl ArenaRef {
+    /// 저장된 행 바이트 수
+    pub fn byte_len(&self) -> u64 {
+        self.len as u64
+    }
+}
+
+/// 메모리 테이블 행을 담는 바이트 아레나
+///
+/// 행마다 셀 목록과 값을 따로 할당하는 대신 인코딩된 행을
+/// 큰 청크에 이어 붙이고, 메모리 테이블이 해제될 때 청크 단위로 한 번에 해제함.
+/// 덮어쓴 행의 이전 바이트는 회수하지 않음 (플러시로 정리)
+#[derive(Debug, Default)]
+pub struct RowArena {
+    chunks: RwLock<Vec<Vec<u8>>>,
+}
+
+impl RowArena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 바이트를 아레나에 복사하고 위치 반환
+    pub fn alloc(&self, bytes: &[u8]) -> ArenaRef {
+        let mut chunks = self.chunks.write().unwrap();
+        let needs_chunk = chunks.last()
+            .is_none_or(|chunk| chunk.capacity() - chunk.len() < bytes.len());
+        if needs_chunk {
+            // 청크보다 큰 행은 전용 청크에 저장
+            chunks.push(Vec::with_capacity(CHUNK_SIZE.max(bytes.len())));
+        }
+        let chunk_index = chunks.len() - 1;
+        let chunk = &mut chunks[chunk_index];
+        let offset = chunk.len();
+        chunk.extend_from_slice(bytes);
+        ArenaRef { chunk: chunk_index as u32, offset: offset as u32, len: bytes.len() as u32 }
+    }
+
+    /// 저장된 바이트를 빌려 처리
+    pub fn with_bytes<R>(&self, location: ArenaRef, f: impl FnOnce(&[u8]) -> R) -> R {
+        let chunks = self.chunks.read().unwrap();
+        let start = location.offset as usize;
+        f(&chunks[location.chunk as usize][start..start + location.len as usize])
+    }
+
+    /// 청크로 확보한 전체 바이트
+    pub fn allocated_bytes(&self) -> u64 {
+        self.chunks.read().unwrap().iter().map(|chunk| chunk.capacity() as u64).sum()
+    }
+
+    /// 실제로 사용한 바이트
+    pub fn used_bytes(&self) -> u64 {
+        self.chunks.read().unwrap().iter().map(|chunk| chunk.len() as u64).sum()
+    }
+}
+
+impl Clone for RowArena {
+    fn clone(&self) -> Self {
+        // 청크 용량을 유지해야 이후 할당 위치와 사용량 계산이 원본과 같음
+        let chunks = self.chunks.read().unwrap().iter()
+            .map(|chunk| {
+                let mut copy = Vec::with_capacity(chunk.capacity());
+                copy.extend_from_slice(chunk);
+                copy
+            })
+            .collect();
+        Self { chunks: RwLock::new(chunks) }
+    }
+}
+
+/// 컬럼 이름 ↔ 번호 매핑 (메모리 테이블마다 하나, 이름은 한 번만 저장)
+///
+/// 읽을 때 복원한 행들은 같은 컬럼 배치를 공유하고, 새 컬럼이 추가되면
+/// 배치를 복사해 교체하므로 이미 복원된 행에는 영향이 없음
+#[derive(Debug, Default)]
+pub struct ColumnInterner {
+    layout: RwLock<Arc<ColumnLayout>>,
+}
+
+impl ColumnInterner {
+    pub fn new(layout: ColumnLayout) -> Self {
+        Self { layout: RwLock::new(Arc::new(layout)) }
+    }
+
+    pub fn intern(&self, name: &str) -> Result<u16> {
+        if let Some(id) = self.layout.read().unwrap().id(name) {
+            return Ok(id);
+        }
+        let mut layout = self.layout.write().unwrap();
+        if let Some(id) = layout.id(name) {
+            return Ok(id);
+        }
+        if layout.len() > u16::MAX as usize {
+            return Err(CoreDBError::InvalidSchema {
+                message: format!("Too many columns in memtable (column {})", name),
+            });
+        }
+        Ok(Arc::make_mut(&mut layout).push(name))
+    }
+
+    /// 현재 컬럼 배치
+    pub fn layout(&self) -> Arc<ColumnLayout> {
+        self.layout.read().unwrap().clone()
+    }
+}
+
+impl Clone for ColumnInterner {
+    fn clone(&self) -> Self {
+        Self { layout: RwLock::new(self.layout()) }
+    }
+}
+
+/// 행을 아레나 형식으로 인코딩 (키는 SkipMap 키에 있으므로 제외)
+///
+/// 형식: 행 타임스탬프(i64) | 셀 수(u16) | 셀마다 [컬럼 번호(u16) | 타임스탬프(i64) |
+/// TTL 유무(u8) + TTL(u32) | 삭제 여부(u8) | 값 길이(u32) | bincode 값]
+pub fn encode_row(row: &Row, columns: &ColumnInterner, buf: &mut Vec<u8>) -> Result<()> {
+    buf.clear();
+    buf.extend_from_slice(&row.timestamp.to_le_bytes());
+    let cell_count = u16::try_from(row.cells.len()).map_err(|_| CoreDBError::InvalidDataType {
+        message: format!("Row has too many cells: {}", row.cells.len()),
+    })?;
+    buf.extend_from_slice(&cell_count.to_le_bytes());
+    for (name, cell) in &row.cells {
+        buf.extend_from_slice(&columns.intern(name)?.to_le_bytes());
+        buf.extend_from_slice(&cell.timestamp.to_le_bytes());
+        match cell.ttl {
+            Some(ttl) => {
+                buf.push(1);
+                buf.extend_from_slice(&ttl.to_le_bytes());
+            },
+            None => buf.push(0),
+        }
+        buf.push(cell.is_deleted as u8);
+        let value = bincode::serialize(&cell.value)?;
+        buf.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&value);
+    }
+    Ok(())
+}
+
+/// 아레나 형식 행을 디코딩 (선택되지 않은 컬럼은 값 역직렬화를 건너뜀)
+pub fn decode_row(
+    bytes: &[u8],
+    partition_key: &PartitionKey,
+    clustering_key: &Option<ClusteringKey>,
+    columns: &ColumnInterner,
+    options: &ReadOptions,
+) -> Row {
+    let mut reader = ByteReader { bytes, position: 0 };
+    let timestamp = i64::from_le_bytes(reader.take());
+    let cell_count = u16::from_le_bytes(reader.take());
+    let layout = columns.layout();
+
+    let mut cells = Cells::with_layout(layout.clone());
+    for _ in 0..cell_count {
+        let id = u16::from_le_bytes(reader.take());
+        let cell_timestamp = i64::from_le_bytes(reader.take());
+        let ttl = match reader.take::<1>()[0] {
+            1 => Some(u32::from_le_bytes(reader.take())),
+            _ => None,
+        };
+        let is_deleted = reader.take::<1>()[0] == 1;
+        let value_len = u32::from_le_bytes(reader.take()) as usize;
+        let value_bytes = reader.slice(value_len);
+        if !options.includes(layout.name(id)) {
+            continue;
+        }
+        // 직접 인코딩한 바이트이므로 역직렬화는 실패하지 않음
+        let value: CassandraValue = bincode::deserialize(value_bytes)
+            .expect("memtable arena holds a valid encoded value");
+        cells.insert_by_id(id, Cell { value, timestamp: cell_timestamp, ttl, is_deleted });
+    }
+
+    Row {
+        partition_key: partition_key.clone(),
+        clustering_key: clustering_key.clone(),
+        cells,
+        timestamp,
+    }
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take<const N: usize>(&mut self) -> [u8; N] {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.slice(N));
+        out
+    }
+
+    fn slice(&mut self, len: usize) -> &'a [u8] {
+        let slice = &self.bytes[self.position..self.position + len];
+        self.position += len;
+        slice
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arena_row_roundtrip() {
+        let columns = ColumnInterner::new(ColumnLayout::new(["id", "name"]));
+        let arena = RowArena::new();
+        let mut cells = Cells::new();
+        cells.insert("name", Cell {
+            value: CassandraValue::Text("alice".to_string()),
+            timestamp: 7,
+            ttl: Some(60),
+            is_deleted: false,
+        });
+        cells.insert("score", Cell {
+            value: CassandraValue::Double(1.5),
+            timestamp: 8,
+            ttl: None,
+            is_deleted: true,
+        });
+        let row = Row {
+            partition_key: PartitionKey { components: vec![CassandraValue::Int(1)] },
+            clustering_key: None,
+            cells,
+            timestamp: 9,
+        };
+
+        let mut buf = Vec::new();
+        encode_row(&row, &columns, &mut buf).unwrap();
+        let location = arena.alloc(&buf);
+        // 다른 행이 뒤에 붙어도 위치는 그대로
+        arena.alloc(&buf);
+        assert_eq!(arena.used_bytes(), 2 * buf.len() as u64);
+        assert_eq!(arena.allocated_bytes(), CHUNK_SIZE as u64);
+
+        let decoded = arena.with_bytes(location, |bytes| {
+            decode_row(bytes, &row.partition_key, &None, &columns, &ReadOptions::all())
+        });
+        assert_eq!(decoded.timestamp, 9);
+        assert_eq!(decoded.cells.len(), 2);
+        assert_eq!(decoded.cells["name"].ttl, Some(60));
+        assert!(decoded.cells["score"].is_deleted);
+        // 스키마에 없던 컬럼은 새 번호로 추가
+        assert_eq!(columns.intern("score").unwrap(), 2);
+
+        let projected = arena.with_bytes(location, |bytes| {
+            decode_row(bytes, &row.partition_key, &None, &columns, &ReadOptions::all().with_columns(["name"]))
+        });
+        assert_eq!(projected.cells.keys().collect::<Vec<_>>(), vec!["name"]);
+        // 복원한 행은 메모리 테이블의 컬럼 배치를 공유
+        assert!(Arc::ptr_eq(projected.cells.layout(), &columns.layout()));
+    }
+}
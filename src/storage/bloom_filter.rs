@@ -1,81 +1,130 @@
-use bloomfilter::Bloom;
 use crate::schema::{PartitionKey, CassandraValue};
 use std::hash::{Hash, Hasher};
-use std::collections::hash_map::DefaultHasher;
 use serde::{Serialize, Deserialize, Serializer, Deserializer};
 
-/// 블룸 필터 래퍼
-#[derive(Debug, Clone)]
-pub struct BloomFilter {
-    bloom: Bloom<Vec<u8>>,
-    // 직렬화를 위한 설정 저장
-    expected_items: usize,
-    false_positive_rate: f64,
-}
+/// 디스크 형식 버전 (-Filter.db)
+pub const BLOOM_FILTER_FORMAT_VERSION: u8 = 1;
 
-// PartialEq implementation for SSTable compatibility
-impl PartialEq for BloomFilter {
-    fn eq(&self, other: &Self) -> bool {
-        self.expected_items == other.expected_items &&
-        self.false_positive_rate == other.false_positive_rate
-    }
+/// 블룸 필터 (비트 배열을 그대로 저장하므로 다시 열어도 같은 결과)
+///
+/// 키는 bincode 인코딩에 고정된 해시(FNV-1a + splitmix64)를 적용하고 이중 해싱으로
+/// num_hashes개의 비트 위치를 정함. 해시가 실행 환경에 따라 바뀌지 않아야 저장된 비트와 맞음
+#[derive(Debug, Clone, PartialEq)]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    num_bits: u64,
+    num_hashes: u32,
 }
 
 impl BloomFilter {
     pub fn new(expected_items: u64, false_positive_rate: f64) -> Self {
+        let items = expected_items.max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let num_bits = ((-items * false_positive_rate.ln() / (ln2 * ln2)).ceil() as u64).max(64);
+        let num_hashes = ((num_bits as f64 / items * ln2).round() as u32).clamp(1, 30);
         Self {
-            bloom: Bloom::new_for_fp_rate(expected_items as usize, false_positive_rate).expect("Failed to create bloom filter"),
-            expected_items: expected_items as usize,
-            false_positive_rate,
+            bits: vec![0; num_bits.div_ceil(64) as usize],
+            num_bits,
+            num_hashes,
         }
     }
     
     pub fn add(&mut self, key: &PartitionKey) {
-        let key_bytes = self.serialize_key(key);
-        self.bloom.set(&key_bytes);
+        for bit in self.bit_positions(key) {
+            self.bits[(bit / 64) as usize] |= 1 << (bit % 64);
+        }
     }
     
     pub fn might_contain(&self, key: &PartitionKey) -> bool {
-        let key_bytes = self.serialize_key(key);
-        self.bloom.check(&key_bytes)
+        self.bit_positions(key).all(|bit| self.bits[(bit / 64) as usize] & (1 << (bit % 64)) != 0)
+    }
+    
+    /// 비트 배열 크기 (바이트)
+    pub fn size_bytes(&self) -> usize {
+        self.bits.len() * 8
+    }
+    
+    pub fn num_bits(&self) -> u64 {
+        self.num_bits
     }
     
-    fn serialize_key(&self, key: &PartitionKey) -> Vec<u8> {
-        // 간단한 직렬화 (실제로는 더 효율적인 방법 사용 가능)
-        let mut hasher = DefaultHasher::new();
-        key.hash(&mut hasher);
-        hasher.finish().to_le_bytes().to_vec()
+    pub fn num_hashes(&self) -> u32 {
+        self.num_hashes
     }
+    
+    /// 키 items개를 넣었을 때 예상 거짓 양성 비율
+    pub fn expected_false_positive_rate(&self, items: u64) -> f64 {
+        let k = self.num_hashes as f64;
+        (1.0 - (-k * items as f64 / self.num_bits as f64).exp()).powf(k)
+    }
+    
+    fn bit_positions(&self, key: &PartitionKey) -> impl Iterator<Item = u64> {
+        let (h1, h2) = key_hashes(key);
+        let num_bits = self.num_bits;
+        (0..self.num_hashes as u64).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % num_bits)
+    }
+}
+
+/// 키의 두 해시 (이중 해싱용, h2는 홀수)
+fn key_hashes(key: &PartitionKey) -> (u64, u64) {
+    let bytes = bincode::serialize(key).expect("partition key serializes");
+    let mut hash = 0xcbf29ce484222325u64;
+    for byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    let h1 = splitmix64(hash);
+    (h1, splitmix64(h1) | 1)
+}
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e3779b97f4a7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+/// 디스크 표현 (버전 + 비트 배열)
+#[derive(Serialize, Deserialize)]
+struct BloomFilterData {
+    version: u8,
+    num_bits: u64,
+    num_hashes: u32,
+    bits: Vec<u64>,
 }
 
-// Custom Serialize implementation
 impl Serialize for BloomFilter {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        use serde::ser::SerializeStruct;
-        let mut state = serializer.serialize_struct("BloomFilter", 2)?;
-        state.serialize_field("expected_items", &self.expected_items)?;
-        state.serialize_field("false_positive_rate", &self.false_positive_rate)?;
-        state.end()
+        BloomFilterData {
+            version: BLOOM_FILTER_FORMAT_VERSION,
+            num_bits: self.num_bits,
+            num_hashes: self.num_hashes,
+            bits: self.bits.clone(),
+        }.serialize(serializer)
     }
 }
 
-// Custom Deserialize implementation
 impl<'de> Deserialize<'de> for BloomFilter {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        #[derive(Deserialize)]
-        struct BloomFilterData {
-            expected_items: usize,
-            false_positive_rate: f64,
-        }
+        use serde::de::Error;
         
         let data = BloomFilterData::deserialize(deserializer)?;
-        Ok(BloomFilter::new(data.expected_items as u64, data.false_positive_rate))
+        if data.version != BLOOM_FILTER_FORMAT_VERSION {
+            return Err(D::Error::custom(format!("unsupported bloom filter version {}", data.version)));
+        }
+        if data.num_bits == 0 || data.num_hashes == 0 || data.bits.len() as u64 != data.num_bits.div_ceil(64) {
+            return Err(D::Error::custom(format!(
+                "invalid bloom filter: {} bits in {} words with {} hashes",
+                data.num_bits, data.bits.len(), data.num_hashes,
+            )));
+        }
+        Ok(Self { bits: data.bits, num_bits: data.num_bits, num_hashes: data.num_hashes })
     }
 }
 
@@ -146,6 +195,17 @@ fn hash_cassandra_value<H: Hasher>(value: &CassandraValue, state: &mut H) {
                 hash_cassandra_value(item, state);
             }
         },
+        CassandraValue::Vector(v) => {
+            state.write_u8(12);
+            for x in v {
+                x.to_bits().hash(state);
+            }
+        },
+        CassandraValue::Point(lat, lon) => {
+            state.write_u8(13);
+            lat.to_bits().hash(state);
+            lon.to_bits().hash(state);
+        },
     }
 }
 
@@ -172,4 +232,39 @@ mod tests {
         // 다른 키는 거짓 양성이 발생할 수 있지만, 거짓 음성은 발생하지 않아야 함
         assert!(!bloom.might_contain(&other_key));
     }
+
+    #[test]
+    fn test_bloom_filter_bits_survive_serialization() {
+        let keys: Vec<PartitionKey> = (0..1000)
+            .map(|i| PartitionKey { components: vec![CassandraValue::Text(format!("user_{}", i))] })
+            .collect();
+        let mut bloom = BloomFilter::new(keys.len() as u64, 0.01);
+        for key in &keys {
+            bloom.add(key);
+        }
+        
+        let restored: BloomFilter = bincode::deserialize(&bincode::serialize(&bloom).unwrap()).unwrap();
+        assert_eq!(restored, bloom);
+        assert!(keys.iter().all(|key| restored.might_contain(key)));
+        
+        // 거짓 양성 비율은 설정값 근처
+        let false_positives = (1000..11000)
+            .map(|i| PartitionKey { components: vec![CassandraValue::Text(format!("user_{}", i))] })
+            .filter(|key| restored.might_contain(key))
+            .count();
+        assert!(false_positives < 200, "{} false positives", false_positives);
+        let expected = restored.expected_false_positive_rate(keys.len() as u64);
+        assert!((0.005..0.015).contains(&expected), "{}", expected);
+    }
+    
+    #[test]
+    fn test_bloom_filter_rejects_unknown_version() {
+        let data = BloomFilterData { version: 99, num_bits: 64, num_hashes: 3, bits: vec![0] };
+        let encoded = bincode::serialize(&data).unwrap();
+        assert!(bincode::deserialize::<BloomFilter>(&encoded).is_err());
+        
+        let data = BloomFilterData { version: BLOOM_FILTER_FORMAT_VERSION, num_bits: 640, num_hashes: 3, bits: vec![0] };
+        let encoded = bincode::serialize(&data).unwrap();
+        assert!(bincode::deserialize::<BloomFilter>(&encoded).is_err());
+    }
 }
@@ -0,0 +1,1210 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+use crate::schema::{CassandraDataType, CassandraValue, Cell, Cells, ClusteringKey, ColumnDefinition, PartitionKey, Row, TableSchema};
+use crate::error::*;
+
+/// Cassandra big 형식 데이터 파일 이름 접미사 (<version>-<generation>-big-Data.db)
+pub const CASSANDRA_DATA_FILE_SUFFIX: &str = "-big-Data.db";
+
+/// 통계 파일의 직렬화 헤더 항목 번호 (MetadataType.HEADER)
+const METADATA_TYPE_HEADER: u32 = 3;
+/// EncodingStats 기준 시각 (2015-09-22T00:00:00Z, 초)
+const ENCODING_EPOCH_SECONDS: i64 = 1_442_880_000;
+/// 읽는 단위 (압축하지 않은 파일)
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+/// 행 플래그
+const END_OF_PARTITION: u8 = 0x01;
+const IS_MARKER: u8 = 0x02;
+const HAS_TIMESTAMP: u8 = 0x04;
+const HAS_TTL: u8 = 0x08;
+const HAS_DELETION: u8 = 0x10;
+const HAS_ALL_COLUMNS: u8 = 0x20;
+const HAS_COMPLEX_DELETION: u8 = 0x40;
+const EXTENSION_FLAG: u8 = 0x80;
+/// 확장 플래그
+const IS_STATIC: u8 = 0x01;
+
+/// 셀 플래그
+const CELL_IS_DELETED: u8 = 0x01;
+const CELL_IS_EXPIRING: u8 = 0x02;
+const CELL_HAS_EMPTY_VALUE: u8 = 0x04;
+const CELL_USE_ROW_TIMESTAMP: u8 = 0x08;
+const CELL_USE_ROW_TTL: u8 = 0x10;
+
+/// Cassandra 컬럼 타입 (org.apache.cassandra.db.marshal)
+#[derive(Debug, Clone, PartialEq)]
+pub enum CassandraType {
+    /// Int32Type
+    Int,
+    /// ShortType
+    SmallInt,
+    /// ByteType
+    TinyInt,
+    /// LongType
+    BigInt,
+    /// UTF8Type, AsciiType
+    Text,
+    /// UUIDType, TimeUUIDType
+    Uuid,
+    /// TimestampType, DateType (밀리초)
+    Timestamp,
+    Boolean,
+    Double,
+    Float,
+    /// BytesType
+    Blob,
+    List(Box<CassandraType>),
+    Set(Box<CassandraType>),
+    Map(Box<CassandraType>, Box<CassandraType>),
+    /// 고정된 컬렉션 (셀 하나에 통째로 저장)
+    Frozen(Box<CassandraType>),
+}
+
+impl CassandraType {
+    /// 타입 이름 해석 (예: org.apache.cassandra.db.marshal.ListType(org.apache.cassandra.db.marshal.Int32Type))
+    ///
+    /// 클러스터링 정렬 방향(ReversedType)은 값 형식에 영향이 없으므로 벗겨냄
+    pub fn parse(name: &str) -> Result<Self> {
+        let (class, params) = split_type_name(name)?;
+        let param = |index: usize| -> Result<Box<CassandraType>> {
+            params.get(index).map(|param| CassandraType::parse(param).map(Box::new))
+                .unwrap_or_else(|| Err(unsupported_type(name)))
+        };
+        let parsed = match (class, params.len()) {
+            ("Int32Type", 0) => CassandraType::Int,
+            ("ShortType", 0) => CassandraType::SmallInt,
+            ("ByteType", 0) => CassandraType::TinyInt,
+            ("LongType", 0) => CassandraType::BigInt,
+            ("UTF8Type" | "AsciiType", 0) => CassandraType::Text,
+            ("UUIDType" | "TimeUUIDType", 0) => CassandraType::Uuid,
+            ("TimestampType" | "DateType", 0) => CassandraType::Timestamp,
+            ("BooleanType", 0) => CassandraType::Boolean,
+            ("DoubleType", 0) => CassandraType::Double,
+            ("FloatType", 0) => CassandraType::Float,
+            ("BytesType", 0) => CassandraType::Blob,
+            ("ListType", 1) => CassandraType::List(param(0)?),
+            ("SetType", 1) => CassandraType::Set(param(0)?),
+            ("MapType", 2) => CassandraType::Map(param(0)?, param(1)?),
+            ("FrozenType", 1) => CassandraType::Frozen(param(0)?),
+            ("ReversedType", 1) => *param(0)?,
+            _ => return Err(unsupported_type(name)),
+        };
+        Ok(parsed)
+    }
+
+    /// 파티션 키 타입 (복합 키는 CompositeType의 구성 타입들)
+    pub fn parse_key(name: &str) -> Result<Vec<Self>> {
+        match split_type_name(name)? {
+            ("CompositeType", params) => params.into_iter().map(CassandraType::parse).collect(),
+            _ => Ok(vec![CassandraType::parse(name)?]),
+        }
+    }
+
+    /// 셀 경로가 있는 (고정되지 않은) 컬렉션인지
+    pub fn is_complex(&self) -> bool {
+        matches!(self, CassandraType::List(_) | CassandraType::Set(_) | CassandraType::Map(_, _))
+    }
+
+    /// 대응하는 CoreDB 타입
+    pub fn data_type(&self) -> CassandraDataType {
+        match self {
+            CassandraType::Int | CassandraType::SmallInt | CassandraType::TinyInt => CassandraDataType::Int,
+            CassandraType::BigInt => CassandraDataType::BigInt,
+            CassandraType::Text => CassandraDataType::Text,
+            CassandraType::Uuid => CassandraDataType::UUID,
+            CassandraType::Timestamp => CassandraDataType::Timestamp,
+            CassandraType::Boolean => CassandraDataType::Boolean,
+            CassandraType::Double | CassandraType::Float => CassandraDataType::Double,
+            CassandraType::Blob => CassandraDataType::Blob,
+            CassandraType::List(element) => CassandraDataType::List(Box::new(element.data_type())),
+            CassandraType::Set(element) => CassandraDataType::Set(Box::new(element.data_type())),
+            CassandraType::Map(key, value) => CassandraDataType::Map(Box::new(key.data_type()), Box::new(value.data_type())),
+            CassandraType::Frozen(inner) => inner.data_type(),
+        }
+    }
+
+    /// 고정 길이 값의 바이트 수 (가변 길이면 None)
+    fn fixed_length(&self) -> Option<usize> {
+        match self {
+            CassandraType::Int | CassandraType::Float => Some(4),
+            CassandraType::BigInt | CassandraType::Timestamp | CassandraType::Double => Some(8),
+            CassandraType::Uuid => Some(16),
+            CassandraType::Boolean => Some(1),
+            _ => None,
+        }
+    }
+
+    /// 직렬화된 값 해석 (빈 값은 텍스트/blob이 아니면 Null)
+    pub fn decode(&self, bytes: &[u8]) -> Result<CassandraValue> {
+        if bytes.is_empty() && !matches!(self, CassandraType::Text | CassandraType::Blob) {
+            return Ok(CassandraValue::Null);
+        }
+        let fixed = |length: usize| -> Result<&[u8]> {
+            if bytes.len() != length {
+                return Err(invalid(format!("{:?} value has {} bytes, expected {}", self, bytes.len(), length)));
+            }
+            Ok(bytes)
+        };
+        let value = match self {
+            CassandraType::Int => CassandraValue::Int(i32::from_be_bytes(fixed(4)?.try_into().expect("4 bytes"))),
+            CassandraType::SmallInt => CassandraValue::Int(i16::from_be_bytes(fixed(2)?.try_into().expect("2 bytes")) as i32),
+            CassandraType::TinyInt => CassandraValue::Int(fixed(1)?[0] as i8 as i32),
+            CassandraType::BigInt => CassandraValue::BigInt(i64::from_be_bytes(fixed(8)?.try_into().expect("8 bytes"))),
+            CassandraType::Text => CassandraValue::Text(String::from_utf8(bytes.to_vec()).map_err(|e| invalid(e.to_string()))?),
+            CassandraType::Uuid => CassandraValue::UUID(Uuid::from_slice(fixed(16)?).expect("16 bytes")),
+            CassandraType::Timestamp => {
+                let millis = i64::from_be_bytes(fixed(8)?.try_into().expect("8 bytes"));
+                CassandraValue::Timestamp(millis.saturating_mul(1000))
+            },
+            CassandraType::Boolean => CassandraValue::Boolean(fixed(1)?[0] != 0),
+            CassandraType::Double => CassandraValue::Double(f64::from_be_bytes(fixed(8)?.try_into().expect("8 bytes"))),
+            CassandraType::Float => CassandraValue::Double(f32::from_be_bytes(fixed(4)?.try_into().expect("4 bytes")) as f64),
+            CassandraType::Blob => CassandraValue::Blob(bytes.to_vec()),
+            CassandraType::Frozen(inner) => inner.decode_frozen(bytes)?,
+            CassandraType::List(_) | CassandraType::Set(_) | CassandraType::Map(_, _) => self.decode_frozen(bytes)?,
+        };
+        Ok(value)
+    }
+
+    /// 통째로 직렬화된 컬렉션 해석 ([원소 수 i32][길이 i32, 값]...)
+    fn decode_frozen(&self, bytes: &[u8]) -> Result<CassandraValue> {
+        let mut input = ByteReader::new(bytes);
+        let count = input.read_i32()?.max(0) as usize;
+        let element = |input: &mut ByteReader, element_type: &CassandraType| -> Result<CassandraValue> {
+            match input.read_i32()? {
+                length if length < 0 => Ok(CassandraValue::Null),
+                length => element_type.decode(input.read_bytes(length as usize)?),
+            }
+        };
+        let value = match self {
+            CassandraType::List(element_type) => {
+                CassandraValue::List((0..count).map(|_| element(&mut input, element_type)).collect::<Result<_>>()?)
+            },
+            CassandraType::Set(element_type) => {
+                CassandraValue::Set((0..count).map(|_| element(&mut input, element_type)).collect::<Result<_>>()?)
+            },
+            CassandraType::Map(key_type, value_type) => {
+                let mut map = HashMap::with_capacity(count);
+                for _ in 0..count {
+                    let key = element(&mut input, key_type)?;
+                    map.insert(map_key(&key), element(&mut input, value_type)?);
+                }
+                CassandraValue::Map(map)
+            },
+            other => other.decode(bytes)?,
+        };
+        Ok(value)
+    }
+}
+
+/// 타입 이름을 클래스 이름(패키지 제외)과 인자들로 나눔
+fn split_type_name(name: &str) -> Result<(&str, Vec<&str>)> {
+    let name = name.trim();
+    let (class, params) = match name.find('(') {
+        Some(open) if name.ends_with(')') => (&name[..open], &name[open + 1..name.len() - 1]),
+        Some(_) => return Err(unsupported_type(name)),
+        None => (name, ""),
+    };
+    let class = class.rsplit('.').next().unwrap_or(class);
+    let mut split = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in params.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                split.push(&params[start..i]);
+                start = i + 1;
+            },
+            _ => {},
+        }
+    }
+    if !params.trim().is_empty() {
+        split.push(&params[start..]);
+    }
+    Ok((class, split))
+}
+
+/// CoreDB 맵 키 (문자열 키)
+fn map_key(key: &CassandraValue) -> String {
+    match key {
+        CassandraValue::Text(text) => text.clone(),
+        CassandraValue::Int(value) => value.to_string(),
+        CassandraValue::BigInt(value) | CassandraValue::Timestamp(value) => value.to_string(),
+        CassandraValue::UUID(uuid) => uuid.to_string(),
+        CassandraValue::Boolean(value) => value.to_string(),
+        CassandraValue::Double(value) => value.to_string(),
+        CassandraValue::Blob(bytes) => bytes.iter().map(|byte| format!("{:02x}", byte)).collect(),
+        other => format!("{:?}", other),
+    }
+}
+
+/// 직렬화 헤더의 컬럼
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeaderColumn {
+    pub name: String,
+    pub data_type: CassandraType,
+}
+
+/// Cassandra SSTable 직렬화 헤더 (-Statistics.db의 HEADER 항목)
+///
+/// 셀 타임스탬프/TTL/삭제 시각은 헤더의 최소값과의 차이로 저장되고, 행의 컬럼은
+/// 헤더 컬럼 목록을 기준으로 인코딩됨
+#[derive(Debug, Clone, PartialEq)]
+pub struct SerializationHeader {
+    pub min_timestamp: i64,
+    pub min_local_deletion_time: i64,
+    pub min_ttl: i64,
+    pub partition_key_types: Vec<CassandraType>,
+    pub clustering_types: Vec<CassandraType>,
+    /// Cassandra 컬럼 순서 (단순 컬럼 다음 컬렉션, 각각 이름의 바이트 순)
+    pub static_columns: Vec<HeaderColumn>,
+    pub regular_columns: Vec<HeaderColumn>,
+}
+
+impl SerializationHeader {
+    /// 통계 파일에서 헤더 항목 읽기
+    fn read(statistics: &[u8]) -> Result<Self> {
+        let mut toc = ByteReader::new(statistics);
+        let count = toc.read_i32()?;
+        let mut header_offset = None;
+        for _ in 0..count {
+            let (kind, offset) = (toc.read_i32()? as u32, toc.read_i32()? as usize);
+            if kind == METADATA_TYPE_HEADER {
+                header_offset = Some(offset);
+            }
+        }
+        let offset = header_offset.ok_or_else(|| invalid("statistics have no serialization header".to_string()))?;
+        let mut input = ByteReader::new(statistics.get(offset..).ok_or_else(|| invalid("header offset out of range".to_string()))?);
+
+        let min_timestamp = (input.read_unsigned_vint()? as i64).wrapping_add(ENCODING_EPOCH_SECONDS * 1_000_000);
+        let min_local_deletion_time = (input.read_unsigned_vint()? as i32).wrapping_add(ENCODING_EPOCH_SECONDS as i32) as i64;
+        let min_ttl = input.read_unsigned_vint()? as i32 as i64;
+        let partition_key_types = CassandraType::parse_key(&input.read_vint_string()?)?;
+        let clustering_types = (0..input.read_unsigned_vint()?)
+            .map(|_| CassandraType::parse(&input.read_vint_string()?))
+            .collect::<Result<_>>()?;
+        let mut columns = || -> Result<Vec<HeaderColumn>> {
+            let mut columns = (0..input.read_unsigned_vint()?)
+                .map(|_| Ok(HeaderColumn { name: input.read_vint_string()?, data_type: CassandraType::parse(&input.read_vint_string()?)? }))
+                .collect::<Result<Vec<_>>>()?;
+            columns.sort_by(|a, b| (a.data_type.is_complex(), a.name.as_bytes()).cmp(&(b.data_type.is_complex(), b.name.as_bytes())));
+            Ok(columns)
+        };
+        let static_columns = columns()?;
+        let regular_columns = columns()?;
+        Ok(Self { min_timestamp, min_local_deletion_time, min_ttl, partition_key_types, clustering_types, static_columns, regular_columns })
+    }
+
+    /// CoreDB 테이블 스키마와 맞는지 확인
+    ///
+    /// 키 컬럼은 이름이 헤더에 없으므로 순서와 타입으로, 나머지 컬럼은 이름과 타입으로 대조.
+    /// 스키마에만 있는 컬럼은 비어 있는 것으로 보고 허용
+    pub fn validate_schema(&self, schema: &TableSchema) -> Result<()> {
+        let mismatch = |message: String| CoreDBError::InvalidSchema {
+            message: format!("Cassandra SSTable does not match {}.{}: {}", schema.keyspace, schema.name, message),
+        };
+        let check_keys = |kind: &str, types: &[CassandraType], columns: &[ColumnDefinition]| -> Result<()> {
+            if types.len() != columns.len() {
+                return Err(mismatch(format!("{} {} key columns, table has {}", types.len(), kind, columns.len())));
+            }
+            for (data_type, column) in types.iter().zip(columns) {
+                if data_type.data_type() != column.data_type {
+                    return Err(mismatch(format!("{} key column {} is {:?}, table has {:?}", kind, column.name, data_type.data_type(), column.data_type)));
+                }
+            }
+            Ok(())
+        };
+        check_keys("partition", &self.partition_key_types, &schema.partition_key)?;
+        check_keys("clustering", &self.clustering_types, &schema.clustering_key)?;
+
+        for (columns, table_columns) in [(&self.static_columns, &schema.static_columns), (&self.regular_columns, &schema.regular_columns)] {
+            for column in columns {
+                let Some(table_column) = table_columns.iter().find(|table_column| table_column.name == column.name) else {
+                    return Err(mismatch(format!("column {} is not in the table", column.name)));
+                };
+                if column.data_type.data_type() != table_column.data_type {
+                    return Err(mismatch(format!("column {} is {:?}, table has {:?}", column.name, column.data_type.data_type(), table_column.data_type)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn columns(&self, is_static: bool) -> &[HeaderColumn] {
+        if is_static { &self.static_columns } else { &self.regular_columns }
+    }
+}
+
+/// 압축 정보 (-CompressionInfo.db)
+#[derive(Debug, Clone)]
+struct CompressionMetadata {
+    /// 압축기 클래스 이름 (패키지 제외)
+    compressor: String,
+    chunk_length: usize,
+    /// 이보다 크거나 같은 청크는 압축하지 않고 저장됨 (4.0 이후)
+    max_compressed_length: usize,
+    data_length: u64,
+    chunk_offsets: Vec<u64>,
+}
+
+impl CompressionMetadata {
+    fn read(data: &[u8], version: &str) -> Result<Self> {
+        let mut input = ByteReader::new(data);
+        let compressor = input.read_utf()?;
+        let compressor = compressor.rsplit('.').next().unwrap_or(&compressor).to_string();
+        for _ in 0..input.read_i32()? {
+            input.read_utf()?;
+            input.read_utf()?;
+        }
+        let chunk_length = input.read_i32()? as usize;
+        let max_compressed_length = if version >= "na" { input.read_i32()? as usize } else { usize::MAX };
+        let data_length = input.read_i64()? as u64;
+        let chunk_count = input.read_i32()?.max(0) as usize;
+        let chunk_offsets = (0..chunk_count).map(|_| Ok(input.read_i64()? as u64)).collect::<Result<_>>()?;
+        Ok(Self { compressor, chunk_length, max_compressed_length, data_length, chunk_offsets })
+    }
+
+    fn decompress(&self, chunk: &[u8], uncompressed_length: usize) -> Result<Vec<u8>> {
+        if chunk.len() >= self.max_compressed_length {
+            return Ok(chunk.to_vec());
+        }
+        let data = match self.compressor.as_str() {
+            "LZ4Compressor" => {
+                // [압축 전 길이 u32 LE][LZ4 블록]
+                let (length, block) = chunk.split_first_chunk::<4>().ok_or_else(|| invalid("truncated LZ4 chunk".to_string()))?;
+                lz4_flex::block::decompress(block, u32::from_le_bytes(*length) as usize).map_err(|e| invalid(e.to_string()))?
+            },
+            "SnappyCompressor" => snap::raw::Decoder::new().decompress_vec(chunk).map_err(|e| invalid(e.to_string()))?,
+            "ZstdCompressor" => zstd::bulk::decompress(chunk, uncompressed_length).map_err(|e| invalid(e.to_string()))?,
+            "NoopCompressor" => chunk.to_vec(),
+            other => return Err(invalid(format!("unsupported compressor {}", other))),
+        };
+        if data.len() != uncompressed_length {
+            return Err(invalid(format!("chunk decompressed to {} bytes, expected {}", data.len(), uncompressed_length)));
+        }
+        Ok(data)
+    }
+}
+
+/// 바이트 읽기 (빅엔디언, Cassandra vint)
+trait Input {
+    fn read_bytes(&mut self, length: usize) -> Result<&[u8]>;
+
+    fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16> {
+        Ok(u16::from_be_bytes(self.read_bytes(2)?.try_into().expect("2 bytes")))
+    }
+
+    fn read_i32(&mut self) -> Result<i32> {
+        Ok(i32::from_be_bytes(self.read_bytes(4)?.try_into().expect("4 bytes")))
+    }
+
+    fn read_i64(&mut self) -> Result<i64> {
+        Ok(i64::from_be_bytes(self.read_bytes(8)?.try_into().expect("8 bytes")))
+    }
+
+    /// 첫 바이트의 앞쪽 1 비트 수가 뒤따르는 바이트 수
+    fn read_unsigned_vint(&mut self) -> Result<u64> {
+        let first = self.read_u8()?;
+        let extra = first.leading_ones() as usize;
+        let mut value = (first as u64) & (0xff >> extra);
+        for &byte in self.read_bytes(extra)? {
+            value = (value << 8) | byte as u64;
+        }
+        Ok(value)
+    }
+
+    /// vint 길이가 앞에 붙은 바이트
+    fn read_vint_bytes(&mut self) -> Result<Vec<u8>> {
+        let length = self.read_unsigned_vint()? as usize;
+        Ok(self.read_bytes(length)?.to_vec())
+    }
+
+    fn read_vint_string(&mut self) -> Result<String> {
+        String::from_utf8(self.read_vint_bytes()?).map_err(|e| invalid(e.to_string()))
+    }
+
+    /// Java DataOutput.writeUTF 문자열 ([길이 u16][바이트])
+    fn read_utf(&mut self) -> Result<String> {
+        let length = self.read_u16()? as usize;
+        String::from_utf8(self.read_bytes(length)?.to_vec()).map_err(|e| invalid(e.to_string()))
+    }
+}
+
+/// 메모리의 바이트 읽기
+struct ByteReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+}
+
+impl Input for ByteReader<'_> {
+    fn read_bytes(&mut self, length: usize) -> Result<&[u8]> {
+        if length > self.data.len() {
+            return Err(invalid(format!("need {} bytes, {} left", length, self.data.len())));
+        }
+        let (bytes, rest) = self.data.split_at(length);
+        self.data = rest;
+        Ok(bytes)
+    }
+}
+
+/// 데이터/인덱스 파일 순차 읽기 (압축된 데이터 파일은 청크 단위로 풀어 이어 붙임)
+struct FileInput {
+    path: PathBuf,
+    file: File,
+    file_length: u64,
+    compression: Option<CompressionMetadata>,
+    buffer: Vec<u8>,
+    /// buffer[0]의 압축 전 오프셋
+    buffer_start: u64,
+    position: usize,
+    /// 다음에 읽을 청크 번호 또는 파일 오프셋
+    next: u64,
+}
+
+impl FileInput {
+    fn open(path: &Path, compression: Option<CompressionMetadata>) -> Result<Self> {
+        let file = File::open(path)?;
+        let file_length = file.metadata()?.len();
+        Ok(Self { path: path.to_path_buf(), file, file_length, compression, buffer: Vec::new(), buffer_start: 0, position: 0, next: 0 })
+    }
+
+    /// 현재 위치의 압축 전 오프셋
+    fn offset(&self) -> u64 {
+        self.buffer_start + self.position as u64
+    }
+
+    fn is_at_end(&mut self) -> Result<bool> {
+        Ok(self.position == self.buffer.len() && !self.fill()?)
+    }
+
+    /// 다음 청크를 버퍼 뒤에 붙임 (읽은 부분은 버림, 더 없으면 false)
+    fn fill(&mut self) -> Result<bool> {
+        self.buffer.drain(..self.position);
+        self.buffer_start += self.position as u64;
+        self.position = 0;
+
+        let Some(compression) = &self.compression else {
+            let length = READ_BUFFER_SIZE.min((self.file_length - self.next) as usize);
+            if length == 0 {
+                return Ok(false);
+            }
+            let start = self.buffer.len();
+            self.buffer.resize(start + length, 0);
+            self.file.read_exact(&mut self.buffer[start..])?;
+            self.next += length as u64;
+            return Ok(true);
+        };
+
+        // 청크는 [압축된 바이트][CRC32 u32], 청크 길이는 다음 청크 시작까지
+        let index = self.next as usize;
+        let Some(&start) = compression.chunk_offsets.get(index) else { return Ok(false) };
+        let end = compression.chunk_offsets.get(index + 1).copied().unwrap_or(self.file_length);
+        if end < start + 4 || end > self.file_length {
+            return Err(invalid(format!("chunk {} spans {}..{} in a {} byte file", index, start, end, self.file_length)));
+        }
+        let mut chunk = vec![0; (end - start) as usize];
+        self.file.seek(SeekFrom::Start(start))?;
+        self.file.read_exact(&mut chunk)?;
+        let (compressed, checksum) = chunk.split_at(chunk.len() - 4);
+        if crc32(compressed) != u32::from_be_bytes(checksum.try_into().expect("4 bytes")) {
+            return Err(CoreDBError::Corruption {
+                file: self.path.display().to_string(),
+                offset: start,
+                message: format!("checksum mismatch in compressed chunk {}", index),
+            });
+        }
+        let chunk_start = index as u64 * compression.chunk_length as u64;
+        let uncompressed_length = (compression.data_length.saturating_sub(chunk_start) as usize).min(compression.chunk_length);
+        let data = compression.decompress(compressed, uncompressed_length)?;
+        self.buffer.extend_from_slice(&data);
+        self.next += 1;
+        Ok(true)
+    }
+}
+
+impl Input for FileInput {
+    fn read_bytes(&mut self, length: usize) -> Result<&[u8]> {
+        while self.buffer.len() - self.position < length {
+            if !self.fill()? {
+                return Err(invalid(format!("file ends inside a {} byte value at offset {}", length, self.offset())));
+            }
+        }
+        let start = self.position;
+        self.position += length;
+        Ok(&self.buffer[start..self.position])
+    }
+}
+
+/// CRC32 (IEEE, 압축 청크 체크섬)
+fn crc32(data: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0u32; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut crc = i as u32;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+                bit += 1;
+            }
+            table[i] = crc;
+            i += 1;
+        }
+        table
+    };
+    !data.iter().fold(!0u32, |crc, &byte| TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8))
+}
+
+/// Cassandra SSTable에서 읽은 파티션
+#[derive(Debug, Clone)]
+pub struct CassandraPartition {
+    pub partition_key: PartitionKey,
+    /// 클러스터링 순서의 행 (static 컬럼은 클러스터링 키가 None인 행으로)
+    pub rows: Vec<Row>,
+    /// 파티션 삭제 타임스탬프 (CoreDB에 대응하는 표현이 없어 가져오지 않음)
+    pub partition_deletion: Option<i64>,
+    /// 건너뛴 범위 툼스톤 표시 수
+    pub range_tombstone_markers: u64,
+}
+
+/// Cassandra SSTable 가져오기 결과
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CassandraImportStats {
+    pub sstables: usize,
+    pub partitions: u64,
+    pub rows: u64,
+    /// 대응하는 표현이 없어 건너뛴 파티션 삭제와 범위 툼스톤 표시
+    pub skipped_partition_deletions: u64,
+    pub skipped_range_tombstones: u64,
+}
+
+/// Apache Cassandra big 형식 SSTable 읽기 (3.0 ma ~ 4.x nb)
+///
+/// -Statistics.db의 직렬화 헤더로 컬럼과 타입을 알아내 -Data.db의 파티션을 순서대로 읽고,
+/// -Index.db가 있으면 파티션 키와 위치를 대조함. -Summary.db는 인덱스 탐색용이라 쓰지 않음
+#[derive(Debug)]
+pub struct CassandraSSTable {
+    pub data_path: PathBuf,
+    /// 형식 버전 (파일 이름 앞부분, 예: nb)
+    pub version: String,
+    pub header: SerializationHeader,
+    compression: Option<CompressionMetadata>,
+}
+
+impl CassandraSSTable {
+    pub fn open(data_path: &Path) -> Result<Self> {
+        let file_name = data_path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        let prefix = file_name.strip_suffix(CASSANDRA_DATA_FILE_SUFFIX)
+            .ok_or_else(|| invalid(format!("{} is not a big format data file", data_path.display())))?;
+        let version = prefix.split('-').next().unwrap_or_default().to_string();
+        if !(version.as_str() >= "ma" && version.as_str() < "oa") || version.len() != 2 {
+            return Err(CoreDBError::Generic {
+                message: format!("Unsupported Cassandra SSTable version '{}' in {} (supported: ma to nb)", version, data_path.display()),
+            });
+        }
+        let component = |name: &str| data_path.with_file_name(format!("{}-big-{}", prefix, name));
+
+        let header = SerializationHeader::read(&std::fs::read(component("Statistics.db"))?)?;
+        let compression_path = component("CompressionInfo.db");
+        let compression = if compression_path.exists() {
+            Some(CompressionMetadata::read(&std::fs::read(compression_path)?, &version)?)
+        } else {
+            None
+        };
+        Ok(Self { data_path: data_path.to_path_buf(), version, header, compression })
+    }
+
+    /// 디렉토리의 big 형식 데이터 파일 (이름 순)
+    pub fn find(directory: &Path) -> Result<Vec<PathBuf>> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(directory)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|path| path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.ends_with(CASSANDRA_DATA_FILE_SUFFIX)))
+            .collect();
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// 파티션을 순서대로 읽는 반복자
+    pub fn partitions(&self) -> Result<CassandraPartitions<'_>> {
+        let index_path = self.data_path.with_file_name(
+            self.data_path.file_name().and_then(|name| name.to_str()).unwrap_or_default().replace("-Data.db", "-Index.db")
+        );
+        let index = if index_path.exists() { Some(FileInput::open(&index_path, None)?) } else { None };
+        Ok(CassandraPartitions {
+            sstable: self,
+            data: FileInput::open(&self.data_path, self.compression.clone())?,
+            index,
+        })
+    }
+}
+
+/// CassandraSSTable::partitions
+pub struct CassandraPartitions<'a> {
+    sstable: &'a CassandraSSTable,
+    data: FileInput,
+    index: Option<FileInput>,
+}
+
+/// 행의 기본 liveness (셀이 USE_ROW_* 플래그로 참조)
+#[derive(Default)]
+struct RowLiveness {
+    timestamp: i64,
+    ttl: Option<u32>,
+}
+
+impl CassandraPartitions<'_> {
+    fn read_partition(&mut self) -> Result<CassandraPartition> {
+        let header = &self.sstable.header;
+        let start = self.data.offset();
+        let key_length = self.data.read_u16()? as usize;
+        let key_bytes = self.data.read_bytes(key_length)?.to_vec();
+        self.check_index(&key_bytes, start)?;
+
+        let partition_key = PartitionKey { components: decode_partition_key(&header.partition_key_types, &key_bytes)? };
+        let local_deletion_time = self.data.read_i32()?;
+        let marked_for_delete_at = self.data.read_i64()?;
+        let partition_deletion = (local_deletion_time != i32::MAX || marked_for_delete_at != i64::MIN).then_some(marked_for_delete_at);
+
+        let mut partition = CassandraPartition { partition_key, rows: Vec::new(), partition_deletion, range_tombstone_markers: 0 };
+        loop {
+            let flags = self.data.read_u8()?;
+            if flags & END_OF_PARTITION != 0 {
+                return Ok(partition);
+            }
+            if flags & IS_MARKER != 0 {
+                self.skip_marker()?;
+                partition.range_tombstone_markers += 1;
+                continue;
+            }
+            let extended_flags = if flags & EXTENSION_FLAG != 0 { self.data.read_u8()? } else { 0 };
+            let row = self.read_row(&partition.partition_key, flags, extended_flags & IS_STATIC != 0)?;
+            partition.rows.push(row);
+        }
+    }
+
+    /// 인덱스 항목과 파티션 키/위치 대조 ([키 u16 길이][위치 vint][승격 인덱스 길이 vint][승격 인덱스])
+    fn check_index(&mut self, key: &[u8], position: u64) -> Result<()> {
+        let Some(index) = self.index.as_mut() else { return Ok(()) };
+        let key_length = index.read_u16()? as usize;
+        let matches = index.read_bytes(key_length)? == key;
+        let indexed_position = index.read_unsigned_vint()?;
+        let promoted_length = index.read_unsigned_vint()? as usize;
+        index.read_bytes(promoted_length)?;
+        if !matches || indexed_position != position {
+            return Err(CoreDBError::Corruption {
+                file: self.sstable.data_path.display().to_string(),
+                offset: position,
+                message: format!("partition does not match the index entry at position {}", indexed_position),
+            });
+        }
+        Ok(())
+    }
+
+    fn skip_marker(&mut self) -> Result<()> {
+        let _kind = self.data.read_u8()?;
+        let size = self.data.read_u16()? as usize;
+        read_clustering_values(&mut self.data, &self.sstable.header.clustering_types[..size.min(self.sstable.header.clustering_types.len())])?;
+        let body_size = self.data.read_unsigned_vint()? as usize;
+        self.data.read_bytes(body_size)?;
+        Ok(())
+    }
+
+    fn read_row(&mut self, partition_key: &PartitionKey, flags: u8, is_static: bool) -> Result<Row> {
+        let header = &self.sstable.header;
+        let data = &mut self.data;
+        let clustering_key = if is_static || header.clustering_types.is_empty() {
+            None
+        } else {
+            let values = read_clustering_values(data, &header.clustering_types)?;
+            let components = header.clustering_types.iter().zip(values)
+                .map(|(data_type, value)| value.map_or(Ok(CassandraValue::Null), |bytes| data_type.decode(&bytes)))
+                .collect::<Result<_>>()?;
+            Some(ClusteringKey { components })
+        };
+
+        // 행 크기는 이전 행 크기 vint부터 행 끝까지
+        let row_size = data.read_unsigned_vint()?;
+        let body_start = data.offset();
+        let _previous_size = data.read_unsigned_vint()?;
+
+        let mut liveness = RowLiveness { timestamp: i64::MIN, ttl: None };
+        if flags & HAS_TIMESTAMP != 0 {
+            liveness.timestamp = read_timestamp(data, header)?;
+        }
+        if flags & HAS_TTL != 0 {
+            liveness.ttl = Some(read_ttl(data, header)?);
+            read_local_deletion_time(data, header)?;
+        }
+        let row_deletion = if flags & HAS_DELETION != 0 {
+            let deletion = read_timestamp(data, header)?;
+            read_local_deletion_time(data, header)?;
+            Some(deletion)
+        } else {
+            None
+        };
+
+        let columns = header.columns(is_static);
+        let present: Vec<&HeaderColumn> = if flags & HAS_ALL_COLUMNS != 0 {
+            columns.iter().collect()
+        } else {
+            read_column_subset(data, columns.len())?.into_iter().map(|index| &columns[index]).collect()
+        };
+
+        let mut cells = Cells::new();
+        let mut row_timestamp = liveness.timestamp;
+        for column in present {
+            let cell = if column.data_type.is_complex() {
+                read_complex_column(data, header, &column.data_type, flags & HAS_COMPLEX_DELETION != 0, &liveness)?
+            } else {
+                let cell = read_cell(data, header, Some(&column.data_type), false, &liveness)?;
+                Some(Cell {
+                    value: if cell.is_deleted { CassandraValue::Null } else { column.data_type.decode(&cell.value)? },
+                    timestamp: cell.timestamp,
+                    ttl: cell.ttl,
+                    is_deleted: cell.is_deleted,
+                })
+            };
+            if let Some(cell) = cell {
+                row_timestamp = row_timestamp.max(cell.timestamp);
+                cells.insert(&column.name, cell);
+            }
+        }
+
+        // 행 삭제는 그보다 오래된 셀과 없는 컬럼을 툼스톤으로 바꿔 표현
+        if let Some(deletion) = row_deletion {
+            for column in columns {
+                if cells.get(&column.name).is_none_or(|cell| cell.timestamp <= deletion) {
+                    cells.insert(&column.name, Cell { value: CassandraValue::Null, timestamp: deletion, ttl: None, is_deleted: true });
+                }
+            }
+            row_timestamp = row_timestamp.max(deletion);
+        }
+
+        if data.offset() != body_start + row_size {
+            return Err(CoreDBError::Corruption {
+                file: self.sstable.data_path.display().to_string(),
+                offset: body_start,
+                message: format!("row body is {} bytes, header says {}", data.offset() - body_start, row_size),
+            });
+        }
+        Ok(Row { partition_key: partition_key.clone(), clustering_key, cells, timestamp: row_timestamp })
+    }
+}
+
+impl Iterator for CassandraPartitions<'_> {
+    type Item = Result<CassandraPartition>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.is_at_end() {
+            Ok(true) => None,
+            Ok(false) => Some(self.read_partition()),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// 파티션 키 해석 (복합 키는 [길이 u16][값][끝 표시 u8] 반복)
+fn decode_partition_key(types: &[CassandraType], key: &[u8]) -> Result<Vec<CassandraValue>> {
+    if let [single] = types {
+        return Ok(vec![single.decode(key)?]);
+    }
+    let mut input = ByteReader::new(key);
+    types.iter().map(|data_type| {
+        let length = input.read_u16()? as usize;
+        let value = data_type.decode(input.read_bytes(length)?)?;
+        input.read_u8()?;
+        Ok(value)
+    }).collect()
+}
+
+/// 클러스터링 값 (32개마다 값별 2비트 헤더: 빈 값, null)
+fn read_clustering_values(input: &mut impl Input, types: &[CassandraType]) -> Result<Vec<Option<Vec<u8>>>> {
+    let mut values = Vec::with_capacity(types.len());
+    let mut header = 0;
+    for (i, data_type) in types.iter().enumerate() {
+        if i % 32 == 0 {
+            header = input.read_unsigned_vint()?;
+        }
+        let shift = (i % 32) * 2;
+        let value = if header & (1 << (shift + 1)) != 0 {
+            None
+        } else if header & (1 << shift) != 0 {
+            Some(Vec::new())
+        } else {
+            Some(read_value(input, data_type)?)
+        };
+        values.push(value);
+    }
+    Ok(values)
+}
+
+/// 값 하나 (고정 길이 타입은 그대로, 나머지는 vint 길이 접두사)
+fn read_value(input: &mut impl Input, data_type: &CassandraType) -> Result<Vec<u8>> {
+    match data_type.fixed_length() {
+        Some(length) => Ok(input.read_bytes(length)?.to_vec()),
+        None => input.read_vint_bytes(),
+    }
+}
+
+/// 행에 있는 헤더 컬럼 번호 (컬럼 수가 64 미만이면 빠진 컬럼 비트맵, 아니면 번호 목록)
+fn read_column_subset(input: &mut impl Input, superset: usize) -> Result<Vec<usize>> {
+    let encoded = input.read_unsigned_vint()?;
+    if encoded == 0 {
+        return Ok((0..superset).collect());
+    }
+    if superset < 64 {
+        return Ok((0..superset).filter(|index| encoded & (1 << index) == 0).collect());
+    }
+    let missing = encoded as usize;
+    let count = superset.checked_sub(missing).ok_or_else(|| invalid(format!("{} of {} columns missing", missing, superset)))?;
+    if count < superset / 2 {
+        (0..count).map(|_| Ok(input.read_unsigned_vint()? as usize)).collect()
+    } else {
+        let absent = (0..missing).map(|_| Ok(input.read_unsigned_vint()? as usize)).collect::<Result<std::collections::HashSet<_>>>()?;
+        Ok((0..superset).filter(|index| !absent.contains(index)).collect())
+    }
+}
+
+fn read_timestamp(input: &mut impl Input, header: &SerializationHeader) -> Result<i64> {
+    Ok((input.read_unsigned_vint()? as i64).wrapping_add(header.min_timestamp))
+}
+
+fn read_local_deletion_time(input: &mut impl Input, header: &SerializationHeader) -> Result<i64> {
+    Ok((input.read_unsigned_vint()? as i32 as i64).wrapping_add(header.min_local_deletion_time))
+}
+
+fn read_ttl(input: &mut impl Input, header: &SerializationHeader) -> Result<u32> {
+    Ok((input.read_unsigned_vint()? as i64 + header.min_ttl) as u32)
+}
+
+/// 해석 전 셀
+struct RawCell {
+    path: Option<Vec<u8>>,
+    value: Vec<u8>,
+    timestamp: i64,
+    ttl: Option<u32>,
+    is_deleted: bool,
+}
+
+/// 셀 ([플래그][타임스탬프][삭제 시각][TTL][경로][값], 행과 같은 항목은 생략)
+fn read_cell(input: &mut impl Input, header: &SerializationHeader, value_type: Option<&CassandraType>, has_path: bool, row: &RowLiveness) -> Result<RawCell> {
+    let flags = input.read_u8()?;
+    let is_deleted = flags & CELL_IS_DELETED != 0;
+    let is_expiring = flags & CELL_IS_EXPIRING != 0;
+    let use_row_ttl = flags & CELL_USE_ROW_TTL != 0;
+    let timestamp = if flags & CELL_USE_ROW_TIMESTAMP != 0 { row.timestamp } else { read_timestamp(input, header)? };
+    if (is_deleted || is_expiring) && !use_row_ttl {
+        read_local_deletion_time(input, header)?;
+    }
+    let ttl = match (is_expiring, use_row_ttl) {
+        (true, false) => Some(read_ttl(input, header)?),
+        (true, true) => row.ttl,
+        _ => None,
+    };
+    let path = if has_path { Some(input.read_vint_bytes()?) } else { None };
+    let value = match value_type {
+        Some(value_type) if flags & CELL_HAS_EMPTY_VALUE == 0 => read_value(input, value_type)?,
+        _ => Vec::new(),
+    };
+    Ok(RawCell { path, value, timestamp, ttl, is_deleted })
+}
+
+/// 고정되지 않은 컬렉션 컬럼 ([컬렉션 삭제][셀 수 vint][원소 셀...]) 을 CoreDB 컬렉션 셀 하나로
+///
+/// 컬렉션 삭제보다 오래된 원소와 삭제된 원소는 빼고, 남은 원소가 없으면 툼스톤
+fn read_complex_column(input: &mut impl Input, header: &SerializationHeader, data_type: &CassandraType, has_complex_deletion: bool, row: &RowLiveness) -> Result<Option<Cell>> {
+    let deletion = if has_complex_deletion {
+        let deletion = read_timestamp(input, header)?;
+        read_local_deletion_time(input, header)?;
+        (deletion != i64::MIN).then_some(deletion)
+    } else {
+        None
+    };
+    let value_type = match data_type {
+        CassandraType::List(element) => Some(element.as_ref()),
+        CassandraType::Map(_, value) => Some(value.as_ref()),
+        _ => None,
+    };
+    let count = input.read_unsigned_vint()?;
+    let mut elements = Vec::new();
+    for _ in 0..count {
+        elements.push(read_cell(input, header, value_type, true, row)?);
+    }
+
+    let timestamp = elements.iter().map(|cell| cell.timestamp).chain(deletion).max();
+    let ttl = elements.first().and_then(|cell| cell.ttl).filter(|ttl| elements.iter().all(|cell| cell.ttl == Some(*ttl)));
+    elements.retain(|cell| !cell.is_deleted && deletion.is_none_or(|deletion| cell.timestamp > deletion));
+    let Some(timestamp) = timestamp else { return Ok(None) };
+    if elements.is_empty() {
+        return Ok(deletion.map(|deletion| Cell { value: CassandraValue::Null, timestamp: deletion, ttl: None, is_deleted: true }));
+    }
+
+    let path = |cell: &RawCell| cell.path.clone().unwrap_or_default();
+    let value = match data_type {
+        CassandraType::List(element) => CassandraValue::List(elements.iter().map(|cell| element.decode(&cell.value)).collect::<Result<_>>()?),
+        CassandraType::Set(element) => CassandraValue::Set(elements.iter().map(|cell| element.decode(&path(cell))).collect::<Result<_>>()?),
+        CassandraType::Map(key, value) => CassandraValue::Map(elements.iter()
+            .map(|cell| Ok((map_key(&key.decode(&path(cell))?), value.decode(&cell.value)?)))
+            .collect::<Result<_>>()?),
+        other => return Err(unsupported_type(&format!("{:?}", other))),
+    };
+    Ok(Some(Cell { value, timestamp, ttl, is_deleted: false }))
+}
+
+fn unsupported_type(name: &str) -> CoreDBError {
+    CoreDBError::Generic { message: format!("Unsupported Cassandra column type: {}", name) }
+}
+
+fn invalid(message: String) -> CoreDBError {
+    CoreDBError::Generic { message: format!("Invalid Cassandra SSTable: {}", message) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cassandra 직렬화를 흉내 내는 테스트용 쓰기 도구
+    #[derive(Default)]
+    struct Writer {
+        data: Vec<u8>,
+    }
+
+    impl Writer {
+        fn vint(&mut self, value: u64) -> &mut Self {
+            let extra = match value {
+                0..=0x7f => 0,
+                _ => ((64 - value.leading_zeros() as usize) - 1) / 7,
+            }.min(8);
+            let bytes = value.to_be_bytes();
+            if extra == 8 {
+                self.data.push(0xff);
+                self.data.extend(bytes);
+            } else {
+                let mut encoded = bytes[8 - extra - 1..].to_vec();
+                encoded[0] |= !(0xffu8 >> extra);
+                self.data.extend(encoded);
+            }
+            self
+        }
+
+        fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+            self.data.extend_from_slice(bytes);
+            self
+        }
+
+        fn vint_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+            self.vint(bytes.len() as u64).bytes(bytes)
+        }
+    }
+
+    const INT: &str = "org.apache.cassandra.db.marshal.Int32Type";
+    const TEXT: &str = "org.apache.cassandra.db.marshal.UTF8Type";
+    const SET_OF_TEXT: &str = "org.apache.cassandra.db.marshal.SetType(org.apache.cassandra.db.marshal.UTF8Type)";
+
+    /// ks.events (id int, seq int, name text, tags set<text>, PRIMARY KEY (id, seq)) 형식의 SSTable 쓰기
+    fn write_sstable(directory: &Path, compress: bool) -> PathBuf {
+        let min_timestamp = 1_700_000_000_000_000i64;
+        let min_deletion_time = 1_700_000_000i64;
+        let mut statistics_header = Writer::default();
+        statistics_header
+            .vint((min_timestamp - ENCODING_EPOCH_SECONDS * 1_000_000) as u64)
+            .vint((min_deletion_time - ENCODING_EPOCH_SECONDS) as u64)
+            .vint(0)
+            .vint_bytes(INT.as_bytes())
+            .vint(1).vint_bytes(format!("org.apache.cassandra.db.marshal.ReversedType({})", INT).as_bytes())
+            .vint(0)
+            .vint(2)
+            .vint_bytes(b"tags").vint_bytes(SET_OF_TEXT.as_bytes())
+            .vint_bytes(b"name").vint_bytes(TEXT.as_bytes());
+        let mut statistics = Writer::default();
+        statistics.bytes(&1i32.to_be_bytes()).bytes(&3i32.to_be_bytes()).bytes(&12i32.to_be_bytes()).bytes(&statistics_header.data);
+
+        let mut data = Writer::default();
+        let mut index = Writer::default();
+        // 파티션 1: 전체 컬럼 행 하나와 이름만 삭제된 행 하나
+        let position = data.data.len();
+        index.bytes(&4u16.to_be_bytes()).bytes(&1i32.to_be_bytes()).vint(position as u64).vint(0);
+        data.bytes(&4u16.to_be_bytes()).bytes(&1i32.to_be_bytes()).bytes(&i32::MAX.to_be_bytes()).bytes(&i64::MIN.to_be_bytes());
+        {
+            let mut body = Writer::default();
+            body.vint(0) // 이전 행 크기
+                .vint(10) // 행 타임스탬프 (최소값과의 차이)
+                .bytes(&[CELL_USE_ROW_TIMESTAMP]).vint_bytes(b"kim")
+                .vint(2) // 컬렉션 원소 수
+                .bytes(&[CELL_USE_ROW_TIMESTAMP | CELL_HAS_EMPTY_VALUE]).vint_bytes(b"a")
+                .bytes(&[CELL_IS_EXPIRING | CELL_HAS_EMPTY_VALUE]).vint(20).vint(100).vint(60).vint_bytes(b"b");
+            data.bytes(&[HAS_TIMESTAMP | HAS_ALL_COLUMNS]).vint(0).bytes(&7i32.to_be_bytes());
+            data.vint(body.data.len() as u64).bytes(&body.data);
+        }
+        {
+            let mut body = Writer::default();
+            body.vint(0)
+                .vint(0b10) // tags 없음
+                .bytes(&[CELL_IS_DELETED | CELL_HAS_EMPTY_VALUE]).vint(30).vint(5);
+            data.bytes(&[0]).vint(0).bytes(&3i32.to_be_bytes());
+            data.vint(body.data.len() as u64).bytes(&body.data);
+        }
+        // 범위 툼스톤 표시는 건너뜀
+        data.bytes(&[IS_MARKER, 0]).bytes(&1u16.to_be_bytes()).vint(0).bytes(&1i32.to_be_bytes()).vint(1).vint(0);
+        data.bytes(&[END_OF_PARTITION]);
+
+        // 파티션 2: 삭제된 파티션
+        let position = data.data.len();
+        index.bytes(&4u16.to_be_bytes()).bytes(&2i32.to_be_bytes()).vint(position as u64).vint(0);
+        data.bytes(&4u16.to_be_bytes()).bytes(&2i32.to_be_bytes()).bytes(&1_700_000_100i32.to_be_bytes()).bytes(&min_timestamp.to_be_bytes());
+        data.bytes(&[END_OF_PARTITION]);
+
+        let prefix = directory.join("nb-1-big-");
+        let path = |name: &str| PathBuf::from(format!("{}{}", prefix.display(), name));
+        std::fs::write(path("Statistics.db"), &statistics.data).unwrap();
+        std::fs::write(path("Index.db"), &index.data).unwrap();
+        if compress {
+            // 5바이트 청크로 나눠 청크 경계에 값이 걸치도록 함
+            let mut info = Writer::default();
+            info.bytes(&13u16.to_be_bytes()).bytes(b"LZ4Compressor").bytes(&0i32.to_be_bytes())
+                .bytes(&5i32.to_be_bytes()).bytes(&i32::MAX.to_be_bytes())
+                .bytes(&(data.data.len() as i64).to_be_bytes());
+            let chunks: Vec<&[u8]> = data.data.chunks(5).collect();
+            info.bytes(&(chunks.len() as i32).to_be_bytes());
+            let mut file = Vec::new();
+            for chunk in chunks {
+                info.bytes(&(file.len() as i64).to_be_bytes());
+                let mut compressed = (chunk.len() as u32).to_le_bytes().to_vec();
+                compressed.extend(lz4_flex::block::compress(chunk));
+                file.extend(&compressed);
+                file.extend(crc32(&compressed).to_be_bytes());
+            }
+            std::fs::write(path("CompressionInfo.db"), &info.data).unwrap();
+            std::fs::write(path("Data.db"), file).unwrap();
+        } else {
+            std::fs::write(path("Data.db"), &data.data).unwrap();
+        }
+        path("Data.db")
+    }
+
+    #[test]
+    fn test_type_names_and_vints() {
+        assert_eq!(
+            CassandraType::parse("org.apache.cassandra.db.marshal.MapType(org.apache.cassandra.db.marshal.UTF8Type,org.apache.cassandra.db.marshal.FrozenType(org.apache.cassandra.db.marshal.ListType(org.apache.cassandra.db.marshal.LongType)))").unwrap(),
+            CassandraType::Map(Box::new(CassandraType::Text), Box::new(CassandraType::Frozen(Box::new(CassandraType::List(Box::new(CassandraType::BigInt)))))),
+        );
+        assert_eq!(CassandraType::parse_key(&format!("org.apache.cassandra.db.marshal.CompositeType({},{})", INT, TEXT)).unwrap(), vec![CassandraType::Int, CassandraType::Text]);
+        assert!(CassandraType::parse("org.apache.cassandra.db.marshal.InetAddressType").is_err());
+
+        for value in [0, 1, 127, 128, 300, 1 << 20, 1 << 40, u64::MAX] {
+            let mut writer = Writer::default();
+            writer.vint(value);
+            assert_eq!(ByteReader::new(&writer.data).read_unsigned_vint().unwrap(), value);
+        }
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        // 고정된 컬렉션 ([원소 수][길이, 값]...)
+        let mut frozen = 2i32.to_be_bytes().to_vec();
+        for value in [5i64, -1] {
+            frozen.extend(8i32.to_be_bytes());
+            frozen.extend(value.to_be_bytes());
+        }
+        let list = CassandraType::Frozen(Box::new(CassandraType::List(Box::new(CassandraType::BigInt))));
+        assert_eq!(list.decode(&frozen).unwrap(), CassandraValue::List(vec![CassandraValue::BigInt(5), CassandraValue::BigInt(-1)]));
+    }
+
+    #[test]
+    fn test_reads_big_format_partitions() {
+        for compress in [false, true] {
+            let directory = std::env::temp_dir().join(format!("coredb_test_cassandra_sstable_{}", compress));
+            let _ = std::fs::remove_dir_all(&directory);
+            std::fs::create_dir_all(&directory).unwrap();
+            let data_path = write_sstable(&directory, compress);
+            assert_eq!(CassandraSSTable::find(&directory).unwrap(), vec![data_path.clone()]);
+
+            let sstable = CassandraSSTable::open(&data_path).unwrap();
+            assert_eq!(sstable.version, "nb");
+            let names: Vec<&str> = sstable.header.regular_columns.iter().map(|column| column.name.as_str()).collect();
+            assert_eq!(names, ["name", "tags"]);
+            let partitions: Vec<CassandraPartition> = sstable.partitions().unwrap().collect::<Result<_>>().unwrap();
+            assert_eq!(partitions.len(), 2);
+
+            let first = &partitions[0];
+            assert_eq!(first.partition_key.components, vec![CassandraValue::Int(1)]);
+            assert_eq!(first.range_tombstone_markers, 1);
+            let row = &first.rows[0];
+            assert_eq!(row.clustering_key.as_ref().unwrap().components, vec![CassandraValue::Int(7)]);
+            assert_eq!(row.timestamp, 1_700_000_000_000_020);
+            assert_eq!(row.cells["name"].value, CassandraValue::Text("kim".to_string()));
+            assert_eq!(row.cells["name"].timestamp, 1_700_000_000_000_010);
+            let tags = &row.cells["tags"];
+            assert_eq!(tags.value, CassandraValue::Set(vec![CassandraValue::Text("a".to_string()), CassandraValue::Text("b".to_string())]));
+            assert_eq!((tags.timestamp, tags.ttl), (1_700_000_000_000_020, None));
+            let deleted = &first.rows[1].cells["name"];
+            assert!(deleted.is_deleted);
+            assert_eq!(deleted.timestamp, 1_700_000_000_000_030);
+            assert!(first.rows[1].cells.get("tags").is_none());
+
+            assert_eq!(partitions[1].partition_deletion, Some(1_700_000_000_000_000));
+            assert!(partitions[1].rows.is_empty());
+
+            // 인덱스와 맞지 않으면 손상으로 처리
+            let index_path = data_path.with_file_name("nb-1-big-Index.db");
+            let mut index = std::fs::read(&index_path).unwrap();
+            index[5] ^= 0xff;
+            std::fs::write(&index_path, index).unwrap();
+            assert!(sstable.partitions().unwrap().any(|partition| partition.is_err()));
+
+            std::fs::remove_dir_all(&directory).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_into_matching_table() {
+        use crate::database::{CoreDB, DatabaseConfig};
+
+        let directory = std::env::temp_dir().join("coredb_test_cassandra_import");
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        write_sstable(&directory, true);
+
+        let db = CoreDB::new(DatabaseConfig::ephemeral()).await.unwrap();
+        db.create_keyspace("ks".to_string(), 1).await.unwrap();
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let create = |seq_type| TableSchema::new(
+            "events".to_string(),
+            "ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![column("seq", seq_type)],
+            vec![column("name", CassandraDataType::Text), column("tags", CassandraDataType::Set(Box::new(CassandraDataType::Text)))],
+            vec![],
+        );
+
+        // 타입이 다른 테이블에는 가져오지 않음
+        db.create_table("ks".to_string(), "events".to_string(), create(CassandraDataType::BigInt)).await.unwrap();
+        assert!(db.import_cassandra_sstables("ks", "events", &directory).await.is_err());
+
+        db.create_table("ks".to_string(), "events".to_string(), create(CassandraDataType::Int)).await.unwrap();
+        let stats = db.import_cassandra_sstables("ks", "events", &directory).await.unwrap();
+        assert_eq!(stats, CassandraImportStats { sstables: 1, partitions: 2, rows: 2, skipped_partition_deletions: 1, skipped_range_tombstones: 1 });
+
+        let partition_key = PartitionKey { components: vec![CassandraValue::Int(1)] };
+        let row = db.get_row("ks", "events", &partition_key, &Some(ClusteringKey { components: vec![CassandraValue::Int(7)] }))
+            .await.unwrap().unwrap();
+        assert_eq!(row.cells["name"].value, CassandraValue::Text("kim".to_string()));
+        assert_eq!(row.cells["name"].timestamp, 1_700_000_000_000_010);
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
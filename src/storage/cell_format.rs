@@ -0,0 +1,273 @@
+use uuid::Uuid;
+use crate::schema::{CassandraValue, Cell};
+use crate::error::*;
+
+/// 셀을 고정 배치로 쓰기 시작한 SSTable 형식 버전 (이전 형식은 셀마다 bincode)
+pub const FIXED_LAYOUT_CELLS_VERSION: u32 = 11;
+
+/// 셀 플래그: 툼스톤
+const FLAG_DELETED: u8 = 0x01;
+/// 셀 플래그: TTL 있음
+const FLAG_TTL: u8 = 0x02;
+
+/// 값 타입 태그
+const TAG_NULL: u8 = 0;
+const TAG_TEXT: u8 = 1;
+const TAG_INT: u8 = 2;
+const TAG_BIGINT: u8 = 3;
+const TAG_UUID: u8 = 4;
+const TAG_TIMESTAMP: u8 = 5;
+const TAG_BOOLEAN: u8 = 6;
+const TAG_DOUBLE: u8 = 7;
+const TAG_BLOB: u8 = 8;
+const TAG_VECTOR: u8 = 9;
+const TAG_POINT: u8 = 10;
+/// 맵/리스트/셋은 bincode로 저장
+const TAG_COLLECTION: u8 = 11;
+
+/// 셀 인코딩
+///
+/// `[플래그 u8][타임스탬프 i64][TTL u32 (플래그가 있을 때)][타입 태그 u8][값]` (정수는 빅엔디언).
+/// 고정 크기 값은 그대로, 텍스트/blob/벡터는 프레임 끝까지의 바이트라 읽을 때 복사 없이 참조할 수 있음
+pub fn encode_cell(cell: &Cell, out: &mut Vec<u8>) -> Result<()> {
+    let mut flags = 0;
+    if cell.is_deleted {
+        flags |= FLAG_DELETED;
+    }
+    if cell.ttl.is_some() {
+        flags |= FLAG_TTL;
+    }
+    out.push(flags);
+    out.extend_from_slice(&cell.timestamp.to_be_bytes());
+    if let Some(ttl) = cell.ttl {
+        out.extend_from_slice(&ttl.to_be_bytes());
+    }
+    encode_value(&cell.value, out)
+}
+
+fn encode_value(value: &CassandraValue, out: &mut Vec<u8>) -> Result<()> {
+    match value {
+        CassandraValue::Null => out.push(TAG_NULL),
+        CassandraValue::Text(text) => {
+            out.push(TAG_TEXT);
+            out.extend_from_slice(text.as_bytes());
+        },
+        CassandraValue::Int(value) => {
+            out.push(TAG_INT);
+            out.extend_from_slice(&value.to_be_bytes());
+        },
+        CassandraValue::BigInt(value) => {
+            out.push(TAG_BIGINT);
+            out.extend_from_slice(&value.to_be_bytes());
+        },
+        CassandraValue::UUID(uuid) => {
+            out.push(TAG_UUID);
+            out.extend_from_slice(uuid.as_bytes());
+        },
+        CassandraValue::Timestamp(value) => {
+            out.push(TAG_TIMESTAMP);
+            out.extend_from_slice(&value.to_be_bytes());
+        },
+        CassandraValue::Boolean(value) => out.extend_from_slice(&[TAG_BOOLEAN, *value as u8]),
+        CassandraValue::Double(value) => {
+            out.push(TAG_DOUBLE);
+            out.extend_from_slice(&value.to_be_bytes());
+        },
+        CassandraValue::Blob(bytes) => {
+            out.push(TAG_BLOB);
+            out.extend_from_slice(bytes);
+        },
+        CassandraValue::Vector(components) => {
+            out.push(TAG_VECTOR);
+            components.iter().for_each(|component| out.extend_from_slice(&component.to_be_bytes()));
+        },
+        CassandraValue::Point(latitude, longitude) => {
+            out.push(TAG_POINT);
+            out.extend_from_slice(&latitude.to_be_bytes());
+            out.extend_from_slice(&longitude.to_be_bytes());
+        },
+        CassandraValue::Map(_) | CassandraValue::List(_) | CassandraValue::Set(_) => {
+            out.push(TAG_COLLECTION);
+            out.extend(bincode::serialize(value)?);
+        },
+    }
+    Ok(())
+}
+
+/// 인코딩된 셀을 복사 없이 읽는 뷰 (값은 요청할 때만 해석)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellView<'a> {
+    flags: u8,
+    timestamp: i64,
+    ttl: Option<u32>,
+    /// 타입 태그와 값
+    value: &'a [u8],
+}
+
+impl<'a> CellView<'a> {
+    /// 셀 프레임 하나 해석 (헤더만 읽음)
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let (&flags, rest) = data.split_first().ok_or_else(|| invalid("empty cell"))?;
+        let (timestamp, rest) = rest.split_first_chunk::<8>().ok_or_else(|| invalid("truncated timestamp"))?;
+        let (ttl, value) = if flags & FLAG_TTL != 0 {
+            let (ttl, rest) = rest.split_first_chunk::<4>().ok_or_else(|| invalid("truncated ttl"))?;
+            (Some(u32::from_be_bytes(*ttl)), rest)
+        } else {
+            (None, rest)
+        };
+        if value.is_empty() {
+            return Err(invalid("missing value"));
+        }
+        Ok(Self { flags, timestamp: i64::from_be_bytes(*timestamp), ttl, value })
+    }
+
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    pub fn ttl(&self) -> Option<u32> {
+        self.ttl
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.flags & FLAG_DELETED != 0
+    }
+
+    pub fn value(&self) -> Result<ValueView<'a>> {
+        let (&tag, payload) = self.value.split_first().expect("parse checked the value tag");
+        let fixed = |length: usize| -> Result<&'a [u8]> {
+            if payload.len() != length {
+                return Err(invalid(&format!("value of tag {} has {} bytes, expected {}", tag, payload.len(), length)));
+            }
+            Ok(payload)
+        };
+        let value = match tag {
+            TAG_NULL => {
+                fixed(0)?;
+                ValueView::Null
+            },
+            TAG_TEXT => ValueView::Text(std::str::from_utf8(payload).map_err(|e| invalid(&e.to_string()))?),
+            TAG_INT => ValueView::Int(i32::from_be_bytes(fixed(4)?.try_into().expect("4 bytes"))),
+            TAG_BIGINT => ValueView::BigInt(i64::from_be_bytes(fixed(8)?.try_into().expect("8 bytes"))),
+            TAG_UUID => ValueView::UUID(Uuid::from_slice(fixed(16)?).expect("16 bytes")),
+            TAG_TIMESTAMP => ValueView::Timestamp(i64::from_be_bytes(fixed(8)?.try_into().expect("8 bytes"))),
+            TAG_BOOLEAN => ValueView::Boolean(fixed(1)?[0] != 0),
+            TAG_DOUBLE => ValueView::Double(f64::from_be_bytes(fixed(8)?.try_into().expect("8 bytes"))),
+            TAG_BLOB => ValueView::Blob(payload),
+            TAG_VECTOR if payload.len() % 4 == 0 => ValueView::Vector(payload),
+            TAG_POINT => {
+                let point = fixed(16)?;
+                ValueView::Point(
+                    f64::from_be_bytes(point[..8].try_into().expect("8 bytes")),
+                    f64::from_be_bytes(point[8..].try_into().expect("8 bytes")),
+                )
+            },
+            TAG_COLLECTION => ValueView::Collection(payload),
+            _ => return Err(invalid(&format!("unknown value tag {} ({} bytes)", tag, payload.len()))),
+        };
+        Ok(value)
+    }
+
+    /// 소유한 셀로 변환
+    pub fn to_cell(&self) -> Result<Cell> {
+        Ok(Cell {
+            value: self.value()?.to_value()?,
+            timestamp: self.timestamp,
+            ttl: self.ttl,
+            is_deleted: self.is_deleted(),
+        })
+    }
+}
+
+/// 인코딩된 값 (가변 길이 값은 원본 바이트를 참조)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueView<'a> {
+    Null,
+    Text(&'a str),
+    Int(i32),
+    BigInt(i64),
+    UUID(Uuid),
+    Timestamp(i64),
+    Boolean(bool),
+    Double(f64),
+    Blob(&'a [u8]),
+    /// 빅엔디언 f32 연속
+    Vector(&'a [u8]),
+    Point(f64, f64),
+    /// bincode로 인코딩된 맵/리스트/셋
+    Collection(&'a [u8]),
+}
+
+impl ValueView<'_> {
+    pub fn to_value(&self) -> Result<CassandraValue> {
+        Ok(match *self {
+            ValueView::Null => CassandraValue::Null,
+            ValueView::Text(text) => CassandraValue::Text(text.to_string()),
+            ValueView::Int(value) => CassandraValue::Int(value),
+            ValueView::BigInt(value) => CassandraValue::BigInt(value),
+            ValueView::UUID(uuid) => CassandraValue::UUID(uuid),
+            ValueView::Timestamp(value) => CassandraValue::Timestamp(value),
+            ValueView::Boolean(value) => CassandraValue::Boolean(value),
+            ValueView::Double(value) => CassandraValue::Double(value),
+            ValueView::Blob(bytes) => CassandraValue::Blob(bytes.to_vec()),
+            ValueView::Vector(bytes) => CassandraValue::Vector(
+                bytes.chunks_exact(4).map(|component| f32::from_be_bytes(component.try_into().expect("4 bytes"))).collect()
+            ),
+            ValueView::Point(latitude, longitude) => CassandraValue::Point(latitude, longitude),
+            ValueView::Collection(bytes) => bincode::deserialize(bytes)?,
+        })
+    }
+}
+
+fn invalid(message: &str) -> CoreDBError {
+    CoreDBError::Generic { message: format!("Invalid cell encoding: {}", message) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_cell_round_trips_through_fixed_layout() {
+        let values = vec![
+            CassandraValue::Null,
+            CassandraValue::Text("안녕 coredb".to_string()),
+            CassandraValue::Int(-7),
+            CassandraValue::BigInt(1 << 40),
+            CassandraValue::UUID(Uuid::new_v4()),
+            CassandraValue::Timestamp(1_700_000_000_000_000),
+            CassandraValue::Boolean(true),
+            CassandraValue::Double(-2.5),
+            CassandraValue::Blob(vec![0, 1, 2, 255]),
+            CassandraValue::Vector(vec![0.5, -1.0, 3.25]),
+            CassandraValue::Point(37.5, 127.0),
+            CassandraValue::List(vec![CassandraValue::Int(1), CassandraValue::Text("a".to_string())]),
+            CassandraValue::Map(HashMap::from([("k".to_string(), CassandraValue::BigInt(2))])),
+        ];
+        for (i, value) in values.into_iter().enumerate() {
+            let cell = Cell { value, timestamp: -(i as i64), ttl: (i % 2 == 0).then_some(i as u32 * 60), is_deleted: i == 3 };
+            let mut data = Vec::new();
+            encode_cell(&cell, &mut data).unwrap();
+            let view = CellView::parse(&data).unwrap();
+            assert_eq!((view.timestamp(), view.ttl(), view.is_deleted()), (cell.timestamp, cell.ttl, cell.is_deleted));
+            let decoded = view.to_cell().unwrap();
+            assert_eq!((decoded.value, decoded.timestamp, decoded.ttl, decoded.is_deleted), (cell.value, cell.timestamp, cell.ttl, cell.is_deleted));
+        }
+
+        // 텍스트는 인코딩된 바이트를 그대로 참조
+        let cell = Cell { value: CassandraValue::Text("zero-copy".to_string()), timestamp: 1, ttl: None, is_deleted: false };
+        let mut data = Vec::new();
+        encode_cell(&cell, &mut data).unwrap();
+        let ValueView::Text(text) = CellView::parse(&data).unwrap().value().unwrap() else { panic!("expected text") };
+        assert_eq!(text, "zero-copy");
+        assert!(data.as_ptr_range().contains(&text.as_ptr()));
+
+        // 잘린 셀과 길이가 맞지 않는 값은 오류
+        assert!(CellView::parse(&data[..5]).is_err());
+        let mut wrong = Vec::new();
+        encode_cell(&Cell { value: CassandraValue::Int(1), timestamp: 1, ttl: None, is_deleted: false }, &mut wrong).unwrap();
+        wrong.push(0);
+        assert!(CellView::parse(&wrong).unwrap().value().is_err());
+    }
+}
@@ -0,0 +1,67 @@
+use std::path::Path;
+use crate::error::*;
+
+/// 체크섬 길이 (CRC32C, big endian)
+pub const CHECKSUM_LEN: usize = 4;
+
+/// CRC32C 체크섬
+pub fn checksum(data: &[u8]) -> u32 {
+    crc32c::crc32c(data)
+}
+
+/// 데이터 뒤에 체크섬을 붙여 반환
+pub fn with_checksum(mut data: Vec<u8>) -> Vec<u8> {
+    let crc = checksum(&data);
+    data.extend_from_slice(&crc.to_be_bytes());
+    data
+}
+
+/// 체크섬 확인 (offset은 파일에서 data가 시작하는 위치)
+pub fn verify_checksum(data: &[u8], expected: u32, file: &Path, offset: u64) -> Result<()> {
+    let actual = checksum(data);
+    if actual != expected {
+        return Err(CoreDBError::Corruption {
+            file: file.display().to_string(),
+            offset,
+            message: format!("checksum mismatch over {} bytes (expected {:08x}, found {:08x})", data.len(), expected, actual),
+        });
+    }
+    Ok(())
+}
+
+/// with_checksum으로 기록한 데이터를 확인하고 체크섬을 뗀 본문 반환
+pub fn strip_checksum<'a>(data: &'a [u8], file: &Path, offset: u64) -> Result<&'a [u8]> {
+    let Some(body_len) = data.len().checked_sub(CHECKSUM_LEN) else {
+        return Err(CoreDBError::Corruption {
+            file: file.display().to_string(),
+            offset,
+            message: format!("{} bytes is too short for a checksum", data.len()),
+        });
+    };
+    let (body, trailer) = data.split_at(body_len);
+    verify_checksum(body, u32::from_be_bytes(trailer.try_into().expect("4 bytes")), file, offset)?;
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum_detects_flipped_bit() {
+        let file = Path::new("test-Data.db");
+        let data = with_checksum(b"partition data".to_vec());
+        assert_eq!(strip_checksum(&data, file, 0).unwrap(), b"partition data");
+
+        let mut corrupted = data.clone();
+        corrupted[3] ^= 0x01;
+        match strip_checksum(&corrupted, file, 128) {
+            Err(CoreDBError::Corruption { file, offset, .. }) => {
+                assert_eq!(file, "test-Data.db");
+                assert_eq!(offset, 128);
+            },
+            other => panic!("expected corruption, got {:?}", other),
+        }
+        assert!(strip_checksum(&data[..2], file, 0).is_err());
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// 기본 청크 캐시 크기 (MB)
+pub const DEFAULT_CHUNK_CACHE_SIZE_MB: u64 = 128;
+/// 청크 캐시 샤드 수
+const CHUNK_CACHE_SHARDS: usize = 16;
+
+/// (SSTable id, 청크 번호)
+type ChunkCacheKey = (String, usize);
+
+/// 압축 해제된 SSTable 청크 캐시
+///
+/// 모든 테이블이 공유하는 바이트 한도 캐시로, 같은 청크를 다시 읽을 때 파일 읽기와
+/// 압축 해제를 건너뜀. 샤드마다 한도를 나누고 가장 오래 안 쓴 청크부터 내보냄.
+/// 스캐너(컴팩션)의 순차 읽기는 캐시를 거치지 않음
+pub struct ChunkCache {
+    shards: Vec<Mutex<ChunkCacheShard>>,
+    shard_capacity_bytes: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Default)]
+struct ChunkCacheShard {
+    /// 키 → (청크, 마지막 사용 순번)
+    entries: HashMap<ChunkCacheKey, (Arc<Vec<u8>>, u64)>,
+    /// 마지막 사용 순번 → 키
+    recency: BTreeMap<u64, ChunkCacheKey>,
+    next_tick: u64,
+    size_bytes: u64,
+}
+
+impl ChunkCacheShard {
+    fn remove(&mut self, key: &ChunkCacheKey) {
+        if let Some((chunk, tick)) = self.entries.remove(key) {
+            self.recency.remove(&tick);
+            self.size_bytes -= chunk.len() as u64;
+        }
+    }
+
+    /// 오래 안 쓴 청크부터 capacity_bytes 안으로 줄임
+    fn evict_to(&mut self, capacity_bytes: u64) {
+        while self.size_bytes > capacity_bytes {
+            let Some((_, oldest)) = self.recency.pop_first() else { break };
+            let (data, _) = self.entries.remove(&oldest).expect("recency entry has a cached chunk");
+            self.size_bytes -= data.len() as u64;
+        }
+    }
+}
+
+/// 청크 캐시 통계
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ChunkCacheStats {
+    pub chunks: usize,
+    pub size_bytes: u64,
+    pub capacity_bytes: u64,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl ChunkCacheStats {
+    /// 적중률 (조회가 없으면 0.0)
+    pub fn hit_rate(&self) -> f64 {
+        let requests = self.hits + self.misses;
+        if requests == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / requests as f64
+    }
+}
+
+impl ChunkCache {
+    pub fn new(capacity_bytes: u64) -> Self {
+        Self {
+            shards: (0..CHUNK_CACHE_SHARDS).map(|_| Mutex::new(ChunkCacheShard::default())).collect(),
+            shard_capacity_bytes: AtomicU64::new(capacity_bytes / CHUNK_CACHE_SHARDS as u64),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn get(&self, sstable_id: &str, chunk: usize) -> Option<Arc<Vec<u8>>> {
+        let key = (sstable_id.to_string(), chunk);
+        let mut shard = self.shard(&key).lock().unwrap();
+        let tick = shard.next_tick;
+        let found = shard.entries.get_mut(&key).map(|(data, last_used)| (data.clone(), std::mem::replace(last_used, tick)));
+        let Some((data, previous)) = found else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        shard.next_tick += 1;
+        shard.recency.remove(&previous);
+        shard.recency.insert(tick, key);
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(data)
+    }
+
+    /// 통계와 사용 순서에 영향 없이 있는지만 확인
+    pub fn contains(&self, sstable_id: &str, chunk: usize) -> bool {
+        let key = (sstable_id.to_string(), chunk);
+        self.shard(&key).lock().unwrap().entries.contains_key(&key)
+    }
+
+    pub fn insert(&self, sstable_id: &str, chunk: usize, data: Arc<Vec<u8>>) {
+        let capacity_bytes = self.shard_capacity_bytes.load(Ordering::Relaxed);
+        if data.len() as u64 > capacity_bytes {
+            return;
+        }
+        let key = (sstable_id.to_string(), chunk);
+        let mut shard = self.shard(&key).lock().unwrap();
+        shard.remove(&key);
+        let tick = shard.next_tick;
+        shard.next_tick += 1;
+        shard.size_bytes += data.len() as u64;
+        shard.recency.insert(tick, key.clone());
+        shard.entries.insert(key, (data, tick));
+        shard.evict_to(capacity_bytes);
+    }
+
+    /// 용량 변경 (줄이면 오래 안 쓴 청크부터 바로 버림)
+    pub fn set_capacity(&self, capacity_bytes: u64) {
+        let shard_capacity_bytes = capacity_bytes / CHUNK_CACHE_SHARDS as u64;
+        self.shard_capacity_bytes.store(shard_capacity_bytes, Ordering::Relaxed);
+        for shard in &self.shards {
+            shard.lock().unwrap().evict_to(shard_capacity_bytes);
+        }
+    }
+
+    /// 삭제된 SSTable의 청크 제거
+    pub fn invalidate_sstable(&self, sstable_id: &str) {
+        for shard in &self.shards {
+            let mut shard = shard.lock().unwrap();
+            let keys: Vec<ChunkCacheKey> = shard.entries.keys().filter(|(id, _)| id == sstable_id).cloned().collect();
+            for key in keys {
+                shard.remove(&key);
+            }
+        }
+    }
+
+    pub fn stats(&self) -> ChunkCacheStats {
+        let (chunks, size_bytes) = self.shards.iter()
+            .map(|shard| {
+                let shard = shard.lock().unwrap();
+                (shard.entries.len(), shard.size_bytes)
+            })
+            .fold((0, 0), |(chunks, size), (shard_chunks, shard_size)| (chunks + shard_chunks, size + shard_size));
+        ChunkCacheStats {
+            chunks,
+            size_bytes,
+            capacity_bytes: self.shard_capacity_bytes.load(Ordering::Relaxed) * self.shards.len() as u64,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    fn shard(&self, key: &ChunkCacheKey) -> &Mutex<ChunkCacheShard> {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        key.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+}
+
+impl std::fmt::Debug for ChunkCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChunkCache").field("stats", &self.stats()).finish()
+    }
+}
+
+/// 같은 캐시 인스턴스일 때만 같음 (SSTable 비교용)
+impl PartialEq for ChunkCache {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_cache_evicts_within_byte_budget() {
+        let cache = ChunkCache::new(CHUNK_CACHE_SHARDS as u64 * 100);
+        let chunk = |fill: u8| Arc::new(vec![fill; 40]);
+
+        // 같은 샤드에 들어가는 청크만 골라 한도(샤드당 100바이트)를 채움
+        let target = cache.shard(&("a".to_string(), 0)) as *const _;
+        let same_shard: Vec<usize> = (0..10_000)
+            .filter(|index| std::ptr::eq(cache.shard(&("a".to_string(), *index)), target))
+            .take(3)
+            .collect();
+        cache.insert("a", same_shard[0], chunk(0));
+        cache.insert("a", same_shard[1], chunk(1));
+        assert_eq!(cache.get("a", same_shard[0]).unwrap()[0], 0);
+        cache.insert("a", same_shard[2], chunk(2));
+
+        // 가장 오래 안 쓴 청크가 밀려남
+        assert!(!cache.contains("a", same_shard[1]));
+        assert!(cache.contains("a", same_shard[0]));
+        assert!(cache.get("b", same_shard[0]).is_none());
+
+        // 용량을 줄이면 한도 안으로 바로 밀어냄 (샤드당 50바이트면 최근 청크 하나만)
+        cache.set_capacity(CHUNK_CACHE_SHARDS as u64 * 50);
+        assert!(!cache.contains("a", same_shard[0]));
+        assert!(cache.contains("a", same_shard[2]));
+
+        cache.invalidate_sstable("a");
+        let stats = cache.stats();
+        assert_eq!((stats.chunks, stats.size_bytes), (0, 0));
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+        assert_eq!(stats.capacity_bytes, CHUNK_CACHE_SHARDS as u64 * 50);
+    }
+}
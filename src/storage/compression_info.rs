@@ -0,0 +1,283 @@
+use std::collections::{HashMap, HashSet};
+use serde::{Serialize, Deserialize};
+use crate::schema::{CassandraValue, Cell};
+use crate::storage::{CompressionType, checksum};
+use crate::error::*;
+
+/// 기본 청크 크기 (압축 전 64KB)
+pub const DEFAULT_CHUNK_LENGTH: u32 = 64 * 1024;
+/// 청크마다 압축 전 길이(u32)를 앞에 붙이기 시작한 SSTable 형식 버전
+///
+/// 이전 형식은 코덱 고유 틀을 그대로 써서 길이를 모르는 ZSTD는 상한을 추측해 풀어야 했음
+pub const LENGTH_PREFIXED_CHUNKS_VERSION: u32 = 10;
+/// 청크 정보에 zstd 사전을 저장하기 시작한 SSTable 형식 버전
+pub const DICTIONARY_COMPRESSION_VERSION: u32 = 13;
+
+/// 사전 압축에서 사전을 학습할 때까지 모아 두는 압축 전 데이터 크기
+pub const DICTIONARY_TRAINING_BYTES: u64 = 1024 * 1024;
+/// 학습하는 사전의 최대 크기
+const MAX_DICTIONARY_SIZE: usize = 64 * 1024;
+/// 사전 학습에 쓰는 컬럼의 최대 고유 값 비율 (이보다 다양하면 반복되는 문자열이 아님)
+const LOW_CARDINALITY_RATIO: f64 = 0.1;
+/// 사전 학습에 쓰는 컬럼의 최소 값 수
+const MIN_COLUMN_SAMPLES: usize = 64;
+
+/// 데이터 파일의 압축 청크 정보 (-CompressionInfo.db)
+///
+/// 파티션 영역을 압축 전 기준 고정 크기 청크로 나눠 청크마다 따로 압축함.
+/// 압축 전 오프셋을 chunk_length로 나눈 값이 청크 번호이므로 필요한 청크만 읽어 풀 수 있음
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompressionInfo {
+    pub compression: CompressionType,
+    pub chunk_length: u32,
+    /// 압축 전 전체 길이
+    pub data_length: u64,
+    /// 청크별 데이터 파일 오프셋
+    pub chunk_offsets: Vec<u64>,
+    /// 청크별 압축된 바이트의 CRC32C
+    pub chunk_checksums: Vec<u32>,
+    /// 마지막 청크가 끝나는 데이터 파일 오프셋
+    pub compressed_end: u64,
+    /// 청크를 압축한 zstd 사전 (ZSTDDictionary이고 학습에 성공했을 때만, 아니면 비어 있음)
+    pub dictionary: Vec<u8>,
+}
+
+impl CompressionInfo {
+    /// 데이터 파일의 start_offset부터 청크를 쓰기 시작하는 빈 정보
+    pub fn new(compression: CompressionType, chunk_length: u32, start_offset: u64) -> Self {
+        Self {
+            compression,
+            chunk_length,
+            data_length: 0,
+            chunk_offsets: Vec::new(),
+            chunk_checksums: Vec::new(),
+            compressed_end: start_offset,
+            dictionary: Vec::new(),
+        }
+    }
+
+    /// 청크 하나를 압축해 기록할 바이트 반환
+    pub fn append_chunk(&mut self, chunk: &[u8]) -> Result<Vec<u8>> {
+        let compressed = compress_chunk(chunk, self.compression, &self.dictionary)?;
+        self.chunk_offsets.push(self.compressed_end);
+        self.chunk_checksums.push(checksum(&compressed));
+        self.compressed_end += compressed.len() as u64;
+        self.data_length += chunk.len() as u64;
+        Ok(compressed)
+    }
+
+    pub fn chunk_count(&self) -> usize {
+        self.chunk_offsets.len()
+    }
+
+    /// 압축 전 오프셋이 속한 청크 번호
+    pub fn chunk_index(&self, offset: u64) -> usize {
+        (offset / self.chunk_length as u64) as usize
+    }
+
+    /// 청크의 데이터 파일 구간 [start, end)
+    pub fn chunk_range(&self, index: usize) -> (u64, u64) {
+        let end = self.chunk_offsets.get(index + 1).copied().unwrap_or(self.compressed_end);
+        (self.chunk_offsets[index], end)
+    }
+
+    /// 압축된 청크 풀기 (format_version은 데이터 파일 푸터의 형식 버전)
+    pub fn decompress_chunk(&self, data: &[u8], format_version: u32) -> Result<Vec<u8>> {
+        if format_version < LENGTH_PREFIXED_CHUNKS_VERSION {
+            return self.decompress_legacy_chunk(data);
+        }
+        let (length, payload) = data.split_first_chunk::<4>()
+            .map(|(length, payload)| (u32::from_be_bytes(*length) as usize, payload))
+            .ok_or_else(|| CoreDBError::Generic {
+                message: format!("Chunk of {} bytes is too short for its length prefix", data.len()),
+            })?;
+        let chunk = match self.compression {
+            CompressionType::None => payload.to_vec(),
+            CompressionType::LZ4 => lz4_flex::decompress(payload, length)?,
+            CompressionType::Snappy => snap::raw::Decoder::new().decompress_vec(payload)?,
+            CompressionType::ZSTD => zstd::bulk::decompress(payload, length)?,
+            CompressionType::ZSTDDictionary if self.dictionary.is_empty() => zstd::bulk::decompress(payload, length)?,
+            CompressionType::ZSTDDictionary => zstd::bulk::Decompressor::with_dictionary(&self.dictionary)?.decompress(payload, length)?,
+        };
+        if chunk.len() != length {
+            return Err(CoreDBError::Generic {
+                message: format!("Chunk declares {} uncompressed bytes but holds {}", length, chunk.len()),
+            });
+        }
+        Ok(chunk)
+    }
+
+    /// 길이 접두사가 없던 형식의 청크 풀기 (ZSTD는 청크 크기를 상한으로 씀)
+    fn decompress_legacy_chunk(&self, data: &[u8]) -> Result<Vec<u8>> {
+        match self.compression {
+            CompressionType::None => Ok(data.to_vec()),
+            CompressionType::LZ4 => Ok(lz4_flex::decompress_size_prepended(data)?),
+            CompressionType::Snappy => Ok(snap::raw::Decoder::new().decompress_vec(data)?),
+            CompressionType::ZSTD | CompressionType::ZSTDDictionary => Ok(zstd::bulk::decompress(data, self.chunk_length as usize)?),
+        }
+    }
+}
+
+/// 사전이 없던 형식 (버전 13 미만)의 청크 정보
+#[derive(Deserialize)]
+pub(crate) struct LegacyCompressionInfo {
+    compression: CompressionType,
+    chunk_length: u32,
+    data_length: u64,
+    chunk_offsets: Vec<u64>,
+    chunk_checksums: Vec<u32>,
+    compressed_end: u64,
+}
+
+impl From<LegacyCompressionInfo> for CompressionInfo {
+    fn from(legacy: LegacyCompressionInfo) -> Self {
+        Self {
+            compression: legacy.compression,
+            chunk_length: legacy.chunk_length,
+            data_length: legacy.data_length,
+            chunk_offsets: legacy.chunk_offsets,
+            chunk_checksums: legacy.chunk_checksums,
+            compressed_end: legacy.compressed_end,
+            dictionary: Vec::new(),
+        }
+    }
+}
+
+/// SSTable별 zstd 사전 학습 (ZSTDDictionary)
+///
+/// 처음 DICTIONARY_TRAINING_BYTES 동안 텍스트 셀 값을 컬럼별로 모으고, 고유 값 비율이 낮은
+/// (같은 문자열이 반복되는) 컬럼의 값만 표본으로 사전을 학습함
+#[derive(Debug, Default)]
+pub struct DictionaryTrainer {
+    columns: HashMap<String, ColumnSamples>,
+}
+
+#[derive(Debug, Default)]
+struct ColumnSamples {
+    values: Vec<u8>,
+    sizes: Vec<usize>,
+    distinct: HashSet<u64>,
+}
+
+impl DictionaryTrainer {
+    /// 텍스트 셀 값 표본 추가 (다른 타입과 툼스톤은 무시)
+    pub fn add(&mut self, column: &str, cell: &Cell) {
+        let CassandraValue::Text(text) = &cell.value else { return };
+        if cell.is_deleted || text.is_empty() {
+            return;
+        }
+        if !self.columns.contains_key(column) {
+            self.columns.insert(column.to_string(), ColumnSamples::default());
+        }
+        let samples = self.columns.get_mut(column).expect("inserted above");
+        samples.values.extend_from_slice(text.as_bytes());
+        samples.sizes.push(text.len());
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        std::hash::Hash::hash(text, &mut hasher);
+        samples.distinct.insert(std::hash::Hasher::finish(&hasher));
+    }
+
+    /// 사전 학습 (쓸 만한 컬럼이 없거나 학습에 실패하면 빈 사전)
+    pub fn train(self) -> Vec<u8> {
+        let mut values = Vec::new();
+        let mut sizes = Vec::new();
+        let mut columns: Vec<_> = self.columns.into_iter()
+            .filter(|(_, samples)| {
+                samples.sizes.len() >= MIN_COLUMN_SAMPLES
+                    && (samples.distinct.len() as f64) <= samples.sizes.len() as f64 * LOW_CARDINALITY_RATIO
+            })
+            .collect();
+        columns.sort_by(|a, b| a.0.cmp(&b.0));
+        for (_, samples) in &columns {
+            values.extend_from_slice(&samples.values);
+            sizes.extend_from_slice(&samples.sizes);
+        }
+        if sizes.is_empty() {
+            return Vec::new();
+        }
+        match zstd::dict::from_continuous(&values, &sizes, MAX_DICTIONARY_SIZE) {
+            Ok(dictionary) => dictionary,
+            Err(e) => {
+                tracing::debug!("Skipping zstd dictionary for {} samples: {}", sizes.len(), e);
+                Vec::new()
+            },
+        }
+    }
+}
+
+/// 청크 압축 (앞에 압축 전 길이를 붙여 어떤 코덱이든 정확한 크기로 풀 수 있게 함)
+fn compress_chunk(data: &[u8], compression: CompressionType, dictionary: &[u8]) -> Result<Vec<u8>> {
+    let length = u32::try_from(data.len()).map_err(|_| CoreDBError::Generic {
+        message: format!("Chunk of {} bytes exceeds the maximum chunk length", data.len()),
+    })?;
+    let mut chunk = length.to_be_bytes().to_vec();
+    match compression {
+        CompressionType::None => chunk.extend_from_slice(data),
+        CompressionType::LZ4 => chunk.extend(lz4_flex::compress(data)),
+        CompressionType::Snappy => chunk.extend(snap::raw::Encoder::new().compress_vec(data)?),
+        CompressionType::ZSTD => chunk.extend(zstd::bulk::compress(data, 3)?),
+        CompressionType::ZSTDDictionary if dictionary.is_empty() => chunk.extend(zstd::bulk::compress(data, 3)?),
+        CompressionType::ZSTDDictionary => chunk.extend(zstd::bulk::Compressor::with_dictionary(3, dictionary)?.compress(data)?),
+    }
+    Ok(chunk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_map_uncompressed_offsets_to_file_ranges() {
+        let mut info = CompressionInfo::new(CompressionType::LZ4, 16, 100);
+        let data: Vec<u8> = (0..40u8).map(|i| i % 4).collect();
+        let mut file = Vec::new();
+        for chunk in data.chunks(16) {
+            file.extend(info.append_chunk(chunk).unwrap());
+        }
+
+        assert_eq!(info.chunk_count(), 3);
+        assert_eq!(info.data_length, 40);
+        assert_eq!(info.compressed_end, 100 + file.len() as u64);
+        assert_eq!(info.chunk_index(15), 0);
+        assert_eq!(info.chunk_index(16), 1);
+        assert_eq!(info.chunk_index(39), 2);
+
+        // 각 청크는 따로 풀 수 있음
+        let (start, end) = info.chunk_range(1);
+        let chunk = info.decompress_chunk(&file[(start - 100) as usize..(end - 100) as usize], LENGTH_PREFIXED_CHUNKS_VERSION).unwrap();
+        assert_eq!(chunk, &data[16..32]);
+        let (start, end) = info.chunk_range(2);
+        assert_eq!(end, info.compressed_end);
+        let chunk = info.decompress_chunk(&file[(start - 100) as usize..], LENGTH_PREFIXED_CHUNKS_VERSION).unwrap();
+        assert_eq!(chunk, &data[32..]);
+    }
+
+    #[test]
+    fn test_chunks_record_uncompressed_length_for_every_codec() {
+        // 1MB보다 큰 청크도 길이 상한 없이 풀림
+        let data: Vec<u8> = (0..3 * 1024 * 1024u32).map(|i| (i % 251) as u8).collect();
+        for compression in [CompressionType::None, CompressionType::LZ4, CompressionType::Snappy, CompressionType::ZSTD] {
+            let mut info = CompressionInfo::new(compression, 4 * 1024 * 1024, 0);
+            let chunk = info.append_chunk(&data).unwrap();
+            assert_eq!(&chunk[..4], &(data.len() as u32).to_be_bytes());
+            assert_eq!(info.decompress_chunk(&chunk, LENGTH_PREFIXED_CHUNKS_VERSION).unwrap(), data);
+
+            // 길이와 내용이 맞지 않으면 오류
+            let mut wrong_length = chunk.clone();
+            wrong_length[..4].copy_from_slice(&1024u32.to_be_bytes());
+            assert!(info.decompress_chunk(&wrong_length, LENGTH_PREFIXED_CHUNKS_VERSION).is_err());
+        }
+
+        // 이전 형식 청크는 코덱 고유 틀로 읽음
+        let legacy = [
+            (CompressionType::None, data[..1000].to_vec()),
+            (CompressionType::LZ4, lz4_flex::compress_prepend_size(&data[..1000])),
+            (CompressionType::Snappy, snap::raw::Encoder::new().compress_vec(&data[..1000]).unwrap()),
+            (CompressionType::ZSTD, zstd::bulk::compress(&data[..1000], 3).unwrap()),
+        ];
+        for (compression, chunk) in legacy {
+            let info = CompressionInfo::new(compression, DEFAULT_CHUNK_LENGTH, 0);
+            assert_eq!(info.decompress_chunk(&chunk, LENGTH_PREFIXED_CHUNKS_VERSION - 1).unwrap(), &data[..1000]);
+        }
+    }
+}
@@ -0,0 +1,375 @@
+use std::alloc::{alloc_zeroed, dealloc, Layout};
+use std::path::Path;
+use std::ptr::NonNull;
+use serde::{Serialize, Deserialize};
+use tokio::fs::File;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use crate::error::*;
+
+/// O_DIRECT 정렬 단위 (버퍼 주소, 길이, 파일 오프셋이 모두 이 배수여야 함)
+pub const DIRECT_IO_ALIGNMENT: usize = 4096;
+/// 직접 쓰기 버퍼 크기
+#[cfg(target_os = "linux")]
+const DIRECT_WRITE_BUFFER_BYTES: usize = 1024 * 1024;
+
+/// SSTable 데이터 파일 쓰기 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DiskWriteMode {
+    /// 페이지 캐시를 거치는 버퍼 쓰기
+    #[default]
+    Buffered,
+    /// O_DIRECT로 페이지 캐시를 우회 (다시 읽지 않을 컴팩션 출력용).
+    /// 리눅스가 아니거나 파일 시스템이 지원하지 않으면 버퍼 쓰기로 대체
+    Direct,
+}
+
+/// 정렬된 고정 크기 버퍼
+pub struct AlignedBuffer {
+    ptr: NonNull<u8>,
+    capacity: usize,
+    len: usize,
+}
+
+// SAFETY: 버퍼를 단독 소유하며 공유 상태가 없음
+unsafe impl Send for AlignedBuffer {}
+
+impl AlignedBuffer {
+    /// capacity를 정렬 단위로 올림한 크기의 0으로 채운 버퍼
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1).next_multiple_of(DIRECT_IO_ALIGNMENT);
+        let layout = Self::layout(capacity);
+        // SAFETY: layout 크기는 0이 아님
+        let ptr = unsafe { alloc_zeroed(layout) };
+        let ptr = NonNull::new(ptr).unwrap_or_else(|| std::alloc::handle_alloc_error(layout));
+        Self { ptr, capacity, len: 0 }
+    }
+
+    fn layout(capacity: usize) -> Layout {
+        Layout::from_size_align(capacity, DIRECT_IO_ALIGNMENT).expect("aligned buffer layout")
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// 남은 공간만큼 복사하고 복사한 길이 반환
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) -> usize {
+        let take = bytes.len().min(self.capacity - self.len);
+        // SAFETY: [len, len + take)는 할당 범위 안이고 bytes와 겹치지 않음
+        unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), self.ptr.as_ptr().add(self.len), take) };
+        self.len += take;
+        take
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: [0, len)은 초기화된 할당 범위
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// 정렬 단위까지 0으로 채운 내용 (마지막 직접 쓰기용)
+    pub fn padded(&mut self) -> &[u8] {
+        let padded_len = self.len.next_multiple_of(DIRECT_IO_ALIGNMENT);
+        // SAFETY: padded_len <= capacity (capacity는 정렬 단위의 배수)
+        unsafe {
+            std::ptr::write_bytes(self.ptr.as_ptr().add(self.len), 0, padded_len - self.len);
+            std::slice::from_raw_parts(self.ptr.as_ptr(), padded_len)
+        }
+    }
+}
+
+impl Drop for AlignedBuffer {
+    fn drop(&mut self) {
+        // SAFETY: new에서 같은 layout으로 할당
+        unsafe { dealloc(self.ptr.as_ptr(), Self::layout(self.capacity)) };
+    }
+}
+
+/// SSTable 데이터 파일 작성기 (쓰기 방식별)
+pub(crate) enum DataFileWriter {
+    Buffered(BufWriter<File>),
+    #[cfg(target_os = "linux")]
+    Direct(DirectFileWriter),
+}
+
+impl DataFileWriter {
+    pub(crate) async fn create(path: &Path, mode: DiskWriteMode) -> Result<Self> {
+        #[cfg(target_os = "linux")]
+        if mode == DiskWriteMode::Direct {
+            match DirectFileWriter::create(path).await {
+                Ok(writer) => return Ok(Self::Direct(writer)),
+                // tmpfs 같은 파일 시스템은 O_DIRECT 열기를 EINVAL로 거부
+                Err(e) => tracing::debug!("O_DIRECT unavailable for {}, using buffered writes: {}", path.display(), e),
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        let _ = mode;
+        Ok(Self::Buffered(BufWriter::new(File::create(path).await?)))
+    }
+
+    pub(crate) async fn write_all(&mut self, bytes: &[u8]) -> Result<()> {
+        match self {
+            Self::Buffered(file) => Ok(file.write_all(bytes).await?),
+            #[cfg(target_os = "linux")]
+            Self::Direct(file) => file.write_all(bytes).await,
+        }
+    }
+
+    /// 남은 데이터를 쓰고 디스크에 동기화
+    pub(crate) async fn finish(self) -> Result<()> {
+        match self {
+            Self::Buffered(mut file) => {
+                file.flush().await?;
+                file.get_ref().sync_all().await?;
+                Ok(())
+            },
+            #[cfg(target_os = "linux")]
+            Self::Direct(file) => file.finish().await,
+        }
+    }
+}
+
+/// O_DIRECT 파일 작성기
+///
+/// 정렬 버퍼가 찰 때마다 블로킹 스레드에서 통째로 쓰고, 마지막 조각은 정렬 단위까지
+/// 0으로 채워 쓴 뒤 파일을 실제 길이로 자름
+#[cfg(target_os = "linux")]
+pub(crate) struct DirectFileWriter {
+    /// 쓰기 중에는 블로킹 작업으로 넘겨 비어 있음
+    file: Option<std::fs::File>,
+    buffer: Option<AlignedBuffer>,
+    /// 파일에 쓴 길이 (항상 정렬 단위의 배수)
+    written: u64,
+}
+
+#[cfg(target_os = "linux")]
+impl DirectFileWriter {
+    async fn create(path: &Path) -> Result<Self> {
+        use std::os::unix::fs::OpenOptionsExt;
+        let path = path.to_path_buf();
+        let file = tokio::task::spawn_blocking(move || {
+            std::fs::OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .custom_flags(libc::O_DIRECT)
+                .open(path)
+        }).await.map_err(join_error)??;
+        Ok(Self { file: Some(file), buffer: Some(AlignedBuffer::new(DIRECT_WRITE_BUFFER_BYTES)), written: 0 })
+    }
+
+    async fn write_all(&mut self, mut bytes: &[u8]) -> Result<()> {
+        while !bytes.is_empty() {
+            let buffer = self.buffer.as_mut().expect("buffer returned after each write");
+            let taken = buffer.extend_from_slice(bytes);
+            bytes = &bytes[taken..];
+            if buffer.is_full() {
+                self.write_buffer(false).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 버퍼 내용을 쓰기 (last면 정렬 단위까지 채워 씀)
+    async fn write_buffer(&mut self, last: bool) -> Result<()> {
+        let file = self.file.take().expect("file returned after each write");
+        let mut buffer = self.buffer.take().expect("buffer returned after each write");
+        let offset = self.written;
+        let (file, mut buffer) = tokio::task::spawn_blocking(move || {
+            let result = if last { write_direct(&file, buffer.padded(), offset) } else { write_direct(&file, buffer.as_slice(), offset) };
+            result.map(|_| (file, buffer))
+        }).await.map_err(join_error)??;
+        self.written += buffer.len().next_multiple_of(DIRECT_IO_ALIGNMENT) as u64;
+        buffer.clear();
+        self.file = Some(file);
+        self.buffer = Some(buffer);
+        Ok(())
+    }
+
+    async fn finish(mut self) -> Result<()> {
+        let tail = self.buffer.as_ref().map_or(0, |buffer| buffer.len()) as u64;
+        let length = self.written + tail;
+        if tail > 0 {
+            self.write_buffer(true).await?;
+        }
+        let file = self.file.take().expect("file returned after each write");
+        tokio::task::spawn_blocking(move || {
+            file.set_len(length)?;
+            file.sync_all()
+        }).await.map_err(join_error)??;
+        Ok(())
+    }
+}
+
+/// offset에 bytes를 씀
+///
+/// 열 때는 O_DIRECT를 받아들이고 쓸 때 정렬 요구를 EINVAL로 거부하는 파일 시스템이 있어,
+/// 그러면 파일의 O_DIRECT를 끄고 같은 위치에 버퍼 쓰기로 다시 씀 (이후 쓰기도 버퍼 쓰기)
+#[cfg(target_os = "linux")]
+fn write_direct(file: &std::fs::File, bytes: &[u8], offset: u64) -> std::io::Result<()> {
+    use std::os::unix::fs::FileExt;
+    use std::os::unix::io::AsRawFd;
+    match file.write_all_at(bytes, offset) {
+        Err(e) if e.raw_os_error() == Some(libc::EINVAL) => {
+            let fd = file.as_raw_fd();
+            // SAFETY: file이 살아 있는 동안 유효한 fd의 상태 플래그만 바꿈
+            let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+            if flags < 0 || flags & libc::O_DIRECT == 0
+                || unsafe { libc::fcntl(fd, libc::F_SETFL, flags & !libc::O_DIRECT) } < 0 {
+                return Err(e);
+            }
+            tracing::warn!("O_DIRECT write rejected ({}), falling back to buffered writes", e);
+            file.write_all_at(bytes, offset)
+        },
+        result => result,
+    }
+}
+
+/// io_uring 파일 리더
+///
+/// 블로킹 스레드마다 링을 하나씩 만들어 재사용하고, 링을 만들 수 없는 커널에서는 pread로 읽음
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+pub struct UringReader {
+    file: std::sync::Arc<std::fs::File>,
+}
+
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+impl UringReader {
+    pub fn new(file: std::fs::File) -> Self {
+        Self { file: std::sync::Arc::new(file) }
+    }
+
+    /// [start, end) 구간 읽기
+    pub async fn read_range(&self, start: u64, end: u64) -> Result<Vec<u8>> {
+        let file = self.file.clone();
+        tokio::task::spawn_blocking(move || Self::read_blocking(&file, start, end)).await.map_err(join_error)?
+    }
+
+    fn read_blocking(file: &std::fs::File, start: u64, end: u64) -> Result<Vec<u8>> {
+        use std::cell::RefCell;
+        use std::os::unix::fs::FileExt;
+        use std::os::unix::io::AsRawFd;
+
+        thread_local! {
+            static RING: RefCell<Option<io_uring::IoUring>> = RefCell::new(io_uring::IoUring::new(8).ok());
+        }
+        let mut data = vec![0u8; (end - start) as usize];
+        let read = RING.with(|ring| -> Option<std::io::Result<()>> {
+            let mut ring = ring.borrow_mut();
+            let ring = ring.as_mut()?;
+            let mut filled = 0;
+            while filled < data.len() {
+                let remaining = &mut data[filled..];
+                let entry = io_uring::opcode::Read::new(
+                    io_uring::types::Fd(file.as_raw_fd()),
+                    remaining.as_mut_ptr(),
+                    remaining.len().min(u32::MAX as usize) as u32,
+                )
+                .offset(start + filled as u64)
+                .build();
+                // SAFETY: 버퍼는 완료를 기다리는 동안 살아 있고 다른 곳에서 접근하지 않음
+                if let Err(e) = unsafe { ring.submission().push(&entry) } {
+                    return Some(Err(std::io::Error::other(e)));
+                }
+                if let Err(e) = ring.submit_and_wait(1) {
+                    return Some(Err(e));
+                }
+                let result = ring.completion().next().expect("completion after submit_and_wait").result();
+                match result {
+                    0 => return Some(Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "read past end of file"))),
+                    n if n < 0 => return Some(Err(std::io::Error::from_raw_os_error(-n))),
+                    n => filled += n as usize,
+                }
+            }
+            Some(Ok(()))
+        });
+        match read {
+            Some(result) => result?,
+            None => file.read_exact_at(&mut data, start)?,
+        }
+        Ok(data)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn join_error(e: tokio::task::JoinError) -> CoreDBError {
+    CoreDBError::Generic { message: format!("Blocking I/O task failed: {}", e) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aligned_buffer_pads_to_alignment() {
+        let mut buffer = AlignedBuffer::new(100);
+        assert_eq!(buffer.capacity(), DIRECT_IO_ALIGNMENT);
+        assert_eq!(buffer.as_slice().as_ptr() as usize % DIRECT_IO_ALIGNMENT, 0);
+
+        let data = vec![7u8; DIRECT_IO_ALIGNMENT + 10];
+        assert_eq!(buffer.extend_from_slice(&data), DIRECT_IO_ALIGNMENT);
+        assert!(buffer.is_full());
+        buffer.clear();
+        assert_eq!(buffer.extend_from_slice(&data[..10]), 10);
+        let padded = buffer.padded();
+        assert_eq!(padded.len(), DIRECT_IO_ALIGNMENT);
+        assert!(padded[..10].iter().all(|byte| *byte == 7));
+        assert!(padded[10..].iter().all(|byte| *byte == 0));
+    }
+
+    #[tokio::test]
+    async fn test_direct_and_buffered_writers_produce_same_file() {
+        let dir = std::env::temp_dir().join("coredb_test_direct_io");
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let data: Vec<u8> = (0..3 * 1024 * 1024 + 123).map(|i| (i % 251) as u8).collect();
+
+        for mode in [DiskWriteMode::Buffered, DiskWriteMode::Direct] {
+            let path = dir.join(format!("{:?}.db", mode));
+            let mut writer = DataFileWriter::create(&path, mode).await.unwrap();
+            for piece in data.chunks(70_000) {
+                writer.write_all(piece).await.unwrap();
+            }
+            writer.finish().await.unwrap();
+            assert_eq!(tokio::fs::read(&path).await.unwrap(), data);
+        }
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_direct_write_falls_back_when_alignment_is_rejected() {
+        use std::os::unix::fs::OpenOptionsExt;
+        let dir = std::env::temp_dir().join("coredb_test_direct_io_fallback");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("misaligned.db");
+
+        // O_DIRECT로 열 수 없는 파일 시스템에서는 확인할 것이 없음
+        if let Ok(file) = std::fs::OpenOptions::new().write(true).create(true).truncate(true).custom_flags(libc::O_DIRECT).open(&path) {
+            // 정렬되지 않은 길이는 O_DIRECT 쓰기가 거부하므로 버퍼 쓰기로 남아야 함
+            let data: Vec<u8> = (0..100).collect();
+            write_direct(&file, &data[1..], 0).unwrap();
+            drop(file);
+            assert_eq!(std::fs::read(&path).unwrap(), &data[1..]);
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,127 @@
+use std::borrow::Cow;
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, SeekFrom};
+use crate::error::*;
+
+/// SSTable 파일 읽기 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DiskAccessMode {
+    /// tokio File로 구간마다 seek + read
+    #[default]
+    Standard,
+    /// 파일 전체를 메모리 매핑하고 구간은 슬라이스로 참조 (읽기마다 시스템 콜 없음)
+    Mmap,
+    /// io_uring으로 구간 읽기 (io-uring 기능을 켠 리눅스 빌드에서만, 아니면 Standard로 읽음)
+    IoUring,
+}
+
+/// 읽기 패턴 (메모리 매핑일 때 madvise 힌트로 전달)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessPattern {
+    /// 포인트 조회: 미리 읽기를 끔
+    Random,
+    /// 컴팩션/전체 스캔: 적극적으로 미리 읽음
+    Sequential,
+}
+
+#[cfg(unix)]
+impl AccessPattern {
+    fn advice(self) -> memmap2::Advice {
+        match self {
+            AccessPattern::Random => memmap2::Advice::Random,
+            AccessPattern::Sequential => memmap2::Advice::Sequential,
+        }
+    }
+}
+
+/// SSTable 구성 파일 리더
+pub enum ComponentReader {
+    File(File),
+    Mmap(memmap2::Mmap),
+    #[cfg(all(feature = "io-uring", target_os = "linux"))]
+    Uring(crate::storage::UringReader),
+}
+
+impl ComponentReader {
+    pub async fn open(path: &Path, mode: DiskAccessMode, pattern: AccessPattern) -> Result<Self> {
+        let file = File::open(path).await?;
+        match mode {
+            DiskAccessMode::Standard => Ok(Self::File(file)),
+            DiskAccessMode::Mmap => {
+                let file = file.into_std().await;
+                // 빈 파일은 매핑할 수 없으므로 일반 파일로 읽음
+                if file.metadata()?.len() == 0 {
+                    return Ok(Self::File(File::from_std(file)));
+                }
+                // SAFETY: SSTable 구성 파일은 쓰기가 끝난 뒤 바뀌지 않고 삭제는 rename/unlink로만 하므로
+                // 매핑된 내용이 도중에 변하지 않음
+                let mmap = unsafe { memmap2::Mmap::map(&file)? };
+                #[cfg(unix)]
+                mmap.advise(pattern.advice())?;
+                #[cfg(not(unix))]
+                let _ = pattern;
+                Ok(Self::Mmap(mmap))
+            },
+            #[cfg(all(feature = "io-uring", target_os = "linux"))]
+            DiskAccessMode::IoUring => Ok(Self::Uring(crate::storage::UringReader::new(file.into_std().await))),
+            #[cfg(not(all(feature = "io-uring", target_os = "linux")))]
+            DiskAccessMode::IoUring => Ok(Self::File(file)),
+        }
+    }
+
+    /// [start, end) 구간 읽기 (메모리 매핑이면 복사 없이 참조)
+    pub async fn read_range(&mut self, start: u64, end: u64) -> Result<Cow<'_, [u8]>> {
+        match self {
+            Self::File(file) => {
+                file.seek(SeekFrom::Start(start)).await?;
+                let mut data = vec![0u8; (end - start) as usize];
+                file.read_exact(&mut data).await?;
+                Ok(Cow::Owned(data))
+            },
+            Self::Mmap(mmap) => mmap.get(start as usize..end as usize)
+                .map(Cow::Borrowed)
+                .ok_or_else(|| std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    format!("range {}..{} beyond mapped length {}", start, end, mmap.len()),
+                ).into()),
+            #[cfg(all(feature = "io-uring", target_os = "linux"))]
+            Self::Uring(reader) => Ok(Cow::Owned(reader.read_range(start, end).await?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mmap_and_file_readers_return_same_ranges() {
+        let dir = std::env::temp_dir().join("coredb_test_file_reader");
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join("component.db");
+        let data: Vec<u8> = (0..=255u8).collect();
+        tokio::fs::write(&path, &data).await.unwrap();
+
+        for pattern in [AccessPattern::Random, AccessPattern::Sequential] {
+            let mut file = ComponentReader::open(&path, DiskAccessMode::Standard, pattern).await.unwrap();
+            let mut mmap = ComponentReader::open(&path, DiskAccessMode::Mmap, pattern).await.unwrap();
+            let mut uring = ComponentReader::open(&path, DiskAccessMode::IoUring, pattern).await.unwrap();
+            assert!(matches!(mmap, ComponentReader::Mmap(_)));
+            assert_eq!(file.read_range(10, 20).await.unwrap(), &data[10..20]);
+            assert_eq!(mmap.read_range(10, 20).await.unwrap(), &data[10..20]);
+            assert_eq!(uring.read_range(10, 20).await.unwrap(), &data[10..20]);
+            assert!(mmap.read_range(250, 300).await.is_err());
+            assert!(uring.read_range(250, 300).await.is_err());
+        }
+
+        // 빈 파일은 일반 파일 리더로 대체
+        let empty = dir.join("empty.db");
+        tokio::fs::write(&empty, b"").await.unwrap();
+        let reader = ComponentReader::open(&empty, DiskAccessMode::Mmap, AccessPattern::Random).await.unwrap();
+        assert!(matches!(reader, ComponentReader::File(_)));
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
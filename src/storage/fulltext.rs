@@ -0,0 +1,140 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::Path;
+use tantivy::collector::DocSetCollector;
+use tantivy::directory::MmapDirectory;
+use tantivy::query::{BooleanQuery, Occur, Query, TermQuery};
+use tantivy::schema::{Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions, Value, INDEXED, STORED};
+use tantivy::tokenizer::{LowerCaser, SimpleTokenizer, TextAnalyzer};
+use tantivy::{Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, Term};
+use crate::error::{CoreDBError, Result};
+use super::IndexedKey;
+
+/// 테이블 디렉토리 아래 전문 인덱스 디렉토리 (<table>/fulltext/<index>/)
+pub const FULLTEXT_DIRECTORY: &str = "fulltext";
+
+/// tokenize와 같은 규칙(영숫자 단위, 소문자)으로 나누는 토크나이저 이름
+const TOKENIZER: &str = "coredb";
+
+/// 인덱스 쓰기 메모리 한도 (tantivy 최소값)
+const WRITER_MEMORY_BYTES: usize = 15_000_000;
+
+/// commit 페이로드의 SSTable ID 구분자
+const SSTABLE_SEPARATOR: &str = ",";
+
+/// tantivy 전문 인덱스 (행 키 → 텍스트 문서)
+///
+/// 쓰기는 바로 인덱스 작성기에 쌓이고 commit으로 디스크에 남으며, 검색은 마지막 commit까지만 봄.
+/// commit마다 그때 반영된 SSTable ID를 페이로드로 남겨 다시 열 때 SSTable이 바뀌었는지 확인함
+pub struct FullTextIndex {
+    writer: IndexWriter,
+    reader: IndexReader,
+    key: Field,
+    body: Field,
+    /// 마지막 commit의 SSTable ID (정렬, 한 번도 commit하지 않았으면 None)
+    committed: Option<Vec<String>>,
+}
+
+impl fmt::Debug for FullTextIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FullTextIndex").field("documents", &self.reader.searcher().num_docs()).finish()
+    }
+}
+
+impl FullTextIndex {
+    /// 디렉토리의 인덱스를 열거나 새로 만듦 (디렉토리가 없으면 메모리에만 유지)
+    pub fn open(directory: Option<&Path>) -> Result<Self> {
+        let mut builder = Schema::builder();
+        let key = builder.add_bytes_field("key", INDEXED | STORED);
+        let body = builder.add_text_field("body", TextOptions::default().set_indexing_options(
+            TextFieldIndexing::default().set_tokenizer(TOKENIZER).set_index_option(IndexRecordOption::Basic),
+        ));
+        let schema = builder.build();
+
+        let index = match directory {
+            Some(directory) => {
+                std::fs::create_dir_all(directory)?;
+                Index::open_or_create(MmapDirectory::open(directory).map_err(fulltext_error)?, schema).map_err(fulltext_error)?
+            },
+            None => Index::create_in_ram(schema),
+        };
+        let committed = index.load_metas().map_err(fulltext_error)?.payload
+            .map(|payload| payload.split(SSTABLE_SEPARATOR).filter(|id| !id.is_empty()).map(str::to_string).collect());
+        index.tokenizers().register(TOKENIZER, TextAnalyzer::builder(SimpleTokenizer::default()).filter(LowerCaser).build());
+        let writer = index.writer_with_num_threads(1, WRITER_MEMORY_BYTES).map_err(fulltext_error)?;
+        let reader = index.reader_builder().reload_policy(ReloadPolicy::Manual).try_into().map_err(fulltext_error)?;
+        Ok(Self { writer, reader, key, body, committed })
+    }
+
+    /// 한 번이라도 commit된 인덱스인지
+    pub fn is_committed(&self) -> bool {
+        self.committed.is_some()
+    }
+
+    /// 마지막 commit이 주어진 SSTable들까지 반영한 것인지
+    pub fn covers(&self, sstables: &[String]) -> bool {
+        self.committed.as_deref() == Some(sorted(sstables).as_slice())
+    }
+
+    /// 행의 문서를 교체 (text가 None이면 삭제만)
+    pub fn upsert(&mut self, key: &IndexedKey, text: Option<&str>) -> Result<()> {
+        let key_bytes = bincode::serialize(key)?;
+        self.writer.delete_term(Term::from_field_bytes(self.key, &key_bytes));
+        if let Some(text) = text {
+            let mut document = TantivyDocument::default();
+            document.add_bytes(self.key, key_bytes);
+            document.add_text(self.body, text);
+            self.writer.add_document(document).map_err(fulltext_error)?;
+        }
+        Ok(())
+    }
+
+    /// 모든 문서 삭제 (다음 commit에 반영)
+    pub fn clear(&mut self) -> Result<()> {
+        self.writer.delete_all_documents().map_err(fulltext_error)?;
+        Ok(())
+    }
+
+    /// 쌓인 쓰기를 디스크에 남기고 검색에 반영 (sstables는 지금까지의 쓰기를 모두 담은 SSTable)
+    pub fn commit(&mut self, sstables: &[String]) -> Result<()> {
+        let sstables = sorted(sstables);
+        let mut prepared = self.writer.prepare_commit().map_err(fulltext_error)?;
+        prepared.set_payload(&sstables.join(SSTABLE_SEPARATOR));
+        prepared.commit().map_err(fulltext_error)?;
+        self.reader.reload().map_err(fulltext_error)?;
+        self.committed = Some(sstables);
+        Ok(())
+    }
+
+    /// 모든 토큰을 포함하는 커밋된 문서의 행 키
+    pub fn search(&self, tokens: &[String]) -> Result<BTreeSet<IndexedKey>> {
+        if tokens.is_empty() {
+            return Ok(BTreeSet::new());
+        }
+        let clauses: Vec<(Occur, Box<dyn Query>)> = tokens.iter()
+            .map(|token| {
+                let query: Box<dyn Query> = Box::new(TermQuery::new(Term::from_field_text(self.body, token), IndexRecordOption::Basic));
+                (Occur::Must, query)
+            })
+            .collect();
+        let searcher = self.reader.searcher();
+        let mut keys = BTreeSet::new();
+        for address in searcher.search(&BooleanQuery::new(clauses), &DocSetCollector).map_err(fulltext_error)? {
+            let document: TantivyDocument = searcher.doc(address).map_err(fulltext_error)?;
+            if let Some(bytes) = document.get_first(self.key).and_then(|value| value.as_bytes()) {
+                keys.insert(bincode::deserialize(bytes)?);
+            }
+        }
+        Ok(keys)
+    }
+}
+
+fn sorted(sstables: &[String]) -> Vec<String> {
+    let mut sstables = sstables.to_vec();
+    sstables.sort();
+    sstables
+}
+
+fn fulltext_error(e: impl fmt::Display) -> CoreDBError {
+    CoreDBError::Generic { message: format!("Full-text index error: {}", e) }
+}
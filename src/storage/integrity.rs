@@ -0,0 +1,279 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use crate::error::{CoreDBError, Result};
+use super::{Manifest, ReadOptions, SSTable, DATA_FILE_SUFFIX, MANIFEST_FILE};
+use super::scrub::{data_file_ids, scrub_sstable};
+use super::manifest::MANIFEST_TEMP_FILE;
+
+/// 시작할 때 데이터 디렉토리 검사 방식 (`--fsck`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IntegrityCheckMode {
+    /// 검사하지 않음 (매니페스트에 없는 파일만 조용히 정리)
+    #[default]
+    Off,
+    /// 문제가 하나라도 있으면 시작하지 않음
+    Check,
+    /// 고칠 수 있는 문제는 고치고, 고칠 수 없는 문제가 남으면 시작하지 않음
+    Repair,
+}
+
+/// 검사에서 찾은 문제 종류
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntegrityProblem {
+    /// 매니페스트를 읽을 수 없음 (고칠 수 없음)
+    UnreadableManifest { message: String },
+    /// 매니페스트에 있지만 데이터 파일이 없는 SSTable (고칠 수 없음)
+    MissingSSTable { id: String },
+    /// 헤더나 체크섬 검증에 실패한 SSTable (읽을 수 있는 행만 남겨 다시 쓰고 원본은 격리)
+    CorruptSSTable { id: String, message: String },
+    /// 매니페스트에 없는 SSTable 구성 파일 (중단된 플러시나 컴팩션이 남긴 파일, 삭제)
+    OrphanFile,
+    /// 매니페스트 교체 중에 남은 임시 파일 (삭제)
+    LeftoverTempFile,
+}
+
+impl IntegrityProblem {
+    pub fn is_repairable(&self) -> bool {
+        !matches!(self, Self::UnreadableManifest { .. } | Self::MissingSSTable { .. })
+    }
+}
+
+impl fmt::Display for IntegrityProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnreadableManifest { message } => write!(f, "unreadable manifest: {}", message),
+            Self::MissingSSTable { id } => write!(f, "SSTable {} is listed in the manifest but its data file is missing", id),
+            Self::CorruptSSTable { id, message } => write!(f, "SSTable {} is corrupt: {}", id, message),
+            Self::OrphanFile => write!(f, "file is not in the manifest (left over from an interrupted flush or compaction)"),
+            Self::LeftoverTempFile => write!(f, "temporary manifest left over from an interrupted update"),
+        }
+    }
+}
+
+/// 문제 하나와 수리 결과
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntegrityIssue {
+    pub path: PathBuf,
+    pub problem: IntegrityProblem,
+    /// 수리했으면 한 일 (검사만 했거나 고칠 수 없으면 None)
+    pub repair: Option<String>,
+}
+
+/// 데이터 디렉토리 검사 결과
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IntegrityReport {
+    pub tables_checked: usize,
+    pub sstables_checked: usize,
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityReport {
+    /// 수리되지 않고 남은 문제
+    pub fn unresolved(&self) -> impl Iterator<Item = &IntegrityIssue> {
+        self.issues.iter().filter(|issue| issue.repair.is_none())
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    fn push(&mut self, path: PathBuf, problem: IntegrityProblem, repair: Option<String>) {
+        self.issues.push(IntegrityIssue { path, problem, repair });
+    }
+}
+
+impl fmt::Display for IntegrityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "checked {} tables and {} SSTables: {} issues ({} unresolved)",
+               self.tables_checked, self.sstables_checked, self.issues.len(), self.unresolved().count())?;
+        for issue in &self.issues {
+            let status = match (&issue.repair, issue.problem.is_repairable()) {
+                (Some(_), _) => "repaired",
+                (None, true) => "repairable",
+                (None, false) => "unrepairable",
+            };
+            write!(f, "\n  [{}] {}: {}", status, issue.path.display(), issue.problem)?;
+            if let Some(repair) = &issue.repair {
+                write!(f, " -> {}", repair)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 데이터 디렉토리의 모든 테이블 검사 (repair면 고칠 수 있는 문제를 고침)
+///
+/// 매니페스트와 디스크의 파일을 맞춰 보고, 매니페스트의 SSTable은 모두 열어 전체 파티션을 읽으며
+/// 헤더와 청크 체크섬을 검증함
+pub async fn check_data_directory(data_directory: impl AsRef<Path>, repair: bool) -> Result<IntegrityReport> {
+    let mut report = IntegrityReport::default();
+    for (_, _, directory) in Manifest::find_tables(data_directory).await? {
+        report.tables_checked += 1;
+        check_table(&directory, repair, &mut report).await?;
+    }
+    Ok(report)
+}
+
+async fn check_table(directory: &Path, repair: bool, report: &mut IntegrityReport) -> Result<()> {
+    let temp_path = directory.join(MANIFEST_TEMP_FILE);
+    if tokio::fs::try_exists(&temp_path).await? {
+        let repaired = if repair {
+            tokio::fs::remove_file(&temp_path).await?;
+            Some("deleted".to_string())
+        } else {
+            None
+        };
+        report.push(temp_path, IntegrityProblem::LeftoverTempFile, repaired);
+    }
+
+    let mut manifest = match Manifest::load(directory).await {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            report.push(directory.join(MANIFEST_FILE), IntegrityProblem::UnreadableManifest { message: e.to_string() }, None);
+            return Ok(());
+        },
+    };
+    // 매니페스트가 없는 이전 디렉토리는 데이터 파일을 모두 현재 파일로 봄
+    let ids = match &manifest {
+        Some(manifest) => manifest.sstables.iter().map(|entry| entry.id.clone()).collect(),
+        None => data_file_ids(directory).await?,
+    };
+
+    for id in ids {
+        report.sstables_checked += 1;
+        let path = SSTable::component_path(directory, &id, DATA_FILE_SUFFIX);
+        if !tokio::fs::try_exists(&path).await? {
+            report.push(path, IntegrityProblem::MissingSSTable { id }, None);
+            continue;
+        }
+        if let Err(e) = verify_sstable(&path).await {
+            let repaired = if repair {
+                Some(scrub_sstable(directory, manifest.as_mut(), &id).await?.to_string())
+            } else {
+                None
+            };
+            report.push(path, IntegrityProblem::CorruptSSTable { id, message: e.to_string() }, repaired);
+        }
+    }
+
+    // 스크럽 뒤에 확인해야 원본 구성 파일이 고아로 잡히지 않음
+    if let Some(manifest) = &manifest {
+        for orphan in manifest.orphans().await? {
+            let repaired = if repair {
+                tokio::fs::remove_file(&orphan).await?;
+                Some("deleted".to_string())
+            } else {
+                None
+            };
+            report.push(orphan, IntegrityProblem::OrphanFile, repaired);
+        }
+    }
+    Ok(())
+}
+
+/// SSTable을 열고 모든 파티션을 읽어 봄
+async fn verify_sstable(path: &Path) -> Result<()> {
+    let sstable = Arc::new(SSTable::open(path).await?);
+    let mut scanner = sstable.scanner(ReadOptions::all());
+    let mut partitions = 0;
+    while scanner.next_partition().await?.is_some() {
+        partitions += 1;
+    }
+    if partitions != sstable.partition_count {
+        return Err(CoreDBError::Corruption {
+            file: path.display().to_string(),
+            offset: 0,
+            message: format!("footer lists {} partitions but {} were readable", sstable.partition_count, partitions),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{CassandraDataType, CassandraValue, Cell, Cells, ColumnDefinition, PartitionKey, Row, TableSchema};
+    use crate::storage::{CompressionType, Memtable};
+
+    /// 청크 여러 개에 걸친 SSTable (값마다 2KB)
+    async fn flush_partitions(directory: &Path, count: i32) -> SSTable {
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let schema = Arc::new(TableSchema::new(
+            "users".to_string(),
+            "test_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![],
+            vec![column("name", CassandraDataType::Text)],
+            vec![],
+        ));
+        let memtable = Memtable::new(schema);
+        for id in 0..count {
+            let mut cells = Cells::new();
+            cells.insert("name", Cell { value: CassandraValue::Text("x".repeat(2048)), timestamp: 1, ttl: None, is_deleted: false });
+            memtable.put(Row {
+                partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] },
+                clustering_key: None,
+                cells,
+                timestamp: 1,
+            }).unwrap();
+        }
+        SSTable::create_from_memtable(&memtable, directory, CompressionType::None).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_check_reports_and_repairs_corruption_and_leftovers() {
+        let data_directory = std::env::temp_dir().join("coredb_test_integrity");
+        let _ = tokio::fs::remove_dir_all(&data_directory).await;
+        let directory = data_directory.join("test_ks").join("users");
+        tokio::fs::create_dir_all(&directory).await.unwrap();
+
+        let (mut manifest, _) = Manifest::open_table(&directory).await.unwrap();
+        let healthy = flush_partitions(&directory, 10).await;
+        manifest.record_flush(&healthy, None).await.unwrap();
+        let corrupt = flush_partitions(&directory, 100).await;
+        manifest.record_compaction(&[], std::slice::from_ref(&corrupt), 1).await.unwrap();
+        assert!(check_data_directory(&data_directory, false).await.unwrap().is_clean());
+
+        // 두 번째 청크 손상, 중단된 플러시, 남은 임시 매니페스트
+        let chunk_start = corrupt.compression_info.chunk_offsets[1];
+        let mut data = tokio::fs::read(&corrupt.file_path).await.unwrap();
+        data[chunk_start as usize + 5] ^= 0x01;
+        tokio::fs::write(&corrupt.file_path, data).await.unwrap();
+        let crashed = flush_partitions(&directory, 1).await;
+        tokio::fs::write(directory.join(MANIFEST_TEMP_FILE), b"{").await.unwrap();
+
+        // 검사만 하면 아무것도 바꾸지 않음
+        let report = check_data_directory(&data_directory, false).await.unwrap();
+        assert_eq!((report.tables_checked, report.sstables_checked), (1, 2));
+        assert_eq!(report.unresolved().count(), report.issues.len());
+        assert!(report.issues.iter().any(|issue| issue.problem == IntegrityProblem::LeftoverTempFile));
+        assert!(report.issues.iter().any(|issue| matches!(&issue.problem, IntegrityProblem::CorruptSSTable { id, .. } if *id == corrupt.id)));
+        assert!(report.issues.iter().any(|issue| issue.path == crashed.file_path && issue.problem == IntegrityProblem::OrphanFile));
+        assert!(report.to_string().contains("[repairable]"));
+        assert!(crashed.file_path.exists() && corrupt.file_path.exists());
+
+        // 수리하면 읽을 수 있는 파티션만 같은 레벨로 남기고 원본은 격리, 고아 파일은 삭제
+        let report = check_data_directory(&data_directory, true).await.unwrap();
+        assert_eq!(report.unresolved().count(), 0);
+        assert!(!crashed.file_path.exists() && !corrupt.file_path.exists());
+        assert!(SSTable::component_path(&directory.join(crate::storage::QUARANTINE_DIRECTORY), &corrupt.id, DATA_FILE_SUFFIX).exists());
+        assert!(!directory.join(MANIFEST_TEMP_FILE).exists());
+
+        let (reopened, sstables) = Manifest::open_table(&directory).await.unwrap();
+        assert_eq!(sstables.len(), 2);
+        let scrubbed = sstables.iter().find(|sstable| sstable.id != healthy.id).unwrap();
+        assert!(scrubbed.partition_count > 0 && scrubbed.partition_count < 100);
+        assert_eq!(reopened.level(&scrubbed.id), Some(1));
+        assert!(check_data_directory(&data_directory, false).await.unwrap().is_clean());
+
+        // 데이터 파일이 사라진 SSTable은 고칠 수 없음
+        tokio::fs::remove_file(&healthy.file_path).await.unwrap();
+        let report = check_data_directory(&data_directory, true).await.unwrap();
+        assert_eq!(report.unresolved().count(), 1);
+        assert_eq!(report.issues[0].problem, IntegrityProblem::MissingSSTable { id: healthy.id.clone() });
+        assert!(report.to_string().contains("[unrepairable]"));
+
+        tokio::fs::remove_dir_all(&data_directory).await.unwrap();
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use crate::schema::PartitionKey;
+use crate::storage::RecordPosition;
+
+/// 기본 키 캐시 크기 (항목 수)
+pub const DEFAULT_KEY_CACHE_ENTRIES: usize = 100_000;
+/// 키 캐시 샤드 수 (잠금 경합 분산)
+const KEY_CACHE_SHARDS: usize = 16;
+
+/// (SSTable id, 파티션 키)
+type KeyCacheKey = (String, PartitionKey);
+
+/// 파티션 키 캐시
+///
+/// 최근 읽은 SSTable의 (파티션 키 → 레코드 위치)를 보관해, 자주 읽는 키는
+/// 인덱스 파일을 열지 않고 바로 데이터를 읽음. 샤드마다 최근 사용 순서로 오래된 항목부터 내보냄
+pub struct KeyCache {
+    shards: Vec<Mutex<KeyCacheShard>>,
+    shard_capacity: AtomicUsize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Default)]
+struct KeyCacheShard {
+    /// 키 → (위치, 마지막 사용 순번)
+    entries: HashMap<KeyCacheKey, (RecordPosition, u64)>,
+    /// 마지막 사용 순번 → 키 (가장 작은 것이 가장 오래 안 쓴 항목)
+    recency: BTreeMap<u64, KeyCacheKey>,
+    next_tick: u64,
+}
+
+impl KeyCacheShard {
+    fn touch(&mut self, key: &KeyCacheKey) -> Option<RecordPosition> {
+        let tick = self.next_tick;
+        let (position, last_used) = self.entries.get_mut(key)?;
+        let previous = std::mem::replace(last_used, tick);
+        let position = position.clone();
+        self.next_tick += 1;
+        self.recency.remove(&previous);
+        self.recency.insert(tick, key.clone());
+        Some(position)
+    }
+
+    fn insert(&mut self, key: KeyCacheKey, position: RecordPosition, capacity: usize) {
+        let tick = self.next_tick;
+        self.next_tick += 1;
+        if let Some((_, previous)) = self.entries.insert(key.clone(), (position, tick)) {
+            self.recency.remove(&previous);
+        }
+        self.recency.insert(tick, key);
+        self.evict_to(capacity);
+    }
+
+    /// 오래 안 쓴 항목부터 capacity개만 남김
+    fn evict_to(&mut self, capacity: usize) {
+        while self.entries.len() > capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else { break };
+            self.entries.remove(&oldest);
+        }
+    }
+}
+
+/// 키 캐시 통계
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct KeyCacheStats {
+    pub entries: usize,
+    pub capacity: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl KeyCacheStats {
+    /// 적중률 (조회가 없으면 0.0)
+    pub fn hit_rate(&self) -> f64 {
+        let requests = self.hits + self.misses;
+        if requests == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / requests as f64
+    }
+}
+
+impl KeyCache {
+    /// capacity개 항목까지 보관하는 캐시 (샤드마다 균등 분배)
+    pub fn new(capacity: usize) -> Self {
+        let shard_count = KEY_CACHE_SHARDS.min(capacity.max(1));
+        Self {
+            shards: (0..shard_count).map(|_| Mutex::new(KeyCacheShard::default())).collect(),
+            shard_capacity: AtomicUsize::new(capacity.div_ceil(shard_count)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn get(&self, sstable_id: &str, partition_key: &PartitionKey) -> Option<RecordPosition> {
+        let key = (sstable_id.to_string(), partition_key.clone());
+        let position = self.shard(&key).lock().unwrap().touch(&key);
+        let counter = if position.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        position
+    }
+
+    pub fn insert(&self, sstable_id: &str, partition_key: &PartitionKey, position: RecordPosition) {
+        let key = (sstable_id.to_string(), partition_key.clone());
+        self.shard(&key).lock().unwrap().insert(key, position, self.shard_capacity.load(Ordering::Relaxed));
+    }
+
+    /// 용량 변경 (줄이면 오래 안 쓴 항목부터 바로 버림, 샤드 수는 처음 용량으로 정해진 그대로)
+    pub fn set_capacity(&self, capacity: usize) {
+        let shard_capacity = capacity.div_ceil(self.shards.len());
+        self.shard_capacity.store(shard_capacity, Ordering::Relaxed);
+        for shard in &self.shards {
+            shard.lock().unwrap().evict_to(shard_capacity);
+        }
+    }
+
+    /// 삭제된 SSTable의 항목 제거
+    pub fn invalidate_sstable(&self, sstable_id: &str) {
+        for shard in &self.shards {
+            let mut shard = shard.lock().unwrap();
+            let KeyCacheShard { entries, recency, .. } = &mut *shard;
+            entries.retain(|(id, _), (_, tick)| {
+                let keep = id != sstable_id;
+                if !keep {
+                    recency.remove(tick);
+                }
+                keep
+            });
+        }
+    }
+
+    pub fn stats(&self) -> KeyCacheStats {
+        KeyCacheStats {
+            entries: self.shards.iter().map(|shard| shard.lock().unwrap().entries.len()).sum(),
+            capacity: self.shard_capacity.load(Ordering::Relaxed) * self.shards.len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    fn shard(&self, key: &KeyCacheKey) -> &Mutex<KeyCacheShard> {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        key.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+}
+
+impl std::fmt::Debug for KeyCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyCache").field("stats", &self.stats()).finish()
+    }
+}
+
+/// 같은 캐시 인스턴스일 때만 같음 (SSTable 비교용)
+impl PartialEq for KeyCache {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::CassandraValue;
+
+    fn key(id: i32) -> PartitionKey {
+        PartitionKey { components: vec![CassandraValue::Int(id)] }
+    }
+
+    fn position(offset: u64) -> RecordPosition {
+        RecordPosition { offset, length: 10, clustering_bounds: Default::default() }
+    }
+
+    #[test]
+    fn test_key_cache_evicts_least_recently_used() {
+        // 샤드 하나에 두 항목
+        let cache = KeyCache::new(2);
+        assert_eq!(cache.stats().capacity, 2);
+        let single = KeyCache { shards: vec![Mutex::new(KeyCacheShard::default())], shard_capacity: AtomicUsize::new(2), ..cache };
+
+        single.insert("a", &key(1), position(1));
+        single.insert("a", &key(2), position(2));
+        assert_eq!(single.get("a", &key(1)), Some(position(1)));
+        single.insert("a", &key(3), position(3));
+
+        // 최근에 읽은 1은 남고 2가 밀려남
+        assert_eq!(single.get("a", &key(2)), None);
+        assert_eq!(single.get("a", &key(1)), Some(position(1)));
+        assert_eq!(single.get("b", &key(1)), None);
+
+        // 용량을 줄이면 오래 안 쓴 3이 바로 밀려남
+        single.set_capacity(1);
+        assert_eq!(single.stats().capacity, 1);
+        assert_eq!(single.get("a", &key(3)), None);
+        assert_eq!(single.get("a", &key(1)), Some(position(1)));
+
+        single.invalidate_sstable("a");
+        let stats = single.stats();
+        assert_eq!(stats.entries, 0);
+        assert_eq!((stats.hits, stats.misses), (3, 3));
+        assert_eq!(stats.hit_rate(), 0.5);
+    }
+}
@@ -0,0 +1,372 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use serde::{Serialize, Deserialize};
+use crate::storage::{SSTable, DATA_FILE_SUFFIX, SSTABLE_FORMAT_VERSION};
+use crate::wal::CommitLogPosition;
+use crate::fault_injection::{self, FaultPoint};
+use crate::error::*;
+
+/// 매니페스트 파일 이름 (테이블 디렉토리마다 하나)
+pub const MANIFEST_FILE: &str = "MANIFEST";
+/// 교체 중인 매니페스트 임시 파일
+pub(crate) const MANIFEST_TEMP_FILE: &str = "MANIFEST.tmp";
+
+/// 매니페스트에 기록된 SSTable 정보
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub id: String,
+    /// 컴팩션 레벨 (플러시 결과는 0)
+    pub level: u32,
+    pub size_bytes: u64,
+    pub partition_count: u64,
+    pub min_timestamp: i64,
+    pub max_timestamp: i64,
+}
+
+impl ManifestEntry {
+    pub fn new(sstable: &SSTable, level: u32) -> Self {
+        Self {
+            id: sstable.id.clone(),
+            level,
+            size_bytes: sstable.size_bytes,
+            partition_count: sstable.partition_count,
+            min_timestamp: sstable.min_timestamp,
+            max_timestamp: sstable.max_timestamp,
+        }
+    }
+}
+
+/// 테이블의 현재 SSTable 목록
+///
+/// 플러시/컴팩션마다 임시 파일에 쓴 뒤 rename으로 교체하므로 항상 완전한 목록만 남고,
+/// 시작할 때는 여기에 없는 데이터 파일(중단된 플러시/컴팩션 결과)을 지움
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    #[serde(skip)]
+    directory: PathBuf,
+    /// 교체할 때마다 증가
+    pub generation: u64,
+    /// 현재 SSTable (오래된 것부터)
+    pub sstables: Vec<ManifestEntry>,
+    /// 이 테이블의 커밋 로그 엔트리 중 이 위치 앞의 것은 모두 SSTable에 있음 (시작할 때 여기부터 재생)
+    #[serde(default)]
+    pub replay_position: CommitLogPosition,
+}
+
+impl Manifest {
+    /// 테이블 디렉토리의 매니페스트 읽기 (없으면 None)
+    pub async fn load(directory: impl AsRef<Path>) -> Result<Option<Self>> {
+        let directory = directory.as_ref();
+        let data = match tokio::fs::read(directory.join(MANIFEST_FILE)).await {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let mut manifest: Manifest = serde_json::from_slice(&data)?;
+        manifest.directory = directory.to_path_buf();
+        Ok(Some(manifest))
+    }
+
+    /// 매니페스트를 읽고 현재 SSTable 열기
+    ///
+    /// 매니페스트가 없는 이전 디렉토리는 데이터 파일을 모두 현재 파일로 보고 새로 기록함.
+    /// 매니페스트에 없는 데이터 파일과 남은 임시 파일은 삭제하고, 이전 형식 파일은 현재 형식으로 다시 씀
+    pub async fn open_table(directory: impl AsRef<Path>) -> Result<(Self, Vec<SSTable>)> {
+        let (manifest, sstables, _) = Self::open_and_upgrade(directory.as_ref(), false).await?;
+        Ok((manifest, sstables))
+    }
+
+    /// 테이블 디렉토리의 SSTable을 현재 형식으로 다시 쓰기 (`coredb upgrade-sstables`, 다시 쓴 수 반환)
+    ///
+    /// include_all이면 이미 현재 형식인 SSTable도 다시 씀
+    pub async fn upgrade_table(directory: impl AsRef<Path>, include_all: bool) -> Result<usize> {
+        let (_, _, upgraded) = Self::open_and_upgrade(directory.as_ref(), include_all).await?;
+        Ok(upgraded)
+    }
+
+    /// 데이터 디렉토리의 테이블 디렉토리 (<keyspace>/<table>, 매니페스트나 SSTable이 있는 것만, 이름 순)
+    pub async fn find_tables(data_directory: impl AsRef<Path>) -> Result<Vec<(String, String, PathBuf)>> {
+        let mut tables = Vec::new();
+        for keyspace_dir in list_directories(data_directory.as_ref()).await? {
+            for table_dir in list_directories(&keyspace_dir).await? {
+                let mut entries = tokio::fs::read_dir(&table_dir).await?;
+                let mut is_table = false;
+                while let Some(entry) = entries.next_entry().await? {
+                    let name = entry.file_name();
+                    let name = name.to_string_lossy();
+                    if name == MANIFEST_FILE || name.ends_with(DATA_FILE_SUFFIX) {
+                        is_table = true;
+                        break;
+                    }
+                }
+                if is_table {
+                    let name = |path: &Path| path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+                    tables.push((name(&keyspace_dir), name(&table_dir), table_dir));
+                }
+            }
+        }
+        Ok(tables)
+    }
+
+    async fn open_and_upgrade(directory: &Path, include_all: bool) -> Result<(Self, Vec<SSTable>, usize)> {
+        tokio::fs::create_dir_all(directory).await?;
+
+        let Some(mut manifest) = Self::load(directory).await? else {
+            let sstables = SSTable::discover(directory).await?;
+            let mut manifest = Self { directory: directory.to_path_buf(), generation: 0, sstables: Vec::new(), replay_position: CommitLogPosition::default() };
+            manifest.commit(&[], sstables.iter().map(|sstable| ManifestEntry::new(sstable, 0)).collect()).await?;
+            let (sstables, upgraded) = manifest.upgrade_sstables(sstables, include_all).await?;
+            return Ok((manifest, sstables, upgraded));
+        };
+
+        let mut sstables = Vec::with_capacity(manifest.sstables.len());
+        for entry in &manifest.sstables {
+            sstables.push(SSTable::open(manifest.data_file_path(&entry.id)).await?);
+        }
+        for orphan in manifest.orphans().await? {
+            tracing::warn!("Removing orphan SSTable {}", orphan.display());
+            tokio::fs::remove_file(&orphan).await?;
+        }
+        let _ = tokio::fs::remove_file(directory.join(MANIFEST_TEMP_FILE)).await;
+
+        let (sstables, upgraded) = manifest.upgrade_sstables(sstables, include_all).await?;
+        Ok((manifest, sstables, upgraded))
+    }
+
+    /// 이전 형식 SSTable을 다시 써서 매니페스트에서 교체하고 원본 삭제 (다시 쓴 수도 반환)
+    ///
+    /// 교체 전에 중단되면 새 파일은 다음 시작 때 고아 파일로 정리됨
+    async fn upgrade_sstables(&mut self, sstables: Vec<SSTable>, include_all: bool) -> Result<(Vec<SSTable>, usize)> {
+        let mut upgraded = Vec::with_capacity(sstables.len());
+        let mut rewritten_count = 0;
+        for sstable in sstables {
+            if sstable.format_version >= SSTABLE_FORMAT_VERSION && !include_all {
+                upgraded.push(sstable);
+                continue;
+            }
+            tracing::info!("Upgrading SSTable {} from format version {}", sstable.id, sstable.format_version);
+            let level = self.sstables.iter().find(|entry| entry.id == sstable.id).map_or(0, |entry| entry.level);
+            let sstable = Arc::new(sstable);
+            let rewritten = sstable.rewrite().await?;
+            self.record_compaction(std::slice::from_ref(&sstable.id), std::slice::from_ref(&rewritten), level).await?;
+            sstable.delete().await?;
+            upgraded.push(rewritten);
+            rewritten_count += 1;
+        }
+        Ok((upgraded, rewritten_count))
+    }
+
+    /// 가져온 SSTable로 매니페스트를 새로 씀 (`coredb import`, entries의 순서와 레벨 유지)
+    ///
+    /// SSTable을 모두 열어 크기와 시각을 다시 읽음. 다른 노드의 커밋 로그 위치는 쓸 수 없어 재생 위치는 replay_position으로 둠
+    pub async fn rebuild(directory: &Path, entries: &[ManifestEntry], replay_position: CommitLogPosition) -> Result<Self> {
+        let mut added = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let sstable = SSTable::open(SSTable::component_path(directory, &entry.id, DATA_FILE_SUFFIX)).await?;
+            added.push(ManifestEntry::new(&sstable, entry.level));
+        }
+        let mut manifest = Self { directory: directory.to_path_buf(), generation: 0, sstables: Vec::new(), replay_position: CommitLogPosition::default() };
+        manifest.commit_with(&[], added, replay_position).await?;
+        Ok(manifest)
+    }
+
+    /// 플러시 결과 추가 (flushed_until이 있으면 그 앞의 커밋 로그는 더 재생하지 않음)
+    pub async fn record_flush(&mut self, sstable: &SSTable, flushed_until: Option<CommitLogPosition>) -> Result<()> {
+        let replay_position = self.replay_position.max(flushed_until.unwrap_or_default());
+        self.commit_with(&[], vec![ManifestEntry::new(sstable, 0)], replay_position).await
+    }
+
+    /// SSTable은 그대로 두고 재생 위치만 옮김 (시점 복구 뒤 복구 시각 이후의 로컬 커밋 로그를 재생하지 않도록)
+    pub async fn advance_replay_position(&mut self, position: CommitLogPosition) -> Result<()> {
+        let replay_position = self.replay_position.max(position);
+        self.commit_with(&[], Vec::new(), replay_position).await
+    }
+
+    /// 컴팩션 결과 반영 (입력 제거와 출력 추가를 한 번에)
+    pub async fn record_compaction(&mut self, inputs: &[String], outputs: &[SSTable], level: u32) -> Result<()> {
+        let added = outputs.iter().map(|sstable| ManifestEntry::new(sstable, level)).collect();
+        self.commit(inputs, added).await
+    }
+
+    /// 모든 SSTable을 레벨 0으로 되돌림 (컴팩션 전략을 바꿔 새 전략이 처음부터 다시 배치할 때)
+    pub async fn reset_levels(&mut self) -> Result<()> {
+        let ids: Vec<String> = self.sstables.iter().map(|entry| entry.id.clone()).collect();
+        let entries = self.sstables.iter().map(|entry| ManifestEntry { level: 0, ..entry.clone() }).collect();
+        self.commit(&ids, entries).await
+    }
+
+    /// SSTable의 컴팩션 레벨
+    pub fn level(&self, id: &str) -> Option<u32> {
+        self.sstables.iter().find(|entry| entry.id == id).map(|entry| entry.level)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.sstables.iter().any(|entry| entry.id == id)
+    }
+
+    pub fn data_file_path(&self, id: &str) -> PathBuf {
+        SSTable::component_path(&self.directory, id, DATA_FILE_SUFFIX)
+    }
+
+    /// 같은 내용을 다른 디렉토리에 씀 (스냅샷, 이 매니페스트의 디렉토리는 그대로)
+    pub async fn write_to(&self, directory: &Path) -> Result<()> {
+        let temp_path = directory.join(MANIFEST_TEMP_FILE);
+        tokio::fs::write(&temp_path, serde_json::to_vec_pretty(self)?).await?;
+        tokio::fs::rename(&temp_path, directory.join(MANIFEST_FILE)).await?;
+        Ok(())
+    }
+
+    /// 매니페스트에 없는 SSTable 구성 파일
+    pub async fn orphans(&self) -> Result<Vec<PathBuf>> {
+        let mut entries = tokio::fs::read_dir(&self.directory).await?;
+        let mut orphans = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let id = path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(SSTable::component_owner);
+            if id.is_some_and(|id| !self.contains(id)) {
+                orphans.push(path);
+            }
+        }
+        orphans.sort();
+        Ok(orphans)
+    }
+
+    /// 변경된 목록을 임시 파일에 쓰고 rename으로 교체 (실패하면 메모리 상태도 그대로)
+    async fn commit(&mut self, removed: &[String], added: Vec<ManifestEntry>) -> Result<()> {
+        self.commit_with(removed, added, self.replay_position).await
+    }
+
+    async fn commit_with(&mut self, removed: &[String], added: Vec<ManifestEntry>, replay_position: CommitLogPosition) -> Result<()> {
+        let mut next = self.clone();
+        next.generation += 1;
+        next.replay_position = replay_position;
+        next.sstables.retain(|entry| !removed.contains(&entry.id));
+        next.sstables.extend(added);
+
+        let temp_path = self.directory.join(MANIFEST_TEMP_FILE);
+        let data = serde_json::to_vec_pretty(&next)?;
+        {
+            let mut file = tokio::fs::File::create(&temp_path).await?;
+            tokio::io::AsyncWriteExt::write_all(&mut file, &data).await?;
+            fault_injection::sync_file(FaultPoint::ManifestCommit, &temp_path)?;
+            file.sync_all().await?;
+        }
+        tokio::fs::rename(&temp_path, self.directory.join(MANIFEST_FILE)).await?;
+
+        *self = next;
+        Ok(())
+    }
+}
+
+/// 하위 디렉토리 (이름 순, 없으면 빈 목록)
+async fn list_directories(directory: &Path) -> Result<Vec<PathBuf>> {
+    let mut entries = match tokio::fs::read_dir(directory).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut directories = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.file_type().await?.is_dir() {
+            directories.push(entry.path());
+        }
+    }
+    directories.sort();
+    Ok(directories)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::schema::{CassandraDataType, CassandraValue, Cell, Cells, ColumnDefinition, PartitionKey, Row, TableSchema};
+    use crate::storage::{Memtable, CompressionType};
+
+    async fn flush_one(directory: &Path, id: i32) -> SSTable {
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let schema = Arc::new(TableSchema::new(
+            "users".to_string(),
+            "test_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![],
+            vec![column("name", CassandraDataType::Text)],
+            vec![],
+        ));
+        let memtable = Memtable::new(schema);
+        let mut cells = Cells::new();
+        cells.insert("name", Cell { value: CassandraValue::Text("a".to_string()), timestamp: 1, ttl: None, is_deleted: false });
+        memtable.put(Row {
+            partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] },
+            clustering_key: None,
+            cells,
+            timestamp: 1,
+        }).unwrap();
+        SSTable::create_from_memtable(&memtable, directory, CompressionType::None).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_manifest_tracks_live_sstables_and_removes_orphans() {
+        let directory = std::env::temp_dir().join("coredb_test_manifest");
+        let _ = tokio::fs::remove_dir_all(&directory).await;
+        tokio::fs::create_dir_all(&directory).await.unwrap();
+
+        // 매니페스트 없는 디렉토리는 기존 파일을 모두 등록
+        let legacy = flush_one(&directory, 1).await;
+        let (mut manifest, sstables) = Manifest::open_table(&directory).await.unwrap();
+        assert_eq!(sstables, vec![legacy.clone()]);
+        assert_eq!(manifest.generation, 1);
+
+        let flushed = flush_one(&directory, 2).await;
+        let flushed_until = CommitLogPosition { segment_id: 2, offset: 40 };
+        manifest.record_flush(&flushed, Some(flushed_until)).await.unwrap();
+        let compacted = flush_one(&directory, 3).await;
+        manifest.record_compaction(&[legacy.id.clone(), flushed.id.clone()], std::slice::from_ref(&compacted), 1).await.unwrap();
+        assert_eq!(manifest.generation, 3);
+
+        // 매니페스트에 기록되기 전에 중단된 파일과 컴팩션 입력은 다시 열 때 삭제
+        let crashed = flush_one(&directory, 4).await;
+        tokio::fs::write(directory.join(MANIFEST_TEMP_FILE), b"{").await.unwrap();
+
+        let (reopened, sstables) = Manifest::open_table(&directory).await.unwrap();
+        assert_eq!(reopened, manifest);
+        assert_eq!(reopened.sstables[0].level, 1);
+        // 컴팩션은 재생 위치를 바꾸지 않음
+        assert_eq!(reopened.replay_position, flushed_until);
+        assert_eq!(sstables, vec![compacted]);
+        for removed in [&legacy, &flushed, &crashed] {
+            assert!(!removed.file_path.exists());
+        }
+        assert!(!directory.join(MANIFEST_TEMP_FILE).exists());
+
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_upgrade_table_rewrites_sstables_on_request() {
+        let data_directory = std::env::temp_dir().join("coredb_test_manifest_upgrade");
+        let _ = tokio::fs::remove_dir_all(&data_directory).await;
+        let directory = data_directory.join("test_ks").join("users");
+        tokio::fs::create_dir_all(&directory).await.unwrap();
+        tokio::fs::create_dir_all(data_directory.join("test_ks").join("empty")).await.unwrap();
+
+        let first = flush_one(&directory, 1).await;
+        let second = flush_one(&directory, 2).await;
+        let tables = Manifest::find_tables(&data_directory).await.unwrap();
+        assert_eq!(tables, vec![("test_ks".to_string(), "users".to_string(), directory.clone())]);
+
+        // 현재 형식만 있으면 그대로 두고, include_all이면 모두 다시 씀
+        assert_eq!(Manifest::upgrade_table(&directory, false).await.unwrap(), 0);
+        assert!(first.file_path.exists() && second.file_path.exists());
+        assert_eq!(Manifest::upgrade_table(&directory, true).await.unwrap(), 2);
+        assert!(!first.file_path.exists() && !second.file_path.exists());
+
+        let (manifest, sstables) = Manifest::open_table(&directory).await.unwrap();
+        assert_eq!(sstables.len(), 2);
+        assert!(sstables.iter().all(|sstable| sstable.format_version == SSTABLE_FORMAT_VERSION && manifest.contains(&sstable.id)));
+        assert_eq!(sstables.iter().map(|sstable| sstable.partition_count).sum::<u64>(), 2);
+
+        tokio::fs::remove_dir_all(&data_directory).await.unwrap();
+    }
+}
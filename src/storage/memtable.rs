@@ -1,12 +1,15 @@
 use crossbeam_skiplist::SkipMap;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::collections::HashMap;
-use crate::schema::{PartitionKey, ClusteringKey, Row, TableSchema};
+use std::hash::{Hash, Hasher};
+use crate::schema::{CassandraValue, ColumnLayout, PartitionKey, ClusteringKey, Row, TableSchema};
+use crate::storage::{ReadOptions, ScanDirection};
+use crate::storage::arena::{ArenaRef, ColumnInterner, RowArena, encode_row, decode_row};
 use crate::error::*;
 
 /// 메모리 테이블의 파티션
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Partition {
     /// 클러스터링 키로 정렬된 행들
     pub rows: SkipMap<Option<ClusteringKey>, Row>,
@@ -21,170 +24,504 @@ impl Partition {
             static_columns: HashMap::new(),
         }
     }
+
+    /// 같은 클러스터링 키의 기존 행과 셀 단위로 병합하여 저장 (&mut라 병합 중에 다른 쓰기가 끼어들 수 없음)
+    pub fn merge_row(&mut self, row: Row) -> Row {
+        let merged = match self.rows.get(&row.clustering_key) {
+            Some(existing) => {
+                let mut merged = existing.value().clone();
+                merged.merge(row);
+                merged
+            },
+            None => row,
+        };
+        self.rows.insert(merged.clustering_key.clone(), merged.clone());
+        merged
+    }
+
+    /// 다른 파티션 버전을 병합 (컴팩션/읽기 경로 공용)
+    pub fn merge(&mut self, other: Partition) {
+        for (column, cell) in other.static_columns {
+            let merged = match self.static_columns.remove(&column) {
+                Some(existing) => crate::schema::Cell::reconcile(existing, cell),
+                None => cell,
+            };
+            self.static_columns.insert(column, merged);
+        }
+        for row_entry in other.rows.iter() {
+            self.merge_row(row_entry.value().clone());
+        }
+    }
+}
+
+/// 메모리 테이블에 저장된 파티션 (행 위치 + 파티션 크기)
+#[derive(Debug, Default)]
+struct MemtablePartition {
+    /// 클러스터링 키 → 아레나의 행 위치
+    rows: SkipMap<Option<ClusteringKey>, ArenaRef>,
+    /// 유효한 데이터 바이트 (키 + 인코딩된 행)
+    data_bytes: AtomicU64,
+    /// 같은 파티션에 대한 put의 읽기-병합-기록을 한 번에 하나씩 (동시 쓰기가 서로의 셀을 덮어쓰지 않도록)
+    merge_lock: Mutex<()>,
+}
+
+/// 파티션 크기 (메모리 테이블 기준)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PartitionSize {
+    pub bytes: u64,
+    pub rows: usize,
+}
+
+/// 메모리 테이블 샤드 (파티션 키 해시로 분배)
+#[derive(Debug)]
+struct MemtableShard {
+    /// 파티션 키 → 파티션
+    partitions: SkipMap<PartitionKey, MemtablePartition>,
+    /// 행 바이트를 담는 샤드 전용 아레나
+    arena: RowArena,
+}
+
+impl MemtableShard {
+    fn new() -> Self {
+        Self {
+            partitions: SkipMap::new(),
+            arena: RowArena::new(),
+        }
+    }
+}
+
+impl Clone for MemtableShard {
+    fn clone(&self) -> Self {
+        // SkipMap은 Clone을 지원하지 않으므로 위치만 복사
+        let partitions = SkipMap::new();
+        for entry in self.partitions.iter() {
+            let partition = MemtablePartition {
+                rows: SkipMap::new(),
+                data_bytes: AtomicU64::new(entry.value().data_bytes.load(Ordering::Relaxed)),
+                merge_lock: Mutex::new(()),
+            };
+            for row_entry in entry.value().rows.iter() {
+                partition.rows.insert(row_entry.key().clone(), *row_entry.value());
+            }
+            partitions.insert(entry.key().clone(), partition);
+        }
+        Self { partitions, arena: self.arena.clone() }
+    }
+}
+
+/// SkipMap 노드당 고정 비용 (참조 수/높이 워드 + 평균 타워 포인터 + 에포크 헤더)
+const SKIPLIST_NODE_OVERHEAD: u64 = 48;
+
+/// 메모리 테이블 메모리 사용량
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemtableMemory {
+    /// 현재 유효한 데이터 (인코딩된 행 + 직렬화된 키)
+    pub data_bytes: u64,
+    /// 아레나 청크로 확보한 바이트 (덮어쓴 행의 이전 버전 포함)
+    pub arena_bytes: u64,
+    /// SkipMap 노드와 키가 차지하는 바이트
+    pub index_bytes: u64,
+}
+
+impl MemtableMemory {
+    /// 실제로 점유한 전체 바이트
+    pub fn allocated_bytes(&self) -> u64 {
+        self.arena_bytes + self.index_bytes
+    }
+
+    /// 데이터 외의 힙 사용량 (아레나 여유 공간/이전 버전 + 인덱스 구조)
+    pub fn overhead_bytes(&self) -> u64 {
+        self.allocated_bytes().saturating_sub(self.data_bytes)
+    }
+}
+
+/// 키 하나가 힙에 차지하는 바이트
+fn key_heap_bytes(components: &[CassandraValue]) -> u64 {
+    components.len() as u64 * std::mem::size_of::<CassandraValue>() as u64
+        + components.iter().map(|component| component.heap_size()).sum::<u64>()
+}
+
+/// 기본 샤드 수 (코어 수에 맞춘 2의 거듭제곱)
+fn default_shard_count() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .next_power_of_two()
+        .min(64)
 }
 
 /// 메모리 테이블
+///
+/// 쓰기가 하나의 SkipMap에 몰리지 않도록 파티션 키 해시로 여러 샤드에 나누어 저장.
+/// 한 파티션은 항상 같은 샤드에 있으므로 파티션 단위 조회는 샤드 하나만 보고,
+/// 전체 순회는 샤드들을 모아 파티션 키 순으로 정렬.
+/// 행은 컬럼 번호로 인코딩해 샤드 아레나에 저장하고 읽을 때 Row로 복원함
 #[derive(Debug)]
 pub struct Memtable {
-    /// 파티션별로 데이터 구조화
-    partitions: SkipMap<PartitionKey, Partition>,
-    /// 메모리 사용량 (바이트)
-    size_bytes: AtomicU64,
+    shards: Vec<MemtableShard>,
+    /// 컬럼 이름 인터닝 (샤드 공용)
+    columns: ColumnInterner,
+    /// 유효한 데이터 바이트
+    data_bytes: AtomicU64,
+    /// SkipMap 노드와 키 바이트
+    index_bytes: AtomicU64,
     /// 생성 시간
     creation_time: i64,
+    /// 첫 쓰기 시간 (마이크로초, 비어 있으면 i64::MAX)
+    first_write_time: AtomicI64,
+    /// 받은 행 중 가장 작은 타임스탬프 (비어 있으면 i64::MAX)
+    min_timestamp: AtomicI64,
     /// 테이블 스키마
     table_schema: Arc<TableSchema>,
+    /// 플러시 대기 중 (더 이상 쓰기를 받지 않음)
+    immutable: AtomicBool,
+    /// 진행 중인 put 수 (freeze가 끝나기를 기다림)
+    active_writers: AtomicUsize,
+}
+
+/// put 진행 중 표시 (조기 반환에도 해제)
+struct WriterGuard<'a>(&'a AtomicUsize);
+
+impl Drop for WriterGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
 impl Memtable {
     pub fn new(schema: Arc<TableSchema>) -> Self {
+        Self::with_shards(schema, default_shard_count())
+    }
+    
+    /// 샤드 수를 지정하여 생성
+    pub fn with_shards(schema: Arc<TableSchema>, shard_count: usize) -> Self {
+        let columns = ColumnInterner::new(schema.column_layout());
         Self {
-            partitions: SkipMap::new(),
-            size_bytes: AtomicU64::new(0),
+            shards: (0..shard_count.max(1)).map(|_| MemtableShard::new()).collect(),
+            columns,
+            data_bytes: AtomicU64::new(0),
+            index_bytes: AtomicU64::new(0),
             creation_time: chrono::Utc::now().timestamp_micros(),
+            first_write_time: AtomicI64::new(i64::MAX),
+            min_timestamp: AtomicI64::new(i64::MAX),
             table_schema: schema,
+            immutable: AtomicBool::new(false),
+            active_writers: AtomicUsize::new(0),
+        }
+    }
+    
+    /// 플러시 대상으로 고정 (이후 put은 실패)
+    ///
+    /// 이미 시작된 put이 끝날 때까지 기다리므로, 반환 후에는 내용이 더 바뀌지 않아
+    /// 플러시와 읽기가 복사 없이 같은 고정된 내용을 봄
+    pub fn freeze(&self) {
+        self.immutable.store(true, Ordering::SeqCst);
+        while self.active_writers.load(Ordering::SeqCst) > 0 {
+            std::thread::yield_now();
         }
     }
     
+    pub fn is_immutable(&self) -> bool {
+        self.immutable.load(Ordering::SeqCst)
+    }
+    
     pub fn put(&self, row: Row) -> Result<()> {
-        let partition_key = row.partition_key.clone();
-        let clustering_key = row.clustering_key.clone();
+        // 쓰기 중 표시를 먼저 하고 고정 여부를 확인해야 freeze와 엇갈리지 않음
+        self.active_writers.fetch_add(1, Ordering::SeqCst);
+        let _writer = WriterGuard(&self.active_writers);
+        if self.is_immutable() {
+            return Err(CoreDBError::Generic { message: "Memtable is immutable".to_string() });
+        }
+        let shard = self.shard(&row.partition_key);
+        self.min_timestamp.fetch_min(row.timestamp, Ordering::Relaxed);
+        if self.first_write_time.load(Ordering::Relaxed) == i64::MAX {
+            self.first_write_time.fetch_min(chrono::Utc::now().timestamp_micros(), Ordering::Relaxed);
+        }
         
         // 파티션 가져오거나 생성
-        let partition = self.partitions
-            .get_or_insert_with(partition_key.clone(), || Partition::new());
-        
-        // 행 크기 계산
-        let row_size = self.calculate_row_size(&row);
-        
-        // 기존 행이 있다면 크기 차이 계산
-        if let Some(existing_entry) = partition.value().rows.get(&clustering_key) {
-            let old_row_size = self.calculate_row_size(existing_entry.value());
-            let size_delta = row_size as i64 - old_row_size as i64;
-            self.size_bytes.fetch_add(size_delta as u64, Ordering::Relaxed);
-        } else {
-            self.size_bytes.fetch_add(row_size, Ordering::Relaxed);
+        let created = std::cell::Cell::new(false);
+        let partition = shard.partitions
+            .get_or_insert_with(row.partition_key.clone(), || {
+                created.set(true);
+                MemtablePartition::default()
+            });
+        let partition = partition.value();
+        if created.get() {
+            let components = &row.partition_key.components;
+            let key_size = row.partition_key.serialized_size();
+            self.data_bytes.fetch_add(key_size, Ordering::Relaxed);
+            partition.data_bytes.fetch_add(key_size, Ordering::Relaxed);
+            self.index_bytes.fetch_add(
+                SKIPLIST_NODE_OVERHEAD
+                    + std::mem::size_of::<(PartitionKey, MemtablePartition)>() as u64
+                    + key_heap_bytes(components),
+                Ordering::Relaxed,
+            );
         }
         
-        // 행 삽입/업데이트
-        partition.value().rows.insert(clustering_key, row);
+        // 기존 행과 셀 단위 병합 (기록까지 파티션 잠금을 쥠)
+        let _merging = partition.merge_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let previous = partition.rows.get(&row.clustering_key).map(|entry| *entry.value());
+        let existing = previous
+            .map(|location| self.decode(shard, &row.partition_key, &row.clustering_key, location, &ReadOptions::all()));
+        let merged = match existing {
+            Some(mut existing) => {
+                existing.merge(row);
+                existing
+            },
+            None => row,
+        };
+        
+        // 병합 결과를 아레나에 새로 기록 (이전 바이트는 플러시 때 함께 해제)
+        let mut buf = Vec::new();
+        encode_row(&merged, &self.columns, &mut buf)?;
+        let location = shard.arena.alloc(&buf);
+        
+        // 유효 데이터는 새 버전으로 교체, 새 행이면 키와 노드 비용 추가
+        match previous {
+            Some(previous) => {
+                for counter in [&self.data_bytes, &partition.data_bytes] {
+                    counter.fetch_add(location.byte_len(), Ordering::Relaxed);
+                    counter.fetch_sub(previous.byte_len(), Ordering::Relaxed);
+                }
+            },
+            None => {
+                let clustering_size = merged.clustering_key.as_ref().map(|ck| ck.serialized_size()).unwrap_or(0);
+                let clustering_heap = merged.clustering_key.as_ref().map(|ck| key_heap_bytes(&ck.components)).unwrap_or(0);
+                self.data_bytes.fetch_add(location.byte_len() + clustering_size, Ordering::Relaxed);
+                partition.data_bytes.fetch_add(location.byte_len() + clustering_size, Ordering::Relaxed);
+                self.index_bytes.fetch_add(
+                    SKIPLIST_NODE_OVERHEAD
+                        + std::mem::size_of::<(Option<ClusteringKey>, ArenaRef)>() as u64
+                        + clustering_heap,
+                    Ordering::Relaxed,
+                );
+            },
+        }
+        partition.rows.insert(merged.clustering_key, location);
         
         Ok(())
     }
     
     pub fn get(&self, partition_key: &PartitionKey, clustering_key: &Option<ClusteringKey>) 
         -> Option<Row> {
-        self.partitions.get(partition_key)?
-            .value().rows.get(clustering_key)
-            .map(|entry| entry.value().clone())
+        self.get_with(partition_key, clustering_key, &ReadOptions::all())
     }
     
+    /// 클러스터링 키 범위 조회 (None 경계는 제한 없음)
     pub fn range_scan(&self, 
         partition_key: &PartitionKey,
         start_clustering: &Option<ClusteringKey>,
         end_clustering: &Option<ClusteringKey>
     ) -> Vec<Row> {
-        if let Some(partition) = self.partitions.get(partition_key) {
-            partition.value().rows
-                .range(start_clustering..=end_clustering)
-                .map(|entry| entry.value().clone())
-                .collect()
-        } else {
-            Vec::new()
+        self.range_scan_with(partition_key, start_clustering, end_clustering, &ReadOptions::all())
+    }
+    
+    /// 범위 조회 시 선택된 컬럼만 복원하고 limit 개수에서 순회 중단
+    ///
+    /// 역방향이면 SkipMap 범위를 뒤에서부터 순회하므로 파티션 전체를 모아
+    /// 뒤집지 않고도 마지막 행들을 바로 읽을 수 있음 (결과도 역순)
+    pub fn range_scan_with(&self, 
+        partition_key: &PartitionKey,
+        start_clustering: &Option<ClusteringKey>,
+        end_clustering: &Option<ClusteringKey>,
+        options: &ReadOptions
+    ) -> Vec<Row> {
+        use std::ops::Bound;
+        
+        let start = match start_clustering {
+            Some(_) => Bound::Included(start_clustering),
+            None => Bound::Unbounded,
+        };
+        let end = match end_clustering {
+            Some(_) => Bound::Included(end_clustering),
+            None => Bound::Unbounded,
+        };
+        
+        let shard = self.shard(partition_key);
+        let Some(partition) = shard.partitions.get(partition_key) else {
+            return Vec::new();
+        };
+        let range = partition.value().rows.range::<Option<ClusteringKey>, _>((start, end));
+        let limit = options.limit.unwrap_or(usize::MAX);
+        let decode = |entry: crossbeam_skiplist::map::Entry<'_, Option<ClusteringKey>, ArenaRef>| {
+            self.decode(shard, partition_key, entry.key(), *entry.value(), options)
+        };
+        match options.direction {
+            ScanDirection::Forward => range.take(limit).map(decode).collect(),
+            ScanDirection::Reverse => range.rev().take(limit).map(decode).collect(),
         }
     }
     
+    /// 선택된 컬럼만 복원하여 행 조회
+    pub fn get_with(&self, partition_key: &PartitionKey, clustering_key: &Option<ClusteringKey>, options: &ReadOptions) 
+        -> Option<Row> {
+        let shard = self.shard(partition_key);
+        let location = *shard.partitions.get(partition_key)?
+            .value().rows.get(clustering_key)?
+            .value();
+        Some(self.decode(shard, partition_key, clustering_key, location, options))
+    }
+    
+    /// 파티션 키 목록 (정렬 순서)
+    pub fn partition_keys(&self) -> Vec<PartitionKey> {
+        let mut keys: Vec<PartitionKey> = self.shards.iter()
+            .flat_map(|shard| shard.partitions.iter().map(|entry| entry.key().clone()))
+            .collect();
+        keys.sort();
+        keys
+    }
+    
+    /// 모든 파티션 복사본 (파티션 키 순서)
     pub fn get_all_partitions(&self) -> Vec<(PartitionKey, Partition)> {
-        self.partitions.iter()
-            .map(|entry| {
-                let key = entry.key().clone();
-                let partition = entry.value();
-                // Clone Partition manually since SkipMap doesn't implement Clone
-                let mut new_partition = Partition::new();
-                new_partition.static_columns = partition.static_columns.clone();
-                for row_entry in partition.rows.iter() {
-                    new_partition.rows.insert(row_entry.key().clone(), row_entry.value().clone());
-                }
-                (key, new_partition)
+        self.partitions().collect()
+    }
+    
+    /// 파티션 키 순서로 파티션을 하나씩 복원하는 반복자
+    ///
+    /// 샤드별 SkipMap을 병합하며 순회하므로 메모리 테이블 전체를 복사하지 않음.
+    /// 고정된 메모리 테이블(플러시 대상)에서는 일관된 내용을 봄
+    pub fn partitions(&self) -> impl Iterator<Item = (PartitionKey, Partition)> + '_ {
+        // 샤드마다 (반복자, 다음 파티션)
+        let mut cursors: Vec<_> = self.shards.iter()
+            .map(|shard| {
+                let mut iter = shard.partitions.iter();
+                let head = iter.next();
+                (shard, iter, head)
             })
-            .collect()
+            .collect();
+        std::iter::from_fn(move || {
+            // 각 샤드의 다음 키 중 가장 작은 것을 선택
+            let (shard, iter, head) = cursors.iter_mut()
+                .filter(|(_, _, head)| head.is_some())
+                .min_by(|(_, _, a), (_, _, b)| a.as_ref().unwrap().key().cmp(b.as_ref().unwrap().key()))?;
+            let shard: &MemtableShard = shard;
+            let entry = std::mem::replace(head, iter.next())?;
+            let key = entry.key().clone();
+            let partition = Partition::new();
+            for row_entry in entry.value().rows.iter() {
+                let row = self.decode(shard, &key, row_entry.key(), *row_entry.value(), &ReadOptions::all());
+                partition.rows.insert(row_entry.key().clone(), row);
+            }
+            Some((key, partition))
+        })
     }
     
+    /// 메모리 테이블이 실제로 점유한 바이트 (플러시 기준)
     pub fn size_bytes(&self) -> u64 {
-        self.size_bytes.load(Ordering::Relaxed)
+        self.memory().allocated_bytes()
+    }
+    
+    /// 데이터와 힙 오버헤드를 나눈 메모리 사용량
+    pub fn memory(&self) -> MemtableMemory {
+        MemtableMemory {
+            data_bytes: self.data_bytes.load(Ordering::Relaxed),
+            arena_bytes: self.shards.iter().map(|shard| shard.arena.allocated_bytes()).sum(),
+            index_bytes: self.index_bytes.load(Ordering::Relaxed),
+        }
+    }
+    
+    /// 파티션 하나의 크기 (없으면 None)
+    pub fn partition_size(&self, partition_key: &PartitionKey) -> Option<PartitionSize> {
+        self.shard(partition_key).partitions.get(partition_key).map(|entry| PartitionSize {
+            bytes: entry.value().data_bytes.load(Ordering::Relaxed),
+            rows: entry.value().rows.len(),
+        })
+    }
+    
+    /// 크기가 임계값(바이트)을 넘는 파티션들
+    pub fn partitions_larger_than(&self, threshold_bytes: u64) -> Vec<(PartitionKey, PartitionSize)> {
+        let mut partitions: Vec<(PartitionKey, PartitionSize)> = self.shards.iter()
+            .flat_map(|shard| shard.partitions.iter())
+            .filter(|entry| entry.value().data_bytes.load(Ordering::Relaxed) > threshold_bytes)
+            .map(|entry| (entry.key().clone(), PartitionSize {
+                bytes: entry.value().data_bytes.load(Ordering::Relaxed),
+                rows: entry.value().rows.len(),
+            }))
+            .collect();
+        partitions.sort_by(|a, b| a.0.cmp(&b.0));
+        partitions
     }
     
     pub fn partition_count(&self) -> usize {
-        self.partitions.len()
+        self.shards.iter().map(|shard| shard.partitions.len()).sum()
+    }
+    
+    /// 이 메모리 테이블의 컬럼 번호 배치 (쓰기 행이 공유하면 병합/인코딩이 번호로 처리됨)
+    pub fn column_layout(&self) -> Arc<ColumnLayout> {
+        self.columns.layout()
+    }
+    
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
     }
     
     pub fn creation_time(&self) -> i64 {
         self.creation_time
     }
     
+    pub fn min_timestamp(&self) -> i64 {
+        self.min_timestamp.load(Ordering::Relaxed)
+    }
+    
+    /// 첫 쓰기부터 지난 시간 (비어 있으면 None, 최대 나이 플러시 기준)
+    pub fn age(&self) -> Option<std::time::Duration> {
+        let first_write = self.first_write_time.load(Ordering::Relaxed);
+        (first_write != i64::MAX).then(|| {
+            std::time::Duration::from_micros((chrono::Utc::now().timestamp_micros() - first_write).max(0) as u64)
+        })
+    }
+    
     pub fn table_schema(&self) -> &Arc<TableSchema> {
         &self.table_schema
     }
     
-    fn calculate_row_size(&self, row: &Row) -> u64 {
-        // 행 크기 추정 (키 + 값 + 메타데이터)
-        let mut size = 0u64;
-        
-        // 파티션 키 크기
-        size += row.partition_key.serialized_size();
-        
-        // 클러스터링 키 크기
-        if let Some(ref ck) = row.clustering_key {
-            size += ck.serialized_size();
+    /// 같은 내용에 다른 스키마(옵션만 바뀐 것)를 붙인 복사본 (쓰기가 멈춘 동안 호출)
+    pub fn with_schema(&self, schema: Arc<TableSchema>) -> Self {
+        Self { table_schema: schema, ..self.clone() }
+    }
+    
+    /// 파티션 키가 속한 샤드 (직렬화 바이트 해시 기준)
+    fn shard(&self, partition_key: &PartitionKey) -> &MemtableShard {
+        if self.shards.len() == 1 {
+            return &self.shards[0];
         }
-        
-        // 셀들 크기
-        for (column_name, cell) in &row.cells {
-            size += column_name.len() as u64;
-            size += cell.value.serialized_size();
-            size += 16; // timestamp + ttl + flags
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        for component in &partition_key.components {
+            component.comparable_bytes().hash(&mut hasher);
         }
-        
-        size
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+    
+    fn decode(&self, shard: &MemtableShard, partition_key: &PartitionKey, clustering_key: &Option<ClusteringKey>, location: ArenaRef, options: &ReadOptions) -> Row {
+        shard.arena.with_bytes(location, |bytes| {
+            decode_row(bytes, partition_key, clustering_key, &self.columns, options)
+        })
     }
 }
 
 impl Clone for Memtable {
     fn clone(&self) -> Self {
-        // SkipMap과 AtomicU64는 Clone을 지원하지 않으므로
-        // 새로운 Memtable을 생성하고 데이터를 복사
-        let mut new_memtable = Self::new(self.table_schema.clone());
-        
-        for entry in self.partitions.iter() {
-            let partition_key = entry.key().clone();
-            let partition = entry.value();
-            
-            let mut new_partition = Partition::new();
-            
-            // 행들 복사
-            for row_entry in partition.rows.iter() {
-                let clustering_key = row_entry.key().clone();
-                let row = row_entry.value().clone();
-                new_partition.rows.insert(clustering_key, row);
-            }
-            
-            // 정적 컬럼들 복사
-            new_partition.static_columns = partition.static_columns.clone();
-            
-            new_memtable.partitions.insert(partition_key, new_partition);
+        // 아레나 위치는 (청크, 오프셋) 번호이므로 청크와 SkipMap을 그대로 복사하면 됨
+        Self {
+            shards: self.shards.clone(),
+            columns: self.columns.clone(),
+            data_bytes: AtomicU64::new(self.data_bytes.load(Ordering::Relaxed)),
+            index_bytes: AtomicU64::new(self.index_bytes.load(Ordering::Relaxed)),
+            creation_time: self.creation_time,
+            first_write_time: AtomicI64::new(self.first_write_time.load(Ordering::Relaxed)),
+            min_timestamp: AtomicI64::new(self.min_timestamp()),
+            table_schema: self.table_schema.clone(),
+            immutable: AtomicBool::new(false),
+            active_writers: AtomicUsize::new(0),
         }
-        
-        new_memtable.size_bytes.store(self.size_bytes.load(Ordering::Relaxed), Ordering::Relaxed);
-        new_memtable.creation_time = self.creation_time;
-        
-        new_memtable
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::schema::{CassandraValue, ColumnDefinition, CassandraDataType, Cell};
+    use crate::schema::{ColumnDefinition, CassandraDataType, Cell, Cells};
     
     fn create_test_schema() -> Arc<TableSchema> {
         Arc::new(crate::schema::TableSchema::new(
@@ -210,8 +547,6 @@ mod tests {
     }
     
     fn create_test_row(id: i32, timestamp: i64, value: &str) -> Row {
-        use std::collections::HashMap;
-        
         Row {
             partition_key: PartitionKey {
                 components: vec![CassandraValue::Int(id)],
@@ -220,8 +555,8 @@ mod tests {
                 components: vec![CassandraValue::BigInt(timestamp)],
             }),
             cells: {
-                let mut cells = HashMap::new();
-                cells.insert("value".to_string(), Cell {
+                let mut cells = Cells::new();
+                cells.insert("value", Cell {
                     value: CassandraValue::Text(value.to_string()),
                     timestamp: chrono::Utc::now().timestamp_micros(),
                     ttl: None,
@@ -279,6 +614,82 @@ mod tests {
         assert_eq!(results.len(), 3); // timestamp 2000, 3000, 4000
     }
     
+    #[test]
+    fn test_memtable_reverse_range_scan() {
+        let memtable = Memtable::new(create_test_schema());
+        for i in 1..=5 {
+            memtable.put(create_test_row(1, i * 1000, &format!("value_{}", i))).unwrap();
+        }
+        let partition_key = PartitionKey { components: vec![CassandraValue::Int(1)] };
+        let clustering = |ts: i64| Some(ClusteringKey { components: vec![CassandraValue::BigInt(ts)] });
+        
+        // 끝에서부터 두 행
+        let options = ReadOptions::all().with_direction(ScanDirection::Reverse).with_limit(Some(2));
+        let tail: Vec<_> = memtable.range_scan_with(&partition_key, &None, &None, &options)
+            .into_iter()
+            .map(|row| row.clustering_key)
+            .collect();
+        assert_eq!(tail, vec![clustering(5000), clustering(4000)]);
+        
+        // 범위 경계도 역방향에서 동일하게 적용
+        let options = ReadOptions::all().with_direction(ScanDirection::Reverse);
+        let ranged: Vec<_> = memtable.range_scan_with(&partition_key, &clustering(2000), &clustering(4000), &options)
+            .into_iter()
+            .map(|row| row.clustering_key)
+            .collect();
+        assert_eq!(ranged, vec![clustering(4000), clustering(3000), clustering(2000)]);
+    }
+    
+    #[test]
+    fn test_memtable_same_timestamp_tie_breaking() {
+        let schema = create_test_schema();
+        let memtable = Memtable::new(schema);
+        
+        let mut first = create_test_row(1, 1000, "banana");
+        let mut second = create_test_row(1, 1000, "apple");
+        for row in [&mut first, &mut second] {
+            row.cells.get_mut("value").unwrap().timestamp = 42;
+        }
+        
+        // 늦게 쓰였더라도 사전순으로 작은 값은 승리하지 못함
+        memtable.put(first.clone()).unwrap();
+        memtable.put(second).unwrap();
+        
+        let retrieved = memtable.get(&first.partition_key, &first.clustering_key).unwrap();
+        assert_eq!(retrieved.cells["value"].value, CassandraValue::Text("banana".to_string()));
+    }
+    
+    #[test]
+    fn test_sharded_memtable_keeps_partition_order() {
+        let memtable = Arc::new(Memtable::with_shards(create_test_schema(), 4));
+        assert_eq!(memtable.shard_count(), 4);
+        
+        // 여러 스레드에서 동시에 쓰기
+        let writers: Vec<_> = (0..4).map(|t| {
+            let memtable = memtable.clone();
+            std::thread::spawn(move || {
+                for i in 0..50 {
+                    memtable.put(create_test_row(t * 50 + i, 1000, "v")).unwrap();
+                }
+            })
+        }).collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        
+        assert_eq!(memtable.partition_count(), 200);
+        let keys = memtable.partition_keys();
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        let partitions = memtable.get_all_partitions();
+        assert_eq!(partitions.len(), 200);
+        assert!(partitions.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(memtable.get(&keys[17], &Some(ClusteringKey { components: vec![CassandraValue::BigInt(1000)] })).is_some());
+        
+        let copy = (*memtable).clone();
+        assert_eq!(copy.partition_count(), 200);
+        assert_eq!(copy.size_bytes(), memtable.size_bytes());
+    }
+    
     #[test]
     fn test_memtable_size_tracking() {
         let schema = create_test_schema();
@@ -291,4 +702,116 @@ mod tests {
         
         assert!(memtable.size_bytes() > initial_size);
     }
+    
+    #[test]
+    fn test_memtable_memory_breakdown() {
+        let memtable = Memtable::with_shards(create_test_schema(), 1);
+        assert_eq!(memtable.memory(), MemtableMemory::default());
+        
+        memtable.put(create_test_row(1, 1000, "a")).unwrap();
+        let first = memtable.memory();
+        assert!(first.data_bytes > 0 && first.index_bytes > 0);
+        // 아레나는 청크 단위로 확보하므로 데이터보다 큼
+        assert!(first.arena_bytes >= first.data_bytes);
+        assert_eq!(memtable.size_bytes(), first.allocated_bytes());
+        
+        // 같은 행을 덮어쓰면 유효 데이터와 인덱스는 그대로, 이전 버전은 오버헤드로 남음
+        memtable.put(create_test_row(1, 1000, "b")).unwrap();
+        let second = memtable.memory();
+        assert_eq!(second.data_bytes, first.data_bytes);
+        assert_eq!(second.index_bytes, first.index_bytes);
+        assert_eq!(second.overhead_bytes(), first.overhead_bytes());
+        
+        // 새 행은 키와 노드 비용이 추가됨
+        memtable.put(create_test_row(1, 2000, "c")).unwrap();
+        assert!(memtable.memory().index_bytes > second.index_bytes);
+    }
+    
+    #[test]
+    fn test_frozen_memtable_is_stable_under_concurrent_writes() {
+        let memtable = Arc::new(Memtable::with_shards(create_test_schema(), 4));
+        let writers: Vec<_> = (0..4).map(|writer| {
+            let memtable = memtable.clone();
+            std::thread::spawn(move || {
+                let mut written = 0;
+                for id in 0.. {
+                    if memtable.put(create_test_row(writer * 100_000 + id, 1000, "v")).is_err() {
+                        break;
+                    }
+                    written += 1;
+                }
+                written
+            })
+        }).collect();
+        while memtable.partition_count() < 100 {
+            std::thread::yield_now();
+        }
+        
+        // freeze가 반환되면 진행 중이던 쓰기까지 반영이 끝나 내용이 고정됨
+        memtable.freeze();
+        let frozen_count = memtable.partition_count();
+        let frozen_bytes = memtable.memory().data_bytes;
+        let written: i32 = writers.into_iter().map(|writer| writer.join().unwrap()).sum();
+        assert_eq!(frozen_count, written as usize);
+        assert_eq!(memtable.memory().data_bytes, frozen_bytes);
+        
+        // 샤드를 병합한 순회는 복사 없이 파티션 키 순서
+        let keys: Vec<PartitionKey> = memtable.partitions().map(|(key, _)| key).collect();
+        assert_eq!(keys.len(), frozen_count);
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+    
+    #[test]
+    fn test_concurrent_puts_to_one_row_keep_every_cell() {
+        let columns: Vec<_> = (0..8).map(|i| ColumnDefinition { name: format!("c{}", i), data_type: CassandraDataType::Int, is_static: false }).collect();
+        let schema = Arc::new(TableSchema::new("wide".to_string(), "test_keyspace".to_string(),
+                                               vec![ColumnDefinition { name: "id".to_string(), data_type: CassandraDataType::Int, is_static: false }],
+                                               vec![], columns, vec![]));
+        let memtable = Arc::new(Memtable::with_shards(schema, 1));
+        let key = PartitionKey { components: vec![CassandraValue::Int(1)] };
+        // 쓰기마다 서로 다른 컬럼만 쓰므로 병합이 겹쳐도 어느 셀도 사라지면 안 됨
+        let writers: Vec<_> = (0..8).map(|column| {
+            let (memtable, key) = (memtable.clone(), key.clone());
+            std::thread::spawn(move || {
+                for round in 1..=200 {
+                    let mut cells = Cells::new();
+                    cells.insert(format!("c{}", column), Cell { value: CassandraValue::Int(round), timestamp: round as i64, ttl: None, is_deleted: false });
+                    memtable.put(Row { partition_key: key.clone(), clustering_key: None, cells, timestamp: round as i64 }).unwrap();
+                }
+            })
+        }).collect();
+        writers.into_iter().for_each(|writer| writer.join().unwrap());
+        
+        let row = memtable.get(&key, &None).unwrap();
+        for column in 0..8 {
+            assert_eq!(row.cells[format!("c{}", column).as_str()].value, CassandraValue::Int(200));
+        }
+    }
+    
+    #[test]
+    fn test_partition_size_tracking() {
+        let memtable = Memtable::new(create_test_schema());
+        let key = |id| PartitionKey { components: vec![CassandraValue::Int(id)] };
+        assert_eq!(memtable.partition_size(&key(1)), None);
+        
+        memtable.put(create_test_row(1, 1000, "a")).unwrap();
+        let single = memtable.partition_size(&key(1)).unwrap();
+        assert_eq!(single.rows, 1);
+        
+        // 덮어쓰기는 크기를 늘리지 않고, 새 행은 늘림
+        memtable.put(create_test_row(1, 1000, "b")).unwrap();
+        assert_eq!(memtable.partition_size(&key(1)), Some(single));
+        memtable.put(create_test_row(1, 2000, "c")).unwrap();
+        memtable.put(create_test_row(2, 1000, "d")).unwrap();
+        let grown = memtable.partition_size(&key(1)).unwrap();
+        assert_eq!(grown.rows, 2);
+        assert!(grown.bytes > single.bytes);
+        
+        // 파티션 크기 합은 메모리 테이블의 유효 데이터와 같음
+        let total = grown.bytes + memtable.partition_size(&key(2)).unwrap().bytes;
+        assert_eq!(total, memtable.memory().data_bytes);
+        
+        let large = memtable.partitions_larger_than(single.bytes);
+        assert_eq!(large, vec![(key(1), grown)]);
+    }
 }
@@ -1,7 +1,47 @@
 pub mod memtable;
 pub mod sstable;
 pub mod bloom_filter;
+pub mod secondary_index;
+pub mod read_options;
+pub mod vector_index;
+pub mod arena;
+pub mod manifest;
+pub mod compression_info;
+pub mod checksum;
+pub mod file_reader;
+pub mod statistics;
+pub mod key_cache;
+pub mod chunk_cache;
+pub mod cell_format;
+pub mod direct_io;
+pub mod row_cache;
+pub mod sstable_dump;
+pub mod cassandra_sstable;
+pub mod scrub;
+pub mod integrity;
+#[cfg(feature = "fulltext")]
+pub mod fulltext;
 
 pub use memtable::*;
 pub use sstable::*;
 pub use bloom_filter::*;
+pub use secondary_index::*;
+pub use read_options::*;
+pub use vector_index::*;
+pub use arena::*;
+pub use manifest::*;
+pub use compression_info::*;
+pub use checksum::*;
+pub use file_reader::*;
+pub use statistics::*;
+pub use key_cache::*;
+pub use chunk_cache::*;
+pub use cell_format::*;
+pub use direct_io::*;
+pub use row_cache::*;
+pub use sstable_dump::*;
+pub use cassandra_sstable::*;
+pub use scrub::*;
+pub use integrity::*;
+#[cfg(feature = "fulltext")]
+pub use fulltext::*;
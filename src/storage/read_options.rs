@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+use std::ops::{Bound, RangeBounds};
+use crate::schema::{CassandraValue, ClusteringKey, Row};
+
+/// 파티션 내 클러스터링 키 순회 방향
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScanDirection {
+    #[default]
+    Forward,
+    /// ORDER BY ... DESC 또는 끝에서부터 읽는 조회
+    Reverse,
+}
+
+/// 첫 번째 클러스터링 컬럼 값의 범위
+///
+/// SSTable과 파티션의 클러스터링 최소/최대로 읽을 필요가 없는 것을 건너뛰는 데 사용
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClusteringSlice {
+    pub start: Bound<CassandraValue>,
+    pub end: Bound<CassandraValue>,
+}
+
+impl ClusteringSlice {
+    /// 제한 없는 범위
+    pub fn all() -> Self {
+        Self { start: Bound::Unbounded, end: Bound::Unbounded }
+    }
+
+    pub fn is_unbounded(&self) -> bool {
+        self.start == Bound::Unbounded && self.end == Bound::Unbounded
+    }
+
+    /// 하한을 더 좁은 쪽으로 교체
+    pub fn restrict_start(&mut self, bound: Bound<CassandraValue>) {
+        if Self::is_tighter(&bound, &self.start, |new, current| new > current) {
+            self.start = bound;
+        }
+    }
+
+    /// 상한을 더 좁은 쪽으로 교체
+    pub fn restrict_end(&mut self, bound: Bound<CassandraValue>) {
+        if Self::is_tighter(&bound, &self.end, |new, current| new < current) {
+            self.end = bound;
+        }
+    }
+
+    fn is_tighter(new: &Bound<CassandraValue>, current: &Bound<CassandraValue>, narrower: impl Fn(&CassandraValue, &CassandraValue) -> bool) -> bool {
+        match (new, current) {
+            (Bound::Unbounded, _) => false,
+            (_, Bound::Unbounded) => true,
+            (Bound::Included(new) | Bound::Excluded(new), Bound::Included(current) | Bound::Excluded(current)) if new != current => narrower(new, current),
+            (Bound::Excluded(_), Bound::Included(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// 클러스터링 키가 범위 안에 있는지
+    pub fn contains(&self, key: &ClusteringKey) -> bool {
+        key.components.first().is_none_or(|value| (self.start.as_ref(), self.end.as_ref()).contains(value))
+    }
+
+    /// [min, max] 사이의 클러스터링 키 중 범위 안의 것이 있을 수 있는지
+    pub fn overlaps(&self, min: &ClusteringKey, max: &ClusteringKey) -> bool {
+        let (Some(low), Some(high)) = (min.components.first(), max.components.first()) else {
+            return true;
+        };
+        let below = match &self.start {
+            Bound::Included(start) => high < start,
+            Bound::Excluded(start) => high <= start,
+            Bound::Unbounded => false,
+        };
+        let above = match &self.end {
+            Bound::Included(end) => low > end,
+            Bound::Excluded(end) => low >= end,
+            Bound::Unbounded => false,
+        };
+        !below && !above
+    }
+}
+
+/// 저장소 읽기에 전달되는 컬럼 선택과 행 수 제한
+#[derive(Debug, Clone, Default)]
+pub struct ReadOptions {
+    /// 읽을 컬럼 (None이면 전체 컬럼)
+    pub columns: Option<HashSet<String>>,
+    /// 파티션당 최대 행 수 (None이면 제한 없음, 순회 방향 기준 앞에서부터)
+    pub limit: Option<usize>,
+    /// 파티션 내 순회 방향
+    pub direction: ScanDirection,
+    /// 클러스터링 범위 (None이면 파티션 전체, 범위 밖의 행은 읽지 않음)
+    pub clustering_slice: Option<ClusteringSlice>,
+}
+
+impl ReadOptions {
+    /// 전체 컬럼, 제한 없음
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    pub fn with_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn with_limit(mut self, limit: Option<usize>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn with_direction(mut self, direction: ScanDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn with_clustering_slice(mut self, clustering_slice: Option<ClusteringSlice>) -> Self {
+        self.clustering_slice = clustering_slice;
+        self
+    }
+
+    /// 클러스터링 범위에 드는 행인지
+    pub fn includes_row(&self, clustering_key: &Option<ClusteringKey>) -> bool {
+        match (&self.clustering_slice, clustering_key) {
+            (Some(slice), Some(clustering_key)) => slice.contains(clustering_key),
+            _ => true,
+        }
+    }
+
+    pub fn is_reverse(&self) -> bool {
+        self.direction == ScanDirection::Reverse
+    }
+
+    /// 해당 컬럼을 읽어야 하는지 여부
+    pub fn includes(&self, column: &str) -> bool {
+        self.columns.as_ref().is_none_or(|columns| columns.contains(column))
+    }
+
+    /// 선택된 컬럼만 복사한 행
+    pub fn project(&self, row: &Row) -> Row {
+        match &self.columns {
+            None => row.clone(),
+            Some(columns) => Row {
+                partition_key: row.partition_key.clone(),
+                clustering_key: row.clustering_key.clone(),
+                cells: row.cells.filtered(|name| columns.contains(name)),
+                timestamp: row.timestamp,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(value: i32) -> ClusteringKey {
+        ClusteringKey { components: vec![CassandraValue::Int(value)] }
+    }
+
+    #[test]
+    fn test_clustering_slice_overlap() {
+        let mut slice = ClusteringSlice::all();
+        slice.restrict_start(Bound::Included(CassandraValue::Int(10)));
+        slice.restrict_start(Bound::Excluded(CassandraValue::Int(10)));
+        slice.restrict_start(Bound::Included(CassandraValue::Int(5)));
+        slice.restrict_end(Bound::Included(CassandraValue::Int(20)));
+        assert_eq!(slice.start, Bound::Excluded(CassandraValue::Int(10)));
+        assert_eq!(slice.end, Bound::Included(CassandraValue::Int(20)));
+
+        assert!(!slice.contains(&key(10)));
+        assert!(slice.contains(&key(20)));
+        assert!(!slice.overlaps(&key(1), &key(10)));
+        assert!(slice.overlaps(&key(1), &key(11)));
+        assert!(slice.overlaps(&key(20), &key(30)));
+        assert!(!slice.overlaps(&key(21), &key(30)));
+        assert!(ClusteringSlice::all().overlaps(&key(1), &key(2)));
+    }
+}
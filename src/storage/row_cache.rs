@@ -0,0 +1,217 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::schema::{ClusteringKey, PartitionKey, Row};
+
+/// 캐시 항목마다 크기에 더하는 고정 오버헤드 (맵 항목과 Arc)
+const PARTITION_OVERHEAD_BYTES: u64 = 64;
+
+/// 캐시된 파티션 (메모리 테이블과 SSTable의 버전을 모두 병합한 행, 클러스터링 키 순서)
+pub type CachedPartition = BTreeMap<Option<ClusteringKey>, Row>;
+
+/// 행 캐시
+///
+/// 자주 읽는 파티션을 병합이 끝난 상태로 보관해 메모리 테이블과 SSTable을 다시 읽지 않음.
+/// 파티션에 쓰기가 들어오면 무효화하고, 크기 한도를 넘으면 가장 오래 안 쓴 파티션부터 내보냄
+pub struct RowCache {
+    inner: Mutex<RowCacheInner>,
+    capacity_bytes: u64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Default)]
+struct RowCacheInner {
+    /// 파티션 키 → (파티션, 추정 크기, 마지막 사용 순번)
+    entries: HashMap<PartitionKey, (Arc<CachedPartition>, u64, u64)>,
+    /// 마지막 사용 순번 → 파티션 키
+    recency: BTreeMap<u64, PartitionKey>,
+    next_tick: u64,
+    size_bytes: u64,
+    /// 무효화마다 증가 (읽는 동안 쓰기가 있었으면 읽은 결과를 캐시에 넣지 않음)
+    generation: u64,
+}
+
+impl RowCacheInner {
+    fn remove(&mut self, partition_key: &PartitionKey) {
+        if let Some((_, size, tick)) = self.entries.remove(partition_key) {
+            self.recency.remove(&tick);
+            self.size_bytes -= size;
+        }
+    }
+}
+
+/// 행 캐시 통계
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RowCacheStats {
+    pub partitions: usize,
+    pub size_bytes: u64,
+    pub capacity_bytes: u64,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl RowCacheStats {
+    /// 적중률 (조회가 없으면 0.0)
+    pub fn hit_rate(&self) -> f64 {
+        let requests = self.hits + self.misses;
+        if requests == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / requests as f64
+    }
+
+    pub fn merge(&mut self, other: &RowCacheStats) {
+        self.partitions += other.partitions;
+        self.size_bytes += other.size_bytes;
+        self.capacity_bytes += other.capacity_bytes;
+        self.hits += other.hits;
+        self.misses += other.misses;
+    }
+}
+
+impl RowCache {
+    pub fn new(capacity_bytes: u64) -> Self {
+        Self {
+            inner: Mutex::new(RowCacheInner::default()),
+            capacity_bytes,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn get(&self, partition_key: &PartitionKey) -> Option<Arc<CachedPartition>> {
+        let mut inner = self.inner.lock().unwrap();
+        let tick = inner.next_tick;
+        let found = inner.entries.get_mut(partition_key).map(|(partition, _, last_used)| {
+            (partition.clone(), std::mem::replace(last_used, tick))
+        });
+        let Some((partition, previous)) = found else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        inner.next_tick += 1;
+        inner.recency.remove(&previous);
+        inner.recency.insert(tick, partition_key.clone());
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(partition)
+    }
+
+    /// 읽기를 시작하기 전에 가져와 insert에 넘기는 무효화 세대
+    pub fn generation(&self) -> u64 {
+        self.inner.lock().unwrap().generation
+    }
+
+    /// 병합한 파티션 보관 (generation 이후 무효화가 있었거나 한도보다 크면 넣지 않음)
+    ///
+    /// 없는 파티션도 빈 파티션으로 보관하므로 항목마다 키와 고정 오버헤드를 크기에 더함
+    pub fn insert(&self, partition_key: PartitionKey, partition: Arc<CachedPartition>, generation: u64) {
+        let size = partition_key.serialized_size()
+            + PARTITION_OVERHEAD_BYTES
+            + partition.values().map(estimated_row_bytes).sum::<u64>();
+        if size > self.capacity_bytes {
+            return;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        if inner.generation != generation {
+            return;
+        }
+        inner.remove(&partition_key);
+        let tick = inner.next_tick;
+        inner.next_tick += 1;
+        inner.recency.insert(tick, partition_key.clone());
+        inner.entries.insert(partition_key, (partition, size, tick));
+        inner.size_bytes += size;
+        while inner.size_bytes > self.capacity_bytes {
+            let Some((_, oldest)) = inner.recency.pop_first() else { break };
+            let (_, size, _) = inner.entries.remove(&oldest).expect("recency entry has a cached partition");
+            inner.size_bytes -= size;
+        }
+    }
+
+    /// 파티션에 쓰기가 들어왔을 때 호출
+    pub fn invalidate(&self, partition_key: &PartitionKey) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.generation += 1;
+        inner.remove(partition_key);
+    }
+
+    pub fn stats(&self) -> RowCacheStats {
+        let inner = self.inner.lock().unwrap();
+        RowCacheStats {
+            partitions: inner.entries.len(),
+            size_bytes: inner.size_bytes,
+            capacity_bytes: self.capacity_bytes,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl std::fmt::Debug for RowCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RowCache").field("stats", &self.stats()).finish()
+    }
+}
+
+/// 캐시 한도 계산용 행 크기 추정 (키와 셀 값, 셀당 타임스탬프/TTL)
+fn estimated_row_bytes(row: &Row) -> u64 {
+    let cells: u64 = (&row.cells).into_iter()
+        .map(|(column, cell)| column.len() as u64 + cell.value.serialized_size() + 16)
+        .sum();
+    row.partition_key.serialized_size()
+        + row.clustering_key.as_ref().map_or(0, |key| key.serialized_size())
+        + cells
+        + 8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{CassandraValue, Cell, Cells};
+
+    fn partition(id: i32, rows: i64, payload: usize) -> (PartitionKey, Arc<CachedPartition>) {
+        let partition_key = PartitionKey { components: vec![CassandraValue::Int(id)] };
+        let rows = (0..rows).map(|ts| {
+            let clustering_key = Some(ClusteringKey { components: vec![CassandraValue::BigInt(ts)] });
+            let mut cells = Cells::new();
+            cells.insert("value", Cell { value: CassandraValue::Blob(vec![0; payload]), timestamp: 1, ttl: None, is_deleted: false });
+            (clustering_key.clone(), Row { partition_key: partition_key.clone(), clustering_key, cells, timestamp: 1 })
+        }).collect();
+        (partition_key, Arc::new(rows))
+    }
+
+    #[test]
+    fn test_row_cache_evicts_by_size_and_invalidates_on_write() {
+        let cache = RowCache::new(3000);
+        let (first, first_rows) = partition(1, 2, 500);
+        let (second, second_rows) = partition(2, 2, 500);
+        let (third, third_rows) = partition(3, 2, 500);
+
+        cache.insert(first.clone(), first_rows.clone(), cache.generation());
+        cache.insert(second.clone(), second_rows, cache.generation());
+        assert_eq!(cache.get(&first).unwrap().len(), 2);
+
+        // 한도를 넘으면 가장 오래 안 쓴 파티션(2)부터 내보냄
+        cache.insert(third.clone(), third_rows, cache.generation());
+        assert!(cache.get(&second).is_none());
+        assert!(cache.get(&first).is_some());
+        assert!(cache.stats().size_bytes <= 3000);
+
+        // 읽는 도중 무효화가 있었으면 오래된 결과를 넣지 않음
+        let generation = cache.generation();
+        cache.invalidate(&first);
+        assert!(cache.get(&first).is_none());
+        cache.insert(first.clone(), first_rows, generation);
+        assert!(cache.get(&first).is_none());
+
+        // 한도보다 큰 파티션은 캐시하지 않음
+        let (large, large_rows) = partition(4, 10, 1000);
+        cache.insert(large.clone(), large_rows, cache.generation());
+        assert!(cache.get(&large).is_none());
+
+        let stats = cache.stats();
+        assert_eq!(stats.partitions, 1);
+        assert_eq!((stats.hits, stats.misses), (2, 4));
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::{BTreeMap, HashMap};
+use std::collections::btree_map::Entry;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use crossbeam_skiplist::SkipMap;
+use crate::error::{CoreDBError, Result};
+use crate::schema::{Cells, Row};
+use super::{AccessPattern, ComponentReader, Manifest, Partition, PartitionView, ReadOptions, SSTable, SSTableWriter, COMPONENT_SUFFIXES, DATA_FILE_SUFFIX, FIXED_LAYOUT_CELLS_VERSION};
+
+/// 스크럽한 원본 SSTable을 옮겨 두는 테이블 디렉토리 아래 디렉토리
+pub const QUARANTINE_DIRECTORY: &str = "quarantine";
+
+/// SSTable 하나를 스크럽한 결과
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScrubStats {
+    /// SSTable을 열 수 있었는지 (열지 못하면 아무것도 살리지 못함)
+    pub opened: bool,
+    /// 푸터에 기록된 파티션 수
+    pub partitions: u64,
+    /// 모든 청크를 읽을 수 있었던 파티션
+    pub partitions_intact: u64,
+    /// 손상된 청크 앞까지의 행만 살린 파티션
+    pub partitions_salvaged: u64,
+    /// 읽을 수 있는 행이 없어 버린 파티션 (손상된 인덱스 뒤의 파티션 포함)
+    pub partitions_lost: u64,
+    /// 새 SSTable에 옮긴 행
+    pub rows: u64,
+    /// 체크섬이 맞지 않거나 풀 수 없는 청크
+    pub bad_chunks: usize,
+    /// 인덱스 파일을 끝까지 읽지 못함
+    pub index_damaged: bool,
+}
+
+impl ScrubStats {
+    /// 손상이 없어 다시 쓸 필요가 없는지
+    pub fn is_clean(&self) -> bool {
+        self.opened && self.bad_chunks == 0 && !self.index_damaged
+            && self.partitions_salvaged == 0 && self.partitions_lost == 0
+            && self.partitions_intact == self.partitions
+    }
+}
+
+/// SSTable 하나의 스크럽 결과와 처리 내용
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScrubReport {
+    pub id: String,
+    pub stats: ScrubStats,
+    /// 살린 행으로 새로 쓴 SSTable (손상이 없거나 살린 행이 없으면 None)
+    pub replacement: Option<String>,
+    /// 원본을 옮긴 격리 디렉토리 (손상이 없으면 None)
+    pub quarantined: Option<PathBuf>,
+}
+
+impl fmt::Display for ScrubReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stats = &self.stats;
+        if stats.is_clean() {
+            return write!(f, "{}: clean, {} partitions", self.id, stats.partitions);
+        }
+        if !stats.opened {
+            write!(f, "{}: could not be opened", self.id)?;
+        } else {
+            write!(f, "{}: {} unreadable chunks{}, kept {} of {} partitions intact and {} partially ({} rows), lost {}",
+                   self.id, stats.bad_chunks, if stats.index_damaged { " and a damaged index" } else { "" },
+                   stats.partitions_intact, stats.partitions, stats.partitions_salvaged, stats.rows, stats.partitions_lost)?;
+        }
+        match &self.replacement {
+            Some(replacement) => write!(f, ", rewritten as {}", replacement)?,
+            None => write!(f, ", nothing to rewrite")?,
+        }
+        if let Some(quarantined) = &self.quarantined {
+            write!(f, ", original moved to {}", quarantined.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// 테이블 디렉토리의 모든 SSTable 스크럽 (`coredb scrub`, 서버를 멈춘 상태에서 실행)
+pub async fn scrub_table(directory: impl AsRef<Path>) -> Result<Vec<ScrubReport>> {
+    let directory = directory.as_ref();
+    let mut manifest = Manifest::load(directory).await?;
+    // 매니페스트가 없는 이전 디렉토리는 데이터 파일을 모두 현재 파일로 봄
+    let ids = match &manifest {
+        Some(manifest) => manifest.sstables.iter().map(|entry| entry.id.clone()).collect(),
+        None => data_file_ids(directory).await?,
+    };
+    let mut reports = Vec::with_capacity(ids.len());
+    for id in ids {
+        reports.push(scrub_sstable(directory, manifest.as_mut(), &id).await?);
+    }
+    Ok(reports)
+}
+
+/// 디렉토리의 데이터 파일 id (이름 순)
+pub(super) async fn data_file_ids(directory: &Path) -> Result<Vec<String>> {
+    let mut entries = tokio::fs::read_dir(directory).await?;
+    let mut ids = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        if let Some(id) = entry.file_name().to_str().and_then(|name| name.strip_suffix(DATA_FILE_SUFFIX)) {
+            ids.push(id.to_string());
+        }
+    }
+    ids.sort();
+    Ok(ids)
+}
+
+/// SSTable 하나를 청크 단위로 다시 읽어 손상되었으면 읽을 수 있는 행만 새 SSTable로 옮김
+///
+/// 체크섬이 맞지 않는 청크만 건너뛰므로 손상된 청크에 걸친 파티션도 그 앞까지의 행은 남음
+/// (행 경계는 앞에서부터 따라가야 알 수 있어 손상 뒤의 같은 파티션 행은 잃음).
+/// 새 SSTable은 원본과 같은 레벨로 매니페스트에 한 번에 교체되고, 원본 구성 파일은 지우지 않고
+/// <table>/quarantine/으로 옮김. 손상이 없으면 아무것도 바꾸지 않음
+pub async fn scrub_sstable(directory: &Path, manifest: Option<&mut Manifest>, id: &str) -> Result<ScrubReport> {
+    let path = SSTable::component_path(directory, id, DATA_FILE_SUFFIX);
+    let mut stats = ScrubStats::default();
+    let mut replacement = None;
+    if let Ok(sstable) = SSTable::open(&path).await {
+        stats.opened = true;
+        stats.partitions = sstable.partition_count;
+        let mut writer = SSTableWriter::new(directory, sstable.compression, sstable.partition_count).await?;
+        if let Err(e) = salvage(&sstable, &mut writer, &mut stats).await {
+            writer.abort().await?;
+            return Err(e);
+        }
+        if stats.is_clean() || stats.rows == 0 {
+            writer.abort().await?;
+        } else {
+            replacement = Some(writer.finish().await?);
+        }
+    }
+    if stats.is_clean() {
+        return Ok(ScrubReport { id: id.to_string(), stats, replacement: None, quarantined: None });
+    }
+
+    if let Some(manifest) = manifest {
+        let level = manifest.level(id).unwrap_or(0);
+        manifest.record_compaction(&[id.to_string()], replacement.as_slice(), level).await?;
+    }
+    let quarantined = quarantine_sstable(directory, id).await?;
+    Ok(ScrubReport {
+        id: id.to_string(),
+        stats,
+        replacement: replacement.map(|sstable| sstable.id),
+        quarantined: Some(quarantined),
+    })
+}
+
+/// SSTable 구성 파일을 <table>/quarantine/으로 옮김 (격리 디렉토리 반환)
+pub async fn quarantine_sstable(directory: &Path, id: &str) -> Result<PathBuf> {
+    let quarantine = directory.join(QUARANTINE_DIRECTORY);
+    tokio::fs::create_dir_all(&quarantine).await?;
+    for suffix in COMPONENT_SUFFIXES {
+        match tokio::fs::rename(SSTable::component_path(directory, id, suffix), SSTable::component_path(&quarantine, id, suffix)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {},
+        }
+    }
+    Ok(quarantine)
+}
+
+/// 인덱스 순서대로 파티션 레코드를 청크 단위로 모아 읽을 수 있는 만큼 writer에 씀
+async fn salvage(sstable: &SSTable, writer: &mut SSTableWriter, stats: &mut ScrubStats) -> Result<()> {
+    let (entries, complete) = sstable.index_entries().await?;
+    stats.index_damaged = !complete;
+    stats.partitions_lost = sstable.partition_count.saturating_sub(entries.len() as u64);
+
+    let info = &sstable.compression_info;
+    let chunk_length = info.chunk_length as u64;
+    let mut file = ComponentReader::open(&sstable.file_path, sstable.access_mode, AccessPattern::Sequential).await?;
+    // 여러 파티션이 한 청크를 나눠 쓰므로 읽은 청크(손상이면 None)를 지나갈 때까지 유지
+    let mut chunks: BTreeMap<usize, Option<Vec<u8>>> = BTreeMap::new();
+
+    for (partition_key, offset, length) in entries {
+        if length == 0 || offset + length > info.data_length {
+            stats.partitions_lost += 1;
+            continue;
+        }
+        let first = info.chunk_index(offset);
+        let last = info.chunk_index(offset + length - 1);
+        chunks.retain(|index, _| *index >= first);
+
+        let mut data = Vec::with_capacity(length as usize);
+        let mut intact = true;
+        for index in first..=last {
+            let chunk = match chunks.entry(index) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let chunk = sstable.read_chunks(&mut file, index, index + 1).await.ok();
+                    if chunk.is_none() {
+                        stats.bad_chunks += 1;
+                    }
+                    entry.insert(chunk)
+                },
+            };
+            match chunk {
+                Some(chunk) => data.extend_from_slice(chunk),
+                None => {
+                    intact = false;
+                    break;
+                },
+            }
+        }
+        let start = (offset - first as u64 * chunk_length) as usize;
+        data.truncate(start + length as usize);
+        data.drain(..start.min(data.len()));
+
+        let full = match intact {
+            true => SSTable::deserialize_partition(&data, &ReadOptions::all(), sstable.format_version).ok(),
+            false => None,
+        };
+        let (partition, salvaged) = match full {
+            Some(partition) => (Some(partition), false),
+            None => (salvage_prefix(&data, sstable.format_version), true),
+        };
+        match partition.filter(|partition| !partition.rows.is_empty() || !partition.static_columns.is_empty()) {
+            Some(partition) => {
+                stats.rows += partition.rows.len() as u64;
+                if salvaged {
+                    stats.partitions_salvaged += 1;
+                } else {
+                    stats.partitions_intact += 1;
+                }
+                writer.append(&partition_key, &partition).await?;
+            },
+            None => stats.partitions_lost += 1,
+        }
+    }
+    Ok(())
+}
+
+/// 손상 앞까지 남은 레코드 앞부분에서 온전히 읽히는 행만 꺼냄 (파티션 헤더를 읽지 못하면 None)
+///
+/// 셀마다 bincode로 기록하던 이전 형식은 행 단위로 나눠 읽을 수 없어 살리지 않음
+fn salvage_prefix(data: &[u8], format_version: u32) -> Option<Partition> {
+    if format_version < FIXED_LAYOUT_CELLS_VERSION {
+        return None;
+    }
+    let view = PartitionView::parse(data, format_version).ok()?;
+
+    let mut static_columns = HashMap::new();
+    for static_cell in view.static_cells() {
+        let Ok((name, cell)) = static_cell else { break };
+        let Ok(cell) = cell.to_cell() else { break };
+        static_columns.insert(name.to_string(), cell);
+    }
+
+    let rows = SkipMap::new();
+    for row in view.rows() {
+        let Ok(row) = row else { break };
+        let cells = row.cells().try_fold(Cells::new(), |mut cells, cell| {
+            let (id, cell) = cell?;
+            let name = view.columns().get(id as usize).ok_or_else(|| CoreDBError::Generic {
+                message: format!("Invalid column id: {}", id),
+            })?;
+            cells.insert(name, cell.to_cell()?);
+            Ok::<_, CoreDBError>(cells)
+        });
+        let Ok(cells) = cells else { break };
+        rows.insert(row.clustering_key.clone(), Row {
+            partition_key: view.partition_key.clone(),
+            clustering_key: row.clustering_key,
+            cells,
+            timestamp: row.timestamp,
+        });
+    }
+    Some(Partition { rows, static_columns })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::schema::{CassandraDataType, CassandraValue, Cell, ClusteringKey, ColumnDefinition, PartitionKey, TableSchema};
+    use crate::storage::{CompressionType, Memtable};
+
+    /// 파티션 0..3에 각각 클러스터링 행 40개 (값마다 4KB, 파티션마다 청크 세 개 가까이)
+    async fn flush_wide_partitions(directory: &Path) -> SSTable {
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let schema = Arc::new(TableSchema::new(
+            "events".to_string(),
+            "test_ks".to_string(),
+            vec![column("sensor", CassandraDataType::Int)],
+            vec![column("ts", CassandraDataType::Int)],
+            vec![column("reading", CassandraDataType::Text)],
+            vec![],
+        ));
+        let memtable = Memtable::new(schema);
+        for sensor in 0..3 {
+            for ts in 0..40 {
+                let mut cells = Cells::new();
+                cells.insert("reading", Cell { value: CassandraValue::Text("x".repeat(4096)), timestamp: 1, ttl: None, is_deleted: false });
+                memtable.put(Row {
+                    partition_key: PartitionKey { components: vec![CassandraValue::Int(sensor)] },
+                    clustering_key: Some(ClusteringKey { components: vec![CassandraValue::Int(ts)] }),
+                    cells,
+                    timestamp: 1,
+                }).unwrap();
+            }
+        }
+        SSTable::create_from_memtable(&memtable, directory, CompressionType::None).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_scrub_salvages_rows_around_a_corrupt_chunk_and_quarantines_original() {
+        let directory = std::env::temp_dir().join("coredb_test_scrub").join("test_ks").join("events");
+        let _ = tokio::fs::remove_dir_all(&directory).await;
+        tokio::fs::create_dir_all(&directory).await.unwrap();
+
+        let (mut manifest, _) = Manifest::open_table(&directory).await.unwrap();
+        let sstable = flush_wide_partitions(&directory).await;
+        manifest.record_compaction(&[], std::slice::from_ref(&sstable), 2).await.unwrap();
+
+        // 손상이 없으면 아무것도 바꾸지 않음
+        let reports = scrub_table(&directory).await.unwrap();
+        assert_eq!(reports.len(), 1);
+        assert!(reports[0].stats.is_clean() && reports[0].quarantined.is_none());
+        assert!(reports[0].to_string().contains("clean, 3 partitions"));
+
+        // 가운데 파티션 중간의 청크 하나 손상
+        let (entries, _) = sstable.index_entries().await.unwrap();
+        let (_, offset, length) = entries[1].clone();
+        let chunk = sstable.compression_info.chunk_index(offset + length / 2);
+        let chunk_start = sstable.compression_info.chunk_offsets[chunk];
+        let mut data = tokio::fs::read(&sstable.file_path).await.unwrap();
+        data[chunk_start as usize + 5] ^= 0x01;
+        tokio::fs::write(&sstable.file_path, data).await.unwrap();
+
+        let reports = scrub_table(&directory).await.unwrap();
+        let report = &reports[0];
+        assert_eq!(report.stats.bad_chunks, 1);
+        assert_eq!((report.stats.partitions_intact, report.stats.partitions_salvaged, report.stats.partitions_lost), (2, 1, 0));
+        assert!(report.stats.rows > 80 && report.stats.rows < 120);
+
+        // 원본은 지우지 않고 격리, 새 SSTable은 같은 레벨로 교체
+        let quarantine = directory.join(QUARANTINE_DIRECTORY);
+        assert_eq!(report.quarantined.as_deref(), Some(quarantine.as_path()));
+        assert!(!sstable.file_path.exists());
+        assert!(SSTable::component_path(&quarantine, &sstable.id, DATA_FILE_SUFFIX).exists());
+        let (reopened, sstables) = Manifest::open_table(&directory).await.unwrap();
+        assert_eq!(sstables.len(), 1);
+        let scrubbed = &sstables[0];
+        assert_eq!(Some(scrubbed.id.clone()), report.replacement);
+        assert_eq!(reopened.level(&scrubbed.id), Some(2));
+        assert!(reopened.orphans().await.unwrap().is_empty());
+
+        // 손상 앞의 행은 순서대로 남고 뒤의 행은 없음
+        let salvaged = scrubbed.read_partition(&PartitionKey { components: vec![CassandraValue::Int(1)] }).await.unwrap().unwrap();
+        let kept: Vec<_> = salvaged.rows.iter().map(|row| row.key().clone()).collect();
+        let expected: Vec<_> = (0..kept.len() as i32).map(|ts| Some(ClusteringKey { components: vec![CassandraValue::Int(ts)] })).collect();
+        assert!(!kept.is_empty() && kept.len() < 40);
+        assert_eq!(kept, expected);
+        assert_eq!(scrubbed.read_partition(&PartitionKey { components: vec![CassandraValue::Int(2)] }).await.unwrap().unwrap().rows.len(), 40);
+        assert!(scrub_table(&directory).await.unwrap()[0].stats.is_clean());
+
+        tokio::fs::remove_dir_all(directory.parent().unwrap().parent().unwrap()).await.unwrap();
+    }
+}
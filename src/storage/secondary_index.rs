@@ -0,0 +1,320 @@
+use std::collections::{BTreeMap, BTreeSet};
+use crate::error::Result;
+use crate::schema::{CassandraValue, ClusteringKey, PartitionKey, Row};
+#[cfg(feature = "fulltext")]
+use super::FullTextIndex;
+
+/// 인덱스가 가리키는 행 위치
+pub type IndexedKey = (PartitionKey, Option<ClusteringKey>);
+
+/// 인덱스 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexKind {
+    /// 값 전체 일치 (CREATE INDEX)
+    Standard,
+    /// 텍스트 토큰 단위 역색인 (CREATE CUSTOM INDEX ... USING 'fulltext', `fulltext` 기능이면 tantivy로 디스크에 유지)
+    FullText,
+    /// 벡터 근사 최근접 이웃 (CREATE CUSTOM INDEX ... USING 'hnsw', VectorIndex 사용)
+    Vector,
+}
+
+/// 단일 컬럼 보조 인덱스 (값 또는 토큰 → 행 키 집합)
+#[derive(Debug)]
+pub struct SecondaryIndex {
+    pub name: String,
+    pub column: String,
+    pub kind: IndexKind,
+    /// 값의 비교용 바이트 표현(전문 인덱스는 토큰) → 키 (키 순서로 정렬되어 페이지네이션에 사용)
+    ///
+    /// tantivy 인덱스가 있으면 마지막 commit 뒤의 쓰기만 담음
+    entries: BTreeMap<Vec<u8>, BTreeSet<IndexedKey>>,
+    /// 디스크에 유지하는 전문 인덱스
+    #[cfg(feature = "fulltext")]
+    persisted: Option<FullTextIndex>,
+    /// 마지막 commit 뒤에 바뀐 행 (tantivy의 커밋된 결과에서 제외하고 entries로 대신함)
+    #[cfg(feature = "fulltext")]
+    touched: BTreeSet<IndexedKey>,
+}
+
+/// 전문 검색용 토큰화: 영숫자 단위로 나누고 소문자로 정규화
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_lowercase())
+        .collect()
+}
+
+impl SecondaryIndex {
+    pub fn new(name: String, column: String) -> Self {
+        Self::with_kind(name, column, IndexKind::Standard)
+    }
+
+    pub fn with_kind(name: String, column: String, kind: IndexKind) -> Self {
+        Self {
+            name,
+            column,
+            kind,
+            entries: BTreeMap::new(),
+            #[cfg(feature = "fulltext")]
+            persisted: None,
+            #[cfg(feature = "fulltext")]
+            touched: BTreeSet::new(),
+        }
+    }
+
+    /// tantivy로 디스크(directory가 없으면 메모리)에 유지하는 전문 인덱스를 열거나 만듦
+    #[cfg(feature = "fulltext")]
+    pub fn open_fulltext(name: String, column: String, directory: Option<&std::path::Path>) -> Result<Self> {
+        let mut index = Self::with_kind(name, column, IndexKind::FullText);
+        index.persisted = Some(FullTextIndex::open(directory)?);
+        Ok(index)
+    }
+
+    /// 디스크 인덱스가 있고 마지막 commit이 주어진 SSTable들까지 반영한 것인지 (메모리 인덱스는 항상 false)
+    pub fn covers(&self, _sstables: &[String]) -> bool {
+        #[cfg(feature = "fulltext")]
+        if let Some(persisted) = &self.persisted {
+            return persisted.covers(_sstables);
+        }
+        false
+    }
+
+    /// 디스크 인덱스가 이미 commit된 적이 있는지
+    pub fn is_committed(&self) -> bool {
+        #[cfg(feature = "fulltext")]
+        if let Some(persisted) = &self.persisted {
+            return persisted.is_committed();
+        }
+        false
+    }
+
+    /// 쓰기 시 인덱스 갱신 (이전 버전의 값이 있으면 제거)
+    pub fn update(&mut self, previous: Option<&Row>, current: &Row) -> Result<()> {
+        let key = (current.partition_key.clone(), current.clustering_key.clone());
+
+        if let Some(old_cell) = previous.and_then(|row| row.cells.get(&self.column)) {
+            self.remove_entry(&old_cell.value, &key);
+        }
+
+        let live = current.cells.get(&self.column)
+            .filter(|cell| !cell.is_deleted && cell.value != CassandraValue::Null);
+        if let Some(cell) = live {
+            for term in self.terms(&cell.value) {
+                self.entries.entry(term).or_default().insert(key.clone());
+            }
+        }
+
+        #[cfg(feature = "fulltext")]
+        if let Some(persisted) = self.persisted.as_mut() {
+            let text = live.and_then(|cell| match &cell.value {
+                CassandraValue::Text(text) => Some(text.as_str()),
+                _ => None,
+            });
+            persisted.upsert(&key, text)?;
+            self.touched.insert(key);
+        }
+        Ok(())
+    }
+
+    /// 행 삭제 시 인덱스 항목 제거
+    pub fn remove(&mut self, row: &Row) -> Result<()> {
+        let key = (row.partition_key.clone(), row.clustering_key.clone());
+        if let Some(cell) = row.cells.get(&self.column) {
+            self.remove_entry(&cell.value, &key);
+        }
+        #[cfg(feature = "fulltext")]
+        if let Some(persisted) = self.persisted.as_mut() {
+            persisted.upsert(&key, None)?;
+            self.touched.insert(key);
+        }
+        Ok(())
+    }
+
+    /// 주어진 행(sstables와 메모리 테이블의 현재 행)으로 인덱스를 처음부터 다시 채움 (디스크 인덱스는 바로 commit)
+    pub fn rebuild(&mut self, rows: &[Row], sstables: &[String]) -> Result<()> {
+        self.entries.clear();
+        #[cfg(feature = "fulltext")]
+        if let Some(persisted) = self.persisted.as_mut() {
+            persisted.clear()?;
+        }
+        for row in rows {
+            self.update(None, row)?;
+        }
+        self.commit(sstables)
+    }
+
+    /// 디스크 인덱스에 쌓인 쓰기를 남김 (테이블 플러시 때 호출, 메모리 인덱스는 할 일 없음)
+    ///
+    /// sstables는 지금까지의 쓰기를 모두 담은 SSTable (다시 열 때 covers로 확인)
+    pub fn commit(&mut self, _sstables: &[String]) -> Result<()> {
+        #[cfg(feature = "fulltext")]
+        if let Some(persisted) = self.persisted.as_mut() {
+            persisted.commit(_sstables)?;
+            self.entries.clear();
+            self.touched.clear();
+        }
+        Ok(())
+    }
+
+    /// 값과 일치하는 행 키를 키 순서로 반환
+    pub fn lookup(&self, value: &CassandraValue) -> Vec<IndexedKey> {
+        self.entries
+            .get(&value.comparable_bytes())
+            .map(|keys| keys.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// 전문 검색: 질의의 모든 토큰을 포함하는 행 키를 키 순서로 반환
+    pub fn search(&self, query: &str) -> Result<Vec<IndexedKey>> {
+        let tokens = tokenize(query);
+        let found = self.search_entries(&tokens);
+        // 커밋된 결과 중 그 뒤에 바뀐 행은 entries의 결과로 대신함
+        #[cfg(feature = "fulltext")]
+        let found = match &self.persisted {
+            Some(persisted) => {
+                let mut found = found;
+                found.extend(persisted.search(&tokens)?.into_iter().filter(|key| !self.touched.contains(key)));
+                found
+            },
+            None => found,
+        };
+        Ok(found.into_iter().collect())
+    }
+
+    fn search_entries(&self, tokens: &[String]) -> BTreeSet<IndexedKey> {
+        let mut result: Option<BTreeSet<IndexedKey>> = None;
+        for token in tokens {
+            let keys = match self.entries.get(token.as_bytes()) {
+                Some(keys) => keys,
+                None => return BTreeSet::new(),
+            };
+            result = Some(match result {
+                Some(found) => found.intersection(keys).cloned().collect(),
+                None => keys.clone(),
+            });
+        }
+        result.unwrap_or_default()
+    }
+
+    /// 인덱싱된 서로 다른 값(전문 인덱스는 토큰)의 개수 (디스크 인덱스는 아직 commit하지 않은 쓰기만 셈)
+    pub fn value_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 값이 인덱스에 저장되는 항목 키
+    fn terms(&self, value: &CassandraValue) -> Vec<Vec<u8>> {
+        match (self.kind, value) {
+            (IndexKind::FullText, CassandraValue::Text(text)) => tokenize(text)
+                .into_iter()
+                .map(String::into_bytes)
+                .collect(),
+            (IndexKind::FullText, _) | (IndexKind::Vector, _) => Vec::new(),
+            (IndexKind::Standard, value) => vec![value.comparable_bytes()],
+        }
+    }
+
+    fn remove_entry(&mut self, value: &CassandraValue, key: &IndexedKey) {
+        for term in self.terms(value) {
+            if let Some(keys) = self.entries.get_mut(&term) {
+                keys.remove(key);
+                if keys.is_empty() {
+                    self.entries.remove(&term);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{Cell, Cells};
+
+    fn row(id: i32, city: &str) -> Row {
+        let mut cells = Cells::new();
+        cells.insert("city", Cell {
+            value: CassandraValue::Text(city.to_string()),
+            timestamp: 1,
+            ttl: None,
+            is_deleted: false,
+        });
+        Row {
+            partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] },
+            clustering_key: None,
+            cells,
+            timestamp: 1,
+        }
+    }
+
+    #[test]
+    fn test_index_update_replaces_old_value() {
+        let mut index = SecondaryIndex::new("users_city_idx".to_string(), "city".to_string());
+
+        let seoul = row(1, "Seoul");
+        index.update(None, &seoul).unwrap();
+        index.update(None, &row(2, "Seoul")).unwrap();
+        assert_eq!(index.lookup(&CassandraValue::Text("Seoul".to_string())).len(), 2);
+
+        // 값이 바뀌면 이전 항목은 제거되어야 함
+        index.update(Some(&seoul), &row(1, "Busan")).unwrap();
+        let seoul_keys = index.lookup(&CassandraValue::Text("Seoul".to_string()));
+        assert_eq!(seoul_keys.len(), 1);
+        assert_eq!(seoul_keys[0].0.components, vec![CassandraValue::Int(2)]);
+        assert_eq!(index.lookup(&CassandraValue::Text("Busan".to_string())).len(), 1);
+        assert_eq!(index.value_count(), 2);
+    }
+
+    #[test]
+    fn test_fulltext_index_matches_all_tokens() {
+        let mut index = SecondaryIndex::with_kind("posts_city_idx".to_string(), "city".to_string(), IndexKind::FullText);
+
+        let first = row(1, "Seoul, the capital city");
+        index.update(None, &first).unwrap();
+        index.update(None, &row(2, "Busan port city")).unwrap();
+
+        assert_eq!(index.search("CITY").unwrap().len(), 2);
+        assert_eq!(index.search("capital city").unwrap().len(), 1);
+        assert!(index.search("capital port").unwrap().is_empty());
+        assert!(index.search("").unwrap().is_empty());
+
+        // 값이 바뀌면 이전 토큰은 제거되어야 함
+        index.update(Some(&first), &row(1, "Incheon harbor")).unwrap();
+        assert!(index.search("capital").unwrap().is_empty());
+        assert_eq!(index.search("harbor").unwrap().len(), 1);
+    }
+
+    #[cfg(feature = "fulltext")]
+    #[test]
+    fn test_persisted_fulltext_index_survives_reopen() {
+        let directory = std::env::temp_dir().join("coredb_test_persisted_fulltext");
+        let _ = std::fs::remove_dir_all(&directory);
+
+        let sstables = vec!["sstable-1".to_string()];
+        let mut index = SecondaryIndex::open_fulltext("posts_city_idx".to_string(), "city".to_string(), Some(&directory)).unwrap();
+        assert!(!index.is_committed());
+        let first = row(1, "Seoul, the capital city");
+        index.update(None, &first).unwrap();
+        index.update(None, &row(2, "Busan port city")).unwrap();
+        // commit 전에도 검색에 보임
+        assert_eq!(index.search("city").unwrap().len(), 2);
+        index.commit(&sstables).unwrap();
+        assert_eq!(index.value_count(), 0);
+        assert_eq!(index.search("city").unwrap().len(), 2);
+
+        // commit 뒤에 바뀐 행은 커밋된 이전 문서 대신 새 값으로 검색됨
+        index.update(Some(&first), &row(1, "Incheon harbor")).unwrap();
+        assert!(index.search("capital").unwrap().is_empty());
+        assert_eq!(index.search("harbor").unwrap().len(), 1);
+        assert_eq!(index.search("city").unwrap().len(), 1);
+        drop(index);
+
+        // 다시 열면 마지막 commit까지만 남음
+        let index = SecondaryIndex::open_fulltext("posts_city_idx".to_string(), "city".to_string(), Some(&directory)).unwrap();
+        assert!(index.covers(&sstables));
+        assert!(!index.covers(&["sstable-2".to_string()]));
+        assert_eq!(index.search("capital").unwrap().len(), 1);
+        assert!(index.search("harbor").unwrap().is_empty());
+
+        std::fs::remove_dir_all(&directory).ok();
+    }
+}
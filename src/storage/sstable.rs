@@ -1,11 +1,20 @@
-use std::path::PathBuf;
-use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::collections::{BTreeMap, HashMap};
 use tokio::fs::File;
-use tokio::io::{AsyncReadExt, AsyncWriteExt, SeekFrom, AsyncSeekExt};
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufWriter, SeekFrom, AsyncSeekExt};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 use serde::{Serialize, Deserialize};
-use crate::schema::{PartitionKey, Row};
-use crate::storage::{Memtable, BloomFilter};
+use crate::schema::{CassandraValue, PartitionKey, ClusteringKey, Row, Cell, Cells, ColumnLayout};
+use crate::storage::{Memtable, BloomFilter, ReadOptions, ScanDirection, ClusteringSlice, CompressionInfo, DEFAULT_CHUNK_LENGTH};
+use crate::storage::compression_info::{DictionaryTrainer, LegacyCompressionInfo, DICTIONARY_COMPRESSION_VERSION, DICTIONARY_TRAINING_BYTES};
+use crate::storage::{with_checksum, verify_checksum, strip_checksum};
+use crate::storage::{ComponentReader, DiskAccessMode, AccessPattern, SSTableStatistics, KeyCache, ChunkCache};
+use crate::storage::statistics::{LegacySSTableStatistics, DELETION_TIME_STATISTICS_VERSION};
+use crate::fault_injection::{self, FaultPoint};
+use crate::storage::{CellView, encode_cell, FIXED_LAYOUT_CELLS_VERSION, DataFileWriter, DiskWriteMode};
 use crate::storage::memtable::Partition;
 use crate::error::*;
 
@@ -16,303 +25,1544 @@ pub enum CompressionType {
     LZ4,
     Snappy,
     ZSTD,
+    /// SSTable마다 학습한 zstd 사전으로 압축 (반복되는 문자열이 많은 테이블용)
+    ZSTDDictionary,
 }
 
+/// 현재 데이터 파일 형식 버전
+pub const SSTABLE_FORMAT_VERSION: u32 = 14;
+/// BIGINT/TIMESTAMP 클러스터링 키를 파티션 첫 키와의 차이로 기록하기 시작한 형식 버전
+pub const DELTA_CLUSTERING_KEYS_VERSION: u32 = 14;
+/// 열 수 있는 가장 오래된 형식 버전 (현재 버전보다 낮으면 시작할 때 다시 씀)
+pub const MIN_SUPPORTED_FORMAT_VERSION: u32 = 9;
+
+/// 데이터 파일 이름 접미사 (<id>-Data.db)
+pub const DATA_FILE_SUFFIX: &str = "-Data.db";
+/// 파티션 인덱스 파일 이름 접미사 (<id>-Index.db)
+pub const INDEX_FILE_SUFFIX: &str = "-Index.db";
+/// 요약 인덱스 파일 이름 접미사 (<id>-Summary.db)
+pub const SUMMARY_FILE_SUFFIX: &str = "-Summary.db";
+/// 블룸 필터 파일 이름 접미사 (<id>-Filter.db)
+pub const FILTER_FILE_SUFFIX: &str = "-Filter.db";
+/// 압축 청크 정보 파일 이름 접미사 (<id>-CompressionInfo.db)
+pub const COMPRESSION_INFO_FILE_SUFFIX: &str = "-CompressionInfo.db";
+/// 통계 파일 이름 접미사 (<id>-Statistics.db)
+pub const STATISTICS_FILE_SUFFIX: &str = "-Statistics.db";
+/// SSTable을 이루는 파일들의 접미사
+pub const COMPONENT_SUFFIXES: &[&str] = &[
+    DATA_FILE_SUFFIX,
+    INDEX_FILE_SUFFIX,
+    SUMMARY_FILE_SUFFIX,
+    FILTER_FILE_SUFFIX,
+    COMPRESSION_INFO_FILE_SUFFIX,
+    STATISTICS_FILE_SUFFIX,
+];
+
+/// 요약 인덱스 샘플 간격 (인덱스 항목 수)
+const SUMMARY_SAMPLE_RATE: u64 = 128;
+
+/// 스캐너가 미리 읽어 두는 파티션 수
+const SCANNER_PREFETCH_PARTITIONS: usize = 16;
+
+/// 행 헤더 태그: bincode로 기록한 (클러스터링 키, 타임스탬프)
+const ROW_HEADER_BINCODE: u8 = 0;
+/// 행 헤더 태그: BIGINT 클러스터링 키 하나를 차이로 기록
+const ROW_HEADER_BIGINT_DELTA: u8 = 1;
+/// 행 헤더 태그: TIMESTAMP 클러스터링 키 하나를 차이로 기록
+const ROW_HEADER_TIMESTAMP_DELTA: u8 = 2;
+
 /// SSTable 구조
 #[derive(Debug, Clone, PartialEq)]
 pub struct SSTable {
     pub id: String,
     pub file_path: PathBuf,
+    /// 파티션 인덱스 파일 (파티션 키 → 압축 전 레코드 오프셋과 길이, 키 순서)
+    pub index_file_path: PathBuf,
+    pub compression_info_path: PathBuf,
+    /// 데이터 파일 형식 버전 (푸터)
+    pub format_version: u32,
+    /// 압축 청크 위치 (압축 전 오프셋 → 데이터 파일 오프셋)
+    pub compression_info: CompressionInfo,
     pub bloom_filter: BloomFilter,
-    pub partition_index: BTreeMap<PartitionKey, u64>, // 파티션 -> 파일 오프셋
-    pub summary_index: BTreeMap<PartitionKey, u64>,   // 파티션 인덱스의 샘플
+    /// 인덱스 파일의 샘플 (파티션 키 → 인덱스 파일 오프셋), 메모리에는 이것만 유지
+    pub summary_index: BTreeMap<PartitionKey, u64>,
+    pub partition_count: u64,
+    /// 인덱스 파일 크기
+    pub index_size_bytes: u64,
     pub min_timestamp: i64,
     pub max_timestamp: i64,
+    /// 모든 행의 클러스터링 키 최소/최대
+    pub clustering_bounds: ClusteringBounds,
+    /// 마지막 파티션 키 (첫 키는 요약 인덱스의 첫 항목)
+    pub last_key: Option<PartitionKey>,
+    /// 행/셀/툼스톤 수와 파티션 크기 분포
+    pub statistics: SSTableStatistics,
     pub compression: CompressionType,
     pub size_bytes: u64,
+    /// 읽기 경로에서 쓰는 파일 읽기 방식
+    pub access_mode: DiskAccessMode,
+    /// 파티션 키 → 레코드 위치 캐시 (SSTable들이 공유)
+    pub key_cache: Option<Arc<KeyCache>>,
+    /// 압축 해제된 청크 캐시 (SSTable들이 공유)
+    pub chunk_cache: Option<Arc<ChunkCache>>,
+}
+
+/// 인덱스 파일에서 찾은 파티션 레코드 위치 (압축 전 오프셋과 길이, 파티션 클러스터링 최소/최대)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordPosition {
+    pub offset: u64,
+    pub length: u64,
+    pub clustering_bounds: ClusteringBounds,
+}
+
+/// 클러스터링 키 최소/최대 (SSTable 전체와 파티션마다 기록)
+///
+/// 클러스터링 키가 있는 행이 없으면 둘 다 None이고 어떤 범위와도 겹칠 수 있다고 봄
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClusteringBounds {
+    pub min: Option<ClusteringKey>,
+    pub max: Option<ClusteringKey>,
+}
+
+impl ClusteringBounds {
+    /// 행의 클러스터링 키 반영
+    pub fn include(&mut self, clustering_key: &ClusteringKey) {
+        if self.min.as_ref().is_none_or(|min| clustering_key < min) {
+            self.min = Some(clustering_key.clone());
+        }
+        if self.max.as_ref().is_none_or(|max| clustering_key > max) {
+            self.max = Some(clustering_key.clone());
+        }
+    }
+    
+    /// 다른 최소/최대 합치기
+    pub fn extend(&mut self, other: &ClusteringBounds) {
+        for clustering_key in other.min.iter().chain(other.max.iter()) {
+            self.include(clustering_key);
+        }
+    }
+    
+    /// 범위 안의 행이 있을 수 있는지
+    pub fn may_overlap(&self, slice: &ClusteringSlice) -> bool {
+        match (&self.min, &self.max) {
+            (Some(min), Some(max)) => slice.overlaps(min, max),
+            _ => true,
+        }
+    }
 }
 
-/// SSTable 헤더
+/// SSTable 푸터 (데이터 파일 끝, 뒤에 푸터 자체의 CRC32C와 푸터 길이(u32)가 붙음)
 #[derive(Debug, Serialize, Deserialize)]
-struct SSTableHeader {
+struct SSTableFooter {
     pub version: u32,
     pub compression: CompressionType,
     pub min_timestamp: i64,
     pub max_timestamp: i64,
+    pub clustering_bounds: ClusteringBounds,
     pub partition_count: u64,
-    pub bloom_filter_offset: u64,
-    pub partition_index_offset: u64,
-    pub summary_index_offset: u64,
+    /// 압축된 청크 영역 길이 (푸터 시작 위치)
+    pub data_length: u64,
 }
 
-impl SSTable {
-    /// Memtable에서 SSTable 생성
-    pub async fn create_from_memtable(
-        memtable: &Memtable,
-        base_dir: &PathBuf,
-        compression: CompressionType
-    ) -> Result<Self> {
-        let sstable_id = Uuid::new_v4().to_string();
-        let data_file_path = base_dir.join(format!("{}-Data.db", sstable_id));
+/// 압축 전 스트림을 고정 크기 청크로 잘라 압축해 쓰는 작성기 (버퍼는 청크 하나 크기)
+///
+/// 사전 압축이면 set_dictionary를 부를 때까지 청크를 압축하지 않고 모아 둠
+struct ChunkedWriter {
+    file: DataFileWriter,
+    info: CompressionInfo,
+    buffer: Vec<u8>,
+    /// 지금까지 받은 압축 전 길이
+    position: u64,
+    /// 사전 학습을 기다리는 청크 (사전 압축이고 아직 학습 전일 때만 Some)
+    held_chunks: Option<Vec<Vec<u8>>>,
+}
+
+impl ChunkedWriter {
+    fn new(file: DataFileWriter, compression: CompressionType) -> Self {
+        Self {
+            file,
+            info: CompressionInfo::new(compression, DEFAULT_CHUNK_LENGTH, 0),
+            buffer: Vec::with_capacity(DEFAULT_CHUNK_LENGTH as usize),
+            position: 0,
+            held_chunks: (compression == CompressionType::ZSTDDictionary).then(Vec::new),
+        }
+    }
+    
+    fn is_training(&self) -> bool {
+        self.held_chunks.is_some()
+    }
+    
+    /// 사전을 정하고 모아 둔 청크를 압축해 씀 (빈 사전이면 일반 zstd)
+    async fn set_dictionary(&mut self, dictionary: Vec<u8>) -> Result<()> {
+        self.info.dictionary = dictionary;
+        for chunk in self.held_chunks.take().unwrap_or_default() {
+            let compressed = self.info.append_chunk(&chunk)?;
+            self.file.write_all(&compressed).await?;
+        }
+        Ok(())
+    }
+    
+    async fn write(&mut self, mut bytes: &[u8]) -> Result<()> {
+        let chunk_length = self.info.chunk_length as usize;
+        self.position += bytes.len() as u64;
+        while !bytes.is_empty() {
+            let take = (chunk_length - self.buffer.len()).min(bytes.len());
+            self.buffer.extend_from_slice(&bytes[..take]);
+            bytes = &bytes[take..];
+            if self.buffer.len() == chunk_length {
+                self.flush_chunk().await?;
+            }
+        }
+        Ok(())
+    }
+    
+    async fn flush_chunk(&mut self) -> Result<()> {
+        if let Some(held_chunks) = &mut self.held_chunks {
+            held_chunks.push(std::mem::replace(&mut self.buffer, Vec::with_capacity(DEFAULT_CHUNK_LENGTH as usize)));
+            return Ok(());
+        }
+        let compressed = self.info.append_chunk(&self.buffer)?;
+        self.file.write_all(&compressed).await?;
+        self.buffer.clear();
+        Ok(())
+    }
+    
+    /// 남은 청크를 쓰고 파일과 청크 정보 반환
+    async fn finish(mut self) -> Result<(DataFileWriter, CompressionInfo)> {
+        if !self.buffer.is_empty() {
+            self.flush_chunk().await?;
+        }
+        Ok((self.file, self.info))
+    }
+}
+
+/// SSTable 스트리밍 작성기
+///
+/// 파티션을 키 순서로 하나씩 받아 데이터 청크, 인덱스 항목, 요약 샘플을 바로 각 파일에 씀.
+/// 메모리에는 청크 버퍼와 행 하나, 블룸 필터와 요약 샘플만 남고 푸터는 데이터 파일 끝에 씀
+pub struct SSTableWriter {
+    id: String,
+    base_dir: PathBuf,
+    compression: CompressionType,
+    data: ChunkedWriter,
+    index: BufWriter<File>,
+    summary: BufWriter<File>,
+    bloom_filter: BloomFilter,
+    summary_index: BTreeMap<PartitionKey, u64>,
+    last_key: Option<PartitionKey>,
+    partition_count: u64,
+    index_offset: u64,
+    min_timestamp: i64,
+    max_timestamp: i64,
+    clustering_bounds: ClusteringBounds,
+    statistics: SSTableStatistics,
+    /// 사전 압축에서 학습 전까지 모으는 텍스트 값 표본
+    dictionary_trainer: Option<DictionaryTrainer>,
+}
+
+impl SSTableWriter {
+    /// 새 SSTable 파일 생성 (expected_partitions는 블룸 필터 크기 계산용)
+    pub async fn new(base_dir: &Path, compression: CompressionType, expected_partitions: u64) -> Result<Self> {
+        Self::with_write_mode(base_dir, compression, expected_partitions, DiskWriteMode::Buffered).await
+    }
+    
+    /// 데이터 파일 쓰기 방식을 지정해 생성 (인덱스와 나머지 구성 파일은 항상 버퍼 쓰기)
+    pub async fn with_write_mode(base_dir: &Path, compression: CompressionType, expected_partitions: u64, write_mode: DiskWriteMode) -> Result<Self> {
+        let id = Uuid::new_v4().to_string();
+        let create = |suffix| File::create(SSTable::component_path(base_dir, &id, suffix));
+        let data_file = DataFileWriter::create(&SSTable::component_path(base_dir, &id, DATA_FILE_SUFFIX), write_mode).await?;
+        let data = ChunkedWriter::new(data_file, compression);
+        let index = BufWriter::new(create(INDEX_FILE_SUFFIX).await?);
+        let summary = BufWriter::new(create(SUMMARY_FILE_SUFFIX).await?);
         
-        let mut data_file = File::create(&data_file_path).await?;
+        Ok(Self {
+            base_dir: base_dir.to_path_buf(),
+            compression,
+            data,
+            index,
+            summary,
+            bloom_filter: BloomFilter::new(expected_partitions, 0.01),
+            summary_index: BTreeMap::new(),
+            last_key: None,
+            partition_count: 0,
+            index_offset: 0,
+            min_timestamp: i64::MAX,
+            max_timestamp: i64::MIN,
+            clustering_bounds: ClusteringBounds::default(),
+            statistics: SSTableStatistics::default(),
+            dictionary_trainer: (compression == CompressionType::ZSTDDictionary).then(DictionaryTrainer::default),
+            id,
+        })
+    }
+    
+    /// 지금까지 쓴 압축 전 데이터 크기
+    pub fn data_length(&self) -> u64 {
+        self.data.position
+    }
+    
+    /// 파티션 추가 (키 순서대로)
+    pub async fn append(&mut self, partition_key: &PartitionKey, partition: &Partition) -> Result<()> {
+        if self.last_key.as_ref().is_some_and(|last| last >= partition_key) {
+            return Err(CoreDBError::Generic {
+                message: format!("Partitions must be appended in key order: {:?} after {:?}", partition_key, self.last_key),
+            });
+        }
+        self.bloom_filter.add(partition_key);
         
-        let mut bloom_filter = BloomFilter::new(
-            memtable.partition_count() as u64, 
-            0.01
-        );
+        let start = self.data.position;
+        let bounds = self.write_partition(partition_key, partition).await?;
+        let length = self.data.position - start;
+        self.clustering_bounds.extend(&bounds);
+        self.statistics.partition_sizes.add(length);
         
-        let mut partition_index = BTreeMap::new();
-        let mut current_offset = 0u64;
-        let mut min_timestamp = i64::MAX;
-        let mut max_timestamp = i64::MIN;
-        let mut total_size = 0u64;
-        
-        // 헤더 공간 예약 (나중에 업데이트)
-        let header_size = bincode::serialized_size(&SSTableHeader {
-            version: 1,
-            compression: CompressionType::None,
-            min_timestamp: 0,
-            max_timestamp: 0,
-            partition_count: 0,
-            bloom_filter_offset: 0,
-            partition_index_offset: 0,
-            summary_index_offset: 0,
-        })? as u64;
+        // 인덱스 항목 (파티션 클러스터링 최소/최대 포함), 일정 간격마다 요약 샘플
+        if self.partition_count.is_multiple_of(SUMMARY_SAMPLE_RATE) {
+            self.summary.write_all(&keyed_entry(partition_key, &[self.index_offset], &[])?).await?;
+            self.summary_index.insert(partition_key.clone(), self.index_offset);
+        }
+        let index_entry = keyed_entry(partition_key, &[start, length], &bincode::serialize(&bounds)?)?;
+        self.index.write_all(&index_entry).await?;
+        self.index_offset += index_entry.len() as u64;
+        self.partition_count += 1;
+        self.last_key = Some(partition_key.clone());
+        if self.data.position >= DICTIONARY_TRAINING_BYTES {
+            self.train_dictionary().await?;
+        }
+        Ok(())
+    }
+    
+    /// 모은 표본으로 사전을 학습하고 기다리던 청크를 압축 (사전 압축이 아니거나 이미 학습했으면 무시)
+    async fn train_dictionary(&mut self) -> Result<()> {
+        let Some(trainer) = self.dictionary_trainer.take() else { return Ok(()) };
+        let dictionary = tokio::task::spawn_blocking(move || trainer.train()).await
+            .map_err(|e| CoreDBError::Generic { message: format!("Dictionary training failed: {}", e) })?;
+        if dictionary.is_empty() {
+            tracing::debug!("No zstd dictionary for SSTable {}, compressing without one", self.id);
+        }
+        self.data.set_dictionary(dictionary).await
+    }
+    
+    /// 파티션 레코드를 행 단위로 청크 스트림에 기록
+    ///
+    /// 컬럼 이름은 파티션 앞에 한 번만 기록하고 셀은 컬럼 번호와 길이를 앞에 붙여,
+    /// 읽을 때 필요 없는 셀은 해석 없이 건너뛸 수 있도록 함. 셀은 고정 배치(encode_cell)로 기록.
+    /// 파티션의 클러스터링 최소/최대 반환
+    async fn write_partition(&mut self, partition_key: &PartitionKey, partition: &Partition) -> Result<ClusteringBounds> {
+        let mut data = Vec::new();
         
-        current_offset += header_size;
+        // 파티션 키
+        write_framed(&mut data, &bincode::serialize(partition_key)?);
         
-        // 파티션별로 정렬하여 SSTable에 쓰기
-        let mut partitions = memtable.get_all_partitions();
-        partitions.sort_by(|a, b| a.0.cmp(&b.0));
+        // Static 컬럼들 (이름 프레임과 셀 프레임 쌍)
+        let mut cell_data = Vec::new();
+        let mut static_columns = Vec::new();
+        for (name, cell) in &partition.static_columns {
+            write_framed(&mut static_columns, name.as_bytes());
+            cell_data.clear();
+            encode_cell(cell, &mut cell_data)?;
+            write_framed(&mut static_columns, &cell_data);
+            self.statistics.add_cell(cell);
+            if let Some(trainer) = &mut self.dictionary_trainer {
+                trainer.add(name, cell);
+            }
+        }
+        write_framed(&mut data, &static_columns);
         
-        for (partition_key, partition) in partitions {
-            // 블룸 필터에 파티션 키 추가
-            bloom_filter.add(&partition_key);
-            
-            // 파티션 인덱스 업데이트
-            partition_index.insert(partition_key.clone(), current_offset);
-            
-            // 파티션 데이터 직렬화 및 압축
-            let partition_data = Self::serialize_partition(&partition, &compression).await?;
-            
-            // 데이터 파일에 쓰기
-            data_file.write_u32(partition_data.len() as u32).await?;
-            data_file.write_all(&partition_data).await?;
+        // 파티션 컬럼 사전 (행들이 쓰는 컬럼 이름을 번호 순서로)
+        let mut columns = ColumnLayout::default();
+        for row_entry in partition.rows.iter() {
+            for column in row_entry.value().cells.keys() {
+                columns.push(column);
+            }
+        }
+        let names: Vec<&str> = columns.names().collect();
+        write_framed(&mut data, &bincode::serialize(&names)?);
+        
+        data.extend_from_slice(&(partition.rows.len() as u32).to_be_bytes());
+        self.data.write(&data).await?;
+        
+        // 행들 직렬화 (SkipMap 순회는 클러스터링 키 순서)
+        let mut bounds = ClusteringBounds::default();
+        let mut header = Vec::new();
+        let mut delta_base = None;
+        for row_entry in partition.rows.iter() {
+            let row = row_entry.value();
+            if let Some(clustering_key) = &row.clustering_key {
+                bounds.include(clustering_key);
+            }
+            self.statistics.add_row(row);
+            data.clear();
+            header.clear();
+            encode_row_header(&mut header, &row.clustering_key, row.timestamp, &mut delta_base)?;
+            write_framed(&mut data, &header);
             
-            let partition_size = 4 + partition_data.len() as u64;
-            current_offset += partition_size;
-            total_size += partition_size;
+            data.extend_from_slice(&(row.cells.len() as u32).to_be_bytes());
+            for (column, cell) in &row.cells {
+                let id = columns.id(column).expect("column added to partition dictionary");
+                data.extend_from_slice(&id.to_be_bytes());
+                cell_data.clear();
+                encode_cell(cell, &mut cell_data)?;
+                write_framed(&mut data, &cell_data);
+                self.statistics.add_cell(cell);
+                if let Some(trainer) = &mut self.dictionary_trainer {
+                    trainer.add(column, cell);
+                }
+            }
+            self.data.write(&data).await?;
             
-            // 타임스탬프 범위 업데이트
-            for row_entry in partition.rows.iter() {
-                let row = row_entry.value();
-                min_timestamp = min_timestamp.min(row.timestamp);
-                max_timestamp = max_timestamp.max(row.timestamp);
+            self.min_timestamp = self.min_timestamp.min(row.timestamp);
+            self.max_timestamp = self.max_timestamp.max(row.timestamp);
+        }
+        Ok(bounds)
+    }
+    
+    /// 쓰던 SSTable을 버리고 지금까지 만든 구성 파일을 지움
+    pub async fn abort(self) -> Result<()> {
+        let Self { id, base_dir, data, index, summary, .. } = self;
+        drop((data, index, summary));
+        for suffix in COMPONENT_SUFFIXES {
+            match tokio::fs::remove_file(SSTable::component_path(&base_dir, &id, suffix)).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {},
             }
         }
+        Ok(())
+    }
+    
+    /// 남은 청크와 푸터, 블룸 필터, 청크 정보, 통계를 쓰고 모든 파일을 동기화
+    pub async fn finish(mut self) -> Result<SSTable> {
+        self.train_dictionary().await?;
+        debug_assert!(!self.data.is_training());
+        let (mut data_file, compression_info) = self.data.finish().await?;
+        let footer = SSTableFooter {
+            version: SSTABLE_FORMAT_VERSION,
+            compression: self.compression,
+            min_timestamp: self.min_timestamp,
+            max_timestamp: self.max_timestamp,
+            clustering_bounds: self.clustering_bounds.clone(),
+            partition_count: self.partition_count,
+            data_length: compression_info.compressed_end,
+        };
+        let footer = with_checksum(bincode::serialize(&footer)?);
+        data_file.write_all(&footer).await?;
+        data_file.write_all(&(footer.len() as u32).to_be_bytes()).await?;
         
-        let bloom_filter_offset = current_offset;
-        let bloom_filter_data = bincode::serialize(&bloom_filter)?;
-        data_file.write_all(&bloom_filter_data).await?;
-        current_offset += bloom_filter_data.len() as u64;
+        data_file.finish().await?;
+        for file in [&mut self.index, &mut self.summary] {
+            file.flush().await?;
+            file.get_ref().sync_all().await?;
+        }
+        
+        let path = |suffix| SSTable::component_path(&self.base_dir, &self.id, suffix);
+        write_component(&path(FILTER_FILE_SUFFIX), &bincode::serialize(&self.bloom_filter)?).await?;
+        write_component(&path(COMPRESSION_INFO_FILE_SUFFIX), &bincode::serialize(&compression_info)?).await?;
+        self.statistics.uncompressed_bytes = compression_info.data_length;
+        self.statistics.compressed_bytes = compression_info.compressed_end;
+        write_component(&path(STATISTICS_FILE_SUFFIX), &bincode::serialize(&self.statistics)?).await?;
+        fault_injection::sync_sstable(FaultPoint::SSTableSync, &self.base_dir, &self.id)?;
         
-        let partition_index_offset = current_offset;
-        let partition_index_data = bincode::serialize(&partition_index)?;
-        data_file.write_all(&partition_index_data).await?;
-        current_offset += partition_index_data.len() as u64;
+        Ok(SSTable {
+            file_path: path(DATA_FILE_SUFFIX),
+            index_file_path: path(INDEX_FILE_SUFFIX),
+            compression_info_path: path(COMPRESSION_INFO_FILE_SUFFIX),
+            format_version: SSTABLE_FORMAT_VERSION,
+            id: self.id,
+            size_bytes: compression_info.compressed_end,
+            compression_info,
+            bloom_filter: self.bloom_filter,
+            summary_index: self.summary_index,
+            partition_count: self.partition_count,
+            index_size_bytes: self.index_offset,
+            min_timestamp: self.min_timestamp,
+            max_timestamp: self.max_timestamp,
+            clustering_bounds: self.clustering_bounds,
+            last_key: self.last_key,
+            statistics: self.statistics,
+            compression: self.compression,
+            access_mode: DiskAccessMode::default(),
+            key_cache: None,
+            chunk_cache: None,
+        })
+    }
+}
+
+impl SSTable {
+    /// 같은 디렉토리에 현재 형식으로 다시 쓴 SSTable (원본은 그대로 둠)
+    pub async fn rewrite(self: &Arc<Self>) -> Result<SSTable> {
+        let base_dir = self.file_path.parent().unwrap_or(Path::new("."));
+        let mut writer = SSTableWriter::new(base_dir, self.compression, self.partition_count).await?;
+        let mut scanner = self.scanner(ReadOptions::all());
+        while let Some((partition_key, partition)) = scanner.next_partition().await? {
+            writer.append(&partition_key, &partition).await?;
+        }
+        writer.finish().await
+    }
+    
+    /// Memtable에서 SSTable 생성
+    pub async fn create_from_memtable(
+        memtable: &Memtable,
+        base_dir: &Path,
+        compression: CompressionType
+    ) -> Result<Self> {
+        let mut writer = SSTableWriter::new(base_dir, compression, memtable.partition_count() as u64).await?;
+        // 파티션 키 순서로 하나씩 복원하며 SSTable에 쓰기 (전체 복사 없음)
+        for (partition_key, partition) in memtable.partitions() {
+            writer.append(&partition_key, &partition).await?;
+        }
+        writer.finish().await
+    }
+    
+    /// 디스크에 있는 SSTable 열기
+    ///
+    /// 푸터와 블룸 필터, 요약 인덱스, 통계만 읽고 파티션 인덱스와 데이터는 조회할 때 읽음
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file_path = path.as_ref().to_path_buf();
+        let id = file_path.file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(DATA_FILE_SUFFIX))
+            .ok_or_else(|| CoreDBError::Generic {
+                message: format!("Not an SSTable data file: {}", file_path.display()),
+            })?
+            .to_string();
         
-        let summary_index_offset = current_offset;
-        let summary_index = Self::build_summary_index(&partition_index);
-        let summary_index_data = bincode::serialize(&summary_index)?;
-        data_file.write_all(&summary_index_data).await?;
+        let mut file = File::open(&file_path).await?;
+        let file_len = file.metadata().await?.len();
         
-        // 헤더 업데이트
-        let header = SSTableHeader {
-            version: 1,
-            compression,
-            min_timestamp,
-            max_timestamp,
-            partition_count: partition_index.len() as u64,
-            bloom_filter_offset,
-            partition_index_offset,
-            summary_index_offset,
+        // 푸터는 클러스터링 키 때문에 길이가 달라 파일 마지막 4바이트에 길이를 둠
+        let corrupt = |offset, message| CoreDBError::Corruption {
+            file: file_path.display().to_string(),
+            offset,
+            message,
         };
+        if file_len < 4 {
+            return Err(corrupt(0, format!("file is {} bytes, too short for a footer", file_len)));
+        }
+        let length_data = read_range(&mut file, file_len - 4, file_len).await?;
+        let footer_len = u32::from_be_bytes(length_data[..4].try_into().expect("4 bytes")) as u64;
+        let footer_offset = (file_len - 4).checked_sub(footer_len)
+            .ok_or_else(|| corrupt(file_len - 4, format!("footer length {} exceeds file length {}", footer_len, file_len)))?;
+        let footer_data = read_range(&mut file, footer_offset, file_len - 4).await?;
+        let footer: SSTableFooter = bincode::deserialize(strip_checksum(&footer_data, &file_path, footer_offset)?)?;
+        if !(MIN_SUPPORTED_FORMAT_VERSION..=SSTABLE_FORMAT_VERSION).contains(&footer.version) {
+            return Err(CoreDBError::Generic {
+                message: format!("Unsupported SSTable format version {} in {}", footer.version, file_path.display()),
+            });
+        }
         
-        let header_data = bincode::serialize(&header)?;
-        data_file.seek(SeekFrom::Start(0)).await?;
-        data_file.write_all(&header_data).await?;
-        data_file.sync_all().await?;
+        let component = |suffix| file_path.with_file_name(format!("{}{}", id, suffix));
+        let compression_info_path = component(COMPRESSION_INFO_FILE_SUFFIX);
+        let compression_info_data = read_component(&compression_info_path).await?;
+        let compression_info: CompressionInfo = if footer.version < DICTIONARY_COMPRESSION_VERSION {
+            bincode::deserialize::<LegacyCompressionInfo>(&compression_info_data)?.into()
+        } else {
+            bincode::deserialize(&compression_info_data)?
+        };
+        if compression_info.compressed_end != footer.data_length
+            || footer.data_length != footer_offset
+            || compression_info.compression != footer.compression
+        {
+            return Err(CoreDBError::Generic {
+                message: format!("Compression info does not match SSTable {}", file_path.display()),
+            });
+        }
+        
+        let bloom_filter: BloomFilter = bincode::deserialize(&read_component(&component(FILTER_FILE_SUFFIX)).await?)?;
+        let statistics_data = read_component(&component(STATISTICS_FILE_SUFFIX)).await?;
+        let statistics: SSTableStatistics = if footer.version < DELETION_TIME_STATISTICS_VERSION {
+            bincode::deserialize::<LegacySSTableStatistics>(&statistics_data)?.into()
+        } else {
+            bincode::deserialize(&statistics_data)?
+        };
+        
+        let summary_path = component(SUMMARY_FILE_SUFFIX);
+        let summary_data = tokio::fs::read(&summary_path).await?;
+        let mut reader = FramedReader::new(&summary_data);
+        let mut summary_index = BTreeMap::new();
+        while !reader.is_empty() {
+            let (partition_key, [index_offset], _) = reader.read_keyed_entry(&summary_path, 0)?;
+            summary_index.insert(partition_key, index_offset);
+        }
+        
+        let index_file_path = component(INDEX_FILE_SUFFIX);
+        let index_size_bytes = tokio::fs::metadata(&index_file_path).await?.len();
+        
+        // 마지막 파티션 키는 마지막 요약 구간의 인덱스 항목만 읽어서 찾음
+        let mut last_key = None;
+        if let Some(&start) = summary_index.values().next_back() {
+            let mut index = File::open(&index_file_path).await?;
+            let entries = read_range(&mut index, start, index_size_bytes).await?;
+            let mut reader = FramedReader::new(&entries);
+            while !reader.is_empty() {
+                let (partition_key, [_, _], _) = reader.read_keyed_entry(&index_file_path, start)?;
+                last_key = Some(partition_key);
+            }
+        }
         
         Ok(SSTable {
-            id: sstable_id,
-            file_path: data_file_path,
+            id,
+            file_path,
+            index_file_path,
+            compression_info_path,
+            format_version: footer.version,
+            compression_info,
             bloom_filter,
-            partition_index,
             summary_index,
-            min_timestamp,
-            max_timestamp,
-            compression: compression.clone(),
-            size_bytes: total_size,
+            partition_count: footer.partition_count,
+            index_size_bytes,
+            min_timestamp: footer.min_timestamp,
+            max_timestamp: footer.max_timestamp,
+            clustering_bounds: footer.clustering_bounds,
+            last_key,
+            statistics,
+            compression: footer.compression,
+            size_bytes: footer.data_length,
+            access_mode: DiskAccessMode::default(),
+            key_cache: None,
+            chunk_cache: None,
         })
     }
     
+    /// 읽기 방식 지정
+    pub fn with_access_mode(mut self, access_mode: DiskAccessMode) -> Self {
+        self.access_mode = access_mode;
+        self
+    }
+    
+    /// 파티션 키 캐시 지정
+    pub fn with_key_cache(mut self, key_cache: Option<Arc<KeyCache>>) -> Self {
+        self.key_cache = key_cache;
+        self
+    }
+    
+    /// 청크 캐시 지정
+    pub fn with_chunk_cache(mut self, chunk_cache: Option<Arc<ChunkCache>>) -> Self {
+        self.chunk_cache = chunk_cache;
+        self
+    }
+    
+    async fn open_component(&self, path: &Path, pattern: AccessPattern) -> Result<ComponentReader> {
+        ComponentReader::open(path, self.access_mode, pattern).await
+    }
+    
+    /// SSTable 구성 파일 경로 (<dir>/<id><suffix>)
+    pub fn component_path(dir: &Path, id: &str, suffix: &str) -> PathBuf {
+        dir.join(format!("{}{}", id, suffix))
+    }
+    
+    /// 구성 파일 이름이면 SSTable id 반환
+    pub fn component_owner(file_name: &str) -> Option<&str> {
+        COMPONENT_SUFFIXES.iter().find_map(|suffix| file_name.strip_suffix(suffix))
+    }
+    
+    /// 디렉토리의 모든 SSTable 열기 (*-Data.db, 오래된 파일부터)
+    ///
+    /// 디렉토리가 없으면 빈 목록
+    pub async fn discover(dir: impl AsRef<Path>) -> Result<Vec<SSTable>> {
+        let mut entries = match tokio::fs::read_dir(dir.as_ref()).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        
+        let mut files = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let is_data_file = path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.ends_with(DATA_FILE_SUFFIX));
+            if is_data_file {
+                let modified = entry.metadata().await?.modified()?;
+                files.push((modified, path));
+            }
+        }
+        files.sort();
+        
+        let mut sstables = Vec::with_capacity(files.len());
+        for (_, path) in files {
+            sstables.push(Self::open(&path).await?);
+        }
+        Ok(sstables)
+    }
+    
+    /// 모든 파티션 키 (인덱스 파일을 처음부터 순서대로 읽음)
+    pub async fn partition_keys(&self) -> Result<Vec<PartitionKey>> {
+        let mut index = self.open_component(&self.index_file_path, AccessPattern::Sequential).await?;
+        let data = index.read_range(0, self.index_size_bytes).await?;
+        let mut reader = FramedReader::new(&data);
+        let mut keys = Vec::with_capacity(self.partition_count as usize);
+        while !reader.is_empty() {
+            let (partition_key, _, _) = reader.read_keyed_entry::<2>(&self.index_file_path, 0)?;
+            keys.push(partition_key);
+        }
+        Ok(keys)
+    }
+    
+    /// 인덱스 파일의 (파티션 키, 압축 전 레코드 오프셋, 길이)를 순서대로 읽음 (스크럽용)
+    ///
+    /// 손상된 항목을 만나면 그 뒤는 경계를 알 수 없어 멈춤. 끝까지 읽었으면 true를 함께 반환
+    pub(crate) async fn index_entries(&self) -> Result<(Vec<(PartitionKey, u64, u64)>, bool)> {
+        let mut index = self.open_component(&self.index_file_path, AccessPattern::Sequential).await?;
+        let data = index.read_range(0, self.index_size_bytes).await?;
+        let mut reader = FramedReader::new(&data);
+        let mut entries = Vec::with_capacity(self.partition_count as usize);
+        while !reader.is_empty() {
+            match reader.read_keyed_entry::<2>(&self.index_file_path, 0) {
+                Ok((partition_key, [offset, length], _)) => entries.push((partition_key, offset, length)),
+                Err(_) => return Ok((entries, false)),
+            }
+        }
+        Ok((entries, true))
+    }
+    
+    /// 요약 인덱스로 파티션 키가 있을 수 있는 인덱스 파일 구간 [start, end) 계산
+    fn index_window(&self, partition_key: &PartitionKey) -> Option<(u64, u64)> {
+        use std::ops::Bound;
+        
+        let (_, start) = self.summary_index.range(..=partition_key).next_back()?;
+        let end = self.summary_index.range((Bound::Excluded(partition_key), Bound::Unbounded))
+            .next()
+            .map(|(_, offset)| *offset)
+            .unwrap_or(self.index_size_bytes);
+        Some((*start, end))
+    }
+    
+    /// 파티션 키 범위 (첫 키, 마지막 키), 파티션이 없으면 None
+    pub fn key_range(&self) -> Option<(&PartitionKey, &PartitionKey)> {
+        Some((self.summary_index.keys().next()?, self.last_key.as_ref()?))
+    }
+    
+    /// 키 범위가 [first, last]와 겹치는지
+    pub fn overlaps(&self, first: &PartitionKey, last: &PartitionKey) -> bool {
+        self.key_range().is_some_and(|(start, end)| start <= last && first <= end)
+    }
+    
+    /// 클러스터링 범위 안의 행이 이 SSTable에 있을 수 있는지
+    pub fn may_contain_slice(&self, options: &ReadOptions) -> bool {
+        options.clustering_slice.as_ref().is_none_or(|slice| self.clustering_bounds.may_overlap(slice))
+    }
+    
+    /// 인덱스 파일에서 파티션 레코드의 압축 전 오프셋과 길이, 클러스터링 최소/최대 찾기 (요약 구간 하나만 읽음)
+    async fn find_record(&self, index: &mut ComponentReader, partition_key: &PartitionKey) -> Result<Option<RecordPosition>> {
+        let Some((start, end)) = self.index_window(partition_key) else {
+            return Ok(None);
+        };
+        let window = index.read_range(start, end).await?;
+        let mut reader = FramedReader::new(&window);
+        while !reader.is_empty() {
+            let (key, [offset, length], bounds) = reader.read_keyed_entry(&self.index_file_path, start)?;
+            match key.cmp(partition_key) {
+                std::cmp::Ordering::Less => continue,
+                std::cmp::Ordering::Equal => return Ok(Some(RecordPosition {
+                    offset,
+                    length,
+                    clustering_bounds: bincode::deserialize(bounds)?,
+                })),
+                std::cmp::Ordering::Greater => break,
+            }
+        }
+        Ok(None)
+    }
+    
+    /// 레코드 위치 찾기 (키 캐시에 없을 때만 인덱스 파일을 열어 찾고 캐시에 넣음)
+    async fn locate(&self, index: &mut Option<ComponentReader>, pattern: AccessPattern, partition_key: &PartitionKey) -> Result<Option<RecordPosition>> {
+        if let Some(position) = self.key_cache.as_ref().and_then(|cache| cache.get(&self.id, partition_key)) {
+            return Ok(Some(position));
+        }
+        let index = match index {
+            Some(index) => index,
+            None => index.insert(self.open_component(&self.index_file_path, pattern).await?),
+        };
+        let position = self.find_record(index, partition_key).await?;
+        if let (Some(cache), Some(position)) = (&self.key_cache, &position) {
+            cache.insert(&self.id, partition_key, position.clone());
+        }
+        Ok(position)
+    }
+    
     /// 파티션 읽기
     pub async fn read_partition(&self, partition_key: &PartitionKey) -> Result<Option<Partition>> {
-        // 1. 블룸 필터 체크
-        if !self.bloom_filter.might_contain(partition_key) {
+        self.read_partition_with(partition_key, &ReadOptions::all()).await
+    }
+    
+    /// 파티션 읽기 (선택된 컬럼과 행 수만 역직렬화)
+    ///
+    /// 클러스터링 범위가 SSTable이나 파티션의 최소/최대와 겹치지 않으면 데이터를 읽지 않고 None
+    pub async fn read_partition_with(&self, partition_key: &PartitionKey, options: &ReadOptions) -> Result<Option<Partition>> {
+        // 1. 블룸 필터와 SSTable 클러스터링 범위 체크
+        if !self.bloom_filter.might_contain(partition_key) || !self.may_contain_slice(options) {
             return Ok(None);
         }
         
-        // 2. 파티션 인덱스에서 오프셋 찾기
-        let offset = match self.partition_index.get(partition_key) {
-            Some(offset) => *offset,
-            None => return Ok(None),
+        // 2. 키 캐시 또는 요약 인덱스 → 인덱스 파일에서 오프셋 찾기
+        let (offset, length) = match self.locate(&mut None, AccessPattern::Random, partition_key).await? {
+            Some(position) if Self::partition_may_match(&position, options) => (position.offset, position.length),
+            _ => return Ok(None),
         };
         
         // 3. 디스크에서 파티션 데이터 읽기
-        let mut file = File::open(&self.file_path).await?;
-        file.seek(SeekFrom::Start(offset)).await?;
+        let mut data = self.open_component(&self.file_path, AccessPattern::Random).await?;
+        let partition = self.read_partition_at(&mut data, offset, length, options).await?;
         
-        // 파티션 크기 읽기
-        let mut size_buf = [0u8; 4];
-        file.read_exact(&mut size_buf).await?;
-        let partition_size = u32::from_le_bytes(size_buf) as usize;
+        Ok(Some(partition))
+    }
+    
+    /// 여러 파티션을 한 번에 읽기 (파일은 한 번만 열고 오프셋 순서로 순차 읽기)
+    ///
+    /// 블룸 필터와 인덱스에 없는 키는 결과에서 빠짐
+    pub async fn read_partitions(&self, partition_keys: &[PartitionKey], options: &ReadOptions) -> Result<Vec<(PartitionKey, Partition)>> {
+        if !self.may_contain_slice(options) {
+            return Ok(Vec::new());
+        }
+        let candidates: Vec<&PartitionKey> = partition_keys.iter()
+            .filter(|key| self.bloom_filter.might_contain(key))
+            .collect();
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
         
-        // 파티션 데이터 읽기
-        let mut partition_data = vec![0u8; partition_size];
-        file.read_exact(&mut partition_data).await?;
+        let mut index = None;
+        let mut targets: Vec<(u64, u64, &PartitionKey)> = Vec::with_capacity(candidates.len());
+        for key in candidates {
+            match self.locate(&mut index, AccessPattern::Sequential, key).await? {
+                Some(position) if Self::partition_may_match(&position, options) => {
+                    targets.push((position.offset, position.length, key));
+                },
+                _ => {},
+            }
+        }
+        if targets.is_empty() {
+            return Ok(Vec::new());
+        }
+        targets.sort();
         
-        // 압축 해제 및 역직렬화
-        let partition = Self::deserialize_partition(&partition_data, &self.compression).await?;
+        let mut data = self.open_component(&self.file_path, AccessPattern::Sequential).await?;
+        let mut partitions = Vec::with_capacity(targets.len());
+        for (offset, length, key) in targets {
+            let partition = self.read_partition_at(&mut data, offset, length, options).await?;
+            partitions.push((key.clone(), partition));
+        }
         
-        Ok(Some(partition))
+        Ok(partitions)
     }
     
-    /// 파티션 직렬화 및 압축
-    async fn serialize_partition(partition: &Partition, compression: &CompressionType) -> Result<Vec<u8>> {
-        let mut data = Vec::new();
+    fn partition_may_match(position: &RecordPosition, options: &ReadOptions) -> bool {
+        options.clustering_slice.as_ref().is_none_or(|slice| position.clustering_bounds.may_overlap(slice))
+    }
+    
+    async fn read_partition_at(&self, file: &mut ComponentReader, offset: u64, length: u64, options: &ReadOptions) -> Result<Partition> {
+        let partition_data = self.read_record(file, offset, length).await?;
+        Self::deserialize_partition(&partition_data, options, self.format_version)
+    }
+    
+    /// 파티션을 역직렬화하지 않고 뷰로 f에 넘김 (셀은 읽은 레코드 버퍼를 참조)
+    ///
+    /// 고정 배치 셀 형식(버전 11 이상)에서만 쓸 수 있고, 없는 파티션이면 None
+    pub async fn with_partition_view<R>(&self, partition_key: &PartitionKey, f: impl FnOnce(PartitionView<'_>) -> Result<R>) -> Result<Option<R>> {
+        if self.format_version < FIXED_LAYOUT_CELLS_VERSION {
+            return Err(CoreDBError::Generic {
+                message: format!("SSTable {} uses format version {}, partition views need {}", self.id, self.format_version, FIXED_LAYOUT_CELLS_VERSION),
+            });
+        }
+        if !self.bloom_filter.might_contain(partition_key) {
+            return Ok(None);
+        }
+        let Some(position) = self.locate(&mut None, AccessPattern::Random, partition_key).await? else {
+            return Ok(None);
+        };
+        let mut file = self.open_component(&self.file_path, AccessPattern::Random).await?;
+        let data = self.read_record(&mut file, position.offset, position.length).await?;
+        f(PartitionView::parse(&data, self.format_version)?).map(Some)
+    }
+    
+    /// 셀 하나 읽기 (찾는 셀만 해석하고 다른 셀과 행은 건너뜀)
+    pub async fn read_cell(&self, partition_key: &PartitionKey, clustering_key: &Option<ClusteringKey>, column: &str) -> Result<Option<Cell>> {
+        let cell = self.with_partition_view(partition_key, |partition| {
+            for static_cell in partition.static_cells() {
+                let (name, cell) = static_cell?;
+                if name == column {
+                    return Ok(Some(cell.to_cell()?));
+                }
+            }
+            let Some(id) = partition.column_id(column) else {
+                return Ok(None);
+            };
+            for row in partition.rows() {
+                let row = row?;
+                match row.clustering_key.cmp(clustering_key) {
+                    std::cmp::Ordering::Less => continue,
+                    std::cmp::Ordering::Greater => break,
+                    std::cmp::Ordering::Equal => {},
+                }
+                for cell in row.cells() {
+                    let (cell_id, cell) = cell?;
+                    if cell_id == id {
+                        return Ok(Some(cell.to_cell()?));
+                    }
+                }
+                break;
+            }
+            Ok(None)
+        }).await?;
+        Ok(cell.flatten())
+    }
+    
+    /// 압축 전 [offset, offset + length) 레코드 읽기 (레코드가 걸친 청크만 읽어 풂)
+    async fn read_record(&self, file: &mut ComponentReader, offset: u64, length: u64) -> Result<Vec<u8>> {
+        let info = &self.compression_info;
+        if length == 0 || offset + length > info.data_length {
+            return Err(CoreDBError::Corruption {
+                file: self.index_file_path.display().to_string(),
+                offset,
+                message: format!("record of {} bytes outside {} bytes of data", length, info.data_length),
+            });
+        }
+        let first = info.chunk_index(offset);
+        let last = info.chunk_index(offset + length - 1);
+        let mut data = self.read_cached_chunks(file, first, last + 1).await?;
         
-        // Static 컬럼들 직렬화
-        let static_data = bincode::serialize(&partition.static_columns)?;
-        data.write_u32(static_data.len() as u32).await?;
-        data.write_all(&static_data).await?;
+        let start = (offset - first as u64 * info.chunk_length as u64) as usize;
+        data.truncate(start + length as usize);
+        data.drain(..start);
+        Ok(data)
+    }
+    
+    /// [from, to) 청크 읽기 (캐시에 있는 청크는 파일을 읽지 않고, 없는 연속 구간만 읽어 캐시에 넣음)
+    async fn read_cached_chunks(&self, file: &mut ComponentReader, from: usize, to: usize) -> Result<Vec<u8>> {
+        let Some(cache) = &self.chunk_cache else {
+            return self.read_chunks(file, from, to).await;
+        };
+        let chunk_length = self.compression_info.chunk_length as usize;
+        let mut data = Vec::with_capacity((to - from) * chunk_length);
+        let mut index = from;
+        while index < to {
+            if let Some(chunk) = cache.get(&self.id, index) {
+                data.extend_from_slice(&chunk);
+                index += 1;
+                continue;
+            }
+            let mut end = index + 1;
+            while end < to && !cache.contains(&self.id, end) {
+                end += 1;
+            }
+            let chunks = self.read_chunks(file, index, end).await?;
+            for (position, chunk) in chunks.chunks(chunk_length).enumerate() {
+                cache.insert(&self.id, index + position, Arc::new(chunk.to_vec()));
+            }
+            data.extend(chunks);
+            index = end;
+        }
+        Ok(data)
+    }
+    
+    /// [from, to) 청크를 한 번에 읽고 압축 해제
+    pub(crate) async fn read_chunks(&self, file: &mut ComponentReader, from: usize, to: usize) -> Result<Vec<u8>> {
+        let info = &self.compression_info;
+        if from >= to || to > info.chunk_count() {
+            return Err(CoreDBError::Generic {
+                message: format!("Chunk range {}..{} out of bounds in SSTable {}", from, to, self.file_path.display()),
+            });
+        }
+        let (start, _) = info.chunk_range(from);
+        let (_, end) = info.chunk_range(to - 1);
+        let compressed = file.read_range(start, end).await?;
         
-        // 행들 직렬화
-        let mut rows: Vec<Row> = partition.rows.iter().map(|entry| entry.value().clone()).collect();
-        rows.sort_by(|a, b| {
-            match (&a.clustering_key, &b.clustering_key) {
-                (Some(ak), Some(bk)) => ak.cmp(bk),
-                (None, Some(_)) => std::cmp::Ordering::Less,
-                (Some(_), None) => std::cmp::Ordering::Greater,
-                (None, None) => std::cmp::Ordering::Equal,
+        let mut data = Vec::with_capacity((to - from) * info.chunk_length as usize);
+        for index in from..to {
+            let (chunk_start, chunk_end) = info.chunk_range(index);
+            let chunk = &compressed[(chunk_start - start) as usize..(chunk_end - start) as usize];
+            verify_checksum(chunk, info.chunk_checksums[index], &self.file_path, chunk_start)?;
+            data.extend(info.decompress_chunk(chunk, self.format_version)?);
+        }
+        Ok(data)
+    }
+    
+    /// 모든 파티션을 키 순서로 읽는 스캐너
+    ///
+    /// 백그라운드 작업이 인덱스를 요약 구간 단위로, 데이터를 청크 단위로 순차 읽기하며
+    /// 파티션을 미리 읽어 두므로 파일 전체를 메모리에 올리지 않음
+    pub fn scanner(self: &Arc<Self>, options: ReadOptions) -> SSTableScanner {
+        let (sender, receiver) = mpsc::channel(SCANNER_PREFETCH_PARTITIONS);
+        let sstable = self.clone();
+        let task = tokio::spawn(async move {
+            if let Err(e) = sstable.scan_partitions(&options, &sender).await {
+                let _ = sender.send(Err(e)).await;
             }
         });
+        SSTableScanner { receiver, task, current: None }
+    }
+    
+    async fn scan_partitions(&self, options: &ReadOptions, sender: &mpsc::Sender<Result<(PartitionKey, Partition)>>) -> Result<()> {
+        let mut index = self.open_component(&self.index_file_path, AccessPattern::Sequential).await?;
+        let mut data = self.open_component(&self.file_path, AccessPattern::Sequential).await?;
+        let mut chunks = SequentialChunks::default();
         
-        data.write_u32(rows.len() as u32).await?;
-        for row in &rows {
-            let row_data = bincode::serialize(row)?;
-            data.write_u32(row_data.len() as u32).await?;
-            data.write_all(&row_data).await?;
+        // 요약 샘플 사이의 인덱스 구간을 차례로 읽음
+        let mut window_starts: Vec<u64> = self.summary_index.values().copied().collect();
+        window_starts.push(self.index_size_bytes);
+        for window in window_starts.windows(2) {
+            let entries = index.read_range(window[0], window[1]).await?;
+            let mut reader = FramedReader::new(&entries);
+            while !reader.is_empty() {
+                let (partition_key, [offset, length], _) = reader.read_keyed_entry(&self.index_file_path, window[0])?;
+                let record = chunks.read(self, &mut data, offset, length).await?;
+                let partition = Self::deserialize_partition(record, options, self.format_version)?;
+                if sender.send(Ok((partition_key, partition))).await.is_err() {
+                    // 스캐너가 먼저 버려짐
+                    return Ok(());
+                }
+            }
         }
+        Ok(())
+    }
+    
+    /// 파티션 역직렬화 (ReadOptions의 컬럼/행 수 제한 적용)
+    ///
+    /// 선택되지 않은 셀과 클러스터링 범위 밖의 행은 해석하지 않고 건너뜀
+    pub(crate) fn deserialize_partition(data: &[u8], options: &ReadOptions, format_version: u32) -> Result<Partition> {
+        if format_version < FIXED_LAYOUT_CELLS_VERSION {
+            return Self::deserialize_legacy_partition(data, options);
+        }
+        let partition = PartitionView::parse(data, format_version)?;
         
-        // 압축 적용
-        match compression {
-            CompressionType::None => Ok(data),
-            CompressionType::LZ4 => {
-                Ok(lz4_flex::compress_prepend_size(&data))
-            },
-            CompressionType::Snappy => {
-                let mut encoder = snap::raw::Encoder::new();
-                Ok(encoder.compress_vec(&data)?)
-            },
-            CompressionType::ZSTD => {
-                Ok(zstd::bulk::compress(&data, 3)?)
-            },
+        let mut static_columns = HashMap::new();
+        for static_cell in partition.static_cells() {
+            let (name, cell) = static_cell?;
+            if options.includes(name) {
+                static_columns.insert(name.to_string(), cell.to_cell()?);
+            }
+        }
+        
+        // 선택 여부는 컬럼 번호별로 한 번만 계산
+        let included: Vec<bool> = partition.columns().iter().map(|name| options.includes(name)).collect();
+        let layout = Arc::new(ColumnLayout::new(partition.columns().iter().copied()));
+        
+        // 정방향은 limit 도달 시 나머지 행은 읽지 않고, 역방향은 모두 읽은 뒤 마지막 limit개만 남김
+        let row_limit = match options.direction {
+            ScanDirection::Forward => options.limit.unwrap_or(usize::MAX),
+            ScanDirection::Reverse => usize::MAX,
+        };
+        let rows = crossbeam_skiplist::SkipMap::new();
+        for row in partition.rows() {
+            if rows.len() >= row_limit {
+                break;
+            }
+            let row = row?;
+            if !options.includes_row(&row.clustering_key) {
+                continue;
+            }
+            let mut cells = Cells::with_layout(layout.clone());
+            for cell in row.cells() {
+                let (id, cell) = cell?;
+                match included.get(id as usize) {
+                    Some(true) => {
+                        cells.insert_by_id(id, cell.to_cell()?);
+                    },
+                    Some(false) => {},
+                    None => return Err(CoreDBError::Generic { message: format!("Invalid column id: {}", id) }),
+                }
+            }
+            rows.insert(row.clustering_key.clone(), Row {
+                partition_key: partition.partition_key.clone(),
+                clustering_key: row.clustering_key,
+                cells,
+                timestamp: row.timestamp,
+            });
+        }
+        
+        if let (ScanDirection::Reverse, Some(limit)) = (options.direction, options.limit) {
+            while rows.len() > limit {
+                rows.pop_front();
+            }
+        }
+        
+        Ok(Partition {
+            rows,
+            static_columns,
+        })
+    }
+    
+    /// 셀마다 bincode로 기록하던 형식(버전 11 미만)의 파티션 역직렬화
+    fn deserialize_legacy_partition(data: &[u8], options: &ReadOptions) -> Result<Partition> {
+        let mut reader = FramedReader::new(data);
+        
+        let partition_key: PartitionKey = bincode::deserialize(reader.read_framed()?)?;
+        
+        // Static 컬럼들 역직렬화
+        let static_columns: HashMap<String, Cell> = bincode::deserialize(reader.read_framed()?)?;
+        let static_columns = static_columns.into_iter()
+            .filter(|(name, _)| options.includes(name))
+            .collect();
+        
+        // 컬럼 사전 (행들이 공유하는 배치, 선택 여부는 번호별로 한 번만 계산)
+        let names: Vec<String> = bincode::deserialize(reader.read_framed()?)?;
+        let included: Vec<bool> = names.iter().map(|name| options.includes(name)).collect();
+        let layout = Arc::new(ColumnLayout::new(names));
+        
+        // 행들 역직렬화 (정방향은 limit 도달 시 나머지 행은 읽지 않음,
+        // 역방향은 행이 앞에서부터 기록되어 있으므로 모두 읽은 뒤 마지막 limit개만 남김).
+        // 클러스터링 범위 밖의 행은 셀을 역직렬화하지 않고 건너뛰며 limit에도 세지 않음
+        let row_count = reader.read_u32()? as usize;
+        let row_limit = match options.direction {
+            ScanDirection::Forward => options.limit.unwrap_or(usize::MAX),
+            ScanDirection::Reverse => usize::MAX,
+        };
+        
+        let rows = crossbeam_skiplist::SkipMap::new();
+        
+        for _ in 0..row_count {
+            if rows.len() >= row_limit {
+                break;
+            }
+            let (clustering_key, timestamp): (Option<ClusteringKey>, i64) =
+                bincode::deserialize(reader.read_framed()?)?;
+            let in_slice = options.includes_row(&clustering_key);
+            
+            let cell_count = reader.read_u32()? as usize;
+            let mut cells = Cells::with_layout(layout.clone());
+            for _ in 0..cell_count {
+                let id = reader.read_u16()?;
+                let cell_data = reader.read_framed()?;
+                match included.get(id as usize) {
+                    Some(true) if in_slice => {
+                        let cell: Cell = bincode::deserialize(cell_data)?;
+                        cells.insert_by_id(id, cell);
+                    },
+                    Some(_) => {},
+                    None => return Err(CoreDBError::Generic { message: format!("Invalid column id: {}", id) }),
+                }
+            }
+            if !in_slice {
+                continue;
+            }
+            
+            rows.insert(clustering_key.clone(), Row {
+                partition_key: partition_key.clone(),
+                clustering_key,
+                cells,
+                timestamp,
+            });
+        }
+        
+        if let (ScanDirection::Reverse, Some(limit)) = (options.direction, options.limit) {
+            while rows.len() > limit {
+                rows.pop_front();
+            }
+        }
+        
+        Ok(Partition {
+            rows,
+            static_columns,
+        })
+    }
+    
+    /// SSTable 구성 파일 경로
+    pub fn component_file(&self, suffix: &str) -> PathBuf {
+        self.file_path.with_file_name(format!("{}{}", self.id, suffix))
+    }
+    
+    /// 구성 파일별 크기 (COMPONENT_SUFFIXES 순서)
+    pub async fn component_sizes(&self) -> Result<Vec<(&'static str, u64)>> {
+        let mut sizes = Vec::with_capacity(COMPONENT_SUFFIXES.len());
+        for suffix in COMPONENT_SUFFIXES {
+            sizes.push((*suffix, tokio::fs::metadata(self.component_file(suffix)).await?.len()));
+        }
+        Ok(sizes)
+    }
+    
+    /// SSTable 삭제 (데이터 파일과 나머지 구성 파일)
+    pub async fn delete(&self) -> Result<()> {
+        tokio::fs::remove_file(&self.file_path).await?;
+        for suffix in COMPONENT_SUFFIXES.iter().filter(|suffix| **suffix != DATA_FILE_SUFFIX) {
+            match tokio::fs::remove_file(self.component_file(suffix)).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {},
+            }
         }
+        if let Some(cache) = &self.key_cache {
+            cache.invalidate_sstable(&self.id);
+        }
+        if let Some(cache) = &self.chunk_cache {
+            cache.invalidate_sstable(&self.id);
+        }
+        Ok(())
+    }
+    
+    /// 파일 크기 가져오기
+    pub async fn file_size(&self) -> Result<u64> {
+        let metadata = tokio::fs::metadata(&self.file_path).await?;
+        Ok(metadata.len())
+    }
+}
+
+/// SSTable 전체 스캐너 (SSTable::scanner)
+pub struct SSTableScanner {
+    receiver: mpsc::Receiver<Result<(PartitionKey, Partition)>>,
+    task: JoinHandle<()>,
+    current: Option<(PartitionKey, std::vec::IntoIter<Row>)>,
+}
+
+impl SSTableScanner {
+    /// 다음 행 (파티션 키, 클러스터링 키 순서)
+    pub async fn next(&mut self) -> Result<Option<(PartitionKey, Row)>> {
+        loop {
+            if let Some((partition_key, rows)) = self.current.as_mut() {
+                if let Some(row) = rows.next() {
+                    return Ok(Some((partition_key.clone(), row)));
+                }
+            }
+            match self.next_partition().await? {
+                Some((partition_key, partition)) => {
+                    let rows: Vec<Row> = partition.rows.into_iter().map(|(_, row)| row).collect();
+                    self.current = Some((partition_key, rows.into_iter()));
+                },
+                None => return Ok(None),
+            }
+        }
+    }
+    
+    /// 다음 파티션 전체 (next로 일부 읽은 파티션의 나머지 행은 건너뜀)
+    pub async fn next_partition(&mut self) -> Result<Option<(PartitionKey, Partition)>> {
+        self.current = None;
+        self.receiver.recv().await.transpose()
+    }
+}
+
+impl Drop for SSTableScanner {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// 순차 스캔용 압축 해제 버퍼 (연속된 레코드가 같은 청크를 다시 풀지 않도록)
+#[derive(Default)]
+struct SequentialChunks {
+    data: Vec<u8>,
+    /// data[0]의 압축 전 오프셋
+    start: u64,
+    next_chunk: usize,
+}
+
+impl SequentialChunks {
+    async fn read(&mut self, sstable: &SSTable, file: &mut ComponentReader, offset: u64, length: u64) -> Result<&[u8]> {
+        let info = &sstable.compression_info;
+        // 버퍼 밖의 레코드면 그 레코드가 속한 청크부터 다시 읽음
+        if offset < self.start || offset > self.start + self.data.len() as u64 {
+            self.next_chunk = info.chunk_index(offset);
+            self.start = self.next_chunk as u64 * info.chunk_length as u64;
+            self.data.clear();
+        }
+        let mut position = (offset - self.start) as usize;
+        while self.data.len() < position + length as usize {
+            // 이미 지난 부분은 새 청크를 붙일 때만 버림
+            self.data.drain(..position);
+            self.start += position as u64;
+            position = 0;
+            let chunk = sstable.read_chunks(file, self.next_chunk, self.next_chunk + 1).await?;
+            self.data.extend(chunk);
+            self.next_chunk += 1;
+        }
+        Ok(&self.data[position..position + length as usize])
+    }
+}
+
+/// 파일의 [start, end) 구간 읽기
+async fn read_range(file: &mut File, start: u64, end: u64) -> Result<Vec<u8>> {
+    file.seek(SeekFrom::Start(start)).await?;
+    let mut data = vec![0u8; (end - start) as usize];
+    file.read_exact(&mut data).await?;
+    Ok(data)
+}
+
+/// 체크섬을 붙여 구성 파일 쓰기
+async fn write_component(path: &Path, data: &[u8]) -> Result<()> {
+    let mut file = File::create(path).await?;
+    file.write_all(&with_checksum(data.to_vec())).await?;
+    file.sync_all().await?;
+    Ok(())
+}
+
+/// 구성 파일을 읽고 체크섬을 확인한 본문 반환
+async fn read_component(path: &Path) -> Result<Vec<u8>> {
+    let mut data = tokio::fs::read(path).await?;
+    let body_len = strip_checksum(&data, path, 0)?.len();
+    data.truncate(body_len);
+    Ok(data)
+}
+
+/// 인덱스/요약 항목: 길이 접두사가 붙은 파티션 키 + u64 값들 + 길이 접두사가 붙은 부가 정보 + 항목 CRC32C
+fn keyed_entry(partition_key: &PartitionKey, values: &[u64], extra: &[u8]) -> Result<Vec<u8>> {
+    let mut entry = Vec::new();
+    write_framed(&mut entry, &bincode::serialize(partition_key)?);
+    for value in values {
+        entry.extend_from_slice(&value.to_be_bytes());
+    }
+    write_framed(&mut entry, extra);
+    Ok(with_checksum(entry))
+}
+
+/// 길이(u32, big endian) + 데이터 기록
+fn write_framed(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    out.extend_from_slice(bytes);
+}
+
+/// 행 헤더 기록 (클러스터링 키와 행 타임스탬프)
+///
+/// 클러스터링 키가 BIGINT/TIMESTAMP 하나면 파티션에서 처음 차이로 기록한 키(delta_base)와의
+/// 차이를 zigzag varint로 기록해 시계열 행의 키를 몇 바이트로 줄임. 첫 키는 0과의 차이
+fn encode_row_header(out: &mut Vec<u8>, clustering_key: &Option<ClusteringKey>, timestamp: i64, delta_base: &mut Option<i64>) -> Result<()> {
+    let delta_key = match clustering_key.as_ref().map(|key| key.components.as_slice()) {
+        Some([CassandraValue::BigInt(value)]) => Some((ROW_HEADER_BIGINT_DELTA, *value)),
+        Some([CassandraValue::Timestamp(value)]) => Some((ROW_HEADER_TIMESTAMP_DELTA, *value)),
+        _ => None,
+    };
+    let Some((tag, value)) = delta_key else {
+        out.push(ROW_HEADER_BINCODE);
+        out.extend(bincode::serialize(&(clustering_key, timestamp))?);
+        return Ok(());
+    };
+    out.push(tag);
+    write_varint(out, zigzag(value.wrapping_sub(delta_base.unwrap_or(0))));
+    delta_base.get_or_insert(value);
+    out.extend_from_slice(&timestamp.to_be_bytes());
+    Ok(())
+}
+
+/// encode_row_header로 기록한 행 헤더 읽기
+fn decode_row_header(header: &[u8], delta_base: &mut Option<i64>) -> Result<(Option<ClusteringKey>, i64)> {
+    let (&tag, rest) = header.split_first().ok_or_else(|| CoreDBError::Generic { message: "Empty row header".to_string() })?;
+    if tag == ROW_HEADER_BINCODE {
+        return Ok(bincode::deserialize(rest)?);
+    }
+    let mut reader = FramedReader::new(rest);
+    let value = delta_base.unwrap_or(0).wrapping_add(unzigzag(read_varint(&mut reader)?));
+    delta_base.get_or_insert(value);
+    let component = match tag {
+        ROW_HEADER_BIGINT_DELTA => CassandraValue::BigInt(value),
+        ROW_HEADER_TIMESTAMP_DELTA => CassandraValue::Timestamp(value),
+        _ => return Err(CoreDBError::Generic { message: format!("Unknown row header tag {}", tag) }),
+    };
+    let timestamp = reader.read_u64()? as i64;
+    Ok((Some(ClusteringKey { components: vec![component] }), timestamp))
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+/// LEB128 (7비트씩, 낮은 자리부터)
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(reader: &mut FramedReader<'_>) -> Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = reader.read_bytes(1)?[0];
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(CoreDBError::Generic { message: "Varint longer than 10 bytes".to_string() })
+}
+
+/// 파티션 레코드를 복사 없이 읽는 뷰 (고정 배치 셀 형식)
+///
+/// 셀은 CellView로 레코드 바이트를 참조하므로 필요한 셀만 골라 해석할 수 있음
+#[derive(Debug)]
+pub struct PartitionView<'a> {
+    pub partition_key: PartitionKey,
+    static_columns: &'a [u8],
+    /// 컬럼 사전 (번호 순서)
+    columns: Vec<&'a str>,
+    row_count: usize,
+    rows: &'a [u8],
+    /// 레코드를 쓴 형식 버전 (행 헤더 형식이 다름)
+    format_version: u32,
+}
+
+impl<'a> PartitionView<'a> {
+    pub fn parse(data: &'a [u8], format_version: u32) -> Result<Self> {
+        let mut reader = FramedReader::new(data);
+        let partition_key = bincode::deserialize(reader.read_framed()?)?;
+        let static_columns = reader.read_framed()?;
+        let columns = bincode::deserialize(reader.read_framed()?)?;
+        let row_count = reader.read_u32()? as usize;
+        Ok(Self { partition_key, static_columns, columns, row_count, rows: &data[reader.position..], format_version })
+    }
+    
+    pub fn columns(&self) -> &[&'a str] {
+        &self.columns
+    }
+    
+    pub fn column_id(&self, name: &str) -> Option<u16> {
+        self.columns.iter().position(|column| *column == name).map(|id| id as u16)
+    }
+    
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+    
+    /// (컬럼 이름, 셀) 순회
+    pub fn static_cells(&self) -> impl Iterator<Item = Result<(&'a str, CellView<'a>)>> + 'a {
+        let mut reader = FramedReader::new(self.static_columns);
+        std::iter::from_fn(move || {
+            if reader.is_empty() {
+                return None;
+            }
+            let cell = (|| {
+                let name = std::str::from_utf8(reader.read_framed()?).map_err(|e| CoreDBError::Generic {
+                    message: format!("Invalid static column name: {}", e),
+                })?;
+                Ok((name, CellView::parse(reader.read_framed()?)?))
+            })();
+            if cell.is_err() {
+                reader.position = reader.data.len();
+            }
+            Some(cell)
+        })
+    }
+    
+    /// 행 순회 (클러스터링 키 순서, 오류가 나면 거기서 끝남)
+    pub fn rows(&self) -> impl Iterator<Item = Result<RowView<'a>>> + 'a {
+        let mut reader = FramedReader::new(self.rows);
+        let mut remaining = self.row_count;
+        let format_version = self.format_version;
+        let mut delta_base = None;
+        std::iter::from_fn(move || {
+            if remaining == 0 {
+                return None;
+            }
+            let row = RowView::read(&mut reader, format_version, &mut delta_base);
+            remaining = if row.is_ok() { remaining - 1 } else { 0 };
+            Some(row)
+        })
+    }
+}
+
+/// 행 뷰 (셀 영역은 순회할 때 해석)
+#[derive(Debug)]
+pub struct RowView<'a> {
+    pub clustering_key: Option<ClusteringKey>,
+    pub timestamp: i64,
+    cell_count: usize,
+    cells: &'a [u8],
+}
+
+impl<'a> RowView<'a> {
+    /// 행 헤더를 읽고 셀 영역은 길이만 따라가며 건너뜀 (delta_base는 파티션의 차이 기준 키)
+    fn read(reader: &mut FramedReader<'a>, format_version: u32, delta_base: &mut Option<i64>) -> Result<Self> {
+        let header = reader.read_framed()?;
+        let (clustering_key, timestamp) = if format_version < DELTA_CLUSTERING_KEYS_VERSION {
+            bincode::deserialize(header)?
+        } else {
+            decode_row_header(header, delta_base)?
+        };
+        let cell_count = reader.read_u32()? as usize;
+        let start = reader.position;
+        for _ in 0..cell_count {
+            reader.read_u16()?;
+            reader.read_framed()?;
+        }
+        Ok(Self { clustering_key, timestamp, cell_count, cells: &reader.data[start..reader.position] })
+    }
+    
+    pub fn cell_count(&self) -> usize {
+        self.cell_count
+    }
+    
+    /// (컬럼 번호, 셀) 순회 (이름은 PartitionView::columns의 번호 순서)
+    pub fn cells(&self) -> impl Iterator<Item = Result<(u16, CellView<'a>)>> + 'a {
+        let mut reader = FramedReader::new(self.cells);
+        (0..self.cell_count).map(move |_| Ok((reader.read_u16()?, CellView::parse(reader.read_framed()?)?)))
+    }
+}
+
+/// 길이 접두사 형식의 바이트를 순서대로 읽는 리더
+struct FramedReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> FramedReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+    
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.position.checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "truncated partition data"))?;
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        Ok(bytes)
     }
     
-    /// 파티션 역직렬화 및 압축 해제
-    async fn deserialize_partition(data: &[u8], compression: &CompressionType) -> Result<Partition> {
-        // 압축 해제
-        let decompressed_data = match compression {
-            CompressionType::None => data.to_vec(),
-            CompressionType::LZ4 => {
-                lz4_flex::decompress_size_prepended(data)?
-            },
-            CompressionType::Snappy => {
-                let mut decoder = snap::raw::Decoder::new();
-                decoder.decompress_vec(data)?
-            },
-            CompressionType::ZSTD => {
-                zstd::bulk::decompress(data, 1024 * 1024)? // 1MB max
-            },
-        };
-        
-        let mut cursor = std::io::Cursor::new(&decompressed_data);
-        
-        // Static 컬럼들 역직렬화
-        let mut size_buf = [0u8; 4];
-        cursor.read_exact(&mut size_buf).await?;
-        let static_size = u32::from_le_bytes(size_buf) as usize;
-        
-        let mut static_data = vec![0u8; static_size];
-        cursor.read_exact(&mut static_data).await?;
-        let static_columns: std::collections::HashMap<String, crate::schema::Cell> = 
-            bincode::deserialize(&static_data)?;
-        
-        // 행들 역직렬화
-        cursor.read_exact(&mut size_buf).await?;
-        let row_count = u32::from_le_bytes(size_buf) as usize;
-        
-        let mut rows = crossbeam_skiplist::SkipMap::new();
-        
-        for _ in 0..row_count {
-            cursor.read_exact(&mut size_buf).await?;
-            let row_size = u32::from_le_bytes(size_buf) as usize;
-            
-            let mut row_data = vec![0u8; row_size];
-            cursor.read_exact(&mut row_data).await?;
-            
-            let row: Row = bincode::deserialize(&row_data)?;
-            rows.insert(row.clustering_key.clone(), row);
-        }
-        
-        Ok(Partition {
-            rows,
-            static_columns,
-        })
+    fn read_u16(&mut self) -> Result<u16> {
+        let bytes = self.read_bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
     }
     
-    /// 요약 인덱스 생성 (메모리 효율성을 위해)
-    fn build_summary_index(full_index: &BTreeMap<PartitionKey, u64>) -> BTreeMap<PartitionKey, u64> {
-        let sample_rate = 128; // 128개 파티션마다 하나씩 샘플링
-        
-        full_index.iter()
-            .enumerate()
-            .filter(|(i, _)| i % sample_rate == 0)
-            .map(|(_, (k, v))| (k.clone(), *v))
-            .collect()
+    fn read_u32(&mut self) -> Result<u32> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
     }
     
-    /// SSTable 삭제
-    pub async fn delete(&self) -> Result<()> {
-        tokio::fs::remove_file(&self.file_path).await?;
-        Ok(())
+    fn read_u64(&mut self) -> Result<u64> {
+        let bytes = self.read_bytes(8)?;
+        Ok(u64::from_be_bytes(bytes.try_into().expect("8 bytes")))
     }
     
-    /// 파일 크기 가져오기
-    pub async fn file_size(&self) -> Result<u64> {
-        let metadata = tokio::fs::metadata(&self.file_path).await?;
-        Ok(metadata.len())
+    fn read_framed(&mut self) -> Result<&'a [u8]> {
+        let len = self.read_u32()? as usize;
+        self.read_bytes(len)
+    }
+    
+    /// keyed_entry로 기록한 항목 읽기 (인덱스는 오프셋과 길이, 요약은 인덱스 오프셋)
+    ///
+    /// base_offset은 읽고 있는 구간의 파일 내 시작 위치 (손상 위치 보고용).
+    /// 부가 정보(인덱스는 파티션 클러스터링 최소/최대)는 필요한 쪽에서만 역직렬화하도록 바이트로 반환
+    fn read_keyed_entry<const N: usize>(&mut self, file: &Path, base_offset: u64) -> Result<(PartitionKey, [u64; N], &'a [u8])> {
+        let start = self.position;
+        let mut values = [0u64; N];
+        let framed = (|| {
+            let key_data = self.read_framed()?;
+            for value in &mut values {
+                *value = self.read_u64()?;
+            }
+            let extra = self.read_framed()?;
+            let end = self.position;
+            Ok::<_, CoreDBError>((key_data, extra, end, self.read_u32()?))
+        })();
+        // 길이 접두사가 손상되면 체크섬을 확인하기 전에 항목 경계를 잃음
+        let (key_data, extra, end, expected) = framed.map_err(|e| CoreDBError::Corruption {
+            file: file.display().to_string(),
+            offset: base_offset + start as u64,
+            message: format!("truncated entry: {}", e),
+        })?;
+        let entry = &self.data[start..end];
+        verify_checksum(entry, expected, file, base_offset + start as u64)?;
+        Ok((bincode::deserialize(key_data)?, values, extra))
+    }
+    
+    fn is_empty(&self) -> bool {
+        self.position >= self.data.len()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::schema::{CassandraValue, ColumnDefinition, CassandraDataType, Cell, ClusteringKey};
-    use std::collections::HashMap;
+    use crate::schema::{CassandraValue, ColumnDefinition, CassandraDataType};
     
     fn create_test_schema() -> std::sync::Arc<crate::schema::TableSchema> {
         std::sync::Arc::new(crate::schema::TableSchema::new(
@@ -346,8 +1596,8 @@ mod tests {
                 components: vec![CassandraValue::BigInt(timestamp)],
             }),
             cells: {
-                let mut cells = HashMap::new();
-                cells.insert("value".to_string(), Cell {
+                let mut cells = Cells::new();
+                cells.insert("value", Cell {
                     value: CassandraValue::Text(value.to_string()),
                     timestamp: chrono::Utc::now().timestamp_micros(),
                     ttl: None,
@@ -394,4 +1644,827 @@ mod tests {
         // 정리
         sstable.delete().await.unwrap();
     }
+    
+    #[tokio::test]
+    async fn test_open_and_discover_existing_sstables() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_sstable_open");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        assert!(SSTable::discover(&temp_dir).await.unwrap().is_empty());
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let memtable = crate::storage::Memtable::new(create_test_schema());
+        for i in 0..300 {
+            memtable.put(create_test_row(i, 1000, &format!("value_{}", i))).unwrap();
+        }
+        let created = SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::LZ4).await.unwrap();
+        tokio::fs::write(temp_dir.join("notes.txt"), b"not an sstable").await.unwrap();
+        
+        // 다시 연 SSTable은 생성 직후와 같은 메타데이터를 가짐
+        let opened = SSTable::open(&created.file_path).await.unwrap();
+        assert_eq!(opened, created);
+        
+        let partition_key = PartitionKey { components: vec![CassandraValue::Int(42)] };
+        let partition = opened.read_partition(&partition_key).await.unwrap().unwrap();
+        let row = partition.rows.iter().next().unwrap().value().clone();
+        assert_eq!(row.cells["value"].value, CassandraValue::Text("value_42".to_string()));
+        
+        let discovered = SSTable::discover(&temp_dir).await.unwrap();
+        assert_eq!(discovered, vec![created.clone()]);
+        assert!(SSTable::open(temp_dir.join("notes.txt")).await.is_err());
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_partition_index_on_disk_with_sampled_summary() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_sstable_index");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let memtable = crate::storage::Memtable::new(create_test_schema());
+        for i in 0..1000 {
+            memtable.put(create_test_row(i * 2, 1000, &format!("value_{}", i * 2))).unwrap();
+        }
+        let sstable = SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::None).await.unwrap();
+        
+        // 메모리에는 128개마다 하나씩 샘플만 유지
+        assert!(sstable.index_file_path.exists());
+        assert_eq!(sstable.partition_count, 1000);
+        assert_eq!(sstable.summary_index.len(), 8);
+        
+        let keys = sstable.partition_keys().await.unwrap();
+        assert_eq!(keys.len(), 1000);
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        
+        // 요약 구간 경계와 그 사이의 키, 없는 키 모두 인덱스 파일에서 찾음
+        for i in [0, 127, 128, 129, 500, 999] {
+            let partition_key = PartitionKey { components: vec![CassandraValue::Int(i * 2)] };
+            let partition = sstable.read_partition(&partition_key).await.unwrap().unwrap();
+            let row = partition.rows.iter().next().unwrap().value().clone();
+            assert_eq!(row.cells["value"].value, CassandraValue::Text(format!("value_{}", i * 2)));
+        }
+        for missing in [-1, 3, 2001] {
+            let partition_key = PartitionKey { components: vec![CassandraValue::Int(missing)] };
+            assert!(sstable.read_partition(&partition_key).await.unwrap().is_none());
+        }
+        
+        sstable.delete().await.unwrap();
+        assert!(!sstable.file_path.exists());
+        assert!(!sstable.index_file_path.exists());
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_block_compression_reads_only_needed_chunks() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_sstable_chunks");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        for compression in [CompressionType::None, CompressionType::LZ4, CompressionType::Snappy, CompressionType::ZSTD] {
+            // 작은 파티션 여러 개와 여러 청크에 걸친 큰 파티션 하나
+            let memtable = crate::storage::Memtable::new(create_test_schema());
+            for i in 0..50 {
+                memtable.put(create_test_row(i, 1000, &format!("value_{}", i))).unwrap();
+            }
+            for ts in 0..1000 {
+                memtable.put(create_test_row(1000, ts, &format!("{:0>200}", ts))).unwrap();
+            }
+            let sstable = SSTable::create_from_memtable(&memtable, &temp_dir, compression).await.unwrap();
+            let info = &sstable.compression_info;
+            assert!(info.chunk_count() > 2, "{:?}", compression);
+            assert!(sstable.compression_info_path.exists());
+            if compression != CompressionType::None {
+                assert!(info.compressed_end - info.chunk_offsets[0] < info.data_length, "{:?}", compression);
+            }
+            
+            let opened = SSTable::open(&sstable.file_path).await.unwrap();
+            assert_eq!(opened, sstable);
+            
+            let partition_key = PartitionKey { components: vec![CassandraValue::Int(7)] };
+            let partition = opened.read_partition(&partition_key).await.unwrap().unwrap();
+            let row = partition.rows.iter().next().unwrap().value().clone();
+            assert_eq!(row.cells["value"].value, CassandraValue::Text("value_7".to_string()));
+            
+            let partition_key = PartitionKey { components: vec![CassandraValue::Int(1000)] };
+            let partition = opened.read_partition(&partition_key).await.unwrap().unwrap();
+            assert_eq!(partition.rows.len(), 1000);
+            let row = partition.rows.back().unwrap().value().clone();
+            assert_eq!(row.cells["value"].value, CassandraValue::Text(format!("{:0>200}", 999)));
+            
+            sstable.delete().await.unwrap();
+            assert!(!sstable.compression_info_path.exists());
+        }
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_checksums_report_corruption_with_file_and_offset() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_sstable_checksums");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let memtable = crate::storage::Memtable::new(create_test_schema());
+        for i in 0..10 {
+            memtable.put(create_test_row(i, 1000, &format!("value_{}", i))).unwrap();
+        }
+        let sstable = SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::LZ4).await.unwrap();
+        let partition_key = PartitionKey { components: vec![CassandraValue::Int(3)] };
+        
+        let flip = |path: PathBuf, offset: u64| async move {
+            let mut data = tokio::fs::read(&path).await.unwrap();
+            data[offset as usize] ^= 0x01;
+            tokio::fs::write(&path, data).await.unwrap();
+        };
+        let assert_corruption = |result: Result<Option<Partition>>, path: &Path, at: u64| match result {
+            Err(CoreDBError::Corruption { file, offset, .. }) => {
+                assert_eq!(file, path.display().to_string());
+                assert_eq!(offset, at);
+            },
+            other => panic!("expected corruption, got {:?}", other.map(|p| p.is_some())),
+        };
+        
+        // 데이터 청크: 청크 시작 위치를 보고
+        let chunk_start = sstable.compression_info.chunk_offsets[0];
+        flip(sstable.file_path.clone(), chunk_start + 5).await;
+        assert_corruption(sstable.read_partition(&partition_key).await, &sstable.file_path, chunk_start);
+        flip(sstable.file_path.clone(), chunk_start + 5).await;
+        assert!(sstable.read_partition(&partition_key).await.unwrap().is_some());
+        
+        // 인덱스 항목: 손상된 항목의 위치를 보고
+        flip(sstable.index_file_path.clone(), 2).await;
+        let partition_key = PartitionKey { components: vec![CassandraValue::Int(0)] };
+        assert_corruption(sstable.read_partition(&partition_key).await, &sstable.index_file_path, 0);
+        flip(sstable.index_file_path.clone(), 2).await;
+        
+        // 푸터와 블룸 필터: 다시 열 때 확인
+        let footer_offset = sstable.compression_info.compressed_end;
+        flip(sstable.file_path.clone(), footer_offset + 1).await;
+        assert_corruption(SSTable::open(&sstable.file_path).await.map(|_| None), &sstable.file_path, footer_offset);
+        flip(sstable.file_path.clone(), footer_offset + 1).await;
+        
+        let filter_path = sstable.component_file(FILTER_FILE_SUFFIX);
+        flip(filter_path.clone(), 0).await;
+        assert_corruption(SSTable::open(&sstable.file_path).await.map(|_| None), &filter_path, 0);
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_streaming_writer_emits_components_in_key_order() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_sstable_writer");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let memtable = crate::storage::Memtable::new(create_test_schema());
+        for i in 0..500 {
+            memtable.put(create_test_row(i, 1000, &format!("value_{}", i))).unwrap();
+        }
+        // 한 행이 청크 여러 개에 걸쳐도 청크 버퍼 크기를 넘지 않고 나눠 씀
+        memtable.put(create_test_row(1000, 1, &"x".repeat(3 * DEFAULT_CHUNK_LENGTH as usize))).unwrap();
+        
+        let mut writer = SSTableWriter::new(&temp_dir, CompressionType::Snappy, 501).await.unwrap();
+        let mut partitions = memtable.partitions();
+        let (first_key, first) = partitions.next().unwrap();
+        writer.append(&first_key, &first).await.unwrap();
+        assert!(writer.append(&first_key, &first).await.is_err());
+        for (partition_key, partition) in partitions {
+            writer.append(&partition_key, &partition).await.unwrap();
+        }
+        let sstable = writer.finish().await.unwrap();
+        
+        for suffix in COMPONENT_SUFFIXES {
+            assert!(sstable.component_file(suffix).exists(), "{}", suffix);
+        }
+        let sizes = sstable.component_sizes().await.unwrap();
+        assert_eq!(sizes.len(), COMPONENT_SUFFIXES.len());
+        assert_eq!(sizes[0], (DATA_FILE_SUFFIX, tokio::fs::metadata(&sstable.file_path).await.unwrap().len()));
+        assert!(sizes.iter().all(|(_, size)| *size > 0));
+        assert_eq!(sstable.partition_count, 501);
+        assert_eq!(sstable.summary_index.len(), 4);
+        assert!(sstable.min_timestamp <= sstable.max_timestamp);
+        assert_eq!(SSTable::open(&sstable.file_path).await.unwrap(), sstable);
+        
+        let partition_key = PartitionKey { components: vec![CassandraValue::Int(1000)] };
+        let partition = sstable.read_partition(&partition_key).await.unwrap().unwrap();
+        let row = partition.rows.front().unwrap().value().clone();
+        assert_eq!(row.cells["value"].value, CassandraValue::Text("x".repeat(3 * DEFAULT_CHUNK_LENGTH as usize)));
+        
+        sstable.delete().await.unwrap();
+        let mut entries = tokio::fs::read_dir(&temp_dir).await.unwrap();
+        assert!(entries.next_entry().await.unwrap().is_none());
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_scanner_yields_rows_in_key_order() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_sstable_scanner");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let memtable = crate::storage::Memtable::new(create_test_schema());
+        for i in 0..300 {
+            for ts in 0..3 {
+                memtable.put(create_test_row(i, ts, &format!("{:0>500}", i))).unwrap();
+            }
+        }
+        let sstable = Arc::new(SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::LZ4).await.unwrap());
+        assert!(sstable.compression_info.chunk_count() > 1);
+        
+        let mut scanner = sstable.scanner(ReadOptions::all());
+        let mut rows = Vec::new();
+        while let Some((partition_key, row)) = scanner.next().await.unwrap() {
+            assert_eq!(row.partition_key, partition_key);
+            rows.push((partition_key, row.clustering_key.clone().unwrap(), row.cells["value"].value.clone()));
+        }
+        assert_eq!(rows.len(), 900);
+        assert!(rows.windows(2).all(|pair| (&pair[0].0, &pair[0].1) < (&pair[1].0, &pair[1].1)));
+        let keys: Vec<PartitionKey> = rows.iter().map(|row| row.0.clone()).collect::<std::collections::BTreeSet<_>>().into_iter().collect();
+        assert_eq!(keys, sstable.partition_keys().await.unwrap());
+        assert_eq!(rows[3].2, CassandraValue::Text(format!("{:0>500}", match &keys[1].components[0] {
+            CassandraValue::Int(i) => *i,
+            other => panic!("{:?}", other),
+        })));
+        
+        // 파티션 단위 스캔과 일찍 버린 스캐너
+        let mut scanner = sstable.scanner(ReadOptions::all().with_columns(Vec::<String>::new()));
+        let (first_key, first) = scanner.next_partition().await.unwrap().unwrap();
+        assert_eq!(first_key, keys[0]);
+        assert_eq!(first.rows.len(), 3);
+        assert!(first.rows.front().unwrap().value().cells.is_empty());
+        drop(scanner);
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_mmap_reads_match_buffered_reads() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_sstable_mmap");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let memtable = crate::storage::Memtable::new(create_test_schema());
+        for i in 0..300 {
+            memtable.put(create_test_row(i, 1000, &format!("value_{}", i))).unwrap();
+        }
+        let buffered = SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::LZ4).await.unwrap();
+        let mapped = SSTable::open(&buffered.file_path).await.unwrap().with_access_mode(DiskAccessMode::Mmap);
+        
+        assert_eq!(mapped.partition_keys().await.unwrap(), buffered.partition_keys().await.unwrap());
+        for i in [0, 150, 299] {
+            let partition_key = PartitionKey { components: vec![CassandraValue::Int(i)] };
+            let expected = buffered.read_partition(&partition_key).await.unwrap().unwrap();
+            let actual = mapped.read_partition(&partition_key).await.unwrap().unwrap();
+            let (actual, expected) = (actual.rows.front().unwrap(), expected.rows.front().unwrap());
+            assert_eq!(actual.value().cells["value"].value, expected.value().cells["value"].value);
+            assert_eq!(actual.key(), expected.key());
+        }
+        let missing = PartitionKey { components: vec![CassandraValue::Int(1000)] };
+        assert!(mapped.read_partition(&missing).await.unwrap().is_none());
+        
+        let keys = mapped.partition_keys().await.unwrap();
+        let partitions = mapped.read_partitions(&keys, &ReadOptions::all()).await.unwrap();
+        assert_eq!(partitions.len(), 300);
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_read_partition_with_projection_and_limit() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_pushdown");
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let memtable = crate::storage::Memtable::new(create_test_schema());
+        for ts in 1..=5 {
+            let mut row = create_test_row(1, ts * 1000, &format!("value_{}", ts));
+            row.cells.insert("payload", Cell {
+                value: CassandraValue::Blob(vec![0u8; 256]),
+                timestamp: 1,
+                ttl: None,
+                is_deleted: false,
+            });
+            memtable.put(row).unwrap();
+        }
+        
+        let sstable = SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::LZ4).await.unwrap();
+        
+        let options = ReadOptions::all().with_columns(["value"]).with_limit(Some(2));
+        let partition_key = PartitionKey { components: vec![CassandraValue::Int(1)] };
+        let partition = sstable.read_partition_with(&partition_key, &options).await.unwrap().unwrap();
+        
+        // 클러스터링 순서상 앞의 두 행만, 요청한 컬럼만 반환
+        let rows: Vec<Row> = partition.rows.iter().map(|entry| entry.value().clone()).collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].clustering_key.as_ref().unwrap().components, vec![CassandraValue::BigInt(1000)]);
+        assert_eq!(rows[1].clustering_key.as_ref().unwrap().components, vec![CassandraValue::BigInt(2000)]);
+        for row in &rows {
+            assert_eq!(row.partition_key, partition_key);
+            assert!(row.cells.contains_key("value"));
+            assert!(!row.cells.contains_key("payload"));
+        }
+        
+        sstable.delete().await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_clustering_bounds_prune_sstables_and_partitions() {
+        use std::ops::Bound;
+        use crate::storage::ClusteringSlice;
+        
+        let temp_dir = std::env::temp_dir().join("coredb_test_clustering_bounds");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let memtable = crate::storage::Memtable::new(create_test_schema());
+        for ts in 1..=5 {
+            memtable.put(create_test_row(1, ts * 1000, &format!("value_{}", ts))).unwrap();
+        }
+        for ts in 10..=12 {
+            memtable.put(create_test_row(2, ts * 1000, &format!("value_{}", ts))).unwrap();
+        }
+        let sstable = SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::LZ4).await.unwrap();
+        let bigint = |value| ClusteringKey { components: vec![CassandraValue::BigInt(value)] };
+        assert_eq!(sstable.clustering_bounds, ClusteringBounds { min: Some(bigint(1000)), max: Some(bigint(12000)) });
+        assert_eq!(SSTable::open(&sstable.file_path).await.unwrap().clustering_bounds, sstable.clustering_bounds);
+        
+        let slice = |start, end| ReadOptions::all().with_clustering_slice(Some(ClusteringSlice { start, end }));
+        let first = PartitionKey { components: vec![CassandraValue::Int(1)] };
+        let second = PartitionKey { components: vec![CassandraValue::Int(2)] };
+        
+        // 범위 밖의 행은 건너뛰고 limit은 범위 안의 행만 셈
+        let options = slice(Bound::Included(CassandraValue::BigInt(4000)), Bound::Excluded(CassandraValue::BigInt(11000)));
+        let partition = sstable.read_partition_with(&first, &options).await.unwrap().unwrap();
+        let keys: Vec<ClusteringKey> = partition.rows.iter().filter_map(|entry| entry.key().clone()).collect();
+        assert_eq!(keys, vec![bigint(4000), bigint(5000)]);
+        let partition = sstable.read_partition_with(&first, &options.clone().with_limit(Some(1))).await.unwrap().unwrap();
+        assert_eq!(partition.rows.len(), 1);
+        
+        // 파티션 최소/최대와 겹치지 않으면 데이터를 읽지 않음
+        let options = slice(Bound::Unbounded, Bound::Excluded(CassandraValue::BigInt(9000)));
+        assert!(sstable.read_partition_with(&second, &options).await.unwrap().is_none());
+        let partitions = sstable.read_partitions(&[first.clone(), second.clone()], &options).await.unwrap();
+        assert_eq!(partitions.len(), 1);
+        assert_eq!(partitions[0].0, first);
+        
+        // SSTable 최소/최대와 겹치지 않으면 건너뜀
+        let options = slice(Bound::Excluded(CassandraValue::BigInt(12000)), Bound::Unbounded);
+        assert!(!sstable.may_contain_slice(&options));
+        assert!(sstable.read_partition_with(&second, &options).await.unwrap().is_none());
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_statistics_component_counts_rows_cells_and_tombstones() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_sstable_statistics");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let memtable = crate::storage::Memtable::new(create_test_schema());
+        for i in 0..10 {
+            for ts in 0..3 {
+                memtable.put(create_test_row(i, ts, &format!("{:0>200}", i))).unwrap();
+            }
+        }
+        let mut deleted = create_test_row(10, 0, "");
+        deleted.cells.insert("value", Cell { value: CassandraValue::Null, timestamp: 1, ttl: None, is_deleted: true });
+        memtable.put(deleted).unwrap();
+        
+        let sstable = SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::LZ4).await.unwrap();
+        let statistics = &sstable.statistics;
+        assert_eq!(statistics.row_count, 31);
+        assert_eq!(statistics.cell_count, 31);
+        assert_eq!(statistics.tombstone_count, 1);
+        assert_eq!(statistics.partition_sizes.count(), 11);
+        assert!(statistics.partition_sizes.max > 600);
+        assert_eq!(statistics.compressed_bytes, sstable.size_bytes);
+        assert!(statistics.compression_ratio() < 1.0);
+        
+        assert!(sstable.component_file(STATISTICS_FILE_SUFFIX).exists());
+        assert_eq!(&SSTable::open(&sstable.file_path).await.unwrap().statistics, statistics);
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_key_cache_skips_index_for_hot_keys() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_sstable_key_cache");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let memtable = crate::storage::Memtable::new(create_test_schema());
+        for i in 0..20 {
+            memtable.put(create_test_row(i, 1000, &format!("value_{}", i))).unwrap();
+        }
+        let cache = Arc::new(KeyCache::new(100));
+        let sstable = SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::LZ4).await.unwrap()
+            .with_key_cache(Some(cache.clone()));
+        let hot = PartitionKey { components: vec![CassandraValue::Int(7)] };
+        let cold = PartitionKey { components: vec![CassandraValue::Int(8)] };
+        
+        assert!(sstable.read_partition(&hot).await.unwrap().is_some());
+        assert_eq!((cache.stats().hits, cache.stats().misses), (0, 1));
+        
+        // 캐시된 키는 인덱스 파일 없이도 읽힘
+        tokio::fs::remove_file(&sstable.index_file_path).await.unwrap();
+        let partition = sstable.read_partition(&hot).await.unwrap().unwrap();
+        assert_eq!(partition.rows.front().unwrap().value().cells["value"].value, CassandraValue::Text("value_7".to_string()));
+        let partitions = sstable.read_partitions(std::slice::from_ref(&hot), &ReadOptions::all()).await.unwrap();
+        assert_eq!(partitions.len(), 1);
+        assert!(sstable.read_partition(&cold).await.is_err());
+        assert_eq!((cache.stats().hits, cache.stats().misses), (2, 2));
+        
+        // 삭제된 SSTable의 항목은 캐시에서 빠짐
+        sstable.delete().await.unwrap();
+        assert_eq!(cache.stats().entries, 0);
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_chunk_cache_serves_repeat_reads_without_the_data_file() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_sstable_chunk_cache");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        // 파티션마다 10KB라 여러 청크에 나뉨
+        let memtable = crate::storage::Memtable::new(create_test_schema());
+        for i in 0..20 {
+            memtable.put(create_test_row(i, 1000, &format!("{:0>10240}", i))).unwrap();
+        }
+        let cache = Arc::new(ChunkCache::new(16 * 1024 * 1024));
+        let sstable = SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::LZ4).await.unwrap()
+            .with_chunk_cache(Some(cache.clone()));
+        assert!(sstable.compression_info.chunk_count() > 1);
+        let hot = PartitionKey { components: vec![CassandraValue::Int(7)] };
+        assert!(sstable.read_partition(&hot).await.unwrap().is_some());
+        let cold_stats = cache.stats();
+        assert_eq!(cold_stats.hits, 0);
+        assert!(cold_stats.chunks > 0 && cold_stats.size_bytes > 0);
+        
+        // 데이터 파일을 망가뜨려도 캐시된 청크는 다시 읽거나 풀지 않음
+        let length = tokio::fs::metadata(&sstable.file_path).await.unwrap().len();
+        tokio::fs::write(&sstable.file_path, vec![0u8; length as usize]).await.unwrap();
+        let partition = sstable.read_partition(&hot).await.unwrap().unwrap();
+        assert_eq!(partition.rows.front().unwrap().value().cells["value"].value, CassandraValue::Text(format!("{:0>10240}", 7)));
+        assert_eq!(cache.stats().hits, cold_stats.chunks as u64);
+        assert_eq!(cache.stats().misses, cold_stats.misses);
+        
+        // 캐시에 없는 청크는 파일에서 읽으므로 손상이 드러남
+        let mut failures = 0;
+        for i in 0..20 {
+            let key = PartitionKey { components: vec![CassandraValue::Int(i)] };
+            failures += sstable.read_partition(&key).await.is_err() as usize;
+        }
+        assert!(failures > 0);
+        
+        // 삭제된 SSTable의 청크는 캐시에서 빠짐
+        sstable.delete().await.unwrap();
+        assert_eq!(cache.stats().chunks, 0);
+        assert_eq!(cache.stats().size_bytes, 0);
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    /// 행 헤더를 bincode로 기록하던 형식(버전 14 미만)의 파티션 레코드 (버전 11 미만은 셀도 bincode)
+    fn legacy_partition_record(partition_key: &PartitionKey, partition: &Partition, version: u32) -> Vec<u8> {
+        let encode = |cell: &Cell| if version < FIXED_LAYOUT_CELLS_VERSION {
+            bincode::serialize(cell).unwrap()
+        } else {
+            let mut data = Vec::new();
+            encode_cell(cell, &mut data).unwrap();
+            data
+        };
+        let mut data = Vec::new();
+        write_framed(&mut data, &bincode::serialize(partition_key).unwrap());
+        if version < FIXED_LAYOUT_CELLS_VERSION {
+            write_framed(&mut data, &bincode::serialize(&partition.static_columns).unwrap());
+        } else {
+            let mut static_columns = Vec::new();
+            for (name, cell) in &partition.static_columns {
+                write_framed(&mut static_columns, name.as_bytes());
+                write_framed(&mut static_columns, &encode(cell));
+            }
+            write_framed(&mut data, &static_columns);
+        }
+        let mut columns = ColumnLayout::default();
+        for row in partition.rows.iter() {
+            for column in row.value().cells.keys() {
+                columns.push(column);
+            }
+        }
+        write_framed(&mut data, &bincode::serialize(&columns.names().collect::<Vec<_>>()).unwrap());
+        data.extend_from_slice(&(partition.rows.len() as u32).to_be_bytes());
+        for row in partition.rows.iter() {
+            let row = row.value();
+            write_framed(&mut data, &bincode::serialize(&(&row.clustering_key, row.timestamp)).unwrap());
+            data.extend_from_slice(&(row.cells.len() as u32).to_be_bytes());
+            for (column, cell) in &row.cells {
+                data.extend_from_slice(&columns.id(column).unwrap().to_be_bytes());
+                write_framed(&mut data, &encode(cell));
+            }
+        }
+        data
+    }
+    
+    /// SSTable을 이전 형식으로 다시 씀 (버전 9는 청크 길이 접두사도 없음, 이전 형식에 없던 구성 요소도 맞춤)
+    ///
+    /// 인덱스 항목은 크기가 같은 고정 폭 오프셋만 바뀌므로 요약 인덱스는 그대로 둠
+    async fn downgrade(sstable: &Arc<SSTable>, version: u32) {
+        let mut records = Vec::new();
+        let mut positions = Vec::new();
+        let mut scanner = sstable.scanner(ReadOptions::all());
+        while let Some((partition_key, partition)) = scanner.next_partition().await.unwrap() {
+            let record = legacy_partition_record(&partition_key, &partition, version);
+            positions.push((records.len() as u64, record.len() as u64));
+            records.extend(record);
+        }
+        
+        let info = &sstable.compression_info;
+        let mut legacy = CompressionInfo::new(info.compression, info.chunk_length, 0);
+        let mut file = Vec::new();
+        for chunk in records.chunks(info.chunk_length as usize) {
+            if version < crate::storage::LENGTH_PREFIXED_CHUNKS_VERSION {
+                let chunk = lz4_flex::compress_prepend_size(chunk);
+                legacy.chunk_offsets.push(file.len() as u64);
+                legacy.chunk_checksums.push(crate::storage::checksum(&chunk));
+                file.extend(chunk);
+            } else {
+                file.extend(legacy.append_chunk(chunk).unwrap());
+            }
+        }
+        legacy.data_length = records.len() as u64;
+        legacy.compressed_end = file.len() as u64;
+        
+        let data = tokio::fs::read(&sstable.file_path).await.unwrap();
+        let footer_len = u32::from_be_bytes(data[data.len() - 4..].try_into().unwrap()) as usize;
+        let footer_data = &data[data.len() - 4 - footer_len..data.len() - 4];
+        let mut footer: SSTableFooter = bincode::deserialize(strip_checksum(footer_data, &sstable.file_path, 0).unwrap()).unwrap();
+        footer.version = version;
+        footer.data_length = legacy.compressed_end;
+        let footer = with_checksum(bincode::serialize(&footer).unwrap());
+        file.extend(&footer);
+        file.extend((footer.len() as u32).to_be_bytes());
+        tokio::fs::write(&sstable.file_path, file).await.unwrap();
+        let info = if version < crate::storage::DICTIONARY_COMPRESSION_VERSION {
+            // 사전이 없는 청크 정보
+            let legacy_info = (legacy.compression, legacy.chunk_length, legacy.data_length, &legacy.chunk_offsets, &legacy.chunk_checksums, legacy.compressed_end);
+            bincode::serialize(&legacy_info).unwrap()
+        } else {
+            bincode::serialize(&legacy).unwrap()
+        };
+        write_component(&sstable.compression_info_path, &info).await.unwrap();
+        
+        let index = tokio::fs::read(&sstable.index_file_path).await.unwrap();
+        let mut reader = FramedReader::new(&index);
+        let mut rewritten = Vec::new();
+        for (offset, length) in positions {
+            let (partition_key, [_, _], bounds) = reader.read_keyed_entry::<2>(&sstable.index_file_path, 0).unwrap();
+            rewritten.extend(keyed_entry(&partition_key, &[offset, length], bounds).unwrap());
+        }
+        assert_eq!(rewritten.len(), index.len());
+        tokio::fs::write(&sstable.index_file_path, rewritten).await.unwrap();
+        
+        if version >= crate::storage::DELETION_TIME_STATISTICS_VERSION {
+            return;
+        }
+        // 만료 시각이 없는 통계
+        let statistics = &sstable.statistics;
+        let legacy_statistics = (
+            statistics.row_count,
+            statistics.tombstone_count,
+            statistics.cell_count,
+            &statistics.partition_sizes,
+            statistics.uncompressed_bytes,
+            statistics.compressed_bytes,
+        );
+        let statistics_path = sstable.file_path.with_file_name(format!("{}{}", sstable.id, STATISTICS_FILE_SUFFIX));
+        write_component(&statistics_path, &bincode::serialize(&legacy_statistics).unwrap()).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_legacy_formats_are_read_and_upgraded_on_open() {
+        for version in [MIN_SUPPORTED_FORMAT_VERSION, FIXED_LAYOUT_CELLS_VERSION - 1, DELTA_CLUSTERING_KEYS_VERSION - 1] {
+            let temp_dir = std::env::temp_dir().join(format!("coredb_test_sstable_legacy_format_{}", version));
+            let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+            tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+            
+            let memtable = crate::storage::Memtable::new(create_test_schema());
+            for i in 0..20 {
+                memtable.put(create_test_row(i, 1000, &format!("{:0>10240}", i))).unwrap();
+            }
+            let sstable = Arc::new(SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::LZ4).await.unwrap());
+            downgrade(&sstable, version).await;
+            
+            // 이전 형식도 그대로 읽히지만 버전 11 미만은 뷰를 쓸 수 없음
+            let legacy = SSTable::open(&sstable.file_path).await.unwrap();
+            assert_eq!(legacy.format_version, version);
+            assert_eq!(legacy.statistics.row_count, 20);
+            if version < crate::storage::DELETION_TIME_STATISTICS_VERSION {
+                assert_eq!(legacy.statistics.max_deletion_time, i64::MAX);
+            }
+            let key = PartitionKey { components: vec![CassandraValue::Int(7)] };
+            let partition = legacy.read_partition(&key).await.unwrap().unwrap();
+            assert_eq!(partition.rows.front().unwrap().value().cells["value"].value, CassandraValue::Text(format!("{:0>10240}", 7)));
+            assert_eq!(legacy.with_partition_view(&key, |_| Ok(())).await.is_err(), version < FIXED_LAYOUT_CELLS_VERSION);
+            
+            // 테이블을 열 때 현재 형식으로 다시 쓰고 원본은 지움
+            let (manifest, sstables) = crate::storage::Manifest::open_table(&temp_dir).await.unwrap();
+            assert_eq!(sstables.len(), 1);
+            let upgraded = &sstables[0];
+            assert_eq!(upgraded.format_version, SSTABLE_FORMAT_VERSION);
+            assert_ne!(upgraded.id, legacy.id);
+            assert!(!legacy.file_path.exists());
+            assert!(manifest.contains(&upgraded.id) && !manifest.contains(&legacy.id));
+            assert_eq!(upgraded.partition_count, 20);
+            let partition = upgraded.read_partition(&key).await.unwrap().unwrap();
+            assert_eq!(partition.rows.front().unwrap().value().cells["value"].value, CassandraValue::Text(format!("{:0>10240}", 7)));
+            
+            // 다시 열면 더 이상 바꾸지 않음
+            let (_, reopened) = crate::storage::Manifest::open_table(&temp_dir).await.unwrap();
+            assert_eq!(reopened[0].id, upgraded.id);
+            
+            tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_partition_view_reads_single_cells_in_place() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_sstable_partition_view");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let memtable = crate::storage::Memtable::new(create_test_schema());
+        for ts in 0..5 {
+            memtable.put(create_test_row(1, ts, &format!("value_{}", ts))).unwrap();
+        }
+        let mut with_ttl = create_test_row(1, 2, "value_2");
+        with_ttl.cells.insert("value", Cell { value: CassandraValue::Text("value_2".to_string()), timestamp: i64::MAX, ttl: Some(60), is_deleted: false });
+        memtable.put(with_ttl).unwrap();
+        let sstable = SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::LZ4).await.unwrap();
+        let key = PartitionKey { components: vec![CassandraValue::Int(1)] };
+        
+        // 텍스트 값은 읽은 레코드를 그대로 참조
+        let texts = sstable.with_partition_view(&key, |partition| {
+            assert_eq!(partition.row_count(), 5);
+            let value = partition.column_id("value").unwrap();
+            let mut texts = Vec::new();
+            for row in partition.rows() {
+                for cell in row?.cells() {
+                    let (id, cell) = cell?;
+                    assert_eq!(id, value);
+                    let crate::storage::ValueView::Text(text) = cell.value()? else { panic!("expected text") };
+                    texts.push(text.len());
+                }
+            }
+            Ok(texts)
+        }).await.unwrap().unwrap();
+        assert_eq!(texts, vec![7; 5]);
+        
+        let clustering_key = |ts: i64| Some(ClusteringKey { components: vec![CassandraValue::BigInt(ts)] });
+        let cell = sstable.read_cell(&key, &clustering_key(2), "value").await.unwrap().unwrap();
+        assert_eq!((cell.value, cell.ttl), (CassandraValue::Text("value_2".to_string()), Some(60)));
+        assert!(sstable.read_cell(&key, &clustering_key(9), "value").await.unwrap().is_none());
+        assert!(sstable.read_cell(&key, &clustering_key(2), "missing").await.unwrap().is_none());
+        let absent = PartitionKey { components: vec![CassandraValue::Int(2)] };
+        assert!(sstable.read_cell(&absent, &clustering_key(2), "value").await.unwrap().is_none());
+        
+        // static 컬럼도 같은 셀 형식으로 기록됨
+        let static_cell = Cell { value: CassandraValue::BigInt(42), timestamp: 1, ttl: None, is_deleted: false };
+        let partition = Partition {
+            rows: crossbeam_skiplist::SkipMap::new(),
+            static_columns: HashMap::from([("total".to_string(), static_cell)]),
+        };
+        let mut writer = SSTableWriter::new(&temp_dir, CompressionType::None, 1).await.unwrap();
+        writer.append(&key, &partition).await.unwrap();
+        let sstable = writer.finish().await.unwrap();
+        let cell = sstable.read_cell(&key, &None, "total").await.unwrap().unwrap();
+        assert_eq!(cell.value, CassandraValue::BigInt(42));
+        let partition = sstable.read_partition(&key).await.unwrap().unwrap();
+        assert_eq!(partition.static_columns["total"].value, CassandraValue::BigInt(42));
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_dictionary_compression_trains_on_repeated_text() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_sstable_zstd_dictionary");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        // 몇 가지 로그 문장이 반복되는 테이블
+        let messages = [
+            "GET /api/v1/users/profile completed with status 200 in the request handler",
+            "POST /api/v1/orders rejected by the payment gateway: card declined by issuer",
+            "background compaction finished for keyspace events, table page_views",
+            "connection pool exhausted while waiting for a database connection lease",
+        ];
+        let memtable = crate::storage::Memtable::new(create_test_schema());
+        for i in 0..20_000 {
+            memtable.put(create_test_row(i % 100, i as i64, messages[(i as usize * 7) % messages.len()])).unwrap();
+        }
+        let plain = SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::ZSTD).await.unwrap();
+        let sstable = SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::ZSTDDictionary).await.unwrap();
+        let info = &sstable.compression_info;
+        assert!(!info.dictionary.is_empty());
+        assert!(info.chunk_count() > 1);
+        assert!(info.data_length > DICTIONARY_TRAINING_BYTES);
+        assert_eq!(info.data_length, plain.compression_info.data_length);
+        assert!(sstable.size_bytes < plain.size_bytes, "{} >= {}", sstable.size_bytes, plain.size_bytes);
+        
+        // 다시 열어도 사전으로 모든 청크를 풂
+        let reopened = Arc::new(SSTable::open(&sstable.file_path).await.unwrap());
+        assert_eq!(reopened.compression_info.dictionary, info.dictionary);
+        let mut scanner = reopened.scanner(ReadOptions::all());
+        let mut rows = 0;
+        while let Some((_, row)) = scanner.next().await.unwrap() {
+            let CassandraValue::Text(text) = &row.cells["value"].value else { panic!("expected text") };
+            assert!(messages.contains(&text.as_str()));
+            rows += 1;
+        }
+        assert_eq!(rows, 20_000);
+        
+        // 반복되는 텍스트가 없으면 사전 없이 zstd로 압축
+        let memtable = crate::storage::Memtable::new(create_test_schema());
+        for i in 0..100 {
+            memtable.put(create_test_row(i, 1, &format!("unique value {}", i))).unwrap();
+        }
+        let sstable = SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::ZSTDDictionary).await.unwrap();
+        assert!(sstable.compression_info.dictionary.is_empty());
+        let partition = sstable.read_partition(&PartitionKey { components: vec![CassandraValue::Int(42)] }).await.unwrap().unwrap();
+        assert_eq!(partition.rows.front().unwrap().value().cells["value"].value, CassandraValue::Text("unique value 42".to_string()));
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+
+    #[test]
+    fn test_row_header_delta_encodes_time_series_keys() {
+        let key = |value| Some(ClusteringKey { components: vec![value] });
+        let headers = [
+            key(CassandraValue::Timestamp(1_700_000_000_000_000)),
+            key(CassandraValue::Timestamp(1_700_000_001_000_000)),
+            key(CassandraValue::Timestamp(1_699_999_999_000_000)),
+            key(CassandraValue::BigInt(i64::MIN)),
+            key(CassandraValue::BigInt(i64::MAX)),
+            key(CassandraValue::Text("fallback".to_string())),
+            None,
+        ];
+        let mut delta_base = None;
+        let encoded: Vec<Vec<u8>> = headers.iter().enumerate().map(|(i, clustering_key)| {
+            let mut out = Vec::new();
+            encode_row_header(&mut out, clustering_key, i as i64, &mut delta_base).unwrap();
+            out
+        }).collect();
+        
+        // 첫 키 이후 가까운 타임스탬프는 bincode보다 작음
+        let bincode_len = bincode::serialize(&(&headers[1], 1i64)).unwrap().len();
+        assert!(encoded[1].len() < 16 && encoded[1].len() < bincode_len, "{} vs {}", encoded[1].len(), bincode_len);
+        assert_eq!(encoded[5][0], ROW_HEADER_BINCODE);
+        
+        let mut delta_base = None;
+        for (i, (header, clustering_key)) in encoded.iter().zip(&headers).enumerate() {
+            assert_eq!(decode_row_header(header, &mut delta_base).unwrap(), (clustering_key.clone(), i as i64));
+        }
+        assert!(decode_row_header(&[ROW_HEADER_BIGINT_DELTA, 0x80], &mut None).is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_timestamp_clustering_keys_round_trip_through_sstable() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_sstable_delta_clustering");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        
+        let memtable = crate::storage::Memtable::new(create_test_schema());
+        let base = 1_700_000_000_000_000;
+        for i in 0..1000 {
+            memtable.put(create_test_row(i % 4, base + (i as i64) * 1_000_000, "v")).unwrap();
+        }
+        let sstable = Arc::new(SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::None).await.unwrap());
+        assert_eq!(sstable.format_version, DELTA_CLUSTERING_KEYS_VERSION);
+        
+        // 같은 데이터를 bincode 행 헤더로 쓴 형식보다 작음
+        let mut legacy_bytes = 0;
+        let mut scanner = sstable.scanner(ReadOptions::all());
+        while let Some((partition_key, partition)) = scanner.next_partition().await.unwrap() {
+            legacy_bytes += legacy_partition_record(&partition_key, &partition, DELTA_CLUSTERING_KEYS_VERSION - 1).len() as u64;
+        }
+        assert!(sstable.compression_info.data_length < legacy_bytes);
+        
+        let key = PartitionKey { components: vec![CassandraValue::Int(2)] };
+        let partition = sstable.read_partition(&key).await.unwrap().unwrap();
+        let keys: Vec<_> = partition.rows.iter().map(|row| row.value().clustering_key.clone().unwrap().components[0].clone()).collect();
+        assert_eq!(keys.len(), 250);
+        assert_eq!(keys[0], CassandraValue::BigInt(base + 2_000_000));
+        assert_eq!(keys[249], CassandraValue::BigInt(base + 998_000_000));
+        sstable.with_partition_view(&key, |view| {
+            let rows = view.rows().collect::<Result<Vec<_>>>()?;
+            assert_eq!(rows.len(), 250);
+            assert_eq!(rows[1].clustering_key, Some(ClusteringKey { components: vec![CassandraValue::BigInt(base + 6_000_000)] }));
+            Ok(())
+        }).await.unwrap();
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
 }
@@ -0,0 +1,129 @@
+use std::io::Write;
+use std::sync::Arc;
+use serde_json::{json, Map, Value};
+use crate::schema::{Cell, PartitionKey, Row};
+use crate::storage::{SSTable, ReadOptions};
+use crate::storage::memtable::Partition;
+use crate::error::*;
+
+/// SSTable 내용을 JSON 배열로 출력 (`coredb sstable dump`, 출력한 파티션 수 반환)
+///
+/// 파티션을 하나씩 읽어 한 줄에 하나씩 쓰므로 큰 파일도 메모리에 모두 올리지 않음.
+/// 툼스톤과 만료된 TTL 셀도 그대로 출력
+pub async fn dump_sstable(sstable: &Arc<SSTable>, out: &mut impl Write) -> Result<usize> {
+    let mut scanner = sstable.scanner(ReadOptions::all());
+    let mut count = 0;
+    out.write_all(b"[")?;
+    while let Some((partition_key, partition)) = scanner.next_partition().await? {
+        if count > 0 {
+            out.write_all(b",")?;
+        }
+        out.write_all(b"\n  ")?;
+        serde_json::to_writer(&mut *out, &partition_json(&partition_key, &partition)?)?;
+        count += 1;
+    }
+    out.write_all(b"\n]\n")?;
+    out.flush()?;
+    Ok(count)
+}
+
+/// 파티션 하나의 JSON (키, static 셀, 클러스터링 순서의 행)
+pub fn partition_json(partition_key: &PartitionKey, partition: &Partition) -> Result<Value> {
+    let mut static_columns: Vec<_> = partition.static_columns.iter().collect();
+    static_columns.sort_by_key(|(name, _)| name.as_str());
+    let static_cells = static_columns.into_iter()
+        .map(|(name, cell)| cell_json(name, cell))
+        .collect::<Result<Vec<_>>>()?;
+    let rows = partition.rows.iter()
+        .map(|entry| row_json(entry.value()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(json!({
+        "partition": { "key": serde_json::to_value(&partition_key.components)? },
+        "static": static_cells,
+        "rows": rows,
+    }))
+}
+
+fn row_json(row: &Row) -> Result<Value> {
+    let cells = (&row.cells).into_iter()
+        .map(|(name, cell)| cell_json(name, cell))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(json!({
+        "clustering": row.clustering_key.as_ref().map(|key| serde_json::to_value(&key.components)).transpose()?,
+        "timestamp": row.timestamp,
+        "cells": cells,
+    }))
+}
+
+/// 셀 JSON (툼스톤은 값 대신 deleted, TTL 셀은 만료 시각(초)도 포함)
+fn cell_json(name: &str, cell: &Cell) -> Result<Value> {
+    let mut object = Map::new();
+    object.insert("name".to_string(), json!(name));
+    if cell.is_deleted {
+        object.insert("deleted".to_string(), json!(true));
+    } else {
+        object.insert("value".to_string(), serde_json::to_value(&cell.value)?);
+    }
+    object.insert("timestamp".to_string(), json!(cell.timestamp));
+    if let Some(ttl) = cell.ttl {
+        object.insert("ttl".to_string(), json!(ttl));
+        object.insert("expires_at".to_string(), json!(cell.timestamp / 1_000_000 + ttl as i64));
+    }
+    Ok(Value::Object(object))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{CassandraDataType, CassandraValue, ClusteringKey, ColumnDefinition, TableSchema};
+    use crate::storage::{CompressionType, Memtable};
+
+    #[tokio::test]
+    async fn test_dump_prints_rows_timestamps_and_tombstones() {
+        let temp_dir = std::env::temp_dir().join("coredb_test_sstable_dump");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+
+        let column = |name: &str, data_type| ColumnDefinition { name: name.to_string(), data_type, is_static: false };
+        let schema = Arc::new(TableSchema::new(
+            "events".to_string(),
+            "test_ks".to_string(),
+            vec![column("id", CassandraDataType::Int)],
+            vec![column("seq", CassandraDataType::BigInt)],
+            vec![column("value", CassandraDataType::Text)],
+            vec![],
+        ));
+        let memtable = Memtable::new(schema);
+        for (id, seq, cell) in [
+            (1, 1, Cell { value: CassandraValue::Text("a".to_string()), timestamp: 5_000_000, ttl: Some(60), is_deleted: false }),
+            (1, 2, Cell { value: CassandraValue::Null, timestamp: 7_000_000, ttl: None, is_deleted: true }),
+            (2, 1, Cell { value: CassandraValue::Text("b".to_string()), timestamp: 9, ttl: None, is_deleted: false }),
+        ] {
+            let mut cells = crate::schema::Cells::new();
+            cells.insert("value", cell);
+            memtable.put(Row {
+                partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] },
+                clustering_key: Some(ClusteringKey { components: vec![CassandraValue::BigInt(seq)] }),
+                cells,
+                timestamp: seq,
+            }).unwrap();
+        }
+        let sstable = Arc::new(SSTable::create_from_memtable(&memtable, &temp_dir, CompressionType::LZ4).await.unwrap());
+
+        let mut out = Vec::new();
+        assert_eq!(dump_sstable(&sstable, &mut out).await.unwrap(), 2);
+        let dump: Value = serde_json::from_slice(&out).unwrap();
+        let partition = dump.as_array().unwrap().iter()
+            .find(|partition| partition["partition"]["key"] == json!([{ "Int": 1 }]))
+            .unwrap();
+        let rows = partition["rows"].as_array().unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["clustering"], json!([{ "BigInt": 1 }]));
+        assert_eq!(rows[0]["cells"][0], json!({
+            "name": "value", "value": { "Text": "a" }, "timestamp": 5_000_000, "ttl": 60, "expires_at": 65,
+        }));
+        assert_eq!(rows[1]["cells"][0], json!({ "name": "value", "deleted": true, "timestamp": 7_000_000 }));
+
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+}
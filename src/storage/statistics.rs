@@ -0,0 +1,202 @@
+use serde::{Serialize, Deserialize};
+use crate::schema::{Cell, Row};
+
+/// 통계에 데이터 만료 시각을 기록하기 시작한 SSTable 형식 버전
+pub const DELETION_TIME_STATISTICS_VERSION: u32 = 12;
+
+/// 파티션 크기 히스토그램 버킷 수 (2^0 ~ 2^63 바이트)
+const HISTOGRAM_BUCKETS: usize = 64;
+
+/// 파티션 크기 분포 (버킷 i는 [2^i, 2^(i+1)) 바이트, 0바이트는 버킷 0)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartitionSizeHistogram {
+    pub buckets: Vec<u64>,
+    /// 가장 큰 파티션 크기
+    pub max: u64,
+}
+
+impl Default for PartitionSizeHistogram {
+    fn default() -> Self {
+        Self { buckets: vec![0; HISTOGRAM_BUCKETS], max: 0 }
+    }
+}
+
+impl PartitionSizeHistogram {
+    pub fn add(&mut self, size: u64) {
+        let bucket = size.checked_ilog2().unwrap_or(0) as usize;
+        self.buckets[bucket] += 1;
+        self.max = self.max.max(size);
+    }
+
+    pub fn merge(&mut self, other: &PartitionSizeHistogram) {
+        for (bucket, count) in self.buckets.iter_mut().zip(&other.buckets) {
+            *bucket += count;
+        }
+        self.max = self.max.max(other.max);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.buckets.iter().sum()
+    }
+
+    /// 분위수 (0.0 ~ 1.0)에 해당하는 파티션 크기 상한 (버킷 경계 기준 추정, 최대값을 넘지 않음)
+    pub fn percentile(&self, quantile: f64) -> u64 {
+        let total = self.count();
+        if total == 0 {
+            return 0;
+        }
+        let target = ((total as f64 * quantile.clamp(0.0, 1.0)).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bucket, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= target {
+                let upper = 1u64.checked_shl(bucket as u32 + 1).map_or(u64::MAX, |bound| bound - 1);
+                return upper.min(self.max);
+            }
+        }
+        self.max
+    }
+}
+
+/// SSTable 통계 (-Statistics.db)
+///
+/// 컴팩션 대상 선택과 용량 계획에 쓰며, 쓰는 동안 파티션마다 누적함
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SSTableStatistics {
+    pub row_count: u64,
+    /// 삭제 표시된 셀 수 (static 컬럼 포함)
+    pub tombstone_count: u64,
+    /// 셀 수 (static 컬럼 포함)
+    pub cell_count: u64,
+    /// 압축 전 파티션 레코드 크기 분포
+    pub partition_sizes: PartitionSizeHistogram,
+    /// 압축 전 데이터 크기
+    pub uncompressed_bytes: u64,
+    /// 압축된 데이터 크기
+    pub compressed_bytes: u64,
+    /// 모든 데이터가 만료되는 시각 (초, 툼스톤은 삭제 시각, TTL 셀은 만료 시각)
+    ///
+    /// TTL 없는 셀이나 셀 없는 행이 하나라도 있으면 i64::MAX (만료되지 않음)
+    pub max_deletion_time: i64,
+}
+
+impl SSTableStatistics {
+    /// 행 하나 반영 (셀은 add_cell로 따로 반영)
+    pub fn add_row(&mut self, row: &Row) {
+        self.row_count += 1;
+        if row.cells.is_empty() {
+            self.max_deletion_time = i64::MAX;
+        }
+    }
+    
+    /// 셀 하나 반영
+    pub fn add_cell(&mut self, cell: &Cell) {
+        self.cell_count += 1;
+        if cell.is_deleted {
+            self.tombstone_count += 1;
+        }
+        self.max_deletion_time = self.max_deletion_time.max(cell.deletion_time());
+    }
+    
+    /// 모든 데이터가 만료되고 gc_grace_seconds도 지났는지 (now는 초)
+    pub fn is_fully_expired(&self, gc_grace_seconds: u32, now: i64) -> bool {
+        self.max_deletion_time.saturating_add(gc_grace_seconds as i64) < now
+    }
+
+    /// 셀 중 툼스톤 비율 (셀이 없으면 0.0)
+    pub fn tombstone_ratio(&self) -> f64 {
+        if self.cell_count == 0 {
+            return 0.0;
+        }
+        self.tombstone_count as f64 / self.cell_count as f64
+    }
+
+    /// 다른 SSTable 통계 합치기 (테이블/데이터베이스 단위 집계)
+    pub fn merge(&mut self, other: &SSTableStatistics) {
+        self.row_count += other.row_count;
+        self.tombstone_count += other.tombstone_count;
+        self.cell_count += other.cell_count;
+        self.partition_sizes.merge(&other.partition_sizes);
+        self.uncompressed_bytes += other.uncompressed_bytes;
+        self.compressed_bytes += other.compressed_bytes;
+        self.max_deletion_time = self.max_deletion_time.max(other.max_deletion_time);
+    }
+
+    /// 압축률 (압축 후 / 압축 전, 데이터가 없으면 1.0)
+    pub fn compression_ratio(&self) -> f64 {
+        if self.uncompressed_bytes == 0 {
+            return 1.0;
+        }
+        self.compressed_bytes as f64 / self.uncompressed_bytes as f64
+    }
+}
+
+/// 만료 시각이 없던 형식 (버전 12 미만)의 통계
+#[derive(Deserialize)]
+pub(crate) struct LegacySSTableStatistics {
+    row_count: u64,
+    tombstone_count: u64,
+    cell_count: u64,
+    partition_sizes: PartitionSizeHistogram,
+    uncompressed_bytes: u64,
+    compressed_bytes: u64,
+}
+
+impl From<LegacySSTableStatistics> for SSTableStatistics {
+    /// 만료 시각을 모르므로 만료되지 않는 것으로 취급
+    fn from(legacy: LegacySSTableStatistics) -> Self {
+        Self {
+            row_count: legacy.row_count,
+            tombstone_count: legacy.tombstone_count,
+            cell_count: legacy.cell_count,
+            partition_sizes: legacy.partition_sizes,
+            uncompressed_bytes: legacy.uncompressed_bytes,
+            compressed_bytes: legacy.compressed_bytes,
+            max_deletion_time: i64::MAX,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition_size_histogram_percentiles() {
+        let mut histogram = PartitionSizeHistogram::default();
+        for size in [0, 100, 120, 130, 5000] {
+            histogram.add(size);
+        }
+        assert_eq!(histogram.count(), 5);
+        assert_eq!(histogram.buckets[6], 2);
+        assert_eq!(histogram.buckets[7], 1);
+        assert_eq!(histogram.percentile(0.5), 127);
+        assert_eq!(histogram.percentile(0.8), 255);
+        assert_eq!(histogram.percentile(1.0), 5000);
+
+        let mut other = PartitionSizeHistogram::default();
+        other.add(1 << 20);
+        histogram.merge(&other);
+        assert_eq!(histogram.count(), 6);
+        assert_eq!(histogram.percentile(1.0), 1 << 20);
+        assert_eq!(PartitionSizeHistogram::default().percentile(0.99), 0);
+    }
+    
+    #[test]
+    fn test_max_deletion_time_tracks_tombstones_and_ttl() {
+        let cell = |timestamp: i64, ttl, is_deleted| Cell { value: crate::schema::CassandraValue::Int(1), timestamp, ttl, is_deleted };
+        let mut statistics = SSTableStatistics::default();
+        statistics.add_cell(&cell(100_000_000, None, true));
+        statistics.add_cell(&cell(50_000_000, Some(120), false));
+        assert_eq!(statistics.max_deletion_time, 170);
+        assert!(statistics.is_fully_expired(10, 181));
+        assert!(!statistics.is_fully_expired(10, 180));
+        
+        // TTL 없는 살아 있는 셀이 있으면 만료되지 않음
+        let mut live = statistics.clone();
+        live.add_cell(&cell(1, None, false));
+        assert!(!live.is_fully_expired(0, i64::MAX));
+        statistics.merge(&live);
+        assert_eq!(statistics.max_deletion_time, i64::MAX);
+    }
+}
@@ -0,0 +1,319 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BinaryHeap, HashSet};
+use crate::schema::{CassandraValue, Row};
+use crate::storage::IndexedKey;
+
+/// 노드당 최대 이웃 수 (레이어 0은 두 배)
+const MAX_NEIGHBORS: usize = 16;
+/// 삽입 시 후보 탐색 폭
+const EF_CONSTRUCTION: usize = 64;
+/// 검색 시 최소 후보 탐색 폭
+const EF_SEARCH: usize = 40;
+
+/// 코사인 거리 (0 = 같은 방향, 2 = 반대 방향)
+pub fn cosine_distance(a: &[f32], b: &[f32]) -> f32 {
+    let mut dot = 0.0f32;
+    let mut norm_a = 0.0f32;
+    let mut norm_b = 0.0f32;
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 1.0;
+    }
+    1.0 - dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+/// 거리 순 정렬용 후보 (거리, 노드 번호)
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate(f32, usize);
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+#[derive(Debug)]
+struct Node {
+    key: IndexedKey,
+    vector: Vec<f32>,
+    /// 레이어별 이웃 노드 번호
+    neighbors: Vec<Vec<usize>>,
+    /// 값이 바뀌거나 삭제된 노드 (그래프 연결은 유지, 결과에서만 제외)
+    deleted: bool,
+}
+
+/// VECTOR 컬럼용 근사 최근접 이웃 인덱스 (HNSW)
+#[derive(Debug)]
+pub struct VectorIndex {
+    pub name: String,
+    pub column: String,
+    pub dimension: usize,
+    nodes: Vec<Node>,
+    /// 행 키 → 현재 유효한 노드
+    live: BTreeMap<IndexedKey, usize>,
+    entry_point: Option<usize>,
+    max_level: usize,
+    /// 레벨 추첨용 xorshift 상태 (결정적)
+    rng_state: u64,
+}
+
+impl VectorIndex {
+    pub fn new(name: String, column: String, dimension: usize) -> Self {
+        Self {
+            name,
+            column,
+            dimension,
+            nodes: Vec::new(),
+            live: BTreeMap::new(),
+            entry_point: None,
+            max_level: 0,
+            rng_state: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    /// 유효한 벡터 수
+    pub fn len(&self) -> usize {
+        self.live.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.live.is_empty()
+    }
+
+    /// 쓰기 시 인덱스 갱신 (이전 벡터는 삭제 표시 후 새 노드 삽입)
+    pub fn update(&mut self, previous: Option<&Row>, current: &Row) {
+        let key = (current.partition_key.clone(), current.clustering_key.clone());
+        let vector = match current.cells.get(&self.column) {
+            Some(cell) if !cell.is_deleted => match &cell.value {
+                CassandraValue::Vector(v) if v.len() == self.dimension => Some(v.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        // 값이 그대로면 재삽입하지 않음
+        if let (Some(vector), Some(&id)) = (&vector, self.live.get(&key)) {
+            if self.nodes[id].vector == *vector {
+                return;
+            }
+        }
+
+        if previous.is_some() || self.live.contains_key(&key) {
+            self.remove_key(&key);
+        }
+        if let Some(vector) = vector {
+            self.insert(key, vector);
+        }
+    }
+
+    /// 행 삭제 시 인덱스 항목 제거
+    pub fn remove(&mut self, row: &Row) {
+        let key = (row.partition_key.clone(), row.clustering_key.clone());
+        self.remove_key(&key);
+    }
+
+    /// 질의 벡터와 가까운 순서로 최대 k개의 (행 키, 거리) 반환
+    pub fn search(&self, query: &[f32], k: usize) -> Vec<(IndexedKey, f32)> {
+        let entry = match self.entry_point {
+            Some(entry) if k > 0 && query.len() == self.dimension => entry,
+            _ => return Vec::new(),
+        };
+
+        let mut current = entry;
+        for level in (1..=self.max_level).rev() {
+            current = self.search_layer(query, &[current], 1, level)[0].1;
+        }
+
+        // 삭제 표시된 노드만큼 탐색 폭을 넓혀 k개를 채움
+        let deleted = self.nodes.len() - self.live.len();
+        let ef = (k + deleted).max(EF_SEARCH);
+        self.search_layer(query, &[current], ef, 0)
+            .into_iter()
+            .filter(|candidate| !self.nodes[candidate.1].deleted)
+            .take(k)
+            .map(|Candidate(distance, id)| (self.nodes[id].key.clone(), distance))
+            .collect()
+    }
+
+    fn remove_key(&mut self, key: &IndexedKey) {
+        if let Some(id) = self.live.remove(key) {
+            self.nodes[id].deleted = true;
+        }
+    }
+
+    fn insert(&mut self, key: IndexedKey, vector: Vec<f32>) {
+        let id = self.nodes.len();
+        let level = self.random_level();
+        self.nodes.push(Node {
+            key: key.clone(),
+            vector,
+            neighbors: vec![Vec::new(); level + 1],
+            deleted: false,
+        });
+        self.live.insert(key, id);
+
+        let entry = match self.entry_point {
+            Some(entry) => entry,
+            None => {
+                self.entry_point = Some(id);
+                self.max_level = level;
+                return;
+            },
+        };
+
+        let query = self.nodes[id].vector.clone();
+        let mut entry_points = vec![entry];
+        for layer in (level + 1..=self.max_level).rev() {
+            entry_points = vec![self.search_layer(&query, &entry_points, 1, layer)[0].1];
+        }
+
+        for layer in (0..=level.min(self.max_level)).rev() {
+            let candidates = self.search_layer(&query, &entry_points, EF_CONSTRUCTION, layer);
+            let capacity = Self::capacity(layer);
+            let neighbors: Vec<usize> = candidates.iter().take(capacity).map(|c| c.1).collect();
+
+            for &neighbor in &neighbors {
+                self.nodes[neighbor].neighbors[layer].push(id);
+                if self.nodes[neighbor].neighbors[layer].len() > capacity {
+                    self.prune(neighbor, layer, capacity);
+                }
+            }
+            self.nodes[id].neighbors[layer] = neighbors;
+            entry_points = candidates.iter().map(|c| c.1).collect();
+        }
+
+        if level > self.max_level {
+            self.entry_point = Some(id);
+            self.max_level = level;
+        }
+    }
+
+    /// 한 레이어에서 질의와 가까운 ef개의 후보를 거리 순으로 탐색
+    fn search_layer(&self, query: &[f32], entry_points: &[usize], ef: usize, layer: usize) -> Vec<Candidate> {
+        let mut visited: HashSet<usize> = entry_points.iter().copied().collect();
+        let mut candidates: BinaryHeap<Reverse<Candidate>> = BinaryHeap::new();
+        let mut nearest: BinaryHeap<Candidate> = BinaryHeap::new();
+
+        for &id in entry_points {
+            let candidate = Candidate(cosine_distance(query, &self.nodes[id].vector), id);
+            candidates.push(Reverse(candidate));
+            nearest.push(candidate);
+        }
+
+        while let Some(Reverse(closest)) = candidates.pop() {
+            if nearest.len() >= ef && nearest.peek().is_some_and(|farthest| closest.0 > farthest.0) {
+                break;
+            }
+            let Some(neighbors) = self.nodes[closest.1].neighbors.get(layer) else {
+                continue;
+            };
+            for &neighbor in neighbors {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let candidate = Candidate(cosine_distance(query, &self.nodes[neighbor].vector), neighbor);
+                if nearest.len() < ef || nearest.peek().is_some_and(|farthest| candidate.0 < farthest.0) {
+                    candidates.push(Reverse(candidate));
+                    nearest.push(candidate);
+                    if nearest.len() > ef {
+                        nearest.pop();
+                    }
+                }
+            }
+        }
+
+        nearest.into_sorted_vec()
+    }
+
+    /// 이웃 수가 한도를 넘으면 가까운 순으로 잘라냄
+    fn prune(&mut self, id: usize, layer: usize, capacity: usize) {
+        let base = &self.nodes[id].vector;
+        let mut neighbors: Vec<Candidate> = self.nodes[id].neighbors[layer].iter()
+            .map(|&n| Candidate(cosine_distance(base, &self.nodes[n].vector), n))
+            .collect();
+        neighbors.sort();
+        neighbors.truncate(capacity);
+        self.nodes[id].neighbors[layer] = neighbors.into_iter().map(|c| c.1).collect();
+    }
+
+    fn capacity(layer: usize) -> usize {
+        if layer == 0 { MAX_NEIGHBORS * 2 } else { MAX_NEIGHBORS }
+    }
+
+    /// 기하분포 레벨 추첨 (확률 1/MAX_NEIGHBORS로 한 단계씩 상승)
+    fn random_level(&mut self) -> usize {
+        let mut level = 0;
+        loop {
+            self.rng_state ^= self.rng_state << 13;
+            self.rng_state ^= self.rng_state >> 7;
+            self.rng_state ^= self.rng_state << 17;
+            if !self.rng_state.is_multiple_of(MAX_NEIGHBORS as u64) || level >= 16 {
+                return level;
+            }
+            level += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{Cell, Cells, PartitionKey};
+
+    fn row(id: i32, vector: Vec<f32>) -> Row {
+        let mut cells = Cells::new();
+        cells.insert("embedding", Cell {
+            value: CassandraValue::Vector(vector),
+            timestamp: 1,
+            ttl: None,
+            is_deleted: false,
+        });
+        Row {
+            partition_key: PartitionKey { components: vec![CassandraValue::Int(id)] },
+            clustering_key: None,
+            cells,
+            timestamp: 1,
+        }
+    }
+
+    #[test]
+    fn test_vector_index_finds_nearest_neighbors() {
+        let mut index = VectorIndex::new("items_embedding_idx".to_string(), "embedding".to_string(), 2);
+
+        // 원 위에 고르게 분포한 점들
+        for i in 0..500 {
+            let angle = i as f32 * std::f32::consts::TAU / 500.0;
+            index.update(None, &row(i, vec![angle.cos(), angle.sin()]));
+        }
+        assert_eq!(index.len(), 500);
+
+        let hits = index.search(&[1.0, 0.0], 3);
+        let ids: Vec<CassandraValue> = hits.iter().map(|(key, _)| key.0.components[0].clone()).collect();
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids[0], CassandraValue::Int(0));
+        assert!(ids.contains(&CassandraValue::Int(1)) && ids.contains(&CassandraValue::Int(499)));
+        assert!(hits[0].1 <= hits[1].1 && hits[1].1 <= hits[2].1);
+
+        // 값이 바뀐 행은 새 위치로 검색되어야 함
+        let old = row(0, vec![1.0, 0.0]);
+        let angle = 125.5 * std::f32::consts::TAU / 500.0;
+        let moved = vec![angle.cos(), angle.sin()];
+        index.update(Some(&old), &row(0, moved.clone()));
+        let hits = index.search(&[1.0, 0.0], 1);
+        assert_ne!(hits[0].0.0.components[0], CassandraValue::Int(0));
+        assert_eq!(index.search(&moved, 1)[0].0.0.components[0], CassandraValue::Int(0));
+        assert_eq!(index.len(), 500);
+    }
+}
@@ -0,0 +1,133 @@
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::{Sampler, Tracer, TracerProvider};
+use opentelemetry_sdk::Resource;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use crate::error::{CoreDBError, Result};
+
+/// OTLP 전송 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OtlpProtocol {
+    #[default]
+    Grpc,
+    /// HTTP 위의 protobuf (주소 뒤에 /v1/traces를 붙여 보냄)
+    HttpProtobuf,
+}
+
+/// 추적 스팬을 OTLP 수집기로 내보내는 설정
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtlpConfig {
+    /// 수집기 주소 (gRPC는 보통 http://host:4317, HTTP는 http://host:4318, TLS 없음)
+    pub endpoint: String,
+    pub protocol: OtlpProtocol,
+    /// service.name 리소스 속성
+    pub service_name: String,
+    /// 상위 추적이 없는 요청을 남길 비율 (0.0 ~ 1.0, 호출한 서비스가 샘플링한 추적은 그 결정을 따름)
+    pub sample_ratio: f64,
+}
+
+impl OtlpConfig {
+    /// 스팬을 모아 백그라운드에서 내보내는 트레이서 공급자 (tokio 런타임 안에서 호출, 끝낼 때 shutdown으로 남은 스팬을 보냄)
+    pub fn tracer_provider(&self) -> Result<TracerProvider> {
+        if !(0.0..=1.0).contains(&self.sample_ratio) {
+            return Err(CoreDBError::Telemetry { message: format!("Sample ratio must be between 0 and 1, got {}", self.sample_ratio) });
+        }
+        let exporter = match self.protocol {
+            OtlpProtocol::Grpc => opentelemetry_otlp::SpanExporter::builder()
+                .with_tonic()
+                .with_endpoint(self.endpoint.clone())
+                .build(),
+            OtlpProtocol::HttpProtobuf => opentelemetry_otlp::SpanExporter::builder()
+                .with_http()
+                .with_endpoint(http_traces_endpoint(&self.endpoint))
+                .build(),
+        };
+        let exporter = exporter.map_err(|e| CoreDBError::Telemetry { message: format!("Failed to create OTLP exporter for {}: {}", self.endpoint, e) })?;
+        Ok(TracerProvider::builder()
+            .with_batch_exporter(exporter, opentelemetry_sdk::runtime::Tokio)
+            .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(self.sample_ratio))))
+            .with_resource(Resource::new([
+                KeyValue::new("service.name", self.service_name.clone()),
+                KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+            ]))
+            .build())
+    }
+}
+
+/// tracing 스팬을 provider로 보내는 레이어
+pub fn tracing_layer<S>(provider: &TracerProvider) -> OpenTelemetryLayer<S, Tracer>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer("coredb"))
+}
+
+fn http_traces_endpoint(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with("/v1/traces") {
+        endpoint.to_string()
+    } else {
+        format!("{}/v1/traces", endpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use opentelemetry_sdk::export::trace::{ExportResult, SpanData, SpanExporter};
+    use tracing_subscriber::layer::SubscriberExt;
+    use crate::database::{CoreDB, DatabaseConfig};
+
+    /// 내보낸 스팬을 모아 두는 내보내기
+    #[derive(Debug, Clone, Default)]
+    struct CollectingExporter(Arc<Mutex<Vec<SpanData>>>);
+
+    impl SpanExporter for CollectingExporter {
+        fn export(&mut self, batch: Vec<SpanData>) -> std::pin::Pin<Box<dyn std::future::Future<Output = ExportResult> + Send>> {
+            self.0.lock().unwrap().extend(batch);
+            Box::pin(std::future::ready(Ok(())))
+        }
+    }
+
+    #[test]
+    fn test_otlp_config_validation() {
+        assert_eq!(http_traces_endpoint("http://collector:4318/"), "http://collector:4318/v1/traces");
+        assert_eq!(http_traces_endpoint("http://collector:4318/v1/traces"), "http://collector:4318/v1/traces");
+        let config = OtlpConfig {
+            endpoint: "http://localhost:4317".to_string(),
+            protocol: OtlpProtocol::Grpc,
+            service_name: "coredb".to_string(),
+            sample_ratio: 1.5,
+        };
+        assert!(matches!(config.tracer_provider(), Err(CoreDBError::Telemetry { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_cql_and_storage_spans_are_exported() {
+        let exporter = CollectingExporter::default();
+        let provider = TracerProvider::builder().with_simple_exporter(exporter.clone()).build();
+        let subscriber = tracing_subscriber::registry().with(tracing_layer(&provider));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let db = CoreDB::new(DatabaseConfig::ephemeral()).await.unwrap();
+        db.execute_cql("CREATE KEYSPACE app WITH REPLICATION = {'class': 'SimpleStrategy', 'replication_factor': 1}").await.unwrap();
+        db.execute_cql("CREATE TABLE app.items (id INT PRIMARY KEY, name TEXT)").await.unwrap();
+        assert!(db.execute_cql("SELECT * FROM app.missing WHERE id = 1").await.is_err());
+        db.execute_cql("SELECT * FROM system.local WHERE key = 'local'").await.unwrap();
+
+        let spans = exporter.0.lock().unwrap().clone();
+        let attribute = |span: &SpanData, key: &str| span.attributes.iter()
+            .find(|attribute| attribute.key.as_str() == key)
+            .map(|attribute| attribute.value.as_str().to_string());
+        let cql: Vec<_> = spans.iter().filter(|span| span.name == "cql").collect();
+        assert_eq!(cql.iter().map(|span| attribute(span, "db.operation")).collect::<Vec<_>>(),
+                   vec![Some("create_keyspace".to_string()), Some("create_table".to_string()), Some("select".to_string()), Some("select".to_string())]);
+        assert_eq!(attribute(cql[2], "db.namespace").as_deref(), Some("app"));
+        assert_eq!(cql[2].status, opentelemetry::trace::Status::error("Table not found: missing"));
+        // system.local의 스키마 버전은 쿼리 스팬 안에서 읽음
+        let reads: Vec<_> = spans.iter().filter(|span| span.name == "storage.read").collect();
+        assert!(reads.iter().any(|read| read.parent_span_id == cql[3].span_context.span_id()));
+    }
+}
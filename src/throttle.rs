@@ -0,0 +1,114 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// 초당 바이트 수를 제한하는 토큰 버킷 (컴팩션 입출력처럼 백그라운드 작업이 포그라운드 I/O를
+/// 굶기지 않도록 쓰임)
+///
+/// 버킷은 최대 1초 분량까지 차고, 모자라면 모자란 만큼 잠듦. 기다리는 쪽은 도착 순서대로 처리되며
+/// 한도는 실행 중에도 바꿀 수 있음 (0이면 제한 없음)
+#[derive(Debug)]
+pub struct RateLimiter {
+    bytes_per_sec: AtomicU64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// 남은 토큰 (음수면 이미 빌려 쓴 양)
+    available: f64,
+    refilled: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(bytes_per_sec: u64) -> Self {
+        Self {
+            bytes_per_sec: AtomicU64::new(bytes_per_sec),
+            bucket: Mutex::new(Bucket { available: bytes_per_sec as f64, refilled: Instant::now() }),
+        }
+    }
+
+    /// 초당 MB 단위로 생성 (0이면 제한 없음)
+    pub fn per_second_mb(mb_per_sec: u64) -> Self {
+        Self::new(mb_per_sec.saturating_mul(1024 * 1024))
+    }
+
+    pub fn bytes_per_sec(&self) -> u64 {
+        self.bytes_per_sec.load(Ordering::Relaxed)
+    }
+
+    /// 한도 변경 (이미 기다리는 쪽은 바뀐 한도로 다시 계산하지 않음)
+    pub fn set_bytes_per_sec(&self, bytes_per_sec: u64) {
+        self.bytes_per_sec.store(bytes_per_sec, Ordering::Relaxed);
+    }
+
+    /// bytes만큼의 토큰을 얻을 때까지 기다림
+    pub async fn acquire(&self, bytes: u64) {
+        let rate = self.bytes_per_sec();
+        if rate == 0 || bytes == 0 {
+            return;
+        }
+        let rate = rate as f64;
+
+        // 잠든 동안 잠금을 쥐고 있어 뒤에 온 쪽은 앞의 대기가 끝난 뒤에 계산됨
+        let mut bucket = self.bucket.lock().await;
+        bucket.refill(rate);
+        bucket.available -= bytes as f64;
+        if bucket.available < 0.0 {
+            tokio::time::sleep(Duration::from_secs_f64(-bucket.available / rate)).await;
+        }
+    }
+
+    /// bytes만큼의 토큰이 지금 있으면 쓰고 true, 모자라면 쓰지 않고 false (기다리지 않음)
+    pub async fn try_acquire(&self, bytes: u64) -> bool {
+        let rate = self.bytes_per_sec();
+        if rate == 0 || bytes == 0 {
+            return true;
+        }
+        let mut bucket = self.bucket.lock().await;
+        bucket.refill(rate as f64);
+        if bucket.available < bytes as f64 {
+            return false;
+        }
+        bucket.available -= bytes as f64;
+        true
+    }
+}
+
+impl Bucket {
+    fn refill(&mut self, rate: f64) {
+        let now = Instant::now();
+        self.available = (self.available + now.duration_since(self.refilled).as_secs_f64() * rate).min(rate);
+        self.refilled = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rate_limiter_waits_for_tokens_and_adjusts_at_runtime() {
+        let limiter = RateLimiter::new(10_000);
+
+        // 처음 1초 분량은 바로 쓸 수 있고, 넘는 만큼은 한도에 맞춰 기다림
+        let started = Instant::now();
+        limiter.acquire(10_000).await;
+        assert!(started.elapsed() < Duration::from_millis(100));
+        limiter.acquire(2_000).await;
+        assert!(started.elapsed() >= Duration::from_millis(150));
+
+        // 제한을 풀면 바로 통과
+        limiter.set_bytes_per_sec(0);
+        let started = Instant::now();
+        limiter.acquire(1 << 30).await;
+        assert!(started.elapsed() < Duration::from_millis(100));
+        assert_eq!(RateLimiter::per_second_mb(16).bytes_per_sec(), 16 * 1024 * 1024);
+
+        // try_acquire는 모자라면 기다리지 않고 거절
+        let limiter = RateLimiter::new(2);
+        assert!(limiter.try_acquire(1).await);
+        assert!(limiter.try_acquire(1).await);
+        assert!(!limiter.try_acquire(1).await);
+    }
+}
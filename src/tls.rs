@@ -0,0 +1,174 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use crate::error::{CoreDBError, Result};
+
+/// 클라이언트 연결 TLS 설정 (HTTP 서버와 이후 네이티브 프로토콜이 함께 씀)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsConfig {
+    /// 서버 인증서 체인 (PEM)
+    pub cert_file: PathBuf,
+    /// 서버 개인 키 (PEM, PKCS#8 / PKCS#1 / SEC1)
+    pub key_file: PathBuf,
+    /// 클라이언트 인증서를 검증할 CA 인증서 (PEM, 없으면 클라이언트 인증서를 받지 않음)
+    pub ca_file: Option<PathBuf>,
+    /// 클라이언트 인증서가 없는 연결을 거부 (ca_file 필요, 아니면 제시한 인증서만 검증)
+    pub require_client_auth: bool,
+}
+
+impl TlsConfig {
+    /// rustls 서버 설정 (파일을 읽을 수 없거나 키와 인증서가 맞지 않으면 오류)
+    pub fn server_config(&self) -> Result<Arc<rustls::ServerConfig>> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let certs = load_certificates(&self.cert_file)?;
+        let key = load_private_key(&self.key_file)?;
+
+        let builder = rustls::ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(tls_error)?;
+        let builder = match &self.ca_file {
+            Some(ca_file) => {
+                let mut roots = rustls::RootCertStore::empty();
+                for cert in load_certificates(ca_file)? {
+                    roots.add(cert).map_err(|e| CoreDBError::Tls { message: format!("Invalid CA certificate in {}: {}", ca_file.display(), e) })?;
+                }
+                let verifier = rustls::server::WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+                let verifier = if self.require_client_auth { verifier } else { verifier.allow_unauthenticated() };
+                builder.with_client_cert_verifier(verifier.build().map_err(tls_error)?)
+            },
+            None if self.require_client_auth => {
+                return Err(CoreDBError::Tls { message: "Client certificate authentication requires a CA file".to_string() });
+            },
+            None => builder.with_no_client_auth(),
+        };
+
+        let mut config = builder.with_single_cert(certs, key).map_err(tls_error)?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(Arc::new(config))
+    }
+
+    pub fn acceptor(&self) -> Result<tokio_rustls::TlsAcceptor> {
+        Ok(tokio_rustls::TlsAcceptor::from(self.server_config()?))
+    }
+}
+
+/// 검증된 클라이언트 인증서의 CN (역할 이름으로 씀, 인증서가 없거나 CN이 없으면 None)
+pub fn client_certificate_identity(certs: &[CertificateDer<'_>]) -> Option<String> {
+    let (_, cert) = x509_parser::parse_x509_certificate(certs.first()?.as_ref()).ok()?;
+    let common_name = cert.subject().iter_common_name().next()?;
+    common_name.as_str().ok().map(str::to_string)
+}
+
+fn load_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let mut reader = std::io::BufReader::new(std::fs::File::open(path)?);
+    let certs = rustls_pemfile::certs(&mut reader).collect::<std::io::Result<Vec<_>>>()?;
+    if certs.is_empty() {
+        return Err(CoreDBError::Tls { message: format!("No certificates found in {}", path.display()) });
+    }
+    Ok(certs)
+}
+
+fn load_private_key(path: &Path) -> Result<PrivateKeyDer<'static>> {
+    let mut reader = std::io::BufReader::new(std::fs::File::open(path)?);
+    rustls_pemfile::private_key(&mut reader)?
+        .ok_or_else(|| CoreDBError::Tls { message: format!("No private key found in {}", path.display()) })
+}
+
+fn tls_error(e: impl std::fmt::Display) -> CoreDBError {
+    CoreDBError::Tls { message: e.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rcgen::{BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa, KeyPair};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn test_tls_handshake_with_client_certificate() {
+        let test_dir = std::env::temp_dir().join("coredb_test_tls");
+        let _ = std::fs::remove_dir_all(&test_dir);
+        std::fs::create_dir_all(&test_dir).unwrap();
+        let write = |name: &str, pem: String| {
+            let path = test_dir.join(name);
+            std::fs::write(&path, pem).unwrap();
+            path
+        };
+
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(Vec::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params.distinguished_name.push(DnType::CommonName, "coredb test ca");
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+        let server_key = KeyPair::generate().unwrap();
+        let server = CertificateParams::new(vec!["localhost".to_string()]).unwrap().signed_by(&server_key, &ca, &ca_key).unwrap();
+        let client_key = KeyPair::generate().unwrap();
+        let mut client_params = CertificateParams::new(Vec::new()).unwrap();
+        client_params.distinguished_name.push(DnType::CommonName, "reporting");
+        client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+        let client = client_params.signed_by(&client_key, &ca, &ca_key).unwrap();
+
+        let config = TlsConfig {
+            cert_file: write("server.pem", server.pem()),
+            key_file: write("server.key", server_key.serialize_pem()),
+            ca_file: Some(write("ca.pem", ca.pem())),
+            require_client_auth: true,
+        };
+        let acceptor = config.acceptor().unwrap();
+
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(ca.der().clone()).unwrap();
+        let client_config = |with_certificate: bool| {
+            let builder = rustls::ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions().unwrap()
+                .with_root_certificates(roots.clone());
+            let config = if with_certificate {
+                let key = PrivateKeyDer::try_from(client_key.serialize_der()).unwrap();
+                builder.with_client_auth_cert(vec![client.der().clone()], key).unwrap()
+            } else {
+                builder.with_no_client_auth()
+            };
+            tokio_rustls::TlsConnector::from(Arc::new(config))
+        };
+
+        // 클라이언트 인증서의 CN을 서버에서 읽음
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server_acceptor = acceptor.clone();
+        let server_task = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut stream = server_acceptor.accept(stream).await.unwrap();
+            let identity = client_certificate_identity(stream.get_ref().1.peer_certificates().unwrap_or_default());
+            stream.write_all(b"ok").await.unwrap();
+            stream.shutdown().await.ok();
+            identity
+        });
+        let tcp = tokio::net::TcpStream::connect(address).await.unwrap();
+        let server_name = rustls::pki_types::ServerName::try_from("localhost").unwrap();
+        let mut stream = client_config(true).connect(server_name.clone(), tcp).await.unwrap();
+        let mut reply = Vec::new();
+        stream.read_to_end(&mut reply).await.ok();
+        assert_eq!(reply, b"ok");
+        assert_eq!(server_task.await.unwrap().as_deref(), Some("reporting"));
+
+        // 인증서 없는 클라이언트는 거부
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server_task = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            acceptor.accept(stream).await.is_ok()
+        });
+        let tcp = tokio::net::TcpStream::connect(address).await.unwrap();
+        if let Ok(mut stream) = client_config(false).connect(server_name, tcp).await {
+            // TLS 1.3에서는 클라이언트가 거부를 첫 읽기에서 알게 됨
+            let _ = stream.read(&mut [0; 1]).await;
+        }
+        assert!(!server_task.await.unwrap());
+
+        // CA 없이 클라이언트 인증서를 요구하면 설정 오류
+        let config = TlsConfig { ca_file: None, ..config };
+        assert!(matches!(config.server_config(), Err(CoreDBError::Tls { .. })));
+
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+}
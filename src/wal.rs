@@ -1,10 +1,37 @@
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::fs::{File, OpenOptions};
-use tokio::io::{AsyncWriteExt, AsyncReadExt, BufWriter, SeekFrom};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufWriter, SeekFrom};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::{watch, Mutex};
 use serde::{Serialize, Deserialize};
 use crate::schema::{PartitionKey, ClusteringKey, Row};
+use crate::storage::{strip_checksum, with_checksum, CHECKSUM_LEN};
+use crate::latency::{LatencyHistogram, LatencySnapshot};
+use crate::fault_injection::{self, FaultPoint};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
 use crate::error::*;
 
+/// 세그먼트 헤더의 매직 (헤더가 없는 세그먼트는 엔트리에 체크섬이 없는 이전 형식)
+const SEGMENT_MAGIC: &[u8; 4] = b"CDBL";
+/// 세그먼트 형식 버전 (2부터 엔트리마다 CRC32C, 3부터 헤더와 엔트리에 세그먼트 번호, 4부터 헤더에 암호화 키 ID)
+pub const SEGMENT_VERSION: u32 = 4;
+/// 매직 + 버전 + 세그먼트 번호 + 키 ID 길이 + 키 ID (빈 자리는 0)
+const SEGMENT_HEADER_LEN: u64 = 64;
+/// 버전 3 세그먼트 헤더 (매직 + 버전 + 세그먼트 번호)
+const V3_SEGMENT_HEADER_LEN: u64 = 16;
+/// 세그먼트 헤더에 넣을 수 있는 키 ID 길이 (바이트)
+pub const MAX_KEY_ID_LEN: usize = SEGMENT_HEADER_LEN as usize - 20;
+/// 암호화된 엔트리 앞에 붙는 AES-GCM nonce 길이
+const NONCE_LEN: usize = 12;
+/// 기본 세그먼트 크기 (파일을 이 크기로 미리 할당)
+pub const DEFAULT_SEGMENT_SIZE: u64 = 32 * 1024 * 1024;
+/// 지운 대신 재사용하려고 남겨 두는 세그먼트 파일 수
+const MAX_RECYCLED_SEGMENTS: usize = 4;
+
 /// 커밋 로그 엔트리
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CommitLogEntry {
@@ -15,7 +42,7 @@ pub struct CommitLogEntry {
 }
 
 /// 뮤테이션 타입
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Mutation {
     Insert(Row),
     Delete { 
@@ -25,164 +52,1039 @@ pub enum Mutation {
     PartitionDelete { 
         partition_key: PartitionKey 
     },
+    /// 스키마 변경 (CQL DDL 원문, 키스페이스 단위 변경이면 테이블은 빈 문자열)
+    Schema {
+        statement: String,
+    },
+}
+
+/// 커밋 로그 안의 위치 (세그먼트 번호와 세그먼트 안의 바이트 오프셋, 순서대로 비교됨)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct CommitLogPosition {
+    pub segment_id: u64,
+    pub offset: u64,
+}
+
+/// 커밋 로그 fsync 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommitLogSync {
+    /// 엔트리마다 fsync하고 응답 (가장 안전하지만 가장 느림)
+    Always,
+    /// 쓰기는 바로 응답하고 interval마다 fsync (크래시 때 마지막 interval 동안의 쓰기를 잃을 수 있음)
+    Periodic { interval: Duration },
+    /// 쓰기는 fsync될 때까지 기다리고, max_delay 동안 들어온 쓰기를 한 번에 fsync
+    Batch { max_delay: Duration },
+    /// 쓰기는 자기 그룹의 fsync가 끝나면 응답하고, 그룹은 첫 쓰기부터 max_latency가 지나면 fsync
+    /// (쓰기가 없으면 fsync하지 않으며, 지연 목표를 늘릴수록 한 번에 묶이는 쓰기가 많아짐)
+    Group { max_latency: Duration },
+}
+
+impl Default for CommitLogSync {
+    fn default() -> Self {
+        Self::Periodic { interval: Duration::from_secs(10) }
+    }
+}
+
+impl CommitLogSync {
+    /// 백그라운드 fsync 주기 (Always와 Group은 쓰는 쪽이 fsync하므로 None)
+    pub fn sync_interval(&self) -> Option<Duration> {
+        match self {
+            Self::Always | Self::Group { .. } => None,
+            Self::Periodic { interval } => Some(*interval),
+            Self::Batch { max_delay } => Some(*max_delay),
+        }
+    }
+}
+
+/// 커밋 로그 통계
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitLogStats {
+    pub sync_mode: CommitLogSync,
+    /// 엔트리를 fsync한 횟수
+    pub syncs: u64,
+    /// append부터 응답까지 걸린 시간 분포 (Periodic은 fsync를 기다리지 않은 시간)
+    pub commit_latency: LatencySnapshot,
+    /// 정리되지 않고 남은 세그먼트 크기 (추정)
+    pub total_size_bytes: u64,
+}
+
+/// 닫힌 세그먼트 보관 방식 (백업과 시점 복구용)
+///
+/// 세그먼트가 다 차서 닫히면 백그라운드에서 호출되고, 보관에 실패한 세그먼트는 정리할 때 다시 보관을
+/// 시도해 성공하기 전에는 지우거나 재사용하지 않음. 오브젝트 스토어 등은 직접 구현하거나 CommandArchiver로 연결
+#[async_trait::async_trait]
+pub trait CommitLogArchiver: Send + Sync + std::fmt::Debug {
+    async fn archive(&self, segment: &Path) -> Result<()>;
+}
+
+/// 세그먼트를 디렉토리로 복사 (같은 파일 이름으로, 복구할 때 CommitLogRestore의 디렉토리로 사용)
+#[derive(Debug, Clone)]
+pub struct CopyArchiver {
+    pub directory: PathBuf,
+}
+
+#[async_trait::async_trait]
+impl CommitLogArchiver for CopyArchiver {
+    async fn archive(&self, segment: &Path) -> Result<()> {
+        let name = segment.file_name().ok_or_else(|| CoreDBError::CommitLogError {
+            message: format!("{} is not a segment file", segment.display()),
+        })?;
+        tokio::fs::create_dir_all(&self.directory).await?;
+        // 복사 중에 멈춰도 온전하지 않은 파일이 보관본으로 보이지 않도록 임시 이름으로 복사
+        let target = self.directory.join(name);
+        let partial = target.with_extension("partial");
+        tokio::fs::copy(segment, &partial).await?;
+        tokio::fs::rename(&partial, &target).await?;
+        Ok(())
+    }
+}
+
+/// 셸 명령으로 보관 (%path는 세그먼트 경로, %name은 파일 이름으로 바뀜, 예: `aws s3 cp %path s3://bucket/%name`)
+#[derive(Debug, Clone)]
+pub struct CommandArchiver {
+    pub command: String,
+}
+
+#[async_trait::async_trait]
+impl CommitLogArchiver for CommandArchiver {
+    async fn archive(&self, segment: &Path) -> Result<()> {
+        let name = segment.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+        let command = self.command
+            .replace("%path", &segment.display().to_string())
+            .replace("%name", &name);
+        let status = tokio::process::Command::new("sh").arg("-c").arg(&command).status().await?;
+        if !status.success() {
+            return Err(CoreDBError::CommitLogError { message: format!("Archive command `{}` failed with {}", command, status) });
+        }
+        Ok(())
+    }
+}
+
+/// 보관한 세그먼트로 복구 (data_directory에 되돌려 둔 스냅샷 위에 재생)
+///
+/// 테이블을 열 때 매니페스트의 재생 위치 뒤의 보관된 엔트리를 먼저 재생하고, 로컬 커밋 로그도 같은
+/// 시각까지만 재생함. 스냅샷이 복구 시각보다 뒤에 찍혔다면 그 사이의 쓰기는 되돌리지 않음
+#[derive(Debug, Clone)]
+pub struct CommitLogRestore {
+    /// 보관된 세그먼트가 있는 디렉토리
+    pub directory: PathBuf,
+    /// 이 시각(마이크로초)보다 뒤에 쓴 엔트리는 재생하지 않음 (None이면 끝까지)
+    pub point_in_time: Option<i64>,
+}
+
+/// 커밋 로그 암호화 (AES-256-GCM)
+///
+/// 새 세그먼트는 현재 키로 엔트리마다 암호화하고 헤더에 키 ID를 남김 (nonce는 엔트리마다 무작위).
+/// 이전 키는 그 키로 쓴 세그먼트를 읽을 때만 쓰므로, 키를 바꾼 뒤에도 그 세그먼트가 정리되거나
+/// 보관본에서 복구할 일이 없어질 때까지는 with_previous로 함께 지정해야 함
+#[derive(Clone)]
+pub struct CommitLogEncryption {
+    key_id: String,
+    keys: HashMap<String, Arc<Aes256Gcm>>,
+}
+
+impl std::fmt::Debug for CommitLogEncryption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // 키 자체는 출력하지 않음
+        let mut key_ids: Vec<_> = self.keys.keys().collect();
+        key_ids.sort();
+        f.debug_struct("CommitLogEncryption").field("key_id", &self.key_id).field("key_ids", &key_ids).finish()
+    }
+}
+
+impl CommitLogEncryption {
+    /// 32바이트 키로 생성 (KMS에서 받은 데이터 키 등)
+    pub fn new(key_id: impl Into<String>, key: &[u8]) -> Result<Self> {
+        let key_id = key_id.into();
+        if key_id.is_empty() || key_id.len() > MAX_KEY_ID_LEN {
+            return Err(CoreDBError::CommitLogError {
+                message: format!("Commit log key id must be 1 to {} bytes, got {:?}", MAX_KEY_ID_LEN, key_id),
+            });
+        }
+        let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| CoreDBError::CommitLogError {
+            message: format!("Commit log key {} must be 32 bytes, got {}", key_id, key.len()),
+        })?;
+        Ok(Self { keys: HashMap::from([(key_id.clone(), Arc::new(cipher))]), key_id })
+    }
+    
+    /// 키 파일에서 읽기 (32바이트 원본 또는 16진수 64자, 키 ID는 파일 이름에서 확장자를 뺀 부분)
+    pub async fn from_key_file(path: &Path) -> Result<Self> {
+        let data = tokio::fs::read(path).await?;
+        let key_id = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        let key = if data.len() == 32 { data } else { decode_hex_key(&data, &path.display().to_string())? };
+        Self::new(key_id, &key)
+    }
+    
+    /// 셸 명령이 출력한 16진수 키 사용 (KMS로 데이터 키를 복호화하는 명령 등)
+    pub async fn from_command(key_id: impl Into<String>, command: &str) -> Result<Self> {
+        let output = tokio::process::Command::new("sh").arg("-c").arg(command).output().await?;
+        if !output.status.success() {
+            return Err(CoreDBError::CommitLogError { message: format!("Key command `{}` failed with {}", command, output.status) });
+        }
+        Self::new(key_id, &decode_hex_key(&output.stdout, &format!("output of `{}`", command))?)
+    }
+    
+    /// 이전 키 추가 (그 키로 쓴 세그먼트를 읽을 때만 사용)
+    pub fn with_previous(mut self, previous: CommitLogEncryption) -> Self {
+        for (key_id, cipher) in previous.keys {
+            self.keys.entry(key_id).or_insert(cipher);
+        }
+        self
+    }
+    
+    /// 새 세그먼트를 암호화하는 키 ID
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+    
+    /// 현재 키로 암호화 (nonce + 암호문과 인증 태그, 세그먼트 번호도 함께 인증)
+    fn encrypt(&self, segment_id: u64, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self.keys[&self.key_id]
+            .encrypt(&nonce, Payload { msg: plaintext, aad: &segment_id.to_be_bytes() })
+            .map_err(|_| CoreDBError::CommitLogError { message: "Failed to encrypt commit log entry".to_string() })?;
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+    
+    /// key_id 키로 복호화 (인증에 실패하면 None)
+    fn decrypt(&self, key_id: &str, segment_id: u64, sealed: &[u8]) -> Option<Vec<u8>> {
+        if sealed.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        self.keys.get(key_id)?
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: &segment_id.to_be_bytes() })
+            .ok()
+    }
+}
+
+/// 16진수 64자 키 (앞뒤 공백 무시)
+fn decode_hex_key(data: &[u8], source: &str) -> Result<Vec<u8>> {
+    let text = std::str::from_utf8(data).unwrap_or_default().trim();
+    let invalid = || CoreDBError::CommitLogError { message: format!("{} is not a 32-byte key or 64 hex characters", source) };
+    if text.len() != 64 {
+        return Err(invalid());
+    }
+    (0..text.len()).step_by(2)
+        .map(|i| text.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()).ok_or_else(invalid))
+        .collect()
 }
 
 /// 커밋 로그
+///
+/// 동시에 들어온 쓰기는 스테이징 버퍼에 모였다가 한 번의 write와 fsync로 기록됨 (그룹 커밋).
+/// 먼저 쓰기 잠금을 잡은 쪽이 그때까지 모인 엔트리를 모두 쓰고, 기다리던 쪽은 자기 엔트리가
+/// 이미 기록됐으면 바로 돌아감
+///
+/// 세그먼트 파일은 처음부터 전체 크기로 할당하고, 다 쓴 세그먼트는 지우지 않고 이름을 바꿔
+/// 다음 세그먼트로 재사용함 (엔트리마다 세그먼트 번호가 있어 재사용 전의 엔트리는 재생되지 않음)
 pub struct CommitLog {
-    current_segment: BufWriter<File>,
+    /// 엔트리를 쓰는 쪽 (한 번에 하나만)
+    writer: Mutex<SegmentWriter>,
+    /// 아직 파일에 쓰지 않은 엔트리
+    staged: std::sync::Mutex<Staged>,
+    /// 재사용할 세그먼트 파일
+    recycled: Mutex<Vec<PathBuf>>,
     segment_size_limit: u64,
-    current_segment_size: u64,
     base_directory: PathBuf,
-    segment_id: u64,
+    sync: CommitLogSync,
+    /// 디스크에 fsync된 위치
+    synced: watch::Sender<CommitLogPosition>,
+    /// 엔트리를 fsync한 횟수
+    sync_count: AtomicU64,
+    /// Group 방식에서 그룹의 fsync를 맡은 쓰기 (나머지는 잠금을 기다리는 동안 함께 fsync됨)
+    group_leader: Mutex<()>,
+    /// append부터 응답까지 걸린 시간 (방식별로 fsync를 기다린 시간 포함)
+    commit_latency: LatencyHistogram,
+    archiver: Option<Arc<dyn CommitLogArchiver>>,
+    /// 엔트리 암호화 키 (None이면 평문)
+    encryption: Option<CommitLogEncryption>,
+    /// 이번 실행에서 보관을 마친 세그먼트
+    archived: Arc<std::sync::Mutex<HashSet<u64>>>,
+    /// 정리되지 않고 남은 가장 오래된 세그먼트
+    oldest_segment: AtomicU64,
+}
+
+/// 열린 세그먼트와 그 안에 쓴 위치
+struct SegmentWriter {
+    segment: BufWriter<File>,
+    written: CommitLogPosition,
+}
+
+/// 스테이징 버퍼 (엔트리마다 위치를 미리 정하므로 세그먼트가 바뀌는 지점도 여기서 정해짐)
+struct Staged {
+    /// 다음 엔트리가 쓰일 위치
+    next: CommitLogPosition,
+    /// 세그먼트별로 이어 붙인 레코드 (엔트리 크기 + 세그먼트 번호와 데이터, 체크섬)
+    chunks: Vec<(u64, Vec<u8>)>,
 }
 
 impl CommitLog {
+    /// 커밋 로그 열기 (이전 실행의 마지막 세그먼트가 있으면 그 뒤에 이어 씀)
+    ///
+    /// 마지막 세그먼트 끝에 잘리거나 손상된 엔트리가 있으면 0으로 덮고 이어 씀 (뒤에 쓴 엔트리가 재생되도록).
+    /// 마지막 세그먼트가 이전 형식이면 새 세그먼트에 씀
     pub async fn new(base_dir: PathBuf) -> Result<Self> {
+        Self::open(base_dir, DEFAULT_SEGMENT_SIZE).await
+    }
+    
+    /// 세그먼트 크기를 지정해 커밋 로그 열기
+    pub async fn open(base_dir: PathBuf, segment_size: u64) -> Result<Self> {
+        Self::open_with_encryption(base_dir, segment_size, None).await
+    }
+    
+    /// 암호화 키를 지정해 커밋 로그 열기 (마지막 세그먼트가 다른 키나 평문으로 쓰였으면 새 세그먼트에 씀)
+    pub async fn open_with_encryption(base_dir: PathBuf, segment_size: u64, encryption: Option<CommitLogEncryption>) -> Result<Self> {
         tokio::fs::create_dir_all(&base_dir).await?;
         
-        let segment_path = base_dir.join(format!("commitlog-{}.log", 0));
-        let file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(segment_path)
-            .await?;
+        let key_id = encryption.as_ref().map(|encryption| encryption.key_id().to_string());
+        let existing = segment_ids(&base_dir).await?;
+        let oldest_segment = existing.first().copied().unwrap_or(0);
+        let mut segment_id = existing.last().copied().unwrap_or(0);
+        let segment_path = |segment_id: u64| base_dir.join(format!("commitlog-{}.log", segment_id));
+        let last = read_segment(&segment_path(segment_id), segment_id, encryption.as_ref()).await?;
+        let mut length = last.valid_length;
+        if (last.version != SEGMENT_VERSION || last.key_id != key_id) && length > 0 {
+            segment_id += 1;
+            length = 0;
+        }
+        let (segment, offset) = open_segment(&segment_path(segment_id), segment_id, length, segment_size, last.torn, key_id.as_deref()).await?;
+        let written = CommitLogPosition { segment_id, offset };
         
         Ok(Self {
-            current_segment: BufWriter::new(file),
-            segment_size_limit: 32 * 1024 * 1024, // 32MB
-            current_segment_size: 0,
+            writer: Mutex::new(SegmentWriter { segment, written }),
+            staged: std::sync::Mutex::new(Staged { next: written, chunks: Vec::new() }),
+            recycled: Mutex::new(recycled_segments(&base_dir).await?),
+            segment_size_limit: segment_size,
             base_directory: base_dir,
-            segment_id: 0,
+            sync: CommitLogSync::default(),
+            synced: watch::Sender::new(written),
+            sync_count: AtomicU64::new(0),
+            group_leader: Mutex::new(()),
+            commit_latency: LatencyHistogram::default(),
+            archiver: None,
+            encryption,
+            archived: Arc::default(),
+            oldest_segment: AtomicU64::new(oldest_segment),
         })
     }
     
-    pub async fn append(&mut self, entry: CommitLogEntry) -> Result<()> {
-        let serialized = bincode::serialize(&entry)?;
-        let entry_size = serialized.len() as u64;
-        
-        // 세그먼트 크기 초과 시 새 세그먼트 생성
-        if self.current_segment_size + entry_size + 4 > self.segment_size_limit {
-            self.rotate_segment().await?;
+    /// 닫힌 세그먼트 보관 방식 지정
+    pub fn with_archiver(mut self, archiver: Arc<dyn CommitLogArchiver>) -> Self {
+        self.archiver = Some(archiver);
+        self
+    }
+    
+    /// fsync 방식 지정
+    pub fn with_sync(mut self, sync: CommitLogSync) -> Self {
+        self.sync = sync;
+        self
+    }
+    
+    pub fn sync_mode(&self) -> CommitLogSync {
+        self.sync
+    }
+    
+    pub fn encryption(&self) -> Option<&CommitLogEncryption> {
+        self.encryption.as_ref()
+    }
+    
+    /// 다음 엔트리가 쓰일 위치 (그 앞의 엔트리는 모두 append가 끝났거나 기록되는 중)
+    pub fn position(&self) -> CommitLogPosition {
+        self.staged().next
+    }
+    
+    /// fsync된 위치 구독 (Batch 방식에서 쓰기가 자기 엔트리의 fsync를 기다릴 때)
+    pub fn subscribe_synced(&self) -> watch::Receiver<CommitLogPosition> {
+        self.synced.subscribe()
+    }
+    
+    /// 엔트리를 fsync한 횟수 (동시 쓰기가 그룹 커밋으로 묶인 정도를 볼 때)
+    pub fn sync_count(&self) -> u64 {
+        self.sync_count.load(Ordering::Relaxed)
+    }
+    
+    /// 커밋 지연 시간과 fsync 횟수
+    pub fn stats(&self) -> CommitLogStats {
+        CommitLogStats {
+            sync_mode: self.sync,
+            syncs: self.sync_count(),
+            commit_latency: self.commit_latency.snapshot(),
+            total_size_bytes: self.total_size(),
         }
-        
-        // 엔트리 크기 + 데이터 쓰기
-        self.current_segment.write_u32(serialized.len() as u32).await?;
-        self.current_segment.write_all(&serialized).await?;
-        self.current_segment.flush().await?;
-        
-        self.current_segment_size += entry_size + 4; // +4 for length prefix
-        
+    }
+    
+    /// 엔트리를 쓰고 그 끝 위치 반환 (Always와 Group 방식이면 fsync까지 마친 뒤 반환)
+    ///
+    /// Batch 방식은 쓰기만 하고 반환하므로 응답 전에 wait_for_sync를 불러야 함
+    pub async fn append(&self, entry: CommitLogEntry) -> Result<CommitLogPosition> {
+        let started = Instant::now();
+        let end = self.stage(&bincode::serialize(&entry)?)?;
+        self.write_staged(end, self.sync == CommitLogSync::Always).await?;
+        match self.sync {
+            CommitLogSync::Batch { .. } => return Ok(end),
+            CommitLogSync::Group { max_latency } => self.group_sync(end, started, max_latency).await?,
+            CommitLogSync::Always | CommitLogSync::Periodic { .. } => {},
+        }
+        self.commit_latency.record(started.elapsed());
+        Ok(end)
+    }
+    
+    /// Batch 방식에서 end까지 fsync될 때까지 기다림 (started는 append를 시작한 시각, 다른 방식은 바로 반환)
+    pub async fn wait_for_sync(&self, end: CommitLogPosition, started: Instant) -> Result<()> {
+        if !matches!(self.sync, CommitLogSync::Batch { .. }) {
+            return Ok(());
+        }
+        self.subscribe_synced().wait_for(|synced| *synced >= end).await
+            .map_err(|_| CoreDBError::CommitLogError { message: "Commit log closed before the write was synced".to_string() })?;
+        self.commit_latency.record(started.elapsed());
         Ok(())
     }
     
-    async fn rotate_segment(&mut self) -> Result<()> {
-        self.current_segment.flush().await?;
-        
-        self.segment_id += 1;
-        let new_segment_path = self.base_directory
-            .join(format!("commitlog-{}.log", self.segment_id));
-        
-        let file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(new_segment_path)
-            .await?;
+    /// Group 방식의 fsync 대기 (잠금을 먼저 잡은 쓰기가 그룹의 첫 쓰기부터 max_latency까지 모은 뒤 fsync)
+    async fn group_sync(&self, end: CommitLogPosition, started: Instant, max_latency: Duration) -> Result<()> {
+        let _leader = self.group_leader.lock().await;
+        if *self.synced.borrow() >= end {
+            // 앞 그룹의 fsync에 포함됨
+            return Ok(());
+        }
+        tokio::time::sleep(max_latency.saturating_sub(started.elapsed())).await;
+        self.write_staged(self.position(), true).await
+    }
+    
+    /// 지금까지 쓴 엔트리를 디스크에 fsync
+    pub async fn sync(&self) -> Result<()> {
+        self.write_staged(self.position(), true).await
+    }
+    
+    fn staged(&self) -> std::sync::MutexGuard<'_, Staged> {
+        self.staged.lock().unwrap_or_else(|e| e.into_inner())
+    }
+    
+    /// 직렬화한 엔트리를 스테이징 버퍼에 넣고 끝 위치 반환 (세그먼트 크기를 넘으면 다음 세그먼트로)
+    fn stage(&self, serialized: &[u8]) -> Result<CommitLogPosition> {
+        // 엔트리 크기 + 세그먼트 번호 + 데이터(암호화하면 nonce와 인증 태그 포함) + 체크섬
+        let sealed_len = match self.encryption {
+            Some(_) => NONCE_LEN + serialized.len() + 16,
+            None => serialized.len(),
+        };
+        let record_size = 4 + 8 + sealed_len as u64 + CHECKSUM_LEN as u64;
+        let mut staged = self.staged();
+        if staged.next.offset > SEGMENT_HEADER_LEN && staged.next.offset + record_size > self.segment_size_limit {
+            staged.next = CommitLogPosition { segment_id: staged.next.segment_id + 1, offset: SEGMENT_HEADER_LEN };
+        }
+        let segment_id = staged.next.segment_id;
+        let mut record = Vec::with_capacity(record_size as usize - 4);
+        record.extend_from_slice(&segment_id.to_be_bytes());
+        match &self.encryption {
+            Some(encryption) => record.extend_from_slice(&encryption.encrypt(segment_id, serialized)?),
+            None => record.extend_from_slice(serialized),
+        }
+        let record = with_checksum(record);
+        if staged.chunks.last().is_none_or(|(id, _)| *id != segment_id) {
+            staged.chunks.push((segment_id, Vec::new()));
+        }
+        let (_, chunk) = staged.chunks.last_mut().expect("chunk pushed above");
+        chunk.extend_from_slice(&(record.len() as u32).to_be_bytes());
+        chunk.extend_from_slice(&record);
+        staged.next.offset += record_size;
+        Ok(staged.next)
+    }
+    
+    /// end까지의 엔트리가 파일에 쓰이도록 (fsync면 디스크까지) 그때까지 모인 엔트리를 한꺼번에 기록
+    async fn write_staged(&self, end: CommitLogPosition, fsync: bool) -> Result<()> {
+        let mut writer = self.writer.lock().await;
+        let done = if fsync { *self.synced.borrow() } else { writer.written };
+        if done >= end {
+            // 앞서 잠금을 잡은 쪽이 함께 기록함
+            return Ok(());
+        }
         
-        self.current_segment = BufWriter::new(file);
-        self.current_segment_size = 0;
+        let chunks = std::mem::take(&mut self.staged().chunks);
+        for (segment_id, records) in chunks {
+            if segment_id != writer.written.segment_id {
+                // 앞 세그먼트를 fsync해야 다음 세그먼트까지의 fsync된 위치가 의미 있음
+                writer.segment.flush().await?;
+                fault_injection::sync(FaultPoint::CommitLogSync, &self.segment_path(writer.written.segment_id), writer.written.offset)?;
+                writer.segment.get_ref().sync_data().await?;
+                self.synced.send_replace(writer.written);
+                self.archive_in_background(writer.written.segment_id);
+                let (segment, offset) = self.create_segment(segment_id).await?;
+                *writer = SegmentWriter { segment, written: CommitLogPosition { segment_id, offset } };
+            }
+            fault_injection::append(FaultPoint::CommitLogAppend, &self.segment_path(segment_id), writer.written.offset + records.len() as u64)?;
+            writer.segment.write_all(&records).await?;
+            writer.written.offset += records.len() as u64;
+        }
+        writer.segment.flush().await?;
         
+        if fsync && *self.synced.borrow() < writer.written {
+            fault_injection::sync(FaultPoint::CommitLogSync, &self.segment_path(writer.written.segment_id), writer.written.offset)?;
+            writer.segment.get_ref().sync_data().await?;
+            self.sync_count.fetch_add(1, Ordering::Relaxed);
+            self.synced.send_replace(writer.written);
+        }
         Ok(())
     }
     
     /// 복구를 위한 replay 기능
     pub async fn replay_from_segment(&self, segment_id: u64) -> Result<Vec<CommitLogEntry>> {
-        let segment_path = self.base_directory
-            .join(format!("commitlog-{}.log", segment_id));
-        
-        if !segment_path.exists() {
-            return Ok(Vec::new());
+        let segment = read_segment(&self.segment_path(segment_id), segment_id, self.encryption.as_ref()).await?;
+        Ok(segment.entries.into_iter().map(|(_, entry)| entry).collect())
+    }
+    
+    /// 새 세그먼트 열기 (재사용할 파일이 있으면 이름을 바꿔 씀)
+    async fn create_segment(&self, segment_id: u64) -> Result<(BufWriter<File>, u64)> {
+        let path = self.segment_path(segment_id);
+        if let Some(recycled) = self.recycled.lock().await.pop() {
+            tokio::fs::rename(&recycled, &path).await?;
         }
-        
-        let mut file = File::open(segment_path).await?;
-        let mut entries = Vec::new();
-        
-        loop {
-            // 엔트리 크기 읽기
-            let mut size_buf = [0u8; 4];
-            match file.read_exact(&mut size_buf).await {
-                Ok(_) => {
-                    let entry_size = u32::from_le_bytes(size_buf) as usize;
-                    
-                    // 엔트리 데이터 읽기
-                    let mut entry_buf = vec![0u8; entry_size];
-                    file.read_exact(&mut entry_buf).await?;
-                    
-                    // 역직렬화
-                    let entry: CommitLogEntry = bincode::deserialize(&entry_buf)?;
-                    entries.push(entry);
+        let key_id = self.encryption.as_ref().map(|encryption| encryption.key_id());
+        open_segment(&path, segment_id, 0, self.segment_size_limit, false, key_id).await
+    }
+    
+    /// 닫힌 세그먼트를 백그라운드에서 보관
+    fn archive_in_background(&self, segment_id: u64) {
+        let Some(archiver) = self.archiver.clone() else { return };
+        let (path, archived) = (self.segment_path(segment_id), self.archived.clone());
+        tokio::spawn(async move {
+            match archiver.archive(&path).await {
+                Ok(()) => {
+                    archived.lock().unwrap_or_else(|e| e.into_inner()).insert(segment_id);
                 },
-                Err(_) => break, // 파일 끝
+                Err(e) => tracing::error!("Failed to archive commit log segment {}: {}", path.display(), e),
+            }
+        });
+    }
+    
+    /// 더 필요 없는 세그먼트 정리 (세그먼트 크기 그대로인 파일은 재사용 목록이 차지 않았으면 남겨 두고, 아니면 삭제)
+    ///
+    /// 보관 방식이 있으면 보관을 마친 뒤에만 정리 (이전 실행의 세그먼트나 보관에 실패한 세그먼트는 여기서 보관)
+    pub async fn retire_segment(&self, segment_id: u64) -> Result<()> {
+        if segment_id >= self.writer.lock().await.written.segment_id {
+            return Err(CoreDBError::CommitLogError { message: format!("Segment {} is still being written", segment_id) });
+        }
+        let path = self.segment_path(segment_id);
+        let size = match tokio::fs::metadata(&path).await {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        if let Some(archiver) = &self.archiver {
+            let archived = self.archived.lock().unwrap_or_else(|e| e.into_inner()).contains(&segment_id);
+            if !archived {
+                archiver.archive(&path).await?;
             }
         }
         
-        Ok(entries)
+        let mut recycled = self.recycled.lock().await;
+        if size == self.segment_size_limit && recycled.len() < MAX_RECYCLED_SEGMENTS {
+            let target = self.base_directory.join(format!("recycled-{}.log", segment_id));
+            tokio::fs::rename(&path, &target).await?;
+            recycled.push(target);
+        } else {
+            tokio::fs::remove_file(&path).await?;
+        }
+        Ok(())
+    }
+    
+    fn segment_path(&self, segment_id: u64) -> PathBuf {
+        self.base_directory.join(format!("commitlog-{}.log", segment_id))
     }
     
-    /// 모든 세그먼트에서 replay
+    /// 모든 세그먼트 replay
     pub async fn replay_all(&self) -> Result<Vec<CommitLogEntry>> {
+        self.replay_since(CommitLogPosition::default()).await
+    }
+    
+    /// position부터 쓰인 엔트리 replay (그 앞은 이미 SSTable로 플러시된 부분)
+    pub async fn replay_since(&self, position: CommitLogPosition) -> Result<Vec<CommitLogEntry>> {
+        Self::replay_directory(&self.base_directory, position, self.encryption.as_ref()).await
+    }
+    
+    /// 서버를 멈춘 상태에서 디렉토리의 커밋 로그 끝 (다시 열면 이 위치나 그 뒤부터 씀)
+    pub async fn end_position(directory: &Path, encryption: Option<&CommitLogEncryption>) -> Result<CommitLogPosition> {
+        let Some(segment_id) = segment_ids(directory).await?.last().copied() else {
+            return Ok(CommitLogPosition::default());
+        };
+        let last = read_segment(&directory.join(format!("commitlog-{}.log", segment_id)), segment_id, encryption).await?;
+        Ok(CommitLogPosition { segment_id, offset: last.valid_length })
+    }
+    
+    /// 다른 디렉토리(보관된 세그먼트 등)의 세그먼트에서 position부터 쓰인 엔트리 replay (암호화된 세그먼트는 encryption의 키로 읽음)
+    pub async fn replay_directory(directory: &Path, position: CommitLogPosition, encryption: Option<&CommitLogEncryption>) -> Result<Vec<CommitLogEntry>> {
         let mut all_entries = Vec::new();
-        let mut segment_id = 0;
-        
-        loop {
-            let entries = self.replay_from_segment(segment_id).await?;
-            if entries.is_empty() {
-                break;
+        for segment_id in segment_ids(directory).await? {
+            if segment_id < position.segment_id {
+                continue;
+            }
+            for (offset, entry) in read_segment(&directory.join(format!("commitlog-{}.log", segment_id)), segment_id, encryption).await?.entries {
+                if (CommitLogPosition { segment_id, offset }) >= position {
+                    all_entries.push(entry);
+                }
             }
-            all_entries.extend(entries);
-            segment_id += 1;
         }
-        
         Ok(all_entries)
     }
     
-    /// 오래된 세그먼트 정리
-    pub async fn cleanup_old_segments(&self, keep_segments: u64) -> Result<()> {
-        let mut segment_id = 0;
-        
-        loop {
-            let segment_path = self.base_directory
-                .join(format!("commitlog-{}.log", segment_id));
-            
-            if !segment_path.exists() {
-                break;
+    /// from 위치부터 기록되는 변경 구독 (CDC, from이 기본값이면 남아 있는 가장 오래된 세그먼트부터)
+    pub async fn tail(self: &Arc<Self>, from: CommitLogPosition) -> Result<CommitLogTail> {
+        let mut position = from;
+        if from == CommitLogPosition::default() {
+            if let Some(first) = segment_ids(&self.base_directory).await?.first() {
+                position.segment_id = *first;
             }
-            
-            if segment_id < self.segment_id.saturating_sub(keep_segments) {
-                tokio::fs::remove_file(&segment_path).await?;
+        }
+        Ok(CommitLogTail {
+            commit_log: self.clone(),
+            position,
+            header: None,
+            pending: VecDeque::new(),
+            synced: self.subscribe_synced(),
+        })
+    }
+    
+    /// segment_id 앞의 세그먼트를 모두 정리 (정리한 수 반환, 쓰는 중인 세그먼트는 남김)
+    pub async fn retire_segments_before(&self, segment_id: u64) -> Result<usize> {
+        let segment_id = segment_id.min(self.writer.lock().await.written.segment_id);
+        let mut retired = 0;
+        for id in segment_ids(&self.base_directory).await? {
+            if id >= segment_id {
+                break;
             }
-            
-            segment_id += 1;
+            self.retire_segment(id).await?;
+            retired += 1;
         }
-        
-        Ok(())
+        self.oldest_segment.fetch_max(segment_id, Ordering::Relaxed);
+        Ok(retired)
+    }
+    
+    /// 정리되지 않고 남은 가장 오래된 세그먼트 ID
+    pub fn oldest_segment_id(&self) -> u64 {
+        self.oldest_segment.load(Ordering::Relaxed)
+    }
+    
+    /// 남은 세그먼트가 차지하는 크기 (세그먼트는 전체 크기로 미리 할당되므로 세그먼트 수로 추정)
+    pub fn total_size(&self) -> u64 {
+        (self.current_segment_id() + 1).saturating_sub(self.oldest_segment_id()) * self.segment_size_limit
     }
     
     /// 현재 세그먼트 ID
     pub fn current_segment_id(&self) -> u64 {
-        self.segment_id
+        self.position().segment_id
     }
     
     /// 현재 세그먼트 크기
     pub fn current_segment_size(&self) -> u64 {
-        self.current_segment_size
+        self.position().offset
+    }
+}
+
+/// 커밋 로그 변경 하나 (CDC)
+#[derive(Debug)]
+pub struct CommitLogChange {
+    /// 엔트리 시작 위치
+    pub position: CommitLogPosition,
+    /// 다음 엔트리 위치 (이어 받을 때 tail에 넘김)
+    pub next: CommitLogPosition,
+    pub entry: CommitLogEntry,
+}
+
+/// 커밋 로그 변경 구독 (CDC)
+///
+/// fsync된 엔트리를 기록된 순서대로 돌려주고, 끝에 닿으면 다음 fsync를 기다림. 크래시 뒤에 사라질 수 있는
+/// 변경은 내보내지 않는 대신 Periodic 방식이면 fsync 주기만큼 늦게 전달됨. 이미 정리된 세그먼트의
+/// 위치부터는 읽을 수 없으므로 소비하는 쪽은 플러시 속도보다 뒤처지지 않아야 함
+pub struct CommitLogTail {
+    commit_log: Arc<CommitLog>,
+    /// 다음에 읽을 위치
+    position: CommitLogPosition,
+    /// 읽고 있는 세그먼트의 헤더
+    header: Option<SegmentHeader>,
+    /// 읽었지만 아직 돌려주지 않은 변경
+    pending: VecDeque<CommitLogChange>,
+    synced: watch::Receiver<CommitLogPosition>,
+}
+
+impl CommitLogTail {
+    /// 다음 변경 (아직 fsync된 변경이 없으면 기다림, 취소해도 변경을 잃지 않음)
+    pub async fn next(&mut self) -> Result<CommitLogChange> {
+        loop {
+            if let Some(change) = self.pending.pop_front() {
+                return Ok(change);
+            }
+            let synced = *self.synced.borrow_and_update();
+            if self.position >= synced {
+                self.synced.changed().await
+                    .map_err(|_| CoreDBError::CommitLogError { message: "Commit log closed".to_string() })?;
+                continue;
+            }
+            self.read_until(synced).await?;
+        }
+    }
+    
+    /// 다음에 읽을 위치 (돌려준 마지막 변경의 next)
+    pub fn position(&self) -> CommitLogPosition {
+        self.pending.front().map_or(self.position, |change| change.position)
+    }
+    
+    /// 현재 세그먼트에서 synced까지 읽음 (fsync된 위치가 다음 세그먼트로 넘어갔으면 끝까지 읽고 다음 세그먼트로)
+    async fn read_until(&mut self, synced: CommitLogPosition) -> Result<()> {
+        let segment_id = self.position.segment_id;
+        let path = self.commit_log.segment_path(segment_id);
+        let encryption = self.commit_log.encryption.as_ref();
+        let purged = || CoreDBError::CommitLogError {
+            message: format!("Commit log segment {} is no longer available", segment_id),
+        };
+        let mut file = match File::open(&path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Err(purged()),
+            Err(e) => return Err(e.into()),
+        };
+        let header = match &self.header {
+            Some(header) => header.clone(),
+            None => {
+                let mut bytes = Vec::with_capacity(SEGMENT_HEADER_LEN as usize);
+                (&mut file).take(SEGMENT_HEADER_LEN).read_to_end(&mut bytes).await?;
+                let header = parse_header(&bytes, &path, segment_id, encryption)?.ok_or_else(purged)?;
+                self.header = Some(header.clone());
+                header
+            },
+        };
+        
+        let start = self.position.offset.max(header.len);
+        file.seek(SeekFrom::Start(start)).await?;
+        let mut data = Vec::new();
+        let closed = synced.segment_id > segment_id;
+        if closed {
+            file.read_to_end(&mut data).await?;
+        } else {
+            file.take(synced.offset.saturating_sub(start)).read_to_end(&mut data).await?;
+        }
+        let (entries, end, _) = decode_entries(&data, start, &header, &path, segment_id, encryption);
+        let mut next_offsets: Vec<u64> = entries.iter().skip(1).map(|(offset, _)| *offset).collect();
+        next_offsets.push(end);
+        for ((offset, entry), next) in entries.into_iter().zip(next_offsets) {
+            self.pending.push_back(CommitLogChange {
+                position: CommitLogPosition { segment_id, offset },
+                next: CommitLogPosition { segment_id, offset: next },
+                entry,
+            });
+        }
+        
+        // 닫힌 세그먼트는 fsync된 뒤에 다음 세그먼트가 열리므로 끝까지 읽었으면 다음으로
+        self.position = if closed {
+            self.header = None;
+            CommitLogPosition { segment_id: segment_id + 1, offset: 0 }
+        } else {
+            CommitLogPosition { segment_id, offset: end }
+        };
+        Ok(())
+    }
+}
+
+/// 읽은 세그먼트
+#[derive(Default)]
+struct Segment {
+    /// 엔트리와 각 엔트리의 시작 오프셋
+    entries: Vec<(u64, CommitLogEntry)>,
+    /// 마지막 온전한 엔트리의 끝 오프셋
+    valid_length: u64,
+    /// 세그먼트 형식 버전 (헤더가 없는 이전 형식은 0)
+    version: u32,
+    /// 엔트리를 암호화한 키 ID (평문이면 None)
+    key_id: Option<String>,
+    /// 마지막 엔트리 뒤에 잘리거나 손상된 엔트리가 있는지 (미리 할당된 0이나 재사용 전의 엔트리는 아님)
+    torn: bool,
+}
+
+/// 세그먼트 읽기
+///
+/// 쓰던 중에 멈춰 잘렸거나 체크섬이 맞지 않는 첫 엔트리에서 멈추고 그 앞까지만 반환.
+/// 미리 할당된 0 영역이나 재사용하기 전 세그먼트의 엔트리(세그먼트 번호가 다름)를 만나면 거기가 끝.
+/// 암호화된 세그먼트의 키가 encryption에 없으면 오류
+async fn read_segment(segment_path: &Path, segment_id: u64, encryption: Option<&CommitLogEncryption>) -> Result<Segment> {
+    let data = match tokio::fs::read(segment_path).await {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Segment::default()),
+        Err(e) => return Err(e.into()),
+    };
+    let Some(header) = parse_header(&data, segment_path, segment_id, encryption)? else {
+        return Ok(Segment::default());
+    };
+    let (entries, valid_length, torn) = decode_entries(&data[header.len as usize..], header.len, &header, segment_path, segment_id, encryption);
+    Ok(Segment { entries, valid_length, version: header.version, key_id: header.key_id, torn })
+}
+
+/// 세그먼트 헤더 (헤더가 없는 이전 형식은 버전 0, 길이 0)
+#[derive(Debug, Clone)]
+struct SegmentHeader {
+    version: u32,
+    len: u64,
+    /// 엔트리를 암호화한 키 ID (평문이면 None)
+    key_id: Option<String>,
+}
+
+/// 세그먼트 앞부분에서 헤더 해석 (헤더를 쓰다 멈췄거나, 재사용하려고 이름만 바꾸고 헤더는 아직 쓰지 못했으면 None)
+fn parse_header(data: &[u8], segment_path: &Path, segment_id: u64, encryption: Option<&CommitLogEncryption>) -> Result<Option<SegmentHeader>> {
+    let u32_at = |start: usize| data.get(start..start + 4).map(|bytes| u32::from_be_bytes(bytes.try_into().expect("4 bytes")));
+    let u64_at = |start: usize| data.get(start..start + 8).map(|bytes| u64::from_be_bytes(bytes.try_into().expect("8 bytes")));
+    if !data.starts_with(SEGMENT_MAGIC) {
+        return Ok(Some(SegmentHeader { version: 0, len: 0, key_id: None }));
+    }
+    
+    let version = u32_at(4).unwrap_or(0);
+    if version > SEGMENT_VERSION {
+        return Err(CoreDBError::CommitLogError {
+            message: format!("{} uses segment format version {}, newer than {}", segment_path.display(), version, SEGMENT_VERSION),
+        });
+    }
+    let len = match version {
+        4.. => SEGMENT_HEADER_LEN,
+        3 => V3_SEGMENT_HEADER_LEN,
+        _ => 8,
+    };
+    if version < 2 || data.len() < len as usize || (version >= 3 && u64_at(8) != Some(segment_id)) {
+        return Ok(None);
+    }
+    let key_id = match u32_at(16).filter(|_| version >= 4) {
+        Some(key_id_len) if key_id_len > 0 => {
+            let key_id = data.get(20..20 + key_id_len as usize)
+                .filter(|_| key_id_len as usize <= MAX_KEY_ID_LEN)
+                .map(|key_id| String::from_utf8_lossy(key_id).into_owned())
+                .ok_or_else(|| CoreDBError::Corruption {
+                    file: segment_path.display().to_string(),
+                    offset: 16,
+                    message: format!("Invalid key id length {}", key_id_len),
+                })?;
+            if !encryption.is_some_and(|encryption| encryption.keys.contains_key(&key_id)) {
+                return Err(CoreDBError::CommitLogError {
+                    message: format!("{} is encrypted with key {}, which is not configured", segment_path.display(), key_id),
+                });
+            }
+            Some(key_id)
+        },
+        _ => None,
+    };
+    Ok(Some(SegmentHeader { version, len, key_id }))
+}
+
+/// 세그먼트의 base 오프셋부터 읽은 data에서 엔트리 해석
+///
+/// 엔트리와 해석한 끝 오프셋, 잘리거나 손상된 엔트리에서 멈췄는지를 반환
+fn decode_entries(data: &[u8], base: u64, header: &SegmentHeader, segment_path: &Path, segment_id: u64, encryption: Option<&CommitLogEncryption>) -> (Vec<(u64, CommitLogEntry)>, u64, bool) {
+    let version = header.version;
+    let end = base + data.len() as u64;
+    let mut entries = Vec::new();
+    let mut offset = base;
+    let mut torn = false;
+    while offset < end {
+        // 엔트리 크기 + (세그먼트 번호 +) 데이터 (+ 체크섬)
+        let start = (offset - base) as usize;
+        let size = data.get(start..start + 4).map(|bytes| u32::from_be_bytes(bytes.try_into().expect("4 bytes")));
+        if size == Some(0) && version >= 3 {
+            // 미리 할당된 영역
+            break;
+        }
+        let record = size.and_then(|size| data.get(start + 4..start + 4 + size as usize));
+        let mut body = match (record, version) {
+            (Some(record), 0) => Some(record),
+            (Some(record), _) => strip_checksum(record, segment_path, offset + 4).ok(),
+            (None, _) => None,
+        };
+        if version >= 3 {
+            match body.and_then(|body| body.split_first_chunk::<8>()) {
+                Some((id, _)) if u64::from_be_bytes(*id) != segment_id => {
+                    tracing::debug!("Commit log segment {} ends at {} before entries left from its previous use", segment_path.display(), offset);
+                    break;
+                },
+                split => body = split.map(|(_, body)| body),
+            }
+        }
+        let plaintext = match (&header.key_id, encryption, body) {
+            (Some(key_id), Some(encryption), Some(body)) => encryption.decrypt(key_id, segment_id, body),
+            (_, _, body) => body.map(<[u8]>::to_vec),
+        };
+        let (Some(record), Some(entry)) = (record, plaintext.and_then(|body| bincode::deserialize::<CommitLogEntry>(&body).ok())) else {
+            tracing::warn!("Stopping at torn or corrupt commit log entry at {}:{}", segment_path.display(), offset);
+            torn = true;
+            break;
+        };
+        entries.push((offset, entry));
+        offset += record.len() as u64 + 4;
+    }
+    (entries, offset.min(end), torn)
+}
+
+/// 세그먼트를 열어 length부터 이어 쓸 준비 (length가 0이면 헤더를 쓰고, fsync한 뒤 이어 쓸 위치 반환)
+///
+/// 파일은 size까지 미리 할당하고, zero_tail이면 length 뒤에 남은 잘리거나 손상된 엔트리를 0으로 덮음.
+/// 새 헤더에는 엔트리를 암호화할 키 ID를 남김
+async fn open_segment(segment_path: &Path, segment_id: u64, length: u64, size: u64, zero_tail: bool, key_id: Option<&str>) -> Result<(BufWriter<File>, u64)> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(segment_path)
+        .await?;
+    let file_len = file.metadata().await?.len();
+    if zero_tail && file_len > length {
+        file.seek(SeekFrom::Start(length)).await?;
+        let zeros = vec![0u8; 64 * 1024];
+        let mut remaining = file_len - length;
+        while remaining > 0 {
+            let chunk = remaining.min(zeros.len() as u64);
+            file.write_all(&zeros[..chunk as usize]).await?;
+            remaining -= chunk;
+        }
+    }
+    if file_len < size {
+        preallocate(&file, size).await?;
+    }
+    
+    let mut length = length;
+    if length == 0 {
+        file.seek(SeekFrom::Start(0)).await?;
+        let mut header = Vec::with_capacity(SEGMENT_HEADER_LEN as usize);
+        header.extend_from_slice(SEGMENT_MAGIC);
+        header.extend_from_slice(&SEGMENT_VERSION.to_be_bytes());
+        header.extend_from_slice(&segment_id.to_be_bytes());
+        let key_id = key_id.unwrap_or_default().as_bytes();
+        header.extend_from_slice(&(key_id.len() as u32).to_be_bytes());
+        header.extend_from_slice(key_id);
+        header.resize(SEGMENT_HEADER_LEN as usize, 0);
+        file.write_all(&header).await?;
+        length = SEGMENT_HEADER_LEN;
+    }
+    file.seek(SeekFrom::Start(length)).await?;
+    file.sync_data().await?;
+    Ok((BufWriter::new(file), length))
+}
+
+/// 세그먼트 파일을 size까지 미리 할당 (추가 쓰기마다 파일 크기와 블록 할당이 바뀌지 않도록)
+async fn preallocate(file: &File, size: u64) -> Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        let file = file.try_clone().await?.into_std().await;
+        let allocated = tokio::task::spawn_blocking(move || {
+            use std::os::unix::io::AsRawFd;
+            // SAFETY: fd는 file이 살아 있는 동안 유효
+            unsafe { libc::fallocate(file.as_raw_fd(), 0, 0, size as libc::off_t) == 0 }
+        }).await.map_err(|e| CoreDBError::CommitLogError { message: format!("Preallocation task failed: {}", e) })?;
+        if allocated {
+            return Ok(());
+        }
+    }
+    // fallocate를 지원하지 않는 파일 시스템은 길이만 늘림 (0으로 읽힘)
+    file.set_len(size).await?;
+    Ok(())
+}
+
+/// 디렉토리에 있는 세그먼트 번호 (오름차순)
+async fn segment_ids(directory: &Path) -> Result<Vec<u64>> {
+    let mut entries = tokio::fs::read_dir(directory).await?;
+    let mut ids = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        if let Some(id) = segment_id_of(&entry.path()) {
+            ids.push(id);
+        }
+    }
+    ids.sort();
+    Ok(ids)
+}
+
+/// 세그먼트 파일 이름(commitlog-<번호>.log)의 번호
+fn segment_id_of(path: &Path) -> Option<u64> {
+    path.file_name()?.to_str()?
+        .strip_prefix("commitlog-")?
+        .strip_suffix(".log")?
+        .parse().ok()
+}
+
+/// 커밋 로그 엔트리를 기록된 순서대로 한 줄에 하나씩 JSON으로 출력 (`coredb wal dump`, 출력한 엔트리 수 반환)
+///
+/// path가 디렉토리면 그 안의 세그먼트를 번호 순으로 읽음. 잘리거나 손상된 엔트리를 만나면 그 세그먼트는 거기까지만 출력.
+/// 암호화된 세그먼트는 encryption의 키로 읽음
+pub async fn dump_commit_log(path: &Path, encryption: Option<&CommitLogEncryption>, out: &mut impl std::io::Write) -> Result<usize> {
+    let segments = if tokio::fs::metadata(path).await?.is_dir() {
+        segment_ids(path).await?.into_iter()
+            .map(|segment_id| (segment_id, path.join(format!("commitlog-{}.log", segment_id))))
+            .collect()
+    } else {
+        let segment_id = segment_id_of(path).ok_or_else(|| CoreDBError::CommitLogError {
+            message: format!("{} is not a commit log segment (commitlog-<id>.log)", path.display()),
+        })?;
+        vec![(segment_id, path.to_path_buf())]
+    };
+    
+    let mut count = 0;
+    for (segment_id, segment_path) in segments {
+        for (offset, entry) in read_segment(&segment_path, segment_id, encryption).await?.entries {
+            serde_json::to_writer(&mut *out, &entry_json(CommitLogPosition { segment_id, offset }, &entry)?)?;
+            out.write_all(b"\n")?;
+            count += 1;
+        }
+    }
+    out.flush()?;
+    Ok(count)
+}
+
+/// 엔트리 하나의 JSON (위치, 기록 시각, 테이블, 뮤테이션 종류와 키 또는 스키마 변경 문)
+fn entry_json(position: CommitLogPosition, entry: &CommitLogEntry) -> Result<serde_json::Value> {
+    let mut object = serde_json::json!({
+        "segment": position.segment_id,
+        "offset": position.offset,
+        "timestamp": entry.timestamp,
+        "time": chrono::DateTime::from_timestamp_micros(entry.timestamp).map(|time| time.to_rfc3339()),
+        "keyspace": entry.keyspace,
+        "table": entry.table,
+    });
+    let (mutation, partition_key, clustering_key) = match &entry.mutation {
+        Mutation::Insert(row) => ("insert", &row.partition_key, row.clustering_key.as_ref()),
+        Mutation::Delete { partition_key, clustering_key } => ("delete", partition_key, clustering_key.as_ref()),
+        Mutation::PartitionDelete { partition_key } => ("partition_delete", partition_key, None),
+        Mutation::Schema { statement } => {
+            object["mutation"] = "schema".into();
+            object["statement"] = statement.as_str().into();
+            return Ok(object);
+        },
+    };
+    object["mutation"] = mutation.into();
+    object["partition_key"] = serde_json::to_value(&partition_key.components)?;
+    object["clustering_key"] = clustering_key.map(|key| serde_json::to_value(&key.components)).transpose()?.into();
+    Ok(object)
+}
+
+/// 변경 하나의 JSON (CDC, 덤프 형식에 다음 위치와 INSERT의 셀을 더함)
+pub fn change_json(change: &CommitLogChange) -> Result<serde_json::Value> {
+    let mut object = entry_json(change.position, &change.entry)?;
+    object["next_segment"] = change.next.segment_id.into();
+    object["next_offset"] = change.next.offset.into();
+    if let Mutation::Insert(row) = &change.entry.mutation {
+        object["cells"] = serde_json::to_value(&row.cells)?;
     }
+    Ok(object)
+}
+
+/// 재사용하려고 남겨 둔 세그먼트 파일
+async fn recycled_segments(directory: &Path) -> Result<Vec<PathBuf>> {
+    let mut entries = tokio::fs::read_dir(directory).await?;
+    let mut paths = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name();
+        if name.to_str().is_some_and(|name| name.starts_with("recycled-") && name.ends_with(".log")) {
+            paths.push(entry.path());
+        }
+    }
+    Ok(paths)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::schema::{CassandraValue, Cell};
-    use std::collections::HashMap;
+    use crate::schema::{CassandraValue, Cell, Cells};
     
     fn create_test_row() -> Row {
         Row {
@@ -193,8 +1095,8 @@ mod tests {
                 components: vec![CassandraValue::BigInt(1000)],
             }),
             cells: {
-                let mut cells = HashMap::new();
-                cells.insert("value".to_string(), Cell {
+                let mut cells = Cells::new();
+                cells.insert("value", Cell {
                     value: CassandraValue::Text("test".to_string()),
                     timestamp: chrono::Utc::now().timestamp_micros(),
                     ttl: None,
@@ -211,7 +1113,7 @@ mod tests {
         let temp_dir = std::env::temp_dir().join("coredb_wal_test");
         tokio::fs::create_dir_all(&temp_dir).await.unwrap();
         
-        let mut commit_log = CommitLog::new(temp_dir.clone()).await.unwrap();
+        let commit_log = CommitLog::new(temp_dir.clone()).await.unwrap();
         
         let entry = CommitLogEntry {
             keyspace: "test_keyspace".to_string(),
@@ -222,8 +1124,8 @@ mod tests {
         
         commit_log.append(entry).await.unwrap();
         
-        // 세그먼트 강제 플러시
-        commit_log.current_segment.flush().await.unwrap();
+        // 세그먼트 강제 fsync
+        commit_log.sync().await.unwrap();
         
         let entries = commit_log.replay_from_segment(0).await.unwrap();
         assert_eq!(entries.len(), 1);
@@ -234,6 +1136,405 @@ mod tests {
         tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
     }
     
+    #[tokio::test]
+    async fn test_commit_log_replays_since_position_and_skips_torn_tail() {
+        let temp_dir = std::env::temp_dir().join("coredb_wal_replay_since_test");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        
+        let commit_log = CommitLog::new(temp_dir.clone()).await.unwrap();
+        let mut positions = Vec::new();
+        for table in ["a", "b", "c"] {
+            positions.push(commit_log.position());
+            commit_log.append(CommitLogEntry {
+                keyspace: "test_keyspace".to_string(),
+                table: table.to_string(),
+                mutation: Mutation::Insert(create_test_row()),
+                timestamp: chrono::Utc::now().timestamp_micros(),
+            }).await.unwrap();
+        }
+        let end = commit_log.position();
+        drop(commit_log);
+        
+        // 다시 열면 마지막 세그먼트 끝에서 이어 씀
+        let commit_log = CommitLog::new(temp_dir.clone()).await.unwrap();
+        assert_eq!(commit_log.position(), end);
+        let tables = |entries: Vec<CommitLogEntry>| entries.into_iter().map(|entry| entry.table).collect::<Vec<_>>();
+        assert_eq!(tables(commit_log.replay_since(positions[1]).await.unwrap()), vec!["b", "c"]);
+        
+        // 쓰다 잘린 마지막 엔트리는 버림
+        let segment = temp_dir.join("commitlog-0.log");
+        let data = tokio::fs::read(&segment).await.unwrap();
+        tokio::fs::write(&segment, &data[..end.offset as usize - 3]).await.unwrap();
+        assert_eq!(tables(commit_log.replay_all().await.unwrap()), vec!["a", "b"]);
+        drop(commit_log);
+        
+        // 다시 열면 잘린 부분을 지우고 이어 쓰므로 새 엔트리도 재생됨
+        let commit_log = CommitLog::new(temp_dir.clone()).await.unwrap();
+        assert_eq!(commit_log.position(), positions[2]);
+        commit_log.append(CommitLogEntry {
+            keyspace: "test_keyspace".to_string(),
+            table: "d".to_string(),
+            mutation: Mutation::Insert(create_test_row()),
+            timestamp: chrono::Utc::now().timestamp_micros(),
+        }).await.unwrap();
+        assert_eq!(tables(commit_log.replay_all().await.unwrap()), vec!["a", "b", "d"]);
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_commit_log_stops_at_corrupt_entry_and_reads_legacy_segments() {
+        let temp_dir = std::env::temp_dir().join("coredb_wal_checksum_test");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        let entry = |table: &str| CommitLogEntry {
+            keyspace: "test_keyspace".to_string(),
+            table: table.to_string(),
+            mutation: Mutation::Insert(create_test_row()),
+            timestamp: chrono::Utc::now().timestamp_micros(),
+        };
+        let tables = |entries: Vec<CommitLogEntry>| entries.into_iter().map(|entry| entry.table).collect::<Vec<_>>();
+        
+        // 체크섬 없이 엔트리 크기 + 데이터만 쓴 이전 형식 세그먼트
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        let mut legacy = Vec::new();
+        for table in ["old1", "old2"] {
+            let serialized = bincode::serialize(&entry(table)).unwrap();
+            legacy.extend_from_slice(&(serialized.len() as u32).to_be_bytes());
+            legacy.extend_from_slice(&serialized);
+        }
+        tokio::fs::write(temp_dir.join("commitlog-0.log"), &legacy).await.unwrap();
+        
+        // 이전 형식 세그먼트에는 이어 쓰지 않고 새 세그먼트에 씀
+        let commit_log = CommitLog::new(temp_dir.clone()).await.unwrap();
+        assert_eq!(commit_log.position(), CommitLogPosition { segment_id: 1, offset: SEGMENT_HEADER_LEN });
+        let mut positions = Vec::new();
+        for table in ["a", "b", "c"] {
+            positions.push(commit_log.position());
+            commit_log.append(entry(table)).await.unwrap();
+        }
+        assert_eq!(tables(commit_log.replay_all().await.unwrap()), vec!["old1", "old2", "a", "b", "c"]);
+        
+        // 중간 엔트리의 한 바이트가 바뀌면 그 앞까지만 재생
+        let segment = temp_dir.join("commitlog-1.log");
+        let mut data = tokio::fs::read(&segment).await.unwrap();
+        data[positions[1].offset as usize + 10] ^= 0xff;
+        tokio::fs::write(&segment, &data).await.unwrap();
+        assert_eq!(tables(commit_log.replay_all().await.unwrap()), vec!["old1", "old2", "a"]);
+        drop(commit_log);
+        
+        // 다시 열면 손상된 엔트리부터 잘라내고 이어 씀
+        let commit_log = CommitLog::new(temp_dir.clone()).await.unwrap();
+        assert_eq!(commit_log.position(), positions[1]);
+        commit_log.append(entry("d")).await.unwrap();
+        assert_eq!(tables(commit_log.replay_all().await.unwrap()), vec!["old1", "old2", "a", "d"]);
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_concurrent_appends_share_fsyncs() {
+        let temp_dir = std::env::temp_dir().join("coredb_wal_group_commit_test");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        
+        let mut commit_log = CommitLog::new(temp_dir.clone()).await.unwrap().with_sync(CommitLogSync::Always);
+        commit_log.segment_size_limit = 4096;
+        let commit_log = std::sync::Arc::new(commit_log);
+        let appends: Vec<_> = (0..50).map(|i| {
+            let commit_log = commit_log.clone();
+            tokio::spawn(async move {
+                commit_log.append(CommitLogEntry {
+                    keyspace: "test_keyspace".to_string(),
+                    table: format!("t{}", i),
+                    mutation: Mutation::Insert(create_test_row()),
+                    timestamp: chrono::Utc::now().timestamp_micros(),
+                }).await.unwrap()
+            })
+        }).collect();
+        
+        // 모든 쓰기는 fsync된 뒤에 돌아오지만 fsync는 몇 번으로 묶임
+        for append in appends {
+            let end = append.await.unwrap();
+            assert!(*commit_log.subscribe_synced().borrow() >= end);
+        }
+        assert!(commit_log.sync_count() < 10, "{} fsyncs", commit_log.sync_count());
+        assert!(commit_log.current_segment_id() > 0);
+        
+        // 여러 세그먼트에 걸쳐 모든 엔트리가 한 번씩 재생됨
+        let mut tables: Vec<_> = commit_log.replay_all().await.unwrap().into_iter().map(|entry| entry.table).collect();
+        assert_eq!(tables.len(), 50);
+        tables.sort();
+        tables.dedup();
+        assert_eq!(tables.len(), 50);
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_group_sync_waits_for_latency_target() {
+        let temp_dir = std::env::temp_dir().join("coredb_wal_group_sync_test");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        
+        let max_latency = Duration::from_millis(20);
+        let commit_log = std::sync::Arc::new(CommitLog::new(temp_dir.clone()).await.unwrap().with_sync(CommitLogSync::Group { max_latency }));
+        assert_eq!(commit_log.sync_mode().sync_interval(), None);
+        let started = Instant::now();
+        let appends: Vec<_> = (0..20).map(|i| {
+            let commit_log = commit_log.clone();
+            tokio::spawn(async move {
+                commit_log.append(CommitLogEntry {
+                    keyspace: "test_keyspace".to_string(),
+                    table: format!("t{}", i),
+                    mutation: Mutation::Insert(create_test_row()),
+                    timestamp: chrono::Utc::now().timestamp_micros(),
+                }).await.unwrap()
+            })
+        }).collect();
+        
+        // 모든 쓰기는 그룹의 fsync가 끝난 뒤 돌아오고, 그룹은 지연 목표까지 쓰기를 모음
+        for append in appends {
+            let end = append.await.unwrap();
+            assert!(*commit_log.subscribe_synced().borrow() >= end);
+        }
+        assert!(started.elapsed() >= max_latency);
+        let stats = commit_log.stats();
+        assert!(stats.syncs < 5, "{} fsyncs", stats.syncs);
+        assert_eq!(stats.commit_latency.count(), 20);
+        assert!(stats.commit_latency.percentile(0.99) >= 15_000);
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_segments_are_preallocated_and_recycled() {
+        let temp_dir = std::env::temp_dir().join("coredb_wal_recycle_test");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        let entry = |table: String| CommitLogEntry {
+            keyspace: "test_keyspace".to_string(),
+            table,
+            mutation: Mutation::Insert(create_test_row()),
+            timestamp: chrono::Utc::now().timestamp_micros(),
+        };
+        let file_len = |segment_id: u64| {
+            let path = temp_dir.join(format!("commitlog-{}.log", segment_id));
+            async move { tokio::fs::metadata(path).await.unwrap().len() }
+        };
+        
+        let mut commit_log = CommitLog::new(temp_dir.clone()).await.unwrap();
+        assert_eq!(file_len(0).await, DEFAULT_SEGMENT_SIZE);
+        commit_log.segment_size_limit = 1024;
+        let mut i = 0;
+        while commit_log.current_segment_id() < 3 {
+            commit_log.append(entry(format!("t{}", i))).await.unwrap();
+            i += 1;
+        }
+        commit_log.sync().await.unwrap();
+        assert_eq!(file_len(1).await, 1024);
+        
+        // 세그먼트 크기 그대로인 세그먼트는 재사용 목록으로, 아니면 삭제
+        let live = commit_log.replay_all().await.unwrap().len();
+        let retired = commit_log.replay_from_segment(0).await.unwrap().len() + commit_log.replay_from_segment(1).await.unwrap().len();
+        commit_log.retire_segment(0).await.unwrap();
+        commit_log.retire_segment(1).await.unwrap();
+        assert!(commit_log.retire_segment(3).await.is_err());
+        assert!(!temp_dir.join("commitlog-0.log").exists());
+        assert!(temp_dir.join("recycled-1.log").exists());
+        
+        // 다음 세그먼트는 재사용한 파일에 쓰이고, 재사용 전의 엔트리는 재생되지 않음
+        let mut appended = 0;
+        while commit_log.current_segment_id() < 4 {
+            commit_log.append(entry("last".to_string())).await.unwrap();
+            appended += 1;
+        }
+        commit_log.sync().await.unwrap();
+        assert!(!temp_dir.join("recycled-1.log").exists());
+        assert_eq!(file_len(4).await, 1024);
+        let entries = commit_log.replay_from_segment(4).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].table, "last");
+        assert_eq!(commit_log.replay_all().await.unwrap().len(), live - retired + appended);
+        let end = commit_log.position();
+        drop(commit_log);
+        
+        // 다시 열면 마지막 세그먼트의 미리 할당된 영역 앞에서 이어 씀
+        let commit_log = CommitLog::new(temp_dir.clone()).await.unwrap();
+        assert_eq!(commit_log.position(), end);
+        assert_eq!(commit_log.replay_all().await.unwrap().len(), live - retired + appended);
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_closed_segments_are_archived_before_retiring() {
+        let temp_dir = std::env::temp_dir().join("coredb_wal_archive_test");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        let archive_dir = temp_dir.join("archive");
+        
+        let mut commit_log = CommitLog::new(temp_dir.join("commitlog")).await.unwrap()
+            .with_archiver(Arc::new(CopyArchiver { directory: archive_dir.clone() }));
+        commit_log.segment_size_limit = 1024;
+        let mut i = 0;
+        while commit_log.current_segment_id() < 2 {
+            commit_log.append(CommitLogEntry {
+                keyspace: "test_keyspace".to_string(),
+                table: format!("t{}", i),
+                mutation: Mutation::Insert(create_test_row()),
+                timestamp: i,
+            }).await.unwrap();
+            i += 1;
+        }
+        commit_log.sync().await.unwrap();
+        
+        // 닫힌 세그먼트 0은 백그라운드에서 보관되고, 1은 정리할 때 보관됨
+        for _ in 0..100 {
+            if archive_dir.join("commitlog-0.log").exists() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        commit_log.retire_segment(1).await.unwrap();
+        assert!(archive_dir.join("commitlog-1.log").exists());
+        assert!(!archive_dir.join("commitlog-2.log").exists());
+        
+        // 보관된 세그먼트만으로 닫힌 세그먼트의 엔트리를 모두 재생
+        let archived = CommitLog::replay_directory(&archive_dir, CommitLogPosition::default(), None).await.unwrap();
+        let live = commit_log.replay_from_segment(2).await.unwrap();
+        assert_eq!(archived.len() + live.len(), i as usize);
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_encrypted_segments_need_their_key() {
+        let temp_dir = std::env::temp_dir().join("coredb_wal_encryption_test");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        let entry = |table: &str| CommitLogEntry {
+            keyspace: "secret_keyspace".to_string(),
+            table: table.to_string(),
+            mutation: Mutation::Insert(create_test_row()),
+            timestamp: 1,
+        };
+        
+        // 평문 세그먼트 뒤에 키를 지정하면 새 세그먼트부터 암호화
+        let plain = CommitLog::open(temp_dir.clone(), 4096).await.unwrap();
+        plain.append(entry("plain")).await.unwrap();
+        plain.sync().await.unwrap();
+        drop(plain);
+        tokio::fs::write(temp_dir.join("key-2024.key"), "ab".repeat(32)).await.unwrap();
+        let old_key = CommitLogEncryption::from_key_file(&temp_dir.join("key-2024.key")).await.unwrap();
+        assert_eq!(old_key.key_id(), "key-2024");
+        let commit_log = CommitLog::open_with_encryption(temp_dir.clone(), 4096, Some(old_key.clone())).await.unwrap();
+        assert_eq!(commit_log.current_segment_id(), 1);
+        commit_log.append(entry("encrypted")).await.unwrap();
+        commit_log.sync().await.unwrap();
+        drop(commit_log);
+        
+        let segment = tokio::fs::read(temp_dir.join("commitlog-1.log")).await.unwrap();
+        assert_eq!(&segment[20..28], b"key-2024");
+        assert!(!segment.windows(15).any(|window| window == b"secret_keyspace"));
+        
+        // 키가 없거나 다르면 읽지 않고, 키를 바꾼 뒤에도 이전 키로 쓴 세그먼트를 읽음
+        assert!(CommitLog::open(temp_dir.clone(), 4096).await.is_err());
+        let other_key = CommitLogEncryption::new("key-2025", &[7; 32]).unwrap();
+        assert!(dump_commit_log(&temp_dir, Some(&other_key), &mut Vec::new()).await.is_err());
+        assert!(CommitLogEncryption::new("key-2025", &[7; 16]).is_err());
+        let rotated = CommitLog::open_with_encryption(temp_dir.clone(), 4096, Some(other_key.with_previous(old_key))).await.unwrap();
+        assert_eq!(rotated.current_segment_id(), 2);
+        rotated.append(entry("rotated")).await.unwrap();
+        rotated.sync().await.unwrap();
+        let tables: Vec<_> = rotated.replay_all().await.unwrap().into_iter().map(|entry| entry.table).collect();
+        assert_eq!(tables, vec!["plain", "encrypted", "rotated"]);
+        assert_eq!(rotated.encryption().unwrap().key_id(), "key-2025");
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_dump_prints_entries_in_order() {
+        let temp_dir = std::env::temp_dir().join("coredb_wal_dump_test");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        
+        let commit_log = CommitLog::new(temp_dir.clone()).await.unwrap();
+        commit_log.append(CommitLogEntry {
+            keyspace: "test_keyspace".to_string(),
+            table: "a".to_string(),
+            mutation: Mutation::Insert(create_test_row()),
+            timestamp: 1_700_000_000_000_000,
+        }).await.unwrap();
+        commit_log.append(CommitLogEntry {
+            keyspace: "test_keyspace".to_string(),
+            table: "b".to_string(),
+            mutation: Mutation::PartitionDelete { partition_key: PartitionKey { components: vec![CassandraValue::Int(2)] } },
+            timestamp: 1_700_000_000_000_001,
+        }).await.unwrap();
+        commit_log.sync().await.unwrap();
+        
+        let mut out = Vec::new();
+        assert_eq!(dump_commit_log(&temp_dir, None, &mut out).await.unwrap(), 2);
+        let lines: Vec<serde_json::Value> = out.split(|byte| *byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect();
+        assert_eq!(lines[0], serde_json::json!({
+            "segment": 0, "offset": SEGMENT_HEADER_LEN, "timestamp": 1_700_000_000_000_000i64, "time": "2023-11-14T22:13:20+00:00",
+            "keyspace": "test_keyspace", "table": "a", "mutation": "insert",
+            "partition_key": [{ "Int": 1 }], "clustering_key": [{ "BigInt": 1000 }],
+        }));
+        assert_eq!(lines[1]["mutation"], "partition_delete");
+        assert_eq!(lines[1]["partition_key"], serde_json::json!([{ "Int": 2 }]));
+        
+        // 세그먼트 파일 하나만 지정해도 됨
+        let mut out = Vec::new();
+        assert_eq!(dump_commit_log(&temp_dir.join("commitlog-0.log"), None, &mut out).await.unwrap(), 2);
+        assert!(dump_commit_log(&temp_dir.join("other.log"), None, &mut Vec::new()).await.is_err());
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_tail_streams_synced_changes_across_segments() {
+        let temp_dir = std::env::temp_dir().join("coredb_wal_tail_test");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        let entry = |table: String| CommitLogEntry {
+            keyspace: "test_keyspace".to_string(),
+            table,
+            mutation: Mutation::Insert(create_test_row()),
+            timestamp: 1,
+        };
+        
+        // 세그먼트 여러 개에 걸쳐 쓴 변경이 순서대로 나옴
+        let commit_log = Arc::new(CommitLog::open(temp_dir.clone(), 1024).await.unwrap());
+        for i in 0..10 {
+            commit_log.append(entry(format!("t{}", i))).await.unwrap();
+        }
+        commit_log.sync().await.unwrap();
+        assert!(commit_log.current_segment_id() > 0);
+        let mut tail = commit_log.tail(CommitLogPosition::default()).await.unwrap();
+        let mut changes = Vec::new();
+        for _ in 0..10 {
+            changes.push(tail.next().await.unwrap());
+        }
+        let tables: Vec<_> = changes.iter().map(|change| change.entry.table.clone()).collect();
+        assert_eq!(tables, (0..10).map(|i| format!("t{}", i)).collect::<Vec<_>>());
+        assert!(changes.windows(2).all(|pair| pair[0].next <= pair[1].position && pair[0].position < pair[1].position));
+        assert_eq!(tail.position(), changes[9].next);
+        let json = change_json(&changes[0]).unwrap();
+        assert_eq!(json["table"], "t0");
+        assert_eq!(json["next_offset"], changes[0].next.offset);
+        assert!(json["cells"].is_object());
+        
+        // fsync되기 전의 변경은 나오지 않고, fsync되면 나옴
+        commit_log.append(entry("late".to_string())).await.unwrap();
+        assert!(tokio::time::timeout(Duration::from_millis(50), tail.next()).await.is_err());
+        commit_log.sync().await.unwrap();
+        assert_eq!(tail.next().await.unwrap().entry.table, "late");
+        
+        // 받은 변경의 next부터 이어 받기
+        let mut resumed = commit_log.tail(changes[4].next).await.unwrap();
+        assert_eq!(resumed.next().await.unwrap().entry.table, "t5");
+        
+        tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
+    }
+    
     #[tokio::test]
     async fn test_commit_log_segment_rotation() {
         let temp_dir = std::env::temp_dir().join("coredb_wal_rotation_test");
@@ -245,7 +1546,7 @@ mod tests {
         commit_log.segment_size_limit = 1024; // 1KB
         
         // 여러 엔트리 추가하여 세그먼트 로테이션 트리거
-        for i in 0..10 {
+        for _ in 0..10 {
             let entry = CommitLogEntry {
                 keyspace: "test_keyspace".to_string(),
                 table: "test_table".to_string(),
@@ -256,7 +1557,7 @@ mod tests {
         }
         
         // 세그먼트가 로테이션되었는지 확인
-        assert!(commit_log.segment_id > 0);
+        assert!(commit_log.current_segment_id() > 0);
         
         // 정리
         tokio::fs::remove_dir_all(&temp_dir).await.unwrap();
@@ -8,10 +8,35 @@ async fn test_database_lifecycle() {
     let config = DatabaseConfig {
         data_directory: test_dir.clone(),
         commitlog_directory: test_dir.join("commitlog"),
+        commitlog_sync: CommitLogSync::default(),
+        commitlog_segment_size_mb: 32,
+        commitlog_total_space_mb: 8192,
+        commitlog_keyspace_directories: std::collections::HashMap::new(),
+        commitlog_archiver: None,
+        commitlog_restore: None,
+        commitlog_encryption: None,
         memtable_flush_threshold_mb: 64,
+        memtable_max_age_secs: 0,
         compaction_throughput_mb_per_sec: 16,
+        concurrent_compactors: 2,
         concurrent_reads: 32,
         concurrent_writes: 32,
+        max_queued_requests: 1024,
+        max_pending_flushes: 4,
+        max_sstables_per_table: 256,
+        large_partition_warning_threshold_mb: 100,
+        ephemeral: false,
+        disk_access_mode: DiskAccessMode::Standard,
+        compaction_write_mode: DiskWriteMode::Buffered,
+        key_cache_entries: 100_000,
+        chunk_cache_size_mb: 128,
+        slow_query_threshold_ms: 500,
+        disk_usage_warn_percent: 90,
+        disk_usage_fail_percent: 95,
+        integrity_check: IntegrityCheckMode::Off,
+        keyspace_quotas: Default::default(),
+        snapshot_schedules: Default::default(),
+        backup_url: Default::default(),
     };
     
     // 1. 데이터베이스 생성